@@ -85,7 +85,10 @@ fn main() {
     } else {
         log::LevelFilter::Info
     };
-    let mut logger = leaf::common::log::setup_logger(loglevel);
+    let mut logger = leaf::common::log::setup_logger(
+        loglevel,
+        leaf::common::log::LogFormat::new(config.log.as_ref()),
+    );
     let console_output = fern::Output::stdout("\n");
     logger = logger.chain(console_output);
     if let Some(log) = config.log.as_ref() {
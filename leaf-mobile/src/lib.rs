@@ -1,50 +1,100 @@
-use std::{ffi::CStr, os::raw::c_char};
+use std::{ffi::CStr, os::raw::c_char, time::Duration};
 
 use bytes::BytesMut;
 use log::*;
 
+use leaf::app::outbound::manager::AddOutboundError;
 use leaf::config;
 
 pub mod ios;
 
 mod logger;
-use logger::ConsoleWriter;
+use logger::{CallbackLogger, ConsoleWriter, LogCallback};
+
+mod protector;
+use protector::SocketProtector;
 
 // this function is available on iOS 13.0+
 // use ios::os_proc_available_memory;
 
+pub const ERR_OK: i32 = 0;
+pub const ERR_CONFIG_PATH: i32 = 1;
+pub const ERR_CONFIG: i32 = 2;
+pub const ERR_RUNTIME: i32 = 3;
+pub const ERR_INVALID_INPUT: i32 = 4;
+pub const ERR_IMPORT: i32 = 5;
+pub const ERR_BUFFER_TOO_SMALL: i32 = 6;
+pub const ERR_DUPLICATE_TAG: i32 = 7;
+pub const ERR_MISSING_ACTOR: i32 = 8;
+
+fn setup_logger(config: &config::Config) {
+    let loglevel = if let Some(log) = config.log.as_ref() {
+        match log.level {
+            config::Log_Level::TRACE => log::LevelFilter::Trace,
+            config::Log_Level::DEBUG => log::LevelFilter::Debug,
+            config::Log_Level::INFO => log::LevelFilter::Info,
+            config::Log_Level::WARN => log::LevelFilter::Warn,
+            config::Log_Level::ERROR => log::LevelFilter::Error,
+        }
+    } else {
+        log::LevelFilter::Info
+    };
+    let mut logger = leaf::common::log::setup_logger(
+        loglevel,
+        leaf::common::log::LogFormat::new(config.log.as_ref()),
+    );
+    let console_output = fern::Output::writer(Box::new(ConsoleWriter(BytesMut::new())), "\n");
+    // The console output is replaced by the log callback if there's one.
+    logger = logger.chain(
+        fern::Dispatch::new()
+            .filter(|_| !logger::has_log_callback())
+            .chain(console_output),
+    );
+    if let Some(log) = config.log.as_ref() {
+        match log.output {
+            config::Log_Output::CONSOLE => {
+                // console output already applied
+            }
+            config::Log_Output::FILE => {
+                let f = fern::log_file(&log.output_file).expect("open log file failed");
+                let file_output = fern::Output::file(f, "\n");
+                logger = logger.chain(file_output);
+            }
+        }
+    }
+    // The callback gets the messages without the formatting of the other
+    // outputs, filtered by the same levels.
+    let callback_output = fern::Dispatch::new()
+        .filter(leaf::common::log::enabled)
+        .chain(Box::new(CallbackLogger) as Box<dyn log::Log>);
+    leaf::common::log::apply_logger(fern::Dispatch::new().chain(logger).chain(callback_output));
+}
+
+fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(s).to_str().ok() }
+}
+
+// Copies the string to `buf` with a NUL terminator.
+fn write_buf(s: &str, buf: *mut c_char, len: usize) -> i32 {
+    if s.len() + 1 > len {
+        return ERR_BUFFER_TOO_SMALL;
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(s.as_ptr(), buf as *mut u8, s.len());
+        *buf.add(s.len()) = 0;
+    }
+    ERR_OK
+}
+
 #[no_mangle]
 pub extern "C" fn run_leaf(path: *const c_char) {
     if let Ok(path) = unsafe { CStr::from_ptr(path).to_str() } {
         let config = leaf::config::from_file(path).expect("read config failed");
 
-        let loglevel = if let Some(log) = config.log.as_ref() {
-            match log.level {
-                config::Log_Level::TRACE => log::LevelFilter::Trace,
-                config::Log_Level::DEBUG => log::LevelFilter::Debug,
-                config::Log_Level::INFO => log::LevelFilter::Info,
-                config::Log_Level::WARN => log::LevelFilter::Warn,
-                config::Log_Level::ERROR => log::LevelFilter::Error,
-            }
-        } else {
-            log::LevelFilter::Info
-        };
-        let mut logger = leaf::common::log::setup_logger(loglevel);
-        let console_output = fern::Output::writer(Box::new(ConsoleWriter(BytesMut::new())), "\n");
-        logger = logger.chain(console_output);
-        if let Some(log) = config.log.as_ref() {
-            match log.output {
-                config::Log_Output::CONSOLE => {
-                    // console output already applied
-                }
-                config::Log_Output::FILE => {
-                    let f = fern::log_file(&log.output_file).expect("open log file failed");
-                    let file_output = fern::Output::file(f, "\n");
-                    logger = logger.chain(file_output);
-                }
-            }
-        }
-        leaf::common::log::apply_logger(logger);
+        setup_logger(&config);
 
         let mut rt = tokio::runtime::Builder::new()
             .basic_scheduler()
@@ -79,3 +129,535 @@ pub extern "C" fn run_leaf(path: *const c_char) {
         return;
     }
 }
+
+/// Runs leaf with the config file, the instance is identified by `rt_id` for
+/// other functions to operate on, blocks until the instance stops.
+#[no_mangle]
+pub extern "C" fn leaf_run(rt_id: u16, config_path: *const c_char) -> i32 {
+    let path = match to_str(config_path) {
+        Some(v) => v,
+        None => return ERR_CONFIG_PATH,
+    };
+    let config = match leaf::config::from_file(path) {
+        Ok(v) => v,
+        Err(_) => return ERR_CONFIG,
+    };
+    setup_logger(&config);
+    if let Err(e) = leaf::util::run_with_id(rt_id, config) {
+        error!("run leaf failed: {}", e);
+        return ERR_RUNTIME;
+    }
+    ERR_OK
+}
+
+/// Checks the config file without running it. Returns `ERR_OK` with an empty
+/// string in `buf` if the config is valid, or `ERR_CONFIG` with the reason
+/// in `buf`, e.g. `outbound [vmess_out]: invalid vmess outbound settings`,
+/// truncated to fit if the buffer is too small.
+#[no_mangle]
+pub extern "C" fn leaf_test_config_verbose(
+    config_path: *const c_char,
+    buf: *mut c_char,
+    len: usize,
+) -> i32 {
+    let path = match to_str(config_path) {
+        Some(v) => v,
+        None => return ERR_CONFIG_PATH,
+    };
+    if buf.is_null() || len == 0 {
+        return ERR_INVALID_INPUT;
+    }
+    match leaf::test_config(path) {
+        Ok(()) => write_buf("", buf, len),
+        Err(e) => {
+            let mut msg = format!("{:#}", e);
+            if msg.len() + 1 > len {
+                let mut end = len - 1;
+                while !msg.is_char_boundary(end) {
+                    end -= 1;
+                }
+                msg.truncate(end);
+            }
+            write_buf(&msg, buf, len);
+            ERR_CONFIG
+        }
+    }
+}
+
+/// Sets a callback receiving the log lines of the instance, at the level set
+/// in the config, the console output is turned off while there's a callback.
+/// Passing null removes it and restores the console output. Logging is
+/// process-wide, so the callback receives the logs of all running instances.
+/// It can be set before `leaf_run`, and is called on the threads the logs are
+/// written from.
+#[no_mangle]
+pub extern "C" fn leaf_set_log_callback(rt_id: u16, cb: Option<LogCallback>) -> i32 {
+    logger::set_log_callback(rt_id, cb);
+    ERR_OK
+}
+
+/// Sets a callback protecting the outbound sockets from the VPN, e.g. by
+/// `VpnService.protect()` on Android, so the traffic leaf sends out doesn't
+/// loop back into its TUN. It's called with the raw fd of every outbound TCP
+/// and UDP socket before it connects, a socket fails to connect if it
+/// returns false. Passing null removes it. Sockets are process-wide, so the
+/// socket is passed to the callbacks of all running instances. It should be
+/// set before `leaf_run`, and is called on the threads creating the sockets.
+#[no_mangle]
+pub extern "C" fn leaf_set_socket_protector(rt_id: u16, cb: Option<SocketProtector>) -> i32 {
+    protector::set_socket_protector(rt_id, cb);
+    ERR_OK
+}
+
+/// Sets the level of the logs (1 error, 2 warn, 3 info, 4 debug, 5 trace),
+/// taking effect immediately without reloading, e.g. to turn on tracing
+/// while diagnosing an issue. Logging is process-wide, so the level applies
+/// to all running instances, until it's set again or an instance is run with
+/// the level of its config.
+#[no_mangle]
+pub extern "C" fn leaf_set_log_level(_rt_id: u16, level: i32) -> i32 {
+    let level = match level {
+        1 => log::LevelFilter::Error,
+        2 => log::LevelFilter::Warn,
+        3 => log::LevelFilter::Info,
+        4 => log::LevelFilter::Debug,
+        5 => log::LevelFilter::Trace,
+        _ => return ERR_INVALID_INPUT,
+    };
+    leaf::common::log::set_level(level);
+    ERR_OK
+}
+
+/// Shuts down a running instance immediately, the relayed connections are
+/// dropped. `leaf_run` returns once the instance has stopped.
+#[no_mangle]
+pub extern "C" fn leaf_shutdown(rt_id: u16) -> i32 {
+    shutdown(rt_id, None)
+}
+
+/// Shuts down a running instance gracefully. New inbound connections are no
+/// longer accepted, the relayed TCP connections and UDP sessions are given up
+/// to `timeout_secs` seconds to finish before the instance stops. Returns
+/// without waiting, `leaf_run` returns once the instance has stopped.
+#[no_mangle]
+pub extern "C" fn leaf_shutdown_graceful(rt_id: u16, timeout_secs: u32) -> i32 {
+    shutdown(rt_id, Some(Duration::from_secs(timeout_secs as u64)))
+}
+
+fn shutdown(rt_id: u16, grace: Option<Duration>) -> i32 {
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    if let Err(e) = manager.shutdown(grace) {
+        error!("shutdown failed: {}", e);
+        return ERR_RUNTIME;
+    }
+    ERR_OK
+}
+
+/// Adds an outbound defined as a JSON object, in the same form as an item of
+/// `outbounds` in a JSON config, to a running instance. The existing outbounds
+/// are kept, actors of an ensemble outbound must already exist. Returns
+/// `ERR_DUPLICATE_TAG` if the tag is taken, `ERR_MISSING_ACTOR` if an actor is
+/// not found.
+#[no_mangle]
+pub extern "C" fn leaf_add_outbound(rt_id: u16, outbound_json: *const c_char) -> i32 {
+    let outbound_json = match to_str(outbound_json) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let outbound = match leaf::config::json::outbound_from_string(outbound_json) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("invalid outbound: {}", e);
+            return ERR_INVALID_INPUT;
+        }
+    };
+    add_outbound(&manager, &outbound)
+}
+
+/// Adds the outbounds in a subscription, a list of share links or the base64
+/// encoding of it, to a running instance. Stops at the first outbound failed
+/// to add and returns the error as `leaf_add_outbound` does, the outbounds
+/// added before it are kept.
+#[no_mangle]
+pub extern "C" fn leaf_add_outbounds_from_subscription(rt_id: u16, text: *const c_char) -> i32 {
+    let text = match to_str(text) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let outbounds = match leaf::config::parse_subscription(text) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("invalid subscription: {}", e);
+            return ERR_INVALID_INPUT;
+        }
+    };
+    for outbound in outbounds.iter() {
+        let ret = add_outbound(&manager, outbound);
+        if ret != ERR_OK {
+            return ret;
+        }
+    }
+    ERR_OK
+}
+
+fn add_outbound(manager: &leaf::app::runtime::RuntimeManager, outbound: &config::Outbound) -> i32 {
+    if let Err(e) = manager.add_outbound(outbound) {
+        error!("add outbound failed: {}", e);
+        return match e.downcast_ref::<AddOutboundError>() {
+            Some(AddOutboundError::DuplicateTag(_)) => ERR_DUPLICATE_TAG,
+            Some(AddOutboundError::MissingActor(..)) => ERR_MISSING_ACTOR,
+            _ => ERR_CONFIG,
+        };
+    }
+    ERR_OK
+}
+
+/// Adds outbounds from a share link, e.g. `ss://`, `trojan://` or `vmess://`,
+/// to a running instance. The existing outbounds are kept, returns
+/// `ERR_DUPLICATE_TAG` if a tag is taken, `ERR_MISSING_ACTOR` if an actor is
+/// not found, `ERR_IMPORT` on other failures.
+#[no_mangle]
+pub extern "C" fn leaf_add_outbound_from_uri(rt_id: u16, uri: *const c_char) -> i32 {
+    let uri = match to_str(uri) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    if leaf::app::runtime::get(rt_id).is_none() {
+        return ERR_RUNTIME;
+    }
+    if let Err(e) = leaf::app::subscription::add_outbound_from_uri(rt_id, uri) {
+        error!("add outbound from uri failed: {}", e);
+        return import_error(e.downcast_ref());
+    }
+    ERR_OK
+}
+
+/// Fetches the subscription through the control outbound of a running
+/// instance, and adds all share links in it as outbounds. Stops at the first
+/// outbound failed to add and returns the error as
+/// `leaf_add_outbound_from_uri` does, the outbounds added before it are kept.
+#[no_mangle]
+pub extern "C" fn leaf_import_subscription(rt_id: u16, url: *const c_char) -> i32 {
+    let url = match to_str(url) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    if leaf::app::runtime::get(rt_id).is_none() {
+        return ERR_RUNTIME;
+    }
+    if let Err(e) = leaf::app::subscription::import_subscription(rt_id, url) {
+        error!("import subscription failed: {}", e);
+        return import_error(e.downcast_ref());
+    }
+    ERR_OK
+}
+
+fn import_error(e: Option<&AddOutboundError>) -> i32 {
+    match e {
+        Some(AddOutboundError::DuplicateTag(_)) => ERR_DUPLICATE_TAG,
+        Some(AddOutboundError::MissingActor(..)) => ERR_MISSING_ACTOR,
+        _ => ERR_IMPORT,
+    }
+}
+
+/// Selects the actor `actor_tag` of the select outbound `selector_tag` in a
+/// running instance. New connections and UDP sessions go through the actor,
+/// existing ones are kept.
+#[no_mangle]
+pub extern "C" fn leaf_select_outbound(
+    rt_id: u16,
+    selector_tag: *const c_char,
+    actor_tag: *const c_char,
+) -> i32 {
+    let (selector_tag, actor_tag) = match (to_str(selector_tag), to_str(actor_tag)) {
+        (Some(s), Some(a)) => (s, a),
+        _ => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let selector = match manager.get_selector(selector_tag) {
+        Some(v) => v,
+        None => {
+            error!(
+                "select outbound failed: selector [{}] not found",
+                selector_tag
+            );
+            return ERR_INVALID_INPUT;
+        }
+    };
+    if let Err(e) = selector.set_selected(actor_tag) {
+        error!("select outbound failed: {}", e);
+        return ERR_INVALID_INPUT;
+    }
+    ERR_OK
+}
+
+/// Writes the tag of the actor selected by the select outbound
+/// `selector_tag` in a running instance to `buf`, NUL-terminated.
+#[no_mangle]
+pub extern "C" fn leaf_get_selected(
+    rt_id: u16,
+    selector_tag: *const c_char,
+    buf: *mut c_char,
+    len: usize,
+) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let selector_tag = match to_str(selector_tag) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let selector = match manager.get_selector(selector_tag) {
+        Some(v) => v,
+        None => {
+            error!("get selected failed: selector [{}] not found", selector_tag);
+            return ERR_INVALID_INPUT;
+        }
+    };
+    write_buf(selector.get_selected(), buf, len)
+}
+
+/// Reads the traffic carried by the outbound `tag` of a running instance
+/// since it was loaded, the bytes sent and received and the number of TCP
+/// connections and UDP sessions being relayed. Ensemble outbounds count the
+/// traffic of their actors, the actors count only what is routed to them
+/// directly.
+#[no_mangle]
+pub extern "C" fn leaf_outbound_stats(
+    rt_id: u16,
+    tag: *const c_char,
+    tx_bytes: *mut u64,
+    rx_bytes: *mut u64,
+    active_conns: *mut u64,
+) -> i32 {
+    if tx_bytes.is_null() || rx_bytes.is_null() || active_conns.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let tag = match to_str(tag) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let (tx, rx, active) = match manager.outbound_stats(tag) {
+        Some(v) => v,
+        None => {
+            error!("get outbound stats failed: outbound [{}] not found", tag);
+            return ERR_INVALID_INPUT;
+        }
+    };
+    unsafe {
+        *tx_bytes = tx;
+        *rx_bytes = rx;
+        *active_conns = active as u64;
+    }
+    ERR_OK
+}
+
+/// Writes the health check results of the failover outbound `tag` of a
+/// running instance to `buf` as a NUL-terminated JSON object, with the actor
+/// tried first as `preferred`, and the latency in milliseconds and up state
+/// of each actor from the last TCP health check, both `null` if not checked
+/// yet, e.g. `{"preferred":"a","actors":[{"tag":"a","latency":120,"up":true},
+/// {"tag":"b","latency":null,"up":false}]}`.
+#[no_mangle]
+pub extern "C" fn leaf_failover_status(
+    rt_id: u16,
+    tag: *const c_char,
+    buf: *mut c_char,
+    len: usize,
+) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let tag = match to_str(tag) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let json = match manager.dump_failover_status(tag) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("get failover status failed: {}", e);
+            return ERR_INVALID_INPUT;
+        }
+    };
+    write_buf(&json, buf, len)
+}
+
+/// Writes the races won by the actors of the tryall outbound `tag` of a
+/// running instance to `buf` as a NUL-terminated JSON object, with the
+/// number of wins and the average connect latency in milliseconds of each
+/// actor, `null` if never won, e.g. `{"actors":[{"tag":"a","wins":8,
+/// "latency":95},{"tag":"b","wins":0,"latency":null}]}`.
+#[no_mangle]
+pub extern "C" fn leaf_tryall_stats(
+    rt_id: u16,
+    tag: *const c_char,
+    buf: *mut c_char,
+    len: usize,
+) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let tag = match to_str(tag) {
+        Some(v) => v,
+        None => return ERR_INVALID_INPUT,
+    };
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let json = match manager.dump_tryall_stats(tag) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("get tryall stats failed: {}", e);
+            return ERR_INVALID_INPUT;
+        }
+    };
+    write_buf(&json, buf, len)
+}
+
+/// Writes the DNS cache of a running instance to `buf` as a NUL-terminated
+/// JSON array, each entry has the domain, the IPs, whether it's a negative
+/// entry and the remaining TTL in seconds (`null` for static hosts).
+#[no_mangle]
+pub extern "C" fn leaf_dns_cache_dump(rt_id: u16, buf: *mut c_char, len: usize) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let json = match manager.dump_dns_cache() {
+        Ok(v) => v,
+        Err(e) => {
+            error!("dump dns cache failed: {}", e);
+            return ERR_RUNTIME;
+        }
+    };
+    write_buf(&json, buf, len)
+}
+
+/// Writes the routing rules loaded by a running instance to `buf` as a
+/// NUL-terminated JSON object, with the default outbound, whether it's in
+/// dry-run mode, and the rules in the order they are matched. Each rule has
+/// the target outbound and its conditions, the values of a condition are
+/// given as a count for large sets like the ones from geosite, e.g.
+/// `10234 domains`.
+#[no_mangle]
+pub extern "C" fn leaf_dump_routes(rt_id: u16, buf: *mut c_char, len: usize) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    write_buf(&manager.dump_routes(), buf, len)
+}
+
+/// Writes the TCP connections and UDP sessions being relayed by a running
+/// instance to `buf` as a NUL-terminated JSON array, oldest first. Each entry
+/// has an id, the network, source, destination, inbound and outbound tags,
+/// the index of the matched rule as in `leaf_dump_routes` (`null` for the
+/// default route), the bytes sent and received, and the age in seconds, e.g.
+/// `[{"id":7,"network":"tcp","source":"10.0.0.2:50312","destination":
+/// "www.google.com:443","inbound":"tun","outbound":"proxy","rule":3,
+/// "uplink":1024,"downlink":40960,"age":12}]`.
+#[no_mangle]
+pub extern "C" fn leaf_connections(rt_id: u16, buf: *mut c_char, len: usize) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    write_buf(&manager.dump_connections(), buf, len)
+}
+
+/// Clears the DNS cache of a running instance.
+#[no_mangle]
+pub extern "C" fn leaf_dns_cache_flush(rt_id: u16) -> i32 {
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    if let Err(e) = manager.flush_dns_cache() {
+        error!("flush dns cache failed: {}", e);
+        return ERR_RUNTIME;
+    }
+    ERR_OK
+}
+
+/// Writes the load of a running instance to `buf` as a NUL-terminated JSON
+/// object, with the number of relayed TCP connections and UDP sessions, the
+/// throughput in bytes per second averaged since the previous call, the total
+/// bytes relayed, and the resident memory of the process in bytes (`null` if
+/// not available on the platform).
+#[no_mangle]
+pub extern "C" fn leaf_get_load(rt_id: u16, buf: *mut c_char, len: usize) -> i32 {
+    if buf.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    let json = match manager.report_load() {
+        Ok(v) => v,
+        Err(e) => {
+            error!("get load failed: {}", e);
+            return ERR_RUNTIME;
+        }
+    };
+    write_buf(&json, buf, len)
+}
+
+/// Notifies a running instance that the network has changed, e.g. switched
+/// from Wi-Fi to cellular, should be called on the network change event of
+/// the OS.
+///
+/// The DNS cache is flushed so domains are re-resolved on the new network,
+/// and all relayed TCP connections, direct or proxied, are reset since they
+/// are bound to the local address of the previous network, the clients are
+/// expected to reconnect. UDP sessions are kept, they are not bound to a
+/// local address unless the outbound has a specific `bind`, and expire by
+/// the session timeout otherwise. There's no QUIC transport to migrate yet.
+#[no_mangle]
+pub extern "C" fn leaf_notify_network_change(rt_id: u16) -> i32 {
+    let manager = match leaf::app::runtime::get(rt_id) {
+        Some(v) => v,
+        None => return ERR_RUNTIME,
+    };
+    if let Err(e) = manager.notify_network_change() {
+        error!("notify network change failed: {}", e);
+        return ERR_RUNTIME;
+    }
+    ERR_OK
+}
@@ -1,10 +1,14 @@
 use std::{
+    collections::HashMap,
     ffi,
     io::{self, Write},
+    os::raw::c_char,
     ptr,
+    sync::RwLock,
 };
 
 use bytes::BytesMut;
+use lazy_static::lazy_static;
 use log::{Level, Metadata, Record};
 
 use super::ios::{asl_log, ASL_LEVEL_NOTICE};
@@ -67,3 +71,55 @@ impl Write for ConsoleWriter {
         Ok(())
     }
 }
+
+/// Receives a log line as the level (1 error, 2 warn, 3 info, 4 debug,
+/// 5 trace) and the message, which is only valid during the call.
+pub type LogCallback = extern "C" fn(level: i32, msg: *const c_char);
+
+lazy_static! {
+    static ref LOG_CALLBACKS: RwLock<HashMap<u16, LogCallback>> = RwLock::new(HashMap::new());
+}
+
+/// Sets or removes the log callback of an instance.
+pub fn set_log_callback(rt_id: u16, cb: Option<LogCallback>) {
+    let mut callbacks = LOG_CALLBACKS.write().unwrap();
+    match cb {
+        Some(cb) => {
+            callbacks.insert(rt_id, cb);
+        }
+        None => {
+            callbacks.remove(&rt_id);
+        }
+    }
+}
+
+pub fn has_log_callback() -> bool {
+    !LOG_CALLBACKS.read().unwrap().is_empty()
+}
+
+/// Forwards records to the log callbacks. Records are not attributed to
+/// instances, every callback receives all of them.
+pub struct CallbackLogger;
+
+impl log::Log for CallbackLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        has_log_callback()
+    }
+
+    fn log(&self, record: &Record) {
+        // Called without holding the lock, a callback may change callbacks.
+        let callbacks: Vec<LogCallback> = LOG_CALLBACKS.read().unwrap().values().cloned().collect();
+        if callbacks.is_empty() {
+            return;
+        }
+        let msg = match ffi::CString::new(record.args().to_string()) {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        for cb in callbacks {
+            cb(record.level() as i32, msg.as_ptr());
+        }
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,42 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use lazy_static::lazy_static;
+
+/// Protects an outbound socket, given its raw fd, returns whether the socket
+/// is protected.
+pub type SocketProtector = extern "C" fn(fd: i32) -> bool;
+
+lazy_static! {
+    static ref SOCKET_PROTECTORS: RwLock<HashMap<u16, SocketProtector>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Sets or removes the socket protector of an instance.
+pub fn set_socket_protector(rt_id: u16, cb: Option<SocketProtector>) {
+    let mut protectors = SOCKET_PROTECTORS.write().unwrap();
+    match cb {
+        Some(cb) => {
+            protectors.insert(rt_id, cb);
+        }
+        None => {
+            protectors.remove(&rt_id);
+        }
+    }
+    if protectors.is_empty() {
+        leaf::common::sys::set_socket_protector(None);
+    } else {
+        leaf::common::sys::set_socket_protector(Some(Box::new(protect)));
+    }
+}
+
+// Sockets are not attributed to instances, a socket is protected by every
+// protector.
+fn protect(fd: i32) -> bool {
+    let protectors: Vec<SocketProtector> = SOCKET_PROTECTORS
+        .read()
+        .unwrap()
+        .values()
+        .cloned()
+        .collect();
+    protectors.into_iter().all(|cb| cb(fd))
+}
@@ -0,0 +1,88 @@
+//! Throughput of the TCP relay copy loop on loopback with different buffer
+//! sizes, run with `cargo bench --bench relay`.
+
+use std::sync::atomic::AtomicU64;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use leaf::app::dispatcher::{transfer, Activity};
+
+// bytes relayed in each round
+const TOTAL: usize = 1 << 30;
+const ROUNDS: usize = 3;
+
+// Relays TOTAL bytes from a source to a sink through the copy loop, returns
+// the time taken.
+async fn relay_once(buf_size: usize) -> Duration {
+    let mut sink_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let sink_addr = sink_listener.local_addr().unwrap();
+    let mut relay_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let relay_addr = relay_listener.local_addr().unwrap();
+
+    let sink = tokio::spawn(async move {
+        let (mut stream, _) = sink_listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut n = 0;
+        loop {
+            match stream.read(&mut buf).await.unwrap() {
+                0 => break,
+                i => n += i,
+            }
+        }
+        n
+    });
+    let relay = tokio::spawn(async move {
+        let (mut inbound, _) = relay_listener.accept().await.unwrap();
+        let mut outbound = TcpStream::connect(sink_addr).await.unwrap();
+        let (total, outbound_bytes, conn_bytes) =
+            (AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0));
+        let activity = Activity::new();
+        let (mut r, _w) = inbound.split();
+        transfer(
+            &mut r,
+            &mut outbound,
+            buf_size,
+            [&total, &outbound_bytes, &conn_bytes],
+            &activity,
+            &[],
+        )
+        .await
+        .unwrap();
+        outbound.shutdown().await.unwrap();
+    });
+
+    let start = Instant::now();
+    let mut source = TcpStream::connect(relay_addr).await.unwrap();
+    let chunk = vec![0u8; 64 * 1024];
+    let mut sent = 0;
+    while sent < TOTAL {
+        source.write_all(&chunk).await.unwrap();
+        sent += chunk.len();
+    }
+    source.shutdown().await.unwrap();
+    relay.await.unwrap();
+    assert_eq!(sink.await.unwrap(), TOTAL);
+    start.elapsed()
+}
+
+fn main() {
+    let mut rt = tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .unwrap();
+    for &buf_size in [8 * 1024, 64 * 1024].iter() {
+        // the best of the rounds, the first one warms up
+        let best = (0..ROUNDS)
+            .map(|_| rt.block_on(relay_once(buf_size)))
+            .min()
+            .unwrap();
+        println!(
+            "buffer {:>2} KB: {:>8.1} MB/s",
+            buf_size / 1024,
+            TOTAL as f64 / best.as_secs_f64() / (1024.0 * 1024.0)
+        );
+    }
+}
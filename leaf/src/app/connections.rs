@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::session::{Network, Session, SocksAddr};
+
+/// A TCP connection or UDP session being relayed.
+pub struct Connection {
+    pub id: u64,
+    pub network: Network,
+    pub source: SocketAddr,
+    pub destination: SocksAddr,
+    pub inbound_tag: String,
+    pub outbound_tag: String,
+    /// Index of the matched routing rule, `None` for the default route.
+    pub rule: Option<usize>,
+    pub start: Instant,
+    pub uplink: AtomicU64,
+    pub downlink: AtomicU64,
+}
+
+/// A snapshot of a connection.
+#[derive(Clone, Debug)]
+pub struct ConnectionInfo {
+    pub id: u64,
+    pub network: Network,
+    pub source: SocketAddr,
+    pub destination: SocksAddr,
+    pub inbound_tag: String,
+    pub outbound_tag: String,
+    pub rule: Option<usize>,
+    pub uplink: u64,
+    pub downlink: u64,
+    pub age: Duration,
+}
+
+type ConnectionMap = Arc<Mutex<HashMap<u64, Arc<Connection>>>>;
+
+/// The registry of connections being relayed.
+#[derive(Default)]
+pub struct Connections {
+    next_id: AtomicU64,
+    conns: ConnectionMap,
+}
+
+impl Connections {
+    /// Registers the connection of the session, it's unregistered once the
+    /// returned guard is dropped.
+    pub fn register(
+        &self,
+        sess: &Session,
+        outbound_tag: &str,
+        rule: Option<usize>,
+    ) -> ConnectionGuard {
+        let conn = Arc::new(Connection {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            network: sess.network,
+            source: sess.source,
+            destination: sess.destination.clone(),
+            inbound_tag: sess.inbound_tag.clone(),
+            outbound_tag: outbound_tag.to_owned(),
+            rule,
+            start: Instant::now(),
+            uplink: AtomicU64::new(0),
+            downlink: AtomicU64::new(0),
+        });
+        self.conns.lock().unwrap().insert(conn.id, conn.clone());
+        ConnectionGuard {
+            conn,
+            conns: self.conns.clone(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.conns.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the connections being relayed, oldest first.
+    pub fn snapshot(&self) -> Vec<ConnectionInfo> {
+        let mut conns: Vec<ConnectionInfo> = self
+            .conns
+            .lock()
+            .unwrap()
+            .values()
+            .map(|c| ConnectionInfo {
+                id: c.id,
+                network: c.network,
+                source: c.source,
+                destination: c.destination.clone(),
+                inbound_tag: c.inbound_tag.clone(),
+                outbound_tag: c.outbound_tag.clone(),
+                rule: c.rule,
+                uplink: c.uplink.load(Ordering::Relaxed),
+                downlink: c.downlink.load(Ordering::Relaxed),
+                age: c.start.elapsed(),
+            })
+            .collect();
+        conns.sort_by_key(|c| c.id);
+        conns
+    }
+}
+
+/// Keeps a connection registered, the connection is unregistered once
+/// dropped.
+pub struct ConnectionGuard {
+    conn: Arc<Connection>,
+    conns: ConnectionMap,
+}
+
+impl Deref for ConnectionGuard {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.conns.lock().unwrap().remove(&self.conn.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connections() {
+        let connections = Connections::default();
+        let mut sess = Session::default();
+        sess.source = "10.0.0.1:1000".parse().unwrap();
+        sess.destination = ("example.com", 443).into();
+        sess.inbound_tag = "socks_in".to_string();
+        let c1 = connections.register(&sess, "proxy", Some(2));
+        sess.network = Network::Udp;
+        let c2 = connections.register(&sess, "direct", None);
+        c1.uplink.fetch_add(100, Ordering::Relaxed);
+
+        let conns = connections.snapshot();
+        assert_eq!(conns.len(), 2);
+        assert_eq!(conns[0].network, Network::Tcp);
+        assert_eq!(conns[0].outbound_tag, "proxy");
+        assert_eq!(conns[0].rule, Some(2));
+        assert_eq!(conns[0].uplink, 100);
+        assert_eq!(conns[1].network, Network::Udp);
+        assert_eq!(conns[1].rule, None);
+
+        drop(c1);
+        let conns = connections.snapshot();
+        assert_eq!(conns.len(), 1);
+        assert_eq!(conns[0].id, c2.id);
+        drop(c2);
+        assert!(connections.is_empty());
+    }
+}
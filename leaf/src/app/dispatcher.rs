@@ -1,29 +1,35 @@
+use std::any::Any;
 use std::io::{self, ErrorKind};
 use std::pin::Pin;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use std::task::{Context, Poll};
 use std::time::Duration;
 
 use futures::{
-    future::{self, Either},
+    future::{self, BoxFuture, Either},
     ready, Future,
 };
 use log::*;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
-use tokio::sync::Semaphore;
-use tokio::time::timeout;
+use tokio::sync::{broadcast, Semaphore};
+use tokio::time::{timeout, Delay, Instant};
 
 #[cfg(not(target_os = "ios"))]
 use colored::Colorize;
 
 use crate::{
-    // common::stream,
+    common::{process, stream},
     option,
-    proxy::{OutboundDatagram, ProxyHandlerType},
+    proxy::{outbound, OutboundDatagram, OutboundHandler, ProxyHandlerType},
     session::{Session, SocksAddr},
 };
 
+use super::connections::{ConnectionGuard, Connections};
+use super::dns_client::DnsClient;
 use super::outbound::manager::OutboundManager;
+use super::rate_limit::{RateLimiter, TokenBucket};
+use super::rewriter::Rewriter;
 use super::router::Router;
 
 #[inline]
@@ -90,9 +96,25 @@ pub struct Transfer<'a, R: ?Sized, W: ?Sized> {
     cap: usize,
     amt: u64,
     buf: Box<[u8]>,
+    counters: [&'a AtomicU64; 3],
+    activity: &'a Activity,
+    buckets: &'a [Arc<TokenBucket>],
+    // waiting for tokens of the buckets
+    delay: Option<Delay>,
 }
 
-pub fn transfer<'a, R, W>(reader: &'a mut R, writer: &'a mut W) -> Transfer<'a, R, W>
+/// Copies from the reader to the writer through a buffer of `buf_size`
+/// bytes, the bytes written are added to all the counters, e.g. the total,
+/// the outbound and the connection ones. Reads wait for tokens of all the
+/// buckets.
+pub fn transfer<'a, R, W>(
+    reader: &'a mut R,
+    writer: &'a mut W,
+    buf_size: usize,
+    counters: [&'a AtomicU64; 3],
+    activity: &'a Activity,
+    buckets: &'a [Arc<TokenBucket>],
+) -> Transfer<'a, R, W>
 where
     R: AsyncRead + Unpin + ?Sized,
     W: AsyncWrite + Unpin + ?Sized,
@@ -104,7 +126,11 @@ where
         amt: 0,
         pos: 0,
         cap: 0,
-        buf: vec![0; *option::LINK_BUFFER_SIZE * 1024].into_boxed_slice(),
+        buf: vec![0; buf_size].into_boxed_slice(),
+        counters,
+        activity,
+        buckets,
+        delay: None,
     }
 }
 
@@ -120,6 +146,7 @@ where
             // If our buffer is empty, then we need to read some data to
             // continue.
             if self.pos == self.cap && !self.read_done {
+                ready!(self.as_mut().poll_tokens(cx));
                 let me = &mut *self;
                 let n = ready!(Pin::new(&mut *me.reader).poll_read(cx, &mut me.buf))?;
                 if n == 0 {
@@ -127,6 +154,9 @@ where
                 } else {
                     self.pos = 0;
                     self.cap = n;
+                    for bucket in self.buckets.iter() {
+                        bucket.consume(n);
+                    }
                 }
             }
 
@@ -142,6 +172,10 @@ where
                 } else {
                     self.pos += i;
                     self.amt += i as u64;
+                    for counter in self.counters.iter() {
+                        counter.fetch_add(i as u64, Ordering::Relaxed);
+                    }
+                    self.activity.touch();
                 }
             }
 
@@ -156,27 +190,208 @@ where
     }
 }
 
+impl<R: ?Sized, W: ?Sized> Transfer<'_, R, W> {
+    // Waits on a timer until all the buckets have tokens.
+    fn poll_tokens(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if let Some(delay) = self.delay.as_mut() {
+                ready!(Pin::new(delay).poll(cx));
+                self.delay = None;
+            }
+            match self.buckets.iter().filter_map(|b| b.wait_time()).max() {
+                Some(wait) => self.delay = Some(tokio::time::delay_for(wait)),
+                None => return Poll::Ready(()),
+            }
+        }
+    }
+}
+
+/// Time of the last bytes relayed in either direction of a connection.
+pub struct Activity {
+    start: Instant,
+    // milliseconds since start
+    last: AtomicU64,
+}
+
+impl Activity {
+    pub fn new() -> Self {
+        Activity {
+            start: Instant::now(),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    pub fn touch(&self) {
+        self.last
+            .store(self.start.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn idle_for(&self) -> Duration {
+        let last = Duration::from_millis(self.last.load(Ordering::Relaxed));
+        self.start
+            .elapsed()
+            .checked_sub(last)
+            .unwrap_or_else(|| Duration::from_secs(0))
+    }
+}
+
+impl Default for Activity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Completes when there's no activity for the idle timeout, never if the
+/// timeout is `None`.
+async fn wait_idle(activity: &Activity, idle_timeout: Option<Duration>) {
+    let idle_timeout = match idle_timeout {
+        Some(v) => v,
+        None => return future::pending().await,
+    };
+    loop {
+        let idle = activity.idle_for();
+        if idle >= idle_timeout {
+            return;
+        }
+        tokio::time::delay_for(idle_timeout - idle).await;
+    }
+}
+
+/// Bytes relayed since start, UDP included.
+#[derive(Default)]
+pub struct Traffic {
+    pub uplink: AtomicU64,
+    pub downlink: AtomicU64,
+}
+
+// Makes the inbound connection reset rather than closed once dropped,
+// returns false if the connection can't be reset, only TCP connections from
+// tproxy and TUN can.
+fn reset_inbound(lhs: &mut dyn Any) -> bool {
+    if let Some(s) = lhs.downcast_mut::<stream::SniffingStream<tokio::net::TcpStream>>() {
+        return reset_inbound(s.get_mut());
+    }
+    if let Some(s) = lhs.downcast_mut::<tokio::net::TcpStream>() {
+        return s.set_linger(Some(Duration::from_secs(0))).is_ok();
+    }
+    #[cfg(all(
+        feature = "inbound-tun",
+        any(target_os = "ios", target_os = "macos", target_os = "linux")
+    ))]
+    {
+        use crate::proxy::tun::netstack;
+        if let Some(s) = lhs.downcast_mut::<stream::SniffingStream<netstack::TcpStream>>() {
+            return reset_inbound(s.get_mut());
+        }
+        if let Some(s) = lhs.downcast_mut::<netstack::TcpStream>() {
+            s.reset_on_drop();
+            return true;
+        }
+    }
+    false
+}
+
 pub struct Dispatcher {
-    outbound_manager: OutboundManager,
+    outbound_manager: Arc<RwLock<OutboundManager>>,
     router: Router,
+    rewriter: Rewriter,
+    rate_limiter: RateLimiter,
     endpoint_tcp_sem: Semaphore,
     direct_tcp_sem: Semaphore,
     num_endpoint_tcp: AtomicUsize,
     num_direct_tcp: AtomicUsize,
+    traffic: Arc<Traffic>,
+    connections: Connections,
+    network_change: broadcast::Sender<()>,
+    // bytes of the buffer for each direction of a TCP relay
+    link_buffer_size: usize,
 }
 
 impl Dispatcher {
-    pub fn new(outbound_manager: OutboundManager, router: Router) -> Self {
+    pub fn new(
+        outbound_manager: Arc<RwLock<OutboundManager>>,
+        router: Router,
+        rewriter: Rewriter,
+        rate_limiter: RateLimiter,
+    ) -> Self {
+        let (network_change, _) = broadcast::channel(1);
         Dispatcher {
             outbound_manager,
             router,
+            rewriter,
+            rate_limiter,
             endpoint_tcp_sem: Semaphore::new(option::ENDPOINT_TCP_CONCURRENCY),
             direct_tcp_sem: Semaphore::new(option::DIRECT_TCP_CONCURRENCY),
             num_endpoint_tcp: AtomicUsize::new(0),
             num_direct_tcp: AtomicUsize::new(0),
+            traffic: Arc::new(Traffic::default()),
+            connections: Connections::default(),
+            network_change,
+            link_buffer_size: *option::LINK_BUFFER_SIZE * 1024,
+        }
+    }
+
+    /// Sets the size in bytes of the buffer for each direction of a TCP
+    /// relay. Larger buffers take fewer reads and writes for bulk transfers
+    /// at the cost of the memory of every connection.
+    pub fn set_link_buffer_size(&mut self, size: usize) {
+        self.link_buffer_size = size;
+    }
+
+    /// Returns the number of TCP connections being relayed.
+    pub fn num_tcp_connections(&self) -> usize {
+        self.num_direct_tcp.load(Ordering::Relaxed) + self.num_endpoint_tcp.load(Ordering::Relaxed)
+    }
+
+    pub fn router(&self) -> &Router {
+        &self.router
+    }
+
+    pub fn traffic(&self) -> &Arc<Traffic> {
+        &self.traffic
+    }
+
+    /// Returns the TCP connections and UDP sessions being relayed.
+    pub fn connections(&self) -> &Connections {
+        &self.connections
+    }
+
+    /// Resets all relayed TCP connections, as they are bound to the local
+    /// address of the previous network. Returns the number of connections
+    /// reset.
+    pub fn reset_tcp_connections(&self) -> usize {
+        self.network_change.send(()).unwrap_or(0)
+    }
+
+    pub fn dns_client(&self) -> Arc<DnsClient> {
+        self.outbound_manager.read().unwrap().dns_client().clone()
+    }
+
+    /// Tells whether connections to the domain would be sent by a direct
+    /// outbound, groups are resolved to their current actors. `None` if it
+    /// can't be decided by the domain alone.
+    pub fn is_direct_domain(&self, domain: &str) -> Option<bool> {
+        let outbound_manager = self.outbound_manager.read().unwrap();
+        let tag = match self.router.pick_route_by_domain(domain)? {
+            Ok(tag) => tag,
+            Err(_) => outbound_manager.default_handler()?,
+        };
+        let h = outbound_manager.get_current(tag)?;
+        match h.handler_type() {
+            ProxyHandlerType::Direct => Some(true),
+            _ => Some(false),
         }
     }
 
+    /// Applies the destination rewrite rules of the inbound to the session.
+    pub fn rewrite(&self, sess: &mut Session) {
+        self.rewriter.rewrite(sess);
+    }
+
+    pub fn rewrite_addr(&self, inbound_tag: &str, addr: &SocksAddr) -> Option<SocksAddr> {
+        self.rewriter.rewrite_addr(inbound_tag, addr)
+    }
+
     async fn dispatch_endpoint_tcp_start(&self) {
         self.endpoint_tcp_sem.acquire().await.forget();
         let pn = self.num_endpoint_tcp.fetch_add(1, Ordering::SeqCst);
@@ -201,62 +416,214 @@ impl Dispatcher {
         trace!("active direct tcp connections -1: {}", pn - 1)
     }
 
-    pub async fn dispatch_tcp<T>(&self, sess: &mut Session, mut lhs: T)
+    /// Picks the outbound for the session, all sessions go to the default
+    /// outbound in dry-run mode. Returns the outbound tag, whether the
+    /// connection should be logged and the index of the matched rule, `None`
+    /// for the default route, the DNS strategy of the matched rule is set to
+    /// the session.
+    fn pick_outbound(&self, sess: &mut Session) -> Option<(String, bool, Option<usize>)> {
+        if !self.router.dry_run() {
+            match self.router.pick_rule(sess) {
+                Some((idx, tag)) => {
+                    debug!(
+                        "picked route [{}] for {} -> {}",
+                        tag, &sess.source, &sess.destination
+                    );
+                    let tag = tag.to_owned();
+                    sess.dns_strategy = self.router.rule_dns_strategy(idx);
+                    return Some((tag, self.router.rule_logs(idx), Some(idx)));
+                }
+                None => {
+                    trace!("pick route failed: no matching rules");
+                }
+            }
+        }
+        let tag = self
+            .outbound_manager
+            .read()
+            .unwrap()
+            .default_handler()
+            .cloned()?;
+        debug!(
+            "picked default route [{}] for {} -> {}",
+            tag, &sess.source, &sess.destination
+        );
+        Some((tag, true, None))
+    }
+
+    // Looks up the process owning the inbound connection on a blocking
+    // thread, the process name rules then match with the cached result
+    // instead of scanning the sockets on the runtime thread.
+    async fn lookup_process(&self, sess: &Session) {
+        if self.router.has_process_rules() {
+            process::lookup_process_name(sess.network, sess.source).await;
+        }
+    }
+
+    /// Logs the routing decision which would have been made for the session,
+    /// with the sniffed domain taking the place of an IP destination.
+    fn log_dry_run(&self, sess: &Session, sniffed_domain: Option<String>) {
+        let mut eval_sess = sess.clone();
+        if let Some(domain) = sniffed_domain.as_ref() {
+            if !sess.destination.is_domain() {
+                eval_sess.destination = SocksAddr::Domain(domain.clone(), sess.destination.port());
+            }
+        }
+        let decision = match self.router.pick_rule(&eval_sess) {
+            Some((idx, target)) => format!("rule #{} -> [{}]", idx, target),
+            None => "no rule -> default".to_string(),
+        };
+        info!(
+            "[dry-run] [{}] {} -> {} (sniffed: {}) matched {}",
+            &sess.inbound_tag,
+            &sess.source,
+            &sess.destination,
+            sniffed_domain.as_deref().unwrap_or("-"),
+            decision,
+        );
+    }
+
+    pub async fn dispatch_tcp<T>(&self, sess: &mut Session, lhs: T)
     where
         T: 'static + AsyncRead + AsyncWrite + Unpin + Send + Sync,
     {
-        // let lhs: Box<dyn ProxyStream> =
-        //     if sess.destination.is_domain() && sess.destination.port() == 443 {
-        //         Box::new(SimpleProxyStream(lhs))
-        //     } else {
-        //         let mut lhs = stream::SniffingStream::new(lhs);
-        //         if let Some(domain) = lhs.sniff().await? {
-        //             debug!("sniffed domain {}", &domain);
-        //             sess.destination = SocksAddr::from((domain, sess.destination.port()));
-        //         }
-        //         Box::new(SimpleProxyStream(lhs))
-        //     };
-
-        let outbound = match self.router.pick_route(&sess) {
-            Ok(tag) => {
-                debug!(
-                    "picked route [{}] for {} -> {}",
-                    tag, &sess.source, &sess.destination
-                );
-                tag
-            }
-            Err(err) => {
-                trace!("pick route failed: {}", err);
-                if let Some(tag) = self.outbound_manager.default_handler() {
+        self.rewrite(sess);
+        self.lookup_process(sess).await;
+
+        if self.router.dry_run() {
+            // The sniffed bytes are buffered in the stream and replayed to
+            // the outbound.
+            let mut lhs = stream::SniffingStream::new(lhs);
+            let sniffed_domain = match lhs.sniff().await {
+                Ok(v) => v,
+                Err(e) => {
                     debug!(
-                        "picked default route [{}] for {} -> {}",
-                        tag, &sess.source, &sess.destination
+                        "sniff {} -> {} failed: {}",
+                        &sess.source, &sess.destination, e
                     );
-                    tag
-                } else {
-                    warn!("can not find any handlers");
-                    if let Err(e) = lhs.shutdown().await {
-                        debug!(
-                            "tcp downlink {} <- {} error: {}",
-                            &sess.source, &sess.destination, e,
-                        );
-                    }
-                    return;
+                    None
                 }
+            };
+            self.log_dry_run(sess, sniffed_domain);
+            self.relay_tcp(sess, lhs).await;
+        } else {
+            self.relay_tcp(sess, lhs).await;
+        }
+    }
+
+    async fn relay_tcp<T>(&self, sess: &mut Session, mut lhs: T)
+    where
+        T: 'static + AsyncRead + AsyncWrite + Unpin + Send + Sync,
+    {
+        let (outbound, log, rule) = match self.pick_outbound(sess) {
+            Some(v) => v,
+            None => {
+                warn!("can not find any handlers");
+                if let Err(e) = lhs.shutdown().await {
+                    debug!(
+                        "tcp downlink {} <- {} error: {}",
+                        &sess.source, &sess.destination, e,
+                    );
+                }
+                return;
             }
         };
 
         let handshake_start = tokio::time::Instant::now();
-        if let Some(h) = self.outbound_manager.get(outbound) {
+        let (h, idle_timeout) = {
+            let outbound_manager = self.outbound_manager.read().unwrap();
+            (
+                outbound_manager.get(&outbound).cloned(),
+                outbound_manager.idle_timeout(&outbound),
+            )
+        };
+        if let Some(h) = h {
             match h.handler_type() {
                 ProxyHandlerType::Direct => self.dispatch_direct_tcp_start().await,
                 ProxyHandlerType::Endpoint | ProxyHandlerType::Ensemble => {
                     self.dispatch_endpoint_tcp_start().await
                 }
             }
+            h.stats().active_conns.fetch_add(1, Ordering::Relaxed);
+            let conn = self.connections.register(sess, h.tag(), rule);
+
+            // A network change leaves the connection bound to a stale local
+            // address, drops it so the client reconnects. Idle connections
+            // are dropped the same way.
+            let mut network_change = self.network_change.subscribe();
+            let activity = Activity::new();
+            let relay = Box::pin(self.handle_and_relay_tcp(
+                sess,
+                lhs,
+                &h,
+                handshake_start,
+                log,
+                &activity,
+                &conn,
+            ));
+            let interrupt = future::select(
+                Box::pin(network_change.recv()),
+                Box::pin(wait_idle(&activity, idle_timeout)),
+            );
+            match future::select(relay, interrupt).await {
+                Either::Left(_) => (),
+                Either::Right((Either::Left(_), _)) => {
+                    debug!(
+                        "tcp {} -> {} reset on network change [{}]",
+                        &sess.source,
+                        &sess.destination,
+                        &h.tag()
+                    );
+                }
+                Either::Right((Either::Right(_), _)) => {
+                    debug!(
+                        "tcp {} -> {} closed, idle for {}s [{}]",
+                        &sess.source,
+                        &sess.destination,
+                        activity.idle_for().as_secs(),
+                        &h.tag()
+                    );
+                }
+            }
 
-            match h.handle_tcp(sess, None).await {
-                Ok(rhs) => {
+            h.stats().active_conns.fetch_sub(1, Ordering::Relaxed);
+            match h.handler_type() {
+                ProxyHandlerType::Direct => self.dispatch_direct_tcp_done(),
+                ProxyHandlerType::Endpoint | ProxyHandlerType::Ensemble => {
+                    self.dispatch_endpoint_tcp_done()
+                }
+            }
+        } else {
+            // FIXME use  the default handler
+            debug!("handler not found");
+            if let Err(e) = lhs.shutdown().await {
+                debug!(
+                    "tcp downlink {} <- {} error: {}",
+                    &sess.source, &sess.destination, e,
+                );
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_and_relay_tcp<T>(
+        &self,
+        sess: &Session,
+        mut lhs: T,
+        h: &Arc<dyn OutboundHandler>,
+        handshake_start: tokio::time::Instant,
+        log: bool,
+        activity: &Activity,
+        conn: &ConnectionGuard,
+    ) where
+        T: 'static + AsyncRead + AsyncWrite + Unpin + Send + Sync,
+    {
+        match h.handle_tcp(sess, None).await {
+            Ok(rhs) => {
+                // The idle timeout doesn't include the handshake.
+                activity.touch();
+
+                if log {
                     let elapsed = tokio::time::Instant::now().duration_since(handshake_start);
                     log_tcp(
                         &sess.inbound_tag,
@@ -265,126 +632,124 @@ impl Dispatcher {
                         elapsed.as_millis(),
                         &sess.destination,
                     );
+                }
 
-                    let (mut lr, mut lw) = tokio::io::split(lhs);
-                    let (mut rr, mut rw) = tokio::io::split(rhs);
-
-                    let l2r = transfer(&mut lr, &mut rw);
-                    let r2l = transfer(&mut rr, &mut lw);
-
-                    // Drives both uplink and downlink to completion, i.e. read till EOF.
-                    match future::select(l2r, r2l).await {
-                        // Uplink task returns first, with the result of the completed uplink
-                        // task and the uncompleted downlink task.
-                        Either::Left((up_res, new_r2l)) => {
-                            // Logs the uplink result, either successful with bytes transfered
-                            // or an error.
-                            match up_res {
-                                Ok(up_n) => {
-                                    debug!(
-                                        "tcp uplink {} -> {} done, {} bytes transfered [{}]",
-                                        &sess.source,
-                                        &sess.destination,
-                                        up_n,
-                                        &h.tag(),
-                                    );
-                                }
-                                Err(up_e) => {
-                                    debug!(
-                                        "tcp uplink {} -> {} error: {} [{}]",
-                                        &sess.source,
-                                        &sess.destination,
-                                        up_e,
-                                        &h.tag()
-                                    );
-                                }
-                            }
-
-                            // Puts a timeout limit on the uncompleted downlink task, because uplink
-                            // has been completed, and we don't like half-closed connections, the other
-                            // half must complete before timeout.
-                            let timed_r2l = timeout(
-                                Duration::from_secs(*option::TCP_DOWNLINK_TIMEOUT),
-                                new_r2l,
-                            );
+                let stats = h.stats();
+                let (up_buckets, down_buckets) =
+                    self.rate_limiter.buckets(&sess.inbound_tag, h.tag());
+                let up_counters = [&self.traffic.uplink, &stats.tx_bytes, &conn.uplink];
+                let down_counters = [&self.traffic.downlink, &stats.rx_bytes, &conn.downlink];
 
-                            trace!(
-                                "applied {}s downlink timeout to {} <- {}",
-                                *option::TCP_DOWNLINK_TIMEOUT,
-                                &sess.source,
-                                &sess.destination
-                            );
+                // Splicing bypasses the rate limits.
+                #[cfg(all(feature = "splice", target_os = "linux"))]
+                let spliced = if up_buckets.is_empty() && down_buckets.is_empty() {
+                    super::splice::relays(
+                        &lhs as &dyn Any,
+                        rhs.as_ref(),
+                        up_counters,
+                        down_counters,
+                        activity,
+                    )
+                } else {
+                    None
+                };
+                #[cfg(not(all(feature = "splice", target_os = "linux")))]
+                let spliced = None;
 
-                            // Because uplink has been completed, no furture data from the inbound
-                            // connection, we would like to close the write side of the outbound
-                            // connection, so that notifies the close of the pipeline.
-                            let rw_shutdown = rw.shutdown();
+                let (mut lr, mut lw) = tokio::io::split(lhs);
+                let (mut rr, mut rw) = tokio::io::split(rhs);
 
-                            // Drives both the above tasks to completion simultaneously and get the
-                            // results.
-                            let (shutdown_res, timed_r2l_res) =
-                                future::join(rw_shutdown, timed_r2l).await;
+                let (l2r, r2l): (BoxFuture<io::Result<u64>>, BoxFuture<io::Result<u64>>) =
+                    match spliced {
+                        Some(relays) => {
+                            trace!("splicing {} <-> {}", &sess.source, &sess.destination);
+                            relays
+                        }
+                        None => (
+                            Box::pin(transfer(
+                                &mut lr,
+                                &mut rw,
+                                self.link_buffer_size,
+                                up_counters,
+                                activity,
+                                &up_buckets,
+                            )),
+                            Box::pin(transfer(
+                                &mut rr,
+                                &mut lw,
+                                self.link_buffer_size,
+                                down_counters,
+                                activity,
+                                &down_buckets,
+                            )),
+                        ),
+                    };
 
-                            // Logs the shutdown result.
-                            if let Err(e) = shutdown_res {
+                // Drives both uplink and downlink to completion, i.e. read till EOF.
+                match future::select(l2r, r2l).await {
+                    // Uplink task returns first, with the result of the completed uplink
+                    // task and the uncompleted downlink task.
+                    Either::Left((up_res, new_r2l)) => {
+                        // Logs the uplink result, either successful with bytes transfered
+                        // or an error.
+                        match up_res {
+                            Ok(up_n) => {
                                 debug!(
-                                    "tcp uplink {} -> {} error: {} [{}]",
+                                    "tcp uplink {} -> {} done, {} bytes transfered [{}]",
                                     &sess.source,
                                     &sess.destination,
-                                    e,
-                                    &h.tag()
+                                    up_n,
+                                    &h.tag(),
                                 );
                             }
-
-                            // Logs the downlink result.
-                            match timed_r2l_res {
-                                Ok(down_res) => match down_res {
-                                    Ok(down_n) => {
-                                        debug!(
-                                            "tcp downlink {} <- {} done, {} bytes transfered [{}]",
-                                            &sess.source,
-                                            &sess.destination,
-                                            down_n,
-                                            &h.tag(),
-                                        );
-                                    }
-                                    Err(down_e) => {
-                                        debug!(
-                                            "tcp downlink {} <- {} error: {} [{}]",
-                                            &sess.source,
-                                            &sess.destination,
-                                            down_e,
-                                            &h.tag()
-                                        );
-                                    }
-                                },
-                                Err(timeout_e) => {
-                                    debug!(
-                                        "tcp downlink {} <- {} timeout: {} [{}]",
-                                        &sess.source,
-                                        &sess.destination,
-                                        timeout_e,
-                                        &h.tag()
-                                    );
-                                }
-                            }
-
-                            // Finally shuts down the inbound connection.
-                            if let Err(e) = lw.shutdown().await {
+                            Err(up_e) => {
                                 debug!(
-                                    "tcp downlink {} <- {} error: {} [{}]",
+                                    "tcp uplink {} -> {} error: {} [{}]",
                                     &sess.source,
                                     &sess.destination,
-                                    e,
+                                    up_e,
                                     &h.tag()
                                 );
                             }
                         }
 
-                        // In case downlink returns first, the process is similar to the other
-                        // side described above, with the roles of uplink and downlink interchanged.
-                        Either::Right((down_res, new_l2r)) => {
-                            match down_res {
+                        // Puts a timeout limit on the uncompleted downlink task, because uplink
+                        // has been completed, and we don't like half-closed connections, the other
+                        // half must complete before timeout.
+                        let timed_r2l =
+                            timeout(Duration::from_secs(*option::TCP_DOWNLINK_TIMEOUT), new_r2l);
+
+                        trace!(
+                            "applied {}s downlink timeout to {} <- {}",
+                            *option::TCP_DOWNLINK_TIMEOUT,
+                            &sess.source,
+                            &sess.destination
+                        );
+
+                        // Because uplink has been completed, no furture data from the inbound
+                        // connection, we would like to close the write side of the outbound
+                        // connection, so that notifies the close of the pipeline.
+                        let rw_shutdown = rw.shutdown();
+
+                        // Drives both the above tasks to completion simultaneously and get the
+                        // results.
+                        let (shutdown_res, timed_r2l_res) =
+                            future::join(rw_shutdown, timed_r2l).await;
+
+                        // Logs the shutdown result.
+                        if let Err(e) = shutdown_res {
+                            debug!(
+                                "tcp uplink {} -> {} error: {} [{}]",
+                                &sess.source,
+                                &sess.destination,
+                                e,
+                                &h.tag()
+                            );
+                        }
+
+                        // Logs the downlink result.
+                        match timed_r2l_res {
+                            Ok(down_res) => match down_res {
                                 Ok(down_n) => {
                                     debug!(
                                         "tcp downlink {} <- {} done, {} bytes transfered [{}]",
@@ -403,158 +768,195 @@ impl Dispatcher {
                                         &h.tag()
                                     );
                                 }
+                            },
+                            Err(timeout_e) => {
+                                debug!(
+                                    "tcp downlink {} <- {} timeout: {} [{}]",
+                                    &sess.source,
+                                    &sess.destination,
+                                    timeout_e,
+                                    &h.tag()
+                                );
                             }
+                        }
 
-                            let timed_l2r =
-                                timeout(Duration::from_secs(*option::TCP_UPLINK_TIMEOUT), new_l2r);
-
-                            trace!(
-                                "applied {}s uplink timeout to {} -> {}",
-                                *option::TCP_UPLINK_TIMEOUT,
+                        // Finally shuts down the inbound connection.
+                        if let Err(e) = lw.shutdown().await {
+                            debug!(
+                                "tcp downlink {} <- {} error: {} [{}]",
                                 &sess.source,
-                                &sess.destination
+                                &sess.destination,
+                                e,
+                                &h.tag()
                             );
+                        }
+                    }
 
-                            let (shutdown_res, timed_l2r_res) =
-                                future::join(lw.shutdown(), timed_l2r).await;
-
-                            if let Err(e) = shutdown_res {
+                    // In case downlink returns first, the process is similar to the other
+                    // side described above, with the roles of uplink and downlink interchanged.
+                    Either::Right((down_res, new_l2r)) => {
+                        match down_res {
+                            Ok(down_n) => {
+                                debug!(
+                                    "tcp downlink {} <- {} done, {} bytes transfered [{}]",
+                                    &sess.source,
+                                    &sess.destination,
+                                    down_n,
+                                    &h.tag(),
+                                );
+                            }
+                            Err(down_e) => {
                                 debug!(
                                     "tcp downlink {} <- {} error: {} [{}]",
                                     &sess.source,
                                     &sess.destination,
-                                    e,
+                                    down_e,
                                     &h.tag()
                                 );
                             }
+                        }
+
+                        let timed_l2r =
+                            timeout(Duration::from_secs(*option::TCP_UPLINK_TIMEOUT), new_l2r);
+
+                        trace!(
+                            "applied {}s uplink timeout to {} -> {}",
+                            *option::TCP_UPLINK_TIMEOUT,
+                            &sess.source,
+                            &sess.destination
+                        );
+
+                        let (shutdown_res, timed_l2r_res) =
+                            future::join(lw.shutdown(), timed_l2r).await;
 
-                            match timed_l2r_res {
-                                Ok(up_res) => match up_res {
-                                    Ok(up_n) => {
-                                        debug!(
-                                            "tcp uplink {} -> {} done, {} bytes transfered [{}]",
-                                            &sess.source,
-                                            &sess.destination,
-                                            up_n,
-                                            &h.tag(),
-                                        );
-                                    }
-                                    Err(up_e) => {
-                                        debug!(
-                                            "tcp uplink {} -> {} error: {} [{}]",
-                                            &sess.source,
-                                            &sess.destination,
-                                            up_e,
-                                            &h.tag()
-                                        );
-                                    }
-                                },
-                                Err(timeout_e) => {
+                        if let Err(e) = shutdown_res {
+                            debug!(
+                                "tcp downlink {} <- {} error: {} [{}]",
+                                &sess.source,
+                                &sess.destination,
+                                e,
+                                &h.tag()
+                            );
+                        }
+
+                        match timed_l2r_res {
+                            Ok(up_res) => match up_res {
+                                Ok(up_n) => {
+                                    debug!(
+                                        "tcp uplink {} -> {} done, {} bytes transfered [{}]",
+                                        &sess.source,
+                                        &sess.destination,
+                                        up_n,
+                                        &h.tag(),
+                                    );
+                                }
+                                Err(up_e) => {
                                     debug!(
-                                        "tcp uplink {} -> {} timeout: {} [{}]",
+                                        "tcp uplink {} -> {} error: {} [{}]",
                                         &sess.source,
                                         &sess.destination,
-                                        timeout_e,
+                                        up_e,
                                         &h.tag()
                                     );
                                 }
-                            }
-
-                            if let Err(e) = rw.shutdown().await {
+                            },
+                            Err(timeout_e) => {
                                 debug!(
-                                    "tcp uplink {} -> {} error: {} [{}]",
+                                    "tcp uplink {} -> {} timeout: {} [{}]",
                                     &sess.source,
                                     &sess.destination,
-                                    e,
+                                    timeout_e,
                                     &h.tag()
                                 );
                             }
                         }
-                    }
 
-                    match h.handler_type() {
-                        ProxyHandlerType::Direct => self.dispatch_direct_tcp_done(),
-                        ProxyHandlerType::Endpoint | ProxyHandlerType::Ensemble => {
-                            self.dispatch_endpoint_tcp_done()
+                        if let Err(e) = rw.shutdown().await {
+                            debug!(
+                                "tcp uplink {} -> {} error: {} [{}]",
+                                &sess.source,
+                                &sess.destination,
+                                e,
+                                &h.tag()
+                            );
                         }
                     }
                 }
-                Err(e) => {
+            }
+            Err(e) => {
+                debug!(
+                    "dispatch tcp {} -> {} to [{}] failed: {}",
+                    &sess.source,
+                    &sess.destination,
+                    &h.tag(),
+                    e
+                );
+
+                // A reset requested by the outbound, e.g. a drop outbound
+                // with reset, falls back to a close if the inbound can't.
+                if e.kind() == ErrorKind::ConnectionReset && reset_inbound(&mut lhs) {
+                    return;
+                }
+
+                if let Err(e) = lhs.shutdown().await {
                     debug!(
-                        "dispatch tcp {} -> {} to [{}] failed: {}",
+                        "tcp downlink {} <- {} error: {} [{}]",
                         &sess.source,
                         &sess.destination,
-                        &h.tag(),
-                        e
+                        e,
+                        &h.tag()
                     );
-
-                    if let Err(e) = lhs.shutdown().await {
-                        debug!(
-                            "tcp downlink {} <- {} error: {} [{}]",
-                            &sess.source,
-                            &sess.destination,
-                            e,
-                            &h.tag()
-                        );
-                    }
-
-                    match h.handler_type() {
-                        ProxyHandlerType::Direct => self.dispatch_direct_tcp_done(),
-                        ProxyHandlerType::Endpoint | ProxyHandlerType::Ensemble => {
-                            self.dispatch_endpoint_tcp_done()
-                        }
-                    }
                 }
             }
-        } else {
-            // FIXME use  the default handler
-            debug!("handler not found");
-            if let Err(e) = lhs.shutdown().await {
-                debug!(
-                    "tcp downlink {} <- {} error: {}",
-                    &sess.source, &sess.destination, e,
-                );
-            }
         }
     }
 
-    pub async fn dispatch_udp(&self, sess: &Session) -> io::Result<Box<dyn OutboundDatagram>> {
-        let outbound = match self.router.pick_route(&sess) {
-            Ok(tag) => {
-                debug!(
-                    "picked route [{}] for {} -> {}",
-                    tag, &sess.source, &sess.destination
-                );
-                tag
-            }
-            Err(err) => {
-                trace!("pick route failed: {}", err);
-                if let Some(tag) = self.outbound_manager.default_handler() {
-                    debug!(
-                        "picked default route [{}] for {} -> {}",
-                        tag, &sess.source, &sess.destination
-                    );
-                    tag
-                } else {
-                    return Err(io::Error::new(ErrorKind::Other, "no available handler"));
-                }
-            }
+    /// Dispatches the UDP session, returns the outbound datagram, the stats
+    /// of the outbound handler and the registered connection, which the
+    /// caller updates as it relays and drops once the session ends.
+    pub async fn dispatch_udp(
+        &self,
+        sess: &Session,
+    ) -> io::Result<(
+        Box<dyn OutboundDatagram>,
+        Arc<outbound::Stats>,
+        ConnectionGuard,
+    )> {
+        self.lookup_process(sess).await;
+        if self.router.dry_run() {
+            self.log_dry_run(sess, None);
+        }
+
+        // Carries the DNS strategy of the matched rule.
+        let mut sess = sess.clone();
+        let (outbound, log, rule) = match self.pick_outbound(&mut sess) {
+            Some(v) => v,
+            None => return Err(io::Error::new(ErrorKind::Other, "no available handler")),
         };
 
         let handshake_start = tokio::time::Instant::now();
 
-        if let Some(h) = self.outbound_manager.get(outbound) {
-            match h.handle_udp(sess, None).await {
+        let h = self
+            .outbound_manager
+            .read()
+            .unwrap()
+            .get(&outbound)
+            .cloned();
+        if let Some(h) = h {
+            match h.handle_udp(&sess, None).await {
                 Ok(c) => {
-                    let elapsed = tokio::time::Instant::now().duration_since(handshake_start);
-                    log_udp(
-                        &sess.inbound_tag,
-                        h.tag(),
-                        h.color(),
-                        elapsed.as_millis(),
-                        &sess.destination,
-                    );
-                    Ok(c)
+                    if log {
+                        let elapsed = tokio::time::Instant::now().duration_since(handshake_start);
+                        log_udp(
+                            &sess.inbound_tag,
+                            h.tag(),
+                            h.color(),
+                            elapsed.as_millis(),
+                            &sess.destination,
+                        );
+                    }
+                    let conn = self.connections.register(&sess, h.tag(), rule);
+                    Ok((c, h.stats().clone(), conn))
                 }
                 Err(e) => {
                     debug!(
@@ -572,3 +974,146 @@ impl Dispatcher {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::app::rate_limit::RateLimiter;
+    use crate::config::json;
+
+    fn new_dispatcher(config: &str) -> Arc<Dispatcher> {
+        let config = json::to_internal(json::from_string(config.to_string()).unwrap()).unwrap();
+        let outbound_manager = Arc::new(RwLock::new(OutboundManager::new(
+            &config.outbounds,
+            config.dns.as_ref().unwrap(),
+        )));
+        Arc::new(Dispatcher::new(
+            outbound_manager,
+            Router::new(&config.routing_rules),
+            Rewriter::new(&config.inbounds),
+            RateLimiter::new(&config.inbounds, &config.outbounds),
+        ))
+    }
+
+    // Dispatches an accepted connection to the destination, returns the
+    // client end of it.
+    async fn dispatch(dispatcher: &Arc<Dispatcher>, destination: SocksAddr) -> TcpStream {
+        let mut inbound = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(inbound.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (lhs, source) = inbound.accept().await.unwrap();
+        let mut sess = Session::default();
+        sess.source = source;
+        sess.destination = destination;
+        let dispatcher = dispatcher.clone();
+        tokio::spawn(async move {
+            dispatcher.dispatch_tcp(&mut sess, lhs).await;
+        });
+        client
+    }
+
+    fn run<F: Future>(f: F) -> F::Output {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(f)
+    }
+
+    #[test]
+    fn test_reset_tcp_connections() {
+        run(async {
+            let dispatcher = new_dispatcher(
+                r#"{
+                    "outbounds":[{"protocol":"direct","tag":"direct"}],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#,
+            );
+
+            // An echo server as the destination.
+            let mut server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = server.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (stream, _) = server.accept().await.unwrap();
+                let (mut r, mut w) = tokio::io::split(stream);
+                let _ = tokio::io::copy(&mut r, &mut w).await;
+            });
+
+            let mut client = dispatch(&dispatcher, SocksAddr::from(server_addr)).await;
+            let mut buf = [0u8; 16];
+            client.write_all(b"hello").await.unwrap();
+            let n = client.read(&mut buf).await.unwrap();
+            assert_eq!(&buf[..n], b"hello");
+            assert_eq!(dispatcher.num_tcp_connections(), 1);
+
+            assert_eq!(dispatcher.reset_tcp_connections(), 1);
+            let res = tokio::time::timeout(Duration::from_secs(5), client.read(&mut buf))
+                .await
+                .unwrap();
+            assert!(matches!(res, Ok(0) | Err(_)));
+            assert_eq!(dispatcher.num_tcp_connections(), 0);
+        });
+    }
+
+    #[cfg(all(feature = "outbound-select", feature = "outbound-drop"))]
+    #[test]
+    fn test_is_direct_domain_select() {
+        run(async {
+            let dispatcher = new_dispatcher(
+                r#"{
+                    "outbounds":[
+                        {"protocol":"direct","tag":"direct"},
+                        {"protocol":"drop","tag":"drop"},
+                        {"protocol":"select","tag":"select","settings":{"actors":["direct","drop"]}}
+                    ],
+                    "rules":[{"domain":["select.com"],"target":"select"}],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#,
+            );
+            assert_eq!(dispatcher.is_direct_domain("select.com"), Some(true));
+            dispatcher
+                .outbound_manager
+                .read()
+                .unwrap()
+                .get_selector("select")
+                .unwrap()
+                .set_selected("drop")
+                .unwrap();
+            assert_eq!(dispatcher.is_direct_domain("select.com"), Some(false));
+        });
+    }
+
+    #[cfg(feature = "outbound-drop")]
+    #[test]
+    fn test_drop_reset() {
+        run(async {
+            let dispatcher = new_dispatcher(
+                r#"{
+                    "outbounds":[
+                        {"protocol":"drop","tag":"reset","settings":{"reset":true}},
+                        {"protocol":"drop","tag":"drop"}
+                    ],
+                    "rules":[{"domain":["drop.com"],"target":"drop"}],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#,
+            );
+            let mut buf = [0u8; 16];
+
+            // The inbound connection is reset by the default outbound.
+            let mut client =
+                dispatch(&dispatcher, SocksAddr::Domain("reset.com".to_string(), 80)).await;
+            let err = client.read(&mut buf).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ConnectionReset);
+
+            // And closed without reset.
+            let mut client =
+                dispatch(&dispatcher, SocksAddr::Domain("drop.com".to_string(), 80)).await;
+            assert_eq!(client.read(&mut buf).await.unwrap(), 0);
+        });
+    }
+}
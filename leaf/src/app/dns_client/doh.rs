@@ -0,0 +1,174 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use futures::TryFutureExt;
+use h2::client::SendRequest;
+use http::header::{ACCEPT, CONTENT_TYPE};
+use log::*;
+use tokio::sync::Mutex as TokioMutex;
+use tokio::time::timeout;
+
+use crate::{
+    option,
+    proxy::{tls::stream::wrapper, OutboundHandler, ProxyStream},
+};
+
+use super::{connect_outbound, connect_tcp};
+
+const DNS_MESSAGE: &str = "application/dns-message";
+
+/// A DNS-over-HTTPS (RFC 8484) client, queries are sent as POST requests on a
+/// pooled HTTP/2 connection to the server.
+pub struct Client {
+    host: String,
+    port: u16,
+    path: String,
+    conn: TokioMutex<Option<SendRequest<Bytes>>>,
+}
+
+impl Client {
+    pub fn new(host: String, port: u16, path: String) -> Self {
+        Client {
+            host,
+            port,
+            path,
+            conn: TokioMutex::new(None),
+        }
+    }
+
+    /// Returns the pooled connection if it's still usable.
+    pub async fn pooled(&self) -> Option<SendRequest<Bytes>> {
+        let send_request = self.conn.lock().await.as_ref()?.clone();
+        match send_request.ready().await {
+            Ok(send_request) => Some(send_request),
+            Err(e) => {
+                debug!("pooled connection to {} closed: {}", &self.host, e);
+                self.conn.lock().await.take();
+                None
+            }
+        }
+    }
+
+    /// Dials a new connection to the first reachable address and pools it.
+    pub async fn connect(
+        &self,
+        ips: &[IpAddr],
+        bind_addr: &SocketAddr,
+        ca_file: Option<&str>,
+    ) -> Result<SendRequest<Bytes>> {
+        let mut last_err = None;
+        for ip in ips {
+            let addr = SocketAddr::new(*ip, self.port);
+            match timeout(
+                Duration::from_secs(option::DNS_TIMEOUT),
+                self.dial(&addr, bind_addr, ca_file),
+            )
+            .await
+            {
+                Ok(Ok(send_request)) => {
+                    self.conn.lock().await.replace(send_request.clone());
+                    return Ok(send_request);
+                }
+                Ok(Err(e)) => last_err = Some(e),
+                Err(e) => last_err = Some(anyhow!("connect {} timeout: {}", &addr, e)),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("no address for {}", &self.host)))
+    }
+
+    /// Dials a new connection through the outbound and pools it.
+    pub async fn connect_via(
+        &self,
+        handler: &Arc<dyn OutboundHandler>,
+        ca_file: Option<&str>,
+    ) -> Result<SendRequest<Bytes>> {
+        let send_request = timeout(Duration::from_secs(option::DNS_TIMEOUT), async {
+            let stream = connect_outbound(&self.host, self.port, handler).await?;
+            self.handshake(stream, ca_file).await
+        })
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "connect {} via [{}] timeout: {}",
+                &self.host,
+                handler.tag(),
+                e
+            )
+        })??;
+        debug!(
+            "connected to dns server {} via [{}]",
+            &self.host,
+            handler.tag()
+        );
+        self.conn.lock().await.replace(send_request.clone());
+        Ok(send_request)
+    }
+
+    async fn dial(
+        &self,
+        addr: &SocketAddr,
+        bind_addr: &SocketAddr,
+        ca_file: Option<&str>,
+    ) -> Result<SendRequest<Bytes>> {
+        let stream = connect_tcp(addr, bind_addr).await?;
+        let send_request = self.handshake(Box::new(stream), ca_file).await?;
+        debug!("connected to dns server {} at {}", &self.host, addr);
+        Ok(send_request)
+    }
+
+    async fn handshake(
+        &self,
+        stream: Box<dyn ProxyStream>,
+        ca_file: Option<&str>,
+    ) -> Result<SendRequest<Bytes>> {
+        let stream =
+            wrapper::wrap_tls_with_ca(stream, &self.host, vec!["h2".to_string()], ca_file).await?;
+        let (send_request, conn) = h2::client::handshake(stream)
+            .map_err(|e| anyhow!("h2 handshake with {} failed: {}", &self.host, e))
+            .await?;
+        let host = self.host.clone();
+        tokio::spawn(async move {
+            if let Err(e) = conn.await {
+                debug!("connection to {} closed: {}", host, e);
+            }
+        });
+        Ok(send_request)
+    }
+
+    /// Sends the query on the connection, non-2xx statuses are errors.
+    pub async fn send(&self, send_request: SendRequest<Bytes>, request: &[u8]) -> Result<Vec<u8>> {
+        let mut send_request = send_request.ready().await?;
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri(format!(
+                "https://{}:{}{}",
+                &self.host, self.port, &self.path
+            ))
+            .header(CONTENT_TYPE, DNS_MESSAGE)
+            .header(ACCEPT, DNS_MESSAGE)
+            .body(())
+            .map_err(|e| anyhow!("build request failed: {}", e))?;
+        let (response, mut stream) = send_request.send_request(req, false)?;
+        stream.send_data(Bytes::copy_from_slice(request), true)?;
+        let response = timeout(Duration::from_secs(option::DNS_TIMEOUT), response)
+            .await
+            .map_err(|e| anyhow!("response timeout: {}", e))??;
+        if !response.status().is_success() {
+            return Err(anyhow!("http status {}", response.status()));
+        }
+        let mut body = response.into_body();
+        let mut buf = Vec::new();
+        while let Some(data) = body.data().await {
+            let data = data?;
+            let _ = body.flow_control().release_capacity(data.len());
+            buf.extend_from_slice(&data);
+            if buf.len() > u16::MAX as usize {
+                return Err(anyhow!("dns message too large"));
+            }
+        }
+        Ok(buf)
+    }
+}
@@ -0,0 +1,387 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use bytes::{BufMut, BytesMut};
+use log::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex as TokioMutex;
+use tokio::time::timeout;
+
+use crate::{
+    option,
+    proxy::{tls::stream::wrapper, OutboundHandler, ProxyStream},
+};
+
+use super::{connect_outbound, connect_tcp};
+
+// Idle connections kept for a server, the others are closed once used.
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+/// A DNS-over-TLS (RFC 7858) client, messages are prefixed with 2-byte
+/// lengths, connections are kept alive and reused until idle for too long.
+pub struct Client {
+    host: String,
+    port: u16,
+    idle: TokioMutex<Vec<(Box<dyn ProxyStream>, Instant)>>,
+}
+
+impl Client {
+    pub fn new(host: String, port: u16) -> Self {
+        Client {
+            host,
+            port,
+            idle: TokioMutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes the most recently used connection idle for less than
+    /// `idle_timeout`, the expired ones are closed.
+    pub async fn pooled(&self, idle_timeout: Duration) -> Option<Box<dyn ProxyStream>> {
+        let mut idle = self.idle.lock().await;
+        idle.retain(|(_, last_used)| last_used.elapsed() < idle_timeout);
+        idle.pop().map(|(stream, _)| stream)
+    }
+
+    /// Dials a new connection to the first reachable address.
+    pub async fn connect(
+        &self,
+        ips: &[IpAddr],
+        bind_addr: &SocketAddr,
+        ca_file: Option<&str>,
+    ) -> Result<Box<dyn ProxyStream>> {
+        let mut last_err = None;
+        for ip in ips {
+            let addr = SocketAddr::new(*ip, self.port);
+            match timeout(
+                Duration::from_secs(option::DNS_TIMEOUT),
+                self.dial(&addr, bind_addr, ca_file),
+            )
+            .await
+            {
+                Ok(Ok(stream)) => return Ok(stream),
+                Ok(Err(e)) => last_err = Some(e),
+                Err(e) => last_err = Some(anyhow!("connect {} timeout: {}", &addr, e)),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("no address for {}", &self.host)))
+    }
+
+    /// Dials a new connection through the outbound.
+    pub async fn connect_via(
+        &self,
+        handler: &Arc<dyn OutboundHandler>,
+        ca_file: Option<&str>,
+    ) -> Result<Box<dyn ProxyStream>> {
+        timeout(Duration::from_secs(option::DNS_TIMEOUT), async {
+            let stream = connect_outbound(&self.host, self.port, handler).await?;
+            let stream = wrapper::wrap_tls_with_ca(stream, &self.host, Vec::new(), ca_file).await?;
+            debug!(
+                "connected to dns server {} via [{}]",
+                &self.host,
+                handler.tag()
+            );
+            Ok(stream)
+        })
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "connect {} via [{}] timeout: {}",
+                &self.host,
+                handler.tag(),
+                e
+            )
+        })?
+    }
+
+    async fn dial(
+        &self,
+        addr: &SocketAddr,
+        bind_addr: &SocketAddr,
+        ca_file: Option<&str>,
+    ) -> Result<Box<dyn ProxyStream>> {
+        let stream = connect_tcp(addr, bind_addr).await?;
+        let stream = wrapper::wrap_tls_with_ca(stream, &self.host, Vec::new(), ca_file).await?;
+        debug!("connected to dns server {} at {}", &self.host, addr);
+        Ok(stream)
+    }
+
+    /// Sends the query on the connection, which is put back to the pool once
+    /// the response is read.
+    pub async fn send(&self, mut stream: Box<dyn ProxyStream>, request: &[u8]) -> Result<Vec<u8>> {
+        let mut buf = BytesMut::with_capacity(2 + request.len());
+        buf.put_u16(request.len() as u16);
+        buf.put_slice(request);
+        stream.write_all(&buf).await?;
+        let buf = timeout(Duration::from_secs(option::DNS_TIMEOUT), async {
+            let len = stream.read_u16().await?;
+            let mut buf = vec![0u8; len as usize];
+            stream.read_exact(&mut buf).await?;
+            Ok::<_, std::io::Error>(buf)
+        })
+        .await
+        .map_err(|e| anyhow!("response timeout: {}", e))??;
+        let mut idle = self.idle.lock().await;
+        if idle.len() < MAX_IDLE_CONNECTIONS {
+            idle.push((stream, Instant::now()));
+        }
+        Ok(buf)
+    }
+}
+
+#[cfg(all(test, feature = "rustls-tls"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_rustls::{
+        rustls::{internal::pemfile, NoClientAuth, ServerConfig},
+        TlsAcceptor,
+    };
+    use trust_dns_proto::op::{Message, MessageType, OpCode, ResponseCode};
+    use trust_dns_proto::rr::{dns_class::DNSClass, RData, Record, RecordType};
+
+    use super::super::{DnsClient, DnsServer};
+    use super::*;
+    use crate::proxy::{self, OutboundConnect, ProxyHandlerType, TcpOutboundHandler};
+    use crate::session::Session;
+
+    fn testdata(name: &str) -> String {
+        format!(
+            "{}/src/proxy/tls/testdata/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        )
+    }
+
+    // Serves DoT on the listener with the testdata certificate, queries are
+    // answered by `answer` and connections are closed after `max_queries`.
+    // Returns the number of connections accepted.
+    fn serve(
+        mut listener: TcpListener,
+        max_queries: usize,
+        answer: fn(&[u8]) -> Vec<u8>,
+    ) -> Arc<AtomicUsize> {
+        let mut config = ServerConfig::new(NoClientAuth::new());
+        let certs =
+            pemfile::certs(&mut &std::fs::read(testdata("server.pem")).unwrap()[..]).unwrap();
+        let mut keys =
+            pemfile::pkcs8_private_keys(&mut &std::fs::read(testdata("server.key")).unwrap()[..])
+                .unwrap();
+        config.set_single_cert(certs, keys.remove(0)).unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let accepts = Arc::new(AtomicUsize::new(0));
+        let accepted = accepts.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let mut stream = acceptor.accept(stream).await?;
+                    for _ in 0..max_queries {
+                        let len = stream.read_u16().await?;
+                        let mut buf = vec![0u8; len as usize];
+                        stream.read_exact(&mut buf).await?;
+                        let resp = answer(&buf);
+                        stream.write_u16(resp.len() as u16).await?;
+                        stream.write_all(&resp).await?;
+                        stream.flush().await?;
+                    }
+                    Ok::<_, std::io::Error>(())
+                });
+            }
+        });
+        accepts
+    }
+
+    fn echo(request: &[u8]) -> Vec<u8> {
+        request.iter().rev().cloned().collect()
+    }
+
+    // Answers with 1.2.3.4.
+    fn resolve(request: &[u8]) -> Vec<u8> {
+        let req = Message::from_vec(request).unwrap();
+        let query = req.queries()[0].clone();
+        let mut resp = Message::new();
+        resp.set_id(req.id())
+            .set_message_type(MessageType::Response)
+            .set_op_code(OpCode::Query)
+            .set_response_code(ResponseCode::NoError);
+        resp.add_query(query.clone());
+        let mut ans = Record::new();
+        ans.set_name(query.name().clone())
+            .set_rr_type(RecordType::A)
+            .set_ttl(60)
+            .set_dns_class(DNSClass::IN)
+            .set_rdata(RData::A("1.2.3.4".parse().unwrap()));
+        resp.add_answer(ans);
+        resp.to_vec().unwrap()
+    }
+
+    fn run<F: std::future::Future>(f: F) -> F::Output {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(f)
+    }
+
+    #[test]
+    fn test_pool() {
+        run(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let accepts = serve(listener, usize::MAX, echo);
+
+            let client = Client::new("localhost".to_string(), port);
+            let ips = ["127.0.0.1".parse().unwrap()];
+            let bind_addr = "0.0.0.0:0".parse().unwrap();
+            let ca_file = testdata("ca.pem");
+            let stream = client
+                .connect(&ips, &bind_addr, Some(&ca_file))
+                .await
+                .unwrap();
+            // Messages of both sides are framed with their lengths.
+            let request = vec![7u8; 300];
+            assert_eq!(client.send(stream, &request).await.unwrap(), echo(&request));
+
+            // The connection is reused.
+            let stream = client.pooled(Duration::from_secs(60)).await.unwrap();
+            assert!(client.pooled(Duration::from_secs(60)).await.is_none());
+            assert_eq!(client.send(stream, b"hello").await.unwrap(), b"olleh");
+            assert_eq!(accepts.load(Ordering::SeqCst), 1);
+
+            // And closed once idle for too long.
+            tokio::time::delay_for(Duration::from_millis(100)).await;
+            assert!(client.pooled(Duration::from_millis(50)).await.is_none());
+            assert!(client.pooled(Duration::from_secs(60)).await.is_none());
+        });
+    }
+
+    #[test]
+    fn test_retry() {
+        run(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            // The pooled connections are closed by the server.
+            let accepts = serve(listener, 1, resolve);
+
+            let mut hosts = HashMap::new();
+            hosts.insert("localhost".to_string(), vec!["127.0.0.1".to_string()]);
+            let mut client = DnsClient::new(
+                vec![DnsServer::Tls {
+                    host: "localhost".to_string(),
+                    port,
+                }],
+                hosts,
+                "0.0.0.0:0".parse().unwrap(),
+            );
+            let mut ca_files = HashMap::new();
+            ca_files.insert("localhost".to_string(), testdata("ca.pem"));
+            client.set_server_ca_files(ca_files);
+
+            let expected = vec!["1.2.3.4".parse::<IpAddr>().unwrap()];
+            assert_eq!(
+                client.lookup("a.example.com".to_string()).await.unwrap(),
+                expected
+            );
+            assert_eq!(accepts.load(Ordering::SeqCst), 1);
+
+            // The pooled connection fails, the query is sent again on a new
+            // one.
+            assert_eq!(
+                client.lookup("b.example.com".to_string()).await.unwrap(),
+                expected
+            );
+            assert_eq!(accepts.load(Ordering::SeqCst), 2);
+        });
+    }
+
+    // Connects to the address whatever the destination, which is recorded.
+    struct Forward {
+        addr: SocketAddr,
+        destinations: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl TcpOutboundHandler for Forward {
+        fn name(&self) -> &str {
+            "forward"
+        }
+
+        fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+            None
+        }
+
+        async fn handle_tcp<'a>(
+            &'a self,
+            sess: &'a Session,
+            _stream: Option<Box<dyn ProxyStream>>,
+        ) -> std::io::Result<Box<dyn ProxyStream>> {
+            self.destinations
+                .lock()
+                .unwrap()
+                .push(sess.destination.to_string());
+            let stream = TcpStream::connect(&self.addr).await?;
+            Ok(Box::new(proxy::SimpleProxyStream(stream)))
+        }
+    }
+
+    #[test]
+    fn test_via_outbound() {
+        run(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let accepts = serve(listener, usize::MAX, resolve);
+
+            // There's no way to resolve the server host but the outbound.
+            let server = DnsServer::Tls {
+                host: "localhost".to_string(),
+                port: 853,
+            };
+            let mut client = DnsClient::new(
+                vec![server.clone()],
+                HashMap::new(),
+                "0.0.0.0:0".parse().unwrap(),
+            );
+            let mut ca_files = HashMap::new();
+            ca_files.insert("localhost".to_string(), testdata("ca.pem"));
+            client.set_server_ca_files(ca_files);
+            let mut server_outbounds = HashMap::new();
+            server_outbounds.insert(server, "proxy".to_string());
+            client.set_server_outbounds(server_outbounds);
+
+            let destinations = Arc::new(Mutex::new(Vec::new()));
+            let tcp = Box::new(Forward {
+                addr,
+                destinations: destinations.clone(),
+            });
+            let handler = proxy::outbound::Handler::new(
+                "proxy".to_string(),
+                colored::Color::White,
+                ProxyHandlerType::Endpoint,
+                Some(tcp),
+                None,
+                None,
+            );
+            let mut handlers: HashMap<String, Arc<dyn OutboundHandler>> = HashMap::new();
+            handlers.insert("proxy".to_string(), handler);
+            client.bind_outbounds(&handlers);
+
+            assert_eq!(
+                client.lookup("a.example.com".to_string()).await.unwrap(),
+                vec!["1.2.3.4".parse::<IpAddr>().unwrap()]
+            );
+            assert_eq!(accepts.load(Ordering::SeqCst), 1);
+            assert_eq!(
+                *destinations.lock().unwrap(),
+                vec!["localhost:853".to_string()]
+            );
+        });
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Static IPs of domains, like `/etc/hosts`. A `*.example.com` entry matches
+/// the subdomains of `example.com` but not the domain itself, exact entries
+/// take precedence over wildcards, and longer wildcards over shorter ones.
+#[derive(Clone, Debug, Default)]
+pub struct Hosts {
+    exact: HashMap<String, Vec<IpAddr>>,
+    // suffix of the wildcard entries without the `*.`
+    wildcards: HashMap<String, Vec<IpAddr>>,
+}
+
+impl Hosts {
+    /// Invalid IPs are ignored, domains are matched case-insensitively.
+    pub fn new(hosts: &HashMap<String, Vec<String>>) -> Self {
+        let mut exact = HashMap::new();
+        let mut wildcards = HashMap::new();
+        for (name, static_ips) in hosts.iter() {
+            let ips = static_ips
+                .iter()
+                .filter_map(|ip| ip.parse::<IpAddr>().ok())
+                .collect();
+            let name = name.trim().to_lowercase();
+            if let Some(suffix) = name.strip_prefix("*.") {
+                wildcards.insert(suffix.to_owned(), ips);
+            } else {
+                exact.insert(name, ips);
+            }
+        }
+        Hosts { exact, wildcards }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty() && self.wildcards.is_empty()
+    }
+
+    /// Returns the static IPs of the domain, `None` if there's no entry or
+    /// the entry has no valid IPs.
+    pub fn get(&self, domain: &str) -> Option<&[IpAddr]> {
+        if self.is_empty() {
+            return None;
+        }
+        let domain = domain.trim_end_matches('.').to_lowercase();
+        if let Some(ips) = self.exact.get(&domain) {
+            if !ips.is_empty() {
+                return Some(ips);
+            }
+        }
+        if self.wildcards.is_empty() {
+            return None;
+        }
+        for (i, c) in domain.char_indices() {
+            if c != '.' {
+                continue;
+            }
+            if let Some(ips) = self.wildcards.get(&domain[i + 1..]) {
+                if !ips.is_empty() {
+                    return Some(ips);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hosts() {
+        let mut entries = HashMap::new();
+        entries.insert("example.com".to_string(), vec!["1.1.1.1".to_string()]);
+        entries.insert("*.example.com".to_string(), vec!["2.2.2.2".to_string()]);
+        entries.insert(
+            "*.cdn.example.com".to_string(),
+            vec!["3.3.3.3".to_string(), "::3".to_string()],
+        );
+        entries.insert("ads.example.org".to_string(), vec!["0.0.0.0".to_string()]);
+        entries.insert("bad.example.org".to_string(), vec!["x".to_string()]);
+        let hosts = Hosts::new(&entries);
+
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert_eq!(hosts.get("example.com").unwrap(), &[ip("1.1.1.1")]);
+        assert_eq!(hosts.get("Example.COM.").unwrap(), &[ip("1.1.1.1")]);
+        assert_eq!(hosts.get("www.example.com").unwrap(), &[ip("2.2.2.2")]);
+        assert_eq!(hosts.get("a.b.example.com").unwrap(), &[ip("2.2.2.2")]);
+        assert_eq!(
+            hosts.get("img.cdn.example.com").unwrap(),
+            &[ip("3.3.3.3"), ip("::3")]
+        );
+        assert_eq!(hosts.get("cdn.example.com").unwrap(), &[ip("2.2.2.2")]);
+        assert_eq!(hosts.get("ads.example.org").unwrap(), &[ip("0.0.0.0")]);
+        assert!(hosts.get("bad.example.org").is_none());
+        assert!(hosts.get("xexample.com").is_none());
+        assert!(hosts.get("example.org").is_none());
+    }
+}
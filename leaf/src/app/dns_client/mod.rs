@@ -0,0 +1,2053 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, Instant};
+use std::{error, fmt};
+
+use anyhow::{anyhow, Result};
+use futures::future::select_ok;
+use log::*;
+use lru::LruCache;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+#[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+use socket2::{Domain, Socket, Type};
+#[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+use tokio::net::TcpStream;
+use tokio::sync::Mutex as TokioMutex;
+use tokio::time::timeout;
+use trust_dns_proto::{
+    op::{
+        header::MessageType, op_code::OpCode, query::Query, response_code::ResponseCode, Edns,
+        Message,
+    },
+    rr::{
+        rdata::opt::{EdnsCode, EdnsOption},
+        record_data::RData,
+        record_type::RecordType,
+        Name,
+    },
+};
+
+use crate::{
+    app::router::DomainMatcher,
+    config, option,
+    proxy::{OutboundHandler, ProxyStream, UdpConnector},
+    session::{Session, SocksAddr},
+};
+
+#[cfg(feature = "dns-over-https")]
+mod doh;
+#[cfg(feature = "dns-over-tls")]
+mod dot;
+mod hosts;
+
+pub use hosts::Hosts;
+
+/// A DNS server, either a plain one queried over UDP, a DNS-over-HTTPS server
+/// in the form of `https://dns.google/dns-query`, or a DNS-over-TLS server in
+/// the form of `tls://dns.google`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DnsServer {
+    Udp(SocketAddr),
+    Https {
+        host: String,
+        port: u16,
+        path: String,
+    },
+    Tls {
+        host: String,
+        port: u16,
+    },
+}
+
+impl DnsServer {
+    // Splits `host[:port]`, the host must be a domain since it's needed for
+    // SNI and certificate verification, its IP can be given as the bootstrap.
+    fn parse_authority(s: &str, authority: &str, default_port: u16) -> Result<(String, u16)> {
+        let parts: Vec<&str> = authority.rsplitn(2, ':').collect();
+        let (host, port) = if parts.len() == 2 {
+            let port = parts[0]
+                .parse::<u16>()
+                .map_err(|e| anyhow!("invalid port in dns server {}: {}", s, e))?;
+            (parts[1], port)
+        } else {
+            (authority, default_port)
+        };
+        if host.is_empty() {
+            return Err(anyhow!("missing host in dns server {}", s));
+        }
+        if host.starts_with('[') || host.parse::<IpAddr>().is_ok() {
+            return Err(anyhow!(
+                "dns server {} must be a domain, specify its ip as the bootstrap",
+                s
+            ));
+        }
+        Ok((host.to_string(), port))
+    }
+}
+
+impl FromStr for DnsServer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.starts_with("https://") {
+            let rest = &s["https://".len()..];
+            let (authority, path) = match rest.find('/') {
+                Some(i) => (&rest[..i], &rest[i..]),
+                None => (rest, "/dns-query"),
+            };
+            let (host, port) = Self::parse_authority(s, authority, 443)?;
+            return Ok(DnsServer::Https {
+                host,
+                port,
+                path: path.to_string(),
+            });
+        }
+        if s.starts_with("tls://") {
+            let authority = s["tls://".len()..].trim_end_matches('/');
+            let (host, port) = Self::parse_authority(s, authority, 853)?;
+            return Ok(DnsServer::Tls { host, port });
+        }
+        if let Ok(ip) = s.parse::<IpAddr>() {
+            return Ok(DnsServer::Udp(SocketAddr::new(ip, 53)));
+        }
+        s.parse::<SocketAddr>()
+            .map(DnsServer::Udp)
+            .map_err(|e| anyhow!("invalid dns server {}: {}", s, e))
+    }
+}
+
+impl fmt::Display for DnsServer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DnsServer::Udp(addr) => write!(f, "{}", addr),
+            DnsServer::Https { host, port, path } => {
+                if *port == 443 {
+                    write!(f, "https://{}{}", host, path)
+                } else {
+                    write!(f, "https://{}:{}{}", host, port, path)
+                }
+            }
+            DnsServer::Tls { host, port } => {
+                if *port == 853 {
+                    write!(f, "tls://{}", host)
+                } else {
+                    write!(f, "tls://{}:{}", host, port)
+                }
+            }
+        }
+    }
+}
+
+// Dials a TCP connection to an encrypted server from the bind address. An
+// unspecified bind address of the other family binds the unspecified address
+// of the server's family.
+#[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+async fn connect_tcp(addr: &SocketAddr, bind_addr: &SocketAddr) -> Result<TcpStream> {
+    let bind_addr = match (addr, bind_addr) {
+        (SocketAddr::V6(_), SocketAddr::V4(b)) if b.ip().is_unspecified() => {
+            SocketAddr::new(IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED), b.port())
+        }
+        (SocketAddr::V4(_), SocketAddr::V6(b)) if b.ip().is_unspecified() => {
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), b.port())
+        }
+        (a, b) if a.is_ipv4() != b.is_ipv4() => {
+            return Err(anyhow!("connect {} failed: bind address {}", addr, b));
+        }
+        (_, b) => *b,
+    };
+    let domain = if addr.is_ipv6() {
+        Domain::ipv6()
+    } else {
+        Domain::ipv4()
+    };
+    let socket = Socket::new(domain, Type::stream(), None)?;
+    crate::common::sys::mark_outbound_socket(&socket)?;
+    crate::common::sys::protect_outbound_socket(&socket)?;
+    socket.bind(&bind_addr.into())?;
+    TcpStream::connect_std(socket.into_tcp_stream(), addr)
+        .await
+        .map_err(|e| anyhow!("connect {} failed: {}", addr, e))
+}
+
+// Dials the host of an encrypted server through the outbound, which resolves
+// the host itself.
+#[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+async fn connect_outbound(
+    host: &str,
+    port: u16,
+    handler: &Arc<dyn OutboundHandler>,
+) -> Result<Box<dyn ProxyStream>> {
+    let mut sess = Session::default();
+    sess.destination = SocksAddr::Domain(host.to_owned(), port);
+    handler
+        .handle_tcp(&sess, None)
+        .await
+        .map_err(|e| anyhow!("dial outbound [{}] failed: {}", handler.tag(), e))
+}
+
+// An answer without any addresses is a negative entry. Entries without a
+// deadline never expire, e.g. those from static hosts.
+#[derive(Clone)]
+struct CacheEntry {
+    ips: Vec<IpAddr>,
+    deadline: Option<Instant>,
+    inserted: Instant,
+    hits: u32,
+    prefetching: bool,
+}
+
+impl CacheEntry {
+    fn new(ips: Vec<IpAddr>, ttl: Option<u64>) -> Self {
+        let now = Instant::now();
+        CacheEntry {
+            ips,
+            deadline: ttl.map(|ttl| now + Duration::from_secs(ttl)),
+            inserted: now,
+            hits: 0,
+            prefetching: false,
+        }
+    }
+
+    // Returns whether the answer is to be refreshed in the background.
+    fn should_prefetch(&self, settings: &CacheSettings) -> bool {
+        let deadline = match self.deadline {
+            Some(deadline) => deadline,
+            None => return false,
+        };
+        if settings.prefetch == 0
+            || self.prefetching
+            || self.ips.is_empty()
+            || self.hits < settings.prefetch_min_hits
+        {
+            return false;
+        }
+        let ttl = deadline.duration_since(self.inserted);
+        Instant::now() >= self.inserted + ttl * settings.prefetch / 100
+    }
+
+    fn is_expired(&self) -> bool {
+        match self.deadline {
+            Some(deadline) => Instant::now() >= deadline,
+            None => false,
+        }
+    }
+}
+
+/// Which address families a domain is resolved to. With the `*First`
+/// strategies the other family is looked up if the preferred one has no
+/// usable address, with the `*Only` ones the lookup fails then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DnsStrategy {
+    Ipv4Only,
+    Ipv6Only,
+    Ipv4First,
+    Ipv6First,
+}
+
+impl Default for DnsStrategy {
+    fn default() -> Self {
+        DnsStrategy::Ipv4Only
+    }
+}
+
+impl DnsStrategy {
+    /// Returns `None` for the default, which leaves the choice to the
+    /// global strategy.
+    pub fn from_config(strategy: config::DnsStrategy) -> Option<Self> {
+        match strategy {
+            config::DnsStrategy::DEFAULT => None,
+            config::DnsStrategy::IPV4_ONLY => Some(DnsStrategy::Ipv4Only),
+            config::DnsStrategy::IPV6_ONLY => Some(DnsStrategy::Ipv6Only),
+            config::DnsStrategy::IPV4_FIRST => Some(DnsStrategy::Ipv4First),
+            config::DnsStrategy::IPV6_FIRST => Some(DnsStrategy::Ipv6First),
+        }
+    }
+
+    // The record type to query, and the one to fall back to.
+    fn record_types(&self) -> (RecordType, Option<RecordType>) {
+        match self {
+            DnsStrategy::Ipv4Only => (RecordType::A, None),
+            DnsStrategy::Ipv6Only => (RecordType::AAAA, None),
+            DnsStrategy::Ipv4First => (RecordType::A, Some(RecordType::AAAA)),
+            DnsStrategy::Ipv6First => (RecordType::AAAA, Some(RecordType::A)),
+        }
+    }
+}
+
+impl fmt::Display for DnsStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DnsStrategy::Ipv4Only => write!(f, "ipv4_only"),
+            DnsStrategy::Ipv6Only => write!(f, "ipv6_only"),
+            DnsStrategy::Ipv4First => write!(f, "ipv4_first"),
+            DnsStrategy::Ipv6First => write!(f, "ipv6_first"),
+        }
+    }
+}
+
+/// The EDNS Client Subnet option (RFC 7871) sent in queries, which CDNs
+/// geolocate the answers by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientSubnet {
+    /// No option is sent, the servers may add the subnet they see.
+    PassThrough,
+    /// A /0 subnet is sent, asking the servers not to add one.
+    Off,
+    /// The subnet is sent, e.g. one in the region of the exit node.
+    Subnet(IpAddr, u8),
+}
+
+impl Default for ClientSubnet {
+    fn default() -> Self {
+        ClientSubnet::PassThrough
+    }
+}
+
+impl ClientSubnet {
+    /// Parses an empty string or `passthrough`, `off`, or a subnet in the
+    /// CIDR notation.
+    pub fn from_config(s: &str) -> Option<Self> {
+        match s.trim() {
+            "" | "passthrough" => Some(ClientSubnet::PassThrough),
+            "off" => Some(ClientSubnet::Off),
+            s => config::parse_ip_range(s).map(|(ip, prefix)| ClientSubnet::Subnet(ip, prefix)),
+        }
+    }
+
+    // Returns the data of the option, the address is truncated to the
+    // prefix and the rest bits are zeroed.
+    fn option_data(&self) -> Option<Vec<u8>> {
+        let (family, prefix, octets) = match self {
+            ClientSubnet::PassThrough => return None,
+            ClientSubnet::Off => (1u16, 0, Vec::new()),
+            ClientSubnet::Subnet(IpAddr::V4(ip), prefix) => (1u16, *prefix, ip.octets().to_vec()),
+            ClientSubnet::Subnet(IpAddr::V6(ip), prefix) => (2u16, *prefix, ip.octets().to_vec()),
+        };
+        let len = (prefix as usize + 7) / 8;
+        let mut data = Vec::with_capacity(4 + len);
+        data.extend_from_slice(&family.to_be_bytes());
+        data.push(prefix);
+        // scope prefix, must be 0 in queries
+        data.push(0);
+        data.extend_from_slice(&octets[..len]);
+        if prefix % 8 != 0 {
+            data[3 + len] &= 0xff << (8 - prefix % 8);
+        }
+        Some(data)
+    }
+}
+
+// Tells whether the IP is of the family queried by the record type.
+fn is_record_family(ip: &IpAddr, record_type: RecordType) -> bool {
+    match record_type {
+        RecordType::AAAA => ip.is_ipv6(),
+        _ => ip.is_ipv4(),
+    }
+}
+
+/// A snapshot of an entry in the DNS cache.
+#[derive(Debug)]
+pub struct CacheItem {
+    pub domain: String,
+    /// Empty if the domain is cached as non-existent.
+    pub ips: Vec<IpAddr>,
+    /// Remaining TTL in seconds, `None` if the entry never expires.
+    pub ttl: Option<u64>,
+}
+
+impl CacheItem {
+    pub fn is_negative(&self) -> bool {
+        self.ips.is_empty()
+    }
+}
+
+// The domain doesn't exist or has no records.
+#[derive(Debug)]
+struct NegativeAnswer(String);
+
+impl fmt::Display for NegativeAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl error::Error for NegativeAnswer {}
+
+// Keyed by the domain and the query type.
+type CacheKey = (String, RecordType);
+
+type Cache = Arc<TokioMutex<LruCache<CacheKey, CacheEntry>>>;
+
+/// Settings of the answer cache.
+#[derive(Clone, Copy, Debug)]
+pub struct CacheSettings {
+    /// Maximum number of cached answers, the least recently used ones are
+    /// evicted first.
+    pub size: usize,
+    /// TTLs in answers are clamped to the range, in seconds.
+    pub min_ttl: u64,
+    pub max_ttl: u64,
+    /// Duration in seconds to cache a non-existent domain or an empty answer.
+    pub negative_ttl: u64,
+    /// Percentage of the TTL after which an answer hit `prefetch_min_hits`
+    /// times is refreshed in the background, 0 disables prefetching.
+    pub prefetch: u32,
+    pub prefetch_min_hits: u32,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        CacheSettings {
+            size: option::DNS_CACHE_SIZE,
+            min_ttl: 0,
+            max_ttl: u32::MAX as u64,
+            negative_ttl: option::DNS_NEGATIVE_CACHE_TTL,
+            prefetch: 0,
+            prefetch_min_hits: option::DNS_PREFETCH_MIN_HITS,
+        }
+    }
+}
+
+impl CacheSettings {
+    fn clamp_ttl(&self, ttl: u64) -> u64 {
+        ttl.max(self.min_ttl).min(self.max_ttl)
+    }
+
+    fn new_cache(&self) -> Cache {
+        Arc::new(TokioMutex::new(LruCache::new(self.size)))
+    }
+}
+
+/// Strips unexpected addresses from answers, which are likely results of
+/// DNS poisoning, domains with the internal suffixes are not filtered.
+#[derive(Clone, Default)]
+pub struct AnswerFilter {
+    /// RFC1918, loopback and link-local addresses.
+    pub filter_private: bool,
+    /// Other special-purpose and reserved addresses.
+    pub filter_bogons: bool,
+    pub internal_domains: Vec<String>,
+}
+
+impl AnswerFilter {
+    fn is_enabled(&self) -> bool {
+        self.filter_private || self.filter_bogons
+    }
+
+    fn is_internal(&self, domain: &str) -> bool {
+        self.internal_domains.iter().any(|d| {
+            domain == d.as_str()
+                || (domain.ends_with(d.as_str()) && domain[..domain.len() - d.len()].ends_with('.'))
+        })
+    }
+
+    fn is_private(ip: &IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => ip.is_private() || ip.is_loopback() || ip.is_link_local(),
+            IpAddr::V6(ip) => {
+                let seg0 = ip.segments()[0];
+                ip.is_loopback()
+                    || (seg0 & 0xfe00) == 0xfc00 // unique local
+                    || (seg0 & 0xffc0) == 0xfe80 // link-local
+            }
+        }
+    }
+
+    fn is_bogon(ip: &IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => {
+                let o = ip.octets();
+                o[0] == 0 // this network
+                    || (o[0] == 100 && (o[1] & 0xc0) == 64) // shared address space
+                    || (o[0] == 192 && o[1] == 0 && (o[2] == 0 || o[2] == 2)) // protocol assignments, TEST-NET-1
+                    || (o[0] == 198 && (o[1] & 0xfe) == 18) // benchmarking
+                    || (o[0] == 198 && o[1] == 51 && o[2] == 100) // TEST-NET-2
+                    || (o[0] == 203 && o[1] == 0 && o[2] == 113) // TEST-NET-3
+                    || o[0] >= 224 // multicast, reserved and broadcast
+            }
+            IpAddr::V6(ip) => {
+                let seg0 = ip.segments()[0];
+                ip.is_unspecified()
+                    || ip.is_multicast()
+                    || (seg0 == 0x2001 && ip.segments()[1] == 0xdb8) // documentation
+            }
+        }
+    }
+
+    fn is_filtered(&self, ip: &IpAddr) -> bool {
+        (self.filter_private && Self::is_private(ip)) || (self.filter_bogons && Self::is_bogon(ip))
+    }
+
+    /// Returns the answers survived.
+    pub fn apply(&self, domain: &str, addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+        if !self.is_enabled() || self.is_internal(domain) {
+            return addrs;
+        }
+        addrs
+            .into_iter()
+            .filter(|ip| !self.is_filtered(ip))
+            .collect()
+    }
+}
+
+tokio::task_local! {
+    // Set on the queries sent through outbounds, scoped to the query so it's
+    // gone once the query finishes or is dropped.
+    static VIA_OUTBOUND: ();
+}
+
+// Returns whether the current task is querying a server through an outbound,
+// the lookups made by dialing the outbound are then on this task.
+fn via_outbound() -> bool {
+    VIA_OUTBOUND.try_with(|_| ()).is_ok()
+}
+
+pub struct DnsClient {
+    bind_addr: SocketAddr,
+    servers: Vec<DnsServer>,
+    // named groups of servers, and the policies choosing one by domain
+    groups: HashMap<String, Vec<DnsServer>>,
+    policies: Arc<Vec<(DomainMatcher, String)>>,
+    // group resolving the domains matching no policy, instead of servers
+    group: Option<String>,
+    // clients resolving with the groups, for the outbounds set to
+    group_clients: RwLock<HashMap<String, Arc<DnsClient>>>,
+    hosts: Hosts,
+    // host of an encrypted server -> IP to reach it without plain DNS lookups
+    bootstraps: HashMap<String, IpAddr>,
+    // host of an encrypted server -> PEM file of the additional CAs
+    ca_files: HashMap<String, String>,
+    #[cfg(feature = "dns-over-https")]
+    doh_clients: HashMap<DnsServer, doh::Client>,
+    #[cfg(feature = "dns-over-tls")]
+    dot_clients: HashMap<DnsServer, dot::Client>,
+    dot_idle_timeout: Duration,
+    cache: Cache,
+    cache_settings: CacheSettings,
+    answer_filter: AnswerFilter,
+    // server -> tag of the outbound to reach it
+    server_outbounds: HashMap<DnsServer, String>,
+    // Resolved once the outbounds are loaded, weak references since the
+    // outbounds hold this client.
+    outbound_handlers: RwLock<HashMap<DnsServer, Weak<dyn OutboundHandler>>>,
+    // Serve expired cached answers when all servers fail.
+    fail_open: bool,
+    fallback_ip: Option<IpAddr>,
+    strategy: DnsStrategy,
+    client_subnet: ClientSubnet,
+    // Set once the client is shared, for the prefetches in the background.
+    this: RwLock<Weak<DnsClient>>,
+}
+
+impl Default for DnsClient {
+    fn default() -> Self {
+        let mut servers = Vec::new();
+        servers.push(DnsServer::Udp(SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+            53,
+        )));
+        servers.push(DnsServer::Udp(SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::new(8, 8, 4, 4)),
+            53,
+        )));
+        let bind_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0);
+        let cache_settings = CacheSettings::default();
+        DnsClient {
+            servers,
+            bind_addr,
+            groups: HashMap::new(),
+            policies: Arc::new(Vec::new()),
+            group: None,
+            group_clients: RwLock::new(HashMap::new()),
+            hosts: Hosts::default(),
+            bootstraps: HashMap::new(),
+            ca_files: HashMap::new(),
+            #[cfg(feature = "dns-over-https")]
+            doh_clients: HashMap::new(),
+            #[cfg(feature = "dns-over-tls")]
+            dot_clients: HashMap::new(),
+            dot_idle_timeout: Duration::from_secs(option::DNS_DOT_IDLE_TIMEOUT),
+            cache: cache_settings.new_cache(),
+            cache_settings,
+            answer_filter: AnswerFilter::default(),
+            server_outbounds: HashMap::new(),
+            outbound_handlers: RwLock::new(HashMap::new()),
+            fail_open: false,
+            fallback_ip: None,
+            strategy: DnsStrategy::default(),
+            client_subnet: ClientSubnet::default(),
+            this: RwLock::new(Weak::new()),
+        }
+    }
+}
+
+impl DnsClient {
+    pub fn new(
+        servers: Vec<DnsServer>,
+        hosts: HashMap<String, Vec<String>>,
+        bind_addr: SocketAddr,
+    ) -> Self {
+        let cache_settings = CacheSettings::default();
+        let mut client = DnsClient {
+            servers: Vec::new(),
+            bind_addr,
+            groups: HashMap::new(),
+            policies: Arc::new(Vec::new()),
+            group: None,
+            group_clients: RwLock::new(HashMap::new()),
+            hosts: Hosts::new(&hosts),
+            bootstraps: HashMap::new(),
+            ca_files: HashMap::new(),
+            #[cfg(feature = "dns-over-https")]
+            doh_clients: HashMap::new(),
+            #[cfg(feature = "dns-over-tls")]
+            dot_clients: HashMap::new(),
+            dot_idle_timeout: Duration::from_secs(option::DNS_DOT_IDLE_TIMEOUT),
+            cache: cache_settings.new_cache(),
+            cache_settings,
+            answer_filter: AnswerFilter::default(),
+            server_outbounds: HashMap::new(),
+            outbound_handlers: RwLock::new(HashMap::new()),
+            fail_open: false,
+            fallback_ip: None,
+            strategy: DnsStrategy::default(),
+            client_subnet: ClientSubnet::default(),
+            this: RwLock::new(Weak::new()),
+        };
+        client.add_clients(&servers);
+        client.servers = servers;
+        client
+    }
+
+    // Creates the clients of the encrypted servers.
+    fn add_clients(&mut self, servers: &[DnsServer]) {
+        for server in servers.iter() {
+            match server {
+                #[cfg(feature = "dns-over-https")]
+                DnsServer::Https { host, port, path } => {
+                    self.doh_clients.insert(
+                        server.clone(),
+                        doh::Client::new(host.clone(), *port, path.clone()),
+                    );
+                }
+                #[cfg(feature = "dns-over-tls")]
+                DnsServer::Tls { host, port } => {
+                    self.dot_clients
+                        .insert(server.clone(), dot::Client::new(host.clone(), *port));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Sets the named groups of servers and the policies choosing the group
+    /// to resolve a domain with, policies of unknown groups are ignored.
+    pub fn set_policies(
+        &mut self,
+        groups: HashMap<String, Vec<DnsServer>>,
+        policies: &[config::DNS_Policy],
+    ) {
+        for servers in groups.values() {
+            self.add_clients(servers);
+        }
+        self.policies = Arc::new(
+            policies
+                .iter()
+                .filter_map(|p| {
+                    if !groups.contains_key(&p.group) {
+                        warn!("dns server group {} not found", &p.group);
+                        return None;
+                    }
+                    Some((DomainMatcher::new(&p.domains), p.group.clone()))
+                })
+                .collect(),
+        );
+        self.groups = groups;
+    }
+
+    /// Returns whether a policy chooses the servers to resolve the domain.
+    pub fn has_policy(&self, domain: &str) -> bool {
+        self.policies.iter().any(|(m, _)| m.matches(domain))
+    }
+
+    // Returns the servers of the group chosen by the first policy matching
+    // the domain, or the group of the client, or the default servers.
+    fn servers_for(&self, domain: &str) -> &[DnsServer] {
+        self.policies
+            .iter()
+            .find(|(m, _)| m.matches(domain))
+            .and_then(|(_, group)| self.groups.get(group))
+            .or_else(|| self.group.as_ref().and_then(|g| self.groups.get(g)))
+            .unwrap_or(&self.servers)
+    }
+
+    pub fn set_answer_filter(&mut self, answer_filter: AnswerFilter) {
+        self.answer_filter = answer_filter;
+    }
+
+    /// Sets the answers for lookups failed on all servers, the expired cached
+    /// IPs if `fail_open`, or the fallback IP.
+    pub fn set_fail_open(&mut self, fail_open: bool, fallback_ip: Option<IpAddr>) {
+        self.fail_open = fail_open;
+        self.fallback_ip = fallback_ip;
+    }
+
+    /// Sets the outbounds to reach the servers, by tag.
+    pub fn set_server_outbounds(&mut self, server_outbounds: HashMap<DnsServer, String>) {
+        self.server_outbounds = server_outbounds;
+    }
+
+    /// Sets the IPs of the encrypted server hosts, by host.
+    pub fn set_server_bootstraps(&mut self, bootstraps: HashMap<String, IpAddr>) {
+        self.bootstraps = bootstraps;
+    }
+
+    /// Sets the additional CAs to verify the encrypted servers, by host.
+    pub fn set_server_ca_files(&mut self, ca_files: HashMap<String, String>) {
+        self.ca_files = ca_files;
+    }
+
+    /// Sets how long an idle DoT connection is kept for reuse.
+    pub fn set_dot_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.dot_idle_timeout = idle_timeout;
+    }
+
+    /// Sets the size and the TTLs of the answer cache, the cached answers are
+    /// dropped.
+    pub fn set_cache_settings(&mut self, cache_settings: CacheSettings) {
+        self.cache = cache_settings.new_cache();
+        self.cache_settings = cache_settings;
+    }
+
+    /// Sets the strategy of lookups not given one.
+    pub fn set_strategy(&mut self, strategy: DnsStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// Sets the EDNS Client Subnet option of the queries.
+    pub fn set_client_subnet(&mut self, client_subnet: ClientSubnet) {
+        self.client_subnet = client_subnet;
+    }
+
+    /// Enables prefetching, which refreshes answers with the shared client.
+    pub fn enable_prefetch(self: &Arc<Self>) {
+        *self.this.write().unwrap() = Arc::downgrade(self);
+    }
+
+    // Refreshes the answer in the background, the cached one is still
+    // served until it's replaced. Returns whether the refresh is started.
+    fn prefetch(&self, domain: &str, bind_addr: &SocketAddr, record_type: RecordType) -> bool {
+        let client = match self.this.read().unwrap().upgrade() {
+            Some(client) => client,
+            None => return false,
+        };
+        let domain = domain.to_owned();
+        let bind_addr = *bind_addr;
+        tokio::spawn(async move {
+            debug!("prefetching {} {}", &domain, record_type);
+            if let Err(e) = client
+                .query_record(&domain, &bind_addr, record_type, None)
+                .await
+            {
+                debug!("prefetch {} {} failed: {}", &domain, record_type, e);
+                // The entry is refreshed again by a later hit.
+                if let Some(entry) = client.cache.lock().await.get_mut(&(domain, record_type)) {
+                    entry.prefetching = false;
+                }
+            }
+        });
+        true
+    }
+
+    /// Returns the client resolving the domains matching no policy with the
+    /// group rather than the default servers, with its own cache, for the
+    /// outbounds resolving in the region of their exits. `None` if there's
+    /// no such group.
+    ///
+    /// Hosts of the DoH/DoT servers in the group are still resolved with the
+    /// plain default servers, and lookups made while dialing the outbounds to
+    /// the servers skip them as usual, so none of them recurse.
+    pub fn for_group(self: &Arc<Self>, group: &str) -> Option<Arc<DnsClient>> {
+        if !self.groups.contains_key(group) {
+            return None;
+        }
+        if let Some(client) = self.group_clients.read().unwrap().get(group) {
+            return Some(client.clone());
+        }
+        let mut client = DnsClient {
+            bind_addr: self.bind_addr,
+            servers: self.servers.clone(),
+            groups: self.groups.clone(),
+            policies: self.policies.clone(),
+            group: Some(group.to_owned()),
+            group_clients: RwLock::new(HashMap::new()),
+            hosts: self.hosts.clone(),
+            bootstraps: self.bootstraps.clone(),
+            ca_files: self.ca_files.clone(),
+            #[cfg(feature = "dns-over-https")]
+            doh_clients: HashMap::new(),
+            #[cfg(feature = "dns-over-tls")]
+            dot_clients: HashMap::new(),
+            dot_idle_timeout: self.dot_idle_timeout,
+            cache: self.cache_settings.new_cache(),
+            cache_settings: self.cache_settings,
+            answer_filter: self.answer_filter.clone(),
+            server_outbounds: self.server_outbounds.clone(),
+            outbound_handlers: RwLock::new(self.outbound_handlers.read().unwrap().clone()),
+            fail_open: self.fail_open,
+            fallback_ip: self.fallback_ip,
+            strategy: self.strategy,
+            client_subnet: self.client_subnet,
+            this: RwLock::new(Weak::new()),
+        };
+        client.add_clients(&self.servers);
+        for servers in self.groups.values() {
+            client.add_clients(servers);
+        }
+        let client = Arc::new(client);
+        client.enable_prefetch();
+        self.group_clients
+            .write()
+            .unwrap()
+            .insert(group.to_owned(), client.clone());
+        Some(client)
+    }
+
+    /// Returns the address lookups are sent from by default.
+    pub fn bind_addr(&self) -> &SocketAddr {
+        &self.bind_addr
+    }
+
+    /// Returns the static IPs of domains.
+    pub fn hosts(&self) -> &Hosts {
+        &self.hosts
+    }
+
+    /// Resolves the server outbounds from the loaded handlers.
+    pub fn bind_outbounds(&self, handlers: &HashMap<String, Arc<dyn OutboundHandler>>) {
+        let mut outbound_handlers = self.outbound_handlers.write().unwrap();
+        outbound_handlers.clear();
+        for (server, tag) in self.server_outbounds.iter() {
+            match handlers.get(tag) {
+                Some(h) => {
+                    // Encrypted servers are reached over TCP.
+                    let (supported, network) = match server {
+                        DnsServer::Udp(_) => (h.has_udp(), "udp"),
+                        _ => (h.has_tcp(), "tcp"),
+                    };
+                    if !supported {
+                        warn!(
+                            "outbound [{}] for dns server {} does not support {}",
+                            tag, server, network
+                        );
+                    }
+                    outbound_handlers.insert(server.clone(), Arc::downgrade(h));
+                }
+                None => {
+                    warn!("outbound [{}] for dns server {} not found", tag, server);
+                }
+            }
+        }
+        drop(outbound_handlers);
+        for client in self.group_clients.read().unwrap().values() {
+            client.bind_outbounds(handlers);
+        }
+    }
+
+    /// Updates the cache according to the IP address successfully connected.
+    pub async fn optimize_cache(&self, address: String, connected_ip: IpAddr) {
+        // Nothing to do if the target address is an IP address.
+        if address.parse::<IpAddr>().is_ok() {
+            return;
+        }
+
+        // If the connected IP is not in the first place, we should optimize it.
+        let record_type = if connected_ip.is_ipv6() {
+            RecordType::AAAA
+        } else {
+            RecordType::A
+        };
+        let key = (address, record_type);
+        let mut entry = if let Some(entry) = self.cache.lock().await.get(&key) {
+            if !entry.ips.starts_with(&[connected_ip]) && entry.ips.contains(&connected_ip) {
+                entry.clone()
+            } else {
+                return;
+            }
+        } else {
+            return;
+        };
+        let new_ips = &mut entry.ips;
+
+        // Move failed IPs to the end, the optimized vector starts with the connected IP.
+        if let Ok(idx) = new_ips.binary_search(&connected_ip) {
+            trace!("updates DNS cache item from\n{:#?}", &new_ips);
+            new_ips.rotate_left(idx);
+            trace!("to\n{:#?}", &new_ips);
+            self.cache.lock().await.put(key, entry);
+            trace!("updated cache");
+        }
+    }
+
+    fn handle_response(
+        &self,
+        domain: &str,
+        server: &dyn fmt::Display,
+        buf: &[u8],
+        start: tokio::time::Instant,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        let resp = match Message::from_vec(buf) {
+            Ok(resp) => resp,
+            Err(err) => {
+                return Err(anyhow!("parse message failed: {:?}", err));
+            }
+        };
+        if resp.response_code() == ResponseCode::NXDomain {
+            return Err(anyhow::Error::new(NegativeAnswer(format!(
+                "response error {}",
+                resp.response_code()
+            ))));
+        }
+        if resp.response_code() != ResponseCode::NoError {
+            // TODO Needs more careful investigations, I'm not quite sure about
+            // this.
+            return Err(anyhow!("response error {}", resp.response_code()));
+        }
+        let mut addrs = Vec::new();
+        let mut ttl = u32::MAX;
+        for ans in resp.answers() {
+            // TODO checks?
+            match ans.rdata() {
+                RData::A(addr) => addrs.push(IpAddr::V4(addr.to_owned())),
+                RData::AAAA(addr) => addrs.push(IpAddr::V6(addr.to_owned())),
+                _ => continue,
+            }
+            ttl = ttl.min(ans.ttl());
+        }
+        if !addrs.is_empty() && self.answer_filter.is_enabled() {
+            let n = addrs.len();
+            addrs = self.answer_filter.apply(domain, addrs);
+            if addrs.is_empty() {
+                // Poisoned answers probably, try other servers.
+                debug!("filtered all {} ips for {} from {}", n, domain, server);
+                return Err(anyhow!("all answers filtered"));
+            }
+        }
+        if !addrs.is_empty() {
+            let elapsed = tokio::time::Instant::now().duration_since(start);
+            debug!(
+                "return {} ips for {} from {} in {}ms",
+                addrs.len(),
+                domain,
+                server,
+                elapsed.as_millis(),
+            );
+            trace!("ips for {}:\n{:#?}:", domain, &addrs);
+            Ok((addrs, ttl as u64))
+        } else {
+            // response with 0 records
+            //
+            // TODO Not sure how to due with this.
+            Err(anyhow::Error::new(NegativeAnswer("no records".to_string())))
+        }
+    }
+
+    // Queries the server through an outbound.
+    async fn query_outbound_task(
+        &self,
+        request: Box<[u8]>,
+        domain: &str,
+        server: &SocketAddr,
+        handler: Arc<dyn OutboundHandler>,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        let mut sess = Session::default();
+        sess.destination = SocksAddr::Ip(*server);
+        let dgram = handler
+            .handle_udp(&sess, None)
+            .await
+            .map_err(|e| anyhow!("dial outbound [{}] failed: {}", handler.tag(), e))?;
+        let (mut recv, mut send) = dgram.split();
+        let mut last_err = None;
+        for _i in 0..option::MAX_DNS_RETRIES {
+            debug!(
+                "looking up domain {} on {} via [{}]",
+                domain,
+                server,
+                handler.tag()
+            );
+            let start = tokio::time::Instant::now();
+            match send.send_to(&request, &sess.destination).await {
+                Ok(_) => {
+                    let mut buf = vec![0u8; 512];
+                    match timeout(
+                        Duration::from_secs(option::DNS_TIMEOUT),
+                        recv.recv_from(&mut buf),
+                    )
+                    .await
+                    {
+                        Ok(res) => match res {
+                            Ok((n, _)) => {
+                                return self.handle_response(domain, server, &buf[..n], start);
+                            }
+                            Err(err) => {
+                                last_err = Some(anyhow!("recv failed: {:?}", err));
+                            }
+                        },
+                        Err(e) => {
+                            last_err = Some(anyhow!("recv timeout: {}", e));
+                        }
+                    }
+                }
+                Err(err) => {
+                    last_err = Some(anyhow!("send failed: {:?}", err));
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("could not resolve to any address")))
+    }
+
+    // Queries a DoH server, on the pooled connection if there's one.
+    #[cfg(feature = "dns-over-https")]
+    async fn query_https_task(
+        &self,
+        request: Box<[u8]>,
+        domain: &str,
+        server: &DnsServer,
+        bind_addr: &SocketAddr,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        let (client, host) = match (self.doh_clients.get(server), server) {
+            (Some(client), DnsServer::Https { host, .. }) => (client, host),
+            _ => return Err(anyhow!("no client for dns server {}", server)),
+        };
+        debug!("looking up domain {} on {}", domain, server);
+        let start = tokio::time::Instant::now();
+        let send_request = match client.pooled().await {
+            Some(send_request) => send_request,
+            None => {
+                let ca_file = self.ca_files.get(host).map(String::as_str);
+                match self.server_outbound(server) {
+                    Some(h) => {
+                        VIA_OUTBOUND
+                            .scope((), client.connect_via(&h?, ca_file))
+                            .await?
+                    }
+                    None => {
+                        let ips = self.lookup_server_host(host, bind_addr).await?;
+                        client.connect(&ips, bind_addr, ca_file).await?
+                    }
+                }
+            }
+        };
+        let buf = client.send(send_request, &request).await?;
+        self.handle_response(domain, server, &buf, start)
+    }
+
+    // Queries a DoT server, on an idle connection if there's one, which may
+    // have been closed by the server, the query is then sent on a new one.
+    #[cfg(feature = "dns-over-tls")]
+    async fn query_tls_task(
+        &self,
+        request: Box<[u8]>,
+        domain: &str,
+        server: &DnsServer,
+        bind_addr: &SocketAddr,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        let (client, host) = match (self.dot_clients.get(server), server) {
+            (Some(client), DnsServer::Tls { host, .. }) => (client, host),
+            _ => return Err(anyhow!("no client for dns server {}", server)),
+        };
+        debug!("looking up domain {} on {}", domain, server);
+        let start = tokio::time::Instant::now();
+        if let Some(stream) = client.pooled(self.dot_idle_timeout).await {
+            match client.send(stream, &request).await {
+                Ok(buf) => return self.handle_response(domain, server, &buf, start),
+                Err(e) => debug!("idle connection to {} failed: {}", server, e),
+            }
+        }
+        let ca_file = self.ca_files.get(host).map(String::as_str);
+        let stream = match self.server_outbound(server) {
+            Some(h) => {
+                VIA_OUTBOUND
+                    .scope((), client.connect_via(&h?, ca_file))
+                    .await?
+            }
+            None => {
+                let ips = self.lookup_server_host(host, bind_addr).await?;
+                client.connect(&ips, bind_addr, ca_file).await?
+            }
+        };
+        let buf = client.send(stream, &request).await?;
+        self.handle_response(domain, server, &buf, start)
+    }
+
+    // Resolves the host of an encrypted server, with the bootstrap IP, static
+    // hosts, or the plain servers, encrypted ones are not used to avoid
+    // recursion.
+    #[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+    async fn lookup_server_host(&self, host: &str, bind_addr: &SocketAddr) -> Result<Vec<IpAddr>> {
+        if let Some(ip) = self.bootstraps.get(host) {
+            return Ok(vec![*ip]);
+        }
+        if let Some(ips) = self.hosts.get(host) {
+            return Ok(ips.to_vec());
+        }
+        let servers: Vec<&SocketAddr> = self
+            .servers
+            .iter()
+            .filter_map(|server| match server {
+                DnsServer::Udp(addr) if !self.server_outbounds.contains_key(server) => Some(addr),
+                _ => None,
+            })
+            .collect();
+        if servers.is_empty() {
+            return Err(anyhow!(
+                "no bootstrap ip or plain dns servers to resolve {}",
+                host
+            ));
+        }
+        // Both families are queried, IPv4 addresses are tried first.
+        let query = |record_type| {
+            let servers = &servers;
+            async move {
+                let msg_buf = self.new_query(host, record_type)?;
+                let tasks = servers.iter().map(|addr| {
+                    Box::pin(self.query_udp_task(
+                        msg_buf.clone().into_boxed_slice(),
+                        host,
+                        addr,
+                        bind_addr,
+                    ))
+                });
+                let ((ips, _), _) = select_ok(tasks).await?;
+                Ok::<_, anyhow::Error>(ips)
+            }
+        };
+        match futures::future::join(query(RecordType::A), query(RecordType::AAAA)).await {
+            (Ok(mut ips), Ok(ips6)) => {
+                ips.extend(ips6);
+                Ok(ips)
+            }
+            (Ok(ips), Err(_)) | (Err(_), Ok(ips)) => Ok(ips),
+            (Err(e), Err(_)) => Err(anyhow!("resolve {} failed: {}", host, e)),
+        }
+    }
+
+    async fn query_task(
+        &self,
+        request: Box<[u8]>,
+        domain: &str,
+        server: &DnsServer,
+        bind_addr: &SocketAddr,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        match server {
+            DnsServer::Udp(addr) => self.query_udp_task(request, domain, addr, bind_addr).await,
+            #[cfg(feature = "dns-over-https")]
+            DnsServer::Https { .. } => {
+                self.query_https_task(request, domain, server, bind_addr)
+                    .await
+            }
+            #[cfg(not(feature = "dns-over-https"))]
+            DnsServer::Https { .. } => {
+                Err(anyhow!("dns-over-https is not enabled, skip {}", server))
+            }
+            #[cfg(feature = "dns-over-tls")]
+            DnsServer::Tls { .. } => {
+                self.query_tls_task(request, domain, server, bind_addr)
+                    .await
+            }
+            #[cfg(not(feature = "dns-over-tls"))]
+            DnsServer::Tls { .. } => Err(anyhow!("dns-over-tls is not enabled, skip {}", server)),
+        }
+    }
+
+    async fn query_udp_task(
+        &self,
+        request: Box<[u8]>,
+        domain: &str,
+        server: &SocketAddr,
+        bind_addr: &SocketAddr,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        if let Some(h) = self.server_outbound(&DnsServer::Udp(*server)) {
+            let h = h?;
+            return VIA_OUTBOUND
+                .scope((), self.query_outbound_task(request, domain, server, h))
+                .await;
+        }
+        self.query_direct_udp_task(request, domain, server, bind_addr)
+            .await
+    }
+
+    // Returns the outbound to reach the server, `None` if it's reached
+    // directly, an error if the outbound is set but not loaded.
+    fn server_outbound(&self, server: &DnsServer) -> Option<Result<Arc<dyn OutboundHandler>>> {
+        let tag = self.server_outbounds.get(server)?;
+        let handler = self
+            .outbound_handlers
+            .read()
+            .unwrap()
+            .get(server)
+            .and_then(|h| h.upgrade());
+        Some(handler.ok_or_else(|| anyhow!("outbound [{}] not available", tag)))
+    }
+
+    // Queries the server from the bind address, regardless of the outbound
+    // set for it.
+    async fn query_direct_udp_task(
+        &self,
+        request: Box<[u8]>,
+        domain: &str,
+        server: &SocketAddr,
+        bind_addr: &SocketAddr,
+    ) -> Result<(Vec<IpAddr>, u64)> {
+        let mut socket = self.create_udp_socket(bind_addr).await?;
+        let mut last_err = None;
+        for _i in 0..option::MAX_DNS_RETRIES {
+            debug!("looking up domain {} on {}", domain, server);
+            let start = tokio::time::Instant::now();
+            match socket.send_to(&request, server).await {
+                Ok(_) => {
+                    let mut buf = vec![0u8; 512];
+                    match timeout(
+                        Duration::from_secs(option::DNS_TIMEOUT),
+                        socket.recv_from(&mut buf),
+                    )
+                    .await
+                    {
+                        Ok(res) => match res {
+                            Ok((n, _)) => {
+                                // error responses are not retried
+                                return self.handle_response(domain, server, &buf[..n], start);
+                            }
+                            Err(err) => {
+                                last_err = Some(anyhow!("recv failed: {:?}", err));
+                                // socket recv_from error, retry
+                            }
+                        },
+                        Err(e) => {
+                            last_err = Some(anyhow!("recv timeout: {}", e));
+                            // timeout, retry
+                        }
+                    }
+                }
+                Err(err) => {
+                    last_err = Some(anyhow!("send failed: {:?}", err));
+                    // socket send_to error, retry
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("could not resolve to any address")))
+    }
+
+    // Builds a query of the record type, A or AAAA, for the domain.
+    fn new_query(&self, domain: &str, record_type: RecordType) -> Result<Vec<u8>> {
+        let mut msg = Message::new();
+
+        let mut fqdn = domain.to_owned();
+        fqdn.push('.');
+        let name = match Name::from_str(&fqdn) {
+            Ok(n) => n,
+            Err(e) => return Err(anyhow!("invalid domain name [{}]: {}", domain, e)),
+        };
+        let query = Query::query(name, record_type);
+        msg.add_query(query);
+
+        let mut rng = StdRng::from_entropy();
+        let id: u16 = rng.gen();
+        msg.set_id(id);
+
+        msg.set_op_code(OpCode::Query);
+        msg.set_message_type(MessageType::Query);
+        msg.set_recursion_desired(true);
+
+        if let Some(data) = self.client_subnet.option_data() {
+            let mut edns = Edns::new();
+            // the size of the receive buffers
+            edns.set_max_payload(512);
+            edns.options_mut()
+                .insert(EdnsOption::Unknown(u16::from(EdnsCode::Subnet), data));
+            msg.set_edns(edns);
+        }
+
+        match msg.to_vec() {
+            Ok(b) => Ok(b),
+            Err(e) => Err(anyhow!("encode message to buffer failed: {}", e)),
+        }
+    }
+
+    pub async fn lookup(&self, domain: String) -> Result<Vec<IpAddr>> {
+        self.lookup_with_bind(domain, &self.bind_addr).await
+    }
+
+    pub async fn lookup_with_bind(
+        &self,
+        domain: String,
+        bind_addr: &SocketAddr,
+    ) -> Result<Vec<IpAddr>> {
+        self.lookup_with_strategy(domain, bind_addr, None).await
+    }
+
+    /// Looks up the domain with the strategy, or the global one if `None`.
+    pub async fn lookup_with_strategy(
+        &self,
+        domain: String,
+        bind_addr: &SocketAddr,
+        strategy: Option<DnsStrategy>,
+    ) -> Result<Vec<IpAddr>> {
+        if let Ok(ip) = domain.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+
+        let strategy = strategy.unwrap_or(self.strategy);
+        let (record_type, fallback_type) = strategy.record_types();
+        match self.lookup_record(&domain, bind_addr, record_type).await {
+            Ok(ips) => Ok(ips),
+            Err(e) => match fallback_type {
+                Some(fallback_type) => {
+                    debug!(
+                        "lookup {} {} failed, falls back to {}: {}",
+                        &domain, record_type, fallback_type, e
+                    );
+                    self.lookup_record(&domain, bind_addr, fallback_type).await
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    // Looks up the records of the type, A or AAAA.
+    async fn lookup_record(
+        &self,
+        domain: &str,
+        bind_addr: &SocketAddr,
+        record_type: RecordType,
+    ) -> Result<Vec<IpAddr>> {
+        let domain = domain.to_owned();
+        let key = (domain.clone(), record_type);
+
+        // Expired IPs kept for failing open.
+        let mut stale_ips = None;
+        {
+            let mut cache = self.cache.lock().await;
+            if let Some(entry) = cache.get_mut(&key) {
+                if !entry.is_expired() {
+                    if entry.ips.is_empty() {
+                        return Err(anyhow!("{} is cached as non-existent", &domain));
+                    }
+                    entry.hits += 1;
+                    if entry.should_prefetch(&self.cache_settings) {
+                        entry.prefetching = self.prefetch(&domain, bind_addr, record_type);
+                    }
+                    return Ok(entry.ips.to_vec());
+                }
+                if self.fail_open && !entry.ips.is_empty() {
+                    stale_ips = Some(entry.ips.to_vec());
+                } else {
+                    cache.pop(&key);
+                }
+            }
+        }
+
+        // Making cache lookup a priority rather than static hosts lookup
+        // and insert the static IPs to the cache because there's a chance
+        // for the IPs in the cache to be re-ordered.
+        if let Some(ips) = self.hosts.get(&domain) {
+            // A domain in the hosts is not looked up for the missing family.
+            let ips: Vec<IpAddr> = ips
+                .iter()
+                .filter(|ip| is_record_family(ip, record_type))
+                .cloned()
+                .collect();
+            if ips.is_empty() {
+                return Err(anyhow!(
+                    "no {} record for {} in hosts",
+                    record_type,
+                    &domain
+                ));
+            }
+            if ips.len() > 1 {
+                self.cache
+                    .lock()
+                    .await
+                    .put(key, CacheEntry::new(ips.clone(), None));
+            }
+            return Ok(ips);
+        }
+
+        self.query_record(&domain, bind_addr, record_type, stale_ips)
+            .await
+    }
+
+    // Queries the servers for the records and caches the answer, the stale
+    // IPs are answered if all servers fail.
+    async fn query_record(
+        &self,
+        domain: &str,
+        bind_addr: &SocketAddr,
+        record_type: RecordType,
+        stale_ips: Option<Vec<IpAddr>>,
+    ) -> Result<Vec<IpAddr>> {
+        let domain = domain.to_owned();
+        let key = (domain.clone(), record_type);
+        let msg_buf = self.new_query(&domain, record_type)?;
+
+        // Lookups made while querying through the outbounds, e.g. for the
+        // hosts of the proxy servers, skip the servers behind outbounds, or
+        // they would recurse.
+        let via_outbounds = !self.server_outbounds.is_empty() && !via_outbound();
+
+        let servers = self.servers_for(&domain);
+        let mut tasks = Vec::new();
+        for server in servers {
+            if !via_outbounds && self.server_outbounds.contains_key(server) {
+                continue;
+            }
+            let t = self.query_task(
+                msg_buf.clone().into_boxed_slice(),
+                &domain,
+                &server,
+                bind_addr,
+            );
+            tasks.push(Box::pin(t));
+        }
+        let res = if !tasks.is_empty() {
+            select_ok(tasks.into_iter()).await.map(|(v, _)| v)
+        } else if !via_outbounds {
+            // All the servers are behind outbounds, the lookups made while
+            // dialing them, e.g. for the hosts of the proxy servers, are sent
+            // directly rather than failing.
+            let mut tasks = Vec::new();
+            for server in servers {
+                if let DnsServer::Udp(addr) = server {
+                    debug!(
+                        "looking up {} directly for the outbound of {}",
+                        &domain, addr
+                    );
+                    let t = self.query_direct_udp_task(
+                        msg_buf.clone().into_boxed_slice(),
+                        &domain,
+                        addr,
+                        bind_addr,
+                    );
+                    tasks.push(Box::pin(t));
+                }
+            }
+            if tasks.is_empty() {
+                return Err(anyhow!("no dns servers available for {}", &domain));
+            }
+            select_ok(tasks.into_iter()).await.map(|(v, _)| v)
+        } else {
+            return Err(anyhow!("no dns servers available for {}", &domain));
+        };
+        match res {
+            Ok((ips, ttl)) => {
+                let ttl = self.cache_settings.clamp_ttl(ttl);
+                self.cache
+                    .lock()
+                    .await
+                    .put(key, CacheEntry::new(ips.clone(), Some(ttl)));
+                Ok(ips)
+            }
+            Err(e) => {
+                if e.downcast_ref::<NegativeAnswer>().is_some() {
+                    self.cache.lock().await.put(
+                        key,
+                        CacheEntry::new(Vec::new(), Some(self.cache_settings.negative_ttl)),
+                    );
+                    return Err(anyhow!("all dns servers failed, last error: {}", e));
+                }
+                if let Some(ips) = stale_ips {
+                    warn!(
+                        "all dns servers failed for {}, answered with expired ips: {}",
+                        &domain, e
+                    );
+                    return Ok(ips);
+                }
+                if let Some(ip) = self
+                    .fallback_ip
+                    .filter(|ip| is_record_family(ip, record_type))
+                {
+                    warn!(
+                        "all dns servers failed for {}, answered with fallback ip {}: {}",
+                        &domain, ip, e
+                    );
+                    return Ok(vec![ip]);
+                }
+                Err(anyhow!("all dns servers failed, last error: {}", e))
+            }
+        }
+    }
+
+    /// Returns the unexpired entries in the cache, most recently used first.
+    pub async fn cache_snapshot(&self) -> Vec<CacheItem> {
+        let now = Instant::now();
+        self.cache
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, entry)| !entry.is_expired())
+            .map(|((domain, _), entry)| CacheItem {
+                domain: domain.to_owned(),
+                ips: entry.ips.clone(),
+                ttl: entry
+                    .deadline
+                    .map(|deadline| deadline.duration_since(now).as_secs()),
+            })
+            .collect()
+    }
+
+    /// Flushes the cache, and the ones of the group clients.
+    pub async fn flush_cache(&self) {
+        self.cache.lock().await.clear();
+        let group_clients: Vec<Arc<DnsClient>> = self
+            .group_clients
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        for client in group_clients {
+            client.cache.lock().await.clear();
+        }
+        debug!("dns cache flushed");
+    }
+}
+
+impl UdpConnector for DnsClient {}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+    use trust_dns_proto::rr::{dns_class::DNSClass, Record};
+
+    use super::*;
+    use crate::proxy::{
+        self, OutboundConnect, OutboundDatagram, OutboundTransport, ProxyHandlerType,
+        UdpOutboundHandler, UdpTransportType,
+    };
+
+    #[test]
+    fn test_answer_filter() {
+        let addrs: Vec<IpAddr> = vec![
+            "127.0.0.1".parse().unwrap(),
+            "192.168.1.1".parse().unwrap(),
+            "0.0.0.0".parse().unwrap(),
+            "240.0.0.1".parse().unwrap(),
+            "1.2.3.4".parse().unwrap(),
+        ];
+
+        let filter = AnswerFilter {
+            filter_private: true,
+            ..Default::default()
+        };
+        assert_eq!(filter.apply("example.com", addrs.clone()).len(), 3);
+
+        let filter = AnswerFilter {
+            filter_bogons: true,
+            ..Default::default()
+        };
+        assert_eq!(filter.apply("example.com", addrs.clone()).len(), 3);
+
+        let filter = AnswerFilter {
+            filter_private: true,
+            filter_bogons: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.apply("example.com", addrs.clone()),
+            vec!["1.2.3.4".parse::<IpAddr>().unwrap()]
+        );
+
+        let filter = AnswerFilter::default();
+        assert_eq!(filter.apply("example.com", addrs.clone()).len(), 5);
+    }
+
+    #[test]
+    fn test_parse_dns_server() {
+        assert_eq!(
+            "1.1.1.1".parse::<DnsServer>().unwrap(),
+            DnsServer::Udp("1.1.1.1:53".parse().unwrap())
+        );
+        assert_eq!(
+            "127.0.0.1:5353".parse::<DnsServer>().unwrap(),
+            DnsServer::Udp("127.0.0.1:5353".parse().unwrap())
+        );
+
+        let server = "https://dns.google/dns-query".parse::<DnsServer>().unwrap();
+        assert_eq!(
+            server,
+            DnsServer::Https {
+                host: "dns.google".to_string(),
+                port: 443,
+                path: "/dns-query".to_string(),
+            }
+        );
+        assert_eq!(server.to_string(), "https://dns.google/dns-query");
+
+        let server = "https://doh.example.com:8443".parse::<DnsServer>().unwrap();
+        assert_eq!(server.to_string(), "https://doh.example.com:8443/dns-query");
+
+        assert!("https://1.1.1.1/dns-query".parse::<DnsServer>().is_err());
+
+        let server = "tls://dns.google".parse::<DnsServer>().unwrap();
+        assert_eq!(
+            server,
+            DnsServer::Tls {
+                host: "dns.google".to_string(),
+                port: 853,
+            }
+        );
+        assert_eq!(server.to_string(), "tls://dns.google");
+        assert_eq!(
+            "tls://dot.example.com:8853"
+                .parse::<DnsServer>()
+                .unwrap()
+                .to_string(),
+            "tls://dot.example.com:8853"
+        );
+        assert!("tls://1.1.1.1".parse::<DnsServer>().is_err());
+        assert!("https://dns.google:x/dns-query"
+            .parse::<DnsServer>()
+            .is_err());
+        assert!("dns.google".parse::<DnsServer>().is_err());
+    }
+
+    #[test]
+    fn test_cache() {
+        let mut client = DnsClient::default();
+        client.set_cache_settings(CacheSettings {
+            size: 1,
+            min_ttl: 60,
+            max_ttl: 300,
+            negative_ttl: 10,
+            ..Default::default()
+        });
+        assert_eq!(client.cache_settings.clamp_ttl(1), 60);
+        assert_eq!(client.cache_settings.clamp_ttl(120), 120);
+        assert_eq!(client.cache_settings.clamp_ttl(3600), 300);
+
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let ip: IpAddr = "1.2.3.4".parse().unwrap();
+            client.cache.lock().await.put(
+                ("example.com".to_string(), RecordType::A),
+                CacheEntry::new(vec![ip], Some(60)),
+            );
+            assert_eq!(
+                client.lookup("example.com".to_string()).await.unwrap(),
+                vec![ip]
+            );
+
+            // Evicts the previous answer.
+            client.cache.lock().await.put(
+                ("nx.example.com".to_string(), RecordType::A),
+                CacheEntry::new(Vec::new(), Some(10)),
+            );
+            assert!(client.lookup("nx.example.com".to_string()).await.is_err());
+            let items = client.cache_snapshot().await;
+            assert_eq!(items.len(), 1);
+            assert!(items[0].is_negative());
+            assert_eq!(items[0].domain, "nx.example.com");
+
+            client.flush_cache().await;
+            assert!(client.cache_snapshot().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_prefetch() {
+        let mut settings = CacheSettings {
+            prefetch: 50,
+            prefetch_min_hits: 2,
+            ..Default::default()
+        };
+        let mut entry = CacheEntry::new(vec!["1.2.3.4".parse().unwrap()], Some(10));
+        entry.hits = 2;
+        assert!(!entry.should_prefetch(&settings));
+
+        // 6 of the 10 seconds passed
+        entry.inserted -= Duration::from_secs(6);
+        entry.deadline = Some(entry.inserted + Duration::from_secs(10));
+        assert!(entry.should_prefetch(&settings));
+        entry.hits = 1;
+        assert!(!entry.should_prefetch(&settings));
+        entry.hits = 2;
+        entry.prefetching = true;
+        assert!(!entry.should_prefetch(&settings));
+        entry.prefetching = false;
+        settings.prefetch = 0;
+        assert!(!entry.should_prefetch(&settings));
+
+        // answers from hosts never expire
+        let mut entry = CacheEntry::new(vec!["1.2.3.4".parse().unwrap()], None);
+        entry.hits = 2;
+        settings.prefetch = 50;
+        assert!(!entry.should_prefetch(&settings));
+    }
+
+    #[test]
+    fn test_client_subnet() {
+        assert_eq!(
+            ClientSubnet::from_config(""),
+            Some(ClientSubnet::PassThrough)
+        );
+        assert_eq!(ClientSubnet::PassThrough.option_data(), None);
+        assert_eq!(
+            ClientSubnet::from_config("off").unwrap().option_data(),
+            Some(vec![0, 1, 0, 0])
+        );
+        assert_eq!(
+            ClientSubnet::from_config("1.2.3.4/24")
+                .unwrap()
+                .option_data(),
+            Some(vec![0, 1, 24, 0, 1, 2, 3])
+        );
+        assert_eq!(
+            ClientSubnet::from_config("1.2.255.4/20")
+                .unwrap()
+                .option_data(),
+            Some(vec![0, 1, 20, 0, 1, 2, 0xf0])
+        );
+        assert_eq!(
+            ClientSubnet::from_config("2001:db8::/32")
+                .unwrap()
+                .option_data(),
+            Some(vec![0, 2, 32, 0, 0x20, 0x01, 0x0d, 0xb8])
+        );
+        assert_eq!(ClientSubnet::from_config("1.2.3.4"), None);
+        assert_eq!(ClientSubnet::from_config("on"), None);
+    }
+
+    #[test]
+    fn test_strategy() {
+        let mut hosts = HashMap::new();
+        hosts.insert("v4.example.com".to_string(), vec!["1.2.3.4".to_string()]);
+        hosts.insert(
+            "dual.example.com".to_string(),
+            vec!["1.2.3.4".to_string(), "::1".to_string()],
+        );
+        let client = DnsClient::new(Vec::new(), hosts, "0.0.0.0:0".parse().unwrap());
+
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let bind_addr = client.bind_addr;
+            let lookup = |domain: &str, strategy| {
+                client.lookup_with_strategy(domain.to_string(), &bind_addr, strategy)
+            };
+            let v4: IpAddr = "1.2.3.4".parse().unwrap();
+            let v6: IpAddr = "::1".parse().unwrap();
+            assert_eq!(lookup("dual.example.com", None).await.unwrap(), vec![v4]);
+            assert_eq!(
+                lookup("dual.example.com", Some(DnsStrategy::Ipv6First))
+                    .await
+                    .unwrap(),
+                vec![v6]
+            );
+            assert_eq!(
+                lookup("v4.example.com", Some(DnsStrategy::Ipv6First))
+                    .await
+                    .unwrap(),
+                vec![v4]
+            );
+            assert!(lookup("v4.example.com", Some(DnsStrategy::Ipv6Only))
+                .await
+                .is_err());
+        });
+    }
+
+    #[test]
+    fn test_answer_filter_internal_domains() {
+        let addrs: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap()];
+        let filter = AnswerFilter {
+            filter_private: true,
+            filter_bogons: true,
+            internal_domains: vec!["corp.example.com".to_string()],
+        };
+        assert_eq!(filter.apply("corp.example.com", addrs.clone()).len(), 1);
+        assert_eq!(filter.apply("git.corp.example.com", addrs.clone()).len(), 1);
+        assert!(filter.apply("xcorp.example.com", addrs.clone()).is_empty());
+        assert!(filter.apply("example.com", addrs.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_policies() {
+        let public: DnsServer = "8.8.8.8".parse().unwrap();
+        let internal: DnsServer = "10.0.0.53".parse().unwrap();
+        let mut client = DnsClient::new(
+            vec![public.clone()],
+            HashMap::new(),
+            "0.0.0.0:0".parse().unwrap(),
+        );
+        let mut groups = HashMap::new();
+        groups.insert("internal".to_string(), vec![internal.clone()]);
+        let mut domain = config::RoutingRule_Domain::new();
+        domain.field_type = config::RoutingRule_Domain_Type::DOMAIN;
+        domain.value = "corp.example.com".to_string();
+        let mut policy = config::DNS_Policy::new();
+        policy.domains.push(domain.clone());
+        policy.group = "internal".to_string();
+        let mut unknown = config::DNS_Policy::new();
+        unknown.domains.push(domain);
+        unknown.group = "unknown".to_string();
+        client.set_policies(groups, &[unknown, policy]);
+
+        assert!(client.has_policy("git.corp.example.com"));
+        assert_eq!(client.servers_for("git.corp.example.com"), &[internal]);
+        assert!(!client.has_policy("example.com"));
+        assert_eq!(client.servers_for("example.com"), &[public]);
+    }
+
+    #[test]
+    fn test_group_client() {
+        let public: DnsServer = "8.8.8.8".parse().unwrap();
+        let internal: DnsServer = "10.0.0.53".parse().unwrap();
+        let remote: DnsServer = "1.1.1.1".parse().unwrap();
+        let mut client = DnsClient::new(
+            vec![public.clone()],
+            HashMap::new(),
+            "0.0.0.0:0".parse().unwrap(),
+        );
+        let mut groups = HashMap::new();
+        groups.insert("internal".to_string(), vec![internal.clone()]);
+        groups.insert("remote".to_string(), vec![remote.clone()]);
+        let mut domain = config::RoutingRule_Domain::new();
+        domain.field_type = config::RoutingRule_Domain_Type::DOMAIN;
+        domain.value = "corp.example.com".to_string();
+        let mut policy = config::DNS_Policy::new();
+        policy.domains.push(domain);
+        policy.group = "internal".to_string();
+        client.set_policies(groups, &[policy]);
+        let client = Arc::new(client);
+
+        assert!(client.for_group("unknown").is_none());
+        let remote_client = client.for_group("remote").unwrap();
+        assert!(Arc::ptr_eq(
+            &remote_client,
+            &client.for_group("remote").unwrap()
+        ));
+        // The policies still apply.
+        assert_eq!(
+            remote_client.servers_for("git.corp.example.com"),
+            &[internal]
+        );
+        assert_eq!(remote_client.servers_for("example.com"), &[remote]);
+        assert_eq!(client.servers_for("example.com"), &[public]);
+    }
+
+    // Resolves the host of its proxy server when dialed as the proxy outbounds
+    // do, then fails or hangs.
+    struct ResolvingHandler {
+        dns_client: Arc<DnsClient>,
+        dials: Arc<AtomicUsize>,
+        hang: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl UdpOutboundHandler for ResolvingHandler {
+        fn name(&self) -> &str {
+            "resolving"
+        }
+
+        fn udp_connect_addr(&self) -> Option<OutboundConnect> {
+            None
+        }
+
+        fn udp_transport_type(&self) -> UdpTransportType {
+            UdpTransportType::Unknown
+        }
+
+        async fn handle_udp<'a>(
+            &'a self,
+            _sess: &'a Session,
+            _transport: Option<OutboundTransport>,
+        ) -> io::Result<Box<dyn OutboundDatagram>> {
+            self.dials.fetch_add(1, Ordering::SeqCst);
+            self.dns_client
+                .lookup("proxy.example.com".to_string())
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            if self.hang.load(Ordering::SeqCst) {
+                futures::future::pending::<()>().await;
+            }
+            Err(io::Error::new(io::ErrorKind::Other, "no route"))
+        }
+    }
+
+    // Answers every query with 1.2.3.4, or 2001:db8::1 for AAAA, the names
+    // queried are recorded.
+    async fn serve_dns(mut socket: UdpSocket, queried: Arc<Mutex<Vec<String>>>) {
+        let mut buf = vec![0u8; 512];
+        while let Ok((n, src)) = socket.recv_from(&mut buf).await {
+            let req = Message::from_vec(&buf[..n]).unwrap();
+            let query = req.queries()[0].clone();
+            queried.lock().unwrap().push(query.name().to_string());
+            let mut resp = Message::new();
+            resp.set_id(req.id())
+                .set_message_type(MessageType::Response)
+                .set_op_code(OpCode::Query)
+                .set_response_code(ResponseCode::NoError);
+            resp.add_query(query.clone());
+            let rdata = if query.query_type() == RecordType::AAAA {
+                RData::AAAA("2001:db8::1".parse().unwrap())
+            } else {
+                RData::A("1.2.3.4".parse().unwrap())
+            };
+            let mut ans = Record::new();
+            ans.set_name(query.name().clone())
+                .set_rr_type(rdata.to_record_type())
+                .set_ttl(60)
+                .set_dns_class(DNSClass::IN)
+                .set_rdata(rdata);
+            resp.add_answer(ans);
+            let _ = socket.send_to(&resp.to_vec().unwrap(), &src).await;
+        }
+    }
+
+    #[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+    #[test]
+    fn test_lookup_server_host() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let server = socket.local_addr().unwrap();
+            tokio::spawn(serve_dns(socket, Arc::new(Mutex::new(Vec::new()))));
+
+            let bind_addr = "0.0.0.0:0".parse().unwrap();
+            let client = DnsClient::new(vec![DnsServer::Udp(server)], HashMap::new(), bind_addr);
+            let ips = client
+                .lookup_server_host("dns.example.com", &bind_addr)
+                .await
+                .unwrap();
+            assert_eq!(
+                ips,
+                vec![
+                    "1.2.3.4".parse::<IpAddr>().unwrap(),
+                    "2001:db8::1".parse().unwrap()
+                ]
+            );
+        });
+    }
+
+    #[cfg(any(feature = "dns-over-https", feature = "dns-over-tls"))]
+    #[test]
+    fn test_connect_tcp_ipv6() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let mut listener = tokio::net::TcpListener::bind("[::1]:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                let _ = listener.accept().await;
+            });
+            // The default bind address is the IPv4 wildcard.
+            let stream = connect_tcp(&addr, &"0.0.0.0:0".parse().unwrap())
+                .await
+                .unwrap();
+            assert_eq!(stream.peer_addr().unwrap(), addr);
+            assert!(connect_tcp(&addr, &"127.0.0.1:0".parse().unwrap())
+                .await
+                .is_err());
+        });
+    }
+
+    #[test]
+    fn test_outbound_lookups() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let server = socket.local_addr().unwrap();
+            let queried = Arc::new(Mutex::new(Vec::new()));
+            tokio::spawn(serve_dns(socket, queried.clone()));
+
+            let mut client = DnsClient::new(
+                vec![DnsServer::Udp(server)],
+                HashMap::new(),
+                "0.0.0.0:0".parse().unwrap(),
+            );
+            let mut server_outbounds = HashMap::new();
+            server_outbounds.insert(DnsServer::Udp(server), "proxy".to_string());
+            client.set_server_outbounds(server_outbounds);
+            let client = Arc::new(client);
+            let dials = Arc::new(AtomicUsize::new(0));
+            let hang = Arc::new(AtomicBool::new(false));
+            let udp = Box::new(ResolvingHandler {
+                dns_client: client.clone(),
+                dials: dials.clone(),
+                hang: hang.clone(),
+            });
+            let handler = proxy::outbound::Handler::new(
+                "proxy".to_string(),
+                colored::Color::White,
+                ProxyHandlerType::Endpoint,
+                None,
+                Some(udp),
+                None,
+            );
+            let mut handlers: HashMap<String, Arc<dyn OutboundHandler>> = HashMap::new();
+            handlers.insert("proxy".to_string(), handler);
+            client.bind_outbounds(&handlers);
+
+            // Concurrent lookups of a domain both go through the outbound,
+            // only the host of the proxy server is looked up directly.
+            let (a, b) = futures::future::join(
+                client.lookup("a.example.com".to_string()),
+                client.lookup("a.example.com".to_string()),
+            )
+            .await;
+            assert!(a.is_err() && b.is_err());
+            assert!(dials.load(Ordering::SeqCst) >= 2);
+            assert_eq!(
+                client
+                    .lookup("proxy.example.com".to_string())
+                    .await
+                    .unwrap(),
+                vec!["1.2.3.4".parse::<IpAddr>().unwrap()]
+            );
+
+            // A lookup dropped while dialing leaves nothing behind.
+            hang.store(true, Ordering::SeqCst);
+            let dialed = dials.load(Ordering::SeqCst);
+            assert!(timeout(
+                Duration::from_millis(200),
+                client.lookup("b.example.com".to_string())
+            )
+            .await
+            .is_err());
+            assert!(dials.load(Ordering::SeqCst) > dialed);
+            hang.store(false, Ordering::SeqCst);
+            assert!(!via_outbound());
+            assert!(client.lookup("b.example.com".to_string()).await.is_err());
+
+            assert!(queried
+                .lock()
+                .unwrap()
+                .iter()
+                .all(|name| name == "proxy.example.com."));
+        });
+    }
+}
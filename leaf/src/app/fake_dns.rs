@@ -1,5 +1,9 @@
 use std::collections::HashMap;
-use std::net::{IpAddr, Ipv4Addr};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Result};
 use byteorder::{BigEndian, ByteOrder};
@@ -11,73 +15,319 @@ use trust_dns_proto::rr::{
     dns_class::DNSClass, record_data::RData, record_type::RecordType, resource::Record,
 };
 
+use crate::option;
+
+use super::dispatcher::Dispatcher;
+use super::dns_client::{DnsClient, DnsStrategy, Hosts};
+
+// TTL of the real answers to queries resolved by DNS policies, they're
+// cached by the DNS client so they're kept as short as the fake ones.
+const POLICY_ANSWER_TTL: u32 = 1;
+
 pub enum FakeDnsMode {
     Include,
     Exclude,
 }
 
-pub struct FakeDns {
-    ip_to_domain: HashMap<u32, String>,
-    domain_to_ip: HashMap<String, u32>,
-    cursor: u32,
-    min_cursor: u32,
-    max_cursor: u32,
-    ttl: u32,
-    filters: Vec<String>,
-    mode: FakeDnsMode,
+// A range of fake IPs of one family, allocated sequentially and never reused
+// while the process runs, IPs are kept as u128 for both families.
+struct Pool {
+    ipv6: bool,
+    ip_to_domain: HashMap<u128, String>,
+    domain_to_ip: HashMap<String, u128>,
+    // IP -> unix time it was last allocated or looked up
+    last_used: HashMap<u128, u64>,
+    cursor: u128,
+    min_cursor: u128,
+    max_cursor: u128,
 }
 
-impl FakeDns {
-    pub fn new(mode: FakeDnsMode) -> Self {
-        let min_cursor = Self::ip_to_u32(&Ipv4Addr::new(240, 255, 0, 0));
-        let max_cursor = Self::ip_to_u32(&Ipv4Addr::new(240, 255, 4, 255));
-
-        FakeDns {
+impl Pool {
+    fn new(ipv6: bool, min_cursor: u128, max_cursor: u128) -> Self {
+        Pool {
+            ipv6,
             ip_to_domain: HashMap::new(),
             domain_to_ip: HashMap::new(),
+            last_used: HashMap::new(),
             cursor: min_cursor,
             min_cursor,
             max_cursor,
+        }
+    }
+
+    // Takes the hosts of the range, the network and broadcast addresses of an
+    // IPv4 range and the subnet-router anycast address of an IPv6 range are
+    // skipped, at most `option::FAKE_DNS_IPV6_POOL_SIZE` IPv6 addresses are
+    // used.
+    fn from_range(range: &str, ipv6: bool) -> Result<Self> {
+        let (addr, host_bits) = match crate::config::parse_ip_range(range) {
+            Some((IpAddr::V4(addr), prefix)) if !ipv6 => {
+                (FakeDns::ip_to_u32(&addr) as u128, 32 - prefix as u32)
+            }
+            Some((IpAddr::V6(addr), prefix)) if ipv6 => (u128::from(addr), 128 - prefix as u32),
+            _ => return Err(anyhow!("invalid fake dns range {}", range)),
+        };
+        if host_bits < 2 {
+            return Err(anyhow!("fake dns range {} too small", range));
+        }
+        let network = if host_bits == 128 {
+            0
+        } else {
+            addr >> host_bits << host_bits
+        };
+        let max_cursor = if !ipv6 {
+            network + (1u128 << host_bits) - 2
+        } else if host_bits >= 64 {
+            network + option::FAKE_DNS_IPV6_POOL_SIZE as u128 - 1
+        } else {
+            network + (1u128 << host_bits).min(option::FAKE_DNS_IPV6_POOL_SIZE as u128) - 1
+        };
+        Ok(Pool::new(ipv6, network + 1, max_cursor))
+    }
+
+    fn to_ip(&self, ip: u128) -> IpAddr {
+        if self.ipv6 {
+            IpAddr::V6(Ipv6Addr::from(ip))
+        } else {
+            IpAddr::V4(FakeDns::u32_to_ip(ip as u32))
+        }
+    }
+
+    // Returns the IP as u128 if it's of the family of the pool.
+    fn from_ip(&self, ip: &IpAddr) -> Option<u128> {
+        match ip {
+            IpAddr::V4(ip) if !self.ipv6 => Some(FakeDns::ip_to_u32(ip) as u128),
+            IpAddr::V6(ip) if self.ipv6 => Some(u128::from(*ip)),
+            _ => None,
+        }
+    }
+
+    fn contains(&self, ip: u128) -> bool {
+        ip >= self.min_cursor && ip <= self.max_cursor
+    }
+
+    // Returns None if the pool is exhausted. Reusing an IP could send the
+    // connections still open to it, or cached by the apps, to another domain.
+    fn allocate(&mut self, domain: &str, now: u64) -> Option<IpAddr> {
+        // Skips the IPs loaded from a saved file.
+        while self.cursor <= self.max_cursor && self.ip_to_domain.contains_key(&self.cursor) {
+            self.cursor += 1;
+        }
+        if self.cursor > self.max_cursor {
+            return None;
+        }
+        self.ip_to_domain.insert(self.cursor, domain.to_owned());
+        self.domain_to_ip.insert(domain.to_owned(), self.cursor);
+        self.last_used.insert(self.cursor, now);
+        let ip = self.to_ip(self.cursor);
+        self.cursor += 1;
+        Some(ip)
+    }
+
+    fn insert(&mut self, ip: u128, domain: &str, last_used: u64) -> bool {
+        if !self.contains(ip)
+            || self.domain_to_ip.contains_key(domain)
+            || self.ip_to_domain.contains_key(&ip)
+        {
+            return false;
+        }
+        self.ip_to_domain.insert(ip, domain.to_owned());
+        self.domain_to_ip.insert(domain.to_owned(), ip);
+        self.last_used.insert(ip, last_used);
+        true
+    }
+
+    fn write_to(&self, data: &mut String) {
+        for (ip, domain) in self.ip_to_domain.iter() {
+            let last_used = self.last_used.get(ip).copied().unwrap_or(0);
+            data.push_str(&format!("{} {} {}\n", self.to_ip(*ip), domain, last_used));
+        }
+    }
+}
+
+pub struct FakeDns {
+    pool_v4: Pool,
+    pool_v6: Option<Pool>,
+    // Changed since the last snapshot.
+    dirty: bool,
+    ttl: u32,
+    filters: Vec<String>,
+    mode: FakeDnsMode,
+    dispatcher: Option<Arc<Dispatcher>>,
+    hosts: Hosts,
+}
+
+impl FakeDns {
+    /// Allocates fake IPv4 addresses from the range, e.g. `198.18.0.0/15`.
+    /// The range should not overlap with any network reachable from the
+    /// device, connections to such networks would be taken as connections
+    /// to the domains.
+    pub fn new(mode: FakeDnsMode, range: &str) -> Result<Self> {
+        Ok(FakeDns {
+            pool_v4: Pool::from_range(range, false)?,
+            pool_v6: None,
+            dirty: false,
             ttl: 1,
             filters: Vec::new(),
             mode,
-        }
+            dispatcher: None,
+            hosts: Hosts::default(),
+        })
+    }
+
+    /// Answers AAAA queries with fake IPs from the range, e.g. `fd00::/112`,
+    /// at most `option::FAKE_DNS_IPV6_POOL_SIZE` of them are used.
+    pub fn set_ipv6_range(&mut self, range: &str) -> Result<()> {
+        self.pool_v6 = Some(Pool::from_range(range, true)?);
+        Ok(())
     }
 
     pub fn add_filter(&mut self, filter: String) {
         self.filters.push(filter);
     }
 
-    fn allocate_ip(&mut self, domain: &str) -> Ipv4Addr {
-        self.ip_to_domain.insert(self.cursor, domain.to_owned());
-        self.domain_to_ip.insert(domain.to_owned(), self.cursor);
-        let ip = Self::u32_to_ip(self.cursor);
-        self.cursor += 1;
-        if self.cursor > self.max_cursor {
-            self.cursor = self.min_cursor;
+    /// Consults the router on accepted domains, domains routed to a direct
+    /// outbound will not be given fake IPs.
+    pub fn set_routing_aware(&mut self, dispatcher: Arc<Dispatcher>) {
+        self.dispatcher.replace(dispatcher);
+    }
+
+    /// Answers the domains in the hosts with their static IPs instead of
+    /// fake ones, whether they are accepted or not.
+    pub fn set_hosts(&mut self, hosts: Hosts) {
+        self.hosts = hosts;
+    }
+
+    fn pool(&self, ipv6: bool) -> Option<&Pool> {
+        if ipv6 {
+            self.pool_v6.as_ref()
+        } else {
+            Some(&self.pool_v4)
+        }
+    }
+
+    fn pool_mut(&mut self, ipv6: bool) -> Option<&mut Pool> {
+        if ipv6 {
+            self.pool_v6.as_mut()
+        } else {
+            Some(&mut self.pool_v4)
         }
-        ip
+    }
+
+    fn allocate_ip(&mut self, domain: &str, ipv6: bool) -> Result<IpAddr> {
+        let now = Self::now();
+        let pool = self
+            .pool_mut(ipv6)
+            .ok_or_else(|| anyhow!("no fake dns ipv6 range"))?;
+        let ip = pool
+            .allocate(domain, now)
+            .ok_or_else(|| anyhow!("fake dns range exhausted"))?;
+        self.dirty = true;
+        Ok(ip)
     }
 
     pub fn query_domain(&mut self, ip: &IpAddr) -> Option<String> {
-        let ip = match ip {
-            IpAddr::V4(ip) => ip,
-            _ => return None,
-        };
-        match self.ip_to_domain.get(&Self::ip_to_u32(ip)) {
-            Some(v) => Some(v.clone()),
-            None => None,
+        let now = Self::now();
+        let pool = self.pool_mut(ip.is_ipv6())?;
+        let ip = pool.from_ip(ip)?;
+        let domain = pool.ip_to_domain.get(&ip).cloned()?;
+        pool.last_used.insert(ip, now);
+        self.dirty = true;
+        Some(domain)
+    }
+
+    /// Returns the fake IP of the family allocated to the domain.
+    pub fn query_fake_ip(&mut self, domain: &str, ipv6: bool) -> Option<IpAddr> {
+        let now = Self::now();
+        let pool = self.pool_mut(ipv6)?;
+        let ip = *pool.domain_to_ip.get(domain)?;
+        pool.last_used.insert(ip, now);
+        let ip = pool.to_ip(ip);
+        self.dirty = true;
+        Some(ip)
+    }
+
+    /// Loads the allocations saved by `snapshot`, those not used in the last
+    /// `ttl` seconds or out of the pools are dropped. Returns the number of
+    /// allocations loaded.
+    pub fn load(&mut self, path: &Path, ttl: u64) -> Result<usize> {
+        let data = fs::read_to_string(path)?;
+        let now = Self::now();
+        let mut n = 0;
+        for line in data.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            // The cursor lines written by earlier versions are skipped, new
+            // allocations start from the beginning of the ranges and skip the
+            // loaded IPs.
+            if parts.len() != 3 {
+                continue;
+            }
+            let (ip, domain, last_used) = match (
+                parts[0].parse::<IpAddr>(),
+                parts[1],
+                parts[2].parse::<u64>(),
+            ) {
+                (Ok(ip), domain, Ok(last_used)) => (ip, domain, last_used),
+                _ => continue,
+            };
+            if last_used + ttl < now {
+                continue;
+            }
+            if let Some(pool) = self.pool_mut(ip.is_ipv6()) {
+                if let Some(ip) = pool.from_ip(&ip) {
+                    if pool.insert(ip, domain, last_used) {
+                        n += 1;
+                    }
+                }
+            }
         }
+        Ok(n)
     }
 
-    pub fn query_fake_ip(&mut self, domain: &str) -> Option<IpAddr> {
-        match self.domain_to_ip.get(domain) {
-            Some(v) => Some(IpAddr::V4(Self::u32_to_ip(v.to_owned()))),
-            None => None,
+    /// Returns the allocations serialized if changed since the last call.
+    pub fn snapshot(&mut self) -> Option<String> {
+        if !self.dirty {
+            return None;
         }
+        self.dirty = false;
+        let mut data = String::new();
+        self.pool_v4.write_to(&mut data);
+        if let Some(pool) = self.pool_v6.as_ref() {
+            pool.write_to(&mut data);
+        }
+        Some(data)
+    }
+
+    /// Writes a snapshot to the file, replacing the previous one atomically.
+    pub fn save(path: &Path, snapshot: &str) -> Result<()> {
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, snapshot)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
     }
 
     fn accept(&self, domain: &str) -> bool {
+        if !self.accept_by_filters(domain) {
+            return false;
+        }
+        if let Some(dispatcher) = self.dispatcher.as_ref() {
+            // Falls back to fake IP if the router can't decide.
+            if let Some(true) = dispatcher.is_direct_domain(domain) {
+                debug!("domain {} routed direct, fake IP not used", domain);
+                return false;
+            }
+        }
+        true
+    }
+
+    fn accept_by_filters(&self, domain: &str) -> bool {
         match self.mode {
             FakeDnsMode::Exclude => {
                 for d in &self.filters {
@@ -98,7 +348,9 @@ impl FakeDns {
         }
     }
 
-    pub fn generate_fake_response(&mut self, request: &[u8]) -> Result<Vec<u8>> {
+    // Parses an IN A, AAAA or HTTPS query, returns the request and the
+    // queried domain.
+    fn parse_request(request: &[u8]) -> Result<(Message, String)> {
         let req = Message::from_vec(request)?;
 
         if req.queries().is_empty() {
@@ -127,21 +379,80 @@ impl FakeDns {
         } else {
             raw_name.to_ascii()
         };
+        Ok((req, domain))
+    }
 
-        if !self.accept(&domain) {
-            return Err(anyhow!("domain {} not accepted", domain));
-        }
+    /// Returns the domain of an A, AAAA or HTTPS query.
+    pub fn request_domain(request: &[u8]) -> Result<String> {
+        Self::parse_request(request).map(|(_, domain)| domain)
+    }
 
-        let ip = if let Some(ip) = self.query_fake_ip(&domain) {
-            match ip {
-                IpAddr::V4(a) => a,
-                _ => return Err(anyhow!("unexpected Ipv6 fake IP")),
+    /// Answers the query with the real IPs resolved by the DNS client, for
+    /// domains not given fake IPs but resolved by a DNS policy, which would
+    /// bypass the policy if forwarded. Lookup failures are answered with no
+    /// records.
+    pub async fn resolve(dns_client: &DnsClient, request: &[u8]) -> Result<Vec<u8>> {
+        let (req, domain) = Self::parse_request(request)?;
+        let strategy = match req.queries()[0].query_type() {
+            RecordType::A => DnsStrategy::Ipv4Only,
+            RecordType::AAAA => DnsStrategy::Ipv6Only,
+            _ => return Self::build_response(&req, Vec::new(), POLICY_ANSWER_TTL),
+        };
+        let ips = match dns_client
+            .lookup_with_strategy(domain.clone(), dns_client.bind_addr(), Some(strategy))
+            .await
+        {
+            Ok(ips) => ips,
+            Err(e) => {
+                debug!("resolve {} by dns policy failed: {}", &domain, e);
+                Vec::new()
             }
+        };
+        Self::build_response(&req, ips, POLICY_ANSWER_TTL)
+    }
+
+    pub fn generate_fake_response(&mut self, request: &[u8]) -> Result<Vec<u8>> {
+        let (req, domain) = Self::parse_request(request)?;
+        let t = req.queries()[0].query_type();
+
+        let ipv6 = t == RecordType::AAAA;
+        let ips: Vec<IpAddr> = if let Some(ips) = self.hosts.get(&domain) {
+            // Static IPs of the queried family.
+            debug!("answer {} with static ips", &domain);
+            ips.iter()
+                .filter(|ip| t != RecordType::Unknown(65) && ip.is_ipv6() == ipv6)
+                .cloned()
+                .collect()
         } else {
-            let ip = self.allocate_ip(&domain);
-            debug!("allocate {} for {}", &ip, &domain);
-            ip
+            if !self.accept(&domain) {
+                return Err(anyhow!("domain {} not accepted", domain));
+            }
+
+            // AAAA queries are answered with no records if there's no IPv6
+            // pool, so are the others.
+            if t == RecordType::A || (ipv6 && self.pool_v6.is_some()) {
+                if let Some(ip) = self.query_fake_ip(&domain, ipv6) {
+                    vec![ip]
+                } else {
+                    let ip = self.allocate_ip(&domain, ipv6).map_err(|e| {
+                        warn!("allocate fake ip for {} failed: {}", &domain, e);
+                        e
+                    })?;
+                    debug!("allocate {} for {}", ip, &domain);
+                    vec![ip]
+                }
+            } else {
+                Vec::new()
+            }
         };
+        Self::build_response(&req, ips, self.ttl)
+    }
+
+    // Builds the response to the request answering with the IPs.
+    fn build_response(req: &Message, ips: Vec<IpAddr>, ttl: u32) -> Result<Vec<u8>> {
+        let query = &req.queries()[0];
+        let t = query.query_type();
+        let raw_name = query.name();
 
         let mut resp = Message::new();
 
@@ -160,13 +471,17 @@ impl FakeDns {
             resp.add_query(query.clone());
         }
 
-        if query.query_type() == RecordType::A {
+        for ip in ips {
+            let rdata = match ip {
+                IpAddr::V4(ip) => RData::A(ip),
+                IpAddr::V6(ip) => RData::AAAA(ip),
+            };
             let mut ans = Record::new();
             ans.set_name(raw_name.clone())
-                .set_rr_type(RecordType::A)
-                .set_ttl(self.ttl)
+                .set_rr_type(t)
+                .set_ttl(ttl)
                 .set_dns_class(DNSClass::IN)
-                .set_rdata(RData::A(ip));
+                .set_rdata(rdata);
             resp.add_answer(ans);
         }
 
@@ -174,12 +489,10 @@ impl FakeDns {
     }
 
     pub fn is_fake_ip(&self, ip: &IpAddr) -> bool {
-        let ip = match ip {
-            IpAddr::V4(ip) => ip,
-            _ => return false,
-        };
-        let ip = Self::ip_to_u32(ip);
-        ip >= self.min_cursor && ip <= self.max_cursor
+        match self.pool(ip.is_ipv6()) {
+            Some(pool) => pool.from_ip(ip).map_or(false, |ip| pool.contains(ip)),
+            None => false,
+        }
     }
 
     fn u32_to_ip(ip: u32) -> Ipv4Addr {
@@ -210,4 +523,118 @@ mod tests {
         let ip2 = 2130706433u32;
         assert_eq!(ip1, ip2);
     }
+
+    #[test]
+    fn test_persist() {
+        let path = std::env::temp_dir().join(format!("leaf-fake-dns-{}", std::process::id()));
+
+        let mut fake_dns = FakeDns::new(FakeDnsMode::Exclude, "240.255.0.0/16").unwrap();
+        let ip1 = fake_dns.allocate_ip("example.com", false).unwrap();
+        let ip2 = fake_dns.allocate_ip("example.org", false).unwrap();
+        let snapshot = fake_dns.snapshot().unwrap();
+        assert!(fake_dns.snapshot().is_none());
+        // An expired entry.
+        let snapshot = format!("{}240.255.0.9 stale.com 1\n", snapshot);
+        FakeDns::save(&path, &snapshot).unwrap();
+
+        let mut fake_dns = FakeDns::new(FakeDnsMode::Exclude, "240.255.0.0/16").unwrap();
+        assert_eq!(fake_dns.load(&path, 3600).unwrap(), 2);
+        assert_eq!(fake_dns.query_fake_ip("example.com", false), Some(ip1));
+        assert_eq!(fake_dns.query_domain(&ip2), Some("example.org".to_string()));
+        assert_eq!(fake_dns.query_fake_ip("stale.com", false), None);
+        // New allocations don't take the loaded IPs.
+        let ip3 = fake_dns.allocate_ip("example.net", false).unwrap();
+        assert!(ip3 != ip1 && ip3 != ip2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_ipv6_pool() {
+        let mut fake_dns = FakeDns::new(FakeDnsMode::Exclude, "240.255.0.0/16").unwrap();
+        assert!(fake_dns.allocate_ip("example.com", true).is_err());
+        assert!(fake_dns.set_ipv6_range("fd00::/127").is_err());
+        assert!(fake_dns.set_ipv6_range("fd00::").is_err());
+        fake_dns.set_ipv6_range("fd00::/126").unwrap();
+
+        let ip4 = fake_dns.allocate_ip("example.com", false).unwrap();
+        let ip6 = fake_dns.allocate_ip("example.com", true).unwrap();
+        assert_eq!(ip6, "fd00::1".parse::<IpAddr>().unwrap());
+        assert!(fake_dns.is_fake_ip(&ip4) && fake_dns.is_fake_ip(&ip6));
+        assert!(!fake_dns.is_fake_ip(&"fd00::4".parse().unwrap()));
+        assert_eq!(fake_dns.query_domain(&ip6), Some("example.com".to_string()));
+        assert_eq!(fake_dns.query_fake_ip("example.com", true), Some(ip6));
+
+        // Exhausted after fd00::3.
+        fake_dns.allocate_ip("example.org", true).unwrap();
+        fake_dns.allocate_ip("example.net", true).unwrap();
+        assert!(fake_dns.allocate_ip("example.edu", true).is_err());
+        assert_eq!(fake_dns.query_fake_ip("example.com", true), Some(ip6));
+        assert_eq!(fake_dns.query_fake_ip("example.com", false), Some(ip4));
+    }
+
+    #[test]
+    fn test_hosts() {
+        use std::str::FromStr;
+        use trust_dns_proto::op::Query;
+        use trust_dns_proto::rr::Name;
+
+        let answers = |fake_dns: &mut FakeDns, domain: &str, t: RecordType| {
+            let mut req = Message::new();
+            req.add_query(Query::query(Name::from_str(domain).unwrap(), t));
+            let resp = fake_dns.generate_fake_response(&req.to_vec().unwrap())?;
+            let resp = Message::from_vec(&resp).unwrap();
+            Ok::<_, anyhow::Error>(
+                resp.answers()
+                    .iter()
+                    .map(|ans| match ans.rdata() {
+                        RData::A(ip) => IpAddr::V4(*ip),
+                        RData::AAAA(ip) => IpAddr::V6(*ip),
+                        _ => panic!("unexpected record"),
+                    })
+                    .collect::<Vec<IpAddr>>(),
+            )
+        };
+
+        let mut entries = HashMap::new();
+        entries.insert(
+            "*.example.com".to_string(),
+            vec!["1.2.3.4".to_string(), "::1".to_string()],
+        );
+        let mut fake_dns = FakeDns::new(FakeDnsMode::Exclude, "240.255.0.0/16").unwrap();
+        fake_dns.add_filter("example.com".to_string());
+        fake_dns.set_hosts(Hosts::new(&entries));
+
+        // Excluded domains in the hosts are answered with the static IPs.
+        assert_eq!(
+            answers(&mut fake_dns, "www.example.com.", RecordType::A).unwrap(),
+            vec!["1.2.3.4".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(
+            answers(&mut fake_dns, "www.example.com.", RecordType::AAAA).unwrap(),
+            vec!["::1".parse::<IpAddr>().unwrap()]
+        );
+        assert!(answers(&mut fake_dns, "example.com.", RecordType::A).is_err());
+        let ips = answers(&mut fake_dns, "example.org.", RecordType::A).unwrap();
+        assert!(fake_dns.is_fake_ip(&ips[0]));
+    }
+
+    #[test]
+    fn test_range_exhausted() {
+        assert!(FakeDns::new(FakeDnsMode::Exclude, "198.18.0.0/31").is_err());
+        assert!(FakeDns::new(FakeDnsMode::Exclude, "fd00::/64").is_err());
+        let mut fake_dns = FakeDns::new(FakeDnsMode::Exclude, "198.18.0.4/30").unwrap();
+
+        // The network and broadcast addresses are skipped.
+        let ip1 = fake_dns.allocate_ip("example.com", false).unwrap();
+        let ip2 = fake_dns.allocate_ip("example.org", false).unwrap();
+        assert_eq!(ip1, "198.18.0.5".parse::<IpAddr>().unwrap());
+        assert_eq!(ip2, "198.18.0.6".parse::<IpAddr>().unwrap());
+        assert!(!fake_dns.is_fake_ip(&"198.18.0.7".parse().unwrap()));
+
+        // Live mappings are kept instead of being reused.
+        assert!(fake_dns.allocate_ip("example.net", false).is_err());
+        assert_eq!(fake_dns.query_fake_ip("example.com", false), Some(ip1));
+        assert_eq!(fake_dns.query_domain(&ip2), Some("example.org".to_string()));
+    }
 }
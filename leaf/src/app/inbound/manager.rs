@@ -6,7 +6,8 @@ use protobuf::Message;
 use crate::app::dispatcher::Dispatcher;
 use crate::app::nat_manager::NatManager;
 use crate::config::{
-    ChainInboundSettings, Inbound, TrojanInboundSettings, WebSocketInboundSettings,
+    ChainInboundSettings, HttpInboundSettings, Inbound, MixedInboundSettings, SocksInboundSettings,
+    TrojanInboundSettings, WebSocketInboundSettings,
 };
 use crate::proxy;
 use crate::proxy::InboundHandler;
@@ -14,6 +15,8 @@ use crate::Runner;
 
 #[cfg(feature = "inbound-http")]
 use crate::proxy::http;
+#[cfg(feature = "inbound-mixed")]
+use crate::proxy::mixed;
 #[cfg(feature = "inbound-socks")]
 use crate::proxy::socks;
 #[cfg(feature = "inbound-trojan")]
@@ -27,6 +30,8 @@ use crate::proxy::chain;
 use super::network_listener::NetworkInboundListener;
 use super::InboundListener;
 
+#[cfg(all(feature = "inbound-tproxy", target_os = "linux"))]
+use super::tproxy_listener::TproxyInboundListener;
 #[cfg(all(
     feature = "inbound-tun",
     any(target_os = "ios", target_os = "macos", target_os = "linux")
@@ -49,7 +54,9 @@ impl InboundManager {
             match inbound.protocol.as_str() {
                 #[cfg(feature = "inbound-socks")]
                 "socks" => {
-                    let tcp = Arc::new(socks::inbound::TcpHandler);
+                    let settings =
+                        SocksInboundSettings::parse_from_bytes(&inbound.settings).unwrap();
+                    let tcp = Arc::new(socks::inbound::TcpHandler::new(&settings));
                     let udp = Arc::new(socks::inbound::UdpHandler);
                     let handler = Arc::new(proxy::inbound::Handler::new(
                         inbound.tag.clone(),
@@ -60,7 +67,9 @@ impl InboundManager {
                 }
                 #[cfg(feature = "inbound-http")]
                 "http" => {
-                    let tcp = Arc::new(http::inbound::TcpHandler);
+                    let settings =
+                        HttpInboundSettings::parse_from_bytes(&inbound.settings).unwrap();
+                    let tcp = Arc::new(http::inbound::TcpHandler::new(&settings));
                     let handler = Arc::new(proxy::inbound::Handler::new(
                         inbound.tag.clone(),
                         Some(tcp),
@@ -68,6 +77,19 @@ impl InboundManager {
                     ));
                     handlers.insert(inbound.tag.clone(), handler);
                 }
+                #[cfg(feature = "inbound-mixed")]
+                "mixed" => {
+                    let settings =
+                        MixedInboundSettings::parse_from_bytes(&inbound.settings).unwrap();
+                    let tcp = Arc::new(mixed::inbound::TcpHandler::new(&settings));
+                    let udp = Arc::new(socks::inbound::UdpHandler);
+                    let handler = Arc::new(proxy::inbound::Handler::new(
+                        inbound.tag.clone(),
+                        Some(tcp),
+                        Some(udp),
+                    ));
+                    handlers.insert(inbound.tag.clone(), handler);
+                }
                 #[cfg(feature = "inbound-trojan")]
                 "trojan" => {
                     let settings =
@@ -140,12 +162,30 @@ impl InboundManager {
                     });
                     listeners.insert(inbound.tag.clone(), listener);
                 }
+                #[cfg(all(feature = "inbound-tproxy", target_os = "linux"))]
+                "tproxy" => {
+                    let listener = Arc::new(TproxyInboundListener {
+                        tag: inbound.tag.clone(),
+                        address: inbound.address.clone(),
+                        port: inbound.port as u16,
+                        backlog: inbound.backlog,
+                        dispatcher: dispatcher.clone(),
+                        nat_manager: nat_manager.clone(),
+                    });
+                    listeners.insert(inbound.tag.clone(), listener);
+                }
                 _ => {
                     if inbound.port != 0 {
                         if let Some(h) = handlers.get(&inbound.tag) {
+                            let addresses = if inbound.addresses.is_empty() {
+                                vec![inbound.address.clone()]
+                            } else {
+                                inbound.addresses.to_vec()
+                            };
                             let listener = Arc::new(NetworkInboundListener {
-                                address: inbound.address.clone(),
+                                addresses,
                                 port: inbound.port as u16,
+                                backlog: inbound.backlog,
                                 handler: h.clone(),
                                 dispatcher: dispatcher.clone(),
                                 nat_manager: nat_manager.clone(),
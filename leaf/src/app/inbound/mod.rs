@@ -1,5 +1,8 @@
 mod network_listener;
 
+#[cfg(all(feature = "inbound-tproxy", target_os = "linux"))]
+mod tproxy_listener;
+
 #[cfg(all(
     feature = "inbound-tun",
     any(target_os = "ios", target_os = "macos", target_os = "linux")
@@ -1,6 +1,10 @@
+use std::collections::HashSet;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::sync::Arc;
 
 use log::*;
+use socket2::{Domain, Socket, Type};
 use tokio::net::{TcpListener, TcpStream, UdpSocket};
 use tokio::stream::StreamExt;
 use tokio::sync::mpsc::channel as tokio_channel;
@@ -8,17 +12,19 @@ use tokio::sync::mpsc::{Receiver as TokioReceiver, Sender as TokioSender};
 
 use crate::app::dispatcher::Dispatcher;
 use crate::app::nat_manager::{NatManager, UdpPacket};
+use crate::common::sys;
 use crate::proxy::InboundHandler;
-use crate::proxy::{InboundDatagram, InboundTransport, SimpleInboundDatagram, SimpleProxyStream};
+use crate::proxy::{InboundDatagram, InboundTransport, SimpleInboundDatagram};
 use crate::session::{Session, SocksAddr};
 use crate::Runner;
 
 use super::InboundListener;
 
-async fn handle_inbound_datagram(
+pub(super) async fn handle_inbound_datagram(
     inbound_tag: String,
     socket: Box<dyn InboundDatagram>,
     nat_manager: Arc<NatManager>,
+    end_sessions: bool,
 ) {
     let (mut client_sock_recv, mut client_sock_send) = socket.split();
 
@@ -59,6 +65,10 @@ async fn handle_inbound_datagram(
         debug!("udp downlink ended");
     });
 
+    // Sessions added by this datagram, they end along with the datagram if
+    // `end_sessions` is set, e.g. for datagrams of SOCKS UDP associations.
+    let mut sessions = HashSet::new();
+
     let mut buf = [0u8; 2 * 1024];
     loop {
         match client_sock_recv.recv_from(&mut buf).await {
@@ -82,6 +92,9 @@ async fn handle_inbound_datagram(
                     nat_manager
                         .add_session(&sess, src_addr, client_ch_tx.clone())
                         .await;
+                    if end_sessions {
+                        sessions.insert(src_addr);
+                    }
 
                     debug!(
                         "added udp session {}:{} -> {} ({})",
@@ -101,6 +114,10 @@ async fn handle_inbound_datagram(
             }
         }
     }
+
+    for key in sessions.iter() {
+        nat_manager.remove_session(key).await;
+    }
 }
 
 async fn handle_inbound_stream(
@@ -121,10 +138,7 @@ async fn handle_inbound_stream(
     sess.inbound_tag = handler.tag().clone();
 
     match handler
-        .handle_tcp(InboundTransport::Stream(
-            Box::new(SimpleProxyStream(stream)),
-            sess,
-        ))
+        .handle_tcp(InboundTransport::Stream(Box::new(stream), sess))
         .await
     {
         Ok(res) => match res {
@@ -132,7 +146,7 @@ async fn handle_inbound_stream(
                 let _ = dispatcher.dispatch_tcp(&mut sess, stream).await;
             }
             InboundTransport::Datagram(socket) => {
-                handle_inbound_datagram(handler.tag().clone(), socket, nat_manager).await;
+                handle_inbound_datagram(handler.tag().clone(), socket, nat_manager, true).await;
             }
             InboundTransport::Empty => (),
         },
@@ -142,28 +156,88 @@ async fn handle_inbound_stream(
     }
 }
 
+// Same as TcpListener::bind.
+const DEFAULT_BACKLOG: u32 = 128;
+
+// Creates a socket for the address. IPv6 sockets only accept IPv6, so that
+// `0.0.0.0` and `::` can listen on the same port.
+fn new_socket(address: &str, port: u16, ty: Type) -> io::Result<(Socket, SocketAddr)> {
+    let addr = (address, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "could not resolve address"))?;
+    let socket = if addr.is_ipv4() {
+        Socket::new(Domain::ipv4(), ty, None)?
+    } else {
+        let socket = Socket::new(Domain::ipv6(), ty, None)?;
+        socket.set_only_v6(true)?;
+        socket
+    };
+    Ok((socket, addr))
+}
+
+// Binds a TCP listener with the backlog, clamped to the OS maximum, 0 for the
+// default.
+fn bind_tcp(address: &str, port: u16, backlog: u32) -> io::Result<TcpListener> {
+    let (socket, addr) = new_socket(address, port, Type::stream())?;
+    let max_backlog = sys::max_listen_backlog();
+    let backlog = if backlog == 0 {
+        DEFAULT_BACKLOG.min(max_backlog)
+    } else if backlog > max_backlog {
+        warn!(
+            "backlog {} of {}:{} exceeds the os maximum, clamped to {}",
+            backlog, address, port, max_backlog
+        );
+        max_backlog
+    } else {
+        backlog
+    };
+    // Same as TcpListener::bind.
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(backlog as i32)?;
+    let listener = socket.into_tcp_listener();
+    listener.set_nonblocking(true)?;
+    TcpListener::from_std(listener)
+}
+
+fn bind_udp(address: &str, port: u16) -> io::Result<UdpSocket> {
+    let (socket, addr) = new_socket(address, port, Type::dgram())?;
+    socket.bind(&addr.into())?;
+    let socket = socket.into_udp_socket();
+    socket.set_nonblocking(true)?;
+    UdpSocket::from_std(socket)
+}
+
 pub struct NetworkInboundListener {
-    pub address: String,
+    /// Addresses to listen on, a failure on one doesn't affect the others.
+    pub addresses: Vec<String>,
     pub port: u16,
+    /// Listen backlog of the TCP listener, 0 for the default.
+    pub backlog: u32,
     pub handler: Arc<dyn InboundHandler>,
     pub dispatcher: Arc<Dispatcher>,
     pub nat_manager: Arc<NatManager>,
 }
 
-impl InboundListener for NetworkInboundListener {
-    fn listen(&self) -> Vec<Runner> {
-        let mut runners: Vec<Runner> = Vec::new();
-        let handler = self.handler.clone();
-        let dispatcher = self.dispatcher.clone();
-        let nat_manager = self.nat_manager.clone();
-        let address = self.address.clone();
-        let port = self.port;
-
+impl NetworkInboundListener {
+    fn listen_on(&self, address: &str, runners: &mut Vec<Runner>) {
         if self.handler.has_tcp() {
+            let handler = self.handler.clone();
+            let dispatcher = self.dispatcher.clone();
+            let nat_manager = self.nat_manager.clone();
+            let address = address.to_owned();
+            let port = self.port;
+            let backlog = self.backlog;
             let tcp_task = async move {
-                let mut listener = TcpListener::bind(format!("{}:{}", address, port).as_str())
-                    .await
-                    .unwrap();
+                let mut listener = match bind_tcp(&address, port, backlog) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        error!("inbound listening tcp {}:{} failed: {}", address, port, e);
+                        return;
+                    }
+                };
                 info!("inbound listening tcp {}:{}", address, port);
                 while let Some(stream) = listener.next().await {
                     match stream {
@@ -187,12 +261,16 @@ impl InboundListener for NetworkInboundListener {
         if self.handler.has_udp() {
             let nat_manager = self.nat_manager.clone();
             let handler = self.handler.clone();
-            let address = self.address.clone();
+            let address = address.to_owned();
             let port = self.port;
             let udp_task = async move {
-                let socket = UdpSocket::bind(format!("{}:{}", address, port))
-                    .await
-                    .unwrap();
+                let socket = match bind_udp(&address, port) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        error!("inbound listening udp {}:{} failed: {}", address, port, e);
+                        return;
+                    }
+                };
                 info!("inbound listening udp {}:{}", address, port);
 
                 match handler
@@ -200,7 +278,8 @@ impl InboundListener for NetworkInboundListener {
                     .await
                 {
                     Ok(socket) => {
-                        handle_inbound_datagram(handler.tag().clone(), socket, nat_manager).await;
+                        handle_inbound_datagram(handler.tag().clone(), socket, nat_manager, false)
+                            .await;
                     }
                     Err(e) => {
                         error!("handle inbound socket failed: {}", e);
@@ -209,7 +288,40 @@ impl InboundListener for NetworkInboundListener {
             };
             runners.push(Box::pin(udp_task));
         }
+    }
+}
 
+impl InboundListener for NetworkInboundListener {
+    fn listen(&self) -> Vec<Runner> {
+        let mut runners: Vec<Runner> = Vec::new();
+        for address in self.addresses.iter() {
+            self.listen_on(address, &mut runners);
+        }
         runners
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_ipv4_and_ipv6_wildcards() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let listener = bind_tcp("0.0.0.0", 0, 0).unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let listener6 = bind_tcp("::", port, 0).unwrap();
+            assert_eq!(listener6.local_addr().unwrap().port(), port);
+
+            let socket = bind_udp("0.0.0.0", 0).unwrap();
+            let port = socket.local_addr().unwrap().port();
+            let socket6 = bind_udp("::", port).unwrap();
+            assert_eq!(socket6.local_addr().unwrap().port(), port);
+        });
+    }
+}
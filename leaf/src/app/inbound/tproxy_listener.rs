@@ -0,0 +1,98 @@
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+
+use log::*;
+use tokio::stream::StreamExt;
+
+use crate::app::dispatcher::Dispatcher;
+use crate::app::nat_manager::NatManager;
+use crate::proxy::tproxy::{self, TproxyInboundDatagram};
+use crate::session::{Session, SocksAddr};
+use crate::Runner;
+
+use super::network_listener::handle_inbound_datagram;
+use super::InboundListener;
+
+pub struct TproxyInboundListener {
+    pub tag: String,
+    pub address: String,
+    pub port: u16,
+    /// Listen backlog of the TCP listener, 0 for the default.
+    pub backlog: u32,
+    pub dispatcher: Arc<Dispatcher>,
+    pub nat_manager: Arc<NatManager>,
+}
+
+impl InboundListener for TproxyInboundListener {
+    fn listen(&self) -> Vec<Runner> {
+        let mut runners: Vec<Runner> = Vec::new();
+        let addr: SocketAddr = match (self.address.as_str(), self.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+        {
+            Some(a) => a,
+            None => {
+                error!("invalid tproxy listen address {}", &self.address);
+                return runners;
+            }
+        };
+
+        let tag = self.tag.clone();
+        let dispatcher = self.dispatcher.clone();
+        let backlog = self.backlog;
+        let tcp_task = async move {
+            let mut listener = match tproxy::bind_tcp(&addr, backlog) {
+                Ok(v) => v,
+                Err(e) => {
+                    error!("tproxy listen tcp {} failed: {}", &addr, e);
+                    return;
+                }
+            };
+            info!("tproxy inbound listening tcp {}", &addr);
+            while let Some(stream) = listener.next().await {
+                let stream = match stream {
+                    Ok(v) => v,
+                    Err(e) => {
+                        warn!("accept connection failed: {}", e);
+                        continue;
+                    }
+                };
+                let dispatcher = dispatcher.clone();
+                let tag = tag.clone();
+                tokio::spawn(async move {
+                    let (source, local_addr) = match (stream.peer_addr(), stream.local_addr()) {
+                        (Ok(a), Ok(b)) => (a, b),
+                        _ => return,
+                    };
+                    let mut sess = Session::default();
+                    sess.source = source;
+                    sess.local_addr = local_addr;
+                    // The local address of a redirected connection is the
+                    // original destination.
+                    sess.destination = SocksAddr::Ip(local_addr);
+                    sess.inbound_tag = tag;
+                    dispatcher.dispatch_tcp(&mut sess, stream).await;
+                });
+            }
+        };
+        runners.push(Box::pin(tcp_task));
+
+        let tag = self.tag.clone();
+        let nat_manager = self.nat_manager.clone();
+        let udp_task = async move {
+            let socket = match tproxy::bind_udp(&addr).and_then(TproxyInboundDatagram::new) {
+                Ok(v) => v,
+                Err(e) => {
+                    error!("tproxy listen udp {} failed: {}", &addr, e);
+                    return;
+                }
+            };
+            info!("tproxy inbound listening udp {}", &addr);
+            handle_inbound_datagram(tag, Box::new(socket), nat_manager, false).await;
+        };
+        runners.push(Box::pin(udp_task));
+
+        runners
+    }
+}
@@ -1,9 +1,19 @@
+pub mod connections;
 pub mod dispatcher;
 pub mod dns_client;
 pub mod inbound;
 pub mod nat_manager;
 pub mod outbound;
+pub mod rate_limit;
+pub mod rewriter;
 pub mod router;
+pub mod runtime;
+
+#[cfg(all(feature = "splice", target_os = "linux"))]
+pub mod splice;
+
+#[cfg(feature = "config-conf")]
+pub mod subscription;
 
 #[cfg(any(target_os = "ios", target_os = "macos", target_os = "linux"))]
 pub mod fake_dns;
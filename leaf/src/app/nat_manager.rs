@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -11,8 +12,9 @@ use tokio::sync::{
 };
 
 use crate::app::dispatcher::Dispatcher;
+use crate::config::Config;
 use crate::option;
-use crate::session::{Session, SocksAddr};
+use crate::session::{Network, Session, SocksAddr};
 
 #[derive(Debug)]
 pub struct UdpPacket {
@@ -21,20 +23,48 @@ pub struct UdpPacket {
     pub dst_addr: Option<SocksAddr>,
 }
 
-type SessionMap =
-    Arc<TokioMutex<HashMap<SocketAddr, (Sender<UdpPacket>, oneshot::Sender<bool>, Instant)>>>;
+// (uplink channel, downlink abort signal, last activity, idle timeout)
+type SessionMap = Arc<
+    TokioMutex<HashMap<SocketAddr, (Sender<UdpPacket>, oneshot::Sender<bool>, Instant, Duration)>>,
+>;
 
 pub struct NatManager {
     sessions: SessionMap,
     dispatcher: Arc<Dispatcher>,
     timeout_check_task: TokioMutex<Option<BoxFuture<'static, ()>>>,
+    default_timeout: Duration,
+    // Per-inbound overrides of the session timeout by inbound tag.
+    timeouts: HashMap<String, Duration>,
 }
 
 impl NatManager {
-    pub fn new(dispatcher: Arc<Dispatcher>) -> Self {
+    pub fn new(dispatcher: Arc<Dispatcher>, config: &Config) -> Self {
         let sessions: SessionMap = Arc::new(TokioMutex::new(HashMap::new()));
         let sessions2 = sessions.clone();
 
+        let default_timeout = Duration::from_secs(if config.udp_session_timeout > 0 {
+            config.udp_session_timeout as u64
+        } else {
+            option::UDP_SESSION_TIMEOUT
+        });
+        let mut timeouts = HashMap::new();
+        for inbound in config.inbounds.iter() {
+            if inbound.udp_session_timeout > 0 {
+                timeouts.insert(
+                    inbound.tag.clone(),
+                    Duration::from_secs(inbound.udp_session_timeout as u64),
+                );
+            }
+        }
+        // Checks at least as often as the shortest timeout so sessions don't
+        // outlive it by much.
+        let check_interval = timeouts
+            .values()
+            .fold(default_timeout, |a, b| std::cmp::min(a, *b))
+            .min(Duration::from_secs(
+                option::UDP_SESSION_TIMEOUT_CHECK_INTERVAL,
+            ));
+
         // The task is lazy, will not run until any sessions added.
         let timeout_check_task: BoxFuture<'static, ()> = Box::pin(async move {
             loop {
@@ -43,7 +73,7 @@ impl NatManager {
                 let now = Instant::now();
                 let mut to_be_remove = Vec::new();
                 for (key, val) in sessions.iter() {
-                    if now.duration_since(val.2).as_secs() >= option::UDP_SESSION_TIMEOUT {
+                    if now.saturating_duration_since(val.2) >= val.3 {
                         to_be_remove.push(key.to_owned());
                     }
                 }
@@ -69,10 +99,7 @@ impl NatManager {
                         n_remaining
                     );
                 }
-                tokio::time::delay_for(Duration::from_secs(
-                    option::UDP_SESSION_TIMEOUT_CHECK_INTERVAL,
-                ))
-                .await;
+                tokio::time::delay_for(check_interval).await;
             }
         });
 
@@ -80,9 +107,19 @@ impl NatManager {
             sessions,
             dispatcher,
             timeout_check_task: TokioMutex::new(Some(timeout_check_task)),
+            default_timeout,
+            timeouts,
         }
     }
 
+    /// Returns the idle timeout of sessions from the inbound.
+    pub fn session_timeout(&self, inbound_tag: &str) -> Duration {
+        self.timeouts
+            .get(inbound_tag)
+            .copied()
+            .unwrap_or(self.default_timeout)
+    }
+
     pub async fn contains_key(&self, key: &SocketAddr) -> bool {
         self.sessions.lock().await.contains_key(key)
     }
@@ -99,6 +136,16 @@ impl NatManager {
         }
     }
 
+    /// Removes the session and aborts its tasks.
+    pub async fn remove_session(&self, key: &SocketAddr) {
+        if let Some(sess) = self.sessions.lock().await.remove(key) {
+            if let Err(e) = sess.1.send(true) {
+                debug!("failed to send abort signal on session {}: {}", key, e);
+            }
+            debug!("udp session {} removed", key);
+        }
+    }
+
     pub async fn size(&self) -> usize {
         self.sessions.lock().await.len()
     }
@@ -119,22 +166,25 @@ impl NatManager {
         let (target_ch_tx, mut target_ch_rx) = mpsc::channel(64);
         let (downlink_abort_tx, downlink_abort_rx) = oneshot::channel();
 
+        let timeout = self.session_timeout(&sess.inbound_tag);
         self.sessions.lock().await.insert(
             raddr.clone(),
-            (target_ch_tx, downlink_abort_tx, Instant::now()),
+            (target_ch_tx, downlink_abort_tx, Instant::now(), timeout),
         );
 
         let dispatcher = self.dispatcher.clone();
         let sessions = self.sessions.clone();
-        let sess = sess.clone();
+        let mut sess = sess.clone();
+        sess.network = Network::Udp;
+        dispatcher.rewrite(&mut sess);
 
         // Spawns a new task for dispatching to avoid blocking the current task,
         // because we have stream type transports for UDP traffic, establishing a
         // TCP stream would block the task.
         tokio::spawn(async move {
             // new socket to communicate with the target.
-            let socket = match dispatcher.dispatch_udp(&sess).await {
-                Ok(s) => s,
+            let (socket, stats, conn) = match dispatcher.dispatch_udp(&sess).await {
+                Ok(v) => v,
                 Err(_) => {
                     sessions.lock().await.remove(&raddr);
                     return;
@@ -142,12 +192,19 @@ impl NatManager {
             };
 
             let (mut target_sock_recv, mut target_sock_send) = socket.split();
+            let traffic = dispatcher.traffic().clone();
+            let traffic2 = traffic.clone();
+            let stats2 = stats.clone();
+            // Unregistered once both the uplink and downlink tasks end.
+            let conn = Arc::new(conn);
+            let conn2 = conn.clone();
+            stats.active_conns.fetch_add(1, Ordering::Relaxed);
 
             let mut client_ch_tx = client_ch_tx.clone();
 
             // downlink
             let downlink_task = async move {
-                let mut buf = [0u8; 2 * 1024];
+                let mut buf = vec![0u8; *option::UDP_BUFFER_SIZE * 1024];
                 loop {
                     match target_sock_recv.recv_from(&mut buf).await {
                         Err(err) => {
@@ -161,6 +218,9 @@ impl NatManager {
                             break;
                         }
                         Ok((n, addr)) => {
+                            traffic.downlink.fetch_add(n as u64, Ordering::Relaxed);
+                            stats.rx_bytes.fetch_add(n as u64, Ordering::Relaxed);
+                            conn.downlink.fetch_add(n as u64, Ordering::Relaxed);
                             let pkt = UdpPacket {
                                 data: (&buf[..n]).to_vec(),
                                 src_addr: Some(addr.clone()),
@@ -183,9 +243,9 @@ impl NatManager {
                                         // If the destination port is 53, we assume it's a
                                         // DNS query and set a negative timeout so it will
                                         // be removed on next check.
-                                        sess.2.checked_sub(Duration::from_secs(
-                                            option::UDP_SESSION_TIMEOUT,
-                                        ));
+                                        if let Some(t) = sess.2.checked_sub(sess.3) {
+                                            sess.2 = t;
+                                        }
                                     } else {
                                         sess.2 = Instant::now();
                                     }
@@ -211,6 +271,7 @@ impl NatManager {
             });
 
             // uplink
+            let inbound_tag = sess.inbound_tag.clone();
             tokio::spawn(async move {
                 while let Some(pkt) = target_ch_rx.recv().await {
                     if pkt.dst_addr.is_none() {
@@ -224,11 +285,15 @@ impl NatManager {
                             continue;
                         }
                     };
+                    let addr = dispatcher.rewrite_addr(&inbound_tag, &addr).unwrap_or(addr);
                     match target_sock_send.send_to(&pkt.data, &addr).await {
                         Ok(0) => {
                             debug!("uplink send zero bytes");
                         }
-                        Ok(_) => {
+                        Ok(n) => {
+                            traffic2.uplink.fetch_add(n as u64, Ordering::Relaxed);
+                            stats2.tx_bytes.fetch_add(n as u64, Ordering::Relaxed);
+                            conn2.uplink.fetch_add(n as u64, Ordering::Relaxed);
                             continue;
                         }
                         Err(err) => {
@@ -236,7 +301,95 @@ impl NatManager {
                         }
                     }
                 }
+                // The channel is closed once the session is removed.
+                stats2.active_conns.fetch_sub(1, Ordering::Relaxed);
+            });
+        });
+    }
+}
+
+#[cfg(all(test, feature = "config-json", feature = "outbound-direct"))]
+mod tests {
+    use std::sync::RwLock;
+
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::app::{
+        outbound::manager::OutboundManager, rate_limit::RateLimiter, rewriter::Rewriter,
+        router::Router,
+    };
+    use crate::config::json;
+
+    #[test]
+    fn test_session_timeout() {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{
+                    "inbounds":[{"protocol":"socks","tag":"game","port":1086,"udpSessionTimeout":120}],
+                    "outbounds":[{"protocol":"direct","tag":"direct"}],
+                    "dns":{"servers":["127.0.0.1"]},
+                    "udpSessionTimeout":1
+                }"#
+                .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let outbound_manager = Arc::new(RwLock::new(OutboundManager::new(
+                &config.outbounds,
+                config.dns.as_ref().unwrap(),
+            )));
+            let dispatcher = Arc::new(Dispatcher::new(
+                outbound_manager,
+                Router::new(&config.routing_rules),
+                Rewriter::new(&config.inbounds),
+                RateLimiter::new(&config.inbounds, &config.outbounds),
+            ));
+            let nat_manager = NatManager::new(dispatcher, &config);
+            assert_eq!(
+                nat_manager.session_timeout("game"),
+                Duration::from_secs(120)
+            );
+            assert_eq!(nat_manager.session_timeout("other"), Duration::from_secs(1));
+
+            let mut server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = server.local_addr().unwrap();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 64];
+                let (n, addr) = server.recv_from(&mut buf).await.unwrap();
+                server.send_to(&buf[..n], &addr).await.unwrap();
             });
+
+            let raddr: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+            let (client_ch_tx, mut client_ch_rx) = mpsc::channel(8);
+            nat_manager
+                .add_session(&Session::default(), raddr, client_ch_tx)
+                .await;
+            nat_manager
+                .send(
+                    &raddr,
+                    UdpPacket {
+                        data: b"hello".to_vec(),
+                        src_addr: Some(SocksAddr::from(raddr)),
+                        dst_addr: Some(SocksAddr::from(server_addr)),
+                    },
+                )
+                .await;
+            let pkt = client_ch_rx.recv().await.unwrap();
+            assert_eq!(&pkt.data, b"hello");
+
+            // The downlink task holds the last sender, the channel closes
+            // once the idle session is removed and its task aborted.
+            let closed = tokio::time::timeout(Duration::from_secs(5), client_ch_rx.recv()).await;
+            assert!(matches!(closed, Ok(None)));
+            assert!(!nat_manager.contains_key(&raddr).await);
         });
     }
 }
@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Result of the last health check of an actor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Health {
+    /// Not checked yet.
+    Unknown,
+    /// Checked fine with the RTT.
+    Up(Duration),
+    Down,
+}
+
+/// The health check results of a failover outbound, shared by its TCP
+/// handler and the outbound manager so they can be read at runtime.
+pub struct HealthStatus {
+    actors: Vec<String>,
+    health: Mutex<Vec<Health>>,
+    // index of the actor tried first
+    preferred: AtomicUsize,
+}
+
+impl HealthStatus {
+    pub fn new(actors: Vec<String>, preferred: usize) -> Self {
+        let health = vec![Health::Unknown; actors.len()];
+        HealthStatus {
+            actors,
+            health: Mutex::new(health),
+            preferred: AtomicUsize::new(preferred),
+        }
+    }
+
+    pub fn actors(&self) -> &[String] {
+        &self.actors
+    }
+
+    pub fn set_health(&self, idx: usize, health: Health) {
+        self.health.lock().unwrap()[idx] = health;
+    }
+
+    pub fn set_preferred(&self, idx: usize) {
+        self.preferred.store(idx, Ordering::Relaxed);
+    }
+
+    /// Returns the tag of the actor tried first.
+    pub fn preferred(&self) -> &str {
+        &self.actors[self.preferred.load(Ordering::Relaxed)]
+    }
+
+    /// Returns the actor tags with their health.
+    pub fn health(&self) -> Vec<(String, Health)> {
+        let health = self.health.lock().unwrap();
+        self.actors
+            .iter()
+            .cloned()
+            .zip(health.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_status() {
+        let status = HealthStatus::new(vec!["a".to_string(), "b".to_string()], 1);
+        assert_eq!(status.preferred(), "b");
+        status.set_health(0, Health::Up(Duration::from_millis(100)));
+        status.set_health(1, Health::Down);
+        status.set_preferred(0);
+        assert_eq!(status.preferred(), "a");
+        assert_eq!(
+            status.health(),
+            vec![
+                ("a".to_string(), Health::Up(Duration::from_millis(100))),
+                ("b".to_string(), Health::Down)
+            ]
+        );
+    }
+}
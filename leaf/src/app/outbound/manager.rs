@@ -1,14 +1,19 @@
 use std::{
     collections::{hash_map, HashMap},
     convert::From,
-    net::{IpAddr, SocketAddr, SocketAddrV4},
+    fmt,
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
     str::FromStr,
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
 };
 
+use futures::future::AbortHandle;
 use log::*;
 use protobuf::Message;
 
+#[cfg(feature = "outbound-balance")]
+use crate::proxy::balance;
 #[cfg(feature = "outbound-chain")]
 use crate::proxy::chain;
 #[cfg(feature = "outbound-failover")]
@@ -17,12 +22,21 @@ use crate::proxy::failover;
 use crate::proxy::random;
 #[cfg(feature = "outbound-retry")]
 use crate::proxy::retry;
+#[cfg(feature = "outbound-select")]
+use crate::proxy::select;
+#[cfg(any(feature = "outbound-random", feature = "outbound-balance"))]
+use crate::proxy::sticky::StickyTable;
 #[cfg(feature = "outbound-tryall")]
 use crate::proxy::tryall;
+#[cfg(feature = "outbound-urltest")]
+use crate::proxy::urltest;
 
 #[cfg(feature = "outbound-stat")]
 use crate::proxy::stat;
 
+#[cfg(feature = "outbound-pt")]
+use crate::proxy::pt;
+
 #[cfg(feature = "outbound-direct")]
 use crate::proxy::direct;
 #[cfg(feature = "outbound-drop")]
@@ -33,6 +47,8 @@ use crate::proxy::redirect;
 use crate::proxy::shadowsocks;
 #[cfg(feature = "outbound-socks")]
 use crate::proxy::socks;
+#[cfg(feature = "outbound-ssh")]
+use crate::proxy::ssh;
 #[cfg(feature = "outbound-tls")]
 use crate::proxy::tls;
 #[cfg(feature = "outbound-trojan")]
@@ -41,30 +57,284 @@ use crate::proxy::trojan;
 use crate::proxy::vless;
 #[cfg(feature = "outbound-vmess")]
 use crate::proxy::vmess;
+#[cfg(feature = "outbound-wireguard")]
+use crate::proxy::wireguard;
 #[cfg(feature = "outbound-ws")]
 use crate::proxy::ws;
 
+use super::health::HealthStatus;
+use super::race::RaceStats;
+use super::registry;
+use super::selector::OutboundSelector;
 use crate::{
-    app::dns_client::DnsClient,
+    app::dns_client::{
+        AnswerFilter, CacheSettings, ClientSubnet, DnsClient, DnsServer, DnsStrategy,
+    },
     config::{self, Outbound, DNS},
-    proxy::{self, OutboundHandler, ProxyHandlerType},
+    option,
+    proxy::{self, OutboundHandler, ProxyHandlerType, TcpOptions},
 };
 
+/// Tag of the built-in outbound which resets the connections.
+pub const REJECT_TAG: &str = "reject";
+/// Tag of the built-in outbound which drops the connections silently.
+pub const REJECT_DROP_TAG: &str = "reject-drop";
+
+/// Why an outbound can't be added to a running instance.
+#[derive(Debug)]
+pub enum AddOutboundError {
+    /// An outbound with the same tag already exists.
+    DuplicateTag(String),
+    /// The outbound refers to an actor which doesn't exist, as (tag, actor).
+    MissingActor(String, String),
+    /// The outbound can't be loaded, e.g. invalid settings or the protocol
+    /// is not enabled, as (tag, reason).
+    Invalid(String, String),
+}
+
+impl fmt::Display for AddOutboundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddOutboundError::DuplicateTag(tag) => write!(f, "outbound [{}] already exists", tag),
+            AddOutboundError::MissingActor(tag, actor) => {
+                write!(f, "actor [{}] of outbound [{}] not found", actor, tag)
+            }
+            // The reason tells the tag.
+            AddOutboundError::Invalid(_, reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AddOutboundError {}
+
+/// Returns the timeout for establishing connections through the outbound.
+fn connect_timeout(outbound: &Outbound) -> Option<Duration> {
+    let secs = if outbound.connect_timeout > 0 {
+        outbound.connect_timeout as u64
+    } else {
+        option::CONNECT_TIMEOUT
+    };
+    Some(Duration::from_secs(secs))
+}
+
+/// Returns the socket options for TCP connections dialed by the outbound.
+fn tcp_options(outbound: &Outbound) -> TcpOptions {
+    TcpOptions {
+        nodelay: !outbound.disable_tcp_nodelay,
+        keepalive: if outbound.tcp_keepalive > 0 {
+            Some(Duration::from_secs(outbound.tcp_keepalive as u64))
+        } else {
+            None
+        },
+        interface: bind_interface(outbound),
+    }
+}
+
+/// Returns the network interface the outbound binds to, `bind` is the name
+/// of an interface unless it's an IP address.
+fn bind_interface(outbound: &Outbound) -> Option<String> {
+    if outbound.bind.is_empty() || outbound.bind.parse::<IpAddr>().is_ok() {
+        None
+    } else {
+        Some(outbound.bind.clone())
+    }
+}
+
+/// Returns the DNS client resolving with the group of the outbound, or the
+/// default one.
+fn outbound_dns_client(outbound: &Outbound, dns_client: &Arc<DnsClient>) -> Arc<DnsClient> {
+    if outbound.dns.is_empty() {
+        return dns_client.clone();
+    }
+    match dns_client.for_group(&outbound.dns) {
+        Some(client) => client,
+        None => {
+            warn!(
+                "dns server group {} of outbound [{}] not found",
+                &outbound.dns, &outbound.tag
+            );
+            dns_client.clone()
+        }
+    }
+}
+
+/// Returns the UDP over TCP version if enabled, version 2 is the default.
+#[cfg(any(feature = "outbound-shadowsocks", feature = "outbound-trojan"))]
+fn udp_over_tcp(enabled: bool, version: u32) -> anyhow::Result<Option<u32>> {
+    if !enabled {
+        return Ok(None);
+    }
+    match version {
+        0 => Ok(Some(2)),
+        1 | 2 => Ok(Some(version)),
+        _ => Err(anyhow::anyhow!(
+            "unsupported udp over tcp version {}",
+            version
+        )),
+    }
+}
+
+/// Returns the TLS handler wrapped by a trojan outbound itself, the server
+/// name defaults to the address of the server.
+#[cfg(feature = "outbound-trojan")]
+fn trojan_tls(
+    settings: &config::TrojanOutboundSettings,
+) -> anyhow::Result<Arc<dyn proxy::TcpOutboundHandler>> {
+    #[cfg(feature = "outbound-tls")]
+    {
+        let server_name = if settings.server_name.is_empty() {
+            settings.address.clone()
+        } else {
+            settings.server_name.clone()
+        };
+        let options = tls::ClientOptions {
+            alpns: settings.alpn.to_vec(),
+            insecure: settings.insecure,
+            ..Default::default()
+        };
+        Ok(Arc::new(tls::TcpHandler::new(server_name, options, false)))
+    }
+    #[cfg(not(feature = "outbound-tls"))]
+    {
+        let _ = settings;
+        Err(anyhow::anyhow!("tls requires the outbound-tls feature"))
+    }
+}
+
+/// Returns the table of sticky sessions if the timeout in seconds is set.
+#[cfg(any(feature = "outbound-random", feature = "outbound-balance"))]
+fn sticky_table(timeout: u32) -> Option<Arc<StickyTable>> {
+    if timeout == 0 {
+        return None;
+    }
+    Some(Arc::new(StickyTable::new(Duration::from_secs(
+        timeout as u64,
+    ))))
+}
+
+/// Returns the tags of the handlers an ensemble outbound refers to.
+fn actor_tags(outbound: &Outbound) -> Vec<String> {
+    let actors =
+        match outbound.protocol.as_str() {
+            "tryall" => config::TryAllOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "random" => config::RandomOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "balance" => config::BalanceOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "select" => config::SelectOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "urltest" => config::UrlTestOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "failover" => config::FailOverOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "chain" => config::ChainOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            "retry" => config::RetryOutboundSettings::parse_from_bytes(&outbound.settings)
+                .map(|s| s.actors),
+            _ => return Vec::new(),
+        };
+    actors.map(|a| a.into_vec()).unwrap_or_default()
+}
+
 pub struct OutboundManager {
     handlers: HashMap<String, Arc<dyn OutboundHandler>>,
     default_handler: Option<String>,
+    dns_client: Arc<DnsClient>,
+    idle_timeouts: HashMap<String, Duration>,
+    selectors: HashMap<String, Arc<OutboundSelector>>,
+    health_statuses: HashMap<String, Arc<HealthStatus>>,
+    race_stats: HashMap<String, Arc<RaceStats>>,
+    // Background tasks of the handlers, e.g. health checks, to be stopped
+    // once the outbound is removed.
+    abort_handles: HashMap<String, Vec<AbortHandle>>,
 }
 
 impl OutboundManager {
+    /// Creates the handlers of the outbounds, the ones that can't be loaded
+    /// are skipped with a warning.
     pub fn new(outbounds: &protobuf::RepeatedField<Outbound>, dns: &DNS) -> Self {
+        let (manager, errors) = Self::build(outbounds, dns);
+        for e in errors {
+            warn!("{}", e);
+        }
+        manager
+    }
+
+    /// Creates the handlers of the outbounds, fails on the first outbound
+    /// that can't be loaded.
+    pub fn try_new(
+        outbounds: &protobuf::RepeatedField<Outbound>,
+        dns: &DNS,
+    ) -> Result<Self, AddOutboundError> {
+        let (manager, errors) = Self::build(outbounds, dns);
+        if let Some(e) = errors.into_iter().next() {
+            return Err(e);
+        }
+        Ok(manager)
+    }
+
+    fn build(
+        outbounds: &protobuf::RepeatedField<Outbound>,
+        dns: &DNS,
+    ) -> (Self, Vec<AddOutboundError>) {
         let mut handlers: HashMap<String, Arc<dyn OutboundHandler>> = HashMap::new();
         let mut default_handler: Option<String> = None;
-        let mut dns_servers = Vec::new();
+        let mut dns_server_outbounds = HashMap::new();
+        let mut dns_server_bootstraps = HashMap::new();
+        let mut dns_server_ca_files = HashMap::new();
         let mut dns_hosts = HashMap::new();
-        for dns_server in dns.servers.iter() {
-            if let Ok(ip) = dns_server.parse::<IpAddr>() {
-                dns_servers.push(SocketAddr::new(ip, 53));
+        // Servers of the groups are set up the same as the default ones.
+        let mut parse_server = |dns_server: &String| {
+            let server = match dns_server.parse::<DnsServer>() {
+                Ok(s) => s,
+                Err(e) => {
+                    warn!("{}", e);
+                    return None;
+                }
+            };
+            // The outbound for all servers applies to the plain ones only.
+            if let Some(tag) = dns.server_outbounds.get(dns_server) {
+                dns_server_outbounds.insert(server.clone(), tag.to_owned());
+            } else if !dns.outbound.is_empty() {
+                if let DnsServer::Udp(_) = server {
+                    dns_server_outbounds.insert(server.clone(), dns.outbound.clone());
+                }
+            }
+            match &server {
+                DnsServer::Udp(_) => (),
+                DnsServer::Https { host, .. } | DnsServer::Tls { host, .. } => {
+                    if let Some(ip) = dns.server_bootstraps.get(dns_server) {
+                        match ip.parse::<IpAddr>() {
+                            Ok(ip) => {
+                                dns_server_bootstraps.insert(host.to_owned(), ip);
+                            }
+                            Err(e) => {
+                                warn!("invalid bootstrap ip for dns server {}: {}", dns_server, e);
+                            }
+                        }
+                    }
+                    if let Some(ca_file) = dns.server_ca_files.get(dns_server) {
+                        dns_server_ca_files.insert(host.to_owned(), ca_file.to_owned());
+                    }
+                }
             }
+            Some(server)
+        };
+        let dns_servers: Vec<DnsServer> =
+            dns.servers.iter().filter_map(&mut parse_server).collect();
+        let mut dns_groups = HashMap::new();
+        for (name, servers) in dns.groups.iter() {
+            let servers: Vec<DnsServer> = servers
+                .values
+                .iter()
+                .filter_map(&mut parse_server)
+                .collect();
+            if servers.is_empty() {
+                warn!("no valid servers in dns server group {}", name);
+                continue;
+            }
+            dns_groups.insert(name.to_owned(), servers);
         }
         for (name, ips) in dns.hosts.iter() {
             dns_hosts.insert(name.to_owned(), ips.values.to_vec());
@@ -83,52 +353,192 @@ impl OutboundManager {
             };
             SocketAddr::from(addr)
         };
-        let dns_client = Arc::new(DnsClient::new(dns_servers, dns_hosts, dns_bind_addr));
+        let mut dns_client = DnsClient::new(dns_servers, dns_hosts, dns_bind_addr);
+        dns_client.set_answer_filter(AnswerFilter {
+            filter_private: dns.filter_private,
+            filter_bogons: dns.filter_bogons,
+            internal_domains: dns.internal_domains.to_vec(),
+        });
+        dns_client.set_policies(dns_groups, &dns.policies);
+        dns_client.set_server_outbounds(dns_server_outbounds);
+        dns_client.set_server_bootstraps(dns_server_bootstraps);
+        dns_client.set_server_ca_files(dns_server_ca_files);
+        if dns.dot_idle_timeout > 0 {
+            dns_client.set_dot_idle_timeout(Duration::from_secs(dns.dot_idle_timeout as u64));
+        }
+        dns_client.set_fail_open(dns.fail_open, dns.fallback_ip.parse::<IpAddr>().ok());
+        if let Some(strategy) = DnsStrategy::from_config(dns.strategy) {
+            dns_client.set_strategy(strategy);
+        }
+        if let Some(client_subnet) = ClientSubnet::from_config(&dns.client_subnet) {
+            dns_client.set_client_subnet(client_subnet);
+        }
+        let mut cache_settings = CacheSettings::default();
+        if dns.cache_size > 0 {
+            cache_settings.size = dns.cache_size as usize;
+        }
+        cache_settings.min_ttl = dns.min_cache_ttl as u64;
+        if dns.max_cache_ttl > 0 {
+            cache_settings.max_ttl = dns.max_cache_ttl as u64;
+        }
+        if dns.negative_cache_ttl > 0 {
+            cache_settings.negative_ttl = dns.negative_cache_ttl as u64;
+        }
+        cache_settings.prefetch = dns.prefetch;
+        if dns.prefetch_min_hits > 0 {
+            cache_settings.prefetch_min_hits = dns.prefetch_min_hits;
+        }
+        dns_client.set_cache_settings(cache_settings);
+        let dns_client = Arc::new(dns_client);
+
+        let mut selectors = HashMap::new();
+        let mut health_statuses = HashMap::new();
+        let mut race_stats = HashMap::new();
+        let mut abort_handles = HashMap::new();
+        let errors = Self::load_handlers(
+            outbounds,
+            dns_client.clone(),
+            &mut handlers,
+            &mut default_handler,
+            &mut selectors,
+            &mut health_statuses,
+            &mut race_stats,
+            &mut abort_handles,
+        );
+        #[cfg(feature = "outbound-drop")]
+        Self::add_reject_handlers(&mut handlers);
+        dns_client.bind_outbounds(&handlers);
+        dns_client.enable_prefetch();
+
+        let mut idle_timeouts = HashMap::new();
+        Self::load_idle_timeouts(outbounds, &mut idle_timeouts);
+
+        let manager = OutboundManager {
+            handlers,
+            default_handler,
+            dns_client,
+            idle_timeouts,
+            selectors,
+            health_statuses,
+            race_stats,
+            abort_handles,
+        };
+        (manager, errors)
+    }
 
+    fn load_idle_timeouts(
+        outbounds: &protobuf::RepeatedField<Outbound>,
+        idle_timeouts: &mut HashMap<String, Duration>,
+    ) {
+        for outbound in outbounds.iter() {
+            if outbound.idle_timeout > 0 {
+                idle_timeouts.insert(
+                    outbound.tag.clone(),
+                    Duration::from_secs(outbound.idle_timeout as u64),
+                );
+            }
+        }
+    }
+
+    // Rules can target the built-in rejecting outbounds without defining
+    // them, outbounds defined with the same tags take precedence.
+    #[cfg(feature = "outbound-drop")]
+    fn add_reject_handlers(handlers: &mut HashMap<String, Arc<dyn OutboundHandler>>) {
+        for (tag, reset) in &[(REJECT_TAG, true), (REJECT_DROP_TAG, false)] {
+            if handlers.contains_key(*tag) {
+                continue;
+            }
+            let tcp = Box::new(drop::TcpHandler { reset: *reset });
+            let udp = Box::new(drop::UdpHandler);
+            let handler = proxy::outbound::Handler::new(
+                tag.to_string(),
+                colored::Color::Red,
+                ProxyHandlerType::Endpoint,
+                Some(tcp),
+                Some(udp),
+                None,
+            );
+            handlers.insert(tag.to_string(), handler);
+        }
+    }
+
+    fn load_handlers(
+        outbounds: &protobuf::RepeatedField<Outbound>,
+        dns_client: Arc<DnsClient>,
+        handlers: &mut HashMap<String, Arc<dyn OutboundHandler>>,
+        default_handler: &mut Option<String>,
+        selectors: &mut HashMap<String, Arc<OutboundSelector>>,
+        health_statuses: &mut HashMap<String, Arc<HealthStatus>>,
+        race_stats: &mut HashMap<String, Arc<RaceStats>>,
+        abort_handles: &mut HashMap<String, Vec<AbortHandle>>,
+    ) -> Vec<AddOutboundError> {
+        // Why the outbounds failed to load, the last failure is kept for
+        // ensemble outbounds loaded in several rounds.
+        let mut failures: HashMap<String, String> = HashMap::new();
         for outbound in outbounds.iter() {
             let tag = String::from(&outbound.tag);
             if default_handler.is_none() {
-                default_handler = Some(String::from(&outbound.tag));
+                *default_handler = Some(String::from(&outbound.tag));
                 debug!("default handler [{}]", &outbound.tag);
             }
-            let bind_addr = {
-                let addr = format!("{}:0", &outbound.bind);
-                let addr = match SocketAddrV4::from_str(&addr) {
-                    Ok(a) => a,
-                    Err(e) => {
-                        error!(
-                            "invalid bind addr [{}] in outbound {}: {}",
-                            &outbound.bind, &outbound.tag, e
-                        );
-                        panic!("");
-                    }
-                };
-                SocketAddr::from(addr)
-            };
+            // With an interface, the interface picks the source address.
+            // The unspecified address is switched to the family of the
+            // address dialed.
+            let bind_addr = SocketAddr::new(
+                outbound
+                    .bind
+                    .parse::<IpAddr>()
+                    .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+                0,
+            );
+            let dns_client = outbound_dns_client(outbound, &dns_client);
             match outbound.protocol.as_str() {
                 #[cfg(feature = "outbound-direct")]
                 "direct" => {
-                    let tcp = Box::new(direct::TcpHandler::new(bind_addr, dns_client.clone()));
-                    let udp = Box::new(direct::UdpHandler::new(bind_addr, dns_client.clone()));
+                    let tcp = Box::new(direct::TcpHandler::new(
+                        bind_addr,
+                        tcp_options(outbound),
+                        dns_client.clone(),
+                    ));
+                    let udp = Box::new(direct::UdpHandler::new(
+                        bind_addr,
+                        bind_interface(outbound),
+                        dns_client.clone(),
+                    ));
                     let handler = proxy::outbound::Handler::new(
                         tag.clone(),
                         colored::Color::Green,
                         ProxyHandlerType::Direct,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
                 #[cfg(feature = "outbound-drop")]
                 "drop" => {
-                    let tcp = Box::new(drop::TcpHandler {});
-                    let udp = Box::new(drop::UdpHandler {});
+                    let settings =
+                        match config::DropOutboundSettings::parse_from_bytes(&outbound.settings) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                    let tcp = Box::new(drop::TcpHandler {
+                        reset: settings.reset,
+                    });
+                    let udp = Box::new(drop::UdpHandler);
                     let handler = proxy::outbound::Handler::new(
                         tag.clone(),
                         colored::Color::Red,
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
@@ -139,7 +549,10 @@ impl OutboundManager {
                     ) {
                         Ok(s) => s,
                         Err(e) => {
-                            warn!("invalid [{}] outbound settings: {}", &tag, e);
+                            failures.insert(
+                                tag.clone(),
+                                format!("invalid [{}] outbound settings: {}", &tag, e),
+                            );
                             continue;
                         }
                     };
@@ -157,6 +570,7 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
@@ -166,7 +580,10 @@ impl OutboundManager {
                         match config::SocksOutboundSettings::parse_from_bytes(&outbound.settings) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -174,12 +591,14 @@ impl OutboundManager {
                         address: settings.address.clone(),
                         port: settings.port as u16,
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let udp = Box::new(socks::outbound::UdpHandler {
                         address: settings.address.clone(),
                         port: settings.port as u16,
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let handler = proxy::outbound::Handler::new(
@@ -192,6 +611,7 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
@@ -202,32 +622,83 @@ impl OutboundManager {
                     ) {
                         Ok(s) => s,
                         Err(e) => {
-                            warn!("invalid [{}] outbound settings: {}", &tag, e);
+                            failures.insert(
+                                tag.clone(),
+                                format!("invalid [{}] outbound settings: {}", &tag, e),
+                            );
                             continue;
                         }
                     };
+                    let (obfs, plugin) = if settings.plugin.is_empty() {
+                        (None, None)
+                    } else {
+                        match shadowsocks::ObfsSettings::parse(
+                            &settings.plugin,
+                            &settings.plugin_opts,
+                        ) {
+                            Ok(v) => (Some(v), Some(settings.plugin.clone())),
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        }
+                    };
+                    let udp_over_tcp =
+                        match udp_over_tcp(settings.udp_over_tcp, settings.udp_over_tcp_version) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
                     let tcp = Box::new(shadowsocks::TcpHandler {
                         address: settings.address.clone(),
                         port: settings.port as u16,
                         cipher: settings.method.clone(),
                         password: settings.password.clone(),
+                        obfs: obfs.clone(),
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
-                    let udp = Box::new(shadowsocks::UdpHandler {
-                        address: settings.address,
-                        port: settings.port as u16,
-                        cipher: settings.method,
-                        password: settings.password,
-                        bind_addr,
-                        dns_client: dns_client.clone(),
-                    });
+                    // The UDP framing of the 2022 methods is not implemented,
+                    // their UDP is only relayed over TCP.
+                    let udp: Option<Box<dyn proxy::UdpOutboundHandler>> =
+                        if shadowsocks::aead_cipher_2022(&settings.method).is_some()
+                            && udp_over_tcp.is_none()
+                        {
+                            warn!(
+                                "[{}] udp disabled, {} requires udpOverTcp",
+                                &tag, &settings.method
+                            );
+                            None
+                        } else {
+                            Some(Box::new(shadowsocks::UdpHandler {
+                                address: settings.address,
+                                port: settings.port as u16,
+                                cipher: settings.method,
+                                password: settings.password,
+                                plugin,
+                                obfs,
+                                udp_over_tcp,
+                                bind_addr,
+                                tcp_options: tcp_options(outbound),
+                                dns_client: dns_client.clone(),
+                            }))
+                        };
                     let handler = proxy::outbound::Handler::new(
                         tag.clone(),
                         colored::Color::Blue,
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
-                        Some(udp),
+                        udp,
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag, handler);
                 }
@@ -238,22 +709,55 @@ impl OutboundManager {
                     ) {
                         Ok(s) => s,
                         Err(e) => {
-                            warn!("invalid [{}] outbound settings: {}", &tag, e);
+                            failures.insert(
+                                tag.clone(),
+                                format!("invalid [{}] outbound settings: {}", &tag, e),
+                            );
                             continue;
                         }
                     };
+                    let udp_over_tcp =
+                        match udp_over_tcp(settings.udp_over_tcp, settings.udp_over_tcp_version) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                    let tls = if settings.tls {
+                        match trojan_tls(&settings) {
+                            Ok(v) => Some(v),
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        }
+                    } else {
+                        None
+                    };
                     let tcp = Box::new(trojan::outbound::TcpHandler {
                         address: settings.address.clone(),
                         port: settings.port as u16,
                         password: settings.password.clone(),
+                        tls: tls.clone(),
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let udp = Box::new(trojan::outbound::UdpHandler {
                         address: settings.address,
                         port: settings.port as u16,
                         password: settings.password,
+                        tls,
+                        udp_over_tcp,
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let handler = proxy::outbound::Handler::new(
@@ -262,6 +766,7 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag, handler);
                 }
@@ -271,7 +776,10 @@ impl OutboundManager {
                         match config::VMessOutboundSettings::parse_from_bytes(&outbound.settings) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -281,7 +789,10 @@ impl OutboundManager {
                         port: settings.port as u16,
                         uuid: settings.uuid.clone(),
                         security: settings.security.clone(),
+                        legacy_header: settings.legacy_header,
+                        reduce_rtt: settings.reduce_rtt,
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let udp = Box::new(vmess::UdpHandler {
@@ -289,7 +800,9 @@ impl OutboundManager {
                         port: settings.port as u16,
                         uuid: settings.uuid.clone(),
                         security: settings.security.clone(),
+                        legacy_header: settings.legacy_header,
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let handler = proxy::outbound::Handler::new(
@@ -298,6 +811,7 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag, handler);
                 }
@@ -307,16 +821,41 @@ impl OutboundManager {
                         match config::VLessOutboundSettings::parse_from_bytes(&outbound.settings) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
 
+                    if !settings.encryption.is_empty() && settings.encryption != "none" {
+                        failures.insert(
+                            tag.clone(),
+                            format!(
+                                "invalid [{}] outbound settings: unsupported encryption {}",
+                                &tag, &settings.encryption
+                            ),
+                        );
+                        continue;
+                    }
+                    let flow = match settings.flow.parse::<vless::Flow>() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            failures.insert(
+                                tag.clone(),
+                                format!("invalid [{}] outbound settings: {}", &tag, e),
+                            );
+                            continue;
+                        }
+                    };
                     let tcp = Box::new(vless::TcpHandler {
                         address: settings.address.clone(),
                         port: settings.port as u16,
                         uuid: settings.uuid.clone(),
+                        flow,
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let udp = Box::new(vless::UdpHandler {
@@ -324,6 +863,7 @@ impl OutboundManager {
                         port: settings.port as u16,
                         uuid: settings.uuid.clone(),
                         bind_addr,
+                        tcp_options: tcp_options(outbound),
                         dns_client: dns_client.clone(),
                     });
                     let handler = proxy::outbound::Handler::new(
@@ -332,6 +872,117 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
+                    );
+                    handlers.insert(tag, handler);
+                }
+                #[cfg(feature = "outbound-wireguard")]
+                "wireguard" => {
+                    let settings = match config::WireGuardOutboundSettings::parse_from_bytes(
+                        &outbound.settings,
+                    ) {
+                        Ok(s) => s,
+                        Err(e) => {
+                            failures.insert(
+                                tag.clone(),
+                                format!("invalid [{}] outbound settings: {}", &tag, e),
+                            );
+                            continue;
+                        }
+                    };
+                    let config =
+                        match wireguard_config(&settings, bind_addr, bind_interface(outbound)) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                    let tunnel = Arc::new(wireguard::Tunnel::new(config, dns_client.clone()));
+                    let tcp = Box::new(wireguard::TcpHandler {
+                        tunnel: tunnel.clone(),
+                    });
+                    let udp = Box::new(wireguard::UdpHandler { tunnel });
+                    let handler = proxy::outbound::Handler::new(
+                        tag.clone(),
+                        colored::Color::TrueColor {
+                            r: 136,
+                            g: 23,
+                            b: 26,
+                        },
+                        ProxyHandlerType::Endpoint,
+                        Some(tcp),
+                        Some(udp),
+                        connect_timeout(outbound),
+                    );
+                    handlers.insert(tag, handler);
+                }
+                #[cfg(feature = "outbound-ssh")]
+                "ssh" => {
+                    let settings =
+                        match config::SshOutboundSettings::parse_from_bytes(&outbound.settings) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                    let auth = if settings.private_key.is_empty() {
+                        ssh::Auth::Password(settings.password.clone())
+                    } else {
+                        let passphrase = if settings.private_key_passphrase.is_empty() {
+                            None
+                        } else {
+                            Some(settings.private_key_passphrase.as_bytes())
+                        };
+                        match thrussh_keys::load_secret_key(&settings.private_key, passphrase) {
+                            Ok(key) => ssh::Auth::PublicKey(Arc::new(key)),
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound private key: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        }
+                    };
+                    let host_key_fingerprint = if settings.host_key_fingerprint.is_empty() {
+                        warn!(
+                            "[{}] accepts any ssh host key, set the host key fingerprint",
+                            &tag
+                        );
+                        None
+                    } else {
+                        Some(settings.host_key_fingerprint.clone())
+                    };
+                    let tcp = Box::new(ssh::TcpHandler {
+                        address: settings.address.clone(),
+                        port: settings.port as u16,
+                        username: settings.username.clone(),
+                        auth,
+                        host_key_fingerprint,
+                        bind_addr,
+                        tcp_options: tcp_options(outbound),
+                        dns_client: dns_client.clone(),
+                        connections: std::sync::Mutex::new(Vec::new()),
+                    });
+                    let handler = proxy::outbound::Handler::new(
+                        tag.clone(),
+                        colored::Color::TrueColor {
+                            r: 46,
+                            g: 139,
+                            b: 87,
+                        },
+                        ProxyHandlerType::Endpoint,
+                        Some(tcp),
+                        None,
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag, handler);
                 }
@@ -341,7 +992,10 @@ impl OutboundManager {
                         match config::TlsOutboundSettings::parse_from_bytes(&outbound.settings) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -349,10 +1003,60 @@ impl OutboundManager {
                     for alpn in settings.alpn.iter() {
                         alpns.push(alpn.clone());
                     }
-                    let tcp = Box::new(tls::TcpHandler {
-                        server_name: settings.server_name.clone(),
+                    let fingerprint = if settings.fingerprint.is_empty() {
+                        None
+                    } else {
+                        match settings.fingerprint.parse::<tls::Fingerprint>() {
+                            Ok(fp) => Some(fp),
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        }
+                    };
+                    let cert_sha256 = if settings.cert_sha256.is_empty() {
+                        None
+                    } else {
+                        match hex::decode(&settings.cert_sha256) {
+                            Ok(v) if v.len() == 32 => Some(v),
+                            _ => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!(
+                                    "invalid [{}] outbound settings: invalid certificate sha256",
+                                    &tag
+                                ),
+                                );
+                                continue;
+                            }
+                        }
+                    };
+                    let mut options = tls::ClientOptions {
                         alpns: alpns.clone(),
-                    });
+                        fingerprint,
+                        insecure: settings.insecure,
+                        cert_sha256,
+                        ..Default::default()
+                    };
+                    if !settings.client_cert.is_empty() {
+                        if let Err(e) =
+                            options.load_client_cert(&settings.client_cert, &settings.client_key)
+                        {
+                            failures.insert(
+                                tag.clone(),
+                                format!("load client certificate of [{}] failed: {}", &tag, e),
+                            );
+                            continue;
+                        }
+                    }
+                    let tcp = Box::new(tls::TcpHandler::new(
+                        settings.server_name.clone(),
+                        options,
+                        settings.early_data,
+                    ));
                     let handler = proxy::outbound::Handler::new(
                         tag.clone(),
                         colored::Color::TrueColor {
@@ -363,6 +1067,7 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         None,
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
@@ -373,15 +1078,33 @@ impl OutboundManager {
                     ) {
                         Ok(s) => s,
                         Err(e) => {
-                            warn!("invalid [{}] outbound settings: {}", &tag, e);
+                            failures.insert(
+                                tag.clone(),
+                                format!("invalid [{}] outbound settings: {}", &tag, e),
+                            );
                             continue;
                         }
                     };
-                    let tcp = Box::new(ws::outbound::TcpHandler {
+                    let early_data_header_name = if settings.early_data_header_name.is_empty() {
+                        ws::outbound::tcp::DEFAULT_EARLY_DATA_HEADER_NAME.to_string()
+                    } else {
+                        settings.early_data_header_name.clone()
+                    };
+                    let tcp = ws::outbound::TcpHandler {
                         path: settings.path.clone(),
                         headers: settings.headers.clone(),
+                        max_early_data: settings.max_early_data as usize,
+                        early_data_header_name,
                         dns_client: dns_client.clone(),
-                    });
+                    };
+                    if let Err(e) = tcp.check() {
+                        failures.insert(
+                            tag.clone(),
+                            format!("invalid [{}] outbound settings: {}", &tag, e),
+                        );
+                        continue;
+                    }
+                    let tcp = Box::new(tcp);
                     let handler = proxy::outbound::Handler::new(
                         tag.clone(),
                         colored::Color::TrueColor {
@@ -392,6 +1115,7 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         None,
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
@@ -401,7 +1125,10 @@ impl OutboundManager {
                         match config::HTTP2OutboundSettings::parse_from_bytes(&outbound.settings) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -419,6 +1146,57 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         None,
+                        connect_timeout(outbound),
+                    );
+                    handlers.insert(tag.clone(), handler);
+                }
+                #[cfg(feature = "outbound-pt")]
+                "pt" => {
+                    let settings =
+                        match config::PluggableTransportOutboundSettings::parse_from_bytes(
+                            &outbound.settings,
+                        ) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                    let tcp = match pt::TcpHandler::new(
+                        &settings.path,
+                        &settings.args,
+                        settings.transport.clone(),
+                        settings.address.clone(),
+                        settings.port as u16,
+                        settings.options.clone(),
+                        &settings.state_dir,
+                    ) {
+                        Ok(v) => Box::new(v),
+                        Err(e) => {
+                            failures.insert(
+                                tag.clone(),
+                                format!(
+                                    "launch [{}] pt client {} failed: {}",
+                                    &tag, &settings.path, e
+                                ),
+                            );
+                            continue;
+                        }
+                    };
+                    let handler = proxy::outbound::Handler::new(
+                        tag.clone(),
+                        colored::Color::TrueColor {
+                            r: 102,
+                            g: 51,
+                            b: 153,
+                        },
+                        ProxyHandlerType::Endpoint,
+                        Some(tcp),
+                        None,
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
@@ -428,7 +1206,10 @@ impl OutboundManager {
                         match config::StatOutboundSettings::parse_from_bytes(&outbound.settings) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -443,13 +1224,35 @@ impl OutboundManager {
                         ProxyHandlerType::Endpoint,
                         Some(tcp),
                         Some(udp),
+                        connect_timeout(outbound),
                     );
                     handlers.insert(tag.clone(), handler);
                 }
-                _ => (),
+                protocol => {
+                    // Ensemble protocols are loaded below and replace any
+                    // handler registered for them.
+                    if let Some(factory) = registry::get(protocol) {
+                        match factory(outbound, dns_client.clone()) {
+                            Ok(handler) => {
+                                handlers.insert(tag.clone(), handler);
+                            }
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("load [{}] outbound failed: {}", &tag, e),
+                                );
+                            }
+                        }
+                    }
+                }
             }
         }
 
+        // Probing tasks of urltest outbounds, kept across rounds with the
+        // selectors.
+        #[cfg(feature = "outbound-urltest")]
+        let mut checkers: HashMap<String, Arc<urltest::Checker>> = HashMap::new();
+
         // FIXME a better way to find outbound deps?
         for _i in 0..4 {
             for outbound in outbounds.iter() {
@@ -462,26 +1265,40 @@ impl OutboundManager {
                         ) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
                         let mut actors = Vec::new();
+                        let mut actor_tags = Vec::new();
                         for actor in settings.actors.iter() {
                             if let Some(a) = handlers.get(actor) {
                                 actors.push(a.clone());
+                                actor_tags.push(actor.clone());
                             }
                         }
                         if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
                             continue;
                         }
+                        // Kept across rounds as the health statuses.
+                        let stats = match race_stats.get(&tag) {
+                            Some(s) if s.actors() == actor_tags.as_slice() => s.clone(),
+                            _ => Arc::new(RaceStats::new(actor_tags)),
+                        };
+                        race_stats.insert(tag.clone(), stats.clone());
                         let tcp = Box::new(tryall::TcpHandler {
                             actors: actors.clone(),
                             delay_base: settings.delay_base,
+                            race_stats: stats.clone(),
                         });
                         let udp = Box::new(tryall::UdpHandler {
                             actors,
                             delay_base: settings.delay_base,
+                            race_stats: stats,
                         });
                         let handler = proxy::outbound::Handler::new(
                             tag.clone(),
@@ -493,6 +1310,7 @@ impl OutboundManager {
                             ProxyHandlerType::Ensemble,
                             Some(tcp),
                             Some(udp),
+                            None,
                         );
                         handlers.insert(tag.clone(), handler);
                     }
@@ -503,7 +1321,10 @@ impl OutboundManager {
                         ) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -514,12 +1335,102 @@ impl OutboundManager {
                             }
                         }
                         if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
                             continue;
                         }
+                        let sticky = sticky_table(settings.sticky_timeout);
                         let tcp = Box::new(random::TcpHandler {
                             actors: actors.clone(),
+                            sticky: sticky.clone(),
+                        });
+                        let udp = Box::new(random::UdpHandler { actors, sticky });
+                        let handler = proxy::outbound::Handler::new(
+                            tag.clone(),
+                            colored::Color::TrueColor {
+                                r: 182,
+                                g: 235,
+                                b: 250,
+                            },
+                            ProxyHandlerType::Ensemble,
+                            Some(tcp),
+                            Some(udp),
+                            None,
+                        );
+                        handlers.insert(tag.clone(), handler);
+                    }
+                    #[cfg(feature = "outbound-balance")]
+                    "balance" => {
+                        let settings = match config::BalanceOutboundSettings::parse_from_bytes(
+                            &outbound.settings,
+                        ) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                        if !settings.weights.is_empty()
+                            && settings.weights.len() != settings.actors.len()
+                        {
+                            failures.insert(
+                                tag.clone(),
+                                format!(
+                                    "invalid [{}] outbound settings: weights and actors mismatch",
+                                    &tag
+                                ),
+                            );
+                            continue;
+                        }
+                        let mut actors = Vec::new();
+                        let mut actor_tags = Vec::new();
+                        let mut weights = Vec::new();
+                        for (i, actor) in settings.actors.iter().enumerate() {
+                            if let Some(a) = handlers.get(actor) {
+                                actors.push(a.clone());
+                                actor_tags.push(actor.clone());
+                                weights.push(settings.weights.get(i).cloned().unwrap_or(1));
+                            }
+                        }
+                        if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
+                            continue;
+                        }
+                        let strategy = match settings.strategy {
+                            config::BalanceOutboundSettings_Strategy::ROUND_ROBIN => {
+                                balance::Strategy::RoundRobin
+                            }
+                            config::BalanceOutboundSettings_Strategy::WEIGHTED => {
+                                balance::Strategy::Weighted
+                            }
+                            config::BalanceOutboundSettings_Strategy::CONSISTENT_HASH => {
+                                balance::Strategy::ConsistentHash
+                            }
+                        };
+                        let balancer = match balance::Balancer::new(strategy, &actor_tags, weights)
+                        {
+                            Ok(b) => Arc::new(b),
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                        let sticky = sticky_table(settings.sticky_timeout);
+                        let tcp = Box::new(balance::TcpHandler {
+                            actors: actors.clone(),
+                            balancer: balancer.clone(),
+                            sticky: sticky.clone(),
+                        });
+                        let udp = Box::new(balance::UdpHandler {
+                            actors,
+                            balancer,
+                            sticky,
                         });
-                        let udp = Box::new(random::UdpHandler { actors });
                         let handler = proxy::outbound::Handler::new(
                             tag.clone(),
                             colored::Color::TrueColor {
@@ -530,9 +1441,154 @@ impl OutboundManager {
                             ProxyHandlerType::Ensemble,
                             Some(tcp),
                             Some(udp),
+                            None,
                         );
                         handlers.insert(tag.clone(), handler);
                     }
+                    #[cfg(feature = "outbound-select")]
+                    "select" => {
+                        let settings = match config::SelectOutboundSettings::parse_from_bytes(
+                            &outbound.settings,
+                        ) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                        let mut actors = Vec::new();
+                        let mut actor_tags = Vec::new();
+                        for actor in settings.actors.iter() {
+                            if let Some(a) = handlers.get(actor) {
+                                actors.push(a.clone());
+                                actor_tags.push(actor.clone());
+                            }
+                        }
+                        if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
+                            continue;
+                        }
+                        // Outbounds are loaded in rounds, the selector is kept so
+                        // that handlers loaded earlier referring to this outbound
+                        // follow the selection.
+                        let selector = match selectors.get(&tag) {
+                            Some(s) if s.actors() == actor_tags.as_slice() => s.clone(),
+                            _ => Arc::new(OutboundSelector::new(actor_tags)),
+                        };
+                        let tcp = Box::new(select::TcpHandler {
+                            actors: actors.clone(),
+                            selector: selector.clone(),
+                        });
+                        let udp = Box::new(select::UdpHandler {
+                            actors,
+                            selector: selector.clone(),
+                        });
+                        let handler = proxy::outbound::Handler::new(
+                            tag.clone(),
+                            colored::Color::TrueColor {
+                                r: 226,
+                                g: 103,
+                                b: 245,
+                            },
+                            ProxyHandlerType::Ensemble,
+                            Some(tcp),
+                            Some(udp),
+                            None,
+                        );
+                        handlers.insert(tag.clone(), handler);
+                        selectors.insert(tag.clone(), selector);
+                    }
+                    #[cfg(feature = "outbound-urltest")]
+                    "urltest" => {
+                        let settings = match config::UrlTestOutboundSettings::parse_from_bytes(
+                            &outbound.settings,
+                        ) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
+                                continue;
+                            }
+                        };
+                        let url = match urltest::parse_url(&settings.url) {
+                            Some(v) => v,
+                            None => {
+                                failures.insert(
+                                    tag.clone(),
+                                    format!(
+                                        "invalid [{}] outbound settings: invalid url {}",
+                                        &tag, &settings.url
+                                    ),
+                                );
+                                continue;
+                            }
+                        };
+                        let mut actors = Vec::new();
+                        let mut actor_tags = Vec::new();
+                        for actor in settings.actors.iter() {
+                            if let Some(a) = handlers.get(actor) {
+                                actors.push(a.clone());
+                                actor_tags.push(actor.clone());
+                            }
+                        }
+                        if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
+                            continue;
+                        }
+                        // The selector and the checker are kept across rounds as
+                        // the select outbound does, so there's one probing task
+                        // for the handlers of all rounds.
+                        let (selector, checker) = match (selectors.get(&tag), checkers.get(&tag)) {
+                            (Some(s), Some(c)) if s.actors() == actor_tags.as_slice() => {
+                                (s.clone(), c.clone())
+                            }
+                            _ => {
+                                let selector = Arc::new(OutboundSelector::new(actor_tags));
+                                let checker = Arc::new(urltest::Checker::new(
+                                    actors.clone(),
+                                    selector.clone(),
+                                    url,
+                                    Duration::from_secs(settings.interval as u64),
+                                    Duration::from_millis(settings.tolerance as u64),
+                                ));
+                                abort_handles
+                                    .entry(tag.clone())
+                                    .or_insert_with(Vec::new)
+                                    .push(checker.abort_handle());
+                                (selector, checker)
+                            }
+                        };
+                        let tcp = Box::new(urltest::TcpHandler {
+                            actors: actors.clone(),
+                            selector: selector.clone(),
+                            checker: checker.clone(),
+                        });
+                        let udp = Box::new(urltest::UdpHandler {
+                            actors,
+                            selector: selector.clone(),
+                            checker,
+                        });
+                        let handler = proxy::outbound::Handler::new(
+                            tag.clone(),
+                            colored::Color::TrueColor {
+                                r: 226,
+                                g: 103,
+                                b: 245,
+                            },
+                            ProxyHandlerType::Ensemble,
+                            Some(tcp),
+                            Some(udp),
+                            None,
+                        );
+                        handlers.insert(tag.clone(), handler);
+                        selectors.insert(tag.clone(), selector);
+                        checkers.insert(tag.clone(), checker);
+                    }
                     #[cfg(feature = "outbound-failover")]
                     "failover" => {
                         let settings = match config::FailOverOutboundSettings::parse_from_bytes(
@@ -540,19 +1596,45 @@ impl OutboundManager {
                         ) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
+                        if !settings.priorities.is_empty()
+                            && settings.priorities.len() != settings.actors.len()
+                        {
+                            failures.insert(
+                                tag.clone(),
+                                format!(
+                                "invalid [{}] outbound settings: priorities and actors mismatch",
+                                &tag
+                            ),
+                            );
+                            continue;
+                        }
                         let mut actors = Vec::new();
-                        for actor in settings.actors.iter() {
+                        let mut actor_tags = Vec::new();
+                        let mut priorities = Vec::new();
+                        for (i, actor) in settings.actors.iter().enumerate() {
                             if let Some(a) = handlers.get(actor) {
                                 actors.push(a.clone());
+                                actor_tags.push(actor.clone());
+                                priorities.push(settings.priorities.get(i).cloned().unwrap_or(0));
                             }
                         }
                         if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
                             continue;
                         }
+                        // Kept across rounds so the health checks of the
+                        // handlers of all rounds are reported.
+                        let health_status = match health_statuses.get(&tag) {
+                            Some(s) if s.actors() == actor_tags.as_slice() => s.clone(),
+                            _ => Arc::new(HealthStatus::new(actor_tags, 0)),
+                        };
                         let tcp = Box::new(failover::TcpHandler::new(
                             actors.clone(),
                             settings.fail_timeout,
@@ -562,14 +1644,24 @@ impl OutboundManager {
                             settings.fallback_cache,
                             settings.cache_size as usize,
                             settings.cache_timeout as u64,
+                            priorities.clone(),
+                            health_status,
                         ));
+                        health_statuses.insert(tag.clone(), tcp.health_status());
                         let udp = Box::new(failover::UdpHandler::new(
                             actors,
                             settings.fail_timeout,
                             settings.health_check,
                             settings.check_interval,
                             settings.failover,
+                            priorities,
                         ));
+                        // Handlers of the previous rounds may be actors of
+                        // others, their tasks are stopped together.
+                        abort_handles
+                            .entry(tag.clone())
+                            .or_insert_with(Vec::new)
+                            .extend(tcp.abort_handle().into_iter().chain(udp.abort_handle()));
                         let handler = proxy::outbound::Handler::new(
                             tag.clone(),
                             colored::Color::TrueColor {
@@ -580,6 +1672,7 @@ impl OutboundManager {
                             ProxyHandlerType::Ensemble,
                             Some(tcp),
                             Some(udp),
+                            None,
                         );
                         handlers.insert(tag.clone(), handler);
                     }
@@ -590,7 +1683,10 @@ impl OutboundManager {
                         ) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -601,14 +1697,17 @@ impl OutboundManager {
                             }
                         }
                         if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
                             continue;
                         }
                         let tcp = Box::new(chain::outbound::TcpHandler {
                             actors: actors.clone(),
+                            tcp_options: tcp_options(outbound),
                             dns_client: dns_client.clone(),
                         });
                         let udp = Box::new(chain::outbound::UdpHandler {
                             actors: actors.clone(),
+                            tcp_options: tcp_options(outbound),
                             dns_client: dns_client.clone(),
                         });
                         let handler = proxy::outbound::Handler::new(
@@ -621,6 +1720,7 @@ impl OutboundManager {
                             ProxyHandlerType::Ensemble,
                             Some(tcp),
                             Some(udp),
+                            connect_timeout(outbound),
                         );
                         handlers.insert(tag.clone(), handler);
                     }
@@ -631,7 +1731,10 @@ impl OutboundManager {
                         ) {
                             Ok(s) => s,
                             Err(e) => {
-                                warn!("invalid [{}] outbound settings: {}", &tag, e);
+                                failures.insert(
+                                    tag.clone(),
+                                    format!("invalid [{}] outbound settings: {}", &tag, e),
+                                );
                                 continue;
                             }
                         };
@@ -642,6 +1745,7 @@ impl OutboundManager {
                             }
                         }
                         if actors.is_empty() {
+                            failures.insert(tag.clone(), format!("no actors of [{}] loaded", &tag));
                             continue;
                         }
                         let tcp = Box::new(retry::TcpHandler {
@@ -662,6 +1766,7 @@ impl OutboundManager {
                             ProxyHandlerType::Ensemble,
                             Some(tcp),
                             Some(udp),
+                            None,
                         );
                         handlers.insert(tag.clone(), handler);
                     }
@@ -670,24 +1775,163 @@ impl OutboundManager {
             }
         }
 
-        OutboundManager {
-            handlers,
-            default_handler,
+        outbounds
+            .iter()
+            .filter(|outbound| !handlers.contains_key(&outbound.tag))
+            .map(|outbound| {
+                let reason = failures.remove(&outbound.tag).unwrap_or_else(|| {
+                    format!(
+                        "protocol {} of [{}] is not supported",
+                        &outbound.protocol, &outbound.tag
+                    )
+                });
+                AddOutboundError::Invalid(outbound.tag.clone(), reason)
+            })
+            .collect()
+    }
+
+    /// Adds handlers for the outbounds, ensemble outbounds can refer to the
+    /// existing handlers. Returns the outbounds that can't be loaded.
+    pub fn add_outbounds(
+        &mut self,
+        outbounds: &protobuf::RepeatedField<Outbound>,
+    ) -> Vec<AddOutboundError> {
+        let errors = Self::load_handlers(
+            outbounds,
+            self.dns_client.clone(),
+            &mut self.handlers,
+            &mut self.default_handler,
+            &mut self.selectors,
+            &mut self.health_statuses,
+            &mut self.race_stats,
+            &mut self.abort_handles,
+        );
+        self.dns_client.bind_outbounds(&self.handlers);
+        Self::load_idle_timeouts(outbounds, &mut self.idle_timeouts);
+        errors
+    }
+
+    /// Adds a handler for a single outbound, the other handlers are kept as
+    /// is. Actors of an ensemble outbound must already exist.
+    pub fn add_outbound(&mut self, outbound: &Outbound) -> Result<(), AddOutboundError> {
+        if self.handlers.contains_key(&outbound.tag) {
+            return Err(AddOutboundError::DuplicateTag(outbound.tag.clone()));
+        }
+        for actor in actor_tags(outbound) {
+            if !self.handlers.contains_key(&actor) {
+                return Err(AddOutboundError::MissingActor(outbound.tag.clone(), actor));
+            }
+        }
+        let outbounds = protobuf::RepeatedField::from_vec(vec![outbound.clone()]);
+        if let Some(e) = self.add_outbounds(&outbounds).into_iter().next() {
+            return Err(e);
         }
+        Ok(())
     }
 
     pub fn add(&mut self, tag: String, handler: Arc<dyn OutboundHandler>) {
         self.handlers.insert(tag, handler);
     }
 
+    /// Removes the outbound, its background tasks, e.g. health checks, are
+    /// stopped. Ensemble outbounds having it as an actor keep using it.
+    pub fn remove(&mut self, tag: &str) -> Option<Arc<dyn OutboundHandler>> {
+        if let Some(abort_handles) = self.abort_handles.remove(tag) {
+            for abort_handle in abort_handles {
+                abort_handle.abort();
+            }
+        }
+        self.selectors.remove(tag);
+        self.health_statuses.remove(tag);
+        self.race_stats.remove(tag);
+        self.idle_timeouts.remove(tag);
+        if self.default_handler.as_deref() == Some(tag) {
+            self.default_handler = None;
+        }
+        self.handlers.remove(tag)
+    }
+
     pub fn get(&self, tag: &str) -> Option<&Arc<dyn OutboundHandler>> {
         self.handlers.get(tag)
     }
 
+    /// Returns the handler the outbound currently sends connections to, the
+    /// selected actor of a select or urltest outbound, or the preferred one
+    /// of a failover outbound.
+    pub fn get_current(&self, tag: &str) -> Option<&Arc<dyn OutboundHandler>> {
+        let mut tag = tag;
+        // Groups may be nested, the depth is bounded in case of a cycle.
+        for _ in 0..8 {
+            if let Some(selector) = self.selectors.get(tag) {
+                tag = selector.get_selected();
+            } else if let Some(status) = self.health_statuses.get(tag) {
+                tag = status.preferred();
+            } else {
+                break;
+            }
+        }
+        self.get(tag)
+    }
+
+    /// Returns the selector of a select or urltest outbound, the RTTs
+    /// measured by a urltest outbound are kept in it.
+    pub fn get_selector(&self, tag: &str) -> Option<Arc<OutboundSelector>> {
+        self.selectors.get(tag).cloned()
+    }
+
+    /// Returns the health check results of a failover outbound.
+    pub fn get_health_status(&self, tag: &str) -> Option<Arc<HealthStatus>> {
+        self.health_statuses.get(tag).cloned()
+    }
+
+    /// Returns the races won by the actors of a tryall outbound.
+    pub fn get_race_stats(&self, tag: &str) -> Option<Arc<RaceStats>> {
+        self.race_stats.get(tag).cloned()
+    }
+
+    /// Returns the idle timeout of the outbound, `None` if not enabled.
+    pub fn idle_timeout(&self, tag: &str) -> Option<Duration> {
+        self.idle_timeouts.get(tag).cloned()
+    }
+
+    /// Returns the traffic of the handlers by tag, as (tx bytes, rx bytes,
+    /// active connections).
+    pub fn stats(&self) -> HashMap<String, (u64, u64, usize)> {
+        self.handlers
+            .iter()
+            .map(|(tag, h)| {
+                let stats = h.stats();
+                (
+                    tag.clone(),
+                    (
+                        stats.tx_bytes.load(Ordering::Relaxed),
+                        stats.rx_bytes.load(Ordering::Relaxed),
+                        stats.active_conns.load(Ordering::Relaxed),
+                    ),
+                )
+            })
+            .collect()
+    }
+
     pub fn default_handler(&self) -> Option<&String> {
         self.default_handler.as_ref()
     }
 
+    /// Sets the outbound for the connections matching no rules, rather than
+    /// the first loaded one. Returns false if the outbound doesn't exist.
+    pub fn set_default_handler(&mut self, tag: &str) -> bool {
+        if !self.handlers.contains_key(tag) {
+            return false;
+        }
+        debug!("default handler [{}]", tag);
+        self.default_handler = Some(tag.to_owned());
+        true
+    }
+
+    pub fn dns_client(&self) -> &Arc<DnsClient> {
+        &self.dns_client
+    }
+
     pub fn handlers(&self) -> Handlers {
         Handlers {
             inner: self.handlers.values(),
@@ -706,3 +1950,226 @@ impl<'a> Iterator for Handlers<'a> {
         self.inner.next()
     }
 }
+
+#[cfg(feature = "outbound-wireguard")]
+fn wireguard_config(
+    settings: &config::WireGuardOutboundSettings,
+    bind_addr: SocketAddr,
+    interface: Option<String>,
+) -> anyhow::Result<wireguard::Config> {
+    use anyhow::anyhow;
+
+    let private_key = settings
+        .private_key
+        .parse()
+        .map_err(|e| anyhow!("invalid private key: {}", e))?;
+    let peer_public_key = settings
+        .peer_public_key
+        .parse()
+        .map_err(|e| anyhow!("invalid public key: {}", e))?;
+    let preshared_key = if settings.preshared_key.is_empty() {
+        None
+    } else {
+        let key = base64::decode(&settings.preshared_key)?;
+        if key.len() != 32 {
+            return Err(anyhow!("invalid preshared key"));
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(&key);
+        Some(buf)
+    };
+    // The prefix length, if any, is irrelevant as all traffic goes to the
+    // peer.
+    let local_ip = settings
+        .local_address
+        .splitn(2, '/')
+        .next()
+        .unwrap_or("")
+        .parse()
+        .map_err(|e| anyhow!("invalid local address: {}", e))?;
+    let mut allowed_ips = Vec::new();
+    for ip in settings.allowed_ips.iter() {
+        allowed_ips.push(
+            ip.parse::<cidr::IpCidr>()
+                .map_err(|e| anyhow!("invalid allowed ip {}: {}", ip, e))?,
+        );
+    }
+    let keepalive = if settings.keepalive > 0 {
+        Some(settings.keepalive as u16)
+    } else {
+        None
+    };
+    Ok(wireguard::Config {
+        address: settings.address.clone(),
+        port: settings.port as u16,
+        private_key: Arc::new(private_key),
+        peer_public_key: Arc::new(peer_public_key),
+        preshared_key,
+        local_ip,
+        allowed_ips,
+        mtu: if settings.mtu > 0 {
+            settings.mtu as usize
+        } else {
+            1420
+        },
+        keepalive,
+        bind_addr,
+        interface,
+    })
+}
+
+#[cfg(all(
+    test,
+    feature = "config-json",
+    feature = "outbound-direct",
+    feature = "outbound-failover"
+))]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::config::json;
+    use crate::proxy::{OutboundConnect, TcpOutboundHandler, UdpOutboundHandler};
+    use crate::session::{Session, SocksAddr};
+
+    #[test]
+    fn test_remove_aborts_health_check() {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{
+                    "outbounds":[
+                        {"protocol":"direct","tag":"direct"},
+                        {"protocol":"failover","tag":"failover","settings":{"actors":["direct"],"checkInterval":1}}
+                    ],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#
+                .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let mut manager = OutboundManager::new(&config.outbounds, config.dns.as_ref().unwrap());
+            // The health check task is spawned by the first connection, the
+            // health status is held by the task as long as it runs.
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let mut sess = Session::default();
+            sess.destination = SocksAddr::Ip(listener.local_addr().unwrap());
+            let handler = manager.get("failover").unwrap().clone();
+            let stream = handler.handle_tcp(&sess, None).await.unwrap();
+            drop(stream);
+            drop(handler);
+            let health_status = manager.get_health_status("failover").unwrap();
+            assert!(manager.abort_handles.contains_key("failover"));
+
+            assert!(manager.remove("failover").is_some());
+            assert!(manager.get("failover").is_none());
+            assert!(manager.get_health_status("failover").is_none());
+            assert!(!manager.abort_handles.contains_key("failover"));
+            tokio::time::delay_for(Duration::from_millis(100)).await;
+            assert_eq!(Arc::strong_count(&health_status), 1);
+            assert!(manager.remove("failover").is_none());
+        });
+    }
+
+    #[test]
+    fn test_bind() {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{
+                    "outbounds":[
+                        {"protocol":"direct","tag":"v4","bind":"127.0.0.1"},
+                        {"protocol":"direct","tag":"v6","bind":"::1"},
+                        {"protocol":"direct","tag":"interface","bind":"lo"}
+                    ],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#
+                .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let manager =
+            OutboundManager::try_new(&config.outbounds, config.dns.as_ref().unwrap()).unwrap();
+        let bind_addr = |tag: &str| match manager.get(tag).unwrap().udp_connect_addr() {
+            Some(OutboundConnect::Direct(addr)) => addr,
+            _ => panic!("unexpected connect addr of [{}]", tag),
+        };
+        assert_eq!(bind_addr("v4"), "127.0.0.1:0".parse().unwrap());
+        assert_eq!(bind_addr("v6"), "[::1]:0".parse().unwrap());
+        assert_eq!(bind_addr("interface"), "0.0.0.0:0".parse().unwrap());
+    }
+
+    #[cfg(feature = "outbound-shadowsocks")]
+    #[test]
+    fn test_shadowsocks_2022_udp() {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{
+                    "outbounds":[
+                        {"protocol":"shadowsocks","tag":"packet","settings":{"address":"127.0.0.1","port":8388,"method":"2022-blake3-aes-128-gcm","password":"AAECAwQFBgcICQoLDA0ODw=="}},
+                        {"protocol":"shadowsocks","tag":"stream","settings":{"address":"127.0.0.1","port":8388,"method":"2022-blake3-aes-128-gcm","password":"AAECAwQFBgcICQoLDA0ODw==","udpOverTcp":true}},
+                        {"protocol":"shadowsocks","tag":"aead","settings":{"address":"127.0.0.1","port":8388,"method":"aes-128-gcm","password":"password"}}
+                    ],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#
+                .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let manager =
+            OutboundManager::try_new(&config.outbounds, config.dns.as_ref().unwrap()).unwrap();
+        // UDP of the 2022 methods is only relayed over TCP.
+        assert!(manager.get("packet").unwrap().has_tcp());
+        assert!(!manager.get("packet").unwrap().has_udp());
+        assert!(manager.get("stream").unwrap().has_udp());
+        assert!(manager.get("aead").unwrap().has_udp());
+    }
+
+    #[cfg(feature = "outbound-drop")]
+    #[test]
+    fn test_reject_targets() {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{
+                    "outbounds":[{"protocol":"direct","tag":"direct"}],
+                    "rules":[
+                        {"domain":["reset.com"],"target":"reject"},
+                        {"domain":["drop.com"],"target":"reject-drop"}
+                    ],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#
+                .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let manager = OutboundManager::new(&config.outbounds, config.dns.as_ref().unwrap());
+            let router = crate::app::router::Router::new(&config.routing_rules);
+            for (domain, tag, kind) in &[
+                ("reset.com", REJECT_TAG, std::io::ErrorKind::ConnectionReset),
+                ("drop.com", REJECT_DROP_TAG, std::io::ErrorKind::Other),
+            ] {
+                let mut sess = Session::default();
+                sess.destination = SocksAddr::Domain(domain.to_string(), 443);
+                let (_, target) = router.pick_rule(&sess).unwrap();
+                assert_eq!(target, tag);
+                let h = manager.get(target).unwrap();
+                let err = h.handle_tcp(&sess, None).await.err().unwrap();
+                assert_eq!(err.kind(), *kind);
+            }
+        });
+    }
+}
@@ -1 +1,5 @@
+pub mod health;
 pub mod manager;
+pub mod race;
+pub mod registry;
+pub mod selector;
@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Races won by the actors of a tryall outbound, shared by its handlers and
+/// the outbound manager so they can be read at runtime.
+pub struct RaceStats {
+    actors: Vec<String>,
+    wins: Vec<AtomicU64>,
+    // sum of the connect latencies of the wins, in milliseconds
+    latencies: Vec<AtomicU64>,
+}
+
+/// The races won by an actor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceWins {
+    pub tag: String,
+    pub wins: u64,
+    /// Average connect latency of the wins, `None` if never won.
+    pub latency: Option<Duration>,
+}
+
+impl RaceStats {
+    pub fn new(actors: Vec<String>) -> Self {
+        let wins = actors.iter().map(|_| AtomicU64::new(0)).collect();
+        let latencies = actors.iter().map(|_| AtomicU64::new(0)).collect();
+        RaceStats {
+            actors,
+            wins,
+            latencies,
+        }
+    }
+
+    pub fn actors(&self) -> &[String] {
+        &self.actors
+    }
+
+    /// Records a race won by the actor at the index, the latency is from the
+    /// start of the race.
+    pub fn record_win(&self, idx: usize, latency: Duration) {
+        self.wins[idx].fetch_add(1, Ordering::Relaxed);
+        self.latencies[idx].fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the races won by each actor.
+    pub fn wins(&self) -> Vec<RaceWins> {
+        self.actors
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| {
+                let wins = self.wins[i].load(Ordering::Relaxed);
+                let latency = if wins > 0 {
+                    let total = self.latencies[i].load(Ordering::Relaxed);
+                    Some(Duration::from_millis(total / wins))
+                } else {
+                    None
+                };
+                RaceWins {
+                    tag: tag.clone(),
+                    wins,
+                    latency,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_race_stats() {
+        let stats = RaceStats::new(vec!["a".to_string(), "b".to_string()]);
+        stats.record_win(0, Duration::from_millis(100));
+        stats.record_win(0, Duration::from_millis(200));
+        let wins = stats.wins();
+        assert_eq!(wins[0].wins, 2);
+        assert_eq!(wins[0].latency, Some(Duration::from_millis(150)));
+        assert_eq!(wins[1].wins, 0);
+        assert_eq!(wins[1].latency, None);
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use lazy_static::lazy_static;
+
+use crate::app::dns_client::DnsClient;
+use crate::config::Outbound;
+use crate::proxy::OutboundHandler;
+
+/// Creates the handler of an outbound with a registered protocol.
+///
+/// The factory is called from whichever thread loads the outbounds, which
+/// may be a runtime worker or the thread of an FFI call, so it must be
+/// `Send + Sync`, and it's kept for the lifetime of the process. It should
+/// return quickly without blocking, the handler does any I/O later on the
+/// runtime. The DNS client is the one of the instance loading the outbound
+/// and is meant to be kept by the handler for resolving.
+pub type OutboundFactory =
+    dyn Fn(&Outbound, Arc<DnsClient>) -> Result<Arc<dyn OutboundHandler>> + Send + Sync;
+
+lazy_static! {
+    static ref FACTORIES: Mutex<HashMap<String, Arc<OutboundFactory>>> = Mutex::new(HashMap::new());
+}
+
+/// Registers a factory for outbounds of a protocol leaf doesn't implement,
+/// replacing any factory registered for the protocol before. Protocols built
+/// into leaf take precedence over registered ones. Outbounds are loaded
+/// with the factories registered at the time, so register before starting
+/// the instances, or before loading JSON configs, as outbounds of protocols
+/// unknown by then are dropped. The settings of outbounds from JSON configs
+/// are the raw JSON of the `settings` field.
+pub fn register_outbound(protocol: &str, factory: Box<OutboundFactory>) {
+    FACTORIES
+        .lock()
+        .unwrap()
+        .insert(protocol.to_string(), Arc::from(factory));
+}
+
+/// Returns whether a factory is registered for the protocol.
+pub fn is_registered(protocol: &str) -> bool {
+    FACTORIES.lock().unwrap().contains_key(protocol)
+}
+
+// The factory is cloned out so it's not called with the lock held, in case
+// it registers factories itself.
+pub(crate) fn get(protocol: &str) -> Option<Arc<OutboundFactory>> {
+    FACTORIES.lock().unwrap().get(protocol).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_outbound() {
+        assert!(!is_registered("test-registry"));
+        register_outbound(
+            "test-registry",
+            Box::new(|outbound: &Outbound, _| {
+                Err(anyhow::anyhow!("no handler for {}", outbound.tag))
+            }),
+        );
+        assert!(is_registered("test-registry"));
+        let mut outbound = Outbound::new();
+        outbound.tag = "out".to_string();
+        let factory = get("test-registry").unwrap();
+        let dns_client = Arc::new(DnsClient::new(
+            Vec::new(),
+            HashMap::new(),
+            "0.0.0.0:0".parse().unwrap(),
+        ));
+        let err = factory(&outbound, dns_client).err().unwrap();
+        assert_eq!(err.to_string(), "no handler for out");
+    }
+}
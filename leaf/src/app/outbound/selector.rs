@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+// RTT of an actor not measured yet or failed the last probe.
+const NO_RTT: u64 = u64::MAX;
+
+/// The actor selection of a select or urltest outbound, shared by its
+/// handlers and the outbound manager so the selection can be switched at
+/// runtime.
+pub struct OutboundSelector {
+    actors: Vec<String>,
+    selected: AtomicUsize,
+    // RTT in milliseconds of the last probe of each actor
+    rtts: Vec<AtomicU64>,
+}
+
+impl OutboundSelector {
+    /// Creates a selector with the first actor selected.
+    pub fn new(actors: Vec<String>) -> Self {
+        let rtts = actors.iter().map(|_| AtomicU64::new(NO_RTT)).collect();
+        OutboundSelector {
+            actors,
+            selected: AtomicUsize::new(0),
+            rtts,
+        }
+    }
+
+    pub fn actors(&self) -> &[String] {
+        &self.actors
+    }
+
+    /// Returns the index of the selected actor.
+    pub fn selected_index(&self) -> usize {
+        self.selected.load(Ordering::Relaxed)
+    }
+
+    /// Returns the tag of the selected actor.
+    pub fn get_selected(&self) -> &str {
+        &self.actors[self.selected_index()]
+    }
+
+    /// Selects the actor by tag, it must be one of the actors of the select
+    /// outbound.
+    pub fn set_selected(&self, tag: &str) -> Result<()> {
+        match self.actors.iter().position(|a| a == tag) {
+            Some(i) => {
+                self.selected.store(i, Ordering::Relaxed);
+                Ok(())
+            }
+            None => Err(anyhow!("[{}] is not an actor of the selector", tag)),
+        }
+    }
+
+    /// Records the RTT of the last probe of the actor, `None` if it failed.
+    pub fn set_rtt(&self, idx: usize, rtt: Option<Duration>) {
+        let rtt = rtt.map_or(NO_RTT, |v| v.as_millis() as u64);
+        self.rtts[idx].store(rtt, Ordering::Relaxed);
+    }
+
+    /// Returns the RTT of the last probe of the actor, `None` if it's not
+    /// probed yet or the probe failed.
+    pub fn get_rtt(&self, idx: usize) -> Option<Duration> {
+        match self.rtts[idx].load(Ordering::Relaxed) {
+            NO_RTT => None,
+            v => Some(Duration::from_millis(v)),
+        }
+    }
+
+    /// Returns the actor tags with the RTTs of their last probes.
+    pub fn rtts(&self) -> Vec<(String, Option<Duration>)> {
+        self.actors
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| (tag.clone(), self.get_rtt(i)))
+            .collect()
+    }
+
+    /// Selects the actor with the lowest RTT, the selected one is kept if
+    /// it's no slower than that by more than `tolerance`, so the selection
+    /// doesn't flap between actors of similar RTTs. The selection is left
+    /// unchanged and `None` is returned if all probes failed.
+    pub fn select_fastest(&self, tolerance: Duration) -> Option<usize> {
+        let (fastest, min_rtt) = (0..self.actors.len())
+            .filter_map(|i| self.get_rtt(i).map(|rtt| (i, rtt)))
+            .min_by_key(|(_, rtt)| *rtt)?;
+        let selected = self.selected_index();
+        if let Some(rtt) = self.get_rtt(selected) {
+            if rtt <= min_rtt + tolerance {
+                return Some(selected);
+            }
+        }
+        self.selected.store(fastest, Ordering::Relaxed);
+        Some(fastest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_selected() {
+        let selector = OutboundSelector::new(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(selector.get_selected(), "a");
+        selector.set_selected("b").unwrap();
+        assert_eq!(selector.get_selected(), "b");
+        assert_eq!(selector.selected_index(), 1);
+        assert!(selector.set_selected("c").is_err());
+        assert_eq!(selector.get_selected(), "b");
+    }
+
+    #[test]
+    fn test_select_fastest() {
+        let selector = OutboundSelector::new(vec!["a".to_string(), "b".to_string()]);
+        let tolerance = Duration::from_millis(50);
+        assert_eq!(selector.select_fastest(tolerance), None);
+        assert_eq!(selector.get_selected(), "a");
+
+        selector.set_rtt(0, Some(Duration::from_millis(200)));
+        selector.set_rtt(1, Some(Duration::from_millis(100)));
+        assert_eq!(selector.select_fastest(tolerance), Some(1));
+        assert_eq!(selector.get_rtt(1), Some(Duration::from_millis(100)));
+
+        // Not switched back within the tolerance.
+        selector.set_rtt(0, Some(Duration::from_millis(80)));
+        assert_eq!(selector.select_fastest(tolerance), Some(1));
+        selector.set_rtt(0, Some(Duration::from_millis(40)));
+        assert_eq!(selector.select_fastest(tolerance), Some(0));
+
+        // Switched away once the selected one is down.
+        selector.set_rtt(0, None);
+        assert_eq!(selector.select_fastest(tolerance), Some(1));
+        assert_eq!(
+            selector.rtts(),
+            vec![
+                ("a".to_string(), None),
+                ("b".to_string(), Some(Duration::from_millis(100)))
+            ]
+        );
+    }
+}
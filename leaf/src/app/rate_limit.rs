@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use protobuf::RepeatedField;
+use tokio::time::Instant;
+
+use crate::config::{Inbound, Outbound, RateLimit, RateLimit_Bucket};
+
+// Shortest wait for tokens, so an almost refilled bucket doesn't wake the
+// waiting transfer over and over.
+const MIN_WAIT: Duration = Duration::from_millis(5);
+
+/// A token bucket of bytes, refilled at the rate up to the burst. Consuming
+/// may take more tokens than available, later transfers then wait until the
+/// debt is paid off, so a transfer overshoots by at most one read.
+pub struct TokenBucket {
+    // bytes per second
+    rate: f64,
+    burst: f64,
+    // tokens and the time they were last refilled
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    /// A burst of 0 allows one second of the rate.
+    pub fn new(rate: u64, burst: u64) -> Self {
+        let burst = if burst == 0 {
+            rate as f64
+        } else {
+            burst as f64
+        };
+        TokenBucket {
+            rate: rate as f64,
+            burst,
+            state: Mutex::new((burst, Instant::now())),
+        }
+    }
+
+    fn refill(&self, state: &mut (f64, Instant)) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.1).as_secs_f64();
+        state.0 = (state.0 + elapsed * self.rate).min(self.burst);
+        state.1 = now;
+    }
+
+    /// Returns how long to wait until there're tokens, `None` if there're
+    /// already.
+    pub fn wait_time(&self) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        if state.0 > 0.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(-state.0 / self.rate).max(MIN_WAIT))
+    }
+
+    pub fn consume(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        state.0 -= n as f64;
+    }
+}
+
+struct Buckets {
+    uplink: Option<Arc<TokenBucket>>,
+    downlink: Option<Arc<TokenBucket>>,
+}
+
+impl Buckets {
+    fn new(rate_limit: &RateLimit) -> Self {
+        let bucket = |b: Option<&RateLimit_Bucket>| match b {
+            Some(b) if b.rate > 0 => Some(Arc::new(TokenBucket::new(b.rate, b.burst))),
+            _ => None,
+        };
+        Buckets {
+            uplink: bucket(rate_limit.uplink.as_ref()),
+            downlink: bucket(rate_limit.downlink.as_ref()),
+        }
+    }
+}
+
+/// Bandwidth limits of inbounds and outbounds, each shared by all the
+/// connections of an inbound or outbound.
+pub struct RateLimiter {
+    inbounds: HashMap<String, Buckets>,
+    outbounds: HashMap<String, Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(inbounds: &RepeatedField<Inbound>, outbounds: &RepeatedField<Outbound>) -> Self {
+        let inbounds = inbounds
+            .iter()
+            .filter_map(|x| Some((x.tag.clone(), Buckets::new(x.rate_limit.as_ref()?))))
+            .collect();
+        let outbounds = outbounds
+            .iter()
+            .filter_map(|x| Some((x.tag.clone(), Buckets::new(x.rate_limit.as_ref()?))))
+            .collect();
+        RateLimiter {
+            inbounds,
+            outbounds,
+        }
+    }
+
+    /// Returns the uplink and downlink buckets applying to a connection from
+    /// the inbound to the outbound.
+    pub fn buckets(
+        &self,
+        inbound_tag: &str,
+        outbound_tag: &str,
+    ) -> (Vec<Arc<TokenBucket>>, Vec<Arc<TokenBucket>>) {
+        let mut uplink = Vec::new();
+        let mut downlink = Vec::new();
+        for buckets in [
+            self.inbounds.get(inbound_tag),
+            self.outbounds.get(outbound_tag),
+        ]
+        .iter()
+        .flatten()
+        {
+            uplink.extend(buckets.uplink.iter().cloned());
+            downlink.extend(buckets.downlink.iter().cloned());
+        }
+        (uplink, downlink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let bucket = TokenBucket::new(1000, 0);
+        assert_eq!(bucket.wait_time(), None);
+        bucket.consume(1500);
+        // 500 in debt at 1000 bytes per second
+        let wait = bucket.wait_time().unwrap();
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+        std::thread::sleep(Duration::from_millis(600));
+        assert_eq!(bucket.wait_time(), None);
+    }
+
+    #[test]
+    fn test_buckets() {
+        let mut inbound = Inbound::new();
+        inbound.tag = "in".to_string();
+        let mut rate_limit = RateLimit::new();
+        let mut bucket = RateLimit_Bucket::new();
+        bucket.rate = 1000;
+        rate_limit.set_uplink(bucket.clone());
+        rate_limit.set_downlink(bucket);
+        inbound.set_rate_limit(rate_limit);
+        let mut outbound = Outbound::new();
+        outbound.tag = "out".to_string();
+        let mut rate_limit = RateLimit::new();
+        let mut bucket = RateLimit_Bucket::new();
+        bucket.rate = 1000;
+        rate_limit.set_downlink(bucket);
+        outbound.set_rate_limit(rate_limit);
+
+        let limiter = RateLimiter::new(
+            &RepeatedField::from_vec(vec![inbound]),
+            &RepeatedField::from_vec(vec![outbound]),
+        );
+        let (up, down) = limiter.buckets("in", "out");
+        assert_eq!((up.len(), down.len()), (1, 2));
+        let (up, down) = limiter.buckets("other", "out");
+        assert_eq!((up.len(), down.len()), (0, 1));
+        let (up, down) = limiter.buckets("other", "other");
+        assert!(up.is_empty() && down.is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use log::*;
+use protobuf::RepeatedField;
+
+use crate::{
+    config::Inbound,
+    session::{Session, SocksAddr},
+};
+
+/// Rewrites destination domains of sessions per inbound, a rule matches the
+/// domain itself and all its subdomains, the matched suffix is replaced.
+pub struct Rewriter {
+    // inbound tag -> (from, to), longer suffixes first
+    rules: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Rewriter {
+    pub fn new(inbounds: &RepeatedField<Inbound>) -> Self {
+        let mut rules = HashMap::new();
+        for inbound in inbounds.iter() {
+            if inbound.rewrites.is_empty() {
+                continue;
+            }
+            let mut inbound_rules: Vec<(String, String)> = inbound
+                .rewrites
+                .iter()
+                .map(|(from, to)| (from.to_ascii_lowercase(), to.to_owned()))
+                .collect();
+            inbound_rules.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+            rules.insert(inbound.tag.clone(), inbound_rules);
+        }
+        Rewriter { rules }
+    }
+
+    fn rewrite_domain(&self, inbound_tag: &str, domain: &str) -> Option<String> {
+        let rules = self.rules.get(inbound_tag)?;
+        // Keeps the byte length, the suffix is then cut at the same index.
+        let domain = domain.to_ascii_lowercase();
+        for (from, to) in rules.iter() {
+            if domain == *from {
+                return Some(to.clone());
+            }
+            if domain.ends_with(from.as_str()) {
+                let prefix = &domain[..domain.len() - from.len()];
+                if prefix.ends_with('.') {
+                    return Some(format!("{}{}", prefix, to));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the rewritten address, `None` if there's no rule matches.
+    pub fn rewrite_addr(&self, inbound_tag: &str, addr: &SocksAddr) -> Option<SocksAddr> {
+        if let SocksAddr::Domain(domain, port) = addr {
+            let new_domain = self.rewrite_domain(inbound_tag, domain)?;
+            return Some(SocksAddr::Domain(new_domain, *port));
+        }
+        None
+    }
+
+    /// Rewrites the destination of the session in place.
+    pub fn rewrite(&self, sess: &mut Session) {
+        if let Some(new_dest) = self.rewrite_addr(&sess.inbound_tag, &sess.destination) {
+            debug!(
+                "[{}] rewrote destination {} to {}",
+                &sess.inbound_tag, &sess.destination, &new_dest
+            );
+            sess.destination = new_dest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_domain() {
+        let mut inbound = Inbound::new();
+        inbound.tag = "socks".to_string();
+        inbound
+            .rewrites
+            .insert("api.prod".to_string(), "api.staging".to_string());
+        inbound
+            .rewrites
+            .insert("prod".to_string(), "test".to_string());
+        let rewriter = Rewriter::new(&RepeatedField::from_vec(vec![inbound]));
+        assert_eq!(
+            rewriter.rewrite_domain("socks", "api.prod"),
+            Some("api.staging".to_string())
+        );
+        assert_eq!(
+            rewriter.rewrite_domain("socks", "v1.api.prod"),
+            Some("v1.api.staging".to_string())
+        );
+        assert_eq!(
+            rewriter.rewrite_domain("socks", "web.prod"),
+            Some("web.test".to_string())
+        );
+        assert_eq!(rewriter.rewrite_domain("socks", "xapi.staging"), None);
+        assert_eq!(rewriter.rewrite_domain("socks", "myprod"), None);
+        assert_eq!(rewriter.rewrite_domain("http", "api.prod"), None);
+        assert_eq!(
+            rewriter.rewrite_domain("socks", "WEB.Prod"),
+            Some("web.test".to_string())
+        );
+        // Non-ASCII letters are kept as they are, not panicking on the
+        // lowercase forms of different lengths.
+        assert_eq!(
+            rewriter.rewrite_domain("socks", "İİİ.prod"),
+            Some("İİİ.test".to_string())
+        );
+        assert_eq!(rewriter.rewrite_domain("socks", "İprod"), None);
+    }
+}
@@ -1,28 +1,91 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::sync::Arc;
 
+use aho_corasick::AhoCorasick;
 use anyhow::anyhow;
 use anyhow::Result;
 use cidr::{Cidr, IpCidr};
 use log::*;
 use maxminddb::geoip2::Country;
 use memmap::Mmap;
+use protobuf::Message;
 
-use crate::config::{self, RoutingRule};
-use crate::session::Session;
+use crate::app::dns_client::DnsStrategy;
+use crate::common::process;
+use crate::config::{self, geosite, RoutingRule};
+use crate::session::{Network, Session, SocksAddr};
+
+// Values of a condition are listed in the summary up to this many, only the
+// count is given for larger sets, e.g. the ones expanded from geosite.
+const MAX_LISTED_VALUES: usize = 16;
 
 pub trait Condition: Send + Sync + Unpin {
     fn apply(&self, sess: &Session) -> bool;
 }
 
+/// Describes a condition of a loaded rule.
+#[derive(Clone)]
+pub struct ConditionSummary {
+    /// The rule type as in the json config, e.g. `domainSuffix`.
+    pub kind: &'static str,
+    pub count: usize,
+    /// The values, or the count if there are too many.
+    pub value: String,
+}
+
+impl ConditionSummary {
+    fn new(kind: &'static str, unit: &str, values: Vec<String>) -> Self {
+        let value = if values.len() > MAX_LISTED_VALUES {
+            format!("{} {}", values.len(), unit)
+        } else {
+            values.join(", ")
+        };
+        ConditionSummary {
+            kind,
+            count: values.len(),
+            value,
+        }
+    }
+}
+
+/// Describes a loaded rule, a session matches the rule if it matches all the
+/// conditions.
+pub struct RuleSummary {
+    pub target: String,
+    pub log: bool,
+    pub conditions: Vec<ConditionSummary>,
+}
+
 struct Rule {
     target: String,
     condition: Box<dyn Condition>,
+    // Whether the rule needs more than the domain to make a decision, e.g.
+    // it has IP or port conditions.
+    domain_only: bool,
+    // Whether connections matching the rule are logged.
+    log: bool,
+    dns_strategy: Option<DnsStrategy>,
+    summary: Vec<ConditionSummary>,
 }
 
 impl Rule {
-    fn new(target: String, condition: Box<dyn Condition>) -> Self {
-        Rule { target, condition }
+    fn new(
+        target: String,
+        condition: Box<dyn Condition>,
+        domain_only: bool,
+        log: bool,
+        dns_strategy: Option<DnsStrategy>,
+        summary: Vec<ConditionSummary>,
+    ) -> Self {
+        Rule {
+            target,
+            condition,
+            domain_only,
+            log,
+            dns_strategy,
+            summary,
+        }
     }
 }
 
@@ -66,6 +129,92 @@ impl Condition for MmdbMatcher {
     }
 }
 
+struct GeoIpDatMatcher {
+    values: Vec<IpCidr>,
+    reverse_match: bool,
+    country_code: String,
+}
+
+impl GeoIpDatMatcher {
+    fn new(list: &geosite::GeoIPList, country_code: String) -> Self {
+        let mut values = Vec::new();
+        let mut reverse_match = false;
+        if let Some(entry) = list
+            .entry
+            .iter()
+            .find(|e| e.country_code.to_lowercase() == country_code.to_lowercase())
+        {
+            reverse_match = entry.reverse_match;
+            for cidr in entry.cidr.iter() {
+                let ip = match cidr.ip.len() {
+                    4 => {
+                        let mut b = [0u8; 4];
+                        b.copy_from_slice(&cidr.ip);
+                        IpAddr::from(b)
+                    }
+                    16 => {
+                        let mut b = [0u8; 16];
+                        b.copy_from_slice(&cidr.ip);
+                        IpAddr::from(b)
+                    }
+                    _ => continue,
+                };
+                match IpCidr::new(ip, cidr.prefix as u8) {
+                    Ok(cidr) => values.push(cidr),
+                    Err(err) => {
+                        debug!("invalid cidr {}/{}: {}", ip, cidr.prefix, err);
+                    }
+                }
+            }
+        }
+        GeoIpDatMatcher {
+            values,
+            reverse_match,
+            country_code,
+        }
+    }
+}
+
+impl Condition for GeoIpDatMatcher {
+    fn apply(&self, sess: &Session) -> bool {
+        if !sess.destination.is_domain() {
+            if let Some(ip) = sess.destination.ip() {
+                if self.values.iter().any(|cidr| cidr.contains(&ip)) != self.reverse_match {
+                    debug!("[{}] matches geoip code [{}]", ip, &self.country_code);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+/// A GeoIP database loaded from either a MaxMind mmdb file or a v2ray
+/// `geoip.dat` file.
+#[derive(Clone)]
+enum GeoIpDb {
+    Mmdb(Arc<maxminddb::Reader<Mmap>>),
+    Dat(Arc<geosite::GeoIPList>),
+}
+
+impl GeoIpDb {
+    fn open(file: &str) -> Result<Self> {
+        if let Ok(r) = maxminddb::Reader::open_mmap(file) {
+            return Ok(GeoIpDb::Mmdb(Arc::new(r)));
+        }
+        let buf = std::fs::read(file)?;
+        let list = geosite::GeoIPList::parse_from_bytes(&buf)?;
+        Ok(GeoIpDb::Dat(Arc::new(list)))
+    }
+
+    fn matcher(&self, country_code: String) -> Box<dyn Condition> {
+        match self {
+            GeoIpDb::Mmdb(r) => Box::new(MmdbMatcher::new(r.clone(), country_code)),
+            GeoIpDb::Dat(l) => Box::new(GeoIpDatMatcher::new(l, country_code)),
+        }
+    }
+}
+
 struct IpCidrMatcher {
     values: Vec<IpCidr>,
 }
@@ -101,17 +250,70 @@ impl Condition for IpCidrMatcher {
     }
 }
 
+struct ProcessNameMatcher {
+    values: Vec<String>,
+}
+
+impl Condition for ProcessNameMatcher {
+    // The dispatcher looks up the process ahead, it's cached by now.
+    fn apply(&self, sess: &Session) -> bool {
+        if let Some(name) = process::find_process_name(sess.network, &sess.source) {
+            if let Some(v) = self.values.iter().find(|v| v.eq_ignore_ascii_case(&name)) {
+                debug!("[{}] matches process name [{}]", &sess.source, v);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+struct NetworkMatcher {
+    values: Vec<Network>,
+}
+
+impl NetworkMatcher {
+    fn new(networks: &protobuf::RepeatedField<String>) -> Self {
+        let mut values = Vec::new();
+        for network in networks.iter() {
+            match network.as_str() {
+                "tcp" => values.push(Network::Tcp),
+                "udp" => values.push(Network::Udp),
+                _ => warn!("failed to add network matcher: invalid network {}", network),
+            }
+        }
+        NetworkMatcher { values }
+    }
+}
+
+impl Condition for NetworkMatcher {
+    fn apply(&self, sess: &Session) -> bool {
+        if self.values.contains(&sess.network) {
+            debug!("[{}] matches network [{}]", &sess.destination, sess.network);
+            return true;
+        }
+        false
+    }
+}
+
 struct PortMatcher {
     condition: Box<dyn Condition>,
 }
 
 impl PortMatcher {
+    // Each item is a list of ports and port ranges, e.g. `80,443,1000-2000`.
     fn new(port_ranges: &protobuf::RepeatedField<String>) -> Self {
         let mut cond_or = ConditionOr::new();
         for pr in port_ranges.iter() {
-            match PortRangeMatcher::new(pr) {
-                Ok(m) => cond_or.add(Box::new(m)),
-                Err(e) => warn!("failed to add port range matcher: {}", e),
+            match config::parse_ports(pr) {
+                Some(ranges) => {
+                    for (start, end) in ranges {
+                        cond_or.add(Box::new(PortRangeMatcher { start, end }));
+                    }
+                }
+                None => warn!(
+                    "failed to add port range matcher: invalid port range {}",
+                    pr
+                ),
             }
         }
         PortMatcher {
@@ -131,29 +333,6 @@ struct PortRangeMatcher {
     end: u16,
 }
 
-impl PortRangeMatcher {
-    fn new(port_range: &str) -> Result<Self> {
-        let parts: Vec<&str> = port_range.split('-').collect();
-        if parts.len() != 2 {
-            return Err(anyhow!("invalid port range"));
-        }
-        let start = if let Ok(v) = parts[0].parse::<u16>() {
-            v
-        } else {
-            return Err(anyhow!("invalid port range"));
-        };
-        let end = if let Ok(v) = parts[1].parse::<u16>() {
-            v
-        } else {
-            return Err(anyhow!("invalid port range"));
-        };
-        if start > end {
-            return Err(anyhow!("invalid port range"));
-        }
-        Ok(PortRangeMatcher { start, end })
-    }
-}
-
 impl Condition for PortRangeMatcher {
     fn apply(&self, sess: &Session) -> bool {
         let port = sess.destination.port();
@@ -169,45 +348,12 @@ impl Condition for PortRangeMatcher {
     }
 }
 
-struct DomainKeywordMatcher {
-    value: String,
-}
-
-impl DomainKeywordMatcher {
-    fn new(value: String) -> Self {
-        DomainKeywordMatcher { value }
-    }
-}
-
-impl Condition for DomainKeywordMatcher {
-    fn apply(&self, sess: &Session) -> bool {
-        if sess.destination.is_domain() {
-            if let Some(domain) = sess.destination.domain() {
-                if domain.contains(&self.value) {
-                    debug!("[{}] matches domain keyword [{}]", domain, &self.value);
-                    return true;
-                }
-            }
-        }
-        false
-    }
-}
-
-struct DomainSuffixMatcher {
-    value: String,
-}
-
-impl DomainSuffixMatcher {
-    fn new(value: String) -> Self {
-        DomainSuffixMatcher { value }
-    }
-}
-
 // test if domain1 is a subdomain of domain2
 // examples:
 //   video.google.com vs google.com -> true
 //   video.google.com vs gle.com -> false
 //   google.com vs video.google.com -> false
+#[cfg(test)]
 fn is_sub_domain(d1: &str, d2: &str) -> bool {
     let d1_parts: Vec<&str> = d1.split('.').rev().collect();
     let d2_parts: Vec<&str> = d2.split('.').rev().collect();
@@ -223,73 +369,129 @@ fn is_sub_domain(d1: &str, d2: &str) -> bool {
     true
 }
 
-impl Condition for DomainSuffixMatcher {
-    fn apply(&self, sess: &Session) -> bool {
-        if sess.destination.is_domain() {
-            if let Some(domain) = sess.destination.domain() {
-                if is_sub_domain(&domain, &self.value) {
-                    debug!("[{}] matches domain suffix [{}]", domain, &self.value);
-                    return true;
-                }
-            }
-        }
-        false
-    }
+/// A trie keyed by domain labels in reverse order, `google.com` is stored
+/// as `com` -> `google`.
+#[derive(Default)]
+struct DomainSuffixTrie {
+    children: HashMap<String, DomainSuffixTrie>,
+    // Whether a suffix ends at this node.
+    end: bool,
 }
 
-struct DomainFullMatcher {
-    value: String,
-}
-
-impl DomainFullMatcher {
-    fn new(value: String) -> Self {
-        DomainFullMatcher { value }
+impl DomainSuffixTrie {
+    fn insert(&mut self, suffix: &str) {
+        let mut node = self;
+        for label in suffix.split('.').rev() {
+            node = node.children.entry(label.to_owned()).or_default();
+        }
+        node.end = true;
     }
-}
 
-impl Condition for DomainFullMatcher {
-    fn apply(&self, sess: &Session) -> bool {
-        if sess.destination.is_domain() {
-            if let Some(domain) = sess.destination.domain() {
-                if domain == &self.value {
-                    debug!("{} matches domain [{}]", domain, &self.value);
-                    return true;
-                }
+    // Returns the shortest suffix of the domain found in the trie.
+    fn find<'a>(&self, domain: &'a str) -> Option<&'a str> {
+        let mut node = self;
+        let mut len = 0;
+        for label in domain.rsplit('.') {
+            node = node.children.get(label)?;
+            len += label.len();
+            if node.end {
+                return Some(&domain[domain.len() - len..]);
             }
+            len += 1;
         }
-        false
+        None
     }
 }
 
-struct DomainMatcher {
-    condition: Box<dyn Condition>,
+/// Matches domains against full domains, suffixes and keywords, also used
+/// by the DNS policies.
+pub(crate) struct DomainMatcher {
+    fulls: HashSet<String>,
+    suffixes: DomainSuffixTrie,
+    keywords: Option<(AhoCorasick, Vec<String>)>,
 }
 
 impl DomainMatcher {
-    fn new(domains: &protobuf::RepeatedField<config::RoutingRule_Domain>) -> Self {
-        let mut cond_or = ConditionOr::new();
+    fn summarize(
+        domains: &protobuf::RepeatedField<config::RoutingRule_Domain>,
+    ) -> Vec<ConditionSummary> {
+        let mut keywords = Vec::new();
+        let mut suffixes = Vec::new();
+        let mut fulls = Vec::new();
         for rr_domain in domains.iter() {
             match rr_domain.field_type {
-                config::RoutingRule_Domain_Type::PLAIN => {
-                    cond_or.add(Box::new(DomainKeywordMatcher::new(rr_domain.value.clone())));
-                }
-                config::RoutingRule_Domain_Type::DOMAIN => {
-                    cond_or.add(Box::new(DomainSuffixMatcher::new(rr_domain.value.clone())));
-                }
+                config::RoutingRule_Domain_Type::PLAIN => keywords.push(rr_domain.value.clone()),
+                config::RoutingRule_Domain_Type::DOMAIN => suffixes.push(rr_domain.value.clone()),
+                config::RoutingRule_Domain_Type::FULL => fulls.push(rr_domain.value.clone()),
+            }
+        }
+        let mut summary = Vec::new();
+        if !fulls.is_empty() {
+            summary.push(ConditionSummary::new("domain", "domains", fulls));
+        }
+        if !suffixes.is_empty() {
+            summary.push(ConditionSummary::new("domainSuffix", "domains", suffixes));
+        }
+        if !keywords.is_empty() {
+            summary.push(ConditionSummary::new("domainKeyword", "keywords", keywords));
+        }
+        summary
+    }
+
+    pub(crate) fn new(domains: &protobuf::RepeatedField<config::RoutingRule_Domain>) -> Self {
+        let mut fulls = HashSet::new();
+        let mut suffixes = DomainSuffixTrie::default();
+        let mut keywords = Vec::new();
+        for rr_domain in domains.iter() {
+            match rr_domain.field_type {
+                config::RoutingRule_Domain_Type::PLAIN => keywords.push(rr_domain.value.clone()),
+                config::RoutingRule_Domain_Type::DOMAIN => suffixes.insert(&rr_domain.value),
                 config::RoutingRule_Domain_Type::FULL => {
-                    cond_or.add(Box::new(DomainFullMatcher::new(rr_domain.value.clone())));
+                    fulls.insert(rr_domain.value.clone());
                 }
             }
         }
+        let keywords = if keywords.is_empty() {
+            None
+        } else {
+            Some((AhoCorasick::new(&keywords), keywords))
+        };
         DomainMatcher {
-            condition: Box::new(cond_or),
+            fulls,
+            suffixes,
+            keywords,
         }
     }
+
+    pub(crate) fn matches(&self, domain: &str) -> bool {
+        if self.fulls.contains(domain) {
+            debug!("{} matches domain [{}]", domain, domain);
+            return true;
+        }
+        if let Some(suffix) = self.suffixes.find(domain) {
+            debug!("[{}] matches domain suffix [{}]", domain, suffix);
+            return true;
+        }
+        if let Some((ac, values)) = &self.keywords {
+            if let Some(m) = ac.find(domain) {
+                debug!(
+                    "[{}] matches domain keyword [{}]",
+                    domain,
+                    &values[m.pattern()]
+                );
+                return true;
+            }
+        }
+        false
+    }
 }
 
 impl Condition for DomainMatcher {
     fn apply(&self, sess: &Session) -> bool {
-        self.condition.apply(sess)
+        match sess.destination.domain() {
+            Some(domain) => self.matches(domain),
+            None => false,
+        }
     }
 }
 
@@ -353,47 +555,86 @@ impl Condition for ConditionOr {
 
 pub struct Router {
     rules: Vec<Rule>,
+    dry_run: bool,
+    // Whether any rule matches process names.
+    process_rules: bool,
 }
 
 impl Router {
     pub fn new(routing_rules: &protobuf::RepeatedField<RoutingRule>) -> Self {
         let mut rules = Vec::new();
-        let mut mmdb_readers: HashMap<String, Arc<maxminddb::Reader<Mmap>>> = HashMap::new();
+        let mut geoip_dbs: HashMap<String, GeoIpDb> = HashMap::new();
         for rr in routing_rules.iter() {
             let mut cond_and = ConditionAnd::new();
+            let mut summary = Vec::new();
 
             if rr.domains.len() > 0 {
                 cond_and.add(Box::new(DomainMatcher::new(&rr.domains)));
+                summary.append(&mut DomainMatcher::summarize(&rr.domains));
             }
 
             if rr.ip_cidrs.len() > 0 {
-                cond_and.add(Box::new(IpCidrMatcher::new(&rr.ip_cidrs)));
+                let m = IpCidrMatcher::new(&rr.ip_cidrs);
+                summary.push(ConditionSummary::new(
+                    "ip",
+                    "cidrs",
+                    m.values.iter().map(|cidr| cidr.to_string()).collect(),
+                ));
+                cond_and.add(Box::new(m));
             }
 
             if rr.mmdbs.len() > 0 {
                 for mmdb in rr.mmdbs.iter() {
-                    let reader = match mmdb_readers.get(&mmdb.file) {
-                        Some(r) => r.clone(),
-                        None => {
-                            if let Ok(r) = maxminddb::Reader::open_mmap(&mmdb.file) {
-                                let r = Arc::new(r);
-                                mmdb_readers.insert((&mmdb.file).to_owned(), r.clone());
-                                r
-                            } else {
-                                warn!("open mmdb file {} failed", mmdb.file);
+                    let db = match geoip_dbs.get(&mmdb.file) {
+                        Some(db) => db.clone(),
+                        None => match GeoIpDb::open(&mmdb.file) {
+                            Ok(db) => {
+                                geoip_dbs.insert((&mmdb.file).to_owned(), db.clone());
+                                db
+                            }
+                            Err(e) => {
+                                warn!("open geoip file {} failed: {}", mmdb.file, e);
                                 continue;
                             }
-                        }
+                        },
                     };
-                    cond_and.add(Box::new(MmdbMatcher::new(
-                        reader,
-                        mmdb.country_code.clone(),
-                    )));
+                    cond_and.add(db.matcher(mmdb.country_code.clone()));
+                    summary.push(ConditionSummary::new(
+                        "geoip",
+                        "countries",
+                        vec![format!("{} ({})", mmdb.country_code, mmdb.file)],
+                    ));
                 }
             }
 
             if rr.port_ranges.len() > 0 {
                 cond_and.add(Box::new(PortMatcher::new(&rr.port_ranges)));
+                summary.push(ConditionSummary::new(
+                    "portRange",
+                    "ranges",
+                    rr.port_ranges.to_vec(),
+                ));
+            }
+
+            if rr.networks.len() > 0 {
+                cond_and.add(Box::new(NetworkMatcher::new(&rr.networks)));
+                summary.push(ConditionSummary::new(
+                    "network",
+                    "networks",
+                    rr.networks.to_vec(),
+                ));
+            }
+
+            // Comes last as looking up the process is the most expensive.
+            if rr.process_names.len() > 0 {
+                cond_and.add(Box::new(ProcessNameMatcher {
+                    values: rr.process_names.to_vec(),
+                }));
+                summary.push(ConditionSummary::new(
+                    "processName",
+                    "processes",
+                    rr.process_names.to_vec(),
+                ));
             }
 
             if cond_and.is_empty() {
@@ -401,25 +642,104 @@ impl Router {
                 continue;
             }
 
-            rules.push(Rule::new(rr.target_tag.clone(), Box::new(cond_and)));
+            let domain_only = rr.ip_cidrs.len() == 0
+                && rr.mmdbs.len() == 0
+                && rr.port_ranges.len() == 0
+                && rr.networks.len() == 0
+                && rr.process_names.len() == 0;
+
+            rules.push(Rule::new(
+                rr.target_tag.clone(),
+                Box::new(cond_and),
+                domain_only,
+                !rr.no_log,
+                DnsStrategy::from_config(rr.dns_strategy),
+                summary,
+            ));
+        }
+        Router {
+            process_rules: routing_rules.iter().any(|rr| rr.process_names.len() > 0),
+            rules,
+            dry_run: false,
+        }
+    }
+
+    /// Sets whether the decisions are only logged rather than enforced.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Whether picking a route may look up the process owning the inbound
+    /// connection.
+    pub fn has_process_rules(&self) -> bool {
+        self.process_rules
+    }
+
+    /// Returns the index and the target of the first matching rule.
+    pub fn pick_rule(&self, sess: &Session) -> Option<(usize, &String)> {
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.apply(sess) {
+                return Some((i, &rule.target));
+            }
         }
-        Router { rules }
+        None
+    }
+
+    /// Describes the loaded rules in order.
+    pub fn summary(&self) -> Vec<RuleSummary> {
+        self.rules
+            .iter()
+            .map(|r| RuleSummary {
+                target: r.target.clone(),
+                log: r.log,
+                conditions: r.summary.clone(),
+            })
+            .collect()
+    }
+
+    /// Tells whether connections matching the rule at the index are logged.
+    pub fn rule_logs(&self, idx: usize) -> bool {
+        self.rules.get(idx).map(|r| r.log).unwrap_or(true)
+    }
+
+    /// Returns the DNS strategy of the rule at the index, `None` for the
+    /// global one.
+    pub fn rule_dns_strategy(&self, idx: usize) -> Option<DnsStrategy> {
+        self.rules.get(idx).and_then(|r| r.dns_strategy)
     }
 
     pub fn pick_route(&self, sess: &Session) -> Result<&String> {
+        match self.pick_rule(sess) {
+            Some((_, target)) => Ok(target),
+            None => Err(anyhow!("no matching rules")),
+        }
+    }
+
+    /// Picks a route for a domain without knowing the destination IP or port.
+    ///
+    /// Returns `None` if a rule which depends on IP or port is reached before
+    /// any domain rule matches, since the outcome can't be decided then.
+    pub fn pick_route_by_domain(&self, domain: &str) -> Option<Result<&String>> {
+        let mut sess = Session::default();
+        sess.destination = SocksAddr::Domain(domain.to_owned(), 0);
         for rule in &self.rules {
-            if rule.apply(sess) {
-                return Ok(&rule.target);
+            if !rule.domain_only {
+                return None;
+            }
+            if rule.apply(&sess) {
+                return Some(Ok(&rule.target));
             }
         }
-        Err(anyhow!("no matching rules"))
+        Some(Err(anyhow!("no matching rules")))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::session::SocksAddr;
-
     use super::*;
 
     #[test]
@@ -433,13 +753,125 @@ mod tests {
         assert!(!is_sub_domain(&d1, &d2));
     }
 
+    fn domain_rules(
+        values: &[(config::RoutingRule_Domain_Type, &str)],
+    ) -> Vec<config::RoutingRule_Domain> {
+        values
+            .iter()
+            .map(|(t, v)| {
+                let mut d = config::RoutingRule_Domain::new();
+                d.field_type = *t;
+                d.value = v.to_string();
+                d
+            })
+            .collect()
+    }
+
+    // The matching done before domain rules were indexed, kept as a reference.
+    fn linear_match(domains: &[config::RoutingRule_Domain], domain: &str) -> bool {
+        domains.iter().any(|d| match d.field_type {
+            config::RoutingRule_Domain_Type::PLAIN => domain.contains(&d.value),
+            config::RoutingRule_Domain_Type::DOMAIN => is_sub_domain(domain, &d.value),
+            config::RoutingRule_Domain_Type::FULL => domain == d.value,
+        })
+    }
+
+    fn domain_session(domain: &str) -> Session {
+        let mut sess = Session::default();
+        sess.destination = SocksAddr::Domain(domain.to_string(), 443);
+        sess
+    }
+
+    #[test]
+    fn test_domain_matcher() {
+        use config::RoutingRule_Domain_Type::*;
+        let domains = domain_rules(&[
+            (DOMAIN, "google.com"),
+            (DOMAIN, "co.uk"),
+            (DOMAIN, "cn"),
+            (DOMAIN, ".internal"),
+            (FULL, "www.example.com"),
+            (PLAIN, "ads"),
+            (PLAIN, "track"),
+        ]);
+        let m = DomainMatcher::new(&protobuf::RepeatedField::from_vec(domains.clone()));
+        for domain in &[
+            "google.com",
+            "www.google.com",
+            "google.com.hk",
+            "oogle.com",
+            "bbc.co.uk",
+            "co.uk",
+            "uk",
+            "baidu.cn",
+            "cn.example.org",
+            "a..internal",
+            "internal",
+            "www.example.com",
+            "example.com",
+            "wwww.example.com",
+            "ads.example.org",
+            "downloads.example.org",
+            "tracker.example.org",
+            "example.org",
+            "",
+        ] {
+            assert_eq!(
+                m.apply(&domain_session(domain)),
+                linear_match(&domains, domain),
+                "{}",
+                domain
+            );
+        }
+        let mut sess = domain_session("");
+        sess.destination = SocksAddr::Ip("8.8.8.8:53".parse().unwrap());
+        assert!(!m.apply(&sess));
+    }
+
+    // The index agrees with a linear scan over many rules.
+    #[test]
+    fn test_domain_matcher_many_rules() {
+        use config::RoutingRule_Domain_Type::*;
+
+        let mut values = Vec::new();
+        for i in 0..50000 {
+            let t = match i % 10 {
+                0 => PLAIN,
+                1 | 2 => FULL,
+                _ => DOMAIN,
+            };
+            values.push((t, format!("site{}.example{}.com", i, i % 100)));
+        }
+        let values: Vec<_> = values.iter().map(|(t, v)| (*t, v.as_str())).collect();
+        let domains = domain_rules(&values);
+        let queries: Vec<_> = (0..200)
+            .map(|i| {
+                if i % 2 == 0 {
+                    format!("www.site{}.example{}.com", i * 250, (i * 250) % 100)
+                } else {
+                    format!("www.nosite{}.example.org", i)
+                }
+            })
+            .collect();
+
+        let m = DomainMatcher::new(&protobuf::RepeatedField::from_vec(domains.clone()));
+        let linear: Vec<_> = queries.iter().map(|q| linear_match(&domains, q)).collect();
+        let indexed: Vec<_> = queries
+            .iter()
+            .map(|q| m.apply(&domain_session(q)))
+            .collect();
+        assert_eq!(linear, indexed);
+    }
+
     #[test]
     fn test_port_matcher() {
         let mut sess = Session {
+            network: Network::Tcp,
             source: "0.0.0.0:0".parse().unwrap(),
             local_addr: "0.0.0.0:0".parse().unwrap(),
             destination: SocksAddr::Domain("www.google.com".to_string(), 22),
             inbound_tag: "".to_string(),
+            dns_strategy: None,
         };
 
         // test port range
@@ -461,18 +893,256 @@ mod tests {
         sess.destination = SocksAddr::Domain("www.google.com".to_string(), 22);
         assert!(m.apply(&sess));
 
+        // test boundary ports
+        let m = PortMatcher::new(&protobuf::RepeatedField::from_vec(vec![
+            "1024-5000".to_string(),
+            "0".to_string(),
+            "65535".to_string(),
+        ]));
+        for (port, matched) in &[
+            (1023, false),
+            (1024, true),
+            (5000, true),
+            (5001, false),
+            (0, true),
+            (1, false),
+            (65534, false),
+            (65535, true),
+        ] {
+            sess.destination = SocksAddr::Domain("www.google.com".to_string(), *port);
+            assert_eq!(m.apply(&sess), *matched, "port {}", port);
+        }
+
+        // test lists of single ports and overlapping ranges, for UDP too
+        let m = PortMatcher::new(&protobuf::RepeatedField::from_vec(vec![
+            "80, 443".to_string(),
+            "1000-2000,1500-2500".to_string(),
+            "2000-2000".to_string(),
+        ]));
+        for network in &[Network::Tcp, Network::Udp] {
+            sess.network = *network;
+            for (port, matched) in &[
+                (80, true),
+                (443, true),
+                (81, false),
+                (999, false),
+                (1000, true),
+                (1750, true),
+                (2000, true),
+                (2500, true),
+                (2501, false),
+            ] {
+                sess.destination = SocksAddr::Domain("www.google.com".to_string(), *port);
+                assert_eq!(m.apply(&sess), *matched, "port {}", port);
+            }
+        }
+
         // test invalid port ranges
-        let m = PortRangeMatcher::new("22-21");
-        assert!(m.is_err());
-        let m = PortRangeMatcher::new("22");
-        assert!(m.is_err());
-        let m = PortRangeMatcher::new("22-");
-        assert!(m.is_err());
-        let m = PortRangeMatcher::new("-22");
-        assert!(m.is_err());
-        let m = PortRangeMatcher::new("22-abc");
-        assert!(m.is_err());
-        let m = PortRangeMatcher::new("22-23-24");
-        assert!(m.is_err());
+        assert!(config::parse_ports("22-21").is_none());
+        assert!(config::parse_ports("22-").is_none());
+        assert!(config::parse_ports("-22").is_none());
+        assert!(config::parse_ports("22-abc").is_none());
+        assert!(config::parse_ports("22-23-24").is_none());
+        assert!(config::parse_ports("22,").is_none());
+        assert!(config::parse_ports("65536").is_none());
+        assert_eq!(config::parse_ports("22").unwrap(), vec![(22, 22)]);
+
+        // an invalid item matches nothing, the valid ones still do
+        let m = PortMatcher::new(&protobuf::RepeatedField::from_vec(vec![
+            "22-21".to_string(),
+            "53".to_string(),
+        ]));
+        sess.destination = SocksAddr::Domain("www.google.com".to_string(), 22);
+        assert!(!m.apply(&sess));
+        sess.destination = SocksAddr::Domain("www.google.com".to_string(), 53);
+        assert!(m.apply(&sess));
+    }
+
+    #[test]
+    fn test_pick_route_by_port() {
+        // Ports are ANDed with the other conditions of a rule.
+        let mut rr1 = RoutingRule::new();
+        rr1.target_tag = "direct".to_string();
+        rr1.ip_cidrs.push("10.0.0.0/8".to_string());
+        rr1.port_ranges.push("22".to_string());
+
+        let mut rr2 = RoutingRule::new();
+        rr2.target_tag = "proxy".to_string();
+        rr2.port_ranges.push("80,443".to_string());
+
+        let mut rr3 = RoutingRule::new();
+        rr3.target_tag = "block".to_string();
+        rr3.ip_cidrs.push("0.0.0.0/0".to_string());
+
+        let router = Router::new(&protobuf::RepeatedField::from_vec(vec![rr1, rr2, rr3]));
+        let mut sess = geoip_session("10.0.0.1");
+        sess.destination = SocksAddr::Ip("10.0.0.1:22".parse().unwrap());
+        assert_eq!(router.pick_route(&sess).unwrap(), "direct");
+        sess.destination = SocksAddr::Ip("1.1.1.1:22".parse().unwrap());
+        assert_eq!(router.pick_route(&sess).unwrap(), "block");
+        sess.destination = SocksAddr::Ip("10.0.0.1:443".parse().unwrap());
+        assert_eq!(router.pick_route(&sess).unwrap(), "proxy");
+        sess.network = Network::Udp;
+        sess.destination = SocksAddr::Ip("1.1.1.1:80".parse().unwrap());
+        assert_eq!(router.pick_route(&sess).unwrap(), "proxy");
+    }
+
+    #[test]
+    fn test_pick_route_by_network() {
+        // UDP to the port goes direct, the rest of the domain is proxied.
+        let mut rr1 = RoutingRule::new();
+        rr1.target_tag = "direct".to_string();
+        let mut d = config::RoutingRule_Domain::new();
+        d.field_type = config::RoutingRule_Domain_Type::DOMAIN;
+        d.value = "example.com".to_string();
+        rr1.domains.push(d.clone());
+        rr1.port_ranges.push("443".to_string());
+        rr1.networks.push("udp".to_string());
+
+        let mut rr2 = RoutingRule::new();
+        rr2.target_tag = "proxy".to_string();
+        rr2.domains.push(d);
+
+        let mut rr3 = RoutingRule::new();
+        rr3.target_tag = "any".to_string();
+        rr3.networks.push("tcp".to_string());
+        rr3.networks.push("udp".to_string());
+
+        let router = Router::new(&protobuf::RepeatedField::from_vec(vec![rr1, rr2, rr3]));
+        let mut sess = geoip_session("1.1.1.1");
+        sess.destination = SocksAddr::Domain("www.example.com".to_string(), 443);
+        assert_eq!(router.pick_route(&sess).unwrap(), "proxy");
+        sess.network = Network::Udp;
+        assert_eq!(router.pick_route(&sess).unwrap(), "direct");
+        sess.destination = SocksAddr::Domain("www.example.com".to_string(), 80);
+        assert_eq!(router.pick_route(&sess).unwrap(), "proxy");
+        sess.destination = SocksAddr::Domain("www.google.com".to_string(), 443);
+        assert_eq!(router.pick_route(&sess).unwrap(), "any");
+        sess.network = Network::Tcp;
+        assert_eq!(router.pick_route(&sess).unwrap(), "any");
+
+        // The network isn't known from the domain alone.
+        assert!(router.pick_route_by_domain("www.example.com").is_none());
+    }
+
+    #[test]
+    fn test_pick_route_by_domain() {
+        let mut rr1 = RoutingRule::new();
+        rr1.target_tag = "direct".to_string();
+        let mut d = config::RoutingRule_Domain::new();
+        d.field_type = config::RoutingRule_Domain_Type::DOMAIN;
+        d.value = "example.com".to_string();
+        rr1.domains.push(d);
+
+        let mut rr2 = RoutingRule::new();
+        rr2.target_tag = "proxy".to_string();
+        rr2.ip_cidrs.push("10.0.0.0/8".to_string());
+
+        let router = Router::new(&protobuf::RepeatedField::from_vec(vec![rr1, rr2]));
+        assert_eq!(
+            router
+                .pick_route_by_domain("www.example.com")
+                .unwrap()
+                .unwrap(),
+            "direct"
+        );
+        assert!(router.pick_route_by_domain("www.google.com").is_none());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut rr = RoutingRule::new();
+        rr.target_tag = "direct".to_string();
+        let mut d = config::RoutingRule_Domain::new();
+        d.field_type = config::RoutingRule_Domain_Type::FULL;
+        d.value = "example.com".to_string();
+        rr.domains.push(d);
+        for i in 0..100 {
+            rr.ip_cidrs.push(format!("10.0.{}.0/24", i));
+        }
+        rr.no_log = true;
+
+        let router = Router::new(&protobuf::RepeatedField::from_vec(vec![rr]));
+        let summary = router.summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].target, "direct");
+        assert!(!summary[0].log);
+        let conds = &summary[0].conditions;
+        assert_eq!(conds.len(), 2);
+        assert_eq!(conds[0].kind, "domain");
+        assert_eq!(conds[0].value, "example.com");
+        assert_eq!(conds[1].kind, "ip");
+        assert_eq!(conds[1].count, 100);
+        assert_eq!(conds[1].value, "100 cidrs");
+    }
+
+    fn geoip_session(ip: &str) -> Session {
+        Session {
+            network: Network::Tcp,
+            source: "0.0.0.0:0".parse().unwrap(),
+            local_addr: "0.0.0.0:0".parse().unwrap(),
+            destination: SocksAddr::Ip(format!("{}:443", ip).parse().unwrap()),
+            inbound_tag: "".to_string(),
+            dns_strategy: None,
+        }
+    }
+
+    fn geoip_rule(file: &str, country_code: &str) -> RoutingRule {
+        let mut rr = RoutingRule::new();
+        rr.target_tag = country_code.to_string();
+        let mut mmdb = config::RoutingRule_Mmdb::new();
+        mmdb.file = file.to_string();
+        mmdb.country_code = country_code.to_string();
+        rr.mmdbs.push(mmdb);
+        rr
+    }
+
+    #[test]
+    fn test_geoip_mmdb() {
+        // Maps 8.8.8.0/24 to US, 1.0.1.0/24 to CN and 2.16.0.0/13 to FR.
+        let file = concat!(env!("CARGO_MANIFEST_DIR"), "/src/app/testdata/country.mmdb");
+        let router = Router::new(&protobuf::RepeatedField::from_vec(vec![
+            geoip_rule(file, "cn"),
+            geoip_rule(file, "US"),
+        ]));
+        assert_eq!(router.pick_route(&geoip_session("8.8.8.8")).unwrap(), "US");
+        assert_eq!(router.pick_route(&geoip_session("1.0.1.1")).unwrap(), "cn");
+        assert!(router.pick_rule(&geoip_session("2.20.1.1")).is_none());
+        assert!(router.pick_rule(&geoip_session("8.8.9.1")).is_none());
+    }
+
+    #[test]
+    fn test_geoip_dat() {
+        let mut list = geosite::GeoIPList::new();
+        let mut geoip = geosite::GeoIP::new();
+        geoip.country_code = "CN".to_string();
+        let mut cidr = geosite::CIDR::new();
+        cidr.ip = vec![1, 0, 1, 0];
+        cidr.prefix = 24;
+        geoip.cidr.push(cidr);
+        let mut cidr = geosite::CIDR::new();
+        cidr.ip = "2400:3200::"
+            .parse::<std::net::Ipv6Addr>()
+            .unwrap()
+            .octets()
+            .to_vec();
+        cidr.prefix = 32;
+        geoip.cidr.push(cidr);
+        list.entry.push(geoip);
+
+        let path = std::env::temp_dir().join(format!("leaf-geoip-{}.dat", std::process::id()));
+        std::fs::write(&path, list.write_to_bytes().unwrap()).unwrap();
+        let router = Router::new(&protobuf::RepeatedField::from_vec(vec![geoip_rule(
+            path.to_str().unwrap(),
+            "cn",
+        )]));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(router.pick_route(&geoip_session("1.0.1.1")).unwrap(), "cn");
+        assert_eq!(
+            router.pick_route(&geoip_session("[2400:3200::1]")).unwrap(),
+            "cn"
+        );
+        assert!(router.pick_rule(&geoip_session("8.8.8.8")).is_none());
     }
 }
@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use futures::Future;
+use lazy_static::lazy_static;
+use log::*;
+use tokio::runtime::Handle;
+use tokio::sync::oneshot;
+
+use crate::{
+    common::sys,
+    config::Outbound,
+    proxy::{OutboundHandler, ProxyHandlerType},
+};
+
+use super::connections::ConnectionInfo;
+use super::dispatcher::Dispatcher;
+use super::dns_client::CacheItem;
+use super::nat_manager::NatManager;
+use super::outbound::health::Health;
+use super::outbound::manager::OutboundManager;
+use super::outbound::selector::OutboundSelector;
+
+pub type RuntimeId = u16;
+
+lazy_static! {
+    static ref RUNTIME_MANAGERS: Mutex<HashMap<RuntimeId, Arc<RuntimeManager>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Holds the components of a running instance which can be operated from
+/// outside of the runtime, e.g. through FFI calls.
+pub struct RuntimeManager {
+    handle: Handle,
+    outbound_manager: Arc<RwLock<OutboundManager>>,
+    dispatcher: Arc<Dispatcher>,
+    nat_manager: Arc<NatManager>,
+    control_outbound: String,
+    // (time, uplink bytes, downlink bytes) of the last load report
+    last_traffic: Mutex<(Instant, u64, u64)>,
+    // Signals the runner to stop with an optional grace period for draining
+    // the relayed connections, taken by the first shutdown.
+    shutdown: Mutex<Option<oneshot::Sender<Option<Duration>>>>,
+}
+
+/// Load of a running instance.
+pub struct Load {
+    pub tcp_connections: usize,
+    pub udp_sessions: usize,
+    /// Bytes per second since the last report.
+    pub uplink_rate: u64,
+    pub downlink_rate: u64,
+    pub uplink_bytes: u64,
+    pub downlink_bytes: u64,
+    /// Resident memory of the process in bytes, if available.
+    pub rss: Option<u64>,
+}
+
+impl RuntimeManager {
+    pub fn new(
+        handle: Handle,
+        outbound_manager: Arc<RwLock<OutboundManager>>,
+        dispatcher: Arc<Dispatcher>,
+        nat_manager: Arc<NatManager>,
+        control_outbound: String,
+        shutdown: oneshot::Sender<Option<Duration>>,
+    ) -> Self {
+        RuntimeManager {
+            handle,
+            outbound_manager,
+            dispatcher,
+            nat_manager,
+            control_outbound,
+            last_traffic: Mutex::new((Instant::now(), 0, 0)),
+            shutdown: Mutex::new(Some(shutdown)),
+        }
+    }
+
+    /// Runs the future on the runtime and waits for its output, must not be
+    /// called within the runtime.
+    pub fn block_on<F>(&self, f: F) -> Result<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        self.handle.spawn(async move {
+            let _ = tx.send(f.await);
+        });
+        rx.recv().map_err(|e| anyhow!("runtime stopped: {}", e))
+    }
+
+    /// Stops the instance. Inbounds stop accepting new connections at once,
+    /// with a grace period the relayed TCP connections and UDP sessions are
+    /// given up to that long to finish before the runtime is torn down,
+    /// otherwise they're dropped immediately. Returns without waiting for the
+    /// instance to stop.
+    pub fn shutdown(&self, grace: Option<Duration>) -> Result<()> {
+        let tx = self
+            .shutdown
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| anyhow!("runtime is already shutting down"))?;
+        tx.send(grace)
+            .map_err(|_| anyhow!("runtime is not running"))
+    }
+
+    pub fn add_outbounds(&self, outbounds: &protobuf::RepeatedField<Outbound>) {
+        let errors = self
+            .outbound_manager
+            .write()
+            .unwrap()
+            .add_outbounds(outbounds);
+        for e in errors {
+            warn!("{}", e);
+        }
+    }
+
+    /// Adds a single outbound without touching the existing handlers, fails
+    /// with an `AddOutboundError` on a duplicate tag or a missing actor.
+    pub fn add_outbound(&self, outbound: &Outbound) -> Result<()> {
+        self.outbound_manager
+            .write()
+            .unwrap()
+            .add_outbound(outbound)?;
+        Ok(())
+    }
+
+    /// Removes the outbound and stops its health checks, returns false if it
+    /// doesn't exist.
+    pub fn remove_outbound(&self, tag: &str) -> bool {
+        self.outbound_manager.write().unwrap().remove(tag).is_some()
+    }
+
+    pub fn get_outbound(&self, tag: &str) -> Option<Arc<dyn OutboundHandler>> {
+        self.outbound_manager.read().unwrap().get(tag).cloned()
+    }
+
+    /// Returns the selector of a select outbound.
+    pub fn get_selector(&self, tag: &str) -> Option<Arc<OutboundSelector>> {
+        self.outbound_manager.read().unwrap().get_selector(tag)
+    }
+
+    /// Returns the traffic of the outbound as (tx bytes, rx bytes, active
+    /// connections).
+    pub fn outbound_stats(&self, tag: &str) -> Option<(u64, u64, usize)> {
+        self.outbound_manager
+            .read()
+            .unwrap()
+            .stats()
+            .get(tag)
+            .copied()
+    }
+
+    pub fn default_outbound(&self) -> Option<Arc<dyn OutboundHandler>> {
+        let outbound_manager = self.outbound_manager.read().unwrap();
+        outbound_manager
+            .default_handler()
+            .and_then(|tag| outbound_manager.get(tag).cloned())
+    }
+
+    /// Returns the outbound for requests made by leaf itself, e.g. fetching
+    /// subscriptions, falls back to any direct outbound if the configured one
+    /// doesn't exist.
+    pub fn control_outbound(&self) -> Option<Arc<dyn OutboundHandler>> {
+        let outbound_manager = self.outbound_manager.read().unwrap();
+        let tag = if self.control_outbound.is_empty() {
+            "direct"
+        } else {
+            &self.control_outbound
+        };
+        if let Some(h) = outbound_manager.get(tag) {
+            return Some(h.clone());
+        }
+        outbound_manager
+            .handlers()
+            .find(|h| matches!(h.handler_type(), ProxyHandlerType::Direct))
+            .cloned()
+    }
+
+    pub fn dns_cache_snapshot(&self) -> Result<Vec<CacheItem>> {
+        let dns_client = self.outbound_manager.read().unwrap().dns_client().clone();
+        self.block_on(async move { dns_client.cache_snapshot().await })
+    }
+
+    /// Dumps the DNS cache as a JSON array.
+    pub fn dump_dns_cache(&self) -> Result<String> {
+        let items: Vec<String> = self
+            .dns_cache_snapshot()?
+            .iter()
+            .map(|item| {
+                let ips: Vec<String> = item.ips.iter().map(|ip| format!("\"{}\"", ip)).collect();
+                format!(
+                    "{{\"domain\":\"{}\",\"negative\":{},\"ips\":[{}],\"ttl\":{}}}",
+                    escape_json(&item.domain),
+                    item.is_negative(),
+                    ips.join(","),
+                    item.ttl
+                        .map(|ttl| ttl.to_string())
+                        .unwrap_or_else(|| "null".to_string()),
+                )
+            })
+            .collect();
+        Ok(format!("[{}]", items.join(",")))
+    }
+
+    /// Dumps the loaded routing rules as a JSON object, with the rules in
+    /// order, the default outbound and whether it's in dry-run mode.
+    pub fn dump_routes(&self) -> String {
+        let router = self.dispatcher.router();
+        let rules: Vec<String> = router
+            .summary()
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let conditions: Vec<String> = rule
+                    .conditions
+                    .iter()
+                    .map(|cond| {
+                        format!(
+                            "{{\"type\":\"{}\",\"count\":{},\"value\":\"{}\"}}",
+                            cond.kind,
+                            cond.count,
+                            escape_json(&cond.value),
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"index\":{},\"target\":\"{}\",\"log\":{},\"conditions\":[{}]}}",
+                    i,
+                    escape_json(&rule.target),
+                    rule.log,
+                    conditions.join(","),
+                )
+            })
+            .collect();
+        let default = self
+            .outbound_manager
+            .read()
+            .unwrap()
+            .default_handler()
+            .map(|tag| format!("\"{}\"", escape_json(tag)))
+            .unwrap_or_else(|| "null".to_string());
+        format!(
+            "{{\"dryRun\":{},\"default\":{},\"rules\":[{}]}}",
+            router.dry_run(),
+            default,
+            rules.join(","),
+        )
+    }
+
+    /// Dumps the health check results of a failover outbound as a JSON
+    /// object, with the actor tried first and the latency in milliseconds and
+    /// up state of each actor, both `null` if not checked yet.
+    pub fn dump_failover_status(&self, tag: &str) -> Result<String> {
+        let status = self
+            .outbound_manager
+            .read()
+            .unwrap()
+            .get_health_status(tag)
+            .ok_or_else(|| anyhow!("failover outbound [{}] not found", tag))?;
+        let actors: Vec<String> = status
+            .health()
+            .iter()
+            .map(|(tag, health)| {
+                let (latency, up) = match health {
+                    Health::Unknown => ("null".to_string(), "null"),
+                    Health::Up(rtt) => (rtt.as_millis().to_string(), "true"),
+                    Health::Down => ("null".to_string(), "false"),
+                };
+                format!(
+                    "{{\"tag\":\"{}\",\"latency\":{},\"up\":{}}}",
+                    escape_json(tag),
+                    latency,
+                    up,
+                )
+            })
+            .collect();
+        Ok(format!(
+            "{{\"preferred\":\"{}\",\"actors\":[{}]}}",
+            escape_json(status.preferred()),
+            actors.join(","),
+        ))
+    }
+
+    /// Dumps the races won by the actors of a tryall outbound as a JSON
+    /// object, with the number of wins and the average connect latency in
+    /// milliseconds of each actor, `null` if never won.
+    pub fn dump_tryall_stats(&self, tag: &str) -> Result<String> {
+        let stats = self
+            .outbound_manager
+            .read()
+            .unwrap()
+            .get_race_stats(tag)
+            .ok_or_else(|| anyhow!("tryall outbound [{}] not found", tag))?;
+        let actors: Vec<String> = stats
+            .wins()
+            .iter()
+            .map(|w| {
+                format!(
+                    "{{\"tag\":\"{}\",\"wins\":{},\"latency\":{}}}",
+                    escape_json(&w.tag),
+                    w.wins,
+                    w.latency
+                        .map(|l| l.as_millis().to_string())
+                        .unwrap_or_else(|| "null".to_string()),
+                )
+            })
+            .collect();
+        Ok(format!("{{\"actors\":[{}]}}", actors.join(",")))
+    }
+
+    pub fn flush_dns_cache(&self) -> Result<()> {
+        let dns_client = self.outbound_manager.read().unwrap().dns_client().clone();
+        self.block_on(async move { dns_client.flush_cache().await })
+    }
+
+    /// Returns the current load, throughput is averaged since the last call.
+    pub fn load(&self) -> Result<Load> {
+        let nat_manager = self.nat_manager.clone();
+        let udp_sessions = self.block_on(async move { nat_manager.size().await })?;
+        let traffic = self.dispatcher.traffic();
+        let uplink_bytes = traffic.uplink.load(Ordering::Relaxed);
+        let downlink_bytes = traffic.downlink.load(Ordering::Relaxed);
+        let now = Instant::now();
+        let (uplink_rate, downlink_rate) = {
+            let mut last = self.last_traffic.lock().unwrap();
+            let elapsed = now.duration_since(last.0).as_millis().max(1) as u64;
+            let rates = (
+                uplink_bytes.saturating_sub(last.1) * 1000 / elapsed,
+                downlink_bytes.saturating_sub(last.2) * 1000 / elapsed,
+            );
+            *last = (now, uplink_bytes, downlink_bytes);
+            rates
+        };
+        Ok(Load {
+            tcp_connections: self.dispatcher.num_tcp_connections(),
+            udp_sessions,
+            uplink_rate,
+            downlink_rate,
+            uplink_bytes,
+            downlink_bytes,
+            rss: sys::resident_memory(),
+        })
+    }
+
+    /// Reports the current load as a JSON object.
+    pub fn report_load(&self) -> Result<String> {
+        let load = self.load()?;
+        Ok(format!(
+            "{{\"tcpConnections\":{},\"udpSessions\":{},\"uplinkRate\":{},\"downlinkRate\":{},\"uplinkBytes\":{},\"downlinkBytes\":{},\"rss\":{}}}",
+            load.tcp_connections,
+            load.udp_sessions,
+            load.uplink_rate,
+            load.downlink_rate,
+            load.uplink_bytes,
+            load.downlink_bytes,
+            load.rss
+                .map(|rss| rss.to_string())
+                .unwrap_or_else(|| "null".to_string()),
+        ))
+    }
+
+    /// Returns the TCP connections and UDP sessions being relayed.
+    pub fn connections(&self) -> Vec<ConnectionInfo> {
+        self.dispatcher.connections().snapshot()
+    }
+
+    /// Dumps the connections being relayed as a JSON array, oldest first.
+    pub fn dump_connections(&self) -> String {
+        let conns: Vec<String> = self
+            .connections()
+            .iter()
+            .map(|c| {
+                format!(
+                    "{{\"id\":{},\"network\":\"{}\",\"source\":\"{}\",\"destination\":\"{}\",\"inbound\":\"{}\",\"outbound\":\"{}\",\"rule\":{},\"uplink\":{},\"downlink\":{},\"age\":{}}}",
+                    c.id,
+                    c.network,
+                    c.source,
+                    escape_json(&c.destination.to_string()),
+                    escape_json(&c.inbound_tag),
+                    escape_json(&c.outbound_tag),
+                    c.rule
+                        .map(|i| i.to_string())
+                        .unwrap_or_else(|| "null".to_string()),
+                    c.uplink,
+                    c.downlink,
+                    c.age.as_secs(),
+                )
+            })
+            .collect();
+        format!("[{}]", conns.join(","))
+    }
+
+    /// Handles a change of the underlying network, e.g. from Wi-Fi to
+    /// cellular. Cached DNS answers may not be valid on the new network, the
+    /// cache is flushed and domains are resolved again lazily, by the next
+    /// connections to them, nothing is re-resolved ahead. TCP connections
+    /// bound to the previous local address are reset so the clients
+    /// reconnect rather than waiting for timeouts.
+    pub fn notify_network_change(&self) -> Result<()> {
+        self.flush_dns_cache()?;
+        let n = self.dispatcher.reset_tcp_connections();
+        info!("network changed, reset {} tcp connections", n);
+        Ok(())
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn add(id: RuntimeId, manager: RuntimeManager) -> Result<()> {
+    let mut managers = RUNTIME_MANAGERS.lock().unwrap();
+    if managers.contains_key(&id) {
+        return Err(anyhow!("runtime {} already exists", id));
+    }
+    managers.insert(id, Arc::new(manager));
+    Ok(())
+}
+
+pub fn get(id: RuntimeId) -> Option<Arc<RuntimeManager>> {
+    RUNTIME_MANAGERS.lock().unwrap().get(&id).cloned()
+}
+
+pub fn remove(id: RuntimeId) -> Option<Arc<RuntimeManager>> {
+    RUNTIME_MANAGERS.lock().unwrap().remove(&id)
+}
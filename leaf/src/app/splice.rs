@@ -0,0 +1,219 @@
+use std::any::Any;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use futures::{future::BoxFuture, ready, Future};
+use mio::unix::EventedFd;
+use mio::{Evented, PollOpt, Ready, Token};
+use tokio::io::PollEvented;
+use tokio::net::TcpStream;
+
+use crate::proxy::ProxyStream;
+
+use super::dispatcher::Activity;
+
+// Bytes moved by each splice call, the default capacity of a pipe.
+const SPLICE_SIZE: usize = 64 * 1024;
+
+// An owned file descriptor, closed once dropped.
+struct Fd(RawFd);
+
+impl Fd {
+    // Duplicates the descriptor, the duplicate is registered to the reactor
+    // on its own, apart from the one of the stream it comes from.
+    fn dup(fd: RawFd) -> io::Result<Self> {
+        let fd = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Fd(fd))
+    }
+}
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.0) };
+    }
+}
+
+impl Evented for Fd {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        EventedFd(&self.0).deregister(poll)
+    }
+}
+
+fn pipe() -> io::Result<(Fd, Fd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok((Fd(fds[0]), Fd(fds[1])))
+}
+
+fn splice(fd_in: RawFd, fd_out: RawFd, len: usize) -> io::Result<usize> {
+    let n = unsafe {
+        libc::splice(
+            fd_in,
+            std::ptr::null_mut(),
+            fd_out,
+            std::ptr::null_mut(),
+            len,
+            libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+        )
+    };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(n as usize)
+}
+
+/// Relays from one TCP socket to another with splice(2), the bytes are moved
+/// through a pipe in the kernel rather than copied to userspace. Like
+/// `transfer`, the bytes written are added to all the counters.
+pub struct Splice<'a> {
+    reader: PollEvented<Fd>,
+    writer: PollEvented<Fd>,
+    pipe: (Fd, Fd),
+    // bytes in the pipe
+    pending: usize,
+    read_done: bool,
+    amt: u64,
+    counters: [&'a AtomicU64; 3],
+    activity: &'a Activity,
+}
+
+impl<'a> Splice<'a> {
+    /// Must be called within the runtime.
+    pub fn new(
+        reader: RawFd,
+        writer: RawFd,
+        counters: [&'a AtomicU64; 3],
+        activity: &'a Activity,
+    ) -> io::Result<Self> {
+        Ok(Splice {
+            reader: PollEvented::new(Fd::dup(reader)?)?,
+            writer: PollEvented::new(Fd::dup(writer)?)?,
+            pipe: pipe()?,
+            pending: 0,
+            read_done: false,
+            amt: 0,
+            counters,
+            activity,
+        })
+    }
+}
+
+impl Future for Splice<'_> {
+    type Output = io::Result<u64>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let me = &mut *self;
+        loop {
+            // The pipe is empty, so a would-block is for the socket.
+            if me.pending == 0 && !me.read_done {
+                ready!(me.reader.poll_read_ready(cx, Ready::readable()))?;
+                match splice(me.reader.get_ref().0, (me.pipe.1).0, SPLICE_SIZE) {
+                    Ok(0) => me.read_done = true,
+                    Ok(n) => me.pending = n,
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        me.reader.clear_read_ready(cx, Ready::readable())?;
+                        return Poll::Pending;
+                    }
+                    Err(e) => return Poll::Ready(Err(e)),
+                }
+            }
+
+            while me.pending > 0 {
+                ready!(me.writer.poll_write_ready(cx))?;
+                match splice((me.pipe.0).0, me.writer.get_ref().0, me.pending) {
+                    Ok(0) => {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::WriteZero,
+                            "write zero byte into writer",
+                        )));
+                    }
+                    Ok(n) => {
+                        me.pending -= n;
+                        me.amt += n as u64;
+                        for counter in me.counters.iter() {
+                            counter.fetch_add(n as u64, Ordering::Relaxed);
+                        }
+                        me.activity.touch();
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        me.writer.clear_write_ready(cx)?;
+                        return Poll::Pending;
+                    }
+                    Err(e) => return Poll::Ready(Err(e)),
+                }
+            }
+
+            if me.read_done {
+                return Poll::Ready(Ok(me.amt));
+            }
+        }
+    }
+}
+
+// Returns the socket of a plain TCP inbound connection, e.g. from a SOCKS or
+// TPROXY inbound, not one from TUN or with sniffed bytes buffered.
+fn inbound_fd(lhs: &dyn Any) -> Option<RawFd> {
+    if let Some(s) = lhs.downcast_ref::<TcpStream>() {
+        return Some(s.as_raw_fd());
+    }
+    if let Some(s) = lhs.downcast_ref::<Box<dyn ProxyStream>>() {
+        return s.as_tcp_stream().map(AsRawFd::as_raw_fd);
+    }
+    None
+}
+
+/// Returns the uplink and downlink relays splicing between the sockets, if
+/// both ends are plain TCP connections, e.g. to a direct outbound. `None` if
+/// the bytes have to go through userspace, e.g. to be encrypted.
+pub fn relays<'a>(
+    lhs: &dyn Any,
+    rhs: &dyn ProxyStream,
+    uplink_counters: [&'a AtomicU64; 3],
+    downlink_counters: [&'a AtomicU64; 3],
+    activity: &'a Activity,
+) -> Option<(
+    BoxFuture<'a, io::Result<u64>>,
+    BoxFuture<'a, io::Result<u64>>,
+)> {
+    let lhs = inbound_fd(lhs)?;
+    let rhs = rhs.as_tcp_stream()?.as_raw_fd();
+    let relays = Splice::new(lhs, rhs, uplink_counters, activity).and_then(|uplink| {
+        let downlink = Splice::new(rhs, lhs, downlink_counters, activity)?;
+        Ok((uplink, downlink))
+    });
+    match relays {
+        Ok((uplink, downlink)) => Some((Box::pin(uplink), Box::pin(downlink))),
+        Err(e) => {
+            log::debug!("splice not available: {}", e);
+            None
+        }
+    }
+}
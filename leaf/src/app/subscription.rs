@@ -0,0 +1,192 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use log::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::{
+    config::{self, Outbound},
+    proxy::{OutboundHandler, ProxyStream},
+    session::{Session, SocksAddr},
+};
+
+use super::runtime::{self, RuntimeId, RuntimeManager};
+
+struct Url {
+    tls: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+fn parse_url(url: &str) -> Result<Url> {
+    let (tls, rest) = if url.starts_with("https://") {
+        (true, &url["https://".len()..])
+    } else if url.starts_with("http://") {
+        (false, &url["http://".len()..])
+    } else {
+        return Err(anyhow!("unsupported url {}", url));
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/"),
+    };
+    // IPv6 hosts are in brackets, e.g. `[::1]:8443`.
+    let (host, port) = if authority.starts_with('[') {
+        match authority.find(']') {
+            Some(pos) => (&authority[1..pos], &authority[pos + 1..]),
+            None => return Err(anyhow!("invalid host in {}", url)),
+        }
+    } else {
+        match authority.rfind(':') {
+            Some(pos) => (&authority[..pos], &authority[pos..]),
+            None => (authority, ""),
+        }
+    };
+    let port = if port.is_empty() {
+        if tls {
+            443
+        } else {
+            80
+        }
+    } else if port.starts_with(':') {
+        port[1..]
+            .parse::<u16>()
+            .map_err(|e| anyhow!("invalid port in {}: {}", url, e))?
+    } else {
+        return Err(anyhow!("invalid host in {}", url));
+    };
+    Ok(Url {
+        tls,
+        host: host.to_string(),
+        port,
+        path: path.to_string(),
+    })
+}
+
+/// Fetches the body of an HTTP or HTTPS URL through the outbound.
+pub async fn fetch(outbound: Arc<dyn OutboundHandler>, url: &str) -> Result<Vec<u8>> {
+    let url = parse_url(url)?;
+    let mut sess = Session::default();
+    sess.destination = match url.host.parse::<IpAddr>() {
+        Ok(ip) => SocksAddr::Ip(SocketAddr::new(ip, url.port)),
+        Err(_) => SocksAddr::Domain(url.host.clone(), url.port),
+    };
+    let host_header = if url.host.contains(':') {
+        format!("[{}]", &url.host)
+    } else {
+        url.host.clone()
+    };
+
+    let stream = outbound.handle_tcp(&sess, None).await?;
+    let mut stream: Box<dyn ProxyStream> = if url.tls {
+        #[cfg(feature = "outbound-tls")]
+        {
+            use crate::proxy::{tls, TcpOutboundHandler};
+            let tls = tls::TcpHandler::new(
+                url.host.clone(),
+                tls::ClientOptions {
+                    alpns: vec!["http/1.1".to_string()],
+                    ..Default::default()
+                },
+                false,
+            );
+            tls.handle_tcp(&sess, Some(stream)).await?
+        }
+        #[cfg(not(feature = "outbound-tls"))]
+        {
+            return Err(anyhow!("https requires the outbound-tls feature"));
+        }
+    } else {
+        stream
+    };
+
+    // HTTP/1.0 so the server won't respond with chunked encoding.
+    let req = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: leaf\r\nAccept: */*\r\n\r\n",
+        &url.path, &host_header
+    );
+    stream.write_all(req.as_bytes()).await?;
+    let mut resp = Vec::new();
+    stream.read_to_end(&mut resp).await?;
+
+    let pos = resp
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| anyhow!("invalid http response"))?;
+    let head = String::from_utf8_lossy(&resp[..pos]);
+    let status = head.lines().next().unwrap_or("");
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(anyhow!("unexpected http response: {}", status));
+    }
+    Ok(resp[pos + 4..].to_vec())
+}
+
+// Adds the outbounds one by one, the existing ones are never replaced. Stops
+// at the first outbound failed to add, with the `AddOutboundError`.
+fn add_outbounds(manager: &RuntimeManager, outbounds: &[Outbound]) -> Result<()> {
+    for outbound in outbounds {
+        manager.add_outbound(outbound)?;
+    }
+    Ok(())
+}
+
+/// Adds outbounds converted from a share link to the runtime, fails with an
+/// `AddOutboundError` if an outbound can't be added.
+pub fn add_outbound_from_uri(rt_id: RuntimeId, uri: &str) -> Result<()> {
+    let manager = runtime::get(rt_id).ok_or_else(|| anyhow!("runtime {} not found", rt_id))?;
+    let outbounds = config::parse_share_link(uri)?;
+    add_outbounds(&manager, &outbounds)
+}
+
+/// Fetches a subscription through the control outbound of the runtime, and
+/// adds all the share links in it as outbounds, returns the number of
+/// outbounds added. Stops at the first outbound failed to add, with an
+/// `AddOutboundError`, the outbounds added before it are kept.
+pub fn import_subscription(rt_id: RuntimeId, url: &str) -> Result<usize> {
+    let manager = runtime::get(rt_id).ok_or_else(|| anyhow!("runtime {} not found", rt_id))?;
+    let outbound = manager
+        .control_outbound()
+        .ok_or_else(|| anyhow!("no outbound to fetch subscription"))?;
+    let url2 = url.to_string();
+    let body = manager.block_on(async move { fetch(outbound, &url2).await })??;
+    let outbounds = config::parse_subscription(&String::from_utf8_lossy(&body))?;
+    add_outbounds(&manager, &outbounds)?;
+    debug!("imported {} outbounds from {}", outbounds.len(), url);
+    Ok(outbounds.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> (bool, String, u16, String) {
+        let url = parse_url(url).unwrap();
+        (url.tls, url.host, url.port, url.path)
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(
+            parse("https://example.com/sub"),
+            (true, "example.com".to_string(), 443, "/sub".to_string())
+        );
+        assert_eq!(
+            parse("http://example.com:8080"),
+            (false, "example.com".to_string(), 8080, "/".to_string())
+        );
+        assert_eq!(
+            parse("https://[::1]/sub"),
+            (true, "::1".to_string(), 443, "/sub".to_string())
+        );
+        assert_eq!(
+            parse("https://[::1]:8443/sub?a=b"),
+            (true, "::1".to_string(), 8443, "/sub?a=b".to_string())
+        );
+        assert!(parse_url("https://[::1/sub").is_err());
+        assert!(parse_url("https://[::1]x/sub").is_err());
+        assert!(parse_url("https://example.com:x/sub").is_err());
+        assert!(parse_url("ftp://example.com/sub").is_err());
+    }
+}
@@ -202,6 +202,45 @@ pub mod aead {
             Ok(())
         }
     }
+
+    /// Seals a one-off message with its own nonce and the associated data,
+    /// the tag is appended.
+    pub fn seal(
+        cipher: &str,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        in_out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let cipher = AeadCipher::new(cipher)?;
+        let mut tag = vec![0u8; cipher.tag_len()];
+        let ciphertext = symm::encrypt_aead(cipher.cipher, key, Some(nonce), aad, in_out, &mut tag)
+            .map_err(|e| anyhow!("encrypt failed: {}", e))?;
+        in_out.copy_from_slice(&ciphertext);
+        in_out.extend_from_slice(&tag);
+        Ok(())
+    }
+
+    /// Opens a message sealed by `seal`, the tag is removed.
+    pub fn open(
+        cipher: &str,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        in_out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let cipher = AeadCipher::new(cipher)?;
+        let tag_len = cipher.tag_len();
+        if in_out.len() < tag_len {
+            return Err(anyhow!("decrypt failed: data too short"));
+        }
+        let (data, tag) = in_out.split_at(in_out.len() - tag_len);
+        let plaintext = symm::decrypt_aead(cipher.cipher, key, Some(nonce), aad, data, tag)
+            .map_err(|e| anyhow!("decrypt failed: {}", e))?;
+        in_out.truncate(plaintext.len());
+        in_out.copy_from_slice(&plaintext);
+        Ok(())
+    }
 }
 
 #[cfg(feature = "ring-aead")]
@@ -342,6 +381,49 @@ pub mod aead {
             Ok(())
         }
     }
+
+    fn less_safe_key(cipher: &str, key: &[u8]) -> Result<LessSafeKey> {
+        let cipher = AeadCipher::new(cipher)?;
+        let unbound_key = UnboundKey::new(cipher.algorithm, key)
+            .map_err(|e| anyhow!("new unbound key failed: {}", e))?;
+        Ok(LessSafeKey::new(unbound_key))
+    }
+
+    /// Seals a one-off message with its own nonce and the associated data,
+    /// the tag is appended.
+    pub fn seal(
+        cipher: &str,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        in_out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let key = less_safe_key(cipher, key)?;
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|e| anyhow!("encrypt failed: {}", e))?;
+        key.seal_in_place_append_tag(nonce, Aad::from(aad), in_out)
+            .map_err(|e| anyhow!("encrypt failed: {}", e))?;
+        Ok(())
+    }
+
+    /// Opens a message sealed by `seal`, the tag is removed.
+    pub fn open(
+        cipher: &str,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        in_out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let key = less_safe_key(cipher, key)?;
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|e| anyhow!("decrypt failed: {}", e))?;
+        let n = key
+            .open_in_place(nonce, Aad::from(aad), in_out)
+            .map_err(|e| anyhow!("decrypt failed: {}", e))?
+            .len();
+        in_out.truncate(n);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -394,4 +476,17 @@ mod tests {
 
         assert_eq!(&buf[..plaintext.len()], plaintext);
     }
+
+    #[test]
+    fn test_aead_seal_open() {
+        let key = [1u8; 16];
+        let nonce = [2u8; 12];
+        let mut buf = b"Hello, world!".to_vec();
+        aead::seal("aes-128-gcm", &key, &nonce, b"aad", &mut buf).unwrap();
+        assert_eq!(buf.len(), 13 + 16);
+        let mut tampered = buf.clone();
+        assert!(aead::open("aes-128-gcm", &key, &nonce, b"other", &mut tampered).is_err());
+        aead::open("aes-128-gcm", &key, &nonce, b"aad", &mut buf).unwrap();
+        assert_eq!(&buf[..], b"Hello, world!");
+    }
 }
@@ -1,18 +1,90 @@
-pub fn setup_logger(loglevel: log::LevelFilter) -> fern::Dispatch {
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Once, RwLock};
+
+use lazy_static::lazy_static;
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::config::{Log, Log_Format};
+
+/// How log lines are formatted.
+#[derive(Clone, Copy, Debug)]
+pub struct LogFormat {
+    pub format: Log_Format,
+    pub timestamp: bool,
+    pub color: bool,
+}
+
+impl Default for LogFormat {
+    fn default() -> Self {
+        LogFormat {
+            format: Log_Format::DEFAULT,
+            timestamp: true,
+            color: true,
+        }
+    }
+}
+
+impl LogFormat {
+    pub fn new(log: Option<&Log>) -> Self {
+        match log {
+            Some(log) => LogFormat {
+                format: log.format,
+                timestamp: !log.disable_timestamp,
+                color: !log.disable_color,
+            },
+            None => LogFormat::default(),
+        }
+    }
+}
+
+// The level of leaf's logs, the logs of dependencies are at most warn.
+static LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Info as usize);
+
+/// Sets the level of leaf's logs, it takes effect immediately on the applied
+/// logger.
+pub fn set_level(level: LevelFilter) {
+    LEVEL.store(level as usize, Ordering::Relaxed);
+    log::set_max_level(level.max(LevelFilter::Warn));
+}
+
+pub fn level() -> LevelFilter {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Returns whether a record passes the levels, for outputs filtering by
+/// themselves.
+pub fn enabled(metadata: &Metadata) -> bool {
+    let target = metadata.target();
+    let level = if target == "leaf" || target.starts_with("leaf::") {
+        level()
+    } else {
+        LevelFilter::Warn
+    };
+    metadata.level() <= level
+}
+
+/// Returns the dispatch of the logger, `loglevel` is set as the current
+/// level, see `set_level`.
+pub fn setup_logger(loglevel: LevelFilter, format: LogFormat) -> fern::Dispatch {
+    set_level(loglevel);
     fern::Dispatch::new()
-        .format(move |out, message, record| {
-            out.finish(
-                #[cfg(target_os = "ios")]
-                {
-                    format_args!(
-                        "[{date}][{level}] {message}",
-                        date = chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
-                        level = record.level(),
-                        message = message,
-                    )
-                },
-                #[cfg(not(target_os = "ios"))]
-                {
+        .format(move |out, message, record| match format.format {
+            Log_Format::DEFAULT => {
+                let date = if format.timestamp {
+                    format!("[{}]", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"))
+                } else {
+                    String::new()
+                };
+                // There's no color on iOS, the console doesn't support it.
+                if format.color && !cfg!(target_os = "ios") {
                     use fern::colors::{Color, ColoredLevelConfig};
                     let colors_line = ColoredLevelConfig::new()
                         .error(Color::Red)
@@ -22,25 +94,218 @@ pub fn setup_logger(loglevel: log::LevelFilter) -> fern::Dispatch {
                         .trace(Color::BrightBlack);
 
                     let colors_level = colors_line.clone().info(Color::Green);
-                    format_args!(
-                        // "{color_line}[{date}][{level}{color_line}][{target}] {message}\x1B[0m",
-                        "{color_line}[{date}][{level}{color_line}] {message}\x1B[0m",
+                    out.finish(format_args!(
+                        "{color_line}{date}[{level}{color_line}] {message}\x1B[0m",
                         color_line = format_args!(
                             "\x1B[{}m",
                             colors_line.get_color(&record.level()).to_fg_str()
                         ),
-                        date = chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
-                        // target = record.target(),
+                        date = date,
                         level = colors_level.color(record.level()),
                         message = message,
+                    ))
+                } else {
+                    out.finish(format_args!(
+                        "{date}[{level}] {message}",
+                        date = date,
+                        level = record.level(),
+                        message = message,
+                    ))
+                }
+            }
+            Log_Format::PLAIN => {
+                if format.timestamp {
+                    out.finish(format_args!(
+                        "{} {} {}: {}",
+                        chrono::Local::now().to_rfc3339(),
+                        record.level(),
+                        record.target(),
+                        message
+                    ))
+                } else {
+                    out.finish(format_args!(
+                        "{} {}: {}",
+                        record.level(),
+                        record.target(),
+                        message
+                    ))
+                }
+            }
+            Log_Format::JSON => {
+                let ts = if format.timestamp {
+                    Some(chrono::Local::now().to_rfc3339())
+                } else {
+                    None
+                };
+                out.finish(format_args!(
+                    "{}",
+                    json_line(
+                        record.level(),
+                        record.target(),
+                        ts.as_deref(),
+                        &message.to_string()
                     )
-                },
-            )
+                ))
+            }
         })
-        .level(log::LevelFilter::Warn)
-        .level_for("leaf", loglevel)
+        .filter(enabled)
 }
 
+lazy_static! {
+    static ref LOGGER: RwLock<Option<Box<dyn Log>>> = RwLock::new(None);
+}
+
+static INSTALL_LOGGER: Once = Once::new();
+
+// The global logger, forwarding to the last applied one.
+struct SwappableLogger;
+
+static SWAPPABLE_LOGGER: SwappableLogger = SwappableLogger;
+
+impl Log for SwappableLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match LOGGER.read().unwrap().as_ref() {
+            Some(logger) => logger.enabled(metadata),
+            None => false,
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        if let Some(logger) = LOGGER.read().unwrap().as_ref() {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(logger) = LOGGER.read().unwrap().as_ref() {
+            logger.flush();
+        }
+    }
+}
+
+/// Applies the dispatch as the logger. It can be applied again, e.g. when
+/// an instance is run with another config, the outputs of the previous one
+/// are then replaced.
 pub fn apply_logger(dispatch: fern::Dispatch) {
-    dispatch.apply().expect("setup logger failed");
+    let (_, logger) = dispatch.into_log();
+    let previous = LOGGER.write().unwrap().replace(logger);
+    if let Some(previous) = previous {
+        previous.flush();
+    }
+    INSTALL_LOGGER.call_once(|| {
+        log::set_logger(&SWAPPABLE_LOGGER).expect("setup logger failed");
+    });
+    // The dispatch doesn't know the levels of the filter.
+    log::set_max_level(level().max(LevelFilter::Warn));
+}
+
+// Returns the record as a JSON object on a single line.
+fn json_line(level: log::Level, target: &str, ts: Option<&str>, msg: &str) -> String {
+    let mut line = String::new();
+    line.push_str("{\"level\":");
+    push_json_str(&mut line, &level.to_string());
+    line.push_str(",\"target\":");
+    push_json_str(&mut line, target);
+    if let Some(ts) = ts {
+        line.push_str(",\"ts\":");
+        push_json_str(&mut line, ts);
+    }
+    line.push_str(",\"msg\":");
+    push_json_str(&mut line, msg);
+    line.push('}');
+    line
+}
+
+fn push_json_str(buf: &mut String, s: &str) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    // Captures the records of the test's own target, other tests may log
+    // through the global logger at the same time.
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Log for Capture {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            if record.target() == "leaf::test" {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_reapply_logger() {
+        let previous_level = level();
+        let previous = LOGGER.write().unwrap().take();
+
+        let first = Arc::new(Mutex::new(Vec::new()));
+        apply_logger(
+            setup_logger(LevelFilter::Info, LogFormat::default())
+                .chain(Box::new(Capture(first.clone())) as Box<dyn Log>),
+        );
+        log::info!(target: "leaf::test", "a");
+        log::debug!(target: "leaf::test", "b");
+        assert_eq!(first.lock().unwrap().len(), 1);
+
+        // The second config has another level and output.
+        let second = Arc::new(Mutex::new(Vec::new()));
+        apply_logger(
+            setup_logger(LevelFilter::Debug, LogFormat::default())
+                .chain(Box::new(Capture(second.clone())) as Box<dyn Log>),
+        );
+        log::debug!(target: "leaf::test", "c");
+        assert_eq!(*first.lock().unwrap(), vec!["a"]);
+        assert_eq!(*second.lock().unwrap(), vec!["c"]);
+
+        set_level(LevelFilter::Trace);
+        log::trace!(target: "leaf::test", "e");
+        set_level(LevelFilter::Error);
+        log::info!(target: "leaf::test", "f");
+        assert_eq!(*second.lock().unwrap(), vec!["c", "e"]);
+
+        *LOGGER.write().unwrap() = previous;
+        set_level(previous_level);
+    }
+
+    #[test]
+    fn test_json_line() {
+        assert_eq!(
+            json_line(
+                log::Level::Info,
+                "leaf::app",
+                Some("2021-01-01T00:00:00+08:00"),
+                "say \"hi\"\n\u{1}"
+            ),
+            r#"{"level":"INFO","target":"leaf::app","ts":"2021-01-01T00:00:00+08:00","msg":"say \"hi\"\n\u0001"}"#
+        );
+        assert_eq!(
+            json_line(log::Level::Warn, "leaf", None, "x"),
+            r#"{"level":"WARN","target":"leaf","msg":"x"}"#
+        );
+    }
 }
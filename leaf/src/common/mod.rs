@@ -1,5 +1,7 @@
 pub mod crypto;
 pub mod log;
 pub mod mutex;
+pub mod process;
 pub mod resolver;
-// pub mod stream;
+pub mod stream;
+pub mod sys;
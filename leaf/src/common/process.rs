@@ -0,0 +1,741 @@
+//! Finds the local process owning a socket, which only works for connections
+//! originated on this host, e.g. the ones from the SOCKS or TUN inbounds.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+
+use crate::option;
+use crate::session::Network;
+
+// Number of the processes owning sockets found recently, which are checked
+// first as they likely own the next sockets too, e.g. a browser.
+const RECENT_PIDS_SIZE: usize = 16;
+
+// Process names with the time looked up.
+type Cache<K> = Mutex<HashMap<K, (Option<String>, Instant)>>;
+
+lazy_static! {
+    static ref CACHE: Cache<(Network, SocketAddr)> = Mutex::new(HashMap::new());
+    static ref NAMES: Cache<u32> = Mutex::new(HashMap::new());
+    static ref RECENT_PIDS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+}
+
+fn get_cached<K>(cache: &Cache<K>, key: &K) -> Option<Option<String>>
+where
+    K: std::hash::Hash + Eq,
+{
+    let ttl = Duration::from_secs(option::PROCESS_LOOKUP_CACHE_TTL);
+    match cache.lock().unwrap().get(key) {
+        Some((name, t)) if t.elapsed() < ttl => Some(name.clone()),
+        _ => None,
+    }
+}
+
+fn put_cached<K>(cache: &Cache<K>, key: K, name: Option<String>)
+where
+    K: std::hash::Hash + Eq,
+{
+    let ttl = Duration::from_secs(option::PROCESS_LOOKUP_CACHE_TTL);
+    let mut cache = cache.lock().unwrap();
+    if cache.len() >= option::PROCESS_LOOKUP_CACHE_SIZE {
+        cache.retain(|_, (_, t)| t.elapsed() < ttl);
+        if cache.len() >= option::PROCESS_LOOKUP_CACHE_SIZE {
+            cache.clear();
+        }
+    }
+    cache.insert(key, (name, Instant::now()));
+}
+
+/// Returns the executable file name, e.g. `firefox` or `firefox.exe`, of the
+/// local process owning the socket bound to `addr`. Results, including
+/// failed lookups, are cached for `PROCESS_LOOKUP_CACHE_TTL` seconds, by the
+/// socket and by the process.
+///
+/// It may scan the sockets of all processes, use `lookup_process_name` on
+/// the runtime threads.
+pub fn find_process_name(network: Network, addr: &SocketAddr) -> Option<String> {
+    if let Some(name) = get_cached(&CACHE, &(network, *addr)) {
+        return name;
+    }
+    // The locks are not held while looking up, it takes a while to scan the
+    // sockets of all processes.
+    let name = find_pid(network, addr).and_then(process_name);
+    put_cached(&CACHE, (network, *addr), name.clone());
+    name
+}
+
+/// Same as `find_process_name`, but scans on a blocking thread, the cached
+/// result is returned right away.
+pub async fn lookup_process_name(network: Network, addr: SocketAddr) -> Option<String> {
+    if let Some(name) = get_cached(&CACHE, &(network, addr)) {
+        return name;
+    }
+    tokio::task::spawn_blocking(move || find_process_name(network, &addr))
+        .await
+        .ok()
+        .flatten()
+}
+
+fn find_pid(network: Network, addr: &SocketAddr) -> Option<u32> {
+    let recent = RECENT_PIDS.lock().unwrap().clone();
+    let pid = sys::find_pid(network, addr, &recent)?;
+    let mut recent = RECENT_PIDS.lock().unwrap();
+    recent.retain(|p| *p != pid);
+    recent.insert(0, pid);
+    recent.truncate(RECENT_PIDS_SIZE);
+    Some(pid)
+}
+
+fn process_name(pid: u32) -> Option<String> {
+    if let Some(name) = get_cached(&NAMES, &pid) {
+        return name;
+    }
+    let name = sys::process_name(pid);
+    put_cached(&NAMES, pid, name.clone());
+    name
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+))]
+fn unmap(ip: std::net::IpAddr) -> std::net::IpAddr {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    if let IpAddr::V6(v6) = ip {
+        if v6.segments()[..6] == [0, 0, 0, 0, 0, 0xffff] {
+            let o = v6.octets();
+            return IpAddr::V4(Ipv4Addr::new(o[12], o[13], o[14], o[15]));
+        }
+    }
+    ip
+}
+
+// Tells whether a socket bound to `local` is the one at `addr`. Dual-stack
+// sockets are bound to IPv4-mapped addresses, and UDP sockets are usually
+// bound to unspecified ones.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+))]
+fn matches(network: Network, local: &SocketAddr, addr: &SocketAddr) -> bool {
+    if local.port() != addr.port() {
+        return false;
+    }
+    let local_ip = unmap(local.ip());
+    local_ip == unmap(addr.ip()) || (network == Network::Udp && local_ip.is_unspecified())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use std::fs;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    use crate::session::Network;
+
+    // Parses a local or remote address in /proc/net/{tcp,udp}{,6}, e.g.
+    // `0100007F:1F90`. The IP is printed as 32-bit words in native endian.
+    fn parse_addr(s: &str) -> Option<SocketAddr> {
+        let mut parts = s.splitn(2, ':');
+        let ip = parts.next()?;
+        let port = u16::from_str_radix(parts.next()?, 16).ok()?;
+        let ip = match ip.len() {
+            8 => {
+                let w = u32::from_str_radix(ip, 16).ok()?;
+                IpAddr::V4(Ipv4Addr::from(w.to_ne_bytes()))
+            }
+            32 => {
+                let mut b = [0u8; 16];
+                for i in 0..4 {
+                    let w = u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16).ok()?;
+                    b[i * 4..i * 4 + 4].copy_from_slice(&w.to_ne_bytes());
+                }
+                IpAddr::V6(Ipv6Addr::from(b))
+            }
+            _ => return None,
+        };
+        Some(SocketAddr::new(ip, port))
+    }
+
+    fn find_inode(network: Network, addr: &SocketAddr) -> Option<u64> {
+        let files = match network {
+            Network::Tcp => ["/proc/net/tcp", "/proc/net/tcp6"],
+            Network::Udp => ["/proc/net/udp", "/proc/net/udp6"],
+        };
+        for file in files.iter() {
+            let content = match fs::read_to_string(file) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            // sl local_address rem_address st tx_queue:rx_queue tr:tm->when
+            // retrnsmt uid timeout inode ...
+            for line in content.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 10 {
+                    continue;
+                }
+                let local = match parse_addr(fields[1]) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                if !super::matches(network, &local, addr) {
+                    continue;
+                }
+                // Sockets in TIME_WAIT have no inode.
+                match fields[9].parse::<u64>() {
+                    Ok(inode) if inode != 0 => return Some(inode),
+                    _ => continue,
+                }
+            }
+        }
+        None
+    }
+
+    fn owns_socket(pid: u32, target: &str) -> bool {
+        let fds = match fs::read_dir(format!("/proc/{}/fd", pid)) {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+        for fd in fds.flatten() {
+            if let Ok(link) = fs::read_link(fd.path()) {
+                if link.to_str() == Some(target) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    pub fn find_pid(network: Network, addr: &SocketAddr, recent: &[u32]) -> Option<u32> {
+        let inode = find_inode(network, addr)?;
+        let target = format!("socket:[{}]", inode);
+        if let Some(pid) = recent.iter().find(|pid| owns_socket(**pid, &target)) {
+            return Some(*pid);
+        }
+        for entry in fs::read_dir("/proc").ok()?.flatten() {
+            let pid = match entry
+                .file_name()
+                .to_str()
+                .and_then(|s| s.parse::<u32>().ok())
+            {
+                Some(v) => v,
+                None => continue,
+            };
+            if !recent.contains(&pid) && owns_socket(pid, &target) {
+                return Some(pid);
+            }
+        }
+        None
+    }
+
+    pub fn process_name(pid: u32) -> Option<String> {
+        if let Ok(exe) = fs::read_link(format!("/proc/{}/exe", pid)) {
+            if let Some(name) = exe.file_name().and_then(|n| n.to_str()) {
+                return Some(name.to_owned());
+            }
+        }
+        // Truncated to 15 bytes, but readable for processes of other users.
+        fs::read_to_string(format!("/proc/{}/comm", pid))
+            .ok()
+            .map(|v| v.trim_end().to_owned())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    use std::ffi::OsStr;
+    use std::mem;
+    use std::net::{IpAddr, SocketAddr};
+    use std::os::raw::{c_int, c_void};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+    use std::ptr;
+
+    use crate::session::Network;
+
+    const PROC_PIDLISTFDS: c_int = 1;
+    const PROC_PIDFDSOCKETINFO: c_int = 3;
+    const PROX_FDTYPE_SOCKET: u32 = 2;
+    const PROC_PIDPATHINFO_MAXSIZE: usize = 4096;
+    const AF_INET: c_int = 2;
+    const AF_INET6: c_int = 30;
+    const IPPROTO_TCP: c_int = 6;
+    const IPPROTO_UDP: c_int = 17;
+    const INI_IPV4: u8 = 0x1;
+
+    // Structs from <sys/proc_info.h>, with the unused parts left opaque.
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct ProcFdInfo {
+        proc_fd: i32,
+        proc_fdtype: u32,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct InSockInfo {
+        insi_fport: i32,
+        insi_lport: i32,
+        insi_gencnt: u64,
+        insi_flags: u32,
+        insi_flow: u32,
+        insi_vflag: u8,
+        insi_ip_ttl: u8,
+        rfu_1: u32,
+        // in4in6_addr for IPv4, which has the address in the last 4 bytes.
+        insi_faddr: [u8; 16],
+        insi_laddr: [u8; 16],
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct SocketFdInfo {
+        pfi: [u8; 24],
+        soi_stat: [u8; 136],
+        soi_so: u64,
+        soi_pcb: u64,
+        soi_type: i32,
+        soi_protocol: i32,
+        soi_family: i32,
+        soi_misc: [u8; 20],
+        soi_rcv: [u8; 24],
+        soi_snd: [u8; 24],
+        soi_kind: i32,
+        rfu_1: u32,
+        // A union, starting with in_sockinfo for both TCP and UDP sockets.
+        soi_proto: [u64; 66],
+    }
+
+    extern "C" {
+        fn proc_listallpids(buffer: *mut c_void, buffersize: c_int) -> c_int;
+        fn proc_pidinfo(
+            pid: c_int,
+            flavor: c_int,
+            arg: u64,
+            buffer: *mut c_void,
+            buffersize: c_int,
+        ) -> c_int;
+        fn proc_pidfdinfo(
+            pid: c_int,
+            fd: c_int,
+            flavor: c_int,
+            buffer: *mut c_void,
+            buffersize: c_int,
+        ) -> c_int;
+        fn proc_pidpath(pid: c_int, buffer: *mut c_void, buffersize: u32) -> c_int;
+    }
+
+    fn list_pids() -> Option<Vec<c_int>> {
+        let n = unsafe { proc_listallpids(ptr::null_mut(), 0) };
+        if n <= 0 {
+            return None;
+        }
+        // Leaves room for the processes started in between.
+        let mut pids = vec![0 as c_int; n as usize + 64];
+        let n = unsafe {
+            proc_listallpids(
+                pids.as_mut_ptr() as *mut c_void,
+                (pids.len() * mem::size_of::<c_int>()) as c_int,
+            )
+        };
+        if n <= 0 {
+            return None;
+        }
+        pids.truncate(n as usize);
+        Some(pids)
+    }
+
+    fn list_socket_fds(pid: c_int) -> Vec<c_int> {
+        let size = unsafe { proc_pidinfo(pid, PROC_PIDLISTFDS, 0, ptr::null_mut(), 0) };
+        if size <= 0 {
+            return Vec::new();
+        }
+        let mut fds: Vec<ProcFdInfo> =
+            Vec::with_capacity(size as usize / mem::size_of::<ProcFdInfo>());
+        let size = unsafe {
+            proc_pidinfo(
+                pid,
+                PROC_PIDLISTFDS,
+                0,
+                fds.as_mut_ptr() as *mut c_void,
+                (fds.capacity() * mem::size_of::<ProcFdInfo>()) as c_int,
+            )
+        };
+        if size <= 0 {
+            return Vec::new();
+        }
+        unsafe { fds.set_len(size as usize / mem::size_of::<ProcFdInfo>()) };
+        fds.iter()
+            .filter(|f| f.proc_fdtype == PROX_FDTYPE_SOCKET)
+            .map(|f| f.proc_fd)
+            .collect()
+    }
+
+    fn socket_local_addr(pid: c_int, fd: c_int, protocol: c_int) -> Option<SocketAddr> {
+        let mut info: SocketFdInfo = unsafe { mem::zeroed() };
+        let size = mem::size_of::<SocketFdInfo>() as c_int;
+        let ret = unsafe {
+            proc_pidfdinfo(
+                pid,
+                fd,
+                PROC_PIDFDSOCKETINFO,
+                &mut info as *mut _ as *mut c_void,
+                size,
+            )
+        };
+        if ret != size
+            || info.soi_protocol != protocol
+            || (info.soi_family != AF_INET && info.soi_family != AF_INET6)
+        {
+            return None;
+        }
+        let ini = unsafe { &*(info.soi_proto.as_ptr() as *const InSockInfo) };
+        let port = u16::from_be(ini.insi_lport as u16);
+        let ip = if ini.insi_vflag & INI_IPV4 != 0 {
+            let mut b = [0u8; 4];
+            b.copy_from_slice(&ini.insi_laddr[12..]);
+            IpAddr::from(b)
+        } else {
+            IpAddr::from(ini.insi_laddr)
+        };
+        Some(SocketAddr::new(ip, port))
+    }
+
+    pub fn process_name(pid: u32) -> Option<String> {
+        let mut buf = vec![0u8; PROC_PIDPATHINFO_MAXSIZE];
+        let n = unsafe {
+            proc_pidpath(
+                pid as c_int,
+                buf.as_mut_ptr() as *mut c_void,
+                PROC_PIDPATHINFO_MAXSIZE as u32,
+            )
+        };
+        if n <= 0 {
+            return None;
+        }
+        let path = Path::new(OsStr::from_bytes(&buf[..n as usize]));
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_owned())
+    }
+
+    fn owns_socket(pid: c_int, network: Network, addr: &SocketAddr) -> bool {
+        let protocol = match network {
+            Network::Tcp => IPPROTO_TCP,
+            Network::Udp => IPPROTO_UDP,
+        };
+        list_socket_fds(pid).into_iter().any(|fd| {
+            socket_local_addr(pid, fd, protocol)
+                .map_or(false, |local| super::matches(network, &local, addr))
+        })
+    }
+
+    pub fn find_pid(network: Network, addr: &SocketAddr, recent: &[u32]) -> Option<u32> {
+        if let Some(pid) = recent
+            .iter()
+            .find(|pid| owns_socket(**pid as c_int, network, addr))
+        {
+            return Some(*pid);
+        }
+        for pid in list_pids()? {
+            if pid == 0 || recent.contains(&(pid as u32)) {
+                continue;
+            }
+            if owns_socket(pid, network, addr) {
+                return Some(pid as u32);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod sys {
+    use std::ffi::OsString;
+    use std::mem;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::os::raw::c_void;
+    use std::os::windows::ffi::OsStringExt;
+    use std::path::Path;
+    use std::slice;
+
+    use crate::session::Network;
+
+    const AF_INET: u32 = 2;
+    const AF_INET6: u32 = 23;
+    const TCP_TABLE_OWNER_PID_ALL: u32 = 5;
+    const UDP_TABLE_OWNER_PID: u32 = 1;
+    const NO_ERROR: u32 = 0;
+    const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    // Rows of the tables from <tcpmib.h> and <udpmib.h>, ports are in network
+    // byte order in the low 16 bits.
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct MibTcpRowOwnerPid {
+        state: u32,
+        local_addr: u32,
+        local_port: u32,
+        remote_addr: u32,
+        remote_port: u32,
+        owning_pid: u32,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct MibTcp6RowOwnerPid {
+        local_addr: [u8; 16],
+        local_scope_id: u32,
+        local_port: u32,
+        remote_addr: [u8; 16],
+        remote_scope_id: u32,
+        remote_port: u32,
+        state: u32,
+        owning_pid: u32,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct MibUdpRowOwnerPid {
+        local_addr: u32,
+        local_port: u32,
+        owning_pid: u32,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct MibUdp6RowOwnerPid {
+        local_addr: [u8; 16],
+        local_scope_id: u32,
+        local_port: u32,
+        owning_pid: u32,
+    }
+
+    #[link(name = "iphlpapi")]
+    extern "system" {
+        fn GetExtendedTcpTable(
+            table: *mut c_void,
+            size: *mut u32,
+            order: i32,
+            af: u32,
+            class: u32,
+            reserved: u32,
+        ) -> u32;
+        fn GetExtendedUdpTable(
+            table: *mut c_void,
+            size: *mut u32,
+            order: i32,
+            af: u32,
+            class: u32,
+            reserved: u32,
+        ) -> u32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn QueryFullProcessImageNameW(
+            process: *mut c_void,
+            flags: u32,
+            name: *mut u16,
+            size: *mut u32,
+        ) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    // Returns the table, the number of rows followed by the rows, in 32-bit
+    // words to keep the rows aligned.
+    fn get_table(network: Network, af: u32) -> Option<Vec<u32>> {
+        let mut buf: Vec<u32> = Vec::new();
+        let mut size = 0u32;
+        loop {
+            let ret = unsafe {
+                match network {
+                    Network::Tcp => GetExtendedTcpTable(
+                        buf.as_mut_ptr() as *mut c_void,
+                        &mut size,
+                        0,
+                        af,
+                        TCP_TABLE_OWNER_PID_ALL,
+                        0,
+                    ),
+                    Network::Udp => GetExtendedUdpTable(
+                        buf.as_mut_ptr() as *mut c_void,
+                        &mut size,
+                        0,
+                        af,
+                        UDP_TABLE_OWNER_PID,
+                        0,
+                    ),
+                }
+            };
+            match ret {
+                NO_ERROR => return Some(buf),
+                // The table may grow in between, try again.
+                ERROR_INSUFFICIENT_BUFFER => buf = vec![0u32; (size as usize + 3) / 4],
+                _ => return None,
+            }
+        }
+    }
+
+    fn rows<T>(table: &[u32]) -> &[T] {
+        if table.is_empty() {
+            return &[];
+        }
+        let n = (table[0] as usize).min((table.len() - 1) * 4 / mem::size_of::<T>());
+        unsafe { slice::from_raw_parts(table[1..].as_ptr() as *const T, n) }
+    }
+
+    fn v4(addr: u32, port: u32) -> SocketAddr {
+        SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::from(addr.to_ne_bytes())),
+            u16::from_be(port as u16),
+        )
+    }
+
+    fn v6(addr: [u8; 16], port: u32) -> SocketAddr {
+        SocketAddr::new(IpAddr::from(addr), u16::from_be(port as u16))
+    }
+
+    // The tables are complete with the owners, there's no need to check the
+    // recent processes first.
+    pub fn find_pid(network: Network, addr: &SocketAddr, _recent: &[u32]) -> Option<u32> {
+        let mut sockets = Vec::new();
+        if let Some(t) = get_table(network, AF_INET) {
+            match network {
+                Network::Tcp => sockets.extend(
+                    rows::<MibTcpRowOwnerPid>(&t)
+                        .iter()
+                        .map(|r| (v4(r.local_addr, r.local_port), r.owning_pid)),
+                ),
+                Network::Udp => sockets.extend(
+                    rows::<MibUdpRowOwnerPid>(&t)
+                        .iter()
+                        .map(|r| (v4(r.local_addr, r.local_port), r.owning_pid)),
+                ),
+            }
+        }
+        if let Some(t) = get_table(network, AF_INET6) {
+            match network {
+                Network::Tcp => sockets.extend(
+                    rows::<MibTcp6RowOwnerPid>(&t)
+                        .iter()
+                        .map(|r| (v6(r.local_addr, r.local_port), r.owning_pid)),
+                ),
+                Network::Udp => sockets.extend(
+                    rows::<MibUdp6RowOwnerPid>(&t)
+                        .iter()
+                        .map(|r| (v6(r.local_addr, r.local_port), r.owning_pid)),
+                ),
+            }
+        }
+        sockets
+            .iter()
+            .find(|(local, _)| super::matches(network, local, addr))
+            .map(|(_, pid)| *pid)
+    }
+
+    pub fn process_name(pid: u32) -> Option<String> {
+        let mut buf = [0u16; 1024];
+        let mut size = buf.len() as u32;
+        let ret = unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if process.is_null() {
+                return None;
+            }
+            let ret = QueryFullProcessImageNameW(process, 0, buf.as_mut_ptr(), &mut size);
+            CloseHandle(process);
+            ret
+        };
+        if ret == 0 {
+            return None;
+        }
+        let path = OsString::from_wide(&buf[..size as usize]);
+        Path::new(&path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_owned())
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+)))]
+mod sys {
+    use std::net::SocketAddr;
+
+    use crate::session::Network;
+
+    pub fn find_pid(_network: Network, _addr: &SocketAddr, _recent: &[u32]) -> Option<u32> {
+        None
+    }
+
+    pub fn process_name(_pid: u32) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_process_name() {
+        let exe = std::env::current_exe().unwrap();
+        let expected = exe.file_name().unwrap().to_str().unwrap().to_string();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let addr = stream.local_addr().unwrap();
+        assert_eq!(
+            find_process_name(Network::Tcp, &addr),
+            Some(expected.clone())
+        );
+
+        // Matches a UDP socket bound to the unspecified address.
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+        let addr = SocketAddr::new(
+            "127.0.0.1".parse().unwrap(),
+            socket.local_addr().unwrap().port(),
+        );
+        assert_eq!(find_process_name(Network::Udp, &addr), Some(expected));
+    }
+
+    #[test]
+    fn test_lookup_process_name() {
+        let exe = std::env::current_exe().unwrap();
+        let expected = exe.file_name().unwrap().to_str().unwrap().to_string();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let addr = stream.local_addr().unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        let name = rt.block_on(lookup_process_name(Network::Tcp, addr));
+        assert_eq!(name, Some(expected.clone()));
+
+        // The process is checked first for the next sockets, its name is
+        // cached by the pid.
+        let pid = std::process::id();
+        assert!(RECENT_PIDS.lock().unwrap().contains(&pid));
+        assert_eq!(get_cached(&NAMES, &pid), Some(Some(expected)));
+        let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let addr = stream.local_addr().unwrap();
+        assert_eq!(find_pid(Network::Tcp, &addr), Some(pid));
+    }
+}
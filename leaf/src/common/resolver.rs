@@ -4,7 +4,7 @@ use std::sync::Arc;
 use anyhow::{anyhow, Result};
 use futures::TryFutureExt;
 
-use crate::app::dns_client::DnsClient;
+use crate::app::dns_client::{DnsClient, DnsStrategy};
 
 pub struct Resolver {
     ips: Vec<IpAddr>,
@@ -17,9 +17,10 @@ impl Resolver {
         bind_addr: &'a SocketAddr,
         address: &'a str,
         port: &'a u16,
+        strategy: Option<DnsStrategy>,
     ) -> Result<Self> {
         let mut ips = client
-            .lookup_with_bind(String::from(address), bind_addr)
+            .lookup_with_strategy(String::from(address), bind_addr, strategy)
             .map_err(|e| anyhow!("lookup {} failed: {}", address, e))
             .await?;
         ips.reverse();
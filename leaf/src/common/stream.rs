@@ -1,12 +1,12 @@
 use std::cmp::min;
-use std::io::{self, ErrorKind};
+use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
 use byteorder::{BigEndian, ByteOrder};
 use bytes::BytesMut;
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
 use tokio::time::timeout;
 
 pub struct SniffingStream<T> {
@@ -18,6 +18,10 @@ impl<T> SniffingStream<T>
 where
     T: AsyncRead + AsyncWrite + Unpin,
 {
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
     pub fn new(inner: T) -> Self {
         SniffingStream {
             inner,
@@ -25,6 +29,15 @@ where
         }
     }
 
+    /// Returns the first byte of the stream without consuming it, `None` if
+    /// the stream ends before any.
+    pub async fn peek(&mut self) -> io::Result<Option<u8>> {
+        if self.buf.is_empty() {
+            self.inner.read_buf(&mut self.buf).await?;
+        }
+        Ok(self.buf.first().copied())
+    }
+
     pub async fn sniff(&mut self) -> io::Result<Option<String>> {
         let mut buf = vec![0u8; 2 * 1024];
         'outer: for _ in 0..2 {
@@ -189,3 +202,67 @@ impl<T: AsyncWrite + Unpin> AsyncWrite for SniffingStream<T> {
         AsyncWrite::poll_shutdown(Pin::new(&mut self.inner), cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn client_hello(server_name: &str) -> Vec<u8> {
+        let name = server_name.as_bytes();
+        let mut sni = Vec::new();
+        sni.extend_from_slice(&(name.len() as u16 + 3).to_be_bytes()); // list len
+        sni.push(0); // DNS hostname
+        sni.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        sni.extend_from_slice(name);
+
+        let mut hello = vec![0x03, 0x03]; // client version
+        hello.extend_from_slice(&[0u8; 32]); // random
+        hello.push(0); // session id
+        hello.extend_from_slice(&[0x00, 0x02, 0x13, 0x01]); // cipher suites
+        hello.extend_from_slice(&[0x01, 0x00]); // compression methods
+        hello.extend_from_slice(&(sni.len() as u16 + 4).to_be_bytes());
+        hello.extend_from_slice(&[0x00, 0x00]); // server name extension
+        hello.extend_from_slice(&(sni.len() as u16).to_be_bytes());
+        hello.extend_from_slice(&sni);
+
+        let mut handshake = vec![0x01, 0x00]; // client hello
+        handshake.extend_from_slice(&(hello.len() as u16).to_be_bytes());
+        handshake.extend_from_slice(&hello);
+
+        let mut record = vec![0x16, 0x03, 0x01];
+        record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+        record.extend_from_slice(&handshake);
+        record
+    }
+
+    #[test]
+    fn test_sniff_replays_bytes() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut data = client_hello("www.example.com");
+            data.extend_from_slice(b"application data");
+            let mut stream = SniffingStream::new(Cursor::new(data.clone()));
+            assert_eq!(
+                stream.sniff().await.unwrap(),
+                Some("www.example.com".to_string())
+            );
+            let mut replayed = Vec::new();
+            stream.read_to_end(&mut replayed).await.unwrap();
+            assert_eq!(replayed, data);
+
+            // not TLS, the bytes read are replayed as well
+            let data = b"GET / HTTP/1.1\r\n\r\n".to_vec();
+            let mut stream = SniffingStream::new(Cursor::new(data.clone()));
+            assert_eq!(stream.sniff().await.unwrap(), None);
+            let mut replayed = Vec::new();
+            stream.read_to_end(&mut replayed).await.unwrap();
+            assert_eq!(replayed, data);
+        });
+    }
+}
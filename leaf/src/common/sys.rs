@@ -0,0 +1,249 @@
+use std::io;
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
+
+/// Returns the maximum listen backlog of the OS, larger values are silently
+/// truncated by the kernel.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn max_listen_backlog() -> u32 {
+    std::fs::read_to_string("/proc/sys/net/core/somaxconn")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(4096)
+}
+
+/// Returns the maximum listen backlog of the OS, larger values are silently
+/// truncated by the kernel.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+pub fn max_listen_backlog() -> u32 {
+    // SOMAXCONN, the default of kern.ipc.somaxconn
+    128
+}
+
+/// Returns the maximum listen backlog of the OS, larger values are silently
+/// truncated by the kernel.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "ios",
+    target_os = "macos"
+)))]
+pub fn max_listen_backlog() -> u32 {
+    i32::MAX as u32
+}
+
+/// Returns the resident set size of the process in bytes.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    for line in status.lines() {
+        if let Some(v) = line.strip_prefix("VmRSS:") {
+            let kb = v.trim().trim_end_matches("kB").trim().parse::<u64>().ok()?;
+            return Some(kb * 1024);
+        }
+    }
+    None
+}
+
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+mod mach {
+    // mach_task_basic_info from <mach/task_info.h>, packed to 4 bytes there
+    // but the layout is the same.
+    #[repr(C)]
+    #[derive(Default)]
+    pub struct MachTaskBasicInfo {
+        pub virtual_size: u64,
+        pub resident_size: u64,
+        pub resident_size_max: u64,
+        pub user_time: [i32; 2],
+        pub system_time: [i32; 2],
+        pub policy: i32,
+        pub suspend_count: i32,
+    }
+
+    pub const MACH_TASK_BASIC_INFO: u32 = 20;
+
+    extern "C" {
+        pub static mach_task_self_: u32;
+        pub fn task_info(
+            target_task: u32,
+            flavor: u32,
+            task_info_out: *mut i32,
+            task_info_out_count: *mut u32,
+        ) -> i32;
+    }
+}
+
+/// Returns the resident set size of the process in bytes.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+pub fn resident_memory() -> Option<u64> {
+    let mut info = mach::MachTaskBasicInfo::default();
+    let mut count = (std::mem::size_of::<mach::MachTaskBasicInfo>() / 4) as u32;
+    let ret = unsafe {
+        mach::task_info(
+            mach::mach_task_self_,
+            mach::MACH_TASK_BASIC_INFO,
+            &mut info as *mut _ as *mut i32,
+            &mut count,
+        )
+    };
+    if ret != 0 {
+        return None;
+    }
+    Some(info.resident_size)
+}
+
+/// Returns the resident set size of the process in bytes.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "ios",
+    target_os = "macos"
+)))]
+pub fn resident_memory() -> Option<u64> {
+    None
+}
+
+/// Sets the firewall mark of an outbound socket to `OUTBOUND_MARK`, if
+/// configured.
+#[cfg(target_os = "linux")]
+pub fn mark_outbound_socket<S: std::os::unix::io::AsRawFd>(socket: &S) -> io::Result<()> {
+    let mark = *crate::option::OUTBOUND_MARK;
+    if mark == 0 {
+        return Ok(());
+    }
+    let ret = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_MARK,
+            &mark as *const u32 as *const libc::c_void,
+            std::mem::size_of::<u32>() as libc::socklen_t,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Sets the firewall mark of an outbound socket to `OUTBOUND_MARK`, if
+/// configured.
+#[cfg(not(target_os = "linux"))]
+pub fn mark_outbound_socket<S>(_socket: &S) -> io::Result<()> {
+    Ok(())
+}
+
+/// Binds the socket to the network interface, so its traffic goes through
+/// the interface whatever address the interface has.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn bind_to_interface<S: std::os::unix::io::AsRawFd>(
+    socket: &S,
+    interface: &str,
+    _ipv6: bool,
+) -> io::Result<()> {
+    let ret = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_BINDTODEVICE,
+            interface.as_ptr() as *const libc::c_void,
+            interface.len() as libc::socklen_t,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Binds the socket to the network interface, so its traffic goes through
+/// the interface whatever address the interface has.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+pub fn bind_to_interface<S: std::os::unix::io::AsRawFd>(
+    socket: &S,
+    interface: &str,
+    ipv6: bool,
+) -> io::Result<()> {
+    let name = std::ffi::CString::new(interface)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // The index is looked up on each call, the interface may come and go.
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    if index == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("interface {} not found", interface),
+        ));
+    }
+    let (level, name) = if ipv6 {
+        (libc::IPPROTO_IPV6, libc::IPV6_BOUND_IF)
+    } else {
+        (libc::IPPROTO_IP, libc::IP_BOUND_IF)
+    };
+    let ret = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            name,
+            &index as *const libc::c_uint as *const libc::c_void,
+            std::mem::size_of::<libc::c_uint>() as libc::socklen_t,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Binds the socket to the network interface, so its traffic goes through
+/// the interface whatever address the interface has.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "ios",
+    target_os = "macos"
+)))]
+pub fn bind_to_interface<S>(_socket: &S, interface: &str, _ipv6: bool) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        format!("binding to interface {} is not supported", interface),
+    ))
+}
+
+/// Protects an outbound socket, given its raw fd, from being routed back
+/// into the TUN, e.g. with `VpnService.protect()` on Android. Returns whether
+/// the socket is protected.
+pub type SocketProtector = dyn Fn(i32) -> bool + Send + Sync;
+
+lazy_static! {
+    static ref SOCKET_PROTECTOR: RwLock<Option<Arc<SocketProtector>>> = RwLock::new(None);
+}
+
+/// Sets or removes the protector called with every outbound socket before
+/// it connects.
+pub fn set_socket_protector(protector: Option<Box<SocketProtector>>) {
+    *SOCKET_PROTECTOR.write().unwrap() = protector.map(Arc::from);
+}
+
+/// Protects an outbound socket with the protector, if any.
+#[cfg(unix)]
+pub fn protect_outbound_socket<S: std::os::unix::io::AsRawFd>(socket: &S) -> io::Result<()> {
+    // Called without holding the lock, the protector may change protectors.
+    let protector = SOCKET_PROTECTOR.read().unwrap().clone();
+    if let Some(protector) = protector {
+        if !protector(socket.as_raw_fd()) {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "protect outbound socket failed",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Protects an outbound socket with the protector, if any.
+#[cfg(not(unix))]
+pub fn protect_outbound_socket<S>(_socket: &S) -> io::Result<()> {
+    Ok(())
+}
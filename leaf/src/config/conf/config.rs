@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufRead};
+use std::net::IpAddr;
 use std::path::Path;
 
 use anyhow::anyhow;
@@ -24,15 +25,56 @@ pub struct TUN {
 pub struct General {
     pub tun: Option<TUN>,
     pub tun_fd: Option<i32>,
+    pub tun_mtu: Option<i32>,
     pub loglevel: Option<String>,
     pub dns_server: Option<Vec<String>>,
     pub dns_interface: Option<String>,
+    pub dns_filter_bogons: Option<bool>,
+    pub dns_filter_private: Option<bool>,
+    pub dns_internal_domains: Option<Vec<String>>,
+    pub dns_fail_open: Option<bool>,
+    pub dns_fallback_ip: Option<String>,
+    pub dns_dot_idle_timeout: Option<u32>,
+    pub dns_cache_size: Option<u32>,
+    pub dns_min_cache_ttl: Option<u32>,
+    pub dns_max_cache_ttl: Option<u32>,
+    pub dns_negative_cache_ttl: Option<u32>,
+    pub dns_prefetch: Option<u32>,
+    pub dns_prefetch_min_hits: Option<u32>,
+    pub dns_client_subnet: Option<String>,
+    pub dns_outbound: Option<String>,
+    pub dns_strategy: Option<String>,
     pub always_real_ip: Option<Vec<String>>,
     pub always_fake_ip: Option<Vec<String>>,
+    pub fake_ip_routing_aware: Option<bool>,
+    pub fake_ip_persist_path: Option<String>,
+    pub fake_ip_persist_ttl: Option<u32>,
+    pub fake_ip_range: Option<String>,
+    pub fake_ip_ipv6_range: Option<String>,
+    pub control_outbound: Option<String>,
+    pub routing_dry_run: Option<bool>,
     pub interface: Option<String>,
     pub port: Option<u16>,
+    pub http_username: Option<String>,
+    pub http_password: Option<String>,
     pub socks_interface: Option<String>,
     pub socks_port: Option<u16>,
+    pub socks_udp_bind: Option<String>,
+    pub socks_udp_port_range: Option<String>,
+    pub socks_udp_advertise: Option<String>,
+    pub socks_username: Option<String>,
+    pub socks_password: Option<String>,
+    pub socks_allow_no_auth: Option<bool>,
+    pub mixed_interface: Option<String>,
+    pub mixed_port: Option<u16>,
+    pub tproxy_interface: Option<String>,
+    pub tproxy_port: Option<u16>,
+    pub listen_backlog: Option<u32>,
+    pub udp_session_timeout: Option<u32>,
+    pub link_buffer_size: Option<u32>,
+    pub connect_timeout: Option<u32>,
+    pub tcp_nodelay: Option<bool>,
+    pub tcp_keepalive: Option<u32>,
 }
 
 #[derive(Debug)]
@@ -40,6 +82,10 @@ pub struct Proxy {
     pub tag: String,
     pub protocol: String,
     pub interface: String,
+    pub idle_timeout: Option<u32>,
+    pub connect_timeout: Option<u32>,
+    pub tcp_nodelay: Option<bool>,
+    pub tcp_keepalive: Option<u32>,
 
     // common
     pub address: Option<String>,
@@ -47,9 +93,14 @@ pub struct Proxy {
 
     // shadowsocks
     pub encrypt_method: Option<String>,
+    pub obfs: Option<String>,
+    pub obfs_host: Option<String>,
+    pub obfs_uri: Option<String>,
 
     // shadowsocks, trojan
     pub password: Option<String>,
+    pub udp_over_tcp: Option<bool>,
+    pub udp_over_tcp_version: Option<u32>,
 
     // vmess, vless
     pub username: Option<String>,
@@ -60,6 +111,15 @@ pub struct Proxy {
 
     // trojan
     pub sni: Option<String>,
+
+    // trojan, vmess
+    pub reduce_rtt: Option<bool>,
+
+    // vless
+    pub flow: Option<String>,
+
+    // drop
+    pub reset: Option<bool>,
 }
 
 impl Default for Proxy {
@@ -68,16 +128,28 @@ impl Default for Proxy {
             tag: "".to_string(),
             protocol: "".to_string(),
             interface: "0.0.0.0".to_string(),
+            idle_timeout: None,
+            connect_timeout: None,
+            tcp_nodelay: None,
+            tcp_keepalive: None,
             address: None,
             port: None,
             encrypt_method: Some("chacha20-ietf-poly1305".to_string()),
+            obfs: None,
+            obfs_host: None,
+            obfs_uri: None,
             password: None,
+            udp_over_tcp: None,
+            udp_over_tcp_version: None,
             username: None,
             ws: Some(false),
             tls: Some(false),
             ws_path: None,
             ws_host: None,
             sni: None,
+            reduce_rtt: Some(false),
+            flow: None,
+            reset: None,
         }
     }
 }
@@ -95,6 +167,19 @@ pub struct ProxyGroup {
     pub fallback_cache: Option<bool>,
     pub cache_size: Option<i32>,
     pub cache_timeout: Option<i32>,
+    pub priorities: Option<Vec<u32>>,
+
+    // balance
+    pub strategy: Option<String>,
+    pub weights: Option<Vec<u32>>,
+
+    // random, balance
+    pub sticky_timeout: Option<u32>,
+
+    // url-test
+    pub url: Option<String>,
+    pub interval: Option<i32>,
+    pub tolerance: Option<i32>,
 
     // tryall
     pub delay_base: Option<i32>,
@@ -116,6 +201,13 @@ impl Default for ProxyGroup {
             fallback_cache: Some(false),
             cache_size: Some(256),
             cache_timeout: Some(60),
+            priorities: None,
+            strategy: None,
+            weights: None,
+            sticky_timeout: None,
+            url: None,
+            interval: Some(300),
+            tolerance: Some(50),
             delay_base: Some(0),
             attempts: Some(2),
         }
@@ -127,6 +219,9 @@ pub struct Rule {
     pub type_field: String,
     pub filter: Option<String>,
     pub target: String,
+    pub no_log: bool,
+    pub dns_strategy: Option<String>,
+    pub network: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -136,6 +231,7 @@ pub struct Config {
     pub proxy_group: Option<Vec<ProxyGroup>>,
     pub rule: Option<Vec<Rule>>,
     pub host: Option<HashMap<String, Vec<String>>>,
+    pub rewrite: Option<HashMap<String, String>>,
 }
 
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
@@ -232,6 +328,9 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
             "tun-fd" => {
                 general.tun_fd = get_value::<i32>(parts[1]);
             }
+            "tun-mtu" => {
+                general.tun_mtu = get_value::<i32>(parts[1]);
+            }
             "tun" => {
                 if let Some(items) = get_char_sep_slice(parts[1], ',') {
                     if items.len() != 5 {
@@ -255,24 +354,144 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
             "dns-interface" => {
                 general.dns_interface = get_string(parts[1]);
             }
+            "dns-filter-bogons" => {
+                general.dns_filter_bogons = get_value::<bool>(parts[1]);
+            }
+            "dns-filter-private" => {
+                general.dns_filter_private = get_value::<bool>(parts[1]);
+            }
+            "dns-internal-domains" => {
+                general.dns_internal_domains = get_char_sep_slice(parts[1], ',');
+            }
+            "dns-fail-open" => {
+                general.dns_fail_open = get_value::<bool>(parts[1]);
+            }
+            "dns-fallback-ip" => {
+                general.dns_fallback_ip = get_string(parts[1]);
+            }
+            "dns-dot-idle-timeout" => {
+                general.dns_dot_idle_timeout = get_value::<u32>(parts[1]);
+            }
+            "dns-cache-size" => {
+                general.dns_cache_size = get_value::<u32>(parts[1]);
+            }
+            "dns-min-cache-ttl" => {
+                general.dns_min_cache_ttl = get_value::<u32>(parts[1]);
+            }
+            "dns-max-cache-ttl" => {
+                general.dns_max_cache_ttl = get_value::<u32>(parts[1]);
+            }
+            "dns-negative-cache-ttl" => {
+                general.dns_negative_cache_ttl = get_value::<u32>(parts[1]);
+            }
+            "dns-prefetch" => {
+                general.dns_prefetch = get_value::<u32>(parts[1]);
+            }
+            "dns-prefetch-min-hits" => {
+                general.dns_prefetch_min_hits = get_value::<u32>(parts[1]);
+            }
+            "dns-client-subnet" => {
+                general.dns_client_subnet = get_string(parts[1]);
+            }
+            "dns-outbound" => {
+                general.dns_outbound = get_string(parts[1]);
+            }
+            "dns-strategy" => {
+                general.dns_strategy = get_string(parts[1]);
+            }
             "always-real-ip" => {
                 general.always_real_ip = get_char_sep_slice(parts[1], ',');
             }
             "always-fake-ip" => {
                 general.always_fake_ip = get_char_sep_slice(parts[1], ',');
             }
+            "fake-ip-routing-aware" => {
+                general.fake_ip_routing_aware = get_value::<bool>(parts[1]);
+            }
+            "fake-ip-persist-path" => {
+                general.fake_ip_persist_path = get_string(parts[1]);
+            }
+            "fake-ip-persist-ttl" => {
+                general.fake_ip_persist_ttl = get_value::<u32>(parts[1]);
+            }
+            "fake-ip-range" => {
+                general.fake_ip_range = get_string(parts[1]);
+            }
+            "fake-ip-ipv6-range" => {
+                general.fake_ip_ipv6_range = get_string(parts[1]);
+            }
+            "control-outbound" => {
+                general.control_outbound = get_string(parts[1]);
+            }
+            "routing-dry-run" => {
+                general.routing_dry_run = get_value::<bool>(parts[1]);
+            }
             "interface" => {
                 general.interface = get_string(parts[1]);
             }
             "port" => {
                 general.port = get_value::<u16>(parts[1]);
             }
+            "http-username" => {
+                general.http_username = get_string(parts[1]);
+            }
+            "http-password" => {
+                general.http_password = get_string(parts[1]);
+            }
             "socks-interface" => {
                 general.socks_interface = get_string(parts[1]);
             }
             "socks-port" => {
                 general.socks_port = get_value::<u16>(parts[1]);
             }
+            "socks-udp-bind" => {
+                general.socks_udp_bind = get_string(parts[1]);
+            }
+            "socks-udp-port-range" => {
+                general.socks_udp_port_range = get_string(parts[1]);
+            }
+            "socks-udp-advertise" => {
+                general.socks_udp_advertise = get_string(parts[1]);
+            }
+            "socks-username" => {
+                general.socks_username = get_string(parts[1]);
+            }
+            "socks-password" => {
+                general.socks_password = get_string(parts[1]);
+            }
+            "socks-allow-no-auth" => {
+                general.socks_allow_no_auth = get_value::<bool>(parts[1]);
+            }
+            "mixed-interface" => {
+                general.mixed_interface = get_string(parts[1]);
+            }
+            "mixed-port" => {
+                general.mixed_port = get_value::<u16>(parts[1]);
+            }
+            "tproxy-interface" => {
+                general.tproxy_interface = get_string(parts[1]);
+            }
+            "tproxy-port" => {
+                general.tproxy_port = get_value::<u16>(parts[1]);
+            }
+            "listen-backlog" => {
+                general.listen_backlog = get_value::<u32>(parts[1]);
+            }
+            "udp-session-timeout" => {
+                general.udp_session_timeout = get_value::<u32>(parts[1]);
+            }
+            "link-buffer-size" => {
+                general.link_buffer_size = get_value::<u32>(parts[1]);
+            }
+            "connect-timeout" => {
+                general.connect_timeout = get_value::<u32>(parts[1]);
+            }
+            "tcp-nodelay" => {
+                general.tcp_nodelay = get_value::<bool>(parts[1]);
+            }
+            "tcp-keepalive" => {
+                general.tcp_keepalive = get_value::<u32>(parts[1]);
+            }
             _ => {}
         }
     }
@@ -318,9 +537,27 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
                 "encrypt-method" => {
                     proxy.encrypt_method = Some(v.to_string());
                 }
+                "obfs" => {
+                    proxy.obfs = Some(v.to_string());
+                }
+                "obfs-host" => {
+                    proxy.obfs_host = Some(v.to_string());
+                }
+                "obfs-uri" => {
+                    proxy.obfs_uri = Some(v.to_string());
+                }
                 "password" => {
                     proxy.password = Some(v.to_string());
                 }
+                "udp-over-tcp" => {
+                    proxy.udp_over_tcp = v.parse::<bool>().ok();
+                }
+                "reset" => {
+                    proxy.reset = v.parse::<bool>().ok();
+                }
+                "udp-over-tcp-version" => {
+                    proxy.udp_over_tcp_version = v.parse::<u32>().ok();
+                }
                 "username" => {
                     proxy.username = Some(v.to_string());
                 }
@@ -335,9 +572,27 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
                 "sni" => {
                     proxy.sni = Some(v.to_string());
                 }
+                "reduce-rtt" => {
+                    proxy.reduce_rtt = if v == "true" { Some(true) } else { Some(false) };
+                }
                 "interface" => {
                     proxy.interface = v.to_string();
                 }
+                "idle-timeout" => {
+                    proxy.idle_timeout = v.parse::<u32>().ok();
+                }
+                "connect-timeout" => {
+                    proxy.connect_timeout = v.parse::<u32>().ok();
+                }
+                "tcp-nodelay" => {
+                    proxy.tcp_nodelay = v.parse::<bool>().ok();
+                }
+                "tcp-keepalive" => {
+                    proxy.tcp_keepalive = v.parse::<u32>().ok();
+                }
+                "flow" => {
+                    proxy.flow = Some(v.to_string());
+                }
                 _ => {}
             }
         }
@@ -355,6 +610,7 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
             // compat
             "reject" => {
                 proxy.protocol = "drop".to_string();
+                proxy.reset = proxy.reset.or(Some(true));
                 proxies.push(proxy);
                 continue;
             }
@@ -482,6 +738,57 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
                         };
                         group.cache_timeout = i;
                     }
+                    "priorities" => {
+                        let mut priorities = Vec::new();
+                        for p in v.split(':') {
+                            if let Ok(p) = p.trim().parse::<u32>() {
+                                priorities.push(p);
+                            } else {
+                                return Err(anyhow!("invalid priorities {}", v));
+                            }
+                        }
+                        group.priorities = Some(priorities);
+                    }
+                    "strategy" => {
+                        group.strategy = Some(v.to_string());
+                    }
+                    "weights" => {
+                        let mut weights = Vec::new();
+                        for w in v.split(':') {
+                            if let Ok(w) = w.trim().parse::<u32>() {
+                                weights.push(w);
+                            } else {
+                                return Err(anyhow!("invalid weights {}", v));
+                            }
+                        }
+                        group.weights = Some(weights);
+                    }
+                    "sticky-timeout" => {
+                        if let Ok(i) = v.parse::<u32>() {
+                            group.sticky_timeout = Some(i);
+                        } else {
+                            return Err(anyhow!("invalid sticky timeout {}", v));
+                        }
+                    }
+                    "url" => {
+                        group.url = Some(v.to_string());
+                    }
+                    "interval" => {
+                        let i = if let Ok(i) = v.parse::<i32>() {
+                            Some(i)
+                        } else {
+                            None
+                        };
+                        group.interval = i;
+                    }
+                    "tolerance" => {
+                        let i = if let Ok(i) = v.parse::<i32>() {
+                            Some(i)
+                        } else {
+                            None
+                        };
+                        group.tolerance = i;
+                    }
                     "delay-base" => {
                         let i = if let Ok(i) = v.parse::<i32>() {
                             Some(i)
@@ -505,10 +812,8 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
 
         // compat
         match group.protocol.as_str() {
-            // url-test group is just failover without failover
             "url-test" => {
-                group.protocol = "failover".to_string();
-                group.failover = Some(false);
+                group.protocol = "urltest".to_string();
             }
             // fallback group is just failover
             "fallback" => {
@@ -545,17 +850,42 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
             continue; // at lease 3 params except the FINAL rule
         }
 
+        // the ports can be a list, e.g. `PORT-RANGE, 80, 443, 1000-2000, Proxy`,
+        // joined back to a single filter
+        let mut params = params;
+        if rule.type_field == "PORT-RANGE" {
+            let n = params[1..params.len() - 1]
+                .iter()
+                .take_while(|p| crate::config::parse_ports(p).is_some())
+                .count();
+            if n > 1 {
+                let ports: Vec<String> = params.drain(1..=n).collect();
+                params.insert(1, ports.join(","));
+            }
+        }
+
         // the 3th must be the target
         rule.target = params[2].to_string();
 
         match rule.type_field.as_str() {
             "IP-CIDR" | "DOMAIN" | "DOMAIN-SUFFIX" | "DOMAIN-KEYWORD" | "GEOIP" | "EXTERNAL"
-            | "PORT-RANGE" => {
+            | "PORT-RANGE" | "PROCESS-NAME" | "NETWORK" => {
                 rule.filter = Some(params[1].to_string());
             }
             _ => {}
         }
 
+        // trailing options
+        for param in params.iter().skip(3) {
+            if *param == "no-log" {
+                rule.no_log = true;
+            } else if let Some(v) = param.strip_prefix("dns-strategy=") {
+                rule.dns_strategy = Some(v.trim().to_string());
+            } else if let Some(v) = param.strip_prefix("network=") {
+                rule.network = Some(v.trim().to_string());
+            }
+        }
+
         rules.push(rule);
     }
 
@@ -575,17 +905,51 @@ pub fn from_lines(lines: Vec<io::Result<String>>) -> Result<Config> {
         hosts.insert(name.to_owned(), ips);
     }
 
+    let mut rewrites = HashMap::new();
+    let rewrite_lines = get_lines_by_section("Rewrite", lines.iter()).unwrap();
+    for line in rewrite_lines {
+        let parts: Vec<&str> = line.split('=').collect();
+        if parts.len() != 2 {
+            continue;
+        }
+        rewrites.insert(parts[0].trim().to_owned(), parts[1].trim().to_owned());
+    }
+
     let mut config = Config::default();
     config.general = Some(general);
     config.proxy = Some(proxies);
     config.proxy_group = Some(proxy_groups);
     config.rule = Some(rules);
     config.host = Some(hosts);
+    config.rewrite = Some(rewrites);
 
     Ok(config)
 }
 
+// Sets the listen addresses of the inbound from a comma-separated list.
+fn set_inbound_addresses(inbound: &mut internal::Inbound, interface: &str) {
+    let addresses = get_char_sep_slice(interface, ',').unwrap_or_default();
+    inbound.address = addresses
+        .first()
+        .cloned()
+        .unwrap_or_else(|| interface.to_string());
+    if addresses.len() > 1 {
+        inbound.addresses = protobuf::RepeatedField::from_vec(addresses);
+    }
+}
+
+/// Converts the config, errors of a proxy or proxy group tell which one it
+/// is.
 pub fn to_internal(conf: Config) -> Result<internal::Config> {
+    let mut context = None;
+    to_internal_in_context(conf, &mut context).map_err(|e| match context {
+        Some(c) => e.context(c),
+        None => e,
+    })
+}
+
+// Keeps the proxy or proxy group being converted in `context`.
+fn to_internal_in_context(conf: Config, context: &mut Option<String>) -> Result<internal::Config> {
     let mut log = internal::Log::new();
     if let Some(ext_general) = &conf.general {
         if let Some(ext_loglevel) = &ext_general.loglevel {
@@ -607,20 +971,84 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
 
     let mut inbounds = protobuf::RepeatedField::new();
     if let Some(ext_general) = &conf.general {
+        let backlog = ext_general.listen_backlog.unwrap_or(0);
+        if ext_general.listen_backlog.is_some() && !crate::config::is_valid_backlog(backlog) {
+            return Err(anyhow!("invalid listen backlog {}", backlog));
+        }
         if ext_general.interface.is_some() && ext_general.port.is_some() {
             let mut inbound = internal::Inbound::new();
             inbound.protocol = "http".to_string();
             inbound.tag = "http".to_string();
-            inbound.address = ext_general.interface.as_ref().unwrap().to_string();
+            set_inbound_addresses(&mut inbound, ext_general.interface.as_ref().unwrap());
             inbound.port = ext_general.port.unwrap() as u32;
+            inbound.backlog = backlog;
+            if let Some(ext_username) = &ext_general.http_username {
+                let mut settings = internal::HttpInboundSettings::new();
+                settings.username = ext_username.clone();
+                if let Some(ext_password) = &ext_general.http_password {
+                    settings.password = ext_password.clone();
+                }
+                inbound.settings = settings.write_to_bytes().unwrap();
+            }
             inbounds.push(inbound);
         }
         if ext_general.socks_interface.is_some() && ext_general.socks_port.is_some() {
             let mut inbound = internal::Inbound::new();
             inbound.protocol = "socks".to_string();
             inbound.tag = "socks".to_string();
-            inbound.address = ext_general.socks_interface.as_ref().unwrap().to_string();
+            set_inbound_addresses(&mut inbound, ext_general.socks_interface.as_ref().unwrap());
             inbound.port = ext_general.socks_port.unwrap() as u32;
+            inbound.backlog = backlog;
+            let mut settings = internal::SocksInboundSettings::new();
+            if let Some(ext_udp_bind) = &ext_general.socks_udp_bind {
+                if ext_udp_bind.parse::<IpAddr>().is_err() {
+                    return Err(anyhow!("invalid socks udp bind {}", ext_udp_bind));
+                }
+                settings.udp_bind = ext_udp_bind.clone();
+            }
+            if let Some(ext_udp_port_range) = &ext_general.socks_udp_port_range {
+                if crate::config::parse_port_range(ext_udp_port_range).is_none() {
+                    return Err(anyhow!(
+                        "invalid socks udp port range {}",
+                        ext_udp_port_range
+                    ));
+                }
+                settings.udp_port_range = ext_udp_port_range.clone();
+            }
+            if let Some(ext_udp_advertise) = &ext_general.socks_udp_advertise {
+                if ext_udp_advertise.parse::<IpAddr>().is_err() {
+                    return Err(anyhow!("invalid socks udp advertise {}", ext_udp_advertise));
+                }
+                settings.udp_advertise = ext_udp_advertise.clone();
+            }
+            if let Some(ext_username) = &ext_general.socks_username {
+                settings.username = ext_username.clone();
+                if let Some(ext_password) = &ext_general.socks_password {
+                    settings.password = ext_password.clone();
+                }
+            }
+            if let Some(ext_allow_no_auth) = ext_general.socks_allow_no_auth {
+                settings.allow_no_auth = ext_allow_no_auth;
+            }
+            inbound.settings = settings.write_to_bytes().unwrap();
+            inbounds.push(inbound);
+        }
+        if ext_general.mixed_interface.is_some() && ext_general.mixed_port.is_some() {
+            let mut inbound = internal::Inbound::new();
+            inbound.protocol = "mixed".to_string();
+            inbound.tag = "mixed".to_string();
+            set_inbound_addresses(&mut inbound, ext_general.mixed_interface.as_ref().unwrap());
+            inbound.port = ext_general.mixed_port.unwrap() as u32;
+            inbound.backlog = backlog;
+            inbounds.push(inbound);
+        }
+        if ext_general.tproxy_interface.is_some() && ext_general.tproxy_port.is_some() {
+            let mut inbound = internal::Inbound::new();
+            inbound.protocol = "tproxy".to_string();
+            inbound.tag = "tproxy".to_string();
+            inbound.address = ext_general.tproxy_interface.as_ref().unwrap().to_string();
+            inbound.port = ext_general.tproxy_port.unwrap() as u32;
+            inbound.backlog = backlog;
             inbounds.push(inbound);
         }
 
@@ -650,6 +1078,30 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                 }
             }
 
+            if let Some(ext_routing_aware) = ext_general.fake_ip_routing_aware {
+                settings.fake_dns_routing_aware = ext_routing_aware;
+            }
+            if let Some(ext_persist_path) = &ext_general.fake_ip_persist_path {
+                settings.fake_dns_persist_path = ext_persist_path.clone();
+            }
+            if let Some(ext_persist_ttl) = ext_general.fake_ip_persist_ttl {
+                settings.fake_dns_persist_ttl = ext_persist_ttl;
+            }
+            if let Some(ext_range) = &ext_general.fake_ip_range {
+                match crate::config::parse_ip_range(ext_range) {
+                    Some((addr, prefix)) if addr.is_ipv4() && prefix <= 30 => (),
+                    _ => return Err(anyhow!("invalid fake ip range {}", ext_range)),
+                }
+                settings.fake_dns_range = ext_range.clone();
+            }
+            if let Some(ext_ipv6_range) = &ext_general.fake_ip_ipv6_range {
+                if !matches!(crate::config::parse_ip_range(ext_ipv6_range), Some((addr, _)) if addr.is_ipv6())
+                {
+                    return Err(anyhow!("invalid fake ip ipv6 range {}", ext_ipv6_range));
+                }
+                settings.fake_dns_ipv6_range = ext_ipv6_range.clone();
+            }
+
             if ext_general.tun_fd.is_some() {
                 settings.fd = ext_general.tun_fd.unwrap();
             } else {
@@ -670,10 +1122,18 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                 }
                 if let Some(ext_mtu) = ext_tun.mtu {
                     settings.mtu = ext_mtu;
-                } else {
-                    settings.mtu = 1500;
                 }
             }
+            // tun-mtu also applies to tun-fd, and takes precedence over the
+            // MTU of tun.
+            if let Some(ext_mtu) = ext_general.tun_mtu {
+                settings.mtu = ext_mtu;
+            }
+            if settings.mtu == 0 {
+                settings.mtu = 1500;
+            } else if !crate::config::is_valid_mtu(settings.mtu) {
+                return Err(anyhow!("invalid tun mtu {}", settings.mtu));
+            }
 
             // TODO tun opts
             let settings = settings.write_to_bytes().unwrap();
@@ -681,7 +1141,15 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
             inbounds.push(inbound);
         }
     }
+    if let Some(ext_rewrites) = &conf.rewrite {
+        for inbound in inbounds.iter_mut() {
+            inbound.rewrites = ext_rewrites.clone();
+        }
+    }
 
+    let connect_timeout = conf.general.as_ref().and_then(|g| g.connect_timeout);
+    let tcp_nodelay = conf.general.as_ref().and_then(|g| g.tcp_nodelay);
+    let tcp_keepalive = conf.general.as_ref().and_then(|g| g.tcp_keepalive);
     let mut outbounds = protobuf::RepeatedField::new();
     if let Some(ext_proxies) = &conf.proxy {
         for ext_proxy in ext_proxies {
@@ -692,9 +1160,23 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
             };
             outbound.protocol = ext_protocol.to_string();
             outbound.tag = ext_proxy.tag.clone();
+            *context = Some(format!("proxy [{}]", &outbound.tag));
             outbound.bind = ext_proxy.interface.clone();
+            outbound.idle_timeout = ext_proxy.idle_timeout.unwrap_or(0);
+            outbound.connect_timeout = ext_proxy.connect_timeout.or(connect_timeout).unwrap_or(0);
+            outbound.disable_tcp_nodelay = !ext_proxy.tcp_nodelay.or(tcp_nodelay).unwrap_or(true);
+            outbound.tcp_keepalive = ext_proxy.tcp_keepalive.or(tcp_keepalive).unwrap_or(0);
             match outbound.protocol.as_str() {
-                "direct" | "drop" => {
+                "direct" => {
+                    outbounds.push(outbound);
+                }
+                "drop" => {
+                    let mut settings = internal::DropOutboundSettings::new();
+                    if let Some(ext_reset) = ext_proxy.reset {
+                        settings.reset = ext_reset;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
                     outbounds.push(outbound);
                 }
                 "socks" => {
@@ -725,6 +1207,19 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     if let Some(ext_password) = &ext_proxy.password {
                         settings.password = ext_password.clone();
                     }
+                    if let Some(ext_obfs) = &ext_proxy.obfs {
+                        settings.plugin = "obfs-local".to_string();
+                        let mut opts = vec![format!("obfs={}", ext_obfs)];
+                        if let Some(ext_obfs_host) = &ext_proxy.obfs_host {
+                            opts.push(format!("obfs-host={}", ext_obfs_host));
+                        }
+                        if let Some(ext_obfs_uri) = &ext_proxy.obfs_uri {
+                            opts.push(format!("obfs-uri={}", ext_obfs_uri));
+                        }
+                        settings.plugin_opts = opts.join(";");
+                    }
+                    settings.udp_over_tcp = ext_proxy.udp_over_tcp.unwrap_or(false);
+                    settings.udp_over_tcp_version = ext_proxy.udp_over_tcp_version.unwrap_or(0);
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -738,6 +1233,7 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     if let Some(ext_sni) = &ext_proxy.sni {
                         tls_settings.server_name = ext_sni.clone();
                     }
+                    tls_settings.early_data = ext_proxy.reduce_rtt.unwrap();
                     let tls_settings = tls_settings.write_to_bytes().unwrap();
                     tls_outbound.settings = tls_settings;
                     tls_outbound.tag = format!("{}_tls_xxx", ext_proxy.tag.clone());
@@ -772,6 +1268,8 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     if let Some(ext_password) = &ext_proxy.password {
                         settings.password = ext_password.clone();
                     }
+                    settings.udp_over_tcp = ext_proxy.udp_over_tcp.unwrap_or(false);
+                    settings.udp_over_tcp_version = ext_proxy.udp_over_tcp_version.unwrap_or(0);
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbound.tag = format!("{}_trojan_xxx", ext_proxy.tag.clone());
@@ -788,6 +1286,10 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     let chain_settings = chain_settings.write_to_bytes().unwrap();
                     chain_outbound.settings = chain_settings;
                     chain_outbound.bind = ext_proxy.interface.clone();
+                    chain_outbound.idle_timeout = outbound.idle_timeout;
+                    chain_outbound.connect_timeout = outbound.connect_timeout;
+                    chain_outbound.disable_tcp_nodelay = outbound.disable_tcp_nodelay;
+                    chain_outbound.tcp_keepalive = outbound.tcp_keepalive;
                     chain_outbound.protocol = "chain".to_string();
 
                     // always push chain first, in case there isn't final rule,
@@ -808,6 +1310,7 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     if let Some(ext_sni) = &ext_proxy.sni {
                         tls_settings.server_name = ext_sni.clone();
                     }
+                    tls_settings.early_data = ext_proxy.reduce_rtt.unwrap();
                     let tls_settings = tls_settings.write_to_bytes().unwrap();
                     tls_outbound.settings = tls_settings;
                     tls_outbound.tag = format!("{}_tls_xxx", ext_proxy.tag.clone());
@@ -853,6 +1356,7 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     if let Some(ext_username) = &ext_proxy.username {
                         settings.uuid = ext_username.clone();
                     }
+                    settings.reduce_rtt = ext_proxy.reduce_rtt.unwrap();
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbound.tag = format!("{}_vmess_xxx", ext_proxy.tag.clone());
@@ -871,6 +1375,10 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     let chain_settings = chain_settings.write_to_bytes().unwrap();
                     chain_outbound.settings = chain_settings;
                     chain_outbound.bind = ext_proxy.interface.clone();
+                    chain_outbound.idle_timeout = outbound.idle_timeout;
+                    chain_outbound.connect_timeout = outbound.connect_timeout;
+                    chain_outbound.disable_tcp_nodelay = outbound.disable_tcp_nodelay;
+                    chain_outbound.tcp_keepalive = outbound.tcp_keepalive;
                     chain_outbound.protocol = "chain".to_string();
 
                     // always push chain first, in case there isn't final rule,
@@ -933,6 +1441,10 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     if let Some(ext_username) = &ext_proxy.username {
                         settings.uuid = ext_username.clone();
                     }
+                    if let Some(ext_flow) = &ext_proxy.flow {
+                        settings.flow = ext_flow.clone();
+                    }
+                    settings.encryption = "none".to_string();
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbound.tag = format!("{}_vless_xxx", ext_proxy.tag.clone());
@@ -951,6 +1463,10 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     let chain_settings = chain_settings.write_to_bytes().unwrap();
                     chain_outbound.settings = chain_settings;
                     chain_outbound.bind = ext_proxy.interface.clone();
+                    chain_outbound.idle_timeout = outbound.idle_timeout;
+                    chain_outbound.connect_timeout = outbound.connect_timeout;
+                    chain_outbound.disable_tcp_nodelay = outbound.disable_tcp_nodelay;
+                    chain_outbound.tcp_keepalive = outbound.tcp_keepalive;
                     chain_outbound.protocol = "chain".to_string();
 
                     // always push chain first, in case there isn't final rule,
@@ -974,6 +1490,7 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
             let mut outbound = internal::Outbound::new();
             outbound.protocol = ext_proxy_group.protocol.clone();
             outbound.tag = ext_proxy_group.tag.clone();
+            *context = Some(format!("proxy group [{}]", &outbound.tag));
             outbound.bind = "0.0.0.0".to_string();
             match outbound.protocol.as_str() {
                 "tryall" => {
@@ -999,6 +1516,75 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                             settings.actors.push(ext_actor.to_string());
                         }
                     }
+                    if let Some(ext_sticky_timeout) = ext_proxy_group.sticky_timeout {
+                        settings.sticky_timeout = ext_sticky_timeout;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "balance" => {
+                    let mut settings = internal::BalanceOutboundSettings::new();
+                    if let Some(ext_actors) = &ext_proxy_group.actors {
+                        for ext_actor in ext_actors {
+                            settings.actors.push(ext_actor.to_string());
+                        }
+                    }
+                    if let Some(ext_strategy) = &ext_proxy_group.strategy {
+                        settings.strategy = crate::config::parse_balance_strategy(ext_strategy)
+                            .ok_or_else(|| anyhow!("invalid balance strategy {}", ext_strategy))?;
+                    }
+                    if let Some(ext_weights) = &ext_proxy_group.weights {
+                        if ext_weights.len() != settings.actors.len() {
+                            return Err(anyhow!(
+                                "balance weights must have the same length as actors"
+                            ));
+                        }
+                        settings.weights = ext_weights.clone();
+                    }
+                    if let Some(ext_sticky_timeout) = ext_proxy_group.sticky_timeout {
+                        settings.sticky_timeout = ext_sticky_timeout;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "select" => {
+                    let mut settings = internal::SelectOutboundSettings::new();
+                    if let Some(ext_actors) = &ext_proxy_group.actors {
+                        for ext_actor in ext_actors {
+                            settings.actors.push(ext_actor.to_string());
+                        }
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "urltest" => {
+                    let mut settings = internal::UrlTestOutboundSettings::new();
+                    if let Some(ext_actors) = &ext_proxy_group.actors {
+                        for ext_actor in ext_actors {
+                            settings.actors.push(ext_actor.to_string());
+                        }
+                    }
+                    if let Some(ext_url) = &ext_proxy_group.url {
+                        if !ext_url.starts_with("http://") {
+                            return Err(anyhow!("invalid url-test url {}", ext_url));
+                        }
+                        settings.url = ext_url.clone();
+                    } else {
+                        settings.url = "http://www.gstatic.com/generate_204".to_string();
+                    }
+                    if let Some(ext_interval) = ext_proxy_group.interval {
+                        settings.interval = ext_interval as u32;
+                    } else {
+                        settings.interval = 300;
+                    }
+                    if let Some(ext_tolerance) = ext_proxy_group.tolerance {
+                        settings.tolerance = ext_tolerance as u32;
+                    } else {
+                        settings.tolerance = 50;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -1045,6 +1631,14 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     } else {
                         settings.cache_timeout = 60; // in minutes
                     }
+                    if let Some(ext_priorities) = &ext_proxy_group.priorities {
+                        if ext_priorities.len() != settings.actors.len() {
+                            return Err(anyhow!(
+                                "failover priorities must have the same length as actors"
+                            ));
+                        }
+                        settings.priorities = ext_priorities.clone();
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -1070,12 +1664,24 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
         }
     }
 
+    *context = None;
+
     let mut rules = protobuf::RepeatedField::new();
     if let Some(ext_rules) = &conf.rule {
         let mut site_group_lists = HashMap::<String, geosite::SiteGroupList>::new();
         for ext_rule in ext_rules {
             let mut rule = internal::RoutingRule::new();
             rule.target_tag = ext_rule.target.clone();
+            rule.no_log = ext_rule.no_log;
+            if let Some(ext_dns_strategy) = &ext_rule.dns_strategy {
+                rule.dns_strategy = crate::config::parse_dns_strategy(ext_dns_strategy)
+                    .ok_or_else(|| anyhow!("invalid dns strategy {}", ext_dns_strategy))?;
+            }
+            if let Some(ext_network) = &ext_rule.network {
+                let networks = crate::config::parse_networks(ext_network)
+                    .ok_or_else(|| anyhow!("invalid network {}", ext_network))?;
+                rule.networks.extend(networks);
+            }
 
             // handle FINAL rule first
             if ext_rule.type_field == "FINAL" {
@@ -1138,13 +1744,24 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
                     ) {
                         Ok(_) => (),
                         Err(e) => {
-                            println!("load external rule failed: {}", e);
+                            return Err(anyhow!("load external rule failed: {}", e));
                         }
                     }
                 }
                 "PORT-RANGE" => {
+                    if crate::config::parse_ports(&ext_filter).is_none() {
+                        return Err(anyhow!("invalid port range {}", ext_filter));
+                    }
                     rule.port_ranges.push(ext_filter);
                 }
+                "PROCESS-NAME" => {
+                    rule.process_names.push(ext_filter);
+                }
+                "NETWORK" => {
+                    let networks = crate::config::parse_networks(&ext_filter)
+                        .ok_or_else(|| anyhow!("invalid network {}", ext_filter))?;
+                    rule.networks.extend(networks);
+                }
                 _ => {}
             }
             rules.push(rule);
@@ -1163,7 +1780,16 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
         }
         if let Some(ext_dns_servers) = &ext_general.dns_server {
             for ext_dns_server in ext_dns_servers {
-                servers.push(ext_dns_server.clone());
+                // an optional outbound to reach the server, e.g. 1.1.1.1@Proxy
+                let parts: Vec<&str> = ext_dns_server.splitn(2, '@').collect();
+                if parts.len() == 2 {
+                    let address = parts[0].trim().to_string();
+                    dns.server_outbounds
+                        .insert(address.clone(), parts[1].trim().to_string());
+                    servers.push(address);
+                } else {
+                    servers.push(ext_dns_server.clone());
+                }
             }
             if servers.len() == 0 {
                 servers.push("114.114.114.114".to_string());
@@ -1171,6 +1797,68 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
             }
             dns.servers = servers;
         }
+        if let Some(ext_filter_bogons) = ext_general.dns_filter_bogons {
+            dns.filter_bogons = ext_filter_bogons;
+        }
+        if let Some(ext_filter_private) = ext_general.dns_filter_private {
+            dns.filter_private = ext_filter_private;
+        }
+        if let Some(ext_internal_domains) = &ext_general.dns_internal_domains {
+            dns.internal_domains = protobuf::RepeatedField::from_vec(ext_internal_domains.clone());
+        }
+        if let Some(ext_fail_open) = ext_general.dns_fail_open {
+            dns.fail_open = ext_fail_open;
+        }
+        if let Some(ext_fallback_ip) = &ext_general.dns_fallback_ip {
+            if ext_fallback_ip.parse::<IpAddr>().is_err() {
+                return Err(anyhow!("invalid dns fallback ip {}", ext_fallback_ip));
+            }
+            dns.fallback_ip = ext_fallback_ip.clone();
+        }
+        if let Some(ext_dot_idle_timeout) = ext_general.dns_dot_idle_timeout {
+            dns.dot_idle_timeout = ext_dot_idle_timeout;
+        }
+        if let Some(ext_cache_size) = ext_general.dns_cache_size {
+            dns.cache_size = ext_cache_size;
+        }
+        if let Some(ext_min_cache_ttl) = ext_general.dns_min_cache_ttl {
+            dns.min_cache_ttl = ext_min_cache_ttl;
+        }
+        if let Some(ext_max_cache_ttl) = ext_general.dns_max_cache_ttl {
+            dns.max_cache_ttl = ext_max_cache_ttl;
+        }
+        if dns.max_cache_ttl > 0 && dns.min_cache_ttl > dns.max_cache_ttl {
+            return Err(anyhow!(
+                "dns min cache ttl {} is greater than max cache ttl {}",
+                dns.min_cache_ttl,
+                dns.max_cache_ttl
+            ));
+        }
+        if let Some(ext_negative_cache_ttl) = ext_general.dns_negative_cache_ttl {
+            dns.negative_cache_ttl = ext_negative_cache_ttl;
+        }
+        if let Some(ext_prefetch) = ext_general.dns_prefetch {
+            if ext_prefetch >= 100 {
+                return Err(anyhow!("invalid dns prefetch {}", ext_prefetch));
+            }
+            dns.prefetch = ext_prefetch;
+        }
+        if let Some(ext_prefetch_min_hits) = ext_general.dns_prefetch_min_hits {
+            dns.prefetch_min_hits = ext_prefetch_min_hits;
+        }
+        if let Some(ext_client_subnet) = &ext_general.dns_client_subnet {
+            if crate::app::dns_client::ClientSubnet::from_config(ext_client_subnet).is_none() {
+                return Err(anyhow!("invalid dns client subnet {}", ext_client_subnet));
+            }
+            dns.client_subnet = ext_client_subnet.clone();
+        }
+        if let Some(ext_dns_outbound) = &ext_general.dns_outbound {
+            dns.outbound = ext_dns_outbound.clone();
+        }
+        if let Some(ext_dns_strategy) = &ext_general.dns_strategy {
+            dns.strategy = crate::config::parse_dns_strategy(ext_dns_strategy)
+                .ok_or_else(|| anyhow!("invalid dns strategy {}", ext_dns_strategy))?;
+        }
     }
     if let Some(ext_hosts) = &conf.host {
         for (name, static_ips) in ext_hosts.iter() {
@@ -1186,6 +1874,18 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
     if hosts.len() > 0 {
         dns.hosts = hosts;
     }
+    for (server, outbound) in dns.server_outbounds.iter() {
+        if !outbounds.iter().any(|o| &o.tag == outbound) {
+            return Err(anyhow!(
+                "outbound [{}] for dns server {} not found",
+                outbound,
+                server
+            ));
+        }
+    }
+    if !dns.outbound.is_empty() && !outbounds.iter().any(|o| o.tag == dns.outbound) {
+        return Err(anyhow!("outbound [{}] for dns not found", dns.outbound));
+    }
 
     let mut config = internal::Config::new();
     config.log = protobuf::SingularPtrField::some(log);
@@ -1193,6 +1893,25 @@ pub fn to_internal(conf: Config) -> Result<internal::Config> {
     config.outbounds = outbounds;
     config.routing_rules = rules;
     config.dns = protobuf::SingularPtrField::some(dns);
+    if let Some(ext_general) = &conf.general {
+        if let Some(ext_control_outbound) = &ext_general.control_outbound {
+            config.control_outbound = ext_control_outbound.clone();
+        }
+        if let Some(ext_routing_dry_run) = ext_general.routing_dry_run {
+            let mut router = internal::Router::new();
+            router.dry_run = ext_routing_dry_run;
+            config.router = protobuf::SingularPtrField::some(router);
+        }
+        if let Some(ext_udp_session_timeout) = ext_general.udp_session_timeout {
+            config.udp_session_timeout = ext_udp_session_timeout;
+        }
+        if let Some(ext_link_buffer_size) = ext_general.link_buffer_size {
+            if !crate::config::is_valid_link_buffer_size(ext_link_buffer_size) {
+                return Err(anyhow!("invalid link buffer size {}", ext_link_buffer_size));
+            }
+            config.link_buffer_size = ext_link_buffer_size;
+        }
+    }
 
     drop(conf); // make sure no partial moved fields
 
@@ -1,3 +1,4 @@
 mod config;
+pub mod share_link;
 
 pub use config::*;
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use crate::config::internal;
+
+use super::{Config, Proxy};
+
+fn decode_base64(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim().trim_end_matches('=');
+    if let Ok(v) = base64::decode_config(s, base64::STANDARD_NO_PAD) {
+        return Ok(v);
+    }
+    base64::decode_config(s, base64::URL_SAFE_NO_PAD).map_err(|e| anyhow!("invalid base64: {}", e))
+}
+
+fn decode_percent(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(hex) = std::str::from_utf8(&bytes[i + 1..i + 3]) {
+                if let Ok(v) = u8::from_str_radix(hex, 16) {
+                    out.push(v);
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn split_host_port(s: &str) -> Result<(String, u16)> {
+    let pos = s
+        .rfind(':')
+        .ok_or_else(|| anyhow!("missing port in {}", s))?;
+    let host = s[..pos].trim_start_matches('[').trim_end_matches(']');
+    let port = s[pos + 1..]
+        .parse::<u16>()
+        .map_err(|e| anyhow!("invalid port in {}: {}", s, e))?;
+    if host.is_empty() {
+        return Err(anyhow!("missing host in {}", s));
+    }
+    Ok((host.to_string(), port))
+}
+
+// Splits `scheme://rest#fragment` into (rest, query params, fragment).
+fn split_link<'a>(
+    link: &'a str,
+    scheme: &str,
+) -> Result<(&'a str, HashMap<String, String>, Option<String>)> {
+    let rest = &link[scheme.len() + 3..];
+    let (rest, fragment) = match rest.find('#') {
+        Some(pos) => (&rest[..pos], Some(decode_percent(&rest[pos + 1..]))),
+        None => (rest, None),
+    };
+    let (rest, query) = match rest.find('?') {
+        Some(pos) => (&rest[..pos], Some(&rest[pos + 1..])),
+        None => (rest, None),
+    };
+    let mut params = HashMap::new();
+    if let Some(query) = query {
+        for kv in query.split('&') {
+            let parts: Vec<&str> = kv.splitn(2, '=').collect();
+            if parts.len() == 2 {
+                params.insert(parts[0].to_string(), decode_percent(parts[1]));
+            }
+        }
+    }
+    Ok((rest.trim_end_matches('/'), params, fragment))
+}
+
+fn default_tag(proxy: &Proxy) -> String {
+    format!(
+        "{}_{}_{}",
+        proxy.protocol,
+        proxy.address.as_ref().unwrap_or(&"".to_string()),
+        proxy.port.unwrap_or(0)
+    )
+}
+
+// ss://base64(method:password)@host:port/?plugin=obfs-local;obfs=http#tag (SIP002)
+// ss://base64(method:password@host:port)#tag (legacy)
+fn ss_to_proxy(link: &str) -> Result<Proxy> {
+    let (rest, params, tag) = split_link(link, "ss")?;
+    let plain = match rest.rfind('@') {
+        Some(pos) => {
+            let user_info = decode_percent(&rest[..pos]);
+            let user_info = match decode_base64(&user_info) {
+                Ok(v) => String::from_utf8(v)?,
+                Err(_) => user_info,
+            };
+            format!("{}@{}", user_info, &rest[pos + 1..])
+        }
+        None => String::from_utf8(decode_base64(rest)?)?,
+    };
+    let pos = plain.rfind('@').ok_or_else(|| anyhow!("invalid ss link"))?;
+    let (method, password) = {
+        let parts: Vec<&str> = plain[..pos].splitn(2, ':').collect();
+        if parts.len() != 2 {
+            return Err(anyhow!("invalid ss link"));
+        }
+        (parts[0].to_string(), parts[1].to_string())
+    };
+    let (address, port) = split_host_port(&plain[pos + 1..])?;
+    let mut proxy = Proxy::default();
+    proxy.protocol = "shadowsocks".to_string();
+    proxy.address = Some(address);
+    proxy.port = Some(port);
+    proxy.encrypt_method = Some(method);
+    proxy.password = Some(password);
+    if let Some(plugin) = params.get("plugin") {
+        let mut opts = plugin.split(';');
+        match opts.next() {
+            Some("obfs-local") | Some("simple-obfs") => (),
+            _ => return Err(anyhow!("unsupported ss plugin: {}", plugin)),
+        }
+        for opt in opts {
+            let parts: Vec<&str> = opt.splitn(2, '=').collect();
+            if parts.len() != 2 {
+                continue;
+            }
+            match parts[0] {
+                "obfs" => proxy.obfs = Some(parts[1].to_string()),
+                "obfs-host" => proxy.obfs_host = Some(parts[1].to_string()),
+                "obfs-uri" => proxy.obfs_uri = Some(parts[1].to_string()),
+                _ => (),
+            }
+        }
+    }
+    proxy.tag = tag.unwrap_or_else(|| default_tag(&proxy));
+    Ok(proxy)
+}
+
+// trojan://password@host:port?sni=example.com&type=ws&path=/&host=example.com#tag
+fn trojan_to_proxy(link: &str) -> Result<Proxy> {
+    let (rest, params, tag) = split_link(link, "trojan")?;
+    let pos = rest
+        .rfind('@')
+        .ok_or_else(|| anyhow!("invalid trojan link"))?;
+    let (address, port) = split_host_port(&rest[pos + 1..])?;
+    let mut proxy = Proxy::default();
+    proxy.protocol = "trojan".to_string();
+    proxy.password = Some(decode_percent(&rest[..pos]));
+    proxy.sni = params
+        .get("sni")
+        .or_else(|| params.get("peer"))
+        .cloned()
+        .or_else(|| Some(address.clone()));
+    if let Some("ws") = params.get("type").map(String::as_str) {
+        proxy.ws = Some(true);
+        proxy.ws_path = params.get("path").cloned();
+        proxy.ws_host = params.get("host").cloned();
+    }
+    proxy.address = Some(address);
+    proxy.port = Some(port);
+    proxy.tag = tag.unwrap_or_else(|| default_tag(&proxy));
+    Ok(proxy)
+}
+
+// vless://uuid@host:port?security=tls&sni=example.com&type=ws&path=/&host=example.com&flow=xtls-rprx-direct#tag
+fn vless_to_proxy(link: &str) -> Result<Proxy> {
+    let (rest, params, tag) = split_link(link, "vless")?;
+    let pos = rest
+        .rfind('@')
+        .ok_or_else(|| anyhow!("invalid vless link"))?;
+    let (address, port) = split_host_port(&rest[pos + 1..])?;
+    if let Some(encryption) = params.get("encryption") {
+        if encryption != "none" {
+            return Err(anyhow!("unsupported vless encryption: {}", encryption));
+        }
+    }
+    let mut proxy = Proxy::default();
+    proxy.protocol = "vless".to_string();
+    proxy.username = Some(decode_percent(&rest[..pos]));
+    match params.get("security").map(String::as_str) {
+        None | Some("") | Some("none") => (),
+        Some("tls") => {
+            proxy.tls = Some(true);
+            proxy.sni = params.get("sni").cloned().or_else(|| Some(address.clone()));
+        }
+        Some(security) => return Err(anyhow!("unsupported vless security: {}", security)),
+    }
+    match params.get("type").map(String::as_str) {
+        None | Some("tcp") => (),
+        Some("ws") => {
+            proxy.ws = Some(true);
+            proxy.ws_path = params.get("path").cloned();
+            proxy.ws_host = params.get("host").cloned();
+        }
+        Some(t) => return Err(anyhow!("unsupported vless transport: {}", t)),
+    }
+    proxy.flow = params.get("flow").filter(|f| !f.is_empty()).cloned();
+    proxy.address = Some(address);
+    proxy.port = Some(port);
+    proxy.tag = tag.unwrap_or_else(|| default_tag(&proxy));
+    Ok(proxy)
+}
+
+// vmess://base64(json), the format used by v2rayN
+#[cfg(feature = "config-json")]
+fn vmess_to_proxy(link: &str) -> Result<Proxy> {
+    let json = String::from_utf8(decode_base64(&link["vmess://".len()..])?)?;
+    let v: serde_json::Value =
+        serde_json::from_str(&json).map_err(|e| anyhow!("invalid vmess link: {}", e))?;
+    let get = |k: &str| -> Option<String> {
+        match v.get(k) {
+            Some(serde_json::Value::String(s)) if !s.is_empty() => Some(s.clone()),
+            Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        }
+    };
+    let mut proxy = Proxy::default();
+    proxy.protocol = "vmess".to_string();
+    proxy.address = Some(get("add").ok_or_else(|| anyhow!("missing vmess address"))?);
+    proxy.port = Some(
+        get("port")
+            .and_then(|p| p.parse::<u16>().ok())
+            .ok_or_else(|| anyhow!("invalid vmess port"))?,
+    );
+    proxy.username = Some(get("id").ok_or_else(|| anyhow!("missing vmess id"))?);
+    if let Some(security) = get("scy") {
+        if security != "auto" {
+            proxy.encrypt_method = Some(security);
+        }
+    }
+    if let Some("ws") = get("net").as_deref() {
+        proxy.ws = Some(true);
+        proxy.ws_path = get("path");
+        proxy.ws_host = get("host");
+    }
+    if let Some("tls") = get("tls").as_deref() {
+        proxy.tls = Some(true);
+        proxy.sni = get("sni").or_else(|| get("host"));
+    }
+    proxy.tag = get("ps").unwrap_or_else(|| default_tag(&proxy));
+    Ok(proxy)
+}
+
+/// Parses a share link, e.g. `ss://`, `trojan://`, `vless://` or `vmess://`,
+/// into a proxy.
+pub fn to_proxy(link: &str) -> Result<Proxy> {
+    let link = link.trim();
+    if link.starts_with("ss://") {
+        return ss_to_proxy(link);
+    }
+    if link.starts_with("trojan://") {
+        return trojan_to_proxy(link);
+    }
+    if link.starts_with("vless://") {
+        return vless_to_proxy(link);
+    }
+    #[cfg(feature = "config-json")]
+    {
+        if link.starts_with("vmess://") {
+            return vmess_to_proxy(link);
+        }
+    }
+    Err(anyhow!("unsupported share link: {}", link))
+}
+
+/// Converts share links to outbounds, a link may result in more than one
+/// outbound, the first one is the entry.
+pub fn to_outbounds(links: &[String]) -> Result<protobuf::RepeatedField<internal::Outbound>> {
+    let mut proxies = Vec::new();
+    for link in links {
+        proxies.push(to_proxy(link)?);
+    }
+    let mut conf = Config::default();
+    conf.proxy = Some(proxies);
+    Ok(super::to_internal(conf)?.outbounds)
+}
+
+/// Decodes a subscription body, which is a base64 encoded list of share links,
+/// links failed to parse are ignored.
+pub fn decode_subscription(body: &[u8]) -> Result<Vec<String>> {
+    let text = String::from_utf8_lossy(body);
+    let text = if text.contains("://") {
+        text.to_string()
+    } else {
+        let joined: String = text.split_whitespace().collect();
+        String::from_utf8(decode_base64(&joined)?)?
+    };
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|l| to_proxy(l).is_ok())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ss_link() {
+        // aes-256-gcm:password
+        let p = to_proxy("ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@1.2.3.4:8388#my%20ss").unwrap();
+        assert_eq!(p.tag, "my ss");
+        assert_eq!(p.encrypt_method.unwrap(), "aes-256-gcm");
+        assert_eq!(p.password.unwrap(), "password");
+        assert_eq!(p.address.unwrap(), "1.2.3.4");
+        assert_eq!(p.port.unwrap(), 8388);
+    }
+
+    #[test]
+    fn test_ss_plugin_link() {
+        let p = to_proxy(
+            "ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@1.2.3.4:8388/?plugin=obfs-local%3Bobfs%3Dhttp%3Bobfs-host%3Dwww.bing.com#t",
+        )
+        .unwrap();
+        assert_eq!(p.obfs.unwrap(), "http");
+        assert_eq!(p.obfs_host.unwrap(), "www.bing.com");
+        assert!(
+            to_proxy("ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@1.2.3.4:8388/?plugin=v2ray-plugin#t")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_trojan_link() {
+        let p = to_proxy("trojan://pass@example.com:443?sni=sni.com&type=ws&path=%2Fws#t").unwrap();
+        assert_eq!(p.tag, "t");
+        assert_eq!(p.password.unwrap(), "pass");
+        assert_eq!(p.sni.unwrap(), "sni.com");
+        assert_eq!(p.ws, Some(true));
+        assert_eq!(p.ws_path.unwrap(), "/ws");
+    }
+
+    #[test]
+    fn test_legacy_ss_link() {
+        // aes-128-gcm:pass@example.com:8388
+        let p = to_proxy("ss://YWVzLTEyOC1nY206cGFzc0BleGFtcGxlLmNvbTo4Mzg4#%E6%97%A5%E6%9C%AC")
+            .unwrap();
+        assert_eq!(p.tag, "日本");
+        assert_eq!(p.encrypt_method.unwrap(), "aes-128-gcm");
+        assert_eq!(p.password.unwrap(), "pass");
+        assert_eq!(p.address.unwrap(), "example.com");
+        assert_eq!(p.port.unwrap(), 8388);
+    }
+
+    #[test]
+    fn test_vless_link() {
+        let p = to_proxy(
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@example.com:443?encryption=none&security=tls&type=ws&path=%2Fv#v",
+        )
+        .unwrap();
+        assert_eq!(p.tag, "v");
+        assert_eq!(p.username.unwrap(), "b831381d-6324-4d53-ad4f-8cda48b30811");
+        assert_eq!(p.tls, Some(true));
+        assert_eq!(p.sni.unwrap(), "example.com");
+        assert_eq!(p.ws, Some(true));
+        assert_eq!(p.ws_path.unwrap(), "/v");
+        assert!(to_proxy("vless://id@example.com:443?security=reality#r").is_err());
+    }
+
+    #[test]
+    fn test_parse_subscription() {
+        let outbounds = crate::config::parse_subscription("c3M6Ly9ZV1Z6TFRJMU5pMW5ZMjA2Y0dGemMzZHZjbVFAMS4yLjMuNDo4Mzg4I3MKdHJvamFuOi8vcGFzc0BleGFtcGxlLmNvbTo0NDMjdAp1bmtub3duOi8veAo=").unwrap();
+        let tags: Vec<&str> = outbounds.iter().map(|o| o.tag.as_str()).collect();
+        assert_eq!(tags, vec!["s", "t_tls_xxx", "t_trojan_xxx", "t"]);
+        assert_eq!(outbounds[0].protocol, "shadowsocks");
+        assert_eq!(outbounds[3].protocol, "chain");
+    }
+}
@@ -36,8 +36,16 @@ pub fn load_mmdb_rule(filter: &str) -> Result<(String, String)> {
     load_file_or_default(filter, "geo.mmdb")
 }
 
+pub fn load_geoip_rule(filter: &str) -> Result<(String, String)> {
+    load_file_or_default(filter, "geoip.dat")
+}
+
 pub fn load_site_rule(filter: &str) -> Result<(String, String)> {
-    load_file_or_default(filter, "site.dat")
+    if filter.starts_with("geosite") {
+        load_file_or_default(filter, "geosite.dat")
+    } else {
+        load_file_or_default(filter, "site.dat")
+    }
 }
 
 pub fn add_external_rule(
@@ -58,75 +66,200 @@ pub fn add_external_rule(
         rule.mmdbs.push(mmdb)
     }
 
-    if ext_external.starts_with("site") {
+    if ext_external.starts_with("geoip") {
+        let (file, code) = match load_geoip_rule(&ext_external) {
+            Ok((f, c)) => (f, c),
+            Err(e) => {
+                return Err(anyhow!("load geoip rule failed: {}", e));
+            }
+        };
+        // The router tells the format apart when loading the file.
+        let mut mmdb = internal::RoutingRule_Mmdb::new();
+        mmdb.file = file;
+        mmdb.country_code = code;
+        rule.mmdbs.push(mmdb)
+    }
+
+    if ext_external.starts_with("site") || ext_external.starts_with("geosite") {
         let (file, code) = match load_site_rule(&ext_external) {
             Ok((f, c)) => (f, c),
             Err(e) => {
                 return Err(anyhow!("load site rule failed: {}", e));
             }
         };
-        let site_group_list = match site_group_lists.get(&file) {
-            Some(l) => l,
+        // geosite:TAG@ATTR only includes the domains having the attribute.
+        let mut parts = code.split('@');
+        let tag = parts.next().unwrap_or_default().to_uppercase();
+        let attrs: Vec<&str> = parts.filter(|a| !a.is_empty()).collect();
+
+        let site_group_list = load_site_group_list(&file, site_group_lists)?;
+        let site_group = match site_group_list.site_group.iter().find(|g| g.tag == tag) {
+            Some(g) => g,
             None => {
-                let mut f = match File::open(&file) {
-                    Ok(f) => f,
-                    Err(e) => {
-                        return Err(anyhow!("open dat file {} failed: {}", &file, e));
-                    }
-                };
-                let mut buf = Vec::new();
-                match f.read_to_end(&mut buf) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(anyhow!("reading dat file {} failed: {}", &file, e));
-                    }
-                }
-                let site_group_list = match geosite::SiteGroupList::parse_from_bytes(&buf) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        return Err(anyhow!("dat file {} has invalid format: {}", &file, e));
-                    }
-                };
-                site_group_lists.insert(file.clone(), site_group_list);
-                site_group_lists.get(&file).unwrap()
+                return Err(anyhow!("tag [{}] not found in dat file {}", tag, &file));
             }
         };
-
-        for site_group in site_group_list.site_group.iter() {
-            if site_group.tag == code.to_uppercase() {
-                for domain in site_group.domain.iter() {
-                    let mut domain_rule = match domain.field_type {
-                        geosite::Domain_Type::Plain => {
-                            let mut d = internal::RoutingRule_Domain::new();
-                            d.field_type = internal::RoutingRule_Domain_Type::PLAIN;
-                            d
-                        }
-                        geosite::Domain_Type::Domain => {
-                            let mut d = internal::RoutingRule_Domain::new();
-                            d.field_type = internal::RoutingRule_Domain_Type::DOMAIN;
-                            d
-                        }
-                        geosite::Domain_Type::Full => {
-                            let mut d = internal::RoutingRule_Domain::new();
-                            d.field_type = internal::RoutingRule_Domain_Type::FULL;
-                            d
-                        }
-                        _ => {
-                            continue;
-                        }
-                    };
-                    domain_rule.value = domain.value.clone();
-                    rule.domains.push(domain_rule);
-                }
-                println!(
-                    "loaded {} domain rules from [{}] for tag [{}]",
-                    rule.domains.len(),
-                    file,
-                    code
-                );
-                break; // assume at most 1 matched tag
+        let n = rule.domains.len();
+        for domain in site_group.domain.iter() {
+            if !attrs
+                .iter()
+                .all(|a| domain.attribute.iter().any(|da| da.key == *a))
+            {
+                continue;
             }
+            let mut domain_rule = match domain.field_type {
+                geosite::Domain_Type::Plain => {
+                    let mut d = internal::RoutingRule_Domain::new();
+                    d.field_type = internal::RoutingRule_Domain_Type::PLAIN;
+                    d
+                }
+                geosite::Domain_Type::Domain => {
+                    let mut d = internal::RoutingRule_Domain::new();
+                    d.field_type = internal::RoutingRule_Domain_Type::DOMAIN;
+                    d
+                }
+                geosite::Domain_Type::Full => {
+                    let mut d = internal::RoutingRule_Domain::new();
+                    d.field_type = internal::RoutingRule_Domain_Type::FULL;
+                    d
+                }
+                _ => {
+                    continue;
+                }
+            };
+            domain_rule.value = domain.value.clone();
+            rule.domains.push(domain_rule);
         }
+        println!(
+            "loaded {} domain rules from [{}] for tag [{}]",
+            rule.domains.len() - n,
+            file,
+            code
+        );
     }
     Ok(())
 }
+
+fn load_site_group_list<'a>(
+    file: &str,
+    site_group_lists: &'a mut HashMap<String, geosite::SiteGroupList>,
+) -> Result<&'a geosite::SiteGroupList> {
+    if !site_group_lists.contains_key(file) {
+        let mut f = match File::open(file) {
+            Ok(f) => f,
+            Err(e) => {
+                return Err(anyhow!("open dat file {} failed: {}", file, e));
+            }
+        };
+        let mut buf = Vec::new();
+        match f.read_to_end(&mut buf) {
+            Ok(_) => (),
+            Err(e) => {
+                return Err(anyhow!("reading dat file {} failed: {}", file, e));
+            }
+        }
+        let site_group_list = match geosite::SiteGroupList::parse_from_bytes(&buf) {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(anyhow!("dat file {} has invalid format: {}", file, e));
+            }
+        };
+        site_group_lists.insert(file.to_owned(), site_group_list);
+    }
+    Ok(site_group_lists.get(file).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site_domain(t: geosite::Domain_Type, value: &str, attrs: &[&str]) -> geosite::Domain {
+        let mut d = geosite::Domain::new();
+        d.field_type = t;
+        d.value = value.to_string();
+        for a in attrs {
+            let mut attr = geosite::Domain_Attribute::new();
+            attr.key = a.to_string();
+            attr.set_bool_value(true);
+            d.attribute.push(attr);
+        }
+        d
+    }
+
+    #[test]
+    fn test_geosite_rule() {
+        let mut group = geosite::SiteGroup::new();
+        group.tag = "GOOGLE".to_string();
+        group
+            .domain
+            .push(site_domain(geosite::Domain_Type::Domain, "google.com", &[]));
+        group.domain.push(site_domain(
+            geosite::Domain_Type::Full,
+            "ads.google.com",
+            &["ads"],
+        ));
+        group.domain.push(site_domain(
+            geosite::Domain_Type::Plain,
+            "doubleclick",
+            &["ads", "cn"],
+        ));
+        let mut list = geosite::SiteGroupList::new();
+        list.site_group.push(group);
+
+        let path = std::env::temp_dir().join(format!("leaf-geosite-{}.dat", std::process::id()));
+        std::fs::write(&path, list.write_to_bytes().unwrap()).unwrap();
+        let file = path.to_str().unwrap();
+        let mut lists = HashMap::new();
+
+        let mut rule = internal::RoutingRule::new();
+        add_external_rule(&mut rule, &format!("geosite:{}:google", file), &mut lists).unwrap();
+        assert_eq!(rule.domains.len(), 3);
+
+        let mut rule = internal::RoutingRule::new();
+        add_external_rule(
+            &mut rule,
+            &format!("geosite:{}:google@ads", file),
+            &mut lists,
+        )
+        .unwrap();
+        let values: Vec<_> = rule.domains.iter().map(|d| d.value.as_str()).collect();
+        assert_eq!(values, vec!["ads.google.com", "doubleclick"]);
+        assert_eq!(
+            rule.domains[0].field_type,
+            internal::RoutingRule_Domain_Type::FULL
+        );
+
+        let mut rule = internal::RoutingRule::new();
+        add_external_rule(
+            &mut rule,
+            &format!("geosite:{}:google@ads@cn", file),
+            &mut lists,
+        )
+        .unwrap();
+        assert_eq!(rule.domains.len(), 1);
+
+        let mut rule = internal::RoutingRule::new();
+        assert!(
+            add_external_rule(&mut rule, &format!("geosite:{}:apple", file), &mut lists).is_err()
+        );
+
+        // A malformed file.
+        std::fs::write(&path, b"\xff\xff\xff").unwrap();
+        let mut rule = internal::RoutingRule::new();
+        assert!(add_external_rule(
+            &mut rule,
+            &format!("geosite:{}:google", file),
+            &mut HashMap::new()
+        )
+        .is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        let mut rule = internal::RoutingRule::new();
+        assert!(add_external_rule(
+            &mut rule,
+            &format!("geosite:{}:google", file),
+            &mut HashMap::new()
+        )
+        .is_err());
+    }
+}
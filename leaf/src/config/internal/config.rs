@@ -29,6 +29,26 @@ pub struct DNS {
     pub servers: ::protobuf::RepeatedField<::std::string::String>,
     pub bind: ::std::string::String,
     pub hosts: ::std::collections::HashMap<::std::string::String, DNS_IPs>,
+    pub filter_bogons: bool,
+    pub filter_private: bool,
+    pub internal_domains: ::protobuf::RepeatedField<::std::string::String>,
+    pub server_outbounds: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub fail_open: bool,
+    pub fallback_ip: ::std::string::String,
+    pub server_bootstraps: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub dot_idle_timeout: u32,
+    pub server_ca_files: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub cache_size: u32,
+    pub min_cache_ttl: u32,
+    pub max_cache_ttl: u32,
+    pub negative_cache_ttl: u32,
+    pub outbound: ::std::string::String,
+    pub strategy: DnsStrategy,
+    pub groups: ::std::collections::HashMap<::std::string::String, DNS_Servers>,
+    pub policies: ::protobuf::RepeatedField<DNS_Policy>,
+    pub prefetch: u32,
+    pub prefetch_min_hits: u32,
+    pub client_subnet: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -120,384 +140,6079 @@ impl DNS {
     pub fn take_hosts(&mut self) -> ::std::collections::HashMap<::std::string::String, DNS_IPs> {
         ::std::mem::replace(&mut self.hosts, ::std::collections::HashMap::new())
     }
-}
 
-impl ::protobuf::Message for DNS {
-    fn is_initialized(&self) -> bool {
-        true
-    }
+    // bool filter_bogons = 4;
 
-    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        while !is.eof()? {
-            let (field_number, wire_type) = is.read_tag_unpack()?;
-            match field_number {
-                1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.servers)?;
-                },
-                2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.bind)?;
-                },
-                3 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(wire_type, is, &mut self.hosts)?;
-                },
-                _ => {
-                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
-                },
-            };
-        }
-        ::std::result::Result::Ok(())
-    }
 
-    // Compute sizes of nested messages
-    #[allow(unused_variables)]
-    fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        for value in &self.servers {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        if !self.bind.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.bind);
-        }
-        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(3, &self.hosts);
-        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
-        self.cached_size.set(my_size);
-        my_size
+    pub fn get_filter_bogons(&self) -> bool {
+        self.filter_bogons
     }
-
-    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.servers {
-            os.write_string(1, &v)?;
-        };
-        if !self.bind.is_empty() {
-            os.write_string(2, &self.bind)?;
-        }
-        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(3, &self.hosts, os)?;
-        os.write_unknown_fields(self.get_unknown_fields())?;
-        ::std::result::Result::Ok(())
+    pub fn clear_filter_bogons(&mut self) {
+        self.filter_bogons = false;
     }
 
-    fn get_cached_size(&self) -> u32 {
-        self.cached_size.get()
+    // Param is passed by value, moved
+    pub fn set_filter_bogons(&mut self, v: bool) {
+        self.filter_bogons = v;
     }
 
-    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
-        &self.unknown_fields
-    }
+    // bool filter_private = 5;
 
-    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
-        &mut self.unknown_fields
+
+    pub fn get_filter_private(&self) -> bool {
+        self.filter_private
+    }
+    pub fn clear_filter_private(&mut self) {
+        self.filter_private = false;
     }
 
-    fn as_any(&self) -> &dyn (::std::any::Any) {
-        self as &dyn (::std::any::Any)
+    // Param is passed by value, moved
+    pub fn set_filter_private(&mut self, v: bool) {
+        self.filter_private = v;
     }
-    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
-        self as &mut dyn (::std::any::Any)
+
+    // repeated string internal_domains = 6;
+
+
+    pub fn get_internal_domains(&self) -> &[::std::string::String] {
+        &self.internal_domains
     }
-    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
-        self
+    pub fn clear_internal_domains(&mut self) {
+        self.internal_domains.clear();
     }
 
-    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
-        Self::descriptor_static()
+    // Param is passed by value, moved
+    pub fn set_internal_domains(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.internal_domains = v;
     }
 
-    fn new() -> DNS {
-        DNS::new()
+    // Mutable pointer to the field.
+    pub fn mut_internal_domains(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.internal_domains
     }
 
-    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
-        descriptor.get(|| {
-            let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "servers",
-                |m: &DNS| { &m.servers },
-                |m: &mut DNS| { &mut m.servers },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "bind",
-                |m: &DNS| { &m.bind },
-                |m: &mut DNS| { &mut m.bind },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(
-                "hosts",
-                |m: &DNS| { &m.hosts },
-                |m: &mut DNS| { &mut m.hosts },
-            ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DNS>(
-                "DNS",
-                fields,
-                file_descriptor_proto()
-            )
-        })
+    // Take field
+    pub fn take_internal_domains(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.internal_domains, ::protobuf::RepeatedField::new())
     }
 
-    fn default_instance() -> &'static DNS {
-        static instance: ::protobuf::rt::LazyV2<DNS> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(DNS::new)
+    // repeated .DNS.Server_outboundsEntry server_outbounds = 7;
+
+
+    pub fn get_server_outbounds(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.server_outbounds
+    }
+    pub fn clear_server_outbounds(&mut self) {
+        self.server_outbounds.clear();
     }
-}
 
-impl ::protobuf::Clear for DNS {
-    fn clear(&mut self) {
-        self.servers.clear();
-        self.bind.clear();
-        self.hosts.clear();
-        self.unknown_fields.clear();
+    // Param is passed by value, moved
+    pub fn set_server_outbounds(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.server_outbounds = v;
     }
-}
 
-impl ::std::fmt::Debug for DNS {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        ::protobuf::text_format::fmt(self, f)
+    // Mutable pointer to the field.
+    pub fn mut_server_outbounds(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.server_outbounds
     }
-}
 
-impl ::protobuf::reflect::ProtobufValue for DNS {
-    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Message(self)
+    // Take field
+    pub fn take_server_outbounds(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.server_outbounds, ::std::collections::HashMap::new())
     }
-}
 
-#[derive(PartialEq,Clone,Default)]
-pub struct DNS_IPs {
-    // message fields
-    pub values: ::protobuf::RepeatedField<::std::string::String>,
-    // special fields
-    pub unknown_fields: ::protobuf::UnknownFields,
-    pub cached_size: ::protobuf::CachedSize,
-}
+    // bool fail_open = 8;
 
-impl<'a> ::std::default::Default for &'a DNS_IPs {
-    fn default() -> &'a DNS_IPs {
-        <DNS_IPs as ::protobuf::Message>::default_instance()
+
+    pub fn get_fail_open(&self) -> bool {
+        self.fail_open
+    }
+    pub fn clear_fail_open(&mut self) {
+        self.fail_open = false;
     }
-}
 
-impl DNS_IPs {
-    pub fn new() -> DNS_IPs {
-        ::std::default::Default::default()
+    // Param is passed by value, moved
+    pub fn set_fail_open(&mut self, v: bool) {
+        self.fail_open = v;
     }
 
-    // repeated string values = 1;
+    // string fallback_ip = 9;
 
 
-    pub fn get_values(&self) -> &[::std::string::String] {
-        &self.values
+    pub fn get_fallback_ip(&self) -> &str {
+        &self.fallback_ip
     }
-    pub fn clear_values(&mut self) {
-        self.values.clear();
+    pub fn clear_fallback_ip(&mut self) {
+        self.fallback_ip.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_values(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.values = v;
+    pub fn set_fallback_ip(&mut self, v: ::std::string::String) {
+        self.fallback_ip = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_values(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.values
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_fallback_ip(&mut self) -> &mut ::std::string::String {
+        &mut self.fallback_ip
     }
 
     // Take field
-    pub fn take_values(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.values, ::protobuf::RepeatedField::new())
+    pub fn take_fallback_ip(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.fallback_ip, ::std::string::String::new())
     }
-}
 
-impl ::protobuf::Message for DNS_IPs {
-    fn is_initialized(&self) -> bool {
-        true
-    }
+    // repeated .DNS.Server_bootstrapsEntry server_bootstraps = 10;
 
-    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        while !is.eof()? {
-            let (field_number, wire_type) = is.read_tag_unpack()?;
-            match field_number {
-                1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.values)?;
-                },
-                _ => {
-                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
-                },
-            };
-        }
-        ::std::result::Result::Ok(())
-    }
 
-    // Compute sizes of nested messages
-    #[allow(unused_variables)]
-    fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        for value in &self.values {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
-        self.cached_size.set(my_size);
-        my_size
+    pub fn get_server_bootstraps(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.server_bootstraps
     }
-
-    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.values {
-            os.write_string(1, &v)?;
-        };
-        os.write_unknown_fields(self.get_unknown_fields())?;
-        ::std::result::Result::Ok(())
+    pub fn clear_server_bootstraps(&mut self) {
+        self.server_bootstraps.clear();
     }
 
-    fn get_cached_size(&self) -> u32 {
-        self.cached_size.get()
+    // Param is passed by value, moved
+    pub fn set_server_bootstraps(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.server_bootstraps = v;
     }
 
-    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
-        &self.unknown_fields
+    // Mutable pointer to the field.
+    pub fn mut_server_bootstraps(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.server_bootstraps
     }
 
-    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
-        &mut self.unknown_fields
+    // Take field
+    pub fn take_server_bootstraps(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.server_bootstraps, ::std::collections::HashMap::new())
     }
 
-    fn as_any(&self) -> &dyn (::std::any::Any) {
-        self as &dyn (::std::any::Any)
-    }
-    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
-        self as &mut dyn (::std::any::Any)
+    // uint32 dot_idle_timeout = 11;
+
+
+    pub fn get_dot_idle_timeout(&self) -> u32 {
+        self.dot_idle_timeout
     }
-    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
-        self
+    pub fn clear_dot_idle_timeout(&mut self) {
+        self.dot_idle_timeout = 0;
     }
 
-    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
-        Self::descriptor_static()
+    // Param is passed by value, moved
+    pub fn set_dot_idle_timeout(&mut self, v: u32) {
+        self.dot_idle_timeout = v;
     }
 
-    fn new() -> DNS_IPs {
-        DNS_IPs::new()
-    }
+    // repeated .DNS.Server_ca_filesEntry server_ca_files = 12;
 
-    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
-        descriptor.get(|| {
-            let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "values",
-                |m: &DNS_IPs| { &m.values },
-                |m: &mut DNS_IPs| { &mut m.values },
-            ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DNS_IPs>(
-                "DNS.IPs",
-                fields,
-                file_descriptor_proto()
-            )
-        })
-    }
 
-    fn default_instance() -> &'static DNS_IPs {
-        static instance: ::protobuf::rt::LazyV2<DNS_IPs> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(DNS_IPs::new)
+    pub fn get_server_ca_files(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.server_ca_files
+    }
+    pub fn clear_server_ca_files(&mut self) {
+        self.server_ca_files.clear();
     }
-}
 
-impl ::protobuf::Clear for DNS_IPs {
-    fn clear(&mut self) {
-        self.values.clear();
-        self.unknown_fields.clear();
+    // Param is passed by value, moved
+    pub fn set_server_ca_files(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.server_ca_files = v;
     }
-}
 
-impl ::std::fmt::Debug for DNS_IPs {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        ::protobuf::text_format::fmt(self, f)
+    // Mutable pointer to the field.
+    pub fn mut_server_ca_files(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.server_ca_files
     }
-}
 
-impl ::protobuf::reflect::ProtobufValue for DNS_IPs {
-    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Message(self)
+    // Take field
+    pub fn take_server_ca_files(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.server_ca_files, ::std::collections::HashMap::new())
     }
-}
 
-#[derive(PartialEq,Clone,Default)]
+    // uint32 cache_size = 13;
+
+
+    pub fn get_cache_size(&self) -> u32 {
+        self.cache_size
+    }
+    pub fn clear_cache_size(&mut self) {
+        self.cache_size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cache_size(&mut self, v: u32) {
+        self.cache_size = v;
+    }
+
+    // uint32 min_cache_ttl = 14;
+
+
+    pub fn get_min_cache_ttl(&self) -> u32 {
+        self.min_cache_ttl
+    }
+    pub fn clear_min_cache_ttl(&mut self) {
+        self.min_cache_ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_min_cache_ttl(&mut self, v: u32) {
+        self.min_cache_ttl = v;
+    }
+
+    // uint32 max_cache_ttl = 15;
+
+
+    pub fn get_max_cache_ttl(&self) -> u32 {
+        self.max_cache_ttl
+    }
+    pub fn clear_max_cache_ttl(&mut self) {
+        self.max_cache_ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_max_cache_ttl(&mut self, v: u32) {
+        self.max_cache_ttl = v;
+    }
+
+    // uint32 negative_cache_ttl = 16;
+
+
+    pub fn get_negative_cache_ttl(&self) -> u32 {
+        self.negative_cache_ttl
+    }
+    pub fn clear_negative_cache_ttl(&mut self) {
+        self.negative_cache_ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_negative_cache_ttl(&mut self, v: u32) {
+        self.negative_cache_ttl = v;
+    }
+
+    // string outbound = 17;
+
+
+    pub fn get_outbound(&self) -> &str {
+        &self.outbound
+    }
+    pub fn clear_outbound(&mut self) {
+        self.outbound.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_outbound(&mut self, v: ::std::string::String) {
+        self.outbound = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_outbound(&mut self) -> &mut ::std::string::String {
+        &mut self.outbound
+    }
+
+    // Take field
+    pub fn take_outbound(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.outbound, ::std::string::String::new())
+    }
+
+    // .DnsStrategy strategy = 18;
+
+
+    pub fn get_strategy(&self) -> DnsStrategy {
+        self.strategy
+    }
+    pub fn clear_strategy(&mut self) {
+        self.strategy = DnsStrategy::DEFAULT;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_strategy(&mut self, v: DnsStrategy) {
+        self.strategy = v;
+    }
+
+    // repeated .DNS.GroupsEntry groups = 19;
+
+
+    pub fn get_groups(&self) -> &::std::collections::HashMap<::std::string::String, DNS_Servers> {
+        &self.groups
+    }
+    pub fn clear_groups(&mut self) {
+        self.groups.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_groups(&mut self, v: ::std::collections::HashMap<::std::string::String, DNS_Servers>) {
+        self.groups = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_groups(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, DNS_Servers> {
+        &mut self.groups
+    }
+
+    // Take field
+    pub fn take_groups(&mut self) -> ::std::collections::HashMap<::std::string::String, DNS_Servers> {
+        ::std::mem::replace(&mut self.groups, ::std::collections::HashMap::new())
+    }
+
+    // repeated .DNS.Policy policies = 20;
+
+
+    pub fn get_policies(&self) -> &[DNS_Policy] {
+        &self.policies
+    }
+    pub fn clear_policies(&mut self) {
+        self.policies.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_policies(&mut self, v: ::protobuf::RepeatedField<DNS_Policy>) {
+        self.policies = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_policies(&mut self) -> &mut ::protobuf::RepeatedField<DNS_Policy> {
+        &mut self.policies
+    }
+
+    // Take field
+    pub fn take_policies(&mut self) -> ::protobuf::RepeatedField<DNS_Policy> {
+        ::std::mem::replace(&mut self.policies, ::protobuf::RepeatedField::new())
+    }
+
+    // uint32 prefetch = 21;
+
+
+    pub fn get_prefetch(&self) -> u32 {
+        self.prefetch
+    }
+    pub fn clear_prefetch(&mut self) {
+        self.prefetch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_prefetch(&mut self, v: u32) {
+        self.prefetch = v;
+    }
+
+    // uint32 prefetch_min_hits = 22;
+
+
+    pub fn get_prefetch_min_hits(&self) -> u32 {
+        self.prefetch_min_hits
+    }
+    pub fn clear_prefetch_min_hits(&mut self) {
+        self.prefetch_min_hits = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_prefetch_min_hits(&mut self, v: u32) {
+        self.prefetch_min_hits = v;
+    }
+
+    // string client_subnet = 23;
+
+
+    pub fn get_client_subnet(&self) -> &str {
+        &self.client_subnet
+    }
+    pub fn clear_client_subnet(&mut self) {
+        self.client_subnet.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_client_subnet(&mut self, v: ::std::string::String) {
+        self.client_subnet = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_client_subnet(&mut self) -> &mut ::std::string::String {
+        &mut self.client_subnet
+    }
+
+    // Take field
+    pub fn take_client_subnet(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.client_subnet, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for DNS {
+    fn is_initialized(&self) -> bool {
+        for v in &self.policies {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.servers)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.bind)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(wire_type, is, &mut self.hosts)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.filter_bogons = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.filter_private = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.internal_domains)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.server_outbounds)?;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.fail_open = tmp;
+                },
+                9 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fallback_ip)?;
+                },
+                10 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.server_bootstraps)?;
+                },
+                11 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.dot_idle_timeout = tmp;
+                },
+                12 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.server_ca_files)?;
+                },
+                13 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.cache_size = tmp;
+                },
+                14 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.min_cache_ttl = tmp;
+                },
+                15 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.max_cache_ttl = tmp;
+                },
+                16 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.negative_cache_ttl = tmp;
+                },
+                17 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.outbound)?;
+                },
+                18 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.strategy, 18, &mut self.unknown_fields)?
+                },
+                19 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_Servers>>(wire_type, is, &mut self.groups)?;
+                },
+                20 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.policies)?;
+                },
+                21 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.prefetch = tmp;
+                },
+                22 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.prefetch_min_hits = tmp;
+                },
+                23 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.client_subnet)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.servers {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if !self.bind.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.bind);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(3, &self.hosts);
+        if self.filter_bogons != false {
+            my_size += 2;
+        }
+        if self.filter_private != false {
+            my_size += 2;
+        }
+        for value in &self.internal_domains {
+            my_size += ::protobuf::rt::string_size(6, &value);
+        };
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(7, &self.server_outbounds);
+        if self.fail_open != false {
+            my_size += 2;
+        }
+        if !self.fallback_ip.is_empty() {
+            my_size += ::protobuf::rt::string_size(9, &self.fallback_ip);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(10, &self.server_bootstraps);
+        if self.dot_idle_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(11, self.dot_idle_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(12, &self.server_ca_files);
+        if self.cache_size != 0 {
+            my_size += ::protobuf::rt::value_size(13, self.cache_size, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.min_cache_ttl != 0 {
+            my_size += ::protobuf::rt::value_size(14, self.min_cache_ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.max_cache_ttl != 0 {
+            my_size += ::protobuf::rt::value_size(15, self.max_cache_ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.negative_cache_ttl != 0 {
+            my_size += ::protobuf::rt::value_size(16, self.negative_cache_ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.outbound.is_empty() {
+            my_size += ::protobuf::rt::string_size(17, &self.outbound);
+        }
+        if self.strategy != DnsStrategy::DEFAULT {
+            my_size += ::protobuf::rt::enum_size(18, self.strategy);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_Servers>>(19, &self.groups);
+        for value in &self.policies {
+            let len = value.compute_size();
+            my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if self.prefetch != 0 {
+            my_size += ::protobuf::rt::value_size(21, self.prefetch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.prefetch_min_hits != 0 {
+            my_size += ::protobuf::rt::value_size(22, self.prefetch_min_hits, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.client_subnet.is_empty() {
+            my_size += ::protobuf::rt::string_size(23, &self.client_subnet);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.servers {
+            os.write_string(1, &v)?;
+        };
+        if !self.bind.is_empty() {
+            os.write_string(2, &self.bind)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(3, &self.hosts, os)?;
+        if self.filter_bogons != false {
+            os.write_bool(4, self.filter_bogons)?;
+        }
+        if self.filter_private != false {
+            os.write_bool(5, self.filter_private)?;
+        }
+        for v in &self.internal_domains {
+            os.write_string(6, &v)?;
+        };
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(7, &self.server_outbounds, os)?;
+        if self.fail_open != false {
+            os.write_bool(8, self.fail_open)?;
+        }
+        if !self.fallback_ip.is_empty() {
+            os.write_string(9, &self.fallback_ip)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(10, &self.server_bootstraps, os)?;
+        if self.dot_idle_timeout != 0 {
+            os.write_uint32(11, self.dot_idle_timeout)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(12, &self.server_ca_files, os)?;
+        if self.cache_size != 0 {
+            os.write_uint32(13, self.cache_size)?;
+        }
+        if self.min_cache_ttl != 0 {
+            os.write_uint32(14, self.min_cache_ttl)?;
+        }
+        if self.max_cache_ttl != 0 {
+            os.write_uint32(15, self.max_cache_ttl)?;
+        }
+        if self.negative_cache_ttl != 0 {
+            os.write_uint32(16, self.negative_cache_ttl)?;
+        }
+        if !self.outbound.is_empty() {
+            os.write_string(17, &self.outbound)?;
+        }
+        if self.strategy != DnsStrategy::DEFAULT {
+            os.write_enum(18, ::protobuf::ProtobufEnum::value(&self.strategy))?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_Servers>>(19, &self.groups, os)?;
+        for v in &self.policies {
+            os.write_tag(20, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if self.prefetch != 0 {
+            os.write_uint32(21, self.prefetch)?;
+        }
+        if self.prefetch_min_hits != 0 {
+            os.write_uint32(22, self.prefetch_min_hits)?;
+        }
+        if !self.client_subnet.is_empty() {
+            os.write_string(23, &self.client_subnet)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DNS {
+        DNS::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "servers",
+                |m: &DNS| { &m.servers },
+                |m: &mut DNS| { &mut m.servers },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "bind",
+                |m: &DNS| { &m.bind },
+                |m: &mut DNS| { &mut m.bind },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_IPs>>(
+                "hosts",
+                |m: &DNS| { &m.hosts },
+                |m: &mut DNS| { &mut m.hosts },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "filter_bogons",
+                |m: &DNS| { &m.filter_bogons },
+                |m: &mut DNS| { &mut m.filter_bogons },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "filter_private",
+                |m: &DNS| { &m.filter_private },
+                |m: &mut DNS| { &mut m.filter_private },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "internal_domains",
+                |m: &DNS| { &m.internal_domains },
+                |m: &mut DNS| { &mut m.internal_domains },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "server_outbounds",
+                |m: &DNS| { &m.server_outbounds },
+                |m: &mut DNS| { &mut m.server_outbounds },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "fail_open",
+                |m: &DNS| { &m.fail_open },
+                |m: &mut DNS| { &mut m.fail_open },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fallback_ip",
+                |m: &DNS| { &m.fallback_ip },
+                |m: &mut DNS| { &mut m.fallback_ip },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "server_bootstraps",
+                |m: &DNS| { &m.server_bootstraps },
+                |m: &mut DNS| { &mut m.server_bootstraps },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "dot_idle_timeout",
+                |m: &DNS| { &m.dot_idle_timeout },
+                |m: &mut DNS| { &mut m.dot_idle_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "server_ca_files",
+                |m: &DNS| { &m.server_ca_files },
+                |m: &mut DNS| { &mut m.server_ca_files },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "cache_size",
+                |m: &DNS| { &m.cache_size },
+                |m: &mut DNS| { &mut m.cache_size },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "min_cache_ttl",
+                |m: &DNS| { &m.min_cache_ttl },
+                |m: &mut DNS| { &mut m.min_cache_ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "max_cache_ttl",
+                |m: &DNS| { &m.max_cache_ttl },
+                |m: &mut DNS| { &mut m.max_cache_ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "negative_cache_ttl",
+                |m: &DNS| { &m.negative_cache_ttl },
+                |m: &mut DNS| { &mut m.negative_cache_ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "outbound",
+                |m: &DNS| { &m.outbound },
+                |m: &mut DNS| { &mut m.outbound },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<DnsStrategy>>(
+                "strategy",
+                |m: &DNS| { &m.strategy },
+                |m: &mut DNS| { &mut m.strategy },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<DNS_Servers>>(
+                "groups",
+                |m: &DNS| { &m.groups },
+                |m: &mut DNS| { &mut m.groups },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<DNS_Policy>>(
+                "policies",
+                |m: &DNS| { &m.policies },
+                |m: &mut DNS| { &mut m.policies },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "prefetch",
+                |m: &DNS| { &m.prefetch },
+                |m: &mut DNS| { &mut m.prefetch },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "prefetch_min_hits",
+                |m: &DNS| { &m.prefetch_min_hits },
+                |m: &mut DNS| { &mut m.prefetch_min_hits },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "client_subnet",
+                |m: &DNS| { &m.client_subnet },
+                |m: &mut DNS| { &mut m.client_subnet },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DNS>(
+                "DNS",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static DNS {
+        static instance: ::protobuf::rt::LazyV2<DNS> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(DNS::new)
+    }
+}
+
+impl ::protobuf::Clear for DNS {
+    fn clear(&mut self) {
+        self.servers.clear();
+        self.bind.clear();
+        self.hosts.clear();
+        self.filter_bogons = false;
+        self.filter_private = false;
+        self.internal_domains.clear();
+        self.server_outbounds.clear();
+        self.fail_open = false;
+        self.fallback_ip.clear();
+        self.server_bootstraps.clear();
+        self.dot_idle_timeout = 0;
+        self.server_ca_files.clear();
+        self.cache_size = 0;
+        self.min_cache_ttl = 0;
+        self.max_cache_ttl = 0;
+        self.negative_cache_ttl = 0;
+        self.outbound.clear();
+        self.strategy = DnsStrategy::DEFAULT;
+        self.groups.clear();
+        self.policies.clear();
+        self.prefetch = 0;
+        self.prefetch_min_hits = 0;
+        self.client_subnet.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DNS {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DNS {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DNS_IPs {
+    // message fields
+    pub values: ::protobuf::RepeatedField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DNS_IPs {
+    fn default() -> &'a DNS_IPs {
+        <DNS_IPs as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DNS_IPs {
+    pub fn new() -> DNS_IPs {
+        ::std::default::Default::default()
+    }
+
+    // repeated string values = 1;
+
+
+    pub fn get_values(&self) -> &[::std::string::String] {
+        &self.values
+    }
+    pub fn clear_values(&mut self) {
+        self.values.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_values(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.values = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_values(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.values
+    }
+
+    // Take field
+    pub fn take_values(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.values, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for DNS_IPs {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.values)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.values {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.values {
+            os.write_string(1, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DNS_IPs {
+        DNS_IPs::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "values",
+                |m: &DNS_IPs| { &m.values },
+                |m: &mut DNS_IPs| { &mut m.values },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DNS_IPs>(
+                "DNS.IPs",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static DNS_IPs {
+        static instance: ::protobuf::rt::LazyV2<DNS_IPs> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(DNS_IPs::new)
+    }
+}
+
+impl ::protobuf::Clear for DNS_IPs {
+    fn clear(&mut self) {
+        self.values.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DNS_IPs {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DNS_IPs {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DNS_Servers {
+    // message fields
+    pub values: ::protobuf::RepeatedField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DNS_Servers {
+    fn default() -> &'a DNS_Servers {
+        <DNS_Servers as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DNS_Servers {
+    pub fn new() -> DNS_Servers {
+        ::std::default::Default::default()
+    }
+
+    // repeated string values = 1;
+
+
+    pub fn get_values(&self) -> &[::std::string::String] {
+        &self.values
+    }
+    pub fn clear_values(&mut self) {
+        self.values.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_values(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.values = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_values(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.values
+    }
+
+    // Take field
+    pub fn take_values(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.values, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for DNS_Servers {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.values)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.values {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.values {
+            os.write_string(1, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DNS_Servers {
+        DNS_Servers::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "values",
+                |m: &DNS_Servers| { &m.values },
+                |m: &mut DNS_Servers| { &mut m.values },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DNS_Servers>(
+                "DNS.Servers",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static DNS_Servers {
+        static instance: ::protobuf::rt::LazyV2<DNS_Servers> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(DNS_Servers::new)
+    }
+}
+
+impl ::protobuf::Clear for DNS_Servers {
+    fn clear(&mut self) {
+        self.values.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DNS_Servers {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DNS_Servers {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DNS_Policy {
+    // message fields
+    pub domains: ::protobuf::RepeatedField<RoutingRule_Domain>,
+    pub group: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DNS_Policy {
+    fn default() -> &'a DNS_Policy {
+        <DNS_Policy as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DNS_Policy {
+    pub fn new() -> DNS_Policy {
+        ::std::default::Default::default()
+    }
+
+    // repeated .RoutingRule.Domain domains = 1;
+
+
+    pub fn get_domains(&self) -> &[RoutingRule_Domain] {
+        &self.domains
+    }
+    pub fn clear_domains(&mut self) {
+        self.domains.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_domains(&mut self, v: ::protobuf::RepeatedField<RoutingRule_Domain>) {
+        self.domains = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_domains(&mut self) -> &mut ::protobuf::RepeatedField<RoutingRule_Domain> {
+        &mut self.domains
+    }
+
+    // Take field
+    pub fn take_domains(&mut self) -> ::protobuf::RepeatedField<RoutingRule_Domain> {
+        ::std::mem::replace(&mut self.domains, ::protobuf::RepeatedField::new())
+    }
+
+    // string group = 2;
+
+
+    pub fn get_group(&self) -> &str {
+        &self.group
+    }
+    pub fn clear_group(&mut self) {
+        self.group.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_group(&mut self, v: ::std::string::String) {
+        self.group = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_group(&mut self) -> &mut ::std::string::String {
+        &mut self.group
+    }
+
+    // Take field
+    pub fn take_group(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.group, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for DNS_Policy {
+    fn is_initialized(&self) -> bool {
+        for v in &self.domains {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.domains)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.group)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.domains {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if !self.group.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.group);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.domains {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if !self.group.is_empty() {
+            os.write_string(2, &self.group)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DNS_Policy {
+        DNS_Policy::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<RoutingRule_Domain>>(
+                "domains",
+                |m: &DNS_Policy| { &m.domains },
+                |m: &mut DNS_Policy| { &mut m.domains },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "group",
+                |m: &DNS_Policy| { &m.group },
+                |m: &mut DNS_Policy| { &mut m.group },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DNS_Policy>(
+                "DNS.Policy",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static DNS_Policy {
+        static instance: ::protobuf::rt::LazyV2<DNS_Policy> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(DNS_Policy::new)
+    }
+}
+
+impl ::protobuf::Clear for DNS_Policy {
+    fn clear(&mut self) {
+        self.domains.clear();
+        self.group.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DNS_Policy {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DNS_Policy {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Log {
     // message fields
-    pub level: Log_Level,
-    pub output: Log_Output,
-    pub output_file: ::std::string::String,
+    pub level: Log_Level,
+    pub output: Log_Output,
+    pub output_file: ::std::string::String,
+    pub format: Log_Format,
+    pub disable_timestamp: bool,
+    pub disable_color: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Log {
+    fn default() -> &'a Log {
+        <Log as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Log {
+    pub fn new() -> Log {
+        ::std::default::Default::default()
+    }
+
+    // .Log.Level level = 1;
+
+
+    pub fn get_level(&self) -> Log_Level {
+        self.level
+    }
+    pub fn clear_level(&mut self) {
+        self.level = Log_Level::TRACE;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_level(&mut self, v: Log_Level) {
+        self.level = v;
+    }
+
+    // .Log.Output output = 2;
+
+
+    pub fn get_output(&self) -> Log_Output {
+        self.output
+    }
+    pub fn clear_output(&mut self) {
+        self.output = Log_Output::CONSOLE;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_output(&mut self, v: Log_Output) {
+        self.output = v;
+    }
+
+    // string output_file = 3;
+
+
+    pub fn get_output_file(&self) -> &str {
+        &self.output_file
+    }
+    pub fn clear_output_file(&mut self) {
+        self.output_file.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_output_file(&mut self, v: ::std::string::String) {
+        self.output_file = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_output_file(&mut self) -> &mut ::std::string::String {
+        &mut self.output_file
+    }
+
+    // Take field
+    pub fn take_output_file(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.output_file, ::std::string::String::new())
+    }
+
+    // .Log.Format format = 4;
+
+
+    pub fn get_format(&self) -> Log_Format {
+        self.format
+    }
+    pub fn clear_format(&mut self) {
+        self.format = Log_Format::DEFAULT;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_format(&mut self, v: Log_Format) {
+        self.format = v;
+    }
+
+    // bool disable_timestamp = 5;
+
+
+    pub fn get_disable_timestamp(&self) -> bool {
+        self.disable_timestamp
+    }
+    pub fn clear_disable_timestamp(&mut self) {
+        self.disable_timestamp = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_disable_timestamp(&mut self, v: bool) {
+        self.disable_timestamp = v;
+    }
+
+    // bool disable_color = 6;
+
+
+    pub fn get_disable_color(&self) -> bool {
+        self.disable_color
+    }
+    pub fn clear_disable_color(&mut self) {
+        self.disable_color = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_disable_color(&mut self, v: bool) {
+        self.disable_color = v;
+    }
+}
+
+impl ::protobuf::Message for Log {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.level, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.output, 2, &mut self.unknown_fields)?
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.output_file)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.format, 4, &mut self.unknown_fields)?
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.disable_timestamp = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.disable_color = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.level != Log_Level::TRACE {
+            my_size += ::protobuf::rt::enum_size(1, self.level);
+        }
+        if self.output != Log_Output::CONSOLE {
+            my_size += ::protobuf::rt::enum_size(2, self.output);
+        }
+        if !self.output_file.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.output_file);
+        }
+        if self.format != Log_Format::DEFAULT {
+            my_size += ::protobuf::rt::enum_size(4, self.format);
+        }
+        if self.disable_timestamp != false {
+            my_size += 2;
+        }
+        if self.disable_color != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.level != Log_Level::TRACE {
+            os.write_enum(1, ::protobuf::ProtobufEnum::value(&self.level))?;
+        }
+        if self.output != Log_Output::CONSOLE {
+            os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.output))?;
+        }
+        if !self.output_file.is_empty() {
+            os.write_string(3, &self.output_file)?;
+        }
+        if self.format != Log_Format::DEFAULT {
+            os.write_enum(4, ::protobuf::ProtobufEnum::value(&self.format))?;
+        }
+        if self.disable_timestamp != false {
+            os.write_bool(5, self.disable_timestamp)?;
+        }
+        if self.disable_color != false {
+            os.write_bool(6, self.disable_color)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Log {
+        Log::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Log_Level>>(
+                "level",
+                |m: &Log| { &m.level },
+                |m: &mut Log| { &mut m.level },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Log_Output>>(
+                "output",
+                |m: &Log| { &m.output },
+                |m: &mut Log| { &mut m.output },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "output_file",
+                |m: &Log| { &m.output_file },
+                |m: &mut Log| { &mut m.output_file },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Log_Format>>(
+                "format",
+                |m: &Log| { &m.format },
+                |m: &mut Log| { &mut m.format },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "disable_timestamp",
+                |m: &Log| { &m.disable_timestamp },
+                |m: &mut Log| { &mut m.disable_timestamp },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "disable_color",
+                |m: &Log| { &m.disable_color },
+                |m: &mut Log| { &mut m.disable_color },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Log>(
+                "Log",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Log {
+        static instance: ::protobuf::rt::LazyV2<Log> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Log::new)
+    }
+}
+
+impl ::protobuf::Clear for Log {
+    fn clear(&mut self) {
+        self.level = Log_Level::TRACE;
+        self.output = Log_Output::CONSOLE;
+        self.output_file.clear();
+        self.format = Log_Format::DEFAULT;
+        self.disable_timestamp = false;
+        self.disable_color = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Log {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Log {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Log_Level {
+    TRACE = 0,
+    DEBUG = 1,
+    INFO = 2,
+    WARN = 3,
+    ERROR = 4,
+}
+
+impl ::protobuf::ProtobufEnum for Log_Level {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Log_Level> {
+        match value {
+            0 => ::std::option::Option::Some(Log_Level::TRACE),
+            1 => ::std::option::Option::Some(Log_Level::DEBUG),
+            2 => ::std::option::Option::Some(Log_Level::INFO),
+            3 => ::std::option::Option::Some(Log_Level::WARN),
+            4 => ::std::option::Option::Some(Log_Level::ERROR),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Log_Level] = &[
+            Log_Level::TRACE,
+            Log_Level::DEBUG,
+            Log_Level::INFO,
+            Log_Level::WARN,
+            Log_Level::ERROR,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Log_Level>("Log.Level", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for Log_Level {
+}
+
+impl ::std::default::Default for Log_Level {
+    fn default() -> Self {
+        Log_Level::TRACE
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Log_Level {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Log_Output {
+    CONSOLE = 0,
+    FILE = 1,
+}
+
+impl ::protobuf::ProtobufEnum for Log_Output {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Log_Output> {
+        match value {
+            0 => ::std::option::Option::Some(Log_Output::CONSOLE),
+            1 => ::std::option::Option::Some(Log_Output::FILE),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Log_Output] = &[
+            Log_Output::CONSOLE,
+            Log_Output::FILE,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Log_Output>("Log.Output", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for Log_Output {
+}
+
+impl ::std::default::Default for Log_Output {
+    fn default() -> Self {
+        Log_Output::CONSOLE
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Log_Output {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Log_Format {
+    DEFAULT = 0,
+    PLAIN = 1,
+    JSON = 2,
+}
+
+impl ::protobuf::ProtobufEnum for Log_Format {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Log_Format> {
+        match value {
+            0 => ::std::option::Option::Some(Log_Format::DEFAULT),
+            1 => ::std::option::Option::Some(Log_Format::PLAIN),
+            2 => ::std::option::Option::Some(Log_Format::JSON),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Log_Format] = &[
+            Log_Format::DEFAULT,
+            Log_Format::PLAIN,
+            Log_Format::JSON,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Log_Format>("Log.Format", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for Log_Format {
+}
+
+impl ::std::default::Default for Log_Format {
+    fn default() -> Self {
+        Log_Format::DEFAULT
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Log_Format {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct TUNInboundSettings {
+    // message fields
+    pub fd: i32,
+    pub name: ::std::string::String,
+    pub address: ::std::string::String,
+    pub gateway: ::std::string::String,
+    pub netmask: ::std::string::String,
+    pub mtu: i32,
+    pub fake_dns_exclude: ::protobuf::RepeatedField<::std::string::String>,
+    pub fake_dns_include: ::protobuf::RepeatedField<::std::string::String>,
+    pub fake_dns_routing_aware: bool,
+    pub fake_dns_persist_path: ::std::string::String,
+    pub fake_dns_persist_ttl: u32,
+    pub fake_dns_ipv6_range: ::std::string::String,
+    pub fake_dns_range: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a TUNInboundSettings {
+    fn default() -> &'a TUNInboundSettings {
+        <TUNInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl TUNInboundSettings {
+    pub fn new() -> TUNInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // int32 fd = 1;
+
+
+    pub fn get_fd(&self) -> i32 {
+        self.fd
+    }
+    pub fn clear_fd(&mut self) {
+        self.fd = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fd(&mut self, v: i32) {
+        self.fd = v;
+    }
+
+    // string name = 2;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.name, ::std::string::String::new())
+    }
+
+    // string address = 3;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // string gateway = 4;
+
+
+    pub fn get_gateway(&self) -> &str {
+        &self.gateway
+    }
+    pub fn clear_gateway(&mut self) {
+        self.gateway.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_gateway(&mut self, v: ::std::string::String) {
+        self.gateway = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_gateway(&mut self) -> &mut ::std::string::String {
+        &mut self.gateway
+    }
+
+    // Take field
+    pub fn take_gateway(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.gateway, ::std::string::String::new())
+    }
+
+    // string netmask = 5;
+
+
+    pub fn get_netmask(&self) -> &str {
+        &self.netmask
+    }
+    pub fn clear_netmask(&mut self) {
+        self.netmask.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_netmask(&mut self, v: ::std::string::String) {
+        self.netmask = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_netmask(&mut self) -> &mut ::std::string::String {
+        &mut self.netmask
+    }
+
+    // Take field
+    pub fn take_netmask(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.netmask, ::std::string::String::new())
+    }
+
+    // int32 mtu = 6;
+
+
+    pub fn get_mtu(&self) -> i32 {
+        self.mtu
+    }
+    pub fn clear_mtu(&mut self) {
+        self.mtu = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_mtu(&mut self, v: i32) {
+        self.mtu = v;
+    }
+
+    // repeated string fake_dns_exclude = 7;
+
+
+    pub fn get_fake_dns_exclude(&self) -> &[::std::string::String] {
+        &self.fake_dns_exclude
+    }
+    pub fn clear_fake_dns_exclude(&mut self) {
+        self.fake_dns_exclude.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_exclude(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.fake_dns_exclude = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_fake_dns_exclude(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.fake_dns_exclude
+    }
+
+    // Take field
+    pub fn take_fake_dns_exclude(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.fake_dns_exclude, ::protobuf::RepeatedField::new())
+    }
+
+    // repeated string fake_dns_include = 8;
+
+
+    pub fn get_fake_dns_include(&self) -> &[::std::string::String] {
+        &self.fake_dns_include
+    }
+    pub fn clear_fake_dns_include(&mut self) {
+        self.fake_dns_include.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_include(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.fake_dns_include = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_fake_dns_include(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.fake_dns_include
+    }
+
+    // Take field
+    pub fn take_fake_dns_include(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.fake_dns_include, ::protobuf::RepeatedField::new())
+    }
+
+    // bool fake_dns_routing_aware = 9;
+
+
+    pub fn get_fake_dns_routing_aware(&self) -> bool {
+        self.fake_dns_routing_aware
+    }
+    pub fn clear_fake_dns_routing_aware(&mut self) {
+        self.fake_dns_routing_aware = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_routing_aware(&mut self, v: bool) {
+        self.fake_dns_routing_aware = v;
+    }
+
+    // string fake_dns_persist_path = 10;
+
+
+    pub fn get_fake_dns_persist_path(&self) -> &str {
+        &self.fake_dns_persist_path
+    }
+    pub fn clear_fake_dns_persist_path(&mut self) {
+        self.fake_dns_persist_path.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_persist_path(&mut self, v: ::std::string::String) {
+        self.fake_dns_persist_path = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_fake_dns_persist_path(&mut self) -> &mut ::std::string::String {
+        &mut self.fake_dns_persist_path
+    }
+
+    // Take field
+    pub fn take_fake_dns_persist_path(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.fake_dns_persist_path, ::std::string::String::new())
+    }
+
+    // uint32 fake_dns_persist_ttl = 11;
+
+
+    pub fn get_fake_dns_persist_ttl(&self) -> u32 {
+        self.fake_dns_persist_ttl
+    }
+    pub fn clear_fake_dns_persist_ttl(&mut self) {
+        self.fake_dns_persist_ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_persist_ttl(&mut self, v: u32) {
+        self.fake_dns_persist_ttl = v;
+    }
+
+    // string fake_dns_ipv6_range = 12;
+
+
+    pub fn get_fake_dns_ipv6_range(&self) -> &str {
+        &self.fake_dns_ipv6_range
+    }
+    pub fn clear_fake_dns_ipv6_range(&mut self) {
+        self.fake_dns_ipv6_range.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_ipv6_range(&mut self, v: ::std::string::String) {
+        self.fake_dns_ipv6_range = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_fake_dns_ipv6_range(&mut self) -> &mut ::std::string::String {
+        &mut self.fake_dns_ipv6_range
+    }
+
+    // Take field
+    pub fn take_fake_dns_ipv6_range(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.fake_dns_ipv6_range, ::std::string::String::new())
+    }
+
+    // string fake_dns_range = 13;
+
+
+    pub fn get_fake_dns_range(&self) -> &str {
+        &self.fake_dns_range
+    }
+    pub fn clear_fake_dns_range(&mut self) {
+        self.fake_dns_range.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fake_dns_range(&mut self, v: ::std::string::String) {
+        self.fake_dns_range = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_fake_dns_range(&mut self) -> &mut ::std::string::String {
+        &mut self.fake_dns_range
+    }
+
+    // Take field
+    pub fn take_fake_dns_range(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.fake_dns_range, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for TUNInboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.fd = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.gateway)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.netmask)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.mtu = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.fake_dns_exclude)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.fake_dns_include)?;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.fake_dns_routing_aware = tmp;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fake_dns_persist_path)?;
+                },
+                11 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.fake_dns_persist_ttl = tmp;
+                },
+                12 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fake_dns_ipv6_range)?;
+                },
+                13 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fake_dns_range)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.fd != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.fd, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.name);
+        }
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.address);
+        }
+        if !self.gateway.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.gateway);
+        }
+        if !self.netmask.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.netmask);
+        }
+        if self.mtu != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.mtu, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.fake_dns_exclude {
+            my_size += ::protobuf::rt::string_size(7, &value);
+        };
+        for value in &self.fake_dns_include {
+            my_size += ::protobuf::rt::string_size(8, &value);
+        };
+        if self.fake_dns_routing_aware != false {
+            my_size += 2;
+        }
+        if !self.fake_dns_persist_path.is_empty() {
+            my_size += ::protobuf::rt::string_size(10, &self.fake_dns_persist_path);
+        }
+        if self.fake_dns_persist_ttl != 0 {
+            my_size += ::protobuf::rt::value_size(11, self.fake_dns_persist_ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.fake_dns_ipv6_range.is_empty() {
+            my_size += ::protobuf::rt::string_size(12, &self.fake_dns_ipv6_range);
+        }
+        if !self.fake_dns_range.is_empty() {
+            my_size += ::protobuf::rt::string_size(13, &self.fake_dns_range);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.fd != 0 {
+            os.write_int32(1, self.fd)?;
+        }
+        if !self.name.is_empty() {
+            os.write_string(2, &self.name)?;
+        }
+        if !self.address.is_empty() {
+            os.write_string(3, &self.address)?;
+        }
+        if !self.gateway.is_empty() {
+            os.write_string(4, &self.gateway)?;
+        }
+        if !self.netmask.is_empty() {
+            os.write_string(5, &self.netmask)?;
+        }
+        if self.mtu != 0 {
+            os.write_int32(6, self.mtu)?;
+        }
+        for v in &self.fake_dns_exclude {
+            os.write_string(7, &v)?;
+        };
+        for v in &self.fake_dns_include {
+            os.write_string(8, &v)?;
+        };
+        if self.fake_dns_routing_aware != false {
+            os.write_bool(9, self.fake_dns_routing_aware)?;
+        }
+        if !self.fake_dns_persist_path.is_empty() {
+            os.write_string(10, &self.fake_dns_persist_path)?;
+        }
+        if self.fake_dns_persist_ttl != 0 {
+            os.write_uint32(11, self.fake_dns_persist_ttl)?;
+        }
+        if !self.fake_dns_ipv6_range.is_empty() {
+            os.write_string(12, &self.fake_dns_ipv6_range)?;
+        }
+        if !self.fake_dns_range.is_empty() {
+            os.write_string(13, &self.fake_dns_range)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> TUNInboundSettings {
+        TUNInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                "fd",
+                |m: &TUNInboundSettings| { &m.fd },
+                |m: &mut TUNInboundSettings| { &mut m.fd },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "name",
+                |m: &TUNInboundSettings| { &m.name },
+                |m: &mut TUNInboundSettings| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &TUNInboundSettings| { &m.address },
+                |m: &mut TUNInboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "gateway",
+                |m: &TUNInboundSettings| { &m.gateway },
+                |m: &mut TUNInboundSettings| { &mut m.gateway },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "netmask",
+                |m: &TUNInboundSettings| { &m.netmask },
+                |m: &mut TUNInboundSettings| { &mut m.netmask },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                "mtu",
+                |m: &TUNInboundSettings| { &m.mtu },
+                |m: &mut TUNInboundSettings| { &mut m.mtu },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fake_dns_exclude",
+                |m: &TUNInboundSettings| { &m.fake_dns_exclude },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_exclude },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fake_dns_include",
+                |m: &TUNInboundSettings| { &m.fake_dns_include },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_include },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "fake_dns_routing_aware",
+                |m: &TUNInboundSettings| { &m.fake_dns_routing_aware },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_routing_aware },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fake_dns_persist_path",
+                |m: &TUNInboundSettings| { &m.fake_dns_persist_path },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_persist_path },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "fake_dns_persist_ttl",
+                |m: &TUNInboundSettings| { &m.fake_dns_persist_ttl },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_persist_ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fake_dns_ipv6_range",
+                |m: &TUNInboundSettings| { &m.fake_dns_ipv6_range },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_ipv6_range },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fake_dns_range",
+                |m: &TUNInboundSettings| { &m.fake_dns_range },
+                |m: &mut TUNInboundSettings| { &mut m.fake_dns_range },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TUNInboundSettings>(
+                "TUNInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static TUNInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<TUNInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(TUNInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for TUNInboundSettings {
+    fn clear(&mut self) {
+        self.fd = 0;
+        self.name.clear();
+        self.address.clear();
+        self.gateway.clear();
+        self.netmask.clear();
+        self.mtu = 0;
+        self.fake_dns_exclude.clear();
+        self.fake_dns_include.clear();
+        self.fake_dns_routing_aware = false;
+        self.fake_dns_persist_path.clear();
+        self.fake_dns_persist_ttl = 0;
+        self.fake_dns_ipv6_range.clear();
+        self.fake_dns_range.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for TUNInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for TUNInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct HttpInboundSettings {
+    // message fields
+    pub username: ::std::string::String,
+    pub password: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a HttpInboundSettings {
+    fn default() -> &'a HttpInboundSettings {
+        <HttpInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl HttpInboundSettings {
+    pub fn new() -> HttpInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string username = 1;
+
+
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn clear_username(&mut self) {
+        self.username.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_username(&mut self, v: ::std::string::String) {
+        self.username = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_username(&mut self) -> &mut ::std::string::String {
+        &mut self.username
+    }
+
+    // Take field
+    pub fn take_username(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.username, ::std::string::String::new())
+    }
+
+    // string password = 2;
+
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+    pub fn clear_password(&mut self) {
+        self.password.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_password(&mut self, v: ::std::string::String) {
+        self.password = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_password(&mut self) -> &mut ::std::string::String {
+        &mut self.password
+    }
+
+    // Take field
+    pub fn take_password(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for HttpInboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.username)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.username.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.username);
+        }
+        if !self.password.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.password);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.username.is_empty() {
+            os.write_string(1, &self.username)?;
+        }
+        if !self.password.is_empty() {
+            os.write_string(2, &self.password)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> HttpInboundSettings {
+        HttpInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "username",
+                |m: &HttpInboundSettings| { &m.username },
+                |m: &mut HttpInboundSettings| { &mut m.username },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "password",
+                |m: &HttpInboundSettings| { &m.password },
+                |m: &mut HttpInboundSettings| { &mut m.password },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HttpInboundSettings>(
+                "HttpInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static HttpInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<HttpInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(HttpInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for HttpInboundSettings {
+    fn clear(&mut self) {
+        self.username.clear();
+        self.password.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for HttpInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HttpInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct SocksInboundSettings {
+    // message fields
+    pub udp_bind: ::std::string::String,
+    pub udp_port_range: ::std::string::String,
+    pub udp_advertise: ::std::string::String,
+    pub username: ::std::string::String,
+    pub password: ::std::string::String,
+    pub allow_no_auth: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a SocksInboundSettings {
+    fn default() -> &'a SocksInboundSettings {
+        <SocksInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SocksInboundSettings {
+    pub fn new() -> SocksInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string udp_bind = 1;
+
+
+    pub fn get_udp_bind(&self) -> &str {
+        &self.udp_bind
+    }
+    pub fn clear_udp_bind(&mut self) {
+        self.udp_bind.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_bind(&mut self, v: ::std::string::String) {
+        self.udp_bind = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_udp_bind(&mut self) -> &mut ::std::string::String {
+        &mut self.udp_bind
+    }
+
+    // Take field
+    pub fn take_udp_bind(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.udp_bind, ::std::string::String::new())
+    }
+
+    // string udp_port_range = 2;
+
+
+    pub fn get_udp_port_range(&self) -> &str {
+        &self.udp_port_range
+    }
+    pub fn clear_udp_port_range(&mut self) {
+        self.udp_port_range.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_port_range(&mut self, v: ::std::string::String) {
+        self.udp_port_range = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_udp_port_range(&mut self) -> &mut ::std::string::String {
+        &mut self.udp_port_range
+    }
+
+    // Take field
+    pub fn take_udp_port_range(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.udp_port_range, ::std::string::String::new())
+    }
+
+    // string udp_advertise = 3;
+
+
+    pub fn get_udp_advertise(&self) -> &str {
+        &self.udp_advertise
+    }
+    pub fn clear_udp_advertise(&mut self) {
+        self.udp_advertise.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_advertise(&mut self, v: ::std::string::String) {
+        self.udp_advertise = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_udp_advertise(&mut self) -> &mut ::std::string::String {
+        &mut self.udp_advertise
+    }
+
+    // Take field
+    pub fn take_udp_advertise(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.udp_advertise, ::std::string::String::new())
+    }
+
+    // string username = 4;
+
+
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn clear_username(&mut self) {
+        self.username.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_username(&mut self, v: ::std::string::String) {
+        self.username = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_username(&mut self) -> &mut ::std::string::String {
+        &mut self.username
+    }
+
+    // Take field
+    pub fn take_username(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.username, ::std::string::String::new())
+    }
+
+    // string password = 5;
+
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+    pub fn clear_password(&mut self) {
+        self.password.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_password(&mut self, v: ::std::string::String) {
+        self.password = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_password(&mut self) -> &mut ::std::string::String {
+        &mut self.password
+    }
+
+    // Take field
+    pub fn take_password(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    }
+
+    // bool allow_no_auth = 6;
+
+
+    pub fn get_allow_no_auth(&self) -> bool {
+        self.allow_no_auth
+    }
+    pub fn clear_allow_no_auth(&mut self) {
+        self.allow_no_auth = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_allow_no_auth(&mut self, v: bool) {
+        self.allow_no_auth = v;
+    }
+}
+
+impl ::protobuf::Message for SocksInboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.udp_bind)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.udp_port_range)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.udp_advertise)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.username)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.allow_no_auth = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.udp_bind.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.udp_bind);
+        }
+        if !self.udp_port_range.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.udp_port_range);
+        }
+        if !self.udp_advertise.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.udp_advertise);
+        }
+        if !self.username.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.username);
+        }
+        if !self.password.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.password);
+        }
+        if self.allow_no_auth != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.udp_bind.is_empty() {
+            os.write_string(1, &self.udp_bind)?;
+        }
+        if !self.udp_port_range.is_empty() {
+            os.write_string(2, &self.udp_port_range)?;
+        }
+        if !self.udp_advertise.is_empty() {
+            os.write_string(3, &self.udp_advertise)?;
+        }
+        if !self.username.is_empty() {
+            os.write_string(4, &self.username)?;
+        }
+        if !self.password.is_empty() {
+            os.write_string(5, &self.password)?;
+        }
+        if self.allow_no_auth != false {
+            os.write_bool(6, self.allow_no_auth)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> SocksInboundSettings {
+        SocksInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "udp_bind",
+                |m: &SocksInboundSettings| { &m.udp_bind },
+                |m: &mut SocksInboundSettings| { &mut m.udp_bind },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "udp_port_range",
+                |m: &SocksInboundSettings| { &m.udp_port_range },
+                |m: &mut SocksInboundSettings| { &mut m.udp_port_range },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "udp_advertise",
+                |m: &SocksInboundSettings| { &m.udp_advertise },
+                |m: &mut SocksInboundSettings| { &mut m.udp_advertise },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "username",
+                |m: &SocksInboundSettings| { &m.username },
+                |m: &mut SocksInboundSettings| { &mut m.username },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "password",
+                |m: &SocksInboundSettings| { &m.password },
+                |m: &mut SocksInboundSettings| { &mut m.password },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "allow_no_auth",
+                |m: &SocksInboundSettings| { &m.allow_no_auth },
+                |m: &mut SocksInboundSettings| { &mut m.allow_no_auth },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<SocksInboundSettings>(
+                "SocksInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static SocksInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<SocksInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(SocksInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for SocksInboundSettings {
+    fn clear(&mut self) {
+        self.udp_bind.clear();
+        self.udp_port_range.clear();
+        self.udp_advertise.clear();
+        self.username.clear();
+        self.password.clear();
+        self.allow_no_auth = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for SocksInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SocksInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct MixedInboundSettings {
+    // message fields
+    pub socks: ::protobuf::SingularPtrField<SocksInboundSettings>,
+    pub http: ::protobuf::SingularPtrField<HttpInboundSettings>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a MixedInboundSettings {
+    fn default() -> &'a MixedInboundSettings {
+        <MixedInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MixedInboundSettings {
+    pub fn new() -> MixedInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // .SocksInboundSettings socks = 1;
+
+
+    pub fn get_socks(&self) -> &SocksInboundSettings {
+        self.socks.as_ref().unwrap_or_else(|| <SocksInboundSettings as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_socks(&mut self) {
+        self.socks.clear();
+    }
+
+    pub fn has_socks(&self) -> bool {
+        self.socks.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_socks(&mut self, v: SocksInboundSettings) {
+        self.socks = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_socks(&mut self) -> &mut SocksInboundSettings {
+        if self.socks.is_none() {
+            self.socks.set_default();
+        }
+        self.socks.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_socks(&mut self) -> SocksInboundSettings {
+        self.socks.take().unwrap_or_else(|| SocksInboundSettings::new())
+    }
+
+    // .HttpInboundSettings http = 2;
+
+
+    pub fn get_http(&self) -> &HttpInboundSettings {
+        self.http.as_ref().unwrap_or_else(|| <HttpInboundSettings as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_http(&mut self) {
+        self.http.clear();
+    }
+
+    pub fn has_http(&self) -> bool {
+        self.http.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_http(&mut self, v: HttpInboundSettings) {
+        self.http = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_http(&mut self) -> &mut HttpInboundSettings {
+        if self.http.is_none() {
+            self.http.set_default();
+        }
+        self.http.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_http(&mut self) -> HttpInboundSettings {
+        self.http.take().unwrap_or_else(|| HttpInboundSettings::new())
+    }
+}
+
+impl ::protobuf::Message for MixedInboundSettings {
+    fn is_initialized(&self) -> bool {
+        for v in &self.socks {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.http {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.socks)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.http)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.socks.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.http.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.socks.as_ref() {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.http.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> MixedInboundSettings {
+        MixedInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<SocksInboundSettings>>(
+                "socks",
+                |m: &MixedInboundSettings| { &m.socks },
+                |m: &mut MixedInboundSettings| { &mut m.socks },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<HttpInboundSettings>>(
+                "http",
+                |m: &MixedInboundSettings| { &m.http },
+                |m: &mut MixedInboundSettings| { &mut m.http },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<MixedInboundSettings>(
+                "MixedInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static MixedInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<MixedInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(MixedInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for MixedInboundSettings {
+    fn clear(&mut self) {
+        self.socks.clear();
+        self.http.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for MixedInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MixedInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct TrojanInboundSettings {
+    // message fields
+    pub password: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a TrojanInboundSettings {
+    fn default() -> &'a TrojanInboundSettings {
+        <TrojanInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl TrojanInboundSettings {
+    pub fn new() -> TrojanInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string password = 3;
+
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+    pub fn clear_password(&mut self) {
+        self.password.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_password(&mut self, v: ::std::string::String) {
+        self.password = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_password(&mut self) -> &mut ::std::string::String {
+        &mut self.password
+    }
+
+    // Take field
+    pub fn take_password(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for TrojanInboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.password.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.password);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.password.is_empty() {
+            os.write_string(3, &self.password)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> TrojanInboundSettings {
+        TrojanInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "password",
+                |m: &TrojanInboundSettings| { &m.password },
+                |m: &mut TrojanInboundSettings| { &mut m.password },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TrojanInboundSettings>(
+                "TrojanInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static TrojanInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<TrojanInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(TrojanInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for TrojanInboundSettings {
+    fn clear(&mut self) {
+        self.password.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for TrojanInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for TrojanInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct WebSocketInboundSettings {
+    // message fields
+    pub path: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a WebSocketInboundSettings {
+    fn default() -> &'a WebSocketInboundSettings {
+        <WebSocketInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl WebSocketInboundSettings {
+    pub fn new() -> WebSocketInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string path = 1;
+
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+    pub fn clear_path(&mut self) {
+        self.path.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_path(&mut self, v: ::std::string::String) {
+        self.path = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_path(&mut self) -> &mut ::std::string::String {
+        &mut self.path
+    }
+
+    // Take field
+    pub fn take_path(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.path, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for WebSocketInboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.path.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.path);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.path.is_empty() {
+            os.write_string(1, &self.path)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> WebSocketInboundSettings {
+        WebSocketInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "path",
+                |m: &WebSocketInboundSettings| { &m.path },
+                |m: &mut WebSocketInboundSettings| { &mut m.path },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<WebSocketInboundSettings>(
+                "WebSocketInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static WebSocketInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<WebSocketInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(WebSocketInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for WebSocketInboundSettings {
+    fn clear(&mut self) {
+        self.path.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WebSocketInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WebSocketInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ChainInboundSettings {
+    // message fields
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a ChainInboundSettings {
+    fn default() -> &'a ChainInboundSettings {
+        <ChainInboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ChainInboundSettings {
+    pub fn new() -> ChainInboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // repeated string actors = 1;
+
+
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
+    }
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
+    }
+
+    // Take field
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for ChainInboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ChainInboundSettings {
+        ChainInboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &ChainInboundSettings| { &m.actors },
+                |m: &mut ChainInboundSettings| { &mut m.actors },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ChainInboundSettings>(
+                "ChainInboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static ChainInboundSettings {
+        static instance: ::protobuf::rt::LazyV2<ChainInboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(ChainInboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for ChainInboundSettings {
+    fn clear(&mut self) {
+        self.actors.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ChainInboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ChainInboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Inbound {
+    // message fields
+    pub tag: ::std::string::String,
+    pub protocol: ::std::string::String,
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub settings: ::std::vec::Vec<u8>,
+    pub rewrites: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub backlog: u32,
+    pub udp_session_timeout: u32,
+    pub addresses: ::protobuf::RepeatedField<::std::string::String>,
+    pub rate_limit: ::protobuf::SingularPtrField<RateLimit>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Inbound {
+    fn default() -> &'a Inbound {
+        <Inbound as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Inbound {
+    pub fn new() -> Inbound {
+        ::std::default::Default::default()
+    }
+
+    // string tag = 1;
+
+
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+    pub fn clear_tag(&mut self) {
+        self.tag.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tag(&mut self, v: ::std::string::String) {
+        self.tag = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_tag(&mut self) -> &mut ::std::string::String {
+        &mut self.tag
+    }
+
+    // Take field
+    pub fn take_tag(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.tag, ::std::string::String::new())
+    }
+
+    // string protocol = 2;
+
+
+    pub fn get_protocol(&self) -> &str {
+        &self.protocol
+    }
+    pub fn clear_protocol(&mut self) {
+        self.protocol.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_protocol(&mut self, v: ::std::string::String) {
+        self.protocol = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_protocol(&mut self) -> &mut ::std::string::String {
+        &mut self.protocol
+    }
+
+    // Take field
+    pub fn take_protocol(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.protocol, ::std::string::String::new())
+    }
+
+    // string address = 3;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 4;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+
+    // bytes settings = 5;
+
+
+    pub fn get_settings(&self) -> &[u8] {
+        &self.settings
+    }
+    pub fn clear_settings(&mut self) {
+        self.settings.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_settings(&mut self, v: ::std::vec::Vec<u8>) {
+        self.settings = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_settings(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.settings
+    }
+
+    // Take field
+    pub fn take_settings(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.settings, ::std::vec::Vec::new())
+    }
+
+    // repeated .Inbound.RewritesEntry rewrites = 6;
+
+
+    pub fn get_rewrites(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.rewrites
+    }
+    pub fn clear_rewrites(&mut self) {
+        self.rewrites.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rewrites(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.rewrites = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_rewrites(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.rewrites
+    }
+
+    // Take field
+    pub fn take_rewrites(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.rewrites, ::std::collections::HashMap::new())
+    }
+
+    // uint32 backlog = 7;
+
+
+    pub fn get_backlog(&self) -> u32 {
+        self.backlog
+    }
+    pub fn clear_backlog(&mut self) {
+        self.backlog = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_backlog(&mut self, v: u32) {
+        self.backlog = v;
+    }
+
+    // uint32 udp_session_timeout = 8;
+
+
+    pub fn get_udp_session_timeout(&self) -> u32 {
+        self.udp_session_timeout
+    }
+    pub fn clear_udp_session_timeout(&mut self) {
+        self.udp_session_timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_session_timeout(&mut self, v: u32) {
+        self.udp_session_timeout = v;
+    }
+
+    // repeated string addresses = 9;
+
+
+    pub fn get_addresses(&self) -> &[::std::string::String] {
+        &self.addresses
+    }
+    pub fn clear_addresses(&mut self) {
+        self.addresses.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_addresses(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.addresses = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_addresses(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.addresses
+    }
+
+    // Take field
+    pub fn take_addresses(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.addresses, ::protobuf::RepeatedField::new())
+    }
+
+    // .RateLimit rate_limit = 10;
+
+
+    pub fn get_rate_limit(&self) -> &RateLimit {
+        self.rate_limit.as_ref().unwrap_or_else(|| <RateLimit as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_rate_limit(&mut self) {
+        self.rate_limit.clear();
+    }
+
+    pub fn has_rate_limit(&self) -> bool {
+        self.rate_limit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rate_limit(&mut self, v: RateLimit) {
+        self.rate_limit = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_rate_limit(&mut self) -> &mut RateLimit {
+        if self.rate_limit.is_none() {
+            self.rate_limit.set_default();
+        }
+        self.rate_limit.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_rate_limit(&mut self) -> RateLimit {
+        self.rate_limit.take().unwrap_or_else(|| RateLimit::new())
+    }
+}
+
+impl ::protobuf::Message for Inbound {
+    fn is_initialized(&self) -> bool {
+        for v in &self.rate_limit {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.tag)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.protocol)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.settings)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.rewrites)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.backlog = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.udp_session_timeout = tmp;
+                },
+                9 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.addresses)?;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.rate_limit)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.tag.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.tag);
+        }
+        if !self.protocol.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.protocol);
+        }
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.settings.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(5, &self.settings);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(6, &self.rewrites);
+        if self.backlog != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.backlog, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.udp_session_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.udp_session_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.addresses {
+            my_size += ::protobuf::rt::string_size(9, &value);
+        };
+        if let Some(ref v) = self.rate_limit.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.tag.is_empty() {
+            os.write_string(1, &self.tag)?;
+        }
+        if !self.protocol.is_empty() {
+            os.write_string(2, &self.protocol)?;
+        }
+        if !self.address.is_empty() {
+            os.write_string(3, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(4, self.port)?;
+        }
+        if !self.settings.is_empty() {
+            os.write_bytes(5, &self.settings)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(6, &self.rewrites, os)?;
+        if self.backlog != 0 {
+            os.write_uint32(7, self.backlog)?;
+        }
+        if self.udp_session_timeout != 0 {
+            os.write_uint32(8, self.udp_session_timeout)?;
+        }
+        for v in &self.addresses {
+            os.write_string(9, &v)?;
+        };
+        if let Some(ref v) = self.rate_limit.as_ref() {
+            os.write_tag(10, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Inbound {
+        Inbound::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "tag",
+                |m: &Inbound| { &m.tag },
+                |m: &mut Inbound| { &mut m.tag },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "protocol",
+                |m: &Inbound| { &m.protocol },
+                |m: &mut Inbound| { &mut m.protocol },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &Inbound| { &m.address },
+                |m: &mut Inbound| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &Inbound| { &m.port },
+                |m: &mut Inbound| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "settings",
+                |m: &Inbound| { &m.settings },
+                |m: &mut Inbound| { &mut m.settings },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "rewrites",
+                |m: &Inbound| { &m.rewrites },
+                |m: &mut Inbound| { &mut m.rewrites },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "backlog",
+                |m: &Inbound| { &m.backlog },
+                |m: &mut Inbound| { &mut m.backlog },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "udp_session_timeout",
+                |m: &Inbound| { &m.udp_session_timeout },
+                |m: &mut Inbound| { &mut m.udp_session_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "addresses",
+                |m: &Inbound| { &m.addresses },
+                |m: &mut Inbound| { &mut m.addresses },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<RateLimit>>(
+                "rate_limit",
+                |m: &Inbound| { &m.rate_limit },
+                |m: &mut Inbound| { &mut m.rate_limit },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Inbound>(
+                "Inbound",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Inbound {
+        static instance: ::protobuf::rt::LazyV2<Inbound> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Inbound::new)
+    }
+}
+
+impl ::protobuf::Clear for Inbound {
+    fn clear(&mut self) {
+        self.tag.clear();
+        self.protocol.clear();
+        self.address.clear();
+        self.port = 0;
+        self.settings.clear();
+        self.rewrites.clear();
+        self.backlog = 0;
+        self.udp_session_timeout = 0;
+        self.addresses.clear();
+        self.rate_limit.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Inbound {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Inbound {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct RedirectOutboundSettings {
+    // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a RedirectOutboundSettings {
+    fn default() -> &'a RedirectOutboundSettings {
+        <RedirectOutboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl RedirectOutboundSettings {
+    pub fn new() -> RedirectOutboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+}
+
+impl ::protobuf::Message for RedirectOutboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> RedirectOutboundSettings {
+        RedirectOutboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &RedirectOutboundSettings| { &m.address },
+                |m: &mut RedirectOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &RedirectOutboundSettings| { &m.port },
+                |m: &mut RedirectOutboundSettings| { &mut m.port },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RedirectOutboundSettings>(
+                "RedirectOutboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static RedirectOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<RedirectOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(RedirectOutboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for RedirectOutboundSettings {
+    fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for RedirectOutboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RedirectOutboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct SocksOutboundSettings {
+    // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a SocksOutboundSettings {
+    fn default() -> &'a SocksOutboundSettings {
+        <SocksOutboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SocksOutboundSettings {
+    pub fn new() -> SocksOutboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+}
+
+impl ::protobuf::Message for SocksOutboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> SocksOutboundSettings {
+        SocksOutboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &SocksOutboundSettings| { &m.address },
+                |m: &mut SocksOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &SocksOutboundSettings| { &m.port },
+                |m: &mut SocksOutboundSettings| { &mut m.port },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<SocksOutboundSettings>(
+                "SocksOutboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static SocksOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<SocksOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(SocksOutboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for SocksOutboundSettings {
+    fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for SocksOutboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SocksOutboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ShadowsocksOutboundSettings {
+    // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub method: ::std::string::String,
+    pub password: ::std::string::String,
+    pub plugin: ::std::string::String,
+    pub plugin_opts: ::std::string::String,
+    pub udp_over_tcp: bool,
+    pub udp_over_tcp_version: u32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a ShadowsocksOutboundSettings {
+    fn default() -> &'a ShadowsocksOutboundSettings {
+        <ShadowsocksOutboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ShadowsocksOutboundSettings {
+    pub fn new() -> ShadowsocksOutboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+
+    // string method = 3;
+
+
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+    pub fn clear_method(&mut self) {
+        self.method.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_method(&mut self, v: ::std::string::String) {
+        self.method = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_method(&mut self) -> &mut ::std::string::String {
+        &mut self.method
+    }
+
+    // Take field
+    pub fn take_method(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.method, ::std::string::String::new())
+    }
+
+    // string password = 4;
+
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+    pub fn clear_password(&mut self) {
+        self.password.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_password(&mut self, v: ::std::string::String) {
+        self.password = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_password(&mut self) -> &mut ::std::string::String {
+        &mut self.password
+    }
+
+    // Take field
+    pub fn take_password(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    }
+
+    // string plugin = 5;
+
+
+    pub fn get_plugin(&self) -> &str {
+        &self.plugin
+    }
+    pub fn clear_plugin(&mut self) {
+        self.plugin.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_plugin(&mut self, v: ::std::string::String) {
+        self.plugin = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_plugin(&mut self) -> &mut ::std::string::String {
+        &mut self.plugin
+    }
+
+    // Take field
+    pub fn take_plugin(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.plugin, ::std::string::String::new())
+    }
+
+    // string plugin_opts = 6;
+
+
+    pub fn get_plugin_opts(&self) -> &str {
+        &self.plugin_opts
+    }
+    pub fn clear_plugin_opts(&mut self) {
+        self.plugin_opts.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_plugin_opts(&mut self, v: ::std::string::String) {
+        self.plugin_opts = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_plugin_opts(&mut self) -> &mut ::std::string::String {
+        &mut self.plugin_opts
+    }
+
+    // Take field
+    pub fn take_plugin_opts(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.plugin_opts, ::std::string::String::new())
+    }
+
+    // bool udp_over_tcp = 7;
+
+
+    pub fn get_udp_over_tcp(&self) -> bool {
+        self.udp_over_tcp
+    }
+    pub fn clear_udp_over_tcp(&mut self) {
+        self.udp_over_tcp = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_over_tcp(&mut self, v: bool) {
+        self.udp_over_tcp = v;
+    }
+
+    // uint32 udp_over_tcp_version = 8;
+
+
+    pub fn get_udp_over_tcp_version(&self) -> u32 {
+        self.udp_over_tcp_version
+    }
+    pub fn clear_udp_over_tcp_version(&mut self) {
+        self.udp_over_tcp_version = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_over_tcp_version(&mut self, v: u32) {
+        self.udp_over_tcp_version = v;
+    }
+}
+
+impl ::protobuf::Message for ShadowsocksOutboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.method)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.plugin)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.plugin_opts)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.udp_over_tcp = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.udp_over_tcp_version = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.method.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.method);
+        }
+        if !self.password.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.password);
+        }
+        if !self.plugin.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.plugin);
+        }
+        if !self.plugin_opts.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.plugin_opts);
+        }
+        if self.udp_over_tcp != false {
+            my_size += 2;
+        }
+        if self.udp_over_tcp_version != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.udp_over_tcp_version, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
+        }
+        if !self.method.is_empty() {
+            os.write_string(3, &self.method)?;
+        }
+        if !self.password.is_empty() {
+            os.write_string(4, &self.password)?;
+        }
+        if !self.plugin.is_empty() {
+            os.write_string(5, &self.plugin)?;
+        }
+        if !self.plugin_opts.is_empty() {
+            os.write_string(6, &self.plugin_opts)?;
+        }
+        if self.udp_over_tcp != false {
+            os.write_bool(7, self.udp_over_tcp)?;
+        }
+        if self.udp_over_tcp_version != 0 {
+            os.write_uint32(8, self.udp_over_tcp_version)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ShadowsocksOutboundSettings {
+        ShadowsocksOutboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &ShadowsocksOutboundSettings| { &m.address },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &ShadowsocksOutboundSettings| { &m.port },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "method",
+                |m: &ShadowsocksOutboundSettings| { &m.method },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.method },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "password",
+                |m: &ShadowsocksOutboundSettings| { &m.password },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.password },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "plugin",
+                |m: &ShadowsocksOutboundSettings| { &m.plugin },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.plugin },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "plugin_opts",
+                |m: &ShadowsocksOutboundSettings| { &m.plugin_opts },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.plugin_opts },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "udp_over_tcp",
+                |m: &ShadowsocksOutboundSettings| { &m.udp_over_tcp },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.udp_over_tcp },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "udp_over_tcp_version",
+                |m: &ShadowsocksOutboundSettings| { &m.udp_over_tcp_version },
+                |m: &mut ShadowsocksOutboundSettings| { &mut m.udp_over_tcp_version },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ShadowsocksOutboundSettings>(
+                "ShadowsocksOutboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static ShadowsocksOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<ShadowsocksOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(ShadowsocksOutboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for ShadowsocksOutboundSettings {
+    fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.method.clear();
+        self.password.clear();
+        self.plugin.clear();
+        self.plugin_opts.clear();
+        self.udp_over_tcp = false;
+        self.udp_over_tcp_version = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ShadowsocksOutboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ShadowsocksOutboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct TrojanOutboundSettings {
+    // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub password: ::std::string::String,
+    pub udp_over_tcp: bool,
+    pub udp_over_tcp_version: u32,
+    pub tls: bool,
+    pub server_name: ::std::string::String,
+    pub alpn: ::protobuf::RepeatedField<::std::string::String>,
+    pub insecure: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a TrojanOutboundSettings {
+    fn default() -> &'a TrojanOutboundSettings {
+        <TrojanOutboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl TrojanOutboundSettings {
+    pub fn new() -> TrojanOutboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+
+    // string password = 3;
+
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+    pub fn clear_password(&mut self) {
+        self.password.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_password(&mut self, v: ::std::string::String) {
+        self.password = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_password(&mut self) -> &mut ::std::string::String {
+        &mut self.password
+    }
+
+    // Take field
+    pub fn take_password(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    }
+
+    // bool udp_over_tcp = 4;
+
+
+    pub fn get_udp_over_tcp(&self) -> bool {
+        self.udp_over_tcp
+    }
+    pub fn clear_udp_over_tcp(&mut self) {
+        self.udp_over_tcp = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_over_tcp(&mut self, v: bool) {
+        self.udp_over_tcp = v;
+    }
+
+    // uint32 udp_over_tcp_version = 5;
+
+
+    pub fn get_udp_over_tcp_version(&self) -> u32 {
+        self.udp_over_tcp_version
+    }
+    pub fn clear_udp_over_tcp_version(&mut self) {
+        self.udp_over_tcp_version = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_over_tcp_version(&mut self, v: u32) {
+        self.udp_over_tcp_version = v;
+    }
+
+    // bool tls = 6;
+
+
+    pub fn get_tls(&self) -> bool {
+        self.tls
+    }
+    pub fn clear_tls(&mut self) {
+        self.tls = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tls(&mut self, v: bool) {
+        self.tls = v;
+    }
+
+    // string server_name = 7;
+
+
+    pub fn get_server_name(&self) -> &str {
+        &self.server_name
+    }
+    pub fn clear_server_name(&mut self) {
+        self.server_name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_server_name(&mut self, v: ::std::string::String) {
+        self.server_name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_server_name(&mut self) -> &mut ::std::string::String {
+        &mut self.server_name
+    }
+
+    // Take field
+    pub fn take_server_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.server_name, ::std::string::String::new())
+    }
+
+    // repeated string alpn = 8;
+
+
+    pub fn get_alpn(&self) -> &[::std::string::String] {
+        &self.alpn
+    }
+    pub fn clear_alpn(&mut self) {
+        self.alpn.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_alpn(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.alpn = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_alpn(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.alpn
+    }
+
+    // Take field
+    pub fn take_alpn(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.alpn, ::protobuf::RepeatedField::new())
+    }
+
+    // bool insecure = 9;
+
+
+    pub fn get_insecure(&self) -> bool {
+        self.insecure
+    }
+    pub fn clear_insecure(&mut self) {
+        self.insecure = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_insecure(&mut self, v: bool) {
+        self.insecure = v;
+    }
+}
+
+impl ::protobuf::Message for TrojanOutboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.udp_over_tcp = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.udp_over_tcp_version = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.tls = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.server_name)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.alpn)?;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.insecure = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.password.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.password);
+        }
+        if self.udp_over_tcp != false {
+            my_size += 2;
+        }
+        if self.udp_over_tcp_version != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.udp_over_tcp_version, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.tls != false {
+            my_size += 2;
+        }
+        if !self.server_name.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.server_name);
+        }
+        for value in &self.alpn {
+            my_size += ::protobuf::rt::string_size(8, &value);
+        };
+        if self.insecure != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
+        }
+        if !self.password.is_empty() {
+            os.write_string(3, &self.password)?;
+        }
+        if self.udp_over_tcp != false {
+            os.write_bool(4, self.udp_over_tcp)?;
+        }
+        if self.udp_over_tcp_version != 0 {
+            os.write_uint32(5, self.udp_over_tcp_version)?;
+        }
+        if self.tls != false {
+            os.write_bool(6, self.tls)?;
+        }
+        if !self.server_name.is_empty() {
+            os.write_string(7, &self.server_name)?;
+        }
+        for v in &self.alpn {
+            os.write_string(8, &v)?;
+        };
+        if self.insecure != false {
+            os.write_bool(9, self.insecure)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> TrojanOutboundSettings {
+        TrojanOutboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &TrojanOutboundSettings| { &m.address },
+                |m: &mut TrojanOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &TrojanOutboundSettings| { &m.port },
+                |m: &mut TrojanOutboundSettings| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "password",
+                |m: &TrojanOutboundSettings| { &m.password },
+                |m: &mut TrojanOutboundSettings| { &mut m.password },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "udp_over_tcp",
+                |m: &TrojanOutboundSettings| { &m.udp_over_tcp },
+                |m: &mut TrojanOutboundSettings| { &mut m.udp_over_tcp },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "udp_over_tcp_version",
+                |m: &TrojanOutboundSettings| { &m.udp_over_tcp_version },
+                |m: &mut TrojanOutboundSettings| { &mut m.udp_over_tcp_version },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "tls",
+                |m: &TrojanOutboundSettings| { &m.tls },
+                |m: &mut TrojanOutboundSettings| { &mut m.tls },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "server_name",
+                |m: &TrojanOutboundSettings| { &m.server_name },
+                |m: &mut TrojanOutboundSettings| { &mut m.server_name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "alpn",
+                |m: &TrojanOutboundSettings| { &m.alpn },
+                |m: &mut TrojanOutboundSettings| { &mut m.alpn },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "insecure",
+                |m: &TrojanOutboundSettings| { &m.insecure },
+                |m: &mut TrojanOutboundSettings| { &mut m.insecure },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TrojanOutboundSettings>(
+                "TrojanOutboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static TrojanOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<TrojanOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(TrojanOutboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for TrojanOutboundSettings {
+    fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.password.clear();
+        self.udp_over_tcp = false;
+        self.udp_over_tcp_version = 0;
+        self.tls = false;
+        self.server_name.clear();
+        self.alpn.clear();
+        self.insecure = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for TrojanOutboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for TrojanOutboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct VMessOutboundSettings {
+    // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub uuid: ::std::string::String,
+    pub security: ::std::string::String,
+    pub reduce_rtt: bool,
+    pub legacy_header: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a VMessOutboundSettings {
+    fn default() -> &'a VMessOutboundSettings {
+        <VMessOutboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl VMessOutboundSettings {
+    pub fn new() -> VMessOutboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+
+    // string uuid = 3;
+
+
+    pub fn get_uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn clear_uuid(&mut self) {
+        self.uuid.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_uuid(&mut self, v: ::std::string::String) {
+        self.uuid = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_uuid(&mut self) -> &mut ::std::string::String {
+        &mut self.uuid
+    }
+
+    // Take field
+    pub fn take_uuid(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.uuid, ::std::string::String::new())
+    }
+
+    // string security = 4;
+
+
+    pub fn get_security(&self) -> &str {
+        &self.security
+    }
+    pub fn clear_security(&mut self) {
+        self.security.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_security(&mut self, v: ::std::string::String) {
+        self.security = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_security(&mut self) -> &mut ::std::string::String {
+        &mut self.security
+    }
+
+    // Take field
+    pub fn take_security(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.security, ::std::string::String::new())
+    }
+
+    // bool reduce_rtt = 5;
+
+
+    pub fn get_reduce_rtt(&self) -> bool {
+        self.reduce_rtt
+    }
+    pub fn clear_reduce_rtt(&mut self) {
+        self.reduce_rtt = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_reduce_rtt(&mut self, v: bool) {
+        self.reduce_rtt = v;
+    }
+
+    // bool legacy_header = 6;
+
+
+    pub fn get_legacy_header(&self) -> bool {
+        self.legacy_header
+    }
+    pub fn clear_legacy_header(&mut self) {
+        self.legacy_header = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_legacy_header(&mut self, v: bool) {
+        self.legacy_header = v;
+    }
+}
+
+impl ::protobuf::Message for VMessOutboundSettings {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.uuid)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.security)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.reduce_rtt = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.legacy_header = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.uuid.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.uuid);
+        }
+        if !self.security.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.security);
+        }
+        if self.reduce_rtt != false {
+            my_size += 2;
+        }
+        if self.legacy_header != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
+        }
+        if !self.uuid.is_empty() {
+            os.write_string(3, &self.uuid)?;
+        }
+        if !self.security.is_empty() {
+            os.write_string(4, &self.security)?;
+        }
+        if self.reduce_rtt != false {
+            os.write_bool(5, self.reduce_rtt)?;
+        }
+        if self.legacy_header != false {
+            os.write_bool(6, self.legacy_header)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> VMessOutboundSettings {
+        VMessOutboundSettings::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &VMessOutboundSettings| { &m.address },
+                |m: &mut VMessOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &VMessOutboundSettings| { &m.port },
+                |m: &mut VMessOutboundSettings| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "uuid",
+                |m: &VMessOutboundSettings| { &m.uuid },
+                |m: &mut VMessOutboundSettings| { &mut m.uuid },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "security",
+                |m: &VMessOutboundSettings| { &m.security },
+                |m: &mut VMessOutboundSettings| { &mut m.security },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "reduce_rtt",
+                |m: &VMessOutboundSettings| { &m.reduce_rtt },
+                |m: &mut VMessOutboundSettings| { &mut m.reduce_rtt },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "legacy_header",
+                |m: &VMessOutboundSettings| { &m.legacy_header },
+                |m: &mut VMessOutboundSettings| { &mut m.legacy_header },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VMessOutboundSettings>(
+                "VMessOutboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static VMessOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<VMessOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(VMessOutboundSettings::new)
+    }
+}
+
+impl ::protobuf::Clear for VMessOutboundSettings {
+    fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.uuid.clear();
+        self.security.clear();
+        self.reduce_rtt = false;
+        self.legacy_header = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for VMessOutboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for VMessOutboundSettings {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct VLessOutboundSettings {
+    // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub uuid: ::std::string::String,
+    pub flow: ::std::string::String,
+    pub encryption: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a Log {
-    fn default() -> &'a Log {
-        <Log as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a VLessOutboundSettings {
+    fn default() -> &'a VLessOutboundSettings {
+        <VLessOutboundSettings as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl VLessOutboundSettings {
+    pub fn new() -> VLessOutboundSettings {
+        ::std::default::Default::default()
+    }
+
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
     }
-}
 
-impl Log {
-    pub fn new() -> Log {
-        ::std::default::Default::default()
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
     }
 
-    // .Log.Level level = 1;
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
 
+    // uint32 port = 2;
 
-    pub fn get_level(&self) -> Log_Level {
-        self.level
+
+    pub fn get_port(&self) -> u32 {
+        self.port
     }
-    pub fn clear_level(&mut self) {
-        self.level = Log_Level::TRACE;
+    pub fn clear_port(&mut self) {
+        self.port = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_level(&mut self, v: Log_Level) {
-        self.level = v;
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
     }
 
-    // .Log.Output output = 2;
+    // string uuid = 3;
 
 
-    pub fn get_output(&self) -> Log_Output {
-        self.output
+    pub fn get_uuid(&self) -> &str {
+        &self.uuid
     }
-    pub fn clear_output(&mut self) {
-        self.output = Log_Output::CONSOLE;
+    pub fn clear_uuid(&mut self) {
+        self.uuid.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_output(&mut self, v: Log_Output) {
-        self.output = v;
+    pub fn set_uuid(&mut self, v: ::std::string::String) {
+        self.uuid = v;
     }
 
-    // string output_file = 3;
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_uuid(&mut self) -> &mut ::std::string::String {
+        &mut self.uuid
+    }
+
+    // Take field
+    pub fn take_uuid(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.uuid, ::std::string::String::new())
+    }
 
+    // string flow = 4;
 
-    pub fn get_output_file(&self) -> &str {
-        &self.output_file
+
+    pub fn get_flow(&self) -> &str {
+        &self.flow
     }
-    pub fn clear_output_file(&mut self) {
-        self.output_file.clear();
+    pub fn clear_flow(&mut self) {
+        self.flow.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_output_file(&mut self, v: ::std::string::String) {
-        self.output_file = v;
+    pub fn set_flow(&mut self, v: ::std::string::String) {
+        self.flow = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_output_file(&mut self) -> &mut ::std::string::String {
-        &mut self.output_file
+    pub fn mut_flow(&mut self) -> &mut ::std::string::String {
+        &mut self.flow
     }
 
     // Take field
-    pub fn take_output_file(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.output_file, ::std::string::String::new())
+    pub fn take_flow(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.flow, ::std::string::String::new())
+    }
+
+    // string encryption = 5;
+
+
+    pub fn get_encryption(&self) -> &str {
+        &self.encryption
+    }
+    pub fn clear_encryption(&mut self) {
+        self.encryption.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_encryption(&mut self, v: ::std::string::String) {
+        self.encryption = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_encryption(&mut self) -> &mut ::std::string::String {
+        &mut self.encryption
+    }
+
+    // Take field
+    pub fn take_encryption(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.encryption, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for Log {
+impl ::protobuf::Message for VLessOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -507,13 +6222,23 @@ impl ::protobuf::Message for Log {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.level, 1, &mut self.unknown_fields)?
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.output, 2, &mut self.unknown_fields)?
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
                 },
                 3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.output_file)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.uuid)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.flow)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.encryption)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -527,14 +6252,20 @@ impl ::protobuf::Message for Log {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.level != Log_Level::TRACE {
-            my_size += ::protobuf::rt::enum_size(1, self.level);
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
         }
-        if self.output != Log_Output::CONSOLE {
-            my_size += ::protobuf::rt::enum_size(2, self.output);
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
         }
-        if !self.output_file.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.output_file);
+        if !self.uuid.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.uuid);
+        }
+        if !self.flow.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.flow);
+        }
+        if !self.encryption.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.encryption);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -542,425 +6273,351 @@ impl ::protobuf::Message for Log {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if self.level != Log_Level::TRACE {
-            os.write_enum(1, ::protobuf::ProtobufEnum::value(&self.level))?;
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
         }
-        if self.output != Log_Output::CONSOLE {
-            os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.output))?;
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
         }
-        if !self.output_file.is_empty() {
-            os.write_string(3, &self.output_file)?;
+        if !self.uuid.is_empty() {
+            os.write_string(3, &self.uuid)?;
+        }
+        if !self.flow.is_empty() {
+            os.write_string(4, &self.flow)?;
+        }
+        if !self.encryption.is_empty() {
+            os.write_string(5, &self.encryption)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
-    fn get_cached_size(&self) -> u32 {
-        self.cached_size.get()
-    }
-
-    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
-        &self.unknown_fields
-    }
-
-    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
-        &mut self.unknown_fields
-    }
-
-    fn as_any(&self) -> &dyn (::std::any::Any) {
-        self as &dyn (::std::any::Any)
-    }
-    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
-        self as &mut dyn (::std::any::Any)
-    }
-    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
-        self
-    }
-
-    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
-        Self::descriptor_static()
-    }
-
-    fn new() -> Log {
-        Log::new()
-    }
-
-    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
-        descriptor.get(|| {
-            let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Log_Level>>(
-                "level",
-                |m: &Log| { &m.level },
-                |m: &mut Log| { &mut m.level },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Log_Output>>(
-                "output",
-                |m: &Log| { &m.output },
-                |m: &mut Log| { &mut m.output },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "output_file",
-                |m: &Log| { &m.output_file },
-                |m: &mut Log| { &mut m.output_file },
-            ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Log>(
-                "Log",
-                fields,
-                file_descriptor_proto()
-            )
-        })
-    }
-
-    fn default_instance() -> &'static Log {
-        static instance: ::protobuf::rt::LazyV2<Log> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(Log::new)
-    }
-}
-
-impl ::protobuf::Clear for Log {
-    fn clear(&mut self) {
-        self.level = Log_Level::TRACE;
-        self.output = Log_Output::CONSOLE;
-        self.output_file.clear();
-        self.unknown_fields.clear();
-    }
-}
-
-impl ::std::fmt::Debug for Log {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        ::protobuf::text_format::fmt(self, f)
-    }
-}
-
-impl ::protobuf::reflect::ProtobufValue for Log {
-    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Message(self)
-    }
-}
-
-#[derive(Clone,PartialEq,Eq,Debug,Hash)]
-pub enum Log_Level {
-    TRACE = 0,
-    DEBUG = 1,
-    INFO = 2,
-    WARN = 3,
-    ERROR = 4,
-}
-
-impl ::protobuf::ProtobufEnum for Log_Level {
-    fn value(&self) -> i32 {
-        *self as i32
-    }
-
-    fn from_i32(value: i32) -> ::std::option::Option<Log_Level> {
-        match value {
-            0 => ::std::option::Option::Some(Log_Level::TRACE),
-            1 => ::std::option::Option::Some(Log_Level::DEBUG),
-            2 => ::std::option::Option::Some(Log_Level::INFO),
-            3 => ::std::option::Option::Some(Log_Level::WARN),
-            4 => ::std::option::Option::Some(Log_Level::ERROR),
-            _ => ::std::option::Option::None
-        }
-    }
-
-    fn values() -> &'static [Self] {
-        static values: &'static [Log_Level] = &[
-            Log_Level::TRACE,
-            Log_Level::DEBUG,
-            Log_Level::INFO,
-            Log_Level::WARN,
-            Log_Level::ERROR,
-        ];
-        values
-    }
-
-    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
-        descriptor.get(|| {
-            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Log_Level>("Log.Level", file_descriptor_proto())
-        })
-    }
-}
-
-impl ::std::marker::Copy for Log_Level {
-}
-
-impl ::std::default::Default for Log_Level {
-    fn default() -> Self {
-        Log_Level::TRACE
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
     }
-}
 
-impl ::protobuf::reflect::ProtobufValue for Log_Level {
-    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
     }
-}
 
-#[derive(Clone,PartialEq,Eq,Debug,Hash)]
-pub enum Log_Output {
-    CONSOLE = 0,
-    FILE = 1,
-}
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
 
-impl ::protobuf::ProtobufEnum for Log_Output {
-    fn value(&self) -> i32 {
-        *self as i32
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
     }
 
-    fn from_i32(value: i32) -> ::std::option::Option<Log_Output> {
-        match value {
-            0 => ::std::option::Option::Some(Log_Output::CONSOLE),
-            1 => ::std::option::Option::Some(Log_Output::FILE),
-            _ => ::std::option::Option::None
-        }
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
     }
 
-    fn values() -> &'static [Self] {
-        static values: &'static [Log_Output] = &[
-            Log_Output::CONSOLE,
-            Log_Output::FILE,
-        ];
-        values
+    fn new() -> VLessOutboundSettings {
+        VLessOutboundSettings::new()
     }
 
-    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
-            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Log_Output>("Log.Output", file_descriptor_proto())
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &VLessOutboundSettings| { &m.address },
+                |m: &mut VLessOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &VLessOutboundSettings| { &m.port },
+                |m: &mut VLessOutboundSettings| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "uuid",
+                |m: &VLessOutboundSettings| { &m.uuid },
+                |m: &mut VLessOutboundSettings| { &mut m.uuid },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "flow",
+                |m: &VLessOutboundSettings| { &m.flow },
+                |m: &mut VLessOutboundSettings| { &mut m.flow },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "encryption",
+                |m: &VLessOutboundSettings| { &m.encryption },
+                |m: &mut VLessOutboundSettings| { &mut m.encryption },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VLessOutboundSettings>(
+                "VLessOutboundSettings",
+                fields,
+                file_descriptor_proto()
+            )
         })
     }
+
+    fn default_instance() -> &'static VLessOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<VLessOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(VLessOutboundSettings::new)
+    }
 }
 
-impl ::std::marker::Copy for Log_Output {
+impl ::protobuf::Clear for VLessOutboundSettings {
+    fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.uuid.clear();
+        self.flow.clear();
+        self.encryption.clear();
+        self.unknown_fields.clear();
+    }
 }
 
-impl ::std::default::Default for Log_Output {
-    fn default() -> Self {
-        Log_Output::CONSOLE
+impl ::std::fmt::Debug for VLessOutboundSettings {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Log_Output {
+impl ::protobuf::reflect::ProtobufValue for VLessOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+        ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct TUNInboundSettings {
+pub struct WireGuardOutboundSettings {
     // message fields
-    pub fd: i32,
-    pub name: ::std::string::String,
     pub address: ::std::string::String,
-    pub gateway: ::std::string::String,
-    pub netmask: ::std::string::String,
-    pub mtu: i32,
-    pub fake_dns_exclude: ::protobuf::RepeatedField<::std::string::String>,
-    pub fake_dns_include: ::protobuf::RepeatedField<::std::string::String>,
+    pub port: u32,
+    pub private_key: ::std::string::String,
+    pub peer_public_key: ::std::string::String,
+    pub preshared_key: ::std::string::String,
+    pub local_address: ::std::string::String,
+    pub allowed_ips: ::protobuf::RepeatedField<::std::string::String>,
+    pub mtu: u32,
+    pub keepalive: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a TUNInboundSettings {
-    fn default() -> &'a TUNInboundSettings {
-        <TUNInboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a WireGuardOutboundSettings {
+    fn default() -> &'a WireGuardOutboundSettings {
+        <WireGuardOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl TUNInboundSettings {
-    pub fn new() -> TUNInboundSettings {
+impl WireGuardOutboundSettings {
+    pub fn new() -> WireGuardOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // int32 fd = 1;
+    // string address = 1;
 
 
-    pub fn get_fd(&self) -> i32 {
-        self.fd
+    pub fn get_address(&self) -> &str {
+        &self.address
     }
-    pub fn clear_fd(&mut self) {
-        self.fd = 0;
+    pub fn clear_address(&mut self) {
+        self.address.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_fd(&mut self, v: i32) {
-        self.fd = v;
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
     }
 
-    // string name = 2;
-
-
-    pub fn get_name(&self) -> &str {
-        &self.name
-    }
-    pub fn clear_name(&mut self) {
-        self.name.clear();
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
     }
 
-    // Param is passed by value, moved
-    pub fn set_name(&mut self, v: ::std::string::String) {
-        self.name = v;
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
     }
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_name(&mut self) -> &mut ::std::string::String {
-        &mut self.name
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
     }
 
-    // Take field
-    pub fn take_name(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.name, ::std::string::String::new())
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
     }
 
-    // string address = 3;
+    // string private_key = 3;
 
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_private_key(&self) -> &str {
+        &self.private_key
     }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    pub fn clear_private_key(&mut self) {
+        self.private_key.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    pub fn set_private_key(&mut self, v: ::std::string::String) {
+        self.private_key = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    pub fn mut_private_key(&mut self) -> &mut ::std::string::String {
+        &mut self.private_key
     }
 
     // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    pub fn take_private_key(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.private_key, ::std::string::String::new())
     }
 
-    // string gateway = 4;
+    // string peer_public_key = 4;
 
 
-    pub fn get_gateway(&self) -> &str {
-        &self.gateway
+    pub fn get_peer_public_key(&self) -> &str {
+        &self.peer_public_key
     }
-    pub fn clear_gateway(&mut self) {
-        self.gateway.clear();
+    pub fn clear_peer_public_key(&mut self) {
+        self.peer_public_key.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_gateway(&mut self, v: ::std::string::String) {
-        self.gateway = v;
+    pub fn set_peer_public_key(&mut self, v: ::std::string::String) {
+        self.peer_public_key = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_gateway(&mut self) -> &mut ::std::string::String {
-        &mut self.gateway
+    pub fn mut_peer_public_key(&mut self) -> &mut ::std::string::String {
+        &mut self.peer_public_key
     }
 
     // Take field
-    pub fn take_gateway(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.gateway, ::std::string::String::new())
+    pub fn take_peer_public_key(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.peer_public_key, ::std::string::String::new())
     }
 
-    // string netmask = 5;
+    // string preshared_key = 5;
 
 
-    pub fn get_netmask(&self) -> &str {
-        &self.netmask
+    pub fn get_preshared_key(&self) -> &str {
+        &self.preshared_key
     }
-    pub fn clear_netmask(&mut self) {
-        self.netmask.clear();
+    pub fn clear_preshared_key(&mut self) {
+        self.preshared_key.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_netmask(&mut self, v: ::std::string::String) {
-        self.netmask = v;
+    pub fn set_preshared_key(&mut self, v: ::std::string::String) {
+        self.preshared_key = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_netmask(&mut self) -> &mut ::std::string::String {
-        &mut self.netmask
+    pub fn mut_preshared_key(&mut self) -> &mut ::std::string::String {
+        &mut self.preshared_key
     }
 
     // Take field
-    pub fn take_netmask(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.netmask, ::std::string::String::new())
+    pub fn take_preshared_key(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.preshared_key, ::std::string::String::new())
     }
 
-    // int32 mtu = 6;
+    // string local_address = 6;
 
 
-    pub fn get_mtu(&self) -> i32 {
-        self.mtu
+    pub fn get_local_address(&self) -> &str {
+        &self.local_address
     }
-    pub fn clear_mtu(&mut self) {
-        self.mtu = 0;
+    pub fn clear_local_address(&mut self) {
+        self.local_address.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_mtu(&mut self, v: i32) {
-        self.mtu = v;
+    pub fn set_local_address(&mut self, v: ::std::string::String) {
+        self.local_address = v;
     }
 
-    // repeated string fake_dns_exclude = 7;
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_local_address(&mut self) -> &mut ::std::string::String {
+        &mut self.local_address
+    }
 
+    // Take field
+    pub fn take_local_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.local_address, ::std::string::String::new())
+    }
 
-    pub fn get_fake_dns_exclude(&self) -> &[::std::string::String] {
-        &self.fake_dns_exclude
+    // repeated string allowed_ips = 7;
+
+
+    pub fn get_allowed_ips(&self) -> &[::std::string::String] {
+        &self.allowed_ips
     }
-    pub fn clear_fake_dns_exclude(&mut self) {
-        self.fake_dns_exclude.clear();
+    pub fn clear_allowed_ips(&mut self) {
+        self.allowed_ips.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_fake_dns_exclude(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.fake_dns_exclude = v;
+    pub fn set_allowed_ips(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.allowed_ips = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_fake_dns_exclude(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.fake_dns_exclude
+    pub fn mut_allowed_ips(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.allowed_ips
     }
 
     // Take field
-    pub fn take_fake_dns_exclude(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.fake_dns_exclude, ::protobuf::RepeatedField::new())
+    pub fn take_allowed_ips(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.allowed_ips, ::protobuf::RepeatedField::new())
     }
 
-    // repeated string fake_dns_include = 8;
+    // uint32 mtu = 8;
 
 
-    pub fn get_fake_dns_include(&self) -> &[::std::string::String] {
-        &self.fake_dns_include
+    pub fn get_mtu(&self) -> u32 {
+        self.mtu
     }
-    pub fn clear_fake_dns_include(&mut self) {
-        self.fake_dns_include.clear();
+    pub fn clear_mtu(&mut self) {
+        self.mtu = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_fake_dns_include(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.fake_dns_include = v;
+    pub fn set_mtu(&mut self, v: u32) {
+        self.mtu = v;
     }
 
-    // Mutable pointer to the field.
-    pub fn mut_fake_dns_include(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.fake_dns_include
+    // uint32 keepalive = 9;
+
+
+    pub fn get_keepalive(&self) -> u32 {
+        self.keepalive
+    }
+    pub fn clear_keepalive(&mut self) {
+        self.keepalive = 0;
     }
 
-    // Take field
-    pub fn take_fake_dns_include(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.fake_dns_include, ::protobuf::RepeatedField::new())
+    // Param is passed by value, moved
+    pub fn set_keepalive(&mut self, v: u32) {
+        self.keepalive = v;
     }
 }
 
-impl ::protobuf::Message for TUNInboundSettings {
+impl ::protobuf::Message for WireGuardOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -970,36 +6627,43 @@ impl ::protobuf::Message for TUNInboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_int32()?;
-                    self.fd = tmp;
-                },
-                2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
                 },
                 3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.private_key)?;
                 },
                 4 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.gateway)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.peer_public_key)?;
                 },
                 5 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.netmask)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.preshared_key)?;
                 },
                 6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.local_address)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.allowed_ips)?;
+                },
+                8 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_int32()?;
+                    let tmp = is.read_uint32()?;
                     self.mtu = tmp;
                 },
-                7 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.fake_dns_exclude)?;
-                },
-                8 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.fake_dns_include)?;
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.keepalive = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -1013,60 +6677,66 @@ impl ::protobuf::Message for TUNInboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.fd != 0 {
-            my_size += ::protobuf::rt::value_size(1, self.fd, ::protobuf::wire_format::WireTypeVarint);
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
         }
-        if !self.name.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.name);
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
         }
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.address);
+        if !self.private_key.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.private_key);
         }
-        if !self.gateway.is_empty() {
-            my_size += ::protobuf::rt::string_size(4, &self.gateway);
+        if !self.peer_public_key.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.peer_public_key);
         }
-        if !self.netmask.is_empty() {
-            my_size += ::protobuf::rt::string_size(5, &self.netmask);
+        if !self.preshared_key.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.preshared_key);
         }
-        if self.mtu != 0 {
-            my_size += ::protobuf::rt::value_size(6, self.mtu, ::protobuf::wire_format::WireTypeVarint);
+        if !self.local_address.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.local_address);
         }
-        for value in &self.fake_dns_exclude {
+        for value in &self.allowed_ips {
             my_size += ::protobuf::rt::string_size(7, &value);
         };
-        for value in &self.fake_dns_include {
-            my_size += ::protobuf::rt::string_size(8, &value);
-        };
+        if self.mtu != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.mtu, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.keepalive != 0 {
+            my_size += ::protobuf::rt::value_size(9, self.keepalive, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if self.fd != 0 {
-            os.write_int32(1, self.fd)?;
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
         }
-        if !self.name.is_empty() {
-            os.write_string(2, &self.name)?;
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
         }
-        if !self.address.is_empty() {
-            os.write_string(3, &self.address)?;
+        if !self.private_key.is_empty() {
+            os.write_string(3, &self.private_key)?;
         }
-        if !self.gateway.is_empty() {
-            os.write_string(4, &self.gateway)?;
+        if !self.peer_public_key.is_empty() {
+            os.write_string(4, &self.peer_public_key)?;
         }
-        if !self.netmask.is_empty() {
-            os.write_string(5, &self.netmask)?;
+        if !self.preshared_key.is_empty() {
+            os.write_string(5, &self.preshared_key)?;
         }
-        if self.mtu != 0 {
-            os.write_int32(6, self.mtu)?;
+        if !self.local_address.is_empty() {
+            os.write_string(6, &self.local_address)?;
         }
-        for v in &self.fake_dns_exclude {
+        for v in &self.allowed_ips {
             os.write_string(7, &v)?;
         };
-        for v in &self.fake_dns_include {
-            os.write_string(8, &v)?;
-        };
+        if self.mtu != 0 {
+            os.write_uint32(8, self.mtu)?;
+        }
+        if self.keepalive != 0 {
+            os.write_uint32(9, self.keepalive)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1097,115 +6767,194 @@ impl ::protobuf::Message for TUNInboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> TUNInboundSettings {
-        TUNInboundSettings::new()
+    fn new() -> WireGuardOutboundSettings {
+        WireGuardOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
-                "fd",
-                |m: &TUNInboundSettings| { &m.fd },
-                |m: &mut TUNInboundSettings| { &mut m.fd },
-            ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "name",
-                |m: &TUNInboundSettings| { &m.name },
-                |m: &mut TUNInboundSettings| { &mut m.name },
+                "address",
+                |m: &WireGuardOutboundSettings| { &m.address },
+                |m: &mut WireGuardOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &WireGuardOutboundSettings| { &m.port },
+                |m: &mut WireGuardOutboundSettings| { &mut m.port },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &TUNInboundSettings| { &m.address },
-                |m: &mut TUNInboundSettings| { &mut m.address },
+                "private_key",
+                |m: &WireGuardOutboundSettings| { &m.private_key },
+                |m: &mut WireGuardOutboundSettings| { &mut m.private_key },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "gateway",
-                |m: &TUNInboundSettings| { &m.gateway },
-                |m: &mut TUNInboundSettings| { &mut m.gateway },
+                "peer_public_key",
+                |m: &WireGuardOutboundSettings| { &m.peer_public_key },
+                |m: &mut WireGuardOutboundSettings| { &mut m.peer_public_key },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "netmask",
-                |m: &TUNInboundSettings| { &m.netmask },
-                |m: &mut TUNInboundSettings| { &mut m.netmask },
+                "preshared_key",
+                |m: &WireGuardOutboundSettings| { &m.preshared_key },
+                |m: &mut WireGuardOutboundSettings| { &mut m.preshared_key },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
-                "mtu",
-                |m: &TUNInboundSettings| { &m.mtu },
-                |m: &mut TUNInboundSettings| { &mut m.mtu },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "local_address",
+                |m: &WireGuardOutboundSettings| { &m.local_address },
+                |m: &mut WireGuardOutboundSettings| { &mut m.local_address },
             ));
             fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "fake_dns_exclude",
-                |m: &TUNInboundSettings| { &m.fake_dns_exclude },
-                |m: &mut TUNInboundSettings| { &mut m.fake_dns_exclude },
+                "allowed_ips",
+                |m: &WireGuardOutboundSettings| { &m.allowed_ips },
+                |m: &mut WireGuardOutboundSettings| { &mut m.allowed_ips },
             ));
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "fake_dns_include",
-                |m: &TUNInboundSettings| { &m.fake_dns_include },
-                |m: &mut TUNInboundSettings| { &mut m.fake_dns_include },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "mtu",
+                |m: &WireGuardOutboundSettings| { &m.mtu },
+                |m: &mut WireGuardOutboundSettings| { &mut m.mtu },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TUNInboundSettings>(
-                "TUNInboundSettings",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "keepalive",
+                |m: &WireGuardOutboundSettings| { &m.keepalive },
+                |m: &mut WireGuardOutboundSettings| { &mut m.keepalive },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<WireGuardOutboundSettings>(
+                "WireGuardOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static TUNInboundSettings {
-        static instance: ::protobuf::rt::LazyV2<TUNInboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(TUNInboundSettings::new)
+    fn default_instance() -> &'static WireGuardOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<WireGuardOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(WireGuardOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for TUNInboundSettings {
+impl ::protobuf::Clear for WireGuardOutboundSettings {
     fn clear(&mut self) {
-        self.fd = 0;
-        self.name.clear();
         self.address.clear();
-        self.gateway.clear();
-        self.netmask.clear();
+        self.port = 0;
+        self.private_key.clear();
+        self.peer_public_key.clear();
+        self.preshared_key.clear();
+        self.local_address.clear();
+        self.allowed_ips.clear();
         self.mtu = 0;
-        self.fake_dns_exclude.clear();
-        self.fake_dns_include.clear();
+        self.keepalive = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for TUNInboundSettings {
+impl ::std::fmt::Debug for WireGuardOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for TUNInboundSettings {
+impl ::protobuf::reflect::ProtobufValue for WireGuardOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct TrojanInboundSettings {
+pub struct SshOutboundSettings {
     // message fields
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub username: ::std::string::String,
     pub password: ::std::string::String,
+    pub private_key: ::std::string::String,
+    pub private_key_passphrase: ::std::string::String,
+    pub host_key_fingerprint: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a TrojanInboundSettings {
-    fn default() -> &'a TrojanInboundSettings {
-        <TrojanInboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a SshOutboundSettings {
+    fn default() -> &'a SshOutboundSettings {
+        <SshOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl TrojanInboundSettings {
-    pub fn new() -> TrojanInboundSettings {
+impl SshOutboundSettings {
+    pub fn new() -> SshOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string password = 3;
+    // string address = 1;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // uint32 port = 2;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+
+    // string username = 3;
+
+
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn clear_username(&mut self) {
+        self.username.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_username(&mut self, v: ::std::string::String) {
+        self.username = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_username(&mut self) -> &mut ::std::string::String {
+        &mut self.username
+    }
+
+    // Take field
+    pub fn take_username(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.username, ::std::string::String::new())
+    }
+
+    // string password = 4;
 
 
     pub fn get_password(&self) -> &str {
@@ -1230,9 +6979,87 @@ impl TrojanInboundSettings {
     pub fn take_password(&mut self) -> ::std::string::String {
         ::std::mem::replace(&mut self.password, ::std::string::String::new())
     }
+
+    // string private_key = 5;
+
+
+    pub fn get_private_key(&self) -> &str {
+        &self.private_key
+    }
+    pub fn clear_private_key(&mut self) {
+        self.private_key.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_private_key(&mut self, v: ::std::string::String) {
+        self.private_key = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_private_key(&mut self) -> &mut ::std::string::String {
+        &mut self.private_key
+    }
+
+    // Take field
+    pub fn take_private_key(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.private_key, ::std::string::String::new())
+    }
+
+    // string private_key_passphrase = 6;
+
+
+    pub fn get_private_key_passphrase(&self) -> &str {
+        &self.private_key_passphrase
+    }
+    pub fn clear_private_key_passphrase(&mut self) {
+        self.private_key_passphrase.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_private_key_passphrase(&mut self, v: ::std::string::String) {
+        self.private_key_passphrase = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_private_key_passphrase(&mut self) -> &mut ::std::string::String {
+        &mut self.private_key_passphrase
+    }
+
+    // Take field
+    pub fn take_private_key_passphrase(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.private_key_passphrase, ::std::string::String::new())
+    }
+
+    // string host_key_fingerprint = 7;
+
+
+    pub fn get_host_key_fingerprint(&self) -> &str {
+        &self.host_key_fingerprint
+    }
+    pub fn clear_host_key_fingerprint(&mut self) {
+        self.host_key_fingerprint.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_host_key_fingerprint(&mut self, v: ::std::string::String) {
+        self.host_key_fingerprint = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_host_key_fingerprint(&mut self) -> &mut ::std::string::String {
+        &mut self.host_key_fingerprint
+    }
+
+    // Take field
+    pub fn take_host_key_fingerprint(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.host_key_fingerprint, ::std::string::String::new())
+    }
 }
 
-impl ::protobuf::Message for TrojanInboundSettings {
+impl ::protobuf::Message for SshOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -1241,9 +7068,31 @@ impl ::protobuf::Message for TrojanInboundSettings {
         while !is.eof()? {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
                 3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.username)?;
+                },
+                4 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
                 },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.private_key)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.private_key_passphrase)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.host_key_fingerprint)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1256,8 +7105,26 @@ impl ::protobuf::Message for TrojanInboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.username.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.username);
+        }
         if !self.password.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.password);
+            my_size += ::protobuf::rt::string_size(4, &self.password);
+        }
+        if !self.private_key.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.private_key);
+        }
+        if !self.private_key_passphrase.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.private_key_passphrase);
+        }
+        if !self.host_key_fingerprint.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.host_key_fingerprint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -1265,8 +7132,26 @@ impl ::protobuf::Message for TrojanInboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
+        }
+        if !self.username.is_empty() {
+            os.write_string(3, &self.username)?;
+        }
         if !self.password.is_empty() {
-            os.write_string(3, &self.password)?;
+            os.write_string(4, &self.password)?;
+        }
+        if !self.private_key.is_empty() {
+            os.write_string(5, &self.private_key)?;
+        }
+        if !self.private_key_passphrase.is_empty() {
+            os.write_string(6, &self.private_key_passphrase)?;
+        }
+        if !self.host_key_fingerprint.is_empty() {
+            os.write_string(7, &self.host_key_fingerprint)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -1298,258 +7183,302 @@ impl ::protobuf::Message for TrojanInboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> TrojanInboundSettings {
-        TrojanInboundSettings::new()
+    fn new() -> SshOutboundSettings {
+        SshOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &SshOutboundSettings| { &m.address },
+                |m: &mut SshOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &SshOutboundSettings| { &m.port },
+                |m: &mut SshOutboundSettings| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "username",
+                |m: &SshOutboundSettings| { &m.username },
+                |m: &mut SshOutboundSettings| { &mut m.username },
+            ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                 "password",
-                |m: &TrojanInboundSettings| { &m.password },
-                |m: &mut TrojanInboundSettings| { &mut m.password },
+                |m: &SshOutboundSettings| { &m.password },
+                |m: &mut SshOutboundSettings| { &mut m.password },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TrojanInboundSettings>(
-                "TrojanInboundSettings",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "private_key",
+                |m: &SshOutboundSettings| { &m.private_key },
+                |m: &mut SshOutboundSettings| { &mut m.private_key },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "private_key_passphrase",
+                |m: &SshOutboundSettings| { &m.private_key_passphrase },
+                |m: &mut SshOutboundSettings| { &mut m.private_key_passphrase },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "host_key_fingerprint",
+                |m: &SshOutboundSettings| { &m.host_key_fingerprint },
+                |m: &mut SshOutboundSettings| { &mut m.host_key_fingerprint },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<SshOutboundSettings>(
+                "SshOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static TrojanInboundSettings {
-        static instance: ::protobuf::rt::LazyV2<TrojanInboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(TrojanInboundSettings::new)
+    fn default_instance() -> &'static SshOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<SshOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(SshOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for TrojanInboundSettings {
+impl ::protobuf::Clear for SshOutboundSettings {
     fn clear(&mut self) {
+        self.address.clear();
+        self.port = 0;
+        self.username.clear();
         self.password.clear();
+        self.private_key.clear();
+        self.private_key_passphrase.clear();
+        self.host_key_fingerprint.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for TrojanInboundSettings {
+impl ::std::fmt::Debug for SshOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for TrojanInboundSettings {
+impl ::protobuf::reflect::ProtobufValue for SshOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct WebSocketInboundSettings {
+pub struct TlsOutboundSettings {
     // message fields
-    pub path: ::std::string::String,
+    pub server_name: ::std::string::String,
+    pub alpn: ::protobuf::RepeatedField<::std::string::String>,
+    pub early_data: bool,
+    pub fingerprint: ::std::string::String,
+    pub insecure: bool,
+    pub cert_sha256: ::std::string::String,
+    pub client_cert: ::std::string::String,
+    pub client_key: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a WebSocketInboundSettings {
-    fn default() -> &'a WebSocketInboundSettings {
-        <WebSocketInboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a TlsOutboundSettings {
+    fn default() -> &'a TlsOutboundSettings {
+        <TlsOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl WebSocketInboundSettings {
-    pub fn new() -> WebSocketInboundSettings {
+impl TlsOutboundSettings {
+    pub fn new() -> TlsOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string path = 1;
+    // string server_name = 1;
 
 
-    pub fn get_path(&self) -> &str {
-        &self.path
+    pub fn get_server_name(&self) -> &str {
+        &self.server_name
     }
-    pub fn clear_path(&mut self) {
-        self.path.clear();
+    pub fn clear_server_name(&mut self) {
+        self.server_name.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_path(&mut self, v: ::std::string::String) {
-        self.path = v;
+    pub fn set_server_name(&mut self, v: ::std::string::String) {
+        self.server_name = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_path(&mut self) -> &mut ::std::string::String {
-        &mut self.path
+    pub fn mut_server_name(&mut self) -> &mut ::std::string::String {
+        &mut self.server_name
     }
 
     // Take field
-    pub fn take_path(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.path, ::std::string::String::new())
+    pub fn take_server_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.server_name, ::std::string::String::new())
     }
-}
 
-impl ::protobuf::Message for WebSocketInboundSettings {
-    fn is_initialized(&self) -> bool {
-        true
+    // repeated string alpn = 2;
+
+
+    pub fn get_alpn(&self) -> &[::std::string::String] {
+        &self.alpn
+    }
+    pub fn clear_alpn(&mut self) {
+        self.alpn.clear();
     }
 
-    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        while !is.eof()? {
-            let (field_number, wire_type) = is.read_tag_unpack()?;
-            match field_number {
-                1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
-                },
-                _ => {
-                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
-                },
-            };
-        }
-        ::std::result::Result::Ok(())
+    // Param is passed by value, moved
+    pub fn set_alpn(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.alpn = v;
     }
 
-    // Compute sizes of nested messages
-    #[allow(unused_variables)]
-    fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        if !self.path.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.path);
-        }
-        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
-        self.cached_size.set(my_size);
-        my_size
+    // Mutable pointer to the field.
+    pub fn mut_alpn(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.alpn
     }
 
-    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.path.is_empty() {
-            os.write_string(1, &self.path)?;
-        }
-        os.write_unknown_fields(self.get_unknown_fields())?;
-        ::std::result::Result::Ok(())
+    // Take field
+    pub fn take_alpn(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.alpn, ::protobuf::RepeatedField::new())
     }
 
-    fn get_cached_size(&self) -> u32 {
-        self.cached_size.get()
+    // bool early_data = 3;
+
+
+    pub fn get_early_data(&self) -> bool {
+        self.early_data
+    }
+    pub fn clear_early_data(&mut self) {
+        self.early_data = false;
     }
 
-    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
-        &self.unknown_fields
+    // Param is passed by value, moved
+    pub fn set_early_data(&mut self, v: bool) {
+        self.early_data = v;
     }
 
-    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
-        &mut self.unknown_fields
+    // string fingerprint = 4;
+
+
+    pub fn get_fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+    pub fn clear_fingerprint(&mut self) {
+        self.fingerprint.clear();
     }
 
-    fn as_any(&self) -> &dyn (::std::any::Any) {
-        self as &dyn (::std::any::Any)
+    // Param is passed by value, moved
+    pub fn set_fingerprint(&mut self, v: ::std::string::String) {
+        self.fingerprint = v;
     }
-    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
-        self as &mut dyn (::std::any::Any)
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_fingerprint(&mut self) -> &mut ::std::string::String {
+        &mut self.fingerprint
     }
-    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
-        self
+
+    // Take field
+    pub fn take_fingerprint(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.fingerprint, ::std::string::String::new())
     }
 
-    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
-        Self::descriptor_static()
+    // bool insecure = 5;
+
+
+    pub fn get_insecure(&self) -> bool {
+        self.insecure
+    }
+    pub fn clear_insecure(&mut self) {
+        self.insecure = false;
     }
 
-    fn new() -> WebSocketInboundSettings {
-        WebSocketInboundSettings::new()
+    // Param is passed by value, moved
+    pub fn set_insecure(&mut self, v: bool) {
+        self.insecure = v;
     }
 
-    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
-        descriptor.get(|| {
-            let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "path",
-                |m: &WebSocketInboundSettings| { &m.path },
-                |m: &mut WebSocketInboundSettings| { &mut m.path },
-            ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<WebSocketInboundSettings>(
-                "WebSocketInboundSettings",
-                fields,
-                file_descriptor_proto()
-            )
-        })
+    // string cert_sha256 = 6;
+
+
+    pub fn get_cert_sha256(&self) -> &str {
+        &self.cert_sha256
+    }
+    pub fn clear_cert_sha256(&mut self) {
+        self.cert_sha256.clear();
     }
 
-    fn default_instance() -> &'static WebSocketInboundSettings {
-        static instance: ::protobuf::rt::LazyV2<WebSocketInboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(WebSocketInboundSettings::new)
+    // Param is passed by value, moved
+    pub fn set_cert_sha256(&mut self, v: ::std::string::String) {
+        self.cert_sha256 = v;
     }
-}
 
-impl ::protobuf::Clear for WebSocketInboundSettings {
-    fn clear(&mut self) {
-        self.path.clear();
-        self.unknown_fields.clear();
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_cert_sha256(&mut self) -> &mut ::std::string::String {
+        &mut self.cert_sha256
     }
-}
 
-impl ::std::fmt::Debug for WebSocketInboundSettings {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        ::protobuf::text_format::fmt(self, f)
+    // Take field
+    pub fn take_cert_sha256(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.cert_sha256, ::std::string::String::new())
     }
-}
 
-impl ::protobuf::reflect::ProtobufValue for WebSocketInboundSettings {
-    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Message(self)
+    // string client_cert = 7;
+
+
+    pub fn get_client_cert(&self) -> &str {
+        &self.client_cert
+    }
+    pub fn clear_client_cert(&mut self) {
+        self.client_cert.clear();
     }
-}
 
-#[derive(PartialEq,Clone,Default)]
-pub struct ChainInboundSettings {
-    // message fields
-    pub actors: ::protobuf::RepeatedField<::std::string::String>,
-    // special fields
-    pub unknown_fields: ::protobuf::UnknownFields,
-    pub cached_size: ::protobuf::CachedSize,
-}
+    // Param is passed by value, moved
+    pub fn set_client_cert(&mut self, v: ::std::string::String) {
+        self.client_cert = v;
+    }
 
-impl<'a> ::std::default::Default for &'a ChainInboundSettings {
-    fn default() -> &'a ChainInboundSettings {
-        <ChainInboundSettings as ::protobuf::Message>::default_instance()
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_client_cert(&mut self) -> &mut ::std::string::String {
+        &mut self.client_cert
     }
-}
 
-impl ChainInboundSettings {
-    pub fn new() -> ChainInboundSettings {
-        ::std::default::Default::default()
+    // Take field
+    pub fn take_client_cert(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.client_cert, ::std::string::String::new())
     }
 
-    // repeated string actors = 1;
+    // string client_key = 8;
 
 
-    pub fn get_actors(&self) -> &[::std::string::String] {
-        &self.actors
+    pub fn get_client_key(&self) -> &str {
+        &self.client_key
     }
-    pub fn clear_actors(&mut self) {
-        self.actors.clear();
+    pub fn clear_client_key(&mut self) {
+        self.client_key.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.actors = v;
+    pub fn set_client_key(&mut self, v: ::std::string::String) {
+        self.client_key = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.actors
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_client_key(&mut self) -> &mut ::std::string::String {
+        &mut self.client_key
     }
 
     // Take field
-    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
+    pub fn take_client_key(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.client_key, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for ChainInboundSettings {
+impl ::protobuf::Message for TlsOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -1559,7 +7488,36 @@ impl ::protobuf::Message for ChainInboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.server_name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.alpn)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.early_data = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fingerprint)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.insecure = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.cert_sha256)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.client_cert)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.client_key)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -1573,18 +7531,60 @@ impl ::protobuf::Message for ChainInboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.actors {
-            my_size += ::protobuf::rt::string_size(1, &value);
+        if !self.server_name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.server_name);
+        }
+        for value in &self.alpn {
+            my_size += ::protobuf::rt::string_size(2, &value);
         };
+        if self.early_data != false {
+            my_size += 2;
+        }
+        if !self.fingerprint.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.fingerprint);
+        }
+        if self.insecure != false {
+            my_size += 2;
+        }
+        if !self.cert_sha256.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.cert_sha256);
+        }
+        if !self.client_cert.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.client_cert);
+        }
+        if !self.client_key.is_empty() {
+            my_size += ::protobuf::rt::string_size(8, &self.client_key);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.actors {
-            os.write_string(1, &v)?;
+        if !self.server_name.is_empty() {
+            os.write_string(1, &self.server_name)?;
+        }
+        for v in &self.alpn {
+            os.write_string(2, &v)?;
         };
+        if self.early_data != false {
+            os.write_bool(3, self.early_data)?;
+        }
+        if !self.fingerprint.is_empty() {
+            os.write_string(4, &self.fingerprint)?;
+        }
+        if self.insecure != false {
+            os.write_bool(5, self.insecure)?;
+        }
+        if !self.cert_sha256.is_empty() {
+            os.write_string(6, &self.cert_sha256)?;
+        }
+        if !self.client_cert.is_empty() {
+            os.write_string(7, &self.client_cert)?;
+        }
+        if !self.client_key.is_empty() {
+            os.write_string(8, &self.client_key)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1615,197 +7615,211 @@ impl ::protobuf::Message for ChainInboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> ChainInboundSettings {
-        ChainInboundSettings::new()
+    fn new() -> TlsOutboundSettings {
+        TlsOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "server_name",
+                |m: &TlsOutboundSettings| { &m.server_name },
+                |m: &mut TlsOutboundSettings| { &mut m.server_name },
+            ));
             fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "actors",
-                |m: &ChainInboundSettings| { &m.actors },
-                |m: &mut ChainInboundSettings| { &mut m.actors },
+                "alpn",
+                |m: &TlsOutboundSettings| { &m.alpn },
+                |m: &mut TlsOutboundSettings| { &mut m.alpn },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ChainInboundSettings>(
-                "ChainInboundSettings",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "early_data",
+                |m: &TlsOutboundSettings| { &m.early_data },
+                |m: &mut TlsOutboundSettings| { &mut m.early_data },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "fingerprint",
+                |m: &TlsOutboundSettings| { &m.fingerprint },
+                |m: &mut TlsOutboundSettings| { &mut m.fingerprint },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "insecure",
+                |m: &TlsOutboundSettings| { &m.insecure },
+                |m: &mut TlsOutboundSettings| { &mut m.insecure },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "cert_sha256",
+                |m: &TlsOutboundSettings| { &m.cert_sha256 },
+                |m: &mut TlsOutboundSettings| { &mut m.cert_sha256 },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "client_cert",
+                |m: &TlsOutboundSettings| { &m.client_cert },
+                |m: &mut TlsOutboundSettings| { &mut m.client_cert },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "client_key",
+                |m: &TlsOutboundSettings| { &m.client_key },
+                |m: &mut TlsOutboundSettings| { &mut m.client_key },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TlsOutboundSettings>(
+                "TlsOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static ChainInboundSettings {
-        static instance: ::protobuf::rt::LazyV2<ChainInboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(ChainInboundSettings::new)
+    fn default_instance() -> &'static TlsOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<TlsOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(TlsOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for ChainInboundSettings {
+impl ::protobuf::Clear for TlsOutboundSettings {
     fn clear(&mut self) {
-        self.actors.clear();
+        self.server_name.clear();
+        self.alpn.clear();
+        self.early_data = false;
+        self.fingerprint.clear();
+        self.insecure = false;
+        self.cert_sha256.clear();
+        self.client_cert.clear();
+        self.client_key.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for ChainInboundSettings {
+impl ::std::fmt::Debug for TlsOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for ChainInboundSettings {
+impl ::protobuf::reflect::ProtobufValue for TlsOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct Inbound {
+pub struct WebSocketOutboundSettings {
     // message fields
-    pub tag: ::std::string::String,
-    pub protocol: ::std::string::String,
-    pub address: ::std::string::String,
-    pub port: u32,
-    pub settings: ::std::vec::Vec<u8>,
+    pub path: ::std::string::String,
+    pub headers: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub max_early_data: u32,
+    pub early_data_header_name: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a Inbound {
-    fn default() -> &'a Inbound {
-        <Inbound as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a WebSocketOutboundSettings {
+    fn default() -> &'a WebSocketOutboundSettings {
+        <WebSocketOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl Inbound {
-    pub fn new() -> Inbound {
+impl WebSocketOutboundSettings {
+    pub fn new() -> WebSocketOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string tag = 1;
-
-
-    pub fn get_tag(&self) -> &str {
-        &self.tag
-    }
-    pub fn clear_tag(&mut self) {
-        self.tag.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_tag(&mut self, v: ::std::string::String) {
-        self.tag = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_tag(&mut self) -> &mut ::std::string::String {
-        &mut self.tag
-    }
-
-    // Take field
-    pub fn take_tag(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.tag, ::std::string::String::new())
-    }
-
-    // string protocol = 2;
+    // string path = 1;
 
 
-    pub fn get_protocol(&self) -> &str {
-        &self.protocol
+    pub fn get_path(&self) -> &str {
+        &self.path
     }
-    pub fn clear_protocol(&mut self) {
-        self.protocol.clear();
+    pub fn clear_path(&mut self) {
+        self.path.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_protocol(&mut self, v: ::std::string::String) {
-        self.protocol = v;
+    pub fn set_path(&mut self, v: ::std::string::String) {
+        self.path = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_protocol(&mut self) -> &mut ::std::string::String {
-        &mut self.protocol
+    pub fn mut_path(&mut self) -> &mut ::std::string::String {
+        &mut self.path
     }
 
     // Take field
-    pub fn take_protocol(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.protocol, ::std::string::String::new())
+    pub fn take_path(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.path, ::std::string::String::new())
     }
 
-    // string address = 3;
+    // repeated .WebSocketOutboundSettings.HeadersEntry headers = 2;
 
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_headers(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.headers
     }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    pub fn set_headers(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.headers = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    pub fn mut_headers(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.headers
     }
 
     // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    pub fn take_headers(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.headers, ::std::collections::HashMap::new())
     }
 
-    // uint32 port = 4;
+    // uint32 max_early_data = 3;
 
 
-    pub fn get_port(&self) -> u32 {
-        self.port
+    pub fn get_max_early_data(&self) -> u32 {
+        self.max_early_data
     }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
+    pub fn clear_max_early_data(&mut self) {
+        self.max_early_data = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+    pub fn set_max_early_data(&mut self, v: u32) {
+        self.max_early_data = v;
     }
 
-    // bytes settings = 5;
+    // string early_data_header_name = 4;
 
 
-    pub fn get_settings(&self) -> &[u8] {
-        &self.settings
+    pub fn get_early_data_header_name(&self) -> &str {
+        &self.early_data_header_name
     }
-    pub fn clear_settings(&mut self) {
-        self.settings.clear();
+    pub fn clear_early_data_header_name(&mut self) {
+        self.early_data_header_name.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_settings(&mut self, v: ::std::vec::Vec<u8>) {
-        self.settings = v;
+    pub fn set_early_data_header_name(&mut self, v: ::std::string::String) {
+        self.early_data_header_name = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_settings(&mut self) -> &mut ::std::vec::Vec<u8> {
-        &mut self.settings
+    pub fn mut_early_data_header_name(&mut self) -> &mut ::std::string::String {
+        &mut self.early_data_header_name
     }
 
     // Take field
-    pub fn take_settings(&mut self) -> ::std::vec::Vec<u8> {
-        ::std::mem::replace(&mut self.settings, ::std::vec::Vec::new())
+    pub fn take_early_data_header_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.early_data_header_name, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for Inbound {
+impl ::protobuf::Message for WebSocketOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -1815,23 +7829,20 @@ impl ::protobuf::Message for Inbound {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.tag)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.protocol)?;
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.headers)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
-                },
-                4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint32()?;
-                    self.port = tmp;
+                    self.max_early_data = tmp;
                 },
-                5 => {
-                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.settings)?;
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.early_data_header_name)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -1845,20 +7856,15 @@ impl ::protobuf::Message for Inbound {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.tag.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.tag);
-        }
-        if !self.protocol.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.protocol);
-        }
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.address);
+        if !self.path.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.path);
         }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(4, self.port, ::protobuf::wire_format::WireTypeVarint);
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers);
+        if self.max_early_data != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.max_early_data, ::protobuf::wire_format::WireTypeVarint);
         }
-        if !self.settings.is_empty() {
-            my_size += ::protobuf::rt::bytes_size(5, &self.settings);
+        if !self.early_data_header_name.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.early_data_header_name);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -1866,20 +7872,15 @@ impl ::protobuf::Message for Inbound {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.tag.is_empty() {
-            os.write_string(1, &self.tag)?;
-        }
-        if !self.protocol.is_empty() {
-            os.write_string(2, &self.protocol)?;
-        }
-        if !self.address.is_empty() {
-            os.write_string(3, &self.address)?;
+        if !self.path.is_empty() {
+            os.write_string(1, &self.path)?;
         }
-        if self.port != 0 {
-            os.write_uint32(4, self.port)?;
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers, os)?;
+        if self.max_early_data != 0 {
+            os.write_uint32(3, self.max_early_data)?;
         }
-        if !self.settings.is_empty() {
-            os.write_bytes(5, &self.settings)?;
+        if !self.early_data_header_name.is_empty() {
+            os.write_string(4, &self.early_data_header_name)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -1911,8 +7912,8 @@ impl ::protobuf::Message for Inbound {
         Self::descriptor_static()
     }
 
-    fn new() -> Inbound {
-        Inbound::new()
+    fn new() -> WebSocketOutboundSettings {
+        WebSocketOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -1920,131 +7921,136 @@ impl ::protobuf::Message for Inbound {
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "tag",
-                |m: &Inbound| { &m.tag },
-                |m: &mut Inbound| { &mut m.tag },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "protocol",
-                |m: &Inbound| { &m.protocol },
-                |m: &mut Inbound| { &mut m.protocol },
+                "path",
+                |m: &WebSocketOutboundSettings| { &m.path },
+                |m: &mut WebSocketOutboundSettings| { &mut m.path },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &Inbound| { &m.address },
-                |m: &mut Inbound| { &mut m.address },
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "headers",
+                |m: &WebSocketOutboundSettings| { &m.headers },
+                |m: &mut WebSocketOutboundSettings| { &mut m.headers },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &Inbound| { &m.port },
-                |m: &mut Inbound| { &mut m.port },
+                "max_early_data",
+                |m: &WebSocketOutboundSettings| { &m.max_early_data },
+                |m: &mut WebSocketOutboundSettings| { &mut m.max_early_data },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
-                "settings",
-                |m: &Inbound| { &m.settings },
-                |m: &mut Inbound| { &mut m.settings },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "early_data_header_name",
+                |m: &WebSocketOutboundSettings| { &m.early_data_header_name },
+                |m: &mut WebSocketOutboundSettings| { &mut m.early_data_header_name },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Inbound>(
-                "Inbound",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<WebSocketOutboundSettings>(
+                "WebSocketOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static Inbound {
-        static instance: ::protobuf::rt::LazyV2<Inbound> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(Inbound::new)
+    fn default_instance() -> &'static WebSocketOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<WebSocketOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(WebSocketOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for Inbound {
+impl ::protobuf::Clear for WebSocketOutboundSettings {
     fn clear(&mut self) {
-        self.tag.clear();
-        self.protocol.clear();
-        self.address.clear();
-        self.port = 0;
-        self.settings.clear();
+        self.path.clear();
+        self.headers.clear();
+        self.max_early_data = 0;
+        self.early_data_header_name.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Inbound {
+impl ::std::fmt::Debug for WebSocketOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Inbound {
+impl ::protobuf::reflect::ProtobufValue for WebSocketOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct RedirectOutboundSettings {
+pub struct HTTP2OutboundSettings {
     // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
+    pub path: ::std::string::String,
+    pub host: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a RedirectOutboundSettings {
-    fn default() -> &'a RedirectOutboundSettings {
-        <RedirectOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a HTTP2OutboundSettings {
+    fn default() -> &'a HTTP2OutboundSettings {
+        <HTTP2OutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl RedirectOutboundSettings {
-    pub fn new() -> RedirectOutboundSettings {
+impl HTTP2OutboundSettings {
+    pub fn new() -> HTTP2OutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string address = 1;
+    // string path = 1;
 
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_path(&self) -> &str {
+        &self.path
     }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    pub fn clear_path(&mut self) {
+        self.path.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    pub fn set_path(&mut self, v: ::std::string::String) {
+        self.path = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    pub fn mut_path(&mut self) -> &mut ::std::string::String {
+        &mut self.path
     }
 
     // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    pub fn take_path(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.path, ::std::string::String::new())
     }
 
-    // uint32 port = 2;
+    // string host = 2;
 
 
-    pub fn get_port(&self) -> u32 {
-        self.port
+    pub fn get_host(&self) -> &str {
+        &self.host
     }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
+    pub fn clear_host(&mut self) {
+        self.host.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+    pub fn set_host(&mut self, v: ::std::string::String) {
+        self.host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_host(&mut self) -> &mut ::std::string::String {
+        &mut self.host
+    }
+
+    // Take field
+    pub fn take_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.host, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for RedirectOutboundSettings {
+impl ::protobuf::Message for HTTP2OutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -2054,14 +8060,10 @@ impl ::protobuf::Message for RedirectOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.port = tmp;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.host)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -2075,11 +8077,11 @@ impl ::protobuf::Message for RedirectOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
+        if !self.path.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.path);
         }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        if !self.host.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.host);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -2087,11 +8089,11 @@ impl ::protobuf::Message for RedirectOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
+        if !self.path.is_empty() {
+            os.write_string(1, &self.path)?;
         }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
+        if !self.host.is_empty() {
+            os.write_string(2, &self.host)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -2123,8 +8125,8 @@ impl ::protobuf::Message for RedirectOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> RedirectOutboundSettings {
-        RedirectOutboundSettings::new()
+    fn new() -> HTTP2OutboundSettings {
+        HTTP2OutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -2132,113 +8134,112 @@ impl ::protobuf::Message for RedirectOutboundSettings {
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &RedirectOutboundSettings| { &m.address },
-                |m: &mut RedirectOutboundSettings| { &mut m.address },
+                "path",
+                |m: &HTTP2OutboundSettings| { &m.path },
+                |m: &mut HTTP2OutboundSettings| { &mut m.path },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &RedirectOutboundSettings| { &m.port },
-                |m: &mut RedirectOutboundSettings| { &mut m.port },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "host",
+                |m: &HTTP2OutboundSettings| { &m.host },
+                |m: &mut HTTP2OutboundSettings| { &mut m.host },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RedirectOutboundSettings>(
-                "RedirectOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HTTP2OutboundSettings>(
+                "HTTP2OutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static RedirectOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<RedirectOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(RedirectOutboundSettings::new)
+    fn default_instance() -> &'static HTTP2OutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<HTTP2OutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(HTTP2OutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for RedirectOutboundSettings {
+impl ::protobuf::Clear for HTTP2OutboundSettings {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
+        self.path.clear();
+        self.host.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for RedirectOutboundSettings {
+impl ::std::fmt::Debug for HTTP2OutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for RedirectOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for HTTP2OutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct SocksOutboundSettings {
+pub struct TryAllOutboundSettings {
     // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    pub delay_base: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a SocksOutboundSettings {
-    fn default() -> &'a SocksOutboundSettings {
-        <SocksOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a TryAllOutboundSettings {
+    fn default() -> &'a TryAllOutboundSettings {
+        <TryAllOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl SocksOutboundSettings {
-    pub fn new() -> SocksOutboundSettings {
+impl TryAllOutboundSettings {
+    pub fn new() -> TryAllOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string address = 1;
+    // repeated string actors = 1;
 
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 
-    // uint32 port = 2;
+    // uint32 delay_base = 2;
 
 
-    pub fn get_port(&self) -> u32 {
-        self.port
+    pub fn get_delay_base(&self) -> u32 {
+        self.delay_base
     }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
+    pub fn clear_delay_base(&mut self) {
+        self.delay_base = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+    pub fn set_delay_base(&mut self, v: u32) {
+        self.delay_base = v;
     }
 }
 
-impl ::protobuf::Message for SocksOutboundSettings {
+impl ::protobuf::Message for TryAllOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -2248,14 +8249,14 @@ impl ::protobuf::Message for SocksOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint32()?;
-                    self.port = tmp;
+                    self.delay_base = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -2269,11 +8270,11 @@ impl ::protobuf::Message for SocksOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
-        }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if self.delay_base != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.delay_base, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -2281,11 +8282,11 @@ impl ::protobuf::Message for SocksOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
-        }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
+        if self.delay_base != 0 {
+            os.write_uint32(2, self.delay_base)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -2317,176 +8318,95 @@ impl ::protobuf::Message for SocksOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> SocksOutboundSettings {
-        SocksOutboundSettings::new()
+    fn new() -> TryAllOutboundSettings {
+        TryAllOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &SocksOutboundSettings| { &m.address },
-                |m: &mut SocksOutboundSettings| { &mut m.address },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &TryAllOutboundSettings| { &m.actors },
+                |m: &mut TryAllOutboundSettings| { &mut m.actors },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &SocksOutboundSettings| { &m.port },
-                |m: &mut SocksOutboundSettings| { &mut m.port },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "delay_base",
+                |m: &TryAllOutboundSettings| { &m.delay_base },
+                |m: &mut TryAllOutboundSettings| { &mut m.delay_base },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<SocksOutboundSettings>(
-                "SocksOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TryAllOutboundSettings>(
+                "TryAllOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static SocksOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<SocksOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(SocksOutboundSettings::new)
+    fn default_instance() -> &'static TryAllOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<TryAllOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(TryAllOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for SocksOutboundSettings {
+impl ::protobuf::Clear for TryAllOutboundSettings {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
+        self.actors.clear();
+        self.delay_base = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for SocksOutboundSettings {
+impl ::std::fmt::Debug for TryAllOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for SocksOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for TryAllOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct ShadowsocksOutboundSettings {
+pub struct DropOutboundSettings {
     // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
-    pub method: ::std::string::String,
-    pub password: ::std::string::String,
+    pub reset: bool,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a ShadowsocksOutboundSettings {
-    fn default() -> &'a ShadowsocksOutboundSettings {
-        <ShadowsocksOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a DropOutboundSettings {
+    fn default() -> &'a DropOutboundSettings {
+        <DropOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl ShadowsocksOutboundSettings {
-    pub fn new() -> ShadowsocksOutboundSettings {
+impl DropOutboundSettings {
+    pub fn new() -> DropOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string address = 1;
-
-
-    pub fn get_address(&self) -> &str {
-        &self.address
-    }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
-    }
-
-    // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
-    }
-
-    // uint32 port = 2;
-
-
-    pub fn get_port(&self) -> u32 {
-        self.port
-    }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
-    }
-
-    // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
-    }
-
-    // string method = 3;
-
-
-    pub fn get_method(&self) -> &str {
-        &self.method
-    }
-    pub fn clear_method(&mut self) {
-        self.method.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_method(&mut self, v: ::std::string::String) {
-        self.method = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_method(&mut self) -> &mut ::std::string::String {
-        &mut self.method
-    }
-
-    // Take field
-    pub fn take_method(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.method, ::std::string::String::new())
-    }
-
-    // string password = 4;
+    // bool reset = 1;
 
 
-    pub fn get_password(&self) -> &str {
-        &self.password
+    pub fn get_reset(&self) -> bool {
+        self.reset
     }
-    pub fn clear_password(&mut self) {
-        self.password.clear();
+    pub fn clear_reset(&mut self) {
+        self.reset = false;
     }
 
     // Param is passed by value, moved
-    pub fn set_password(&mut self, v: ::std::string::String) {
-        self.password = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_password(&mut self) -> &mut ::std::string::String {
-        &mut self.password
-    }
-
-    // Take field
-    pub fn take_password(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    pub fn set_reset(&mut self, v: bool) {
+        self.reset = v;
     }
 }
 
-impl ::protobuf::Message for ShadowsocksOutboundSettings {
+impl ::protobuf::Message for DropOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -2496,20 +8416,11 @@ impl ::protobuf::Message for ShadowsocksOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
-                },
-                2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_uint32()?;
-                    self.port = tmp;
-                },
-                3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.method)?;
-                },
-                4 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                    let tmp = is.read_bool()?;
+                    self.reset = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -2523,17 +8434,8 @@ impl ::protobuf::Message for ShadowsocksOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
-        }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
-        }
-        if !self.method.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.method);
-        }
-        if !self.password.is_empty() {
-            my_size += ::protobuf::rt::string_size(4, &self.password);
+        if self.reset != false {
+            my_size += 2;
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -2541,17 +8443,8 @@ impl ::protobuf::Message for ShadowsocksOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
-        }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
-        }
-        if !self.method.is_empty() {
-            os.write_string(3, &self.method)?;
-        }
-        if !self.password.is_empty() {
-            os.write_string(4, &self.password)?;
+        if self.reset != false {
+            os.write_bool(1, self.reset)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -2583,161 +8476,115 @@ impl ::protobuf::Message for ShadowsocksOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> ShadowsocksOutboundSettings {
-        ShadowsocksOutboundSettings::new()
+    fn new() -> DropOutboundSettings {
+        DropOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &ShadowsocksOutboundSettings| { &m.address },
-                |m: &mut ShadowsocksOutboundSettings| { &mut m.address },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &ShadowsocksOutboundSettings| { &m.port },
-                |m: &mut ShadowsocksOutboundSettings| { &mut m.port },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "method",
-                |m: &ShadowsocksOutboundSettings| { &m.method },
-                |m: &mut ShadowsocksOutboundSettings| { &mut m.method },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "password",
-                |m: &ShadowsocksOutboundSettings| { &m.password },
-                |m: &mut ShadowsocksOutboundSettings| { &mut m.password },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "reset",
+                |m: &DropOutboundSettings| { &m.reset },
+                |m: &mut DropOutboundSettings| { &mut m.reset },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ShadowsocksOutboundSettings>(
-                "ShadowsocksOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<DropOutboundSettings>(
+                "DropOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static ShadowsocksOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<ShadowsocksOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(ShadowsocksOutboundSettings::new)
+    fn default_instance() -> &'static DropOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<DropOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(DropOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for ShadowsocksOutboundSettings {
+impl ::protobuf::Clear for DropOutboundSettings {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
-        self.method.clear();
-        self.password.clear();
+        self.reset = false;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for ShadowsocksOutboundSettings {
+impl ::std::fmt::Debug for DropOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for ShadowsocksOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for DropOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct TrojanOutboundSettings {
+pub struct RandomOutboundSettings {
     // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
-    pub password: ::std::string::String,
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    pub sticky_timeout: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a TrojanOutboundSettings {
-    fn default() -> &'a TrojanOutboundSettings {
-        <TrojanOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a RandomOutboundSettings {
+    fn default() -> &'a RandomOutboundSettings {
+        <RandomOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl TrojanOutboundSettings {
-    pub fn new() -> TrojanOutboundSettings {
+impl RandomOutboundSettings {
+    pub fn new() -> RandomOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string address = 1;
+    // repeated string actors = 1;
 
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
-    }
-
-    // uint32 port = 2;
-
-
-    pub fn get_port(&self) -> u32 {
-        self.port
-    }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
-    }
-
-    // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 
-    // string password = 3;
-
-
-    pub fn get_password(&self) -> &str {
-        &self.password
-    }
-    pub fn clear_password(&mut self) {
-        self.password.clear();
-    }
+    // uint32 sticky_timeout = 2;
 
-    // Param is passed by value, moved
-    pub fn set_password(&mut self, v: ::std::string::String) {
-        self.password = v;
-    }
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_password(&mut self) -> &mut ::std::string::String {
-        &mut self.password
+    pub fn get_sticky_timeout(&self) -> u32 {
+        self.sticky_timeout
+    }
+    pub fn clear_sticky_timeout(&mut self) {
+        self.sticky_timeout = 0;
     }
 
-    // Take field
-    pub fn take_password(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.password, ::std::string::String::new())
+    // Param is passed by value, moved
+    pub fn set_sticky_timeout(&mut self, v: u32) {
+        self.sticky_timeout = v;
     }
 }
 
-impl ::protobuf::Message for TrojanOutboundSettings {
+impl ::protobuf::Message for RandomOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -2747,17 +8594,14 @@ impl ::protobuf::Message for TrojanOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint32()?;
-                    self.port = tmp;
-                },
-                3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.password)?;
+                    self.sticky_timeout = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -2771,14 +8615,11 @@ impl ::protobuf::Message for TrojanOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
-        }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
-        }
-        if !self.password.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.password);
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if self.sticky_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.sticky_timeout, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -2786,14 +8627,11 @@ impl ::protobuf::Message for TrojanOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
-        }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
-        }
-        if !self.password.is_empty() {
-            os.write_string(3, &self.password)?;
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
+        if self.sticky_timeout != 0 {
+            os.write_uint32(2, self.sticky_timeout)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -2825,182 +8663,163 @@ impl ::protobuf::Message for TrojanOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> TrojanOutboundSettings {
-        TrojanOutboundSettings::new()
+    fn new() -> RandomOutboundSettings {
+        RandomOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &TrojanOutboundSettings| { &m.address },
-                |m: &mut TrojanOutboundSettings| { &mut m.address },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &RandomOutboundSettings| { &m.actors },
+                |m: &mut RandomOutboundSettings| { &mut m.actors },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &TrojanOutboundSettings| { &m.port },
-                |m: &mut TrojanOutboundSettings| { &mut m.port },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "password",
-                |m: &TrojanOutboundSettings| { &m.password },
-                |m: &mut TrojanOutboundSettings| { &mut m.password },
+                "sticky_timeout",
+                |m: &RandomOutboundSettings| { &m.sticky_timeout },
+                |m: &mut RandomOutboundSettings| { &mut m.sticky_timeout },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TrojanOutboundSettings>(
-                "TrojanOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RandomOutboundSettings>(
+                "RandomOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static TrojanOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<TrojanOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(TrojanOutboundSettings::new)
+    fn default_instance() -> &'static RandomOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<RandomOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(RandomOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for TrojanOutboundSettings {
+impl ::protobuf::Clear for RandomOutboundSettings {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
-        self.password.clear();
+        self.actors.clear();
+        self.sticky_timeout = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for TrojanOutboundSettings {
+impl ::std::fmt::Debug for RandomOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for TrojanOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for RandomOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct VMessOutboundSettings {
+pub struct BalanceOutboundSettings {
     // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
-    pub uuid: ::std::string::String,
-    pub security: ::std::string::String,
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    pub strategy: BalanceOutboundSettings_Strategy,
+    pub weights: ::std::vec::Vec<u32>,
+    pub sticky_timeout: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a VMessOutboundSettings {
-    fn default() -> &'a VMessOutboundSettings {
-        <VMessOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a BalanceOutboundSettings {
+    fn default() -> &'a BalanceOutboundSettings {
+        <BalanceOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl VMessOutboundSettings {
-    pub fn new() -> VMessOutboundSettings {
+impl BalanceOutboundSettings {
+    pub fn new() -> BalanceOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string address = 1;
+    // repeated string actors = 1;
 
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 
-    // uint32 port = 2;
+    // .BalanceOutboundSettings.Strategy strategy = 2;
 
 
-    pub fn get_port(&self) -> u32 {
-        self.port
+    pub fn get_strategy(&self) -> BalanceOutboundSettings_Strategy {
+        self.strategy
     }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
+    pub fn clear_strategy(&mut self) {
+        self.strategy = BalanceOutboundSettings_Strategy::ROUND_ROBIN;
     }
 
     // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+    pub fn set_strategy(&mut self, v: BalanceOutboundSettings_Strategy) {
+        self.strategy = v;
     }
 
-    // string uuid = 3;
+    // repeated uint32 weights = 3;
 
 
-    pub fn get_uuid(&self) -> &str {
-        &self.uuid
+    pub fn get_weights(&self) -> &[u32] {
+        &self.weights
     }
-    pub fn clear_uuid(&mut self) {
-        self.uuid.clear();
+    pub fn clear_weights(&mut self) {
+        self.weights.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_uuid(&mut self, v: ::std::string::String) {
-        self.uuid = v;
+    pub fn set_weights(&mut self, v: ::std::vec::Vec<u32>) {
+        self.weights = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_uuid(&mut self) -> &mut ::std::string::String {
-        &mut self.uuid
+    pub fn mut_weights(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.weights
     }
 
     // Take field
-    pub fn take_uuid(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.uuid, ::std::string::String::new())
+    pub fn take_weights(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.weights, ::std::vec::Vec::new())
     }
 
-    // string security = 4;
+    // uint32 sticky_timeout = 4;
 
 
-    pub fn get_security(&self) -> &str {
-        &self.security
+    pub fn get_sticky_timeout(&self) -> u32 {
+        self.sticky_timeout
     }
-    pub fn clear_security(&mut self) {
-        self.security.clear();
+    pub fn clear_sticky_timeout(&mut self) {
+        self.sticky_timeout = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_security(&mut self, v: ::std::string::String) {
-        self.security = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_security(&mut self) -> &mut ::std::string::String {
-        &mut self.security
-    }
-
-    // Take field
-    pub fn take_security(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.security, ::std::string::String::new())
+    pub fn set_sticky_timeout(&mut self, v: u32) {
+        self.sticky_timeout = v;
     }
 }
 
-impl ::protobuf::Message for VMessOutboundSettings {
+impl ::protobuf::Message for BalanceOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -3010,20 +8829,20 @@ impl ::protobuf::Message for VMessOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.port = tmp;
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.strategy, 2, &mut self.unknown_fields)?
                 },
                 3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.uuid)?;
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.weights)?;
                 },
                 4 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.security)?;
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.sticky_timeout = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -3037,17 +8856,17 @@ impl ::protobuf::Message for VMessOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
-        }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
-        }
-        if !self.uuid.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.uuid);
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if self.strategy != BalanceOutboundSettings_Strategy::ROUND_ROBIN {
+            my_size += ::protobuf::rt::enum_size(2, self.strategy);
         }
-        if !self.security.is_empty() {
-            my_size += ::protobuf::rt::string_size(4, &self.security);
+        for value in &self.weights {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if self.sticky_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.sticky_timeout, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -3055,17 +8874,17 @@ impl ::protobuf::Message for VMessOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
-        }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
-        }
-        if !self.uuid.is_empty() {
-            os.write_string(3, &self.uuid)?;
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
+        if self.strategy != BalanceOutboundSettings_Strategy::ROUND_ROBIN {
+            os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.strategy))?;
         }
-        if !self.security.is_empty() {
-            os.write_string(4, &self.security)?;
+        for v in &self.weights {
+            os.write_uint32(3, *v)?;
+        };
+        if self.sticky_timeout != 0 {
+            os.write_uint32(4, self.sticky_timeout)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -3097,161 +8916,170 @@ impl ::protobuf::Message for VMessOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> VMessOutboundSettings {
-        VMessOutboundSettings::new()
+    fn new() -> BalanceOutboundSettings {
+        BalanceOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &VMessOutboundSettings| { &m.address },
-                |m: &mut VMessOutboundSettings| { &mut m.address },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &BalanceOutboundSettings| { &m.actors },
+                |m: &mut BalanceOutboundSettings| { &mut m.actors },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &VMessOutboundSettings| { &m.port },
-                |m: &mut VMessOutboundSettings| { &mut m.port },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<BalanceOutboundSettings_Strategy>>(
+                "strategy",
+                |m: &BalanceOutboundSettings| { &m.strategy },
+                |m: &mut BalanceOutboundSettings| { &mut m.strategy },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "uuid",
-                |m: &VMessOutboundSettings| { &m.uuid },
-                |m: &mut VMessOutboundSettings| { &mut m.uuid },
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "weights",
+                |m: &BalanceOutboundSettings| { &m.weights },
+                |m: &mut BalanceOutboundSettings| { &mut m.weights },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "security",
-                |m: &VMessOutboundSettings| { &m.security },
-                |m: &mut VMessOutboundSettings| { &mut m.security },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "sticky_timeout",
+                |m: &BalanceOutboundSettings| { &m.sticky_timeout },
+                |m: &mut BalanceOutboundSettings| { &mut m.sticky_timeout },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VMessOutboundSettings>(
-                "VMessOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<BalanceOutboundSettings>(
+                "BalanceOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static VMessOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<VMessOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(VMessOutboundSettings::new)
+    fn default_instance() -> &'static BalanceOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<BalanceOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(BalanceOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for VMessOutboundSettings {
+impl ::protobuf::Clear for BalanceOutboundSettings {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
-        self.uuid.clear();
-        self.security.clear();
+        self.actors.clear();
+        self.strategy = BalanceOutboundSettings_Strategy::ROUND_ROBIN;
+        self.weights.clear();
+        self.sticky_timeout = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for VMessOutboundSettings {
+impl ::std::fmt::Debug for BalanceOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for VMessOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for BalanceOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
-#[derive(PartialEq,Clone,Default)]
-pub struct VLessOutboundSettings {
-    // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
-    pub uuid: ::std::string::String,
-    // special fields
-    pub unknown_fields: ::protobuf::UnknownFields,
-    pub cached_size: ::protobuf::CachedSize,
-}
-
-impl<'a> ::std::default::Default for &'a VLessOutboundSettings {
-    fn default() -> &'a VLessOutboundSettings {
-        <VLessOutboundSettings as ::protobuf::Message>::default_instance()
-    }
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum BalanceOutboundSettings_Strategy {
+    ROUND_ROBIN = 0,
+    WEIGHTED = 1,
+    CONSISTENT_HASH = 2,
 }
 
-impl VLessOutboundSettings {
-    pub fn new() -> VLessOutboundSettings {
-        ::std::default::Default::default()
+impl ::protobuf::ProtobufEnum for BalanceOutboundSettings_Strategy {
+    fn value(&self) -> i32 {
+        *self as i32
     }
 
-    // string address = 1;
-
-
-    pub fn get_address(&self) -> &str {
-        &self.address
-    }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
+    fn from_i32(value: i32) -> ::std::option::Option<BalanceOutboundSettings_Strategy> {
+        match value {
+            0 => ::std::option::Option::Some(BalanceOutboundSettings_Strategy::ROUND_ROBIN),
+            1 => ::std::option::Option::Some(BalanceOutboundSettings_Strategy::WEIGHTED),
+            2 => ::std::option::Option::Some(BalanceOutboundSettings_Strategy::CONSISTENT_HASH),
+            _ => ::std::option::Option::None
+        }
     }
 
-    // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
+    fn values() -> &'static [Self] {
+        static values: &'static [BalanceOutboundSettings_Strategy] = &[
+            BalanceOutboundSettings_Strategy::ROUND_ROBIN,
+            BalanceOutboundSettings_Strategy::WEIGHTED,
+            BalanceOutboundSettings_Strategy::CONSISTENT_HASH,
+        ];
+        values
     }
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<BalanceOutboundSettings_Strategy>("BalanceOutboundSettings.Strategy", file_descriptor_proto())
+        })
     }
+}
 
-    // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+impl ::std::marker::Copy for BalanceOutboundSettings_Strategy {
+}
+
+impl ::std::default::Default for BalanceOutboundSettings_Strategy {
+    fn default() -> Self {
+        BalanceOutboundSettings_Strategy::ROUND_ROBIN
     }
+}
 
-    // uint32 port = 2;
+impl ::protobuf::reflect::ProtobufValue for BalanceOutboundSettings_Strategy {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
 
+#[derive(PartialEq,Clone,Default)]
+pub struct SelectOutboundSettings {
+    // message fields
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
 
-    pub fn get_port(&self) -> u32 {
-        self.port
-    }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
+impl<'a> ::std::default::Default for &'a SelectOutboundSettings {
+    fn default() -> &'a SelectOutboundSettings {
+        <SelectOutboundSettings as ::protobuf::Message>::default_instance()
     }
+}
 
-    // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+impl SelectOutboundSettings {
+    pub fn new() -> SelectOutboundSettings {
+        ::std::default::Default::default()
     }
 
-    // string uuid = 3;
+    // repeated string actors = 1;
 
 
-    pub fn get_uuid(&self) -> &str {
-        &self.uuid
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_uuid(&mut self) {
-        self.uuid.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_uuid(&mut self, v: ::std::string::String) {
-        self.uuid = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_uuid(&mut self) -> &mut ::std::string::String {
-        &mut self.uuid
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_uuid(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.uuid, ::std::string::String::new())
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 }
 
-impl ::protobuf::Message for VLessOutboundSettings {
+impl ::protobuf::Message for SelectOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -3261,17 +9089,7 @@ impl ::protobuf::Message for VLessOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
-                },
-                2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.port = tmp;
-                },
-                3 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.uuid)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -3285,30 +9103,18 @@ impl ::protobuf::Message for VLessOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
-        }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
-        }
-        if !self.uuid.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.uuid);
-        }
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
-        }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
-        }
-        if !self.uuid.is_empty() {
-            os.write_string(3, &self.uuid)?;
-        }
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -3339,138 +9145,158 @@ impl ::protobuf::Message for VLessOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> VLessOutboundSettings {
-        VLessOutboundSettings::new()
+    fn new() -> SelectOutboundSettings {
+        SelectOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &VLessOutboundSettings| { &m.address },
-                |m: &mut VLessOutboundSettings| { &mut m.address },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &VLessOutboundSettings| { &m.port },
-                |m: &mut VLessOutboundSettings| { &mut m.port },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "uuid",
-                |m: &VLessOutboundSettings| { &m.uuid },
-                |m: &mut VLessOutboundSettings| { &mut m.uuid },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &SelectOutboundSettings| { &m.actors },
+                |m: &mut SelectOutboundSettings| { &mut m.actors },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VLessOutboundSettings>(
-                "VLessOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<SelectOutboundSettings>(
+                "SelectOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static VLessOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<VLessOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(VLessOutboundSettings::new)
+    fn default_instance() -> &'static SelectOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<SelectOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(SelectOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for VLessOutboundSettings {
+impl ::protobuf::Clear for SelectOutboundSettings {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
-        self.uuid.clear();
+        self.actors.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for VLessOutboundSettings {
+impl ::std::fmt::Debug for SelectOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for VLessOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for SelectOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct TlsOutboundSettings {
+pub struct UrlTestOutboundSettings {
     // message fields
-    pub server_name: ::std::string::String,
-    pub alpn: ::protobuf::RepeatedField<::std::string::String>,
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    pub url: ::std::string::String,
+    pub interval: u32,
+    pub tolerance: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a TlsOutboundSettings {
-    fn default() -> &'a TlsOutboundSettings {
-        <TlsOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a UrlTestOutboundSettings {
+    fn default() -> &'a UrlTestOutboundSettings {
+        <UrlTestOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl TlsOutboundSettings {
-    pub fn new() -> TlsOutboundSettings {
+impl UrlTestOutboundSettings {
+    pub fn new() -> UrlTestOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string server_name = 1;
+    // repeated string actors = 1;
 
 
-    pub fn get_server_name(&self) -> &str {
-        &self.server_name
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_server_name(&mut self) {
-        self.server_name.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_server_name(&mut self, v: ::std::string::String) {
-        self.server_name = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_server_name(&mut self) -> &mut ::std::string::String {
-        &mut self.server_name
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_server_name(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.server_name, ::std::string::String::new())
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 
-    // repeated string alpn = 2;
+    // string url = 2;
 
 
-    pub fn get_alpn(&self) -> &[::std::string::String] {
-        &self.alpn
+    pub fn get_url(&self) -> &str {
+        &self.url
     }
-    pub fn clear_alpn(&mut self) {
-        self.alpn.clear();
+    pub fn clear_url(&mut self) {
+        self.url.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_alpn(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.alpn = v;
+    pub fn set_url(&mut self, v: ::std::string::String) {
+        self.url = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_alpn(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.alpn
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_url(&mut self) -> &mut ::std::string::String {
+        &mut self.url
     }
 
     // Take field
-    pub fn take_alpn(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.alpn, ::protobuf::RepeatedField::new())
+    pub fn take_url(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.url, ::std::string::String::new())
+    }
+
+    // uint32 interval = 3;
+
+
+    pub fn get_interval(&self) -> u32 {
+        self.interval
+    }
+    pub fn clear_interval(&mut self) {
+        self.interval = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_interval(&mut self, v: u32) {
+        self.interval = v;
+    }
+
+    // uint32 tolerance = 4;
+
+
+    pub fn get_tolerance(&self) -> u32 {
+        self.tolerance
+    }
+    pub fn clear_tolerance(&mut self) {
+        self.tolerance = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tolerance(&mut self, v: u32) {
+        self.tolerance = v;
     }
 }
 
-impl ::protobuf::Message for TlsOutboundSettings {
+impl ::protobuf::Message for UrlTestOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -3480,10 +9306,24 @@ impl ::protobuf::Message for TlsOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.server_name)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.alpn)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.url)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.interval = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.tolerance = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -3497,24 +9337,36 @@ impl ::protobuf::Message for TlsOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.server_name.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.server_name);
-        }
-        for value in &self.alpn {
-            my_size += ::protobuf::rt::string_size(2, &value);
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
         };
+        if !self.url.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.url);
+        }
+        if self.interval != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.interval, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.tolerance != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.tolerance, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.server_name.is_empty() {
-            os.write_string(1, &self.server_name)?;
-        }
-        for v in &self.alpn {
-            os.write_string(2, &v)?;
+        for v in &self.actors {
+            os.write_string(1, &v)?;
         };
+        if !self.url.is_empty() {
+            os.write_string(2, &self.url)?;
+        }
+        if self.interval != 0 {
+            os.write_uint32(3, self.interval)?;
+        }
+        if self.tolerance != 0 {
+            os.write_uint32(4, self.tolerance)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -3545,132 +9397,117 @@ impl ::protobuf::Message for TlsOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> TlsOutboundSettings {
-        TlsOutboundSettings::new()
+    fn new() -> UrlTestOutboundSettings {
+        UrlTestOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &UrlTestOutboundSettings| { &m.actors },
+                |m: &mut UrlTestOutboundSettings| { &mut m.actors },
+            ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "server_name",
-                |m: &TlsOutboundSettings| { &m.server_name },
-                |m: &mut TlsOutboundSettings| { &mut m.server_name },
+                "url",
+                |m: &UrlTestOutboundSettings| { &m.url },
+                |m: &mut UrlTestOutboundSettings| { &mut m.url },
             ));
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "alpn",
-                |m: &TlsOutboundSettings| { &m.alpn },
-                |m: &mut TlsOutboundSettings| { &mut m.alpn },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "interval",
+                |m: &UrlTestOutboundSettings| { &m.interval },
+                |m: &mut UrlTestOutboundSettings| { &mut m.interval },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TlsOutboundSettings>(
-                "TlsOutboundSettings",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "tolerance",
+                |m: &UrlTestOutboundSettings| { &m.tolerance },
+                |m: &mut UrlTestOutboundSettings| { &mut m.tolerance },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<UrlTestOutboundSettings>(
+                "UrlTestOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static TlsOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<TlsOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(TlsOutboundSettings::new)
+    fn default_instance() -> &'static UrlTestOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<UrlTestOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(UrlTestOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for TlsOutboundSettings {
+impl ::protobuf::Clear for UrlTestOutboundSettings {
     fn clear(&mut self) {
-        self.server_name.clear();
-        self.alpn.clear();
+        self.actors.clear();
+        self.url.clear();
+        self.interval = 0;
+        self.tolerance = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for TlsOutboundSettings {
+impl ::std::fmt::Debug for UrlTestOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for TlsOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for UrlTestOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct WebSocketOutboundSettings {
+pub struct ChainOutboundSettings {
     // message fields
-    pub path: ::std::string::String,
-    pub headers: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a WebSocketOutboundSettings {
-    fn default() -> &'a WebSocketOutboundSettings {
-        <WebSocketOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a ChainOutboundSettings {
+    fn default() -> &'a ChainOutboundSettings {
+        <ChainOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl WebSocketOutboundSettings {
-    pub fn new() -> WebSocketOutboundSettings {
+impl ChainOutboundSettings {
+    pub fn new() -> ChainOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string path = 1;
-
-
-    pub fn get_path(&self) -> &str {
-        &self.path
-    }
-    pub fn clear_path(&mut self) {
-        self.path.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_path(&mut self, v: ::std::string::String) {
-        self.path = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_path(&mut self) -> &mut ::std::string::String {
-        &mut self.path
-    }
-
-    // Take field
-    pub fn take_path(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.path, ::std::string::String::new())
-    }
-
-    // repeated .WebSocketOutboundSettings.HeadersEntry headers = 2;
+    // repeated string actors = 1;
 
 
-    pub fn get_headers(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
-        &self.headers
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_headers(&mut self) {
-        self.headers.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_headers(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
-        self.headers = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_headers(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
-        &mut self.headers
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_headers(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
-        ::std::mem::replace(&mut self.headers, ::std::collections::HashMap::new())
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 }
 
-impl ::protobuf::Message for WebSocketOutboundSettings {
+impl ::protobuf::Message for ChainOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -3680,10 +9517,7 @@ impl ::protobuf::Message for WebSocketOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
-                },
-                2 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.headers)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -3697,20 +9531,18 @@ impl ::protobuf::Message for WebSocketOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.path.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.path);
-        }
-        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers);
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.path.is_empty() {
-            os.write_string(1, &self.path)?;
-        }
-        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers, os)?;
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -3741,133 +9573,115 @@ impl ::protobuf::Message for WebSocketOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> WebSocketOutboundSettings {
-        WebSocketOutboundSettings::new()
+    fn new() -> ChainOutboundSettings {
+        ChainOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "path",
-                |m: &WebSocketOutboundSettings| { &m.path },
-                |m: &mut WebSocketOutboundSettings| { &mut m.path },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
-                "headers",
-                |m: &WebSocketOutboundSettings| { &m.headers },
-                |m: &mut WebSocketOutboundSettings| { &mut m.headers },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &ChainOutboundSettings| { &m.actors },
+                |m: &mut ChainOutboundSettings| { &mut m.actors },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<WebSocketOutboundSettings>(
-                "WebSocketOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ChainOutboundSettings>(
+                "ChainOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static WebSocketOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<WebSocketOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(WebSocketOutboundSettings::new)
+    fn default_instance() -> &'static ChainOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<ChainOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(ChainOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for WebSocketOutboundSettings {
+impl ::protobuf::Clear for ChainOutboundSettings {
     fn clear(&mut self) {
-        self.path.clear();
-        self.headers.clear();
+        self.actors.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for WebSocketOutboundSettings {
+impl ::std::fmt::Debug for ChainOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for WebSocketOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for ChainOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct HTTP2OutboundSettings {
+pub struct RetryOutboundSettings {
     // message fields
-    pub path: ::std::string::String,
-    pub host: ::std::string::String,
+    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    pub attempts: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a HTTP2OutboundSettings {
-    fn default() -> &'a HTTP2OutboundSettings {
-        <HTTP2OutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a RetryOutboundSettings {
+    fn default() -> &'a RetryOutboundSettings {
+        <RetryOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl HTTP2OutboundSettings {
-    pub fn new() -> HTTP2OutboundSettings {
+impl RetryOutboundSettings {
+    pub fn new() -> RetryOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // string path = 1;
+    // repeated string actors = 1;
 
 
-    pub fn get_path(&self) -> &str {
-        &self.path
+    pub fn get_actors(&self) -> &[::std::string::String] {
+        &self.actors
     }
-    pub fn clear_path(&mut self) {
-        self.path.clear();
+    pub fn clear_actors(&mut self) {
+        self.actors.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_path(&mut self, v: ::std::string::String) {
-        self.path = v;
+    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.actors = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_path(&mut self) -> &mut ::std::string::String {
-        &mut self.path
+    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.actors
     }
 
     // Take field
-    pub fn take_path(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.path, ::std::string::String::new())
+    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 
-    // string host = 2;
+    // uint32 attempts = 2;
 
 
-    pub fn get_host(&self) -> &str {
-        &self.host
+    pub fn get_attempts(&self) -> u32 {
+        self.attempts
     }
-    pub fn clear_host(&mut self) {
-        self.host.clear();
+    pub fn clear_attempts(&mut self) {
+        self.attempts = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_host(&mut self, v: ::std::string::String) {
-        self.host = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_host(&mut self) -> &mut ::std::string::String {
-        &mut self.host
-    }
-
-    // Take field
-    pub fn take_host(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.host, ::std::string::String::new())
+    pub fn set_attempts(&mut self, v: u32) {
+        self.attempts = v;
     }
 }
 
-impl ::protobuf::Message for HTTP2OutboundSettings {
+impl ::protobuf::Message for RetryOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -3877,10 +9691,14 @@ impl ::protobuf::Message for HTTP2OutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.host)?;
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.attempts = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -3894,11 +9712,11 @@ impl ::protobuf::Message for HTTP2OutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.path.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.path);
-        }
-        if !self.host.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.host);
+        for value in &self.actors {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if self.attempts != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.attempts, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -3906,11 +9724,11 @@ impl ::protobuf::Message for HTTP2OutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.path.is_empty() {
-            os.write_string(1, &self.path)?;
-        }
-        if !self.host.is_empty() {
-            os.write_string(2, &self.host)?;
+        for v in &self.actors {
+            os.write_string(1, &v)?;
+        };
+        if self.attempts != 0 {
+            os.write_uint32(2, self.attempts)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -3942,76 +9760,83 @@ impl ::protobuf::Message for HTTP2OutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> HTTP2OutboundSettings {
-        HTTP2OutboundSettings::new()
+    fn new() -> RetryOutboundSettings {
+        RetryOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "path",
-                |m: &HTTP2OutboundSettings| { &m.path },
-                |m: &mut HTTP2OutboundSettings| { &mut m.path },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "actors",
+                |m: &RetryOutboundSettings| { &m.actors },
+                |m: &mut RetryOutboundSettings| { &mut m.actors },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "host",
-                |m: &HTTP2OutboundSettings| { &m.host },
-                |m: &mut HTTP2OutboundSettings| { &mut m.host },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "attempts",
+                |m: &RetryOutboundSettings| { &m.attempts },
+                |m: &mut RetryOutboundSettings| { &mut m.attempts },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HTTP2OutboundSettings>(
-                "HTTP2OutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RetryOutboundSettings>(
+                "RetryOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static HTTP2OutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<HTTP2OutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(HTTP2OutboundSettings::new)
+    fn default_instance() -> &'static RetryOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<RetryOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(RetryOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for HTTP2OutboundSettings {
+impl ::protobuf::Clear for RetryOutboundSettings {
     fn clear(&mut self) {
-        self.path.clear();
-        self.host.clear();
+        self.actors.clear();
+        self.attempts = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for HTTP2OutboundSettings {
+impl ::std::fmt::Debug for RetryOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for HTTP2OutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for RetryOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct TryAllOutboundSettings {
+pub struct FailOverOutboundSettings {
     // message fields
     pub actors: ::protobuf::RepeatedField<::std::string::String>,
-    pub delay_base: u32,
+    pub fail_timeout: u32,
+    pub health_check: bool,
+    pub check_interval: u32,
+    pub failover: bool,
+    pub fallback_cache: bool,
+    pub cache_size: u32,
+    pub cache_timeout: u32,
+    pub priorities: ::std::vec::Vec<u32>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a TryAllOutboundSettings {
-    fn default() -> &'a TryAllOutboundSettings {
-        <TryAllOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a FailOverOutboundSettings {
+    fn default() -> &'a FailOverOutboundSettings {
+        <FailOverOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl TryAllOutboundSettings {
-    pub fn new() -> TryAllOutboundSettings {
+impl FailOverOutboundSettings {
+    pub fn new() -> FailOverOutboundSettings {
         ::std::default::Default::default()
     }
 
@@ -4040,23 +9865,138 @@ impl TryAllOutboundSettings {
         ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
     }
 
-    // uint32 delay_base = 2;
+    // uint32 fail_timeout = 2;
 
 
-    pub fn get_delay_base(&self) -> u32 {
-        self.delay_base
+    pub fn get_fail_timeout(&self) -> u32 {
+        self.fail_timeout
     }
-    pub fn clear_delay_base(&mut self) {
-        self.delay_base = 0;
+    pub fn clear_fail_timeout(&mut self) {
+        self.fail_timeout = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_delay_base(&mut self, v: u32) {
-        self.delay_base = v;
+    pub fn set_fail_timeout(&mut self, v: u32) {
+        self.fail_timeout = v;
+    }
+
+    // bool health_check = 3;
+
+
+    pub fn get_health_check(&self) -> bool {
+        self.health_check
+    }
+    pub fn clear_health_check(&mut self) {
+        self.health_check = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_health_check(&mut self, v: bool) {
+        self.health_check = v;
+    }
+
+    // uint32 check_interval = 4;
+
+
+    pub fn get_check_interval(&self) -> u32 {
+        self.check_interval
+    }
+    pub fn clear_check_interval(&mut self) {
+        self.check_interval = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_check_interval(&mut self, v: u32) {
+        self.check_interval = v;
+    }
+
+    // bool failover = 5;
+
+
+    pub fn get_failover(&self) -> bool {
+        self.failover
+    }
+    pub fn clear_failover(&mut self) {
+        self.failover = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_failover(&mut self, v: bool) {
+        self.failover = v;
+    }
+
+    // bool fallback_cache = 6;
+
+
+    pub fn get_fallback_cache(&self) -> bool {
+        self.fallback_cache
+    }
+    pub fn clear_fallback_cache(&mut self) {
+        self.fallback_cache = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fallback_cache(&mut self, v: bool) {
+        self.fallback_cache = v;
+    }
+
+    // uint32 cache_size = 7;
+
+
+    pub fn get_cache_size(&self) -> u32 {
+        self.cache_size
+    }
+    pub fn clear_cache_size(&mut self) {
+        self.cache_size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cache_size(&mut self, v: u32) {
+        self.cache_size = v;
+    }
+
+    // uint32 cache_timeout = 8;
+
+
+    pub fn get_cache_timeout(&self) -> u32 {
+        self.cache_timeout
+    }
+    pub fn clear_cache_timeout(&mut self) {
+        self.cache_timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cache_timeout(&mut self, v: u32) {
+        self.cache_timeout = v;
+    }
+
+    // repeated uint32 priorities = 9;
+
+
+    pub fn get_priorities(&self) -> &[u32] {
+        &self.priorities
+    }
+    pub fn clear_priorities(&mut self) {
+        self.priorities.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_priorities(&mut self, v: ::std::vec::Vec<u32>) {
+        self.priorities = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_priorities(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.priorities
+    }
+
+    // Take field
+    pub fn take_priorities(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.priorities, ::std::vec::Vec::new())
     }
 }
 
-impl ::protobuf::Message for TryAllOutboundSettings {
+impl ::protobuf::Message for FailOverOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -4073,7 +10013,52 @@ impl ::protobuf::Message for TryAllOutboundSettings {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint32()?;
-                    self.delay_base = tmp;
+                    self.fail_timeout = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.health_check = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.check_interval = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.failover = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.fallback_cache = tmp;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.cache_size = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.cache_timeout = tmp;
+                },
+                9 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.priorities)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -4090,9 +10075,30 @@ impl ::protobuf::Message for TryAllOutboundSettings {
         for value in &self.actors {
             my_size += ::protobuf::rt::string_size(1, &value);
         };
-        if self.delay_base != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.delay_base, ::protobuf::wire_format::WireTypeVarint);
+        if self.fail_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.fail_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.health_check != false {
+            my_size += 2;
         }
+        if self.check_interval != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.check_interval, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.failover != false {
+            my_size += 2;
+        }
+        if self.fallback_cache != false {
+            my_size += 2;
+        }
+        if self.cache_size != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.cache_size, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.cache_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.cache_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.priorities {
+            my_size += ::protobuf::rt::value_size(9, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -4102,9 +10108,30 @@ impl ::protobuf::Message for TryAllOutboundSettings {
         for v in &self.actors {
             os.write_string(1, &v)?;
         };
-        if self.delay_base != 0 {
-            os.write_uint32(2, self.delay_base)?;
+        if self.fail_timeout != 0 {
+            os.write_uint32(2, self.fail_timeout)?;
+        }
+        if self.health_check != false {
+            os.write_bool(3, self.health_check)?;
+        }
+        if self.check_interval != 0 {
+            os.write_uint32(4, self.check_interval)?;
+        }
+        if self.failover != false {
+            os.write_bool(5, self.failover)?;
+        }
+        if self.fallback_cache != false {
+            os.write_bool(6, self.fallback_cache)?;
         }
+        if self.cache_size != 0 {
+            os.write_uint32(7, self.cache_size)?;
+        }
+        if self.cache_timeout != 0 {
+            os.write_uint32(8, self.cache_timeout)?;
+        }
+        for v in &self.priorities {
+            os.write_uint32(9, *v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -4135,8 +10162,8 @@ impl ::protobuf::Message for TryAllOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> TryAllOutboundSettings {
-        TryAllOutboundSettings::new()
+    fn new() -> FailOverOutboundSettings {
+        FailOverOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -4145,253 +10172,288 @@ impl ::protobuf::Message for TryAllOutboundSettings {
             let mut fields = ::std::vec::Vec::new();
             fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                 "actors",
-                |m: &TryAllOutboundSettings| { &m.actors },
-                |m: &mut TryAllOutboundSettings| { &mut m.actors },
+                |m: &FailOverOutboundSettings| { &m.actors },
+                |m: &mut FailOverOutboundSettings| { &mut m.actors },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "fail_timeout",
+                |m: &FailOverOutboundSettings| { &m.fail_timeout },
+                |m: &mut FailOverOutboundSettings| { &mut m.fail_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "health_check",
+                |m: &FailOverOutboundSettings| { &m.health_check },
+                |m: &mut FailOverOutboundSettings| { &mut m.health_check },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "check_interval",
+                |m: &FailOverOutboundSettings| { &m.check_interval },
+                |m: &mut FailOverOutboundSettings| { &mut m.check_interval },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "failover",
+                |m: &FailOverOutboundSettings| { &m.failover },
+                |m: &mut FailOverOutboundSettings| { &mut m.failover },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "fallback_cache",
+                |m: &FailOverOutboundSettings| { &m.fallback_cache },
+                |m: &mut FailOverOutboundSettings| { &mut m.fallback_cache },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "delay_base",
-                |m: &TryAllOutboundSettings| { &m.delay_base },
-                |m: &mut TryAllOutboundSettings| { &mut m.delay_base },
+                "cache_size",
+                |m: &FailOverOutboundSettings| { &m.cache_size },
+                |m: &mut FailOverOutboundSettings| { &mut m.cache_size },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<TryAllOutboundSettings>(
-                "TryAllOutboundSettings",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "cache_timeout",
+                |m: &FailOverOutboundSettings| { &m.cache_timeout },
+                |m: &mut FailOverOutboundSettings| { &mut m.cache_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "priorities",
+                |m: &FailOverOutboundSettings| { &m.priorities },
+                |m: &mut FailOverOutboundSettings| { &mut m.priorities },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<FailOverOutboundSettings>(
+                "FailOverOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static TryAllOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<TryAllOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(TryAllOutboundSettings::new)
+    fn default_instance() -> &'static FailOverOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<FailOverOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(FailOverOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for TryAllOutboundSettings {
+impl ::protobuf::Clear for FailOverOutboundSettings {
     fn clear(&mut self) {
         self.actors.clear();
-        self.delay_base = 0;
+        self.fail_timeout = 0;
+        self.health_check = false;
+        self.check_interval = 0;
+        self.failover = false;
+        self.fallback_cache = false;
+        self.cache_size = 0;
+        self.cache_timeout = 0;
+        self.priorities.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for TryAllOutboundSettings {
+impl ::std::fmt::Debug for FailOverOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for TryAllOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for FailOverOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct RandomOutboundSettings {
+pub struct PluggableTransportOutboundSettings {
     // message fields
-    pub actors: ::protobuf::RepeatedField<::std::string::String>,
+    pub path: ::std::string::String,
+    pub args: ::protobuf::RepeatedField<::std::string::String>,
+    pub transport: ::std::string::String,
+    pub address: ::std::string::String,
+    pub port: u32,
+    pub options: ::std::string::String,
+    pub state_dir: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a RandomOutboundSettings {
-    fn default() -> &'a RandomOutboundSettings {
-        <RandomOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a PluggableTransportOutboundSettings {
+    fn default() -> &'a PluggableTransportOutboundSettings {
+        <PluggableTransportOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl RandomOutboundSettings {
-    pub fn new() -> RandomOutboundSettings {
+impl PluggableTransportOutboundSettings {
+    pub fn new() -> PluggableTransportOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // repeated string actors = 1;
+    // string path = 1;
 
 
-    pub fn get_actors(&self) -> &[::std::string::String] {
-        &self.actors
+    pub fn get_path(&self) -> &str {
+        &self.path
     }
-    pub fn clear_actors(&mut self) {
-        self.actors.clear();
+    pub fn clear_path(&mut self) {
+        self.path.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.actors = v;
+    pub fn set_path(&mut self, v: ::std::string::String) {
+        self.path = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.actors
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_path(&mut self) -> &mut ::std::string::String {
+        &mut self.path
     }
 
     // Take field
-    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
+    pub fn take_path(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.path, ::std::string::String::new())
     }
-}
 
-impl ::protobuf::Message for RandomOutboundSettings {
-    fn is_initialized(&self) -> bool {
-        true
+    // repeated string args = 2;
+
+
+    pub fn get_args(&self) -> &[::std::string::String] {
+        &self.args
+    }
+    pub fn clear_args(&mut self) {
+        self.args.clear();
     }
 
-    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        while !is.eof()? {
-            let (field_number, wire_type) = is.read_tag_unpack()?;
-            match field_number {
-                1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
-                },
-                _ => {
-                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
-                },
-            };
-        }
-        ::std::result::Result::Ok(())
+    // Param is passed by value, moved
+    pub fn set_args(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.args = v;
     }
 
-    // Compute sizes of nested messages
-    #[allow(unused_variables)]
-    fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        for value in &self.actors {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
-        self.cached_size.set(my_size);
-        my_size
+    // Mutable pointer to the field.
+    pub fn mut_args(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.args
     }
 
-    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.actors {
-            os.write_string(1, &v)?;
-        };
-        os.write_unknown_fields(self.get_unknown_fields())?;
-        ::std::result::Result::Ok(())
+    // Take field
+    pub fn take_args(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.args, ::protobuf::RepeatedField::new())
     }
 
-    fn get_cached_size(&self) -> u32 {
-        self.cached_size.get()
+    // string transport = 3;
+
+
+    pub fn get_transport(&self) -> &str {
+        &self.transport
+    }
+    pub fn clear_transport(&mut self) {
+        self.transport.clear();
     }
 
-    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
-        &self.unknown_fields
+    // Param is passed by value, moved
+    pub fn set_transport(&mut self, v: ::std::string::String) {
+        self.transport = v;
     }
 
-    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
-        &mut self.unknown_fields
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_transport(&mut self) -> &mut ::std::string::String {
+        &mut self.transport
     }
 
-    fn as_any(&self) -> &dyn (::std::any::Any) {
-        self as &dyn (::std::any::Any)
+    // Take field
+    pub fn take_transport(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.transport, ::std::string::String::new())
     }
-    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
-        self as &mut dyn (::std::any::Any)
+
+    // string address = 4;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
     }
-    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
-        self
+    pub fn clear_address(&mut self) {
+        self.address.clear();
     }
 
-    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
-        Self::descriptor_static()
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
     }
 
-    fn new() -> RandomOutboundSettings {
-        RandomOutboundSettings::new()
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
     }
 
-    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
-        descriptor.get(|| {
-            let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "actors",
-                |m: &RandomOutboundSettings| { &m.actors },
-                |m: &mut RandomOutboundSettings| { &mut m.actors },
-            ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RandomOutboundSettings>(
-                "RandomOutboundSettings",
-                fields,
-                file_descriptor_proto()
-            )
-        })
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
     }
 
-    fn default_instance() -> &'static RandomOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<RandomOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(RandomOutboundSettings::new)
-    }
-}
+    // uint32 port = 5;
 
-impl ::protobuf::Clear for RandomOutboundSettings {
-    fn clear(&mut self) {
-        self.actors.clear();
-        self.unknown_fields.clear();
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
     }
-}
 
-impl ::std::fmt::Debug for RandomOutboundSettings {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        ::protobuf::text_format::fmt(self, f)
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
     }
-}
 
-impl ::protobuf::reflect::ProtobufValue for RandomOutboundSettings {
-    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Message(self)
+    // string options = 6;
+
+
+    pub fn get_options(&self) -> &str {
+        &self.options
+    }
+    pub fn clear_options(&mut self) {
+        self.options.clear();
     }
-}
 
-#[derive(PartialEq,Clone,Default)]
-pub struct ChainOutboundSettings {
-    // message fields
-    pub actors: ::protobuf::RepeatedField<::std::string::String>,
-    // special fields
-    pub unknown_fields: ::protobuf::UnknownFields,
-    pub cached_size: ::protobuf::CachedSize,
-}
+    // Param is passed by value, moved
+    pub fn set_options(&mut self, v: ::std::string::String) {
+        self.options = v;
+    }
 
-impl<'a> ::std::default::Default for &'a ChainOutboundSettings {
-    fn default() -> &'a ChainOutboundSettings {
-        <ChainOutboundSettings as ::protobuf::Message>::default_instance()
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_options(&mut self) -> &mut ::std::string::String {
+        &mut self.options
     }
-}
 
-impl ChainOutboundSettings {
-    pub fn new() -> ChainOutboundSettings {
-        ::std::default::Default::default()
+    // Take field
+    pub fn take_options(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.options, ::std::string::String::new())
     }
 
-    // repeated string actors = 1;
+    // string state_dir = 7;
 
 
-    pub fn get_actors(&self) -> &[::std::string::String] {
-        &self.actors
+    pub fn get_state_dir(&self) -> &str {
+        &self.state_dir
     }
-    pub fn clear_actors(&mut self) {
-        self.actors.clear();
+    pub fn clear_state_dir(&mut self) {
+        self.state_dir.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.actors = v;
+    pub fn set_state_dir(&mut self, v: ::std::string::String) {
+        self.state_dir = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.actors
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_state_dir(&mut self) -> &mut ::std::string::String {
+        &mut self.state_dir
     }
 
     // Take field
-    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
+    pub fn take_state_dir(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.state_dir, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for ChainOutboundSettings {
+impl ::protobuf::Message for PluggableTransportOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -4401,7 +10463,29 @@ impl ::protobuf::Message for ChainOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.args)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.transport)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.options)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.state_dir)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -4415,18 +10499,54 @@ impl ::protobuf::Message for ChainOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.actors {
-            my_size += ::protobuf::rt::string_size(1, &value);
+        if !self.path.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.path);
+        }
+        for value in &self.args {
+            my_size += ::protobuf::rt::string_size(2, &value);
         };
+        if !self.transport.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.transport);
+        }
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.options.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.options);
+        }
+        if !self.state_dir.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.state_dir);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.actors {
-            os.write_string(1, &v)?;
+        if !self.path.is_empty() {
+            os.write_string(1, &self.path)?;
+        }
+        for v in &self.args {
+            os.write_string(2, &v)?;
         };
+        if !self.transport.is_empty() {
+            os.write_string(3, &self.transport)?;
+        }
+        if !self.address.is_empty() {
+            os.write_string(4, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(5, self.port)?;
+        }
+        if !self.options.is_empty() {
+            os.write_string(6, &self.options)?;
+        }
+        if !self.state_dir.is_empty() {
+            os.write_string(7, &self.state_dir)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -4457,115 +10577,152 @@ impl ::protobuf::Message for ChainOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> ChainOutboundSettings {
-        ChainOutboundSettings::new()
+    fn new() -> PluggableTransportOutboundSettings {
+        PluggableTransportOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "path",
+                |m: &PluggableTransportOutboundSettings| { &m.path },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.path },
+            ));
             fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "actors",
-                |m: &ChainOutboundSettings| { &m.actors },
-                |m: &mut ChainOutboundSettings| { &mut m.actors },
+                "args",
+                |m: &PluggableTransportOutboundSettings| { &m.args },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.args },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ChainOutboundSettings>(
-                "ChainOutboundSettings",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "transport",
+                |m: &PluggableTransportOutboundSettings| { &m.transport },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.transport },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &PluggableTransportOutboundSettings| { &m.address },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.address },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "port",
+                |m: &PluggableTransportOutboundSettings| { &m.port },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.port },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "options",
+                |m: &PluggableTransportOutboundSettings| { &m.options },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.options },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "state_dir",
+                |m: &PluggableTransportOutboundSettings| { &m.state_dir },
+                |m: &mut PluggableTransportOutboundSettings| { &mut m.state_dir },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PluggableTransportOutboundSettings>(
+                "PluggableTransportOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static ChainOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<ChainOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(ChainOutboundSettings::new)
+    fn default_instance() -> &'static PluggableTransportOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<PluggableTransportOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PluggableTransportOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for ChainOutboundSettings {
+impl ::protobuf::Clear for PluggableTransportOutboundSettings {
     fn clear(&mut self) {
-        self.actors.clear();
+        self.path.clear();
+        self.args.clear();
+        self.transport.clear();
+        self.address.clear();
+        self.port = 0;
+        self.options.clear();
+        self.state_dir.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for ChainOutboundSettings {
+impl ::std::fmt::Debug for PluggableTransportOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for ChainOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for PluggableTransportOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct RetryOutboundSettings {
+pub struct StatOutboundSettings {
     // message fields
-    pub actors: ::protobuf::RepeatedField<::std::string::String>,
-    pub attempts: u32,
+    pub address: ::std::string::String,
+    pub port: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a RetryOutboundSettings {
-    fn default() -> &'a RetryOutboundSettings {
-        <RetryOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a StatOutboundSettings {
+    fn default() -> &'a StatOutboundSettings {
+        <StatOutboundSettings as ::protobuf::Message>::default_instance()
     }
 }
 
-impl RetryOutboundSettings {
-    pub fn new() -> RetryOutboundSettings {
+impl StatOutboundSettings {
+    pub fn new() -> StatOutboundSettings {
         ::std::default::Default::default()
     }
 
-    // repeated string actors = 1;
+    // string address = 1;
 
 
-    pub fn get_actors(&self) -> &[::std::string::String] {
-        &self.actors
+    pub fn get_address(&self) -> &str {
+        &self.address
     }
-    pub fn clear_actors(&mut self) {
-        self.actors.clear();
+    pub fn clear_address(&mut self) {
+        self.address.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.actors = v;
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.actors
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
     }
 
     // Take field
-    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
     }
 
-    // uint32 attempts = 2;
+    // uint32 port = 2;
 
 
-    pub fn get_attempts(&self) -> u32 {
-        self.attempts
+    pub fn get_port(&self) -> u32 {
+        self.port
     }
-    pub fn clear_attempts(&mut self) {
-        self.attempts = 0;
+    pub fn clear_port(&mut self) {
+        self.port = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_attempts(&mut self, v: u32) {
-        self.attempts = v;
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
     }
 }
 
-impl ::protobuf::Message for RetryOutboundSettings {
+impl ::protobuf::Message for StatOutboundSettings {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -4575,14 +10732,14 @@ impl ::protobuf::Message for RetryOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint32()?;
-                    self.attempts = tmp;
+                    self.port = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -4596,11 +10753,11 @@ impl ::protobuf::Message for RetryOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.actors {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        if self.attempts != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.attempts, ::protobuf::wire_format::WireTypeVarint);
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.address);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -4608,11 +10765,11 @@ impl ::protobuf::Message for RetryOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.actors {
-            os.write_string(1, &v)?;
-        };
-        if self.attempts != 0 {
-            os.write_uint32(2, self.attempts)?;
+        if !self.address.is_empty() {
+            os.write_string(1, &self.address)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(2, self.port)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -4644,218 +10801,158 @@ impl ::protobuf::Message for RetryOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> RetryOutboundSettings {
-        RetryOutboundSettings::new()
+    fn new() -> StatOutboundSettings {
+        StatOutboundSettings::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "actors",
-                |m: &RetryOutboundSettings| { &m.actors },
-                |m: &mut RetryOutboundSettings| { &mut m.actors },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &StatOutboundSettings| { &m.address },
+                |m: &mut StatOutboundSettings| { &mut m.address },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "attempts",
-                |m: &RetryOutboundSettings| { &m.attempts },
-                |m: &mut RetryOutboundSettings| { &mut m.attempts },
+                "port",
+                |m: &StatOutboundSettings| { &m.port },
+                |m: &mut StatOutboundSettings| { &mut m.port },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RetryOutboundSettings>(
-                "RetryOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<StatOutboundSettings>(
+                "StatOutboundSettings",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static RetryOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<RetryOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(RetryOutboundSettings::new)
+    fn default_instance() -> &'static StatOutboundSettings {
+        static instance: ::protobuf::rt::LazyV2<StatOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(StatOutboundSettings::new)
     }
 }
 
-impl ::protobuf::Clear for RetryOutboundSettings {
+impl ::protobuf::Clear for StatOutboundSettings {
     fn clear(&mut self) {
-        self.actors.clear();
-        self.attempts = 0;
+        self.address.clear();
+        self.port = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for RetryOutboundSettings {
+impl ::std::fmt::Debug for StatOutboundSettings {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for RetryOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for StatOutboundSettings {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct FailOverOutboundSettings {
-    // message fields
-    pub actors: ::protobuf::RepeatedField<::std::string::String>,
-    pub fail_timeout: u32,
-    pub health_check: bool,
-    pub check_interval: u32,
-    pub failover: bool,
-    pub fallback_cache: bool,
-    pub cache_size: u32,
-    pub cache_timeout: u32,
-    // special fields
-    pub unknown_fields: ::protobuf::UnknownFields,
-    pub cached_size: ::protobuf::CachedSize,
-}
-
-impl<'a> ::std::default::Default for &'a FailOverOutboundSettings {
-    fn default() -> &'a FailOverOutboundSettings {
-        <FailOverOutboundSettings as ::protobuf::Message>::default_instance()
-    }
-}
-
-impl FailOverOutboundSettings {
-    pub fn new() -> FailOverOutboundSettings {
-        ::std::default::Default::default()
-    }
-
-    // repeated string actors = 1;
-
-
-    pub fn get_actors(&self) -> &[::std::string::String] {
-        &self.actors
-    }
-    pub fn clear_actors(&mut self) {
-        self.actors.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_actors(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.actors = v;
-    }
-
-    // Mutable pointer to the field.
-    pub fn mut_actors(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.actors
-    }
-
-    // Take field
-    pub fn take_actors(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.actors, ::protobuf::RepeatedField::new())
-    }
-
-    // uint32 fail_timeout = 2;
-
-
-    pub fn get_fail_timeout(&self) -> u32 {
-        self.fail_timeout
-    }
-    pub fn clear_fail_timeout(&mut self) {
-        self.fail_timeout = 0;
-    }
-
-    // Param is passed by value, moved
-    pub fn set_fail_timeout(&mut self, v: u32) {
-        self.fail_timeout = v;
-    }
-
-    // bool health_check = 3;
-
-
-    pub fn get_health_check(&self) -> bool {
-        self.health_check
-    }
-    pub fn clear_health_check(&mut self) {
-        self.health_check = false;
-    }
-
-    // Param is passed by value, moved
-    pub fn set_health_check(&mut self, v: bool) {
-        self.health_check = v;
-    }
-
-    // uint32 check_interval = 4;
-
+pub struct RateLimit {
+    // message fields
+    pub uplink: ::protobuf::SingularPtrField<RateLimit_Bucket>,
+    pub downlink: ::protobuf::SingularPtrField<RateLimit_Bucket>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
 
-    pub fn get_check_interval(&self) -> u32 {
-        self.check_interval
-    }
-    pub fn clear_check_interval(&mut self) {
-        self.check_interval = 0;
+impl<'a> ::std::default::Default for &'a RateLimit {
+    fn default() -> &'a RateLimit {
+        <RateLimit as ::protobuf::Message>::default_instance()
     }
+}
 
-    // Param is passed by value, moved
-    pub fn set_check_interval(&mut self, v: u32) {
-        self.check_interval = v;
+impl RateLimit {
+    pub fn new() -> RateLimit {
+        ::std::default::Default::default()
     }
 
-    // bool failover = 5;
+    // .RateLimit.Bucket uplink = 1;
 
 
-    pub fn get_failover(&self) -> bool {
-        self.failover
+    pub fn get_uplink(&self) -> &RateLimit_Bucket {
+        self.uplink.as_ref().unwrap_or_else(|| <RateLimit_Bucket as ::protobuf::Message>::default_instance())
     }
-    pub fn clear_failover(&mut self) {
-        self.failover = false;
+    pub fn clear_uplink(&mut self) {
+        self.uplink.clear();
     }
 
-    // Param is passed by value, moved
-    pub fn set_failover(&mut self, v: bool) {
-        self.failover = v;
+    pub fn has_uplink(&self) -> bool {
+        self.uplink.is_some()
     }
 
-    // bool fallback_cache = 6;
-
-
-    pub fn get_fallback_cache(&self) -> bool {
-        self.fallback_cache
+    // Param is passed by value, moved
+    pub fn set_uplink(&mut self, v: RateLimit_Bucket) {
+        self.uplink = ::protobuf::SingularPtrField::some(v);
     }
-    pub fn clear_fallback_cache(&mut self) {
-        self.fallback_cache = false;
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_uplink(&mut self) -> &mut RateLimit_Bucket {
+        if self.uplink.is_none() {
+            self.uplink.set_default();
+        }
+        self.uplink.as_mut().unwrap()
     }
 
-    // Param is passed by value, moved
-    pub fn set_fallback_cache(&mut self, v: bool) {
-        self.fallback_cache = v;
+    // Take field
+    pub fn take_uplink(&mut self) -> RateLimit_Bucket {
+        self.uplink.take().unwrap_or_else(|| RateLimit_Bucket::new())
     }
 
-    // uint32 cache_size = 7;
+    // .RateLimit.Bucket downlink = 2;
 
 
-    pub fn get_cache_size(&self) -> u32 {
-        self.cache_size
+    pub fn get_downlink(&self) -> &RateLimit_Bucket {
+        self.downlink.as_ref().unwrap_or_else(|| <RateLimit_Bucket as ::protobuf::Message>::default_instance())
     }
-    pub fn clear_cache_size(&mut self) {
-        self.cache_size = 0;
+    pub fn clear_downlink(&mut self) {
+        self.downlink.clear();
     }
 
-    // Param is passed by value, moved
-    pub fn set_cache_size(&mut self, v: u32) {
-        self.cache_size = v;
+    pub fn has_downlink(&self) -> bool {
+        self.downlink.is_some()
     }
 
-    // uint32 cache_timeout = 8;
-
-
-    pub fn get_cache_timeout(&self) -> u32 {
-        self.cache_timeout
+    // Param is passed by value, moved
+    pub fn set_downlink(&mut self, v: RateLimit_Bucket) {
+        self.downlink = ::protobuf::SingularPtrField::some(v);
     }
-    pub fn clear_cache_timeout(&mut self) {
-        self.cache_timeout = 0;
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_downlink(&mut self) -> &mut RateLimit_Bucket {
+        if self.downlink.is_none() {
+            self.downlink.set_default();
+        }
+        self.downlink.as_mut().unwrap()
     }
 
-    // Param is passed by value, moved
-    pub fn set_cache_timeout(&mut self, v: u32) {
-        self.cache_timeout = v;
+    // Take field
+    pub fn take_downlink(&mut self) -> RateLimit_Bucket {
+        self.downlink.take().unwrap_or_else(|| RateLimit_Bucket::new())
     }
 }
 
-impl ::protobuf::Message for FailOverOutboundSettings {
+impl ::protobuf::Message for RateLimit {
     fn is_initialized(&self) -> bool {
+        for v in &self.uplink {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.downlink {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -4864,56 +10961,10 @@ impl ::protobuf::Message for FailOverOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.actors)?;
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.uplink)?;
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.fail_timeout = tmp;
-                },
-                3 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_bool()?;
-                    self.health_check = tmp;
-                },
-                4 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.check_interval = tmp;
-                },
-                5 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_bool()?;
-                    self.failover = tmp;
-                },
-                6 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_bool()?;
-                    self.fallback_cache = tmp;
-                },
-                7 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.cache_size = tmp;
-                },
-                8 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint32()?;
-                    self.cache_timeout = tmp;
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.downlink)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -4927,29 +10978,13 @@ impl ::protobuf::Message for FailOverOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.actors {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        if self.fail_timeout != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.fail_timeout, ::protobuf::wire_format::WireTypeVarint);
-        }
-        if self.health_check != false {
-            my_size += 2;
-        }
-        if self.check_interval != 0 {
-            my_size += ::protobuf::rt::value_size(4, self.check_interval, ::protobuf::wire_format::WireTypeVarint);
-        }
-        if self.failover != false {
-            my_size += 2;
-        }
-        if self.fallback_cache != false {
-            my_size += 2;
-        }
-        if self.cache_size != 0 {
-            my_size += ::protobuf::rt::value_size(7, self.cache_size, ::protobuf::wire_format::WireTypeVarint);
+        if let Some(ref v) = self.uplink.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
-        if self.cache_timeout != 0 {
-            my_size += ::protobuf::rt::value_size(8, self.cache_timeout, ::protobuf::wire_format::WireTypeVarint);
+        if let Some(ref v) = self.downlink.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -4957,29 +10992,15 @@ impl ::protobuf::Message for FailOverOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.actors {
-            os.write_string(1, &v)?;
-        };
-        if self.fail_timeout != 0 {
-            os.write_uint32(2, self.fail_timeout)?;
-        }
-        if self.health_check != false {
-            os.write_bool(3, self.health_check)?;
-        }
-        if self.check_interval != 0 {
-            os.write_uint32(4, self.check_interval)?;
-        }
-        if self.failover != false {
-            os.write_bool(5, self.failover)?;
-        }
-        if self.fallback_cache != false {
-            os.write_bool(6, self.fallback_cache)?;
-        }
-        if self.cache_size != 0 {
-            os.write_uint32(7, self.cache_size)?;
+        if let Some(ref v) = self.uplink.as_ref() {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
         }
-        if self.cache_timeout != 0 {
-            os.write_uint32(8, self.cache_timeout)?;
+        if let Some(ref v) = self.downlink.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -5011,158 +11032,111 @@ impl ::protobuf::Message for FailOverOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> FailOverOutboundSettings {
-        FailOverOutboundSettings::new()
+    fn new() -> RateLimit {
+        RateLimit::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "actors",
-                |m: &FailOverOutboundSettings| { &m.actors },
-                |m: &mut FailOverOutboundSettings| { &mut m.actors },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "fail_timeout",
-                |m: &FailOverOutboundSettings| { &m.fail_timeout },
-                |m: &mut FailOverOutboundSettings| { &mut m.fail_timeout },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
-                "health_check",
-                |m: &FailOverOutboundSettings| { &m.health_check },
-                |m: &mut FailOverOutboundSettings| { &mut m.health_check },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "check_interval",
-                |m: &FailOverOutboundSettings| { &m.check_interval },
-                |m: &mut FailOverOutboundSettings| { &mut m.check_interval },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
-                "failover",
-                |m: &FailOverOutboundSettings| { &m.failover },
-                |m: &mut FailOverOutboundSettings| { &mut m.failover },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
-                "fallback_cache",
-                |m: &FailOverOutboundSettings| { &m.fallback_cache },
-                |m: &mut FailOverOutboundSettings| { &mut m.fallback_cache },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "cache_size",
-                |m: &FailOverOutboundSettings| { &m.cache_size },
-                |m: &mut FailOverOutboundSettings| { &mut m.cache_size },
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<RateLimit_Bucket>>(
+                "uplink",
+                |m: &RateLimit| { &m.uplink },
+                |m: &mut RateLimit| { &mut m.uplink },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "cache_timeout",
-                |m: &FailOverOutboundSettings| { &m.cache_timeout },
-                |m: &mut FailOverOutboundSettings| { &mut m.cache_timeout },
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<RateLimit_Bucket>>(
+                "downlink",
+                |m: &RateLimit| { &m.downlink },
+                |m: &mut RateLimit| { &mut m.downlink },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<FailOverOutboundSettings>(
-                "FailOverOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RateLimit>(
+                "RateLimit",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static FailOverOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<FailOverOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(FailOverOutboundSettings::new)
+    fn default_instance() -> &'static RateLimit {
+        static instance: ::protobuf::rt::LazyV2<RateLimit> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(RateLimit::new)
     }
 }
 
-impl ::protobuf::Clear for FailOverOutboundSettings {
+impl ::protobuf::Clear for RateLimit {
     fn clear(&mut self) {
-        self.actors.clear();
-        self.fail_timeout = 0;
-        self.health_check = false;
-        self.check_interval = 0;
-        self.failover = false;
-        self.fallback_cache = false;
-        self.cache_size = 0;
-        self.cache_timeout = 0;
+        self.uplink.clear();
+        self.downlink.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for FailOverOutboundSettings {
+impl ::std::fmt::Debug for RateLimit {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for FailOverOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for RateLimit {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct StatOutboundSettings {
+pub struct RateLimit_Bucket {
     // message fields
-    pub address: ::std::string::String,
-    pub port: u32,
+    pub rate: u64,
+    pub burst: u64,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a StatOutboundSettings {
-    fn default() -> &'a StatOutboundSettings {
-        <StatOutboundSettings as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a RateLimit_Bucket {
+    fn default() -> &'a RateLimit_Bucket {
+        <RateLimit_Bucket as ::protobuf::Message>::default_instance()
     }
 }
 
-impl StatOutboundSettings {
-    pub fn new() -> StatOutboundSettings {
+impl RateLimit_Bucket {
+    pub fn new() -> RateLimit_Bucket {
         ::std::default::Default::default()
     }
 
-    // string address = 1;
-
-
-    pub fn get_address(&self) -> &str {
-        &self.address
-    }
-    pub fn clear_address(&mut self) {
-        self.address.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_address(&mut self, v: ::std::string::String) {
-        self.address = v;
-    }
+    // uint64 rate = 1;
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_address(&mut self) -> &mut ::std::string::String {
-        &mut self.address
+
+    pub fn get_rate(&self) -> u64 {
+        self.rate
+    }
+    pub fn clear_rate(&mut self) {
+        self.rate = 0;
     }
 
-    // Take field
-    pub fn take_address(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    // Param is passed by value, moved
+    pub fn set_rate(&mut self, v: u64) {
+        self.rate = v;
     }
 
-    // uint32 port = 2;
+    // uint64 burst = 2;
 
 
-    pub fn get_port(&self) -> u32 {
-        self.port
+    pub fn get_burst(&self) -> u64 {
+        self.burst
     }
-    pub fn clear_port(&mut self) {
-        self.port = 0;
+    pub fn clear_burst(&mut self) {
+        self.burst = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_port(&mut self, v: u32) {
-        self.port = v;
+    pub fn set_burst(&mut self, v: u64) {
+        self.burst = v;
     }
 }
 
-impl ::protobuf::Message for StatOutboundSettings {
+impl ::protobuf::Message for RateLimit_Bucket {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -5172,14 +11146,18 @@ impl ::protobuf::Message for StatOutboundSettings {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.rate = tmp;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_uint32()?;
-                    self.port = tmp;
+                    let tmp = is.read_uint64()?;
+                    self.burst = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -5193,11 +11171,11 @@ impl ::protobuf::Message for StatOutboundSettings {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.address.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.address);
+        if self.rate != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.rate, ::protobuf::wire_format::WireTypeVarint);
         }
-        if self.port != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.port, ::protobuf::wire_format::WireTypeVarint);
+        if self.burst != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.burst, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -5205,11 +11183,11 @@ impl ::protobuf::Message for StatOutboundSettings {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.address.is_empty() {
-            os.write_string(1, &self.address)?;
+        if self.rate != 0 {
+            os.write_uint64(1, self.rate)?;
         }
-        if self.port != 0 {
-            os.write_uint32(2, self.port)?;
+        if self.burst != 0 {
+            os.write_uint64(2, self.burst)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -5241,53 +11219,53 @@ impl ::protobuf::Message for StatOutboundSettings {
         Self::descriptor_static()
     }
 
-    fn new() -> StatOutboundSettings {
-        StatOutboundSettings::new()
+    fn new() -> RateLimit_Bucket {
+        RateLimit_Bucket::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "address",
-                |m: &StatOutboundSettings| { &m.address },
-                |m: &mut StatOutboundSettings| { &mut m.address },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "rate",
+                |m: &RateLimit_Bucket| { &m.rate },
+                |m: &mut RateLimit_Bucket| { &mut m.rate },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                "port",
-                |m: &StatOutboundSettings| { &m.port },
-                |m: &mut StatOutboundSettings| { &mut m.port },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "burst",
+                |m: &RateLimit_Bucket| { &m.burst },
+                |m: &mut RateLimit_Bucket| { &mut m.burst },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<StatOutboundSettings>(
-                "StatOutboundSettings",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RateLimit_Bucket>(
+                "RateLimit.Bucket",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static StatOutboundSettings {
-        static instance: ::protobuf::rt::LazyV2<StatOutboundSettings> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(StatOutboundSettings::new)
+    fn default_instance() -> &'static RateLimit_Bucket {
+        static instance: ::protobuf::rt::LazyV2<RateLimit_Bucket> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(RateLimit_Bucket::new)
     }
 }
 
-impl ::protobuf::Clear for StatOutboundSettings {
+impl ::protobuf::Clear for RateLimit_Bucket {
     fn clear(&mut self) {
-        self.address.clear();
-        self.port = 0;
+        self.rate = 0;
+        self.burst = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for StatOutboundSettings {
+impl ::std::fmt::Debug for RateLimit_Bucket {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for StatOutboundSettings {
+impl ::protobuf::reflect::ProtobufValue for RateLimit_Bucket {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
@@ -5300,6 +11278,12 @@ pub struct Outbound {
     pub protocol: ::std::string::String,
     pub bind: ::std::string::String,
     pub settings: ::std::vec::Vec<u8>,
+    pub idle_timeout: u32,
+    pub connect_timeout: u32,
+    pub disable_tcp_nodelay: bool,
+    pub tcp_keepalive: u32,
+    pub rate_limit: ::protobuf::SingularPtrField<RateLimit>,
+    pub dns: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -5419,10 +11403,134 @@ impl Outbound {
     pub fn take_settings(&mut self) -> ::std::vec::Vec<u8> {
         ::std::mem::replace(&mut self.settings, ::std::vec::Vec::new())
     }
+
+    // uint32 idle_timeout = 5;
+
+
+    pub fn get_idle_timeout(&self) -> u32 {
+        self.idle_timeout
+    }
+    pub fn clear_idle_timeout(&mut self) {
+        self.idle_timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_idle_timeout(&mut self, v: u32) {
+        self.idle_timeout = v;
+    }
+
+    // uint32 connect_timeout = 6;
+
+
+    pub fn get_connect_timeout(&self) -> u32 {
+        self.connect_timeout
+    }
+    pub fn clear_connect_timeout(&mut self) {
+        self.connect_timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_connect_timeout(&mut self, v: u32) {
+        self.connect_timeout = v;
+    }
+
+    // bool disable_tcp_nodelay = 7;
+
+
+    pub fn get_disable_tcp_nodelay(&self) -> bool {
+        self.disable_tcp_nodelay
+    }
+    pub fn clear_disable_tcp_nodelay(&mut self) {
+        self.disable_tcp_nodelay = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_disable_tcp_nodelay(&mut self, v: bool) {
+        self.disable_tcp_nodelay = v;
+    }
+
+    // uint32 tcp_keepalive = 8;
+
+
+    pub fn get_tcp_keepalive(&self) -> u32 {
+        self.tcp_keepalive
+    }
+    pub fn clear_tcp_keepalive(&mut self) {
+        self.tcp_keepalive = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tcp_keepalive(&mut self, v: u32) {
+        self.tcp_keepalive = v;
+    }
+
+    // .RateLimit rate_limit = 9;
+
+
+    pub fn get_rate_limit(&self) -> &RateLimit {
+        self.rate_limit.as_ref().unwrap_or_else(|| <RateLimit as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_rate_limit(&mut self) {
+        self.rate_limit.clear();
+    }
+
+    pub fn has_rate_limit(&self) -> bool {
+        self.rate_limit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rate_limit(&mut self, v: RateLimit) {
+        self.rate_limit = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_rate_limit(&mut self) -> &mut RateLimit {
+        if self.rate_limit.is_none() {
+            self.rate_limit.set_default();
+        }
+        self.rate_limit.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_rate_limit(&mut self) -> RateLimit {
+        self.rate_limit.take().unwrap_or_else(|| RateLimit::new())
+    }
+
+    // string dns = 10;
+
+
+    pub fn get_dns(&self) -> &str {
+        &self.dns
+    }
+    pub fn clear_dns(&mut self) {
+        self.dns.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_dns(&mut self, v: ::std::string::String) {
+        self.dns = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_dns(&mut self) -> &mut ::std::string::String {
+        &mut self.dns
+    }
+
+    // Take field
+    pub fn take_dns(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.dns, ::std::string::String::new())
+    }
 }
 
 impl ::protobuf::Message for Outbound {
     fn is_initialized(&self) -> bool {
+        for v in &self.rate_limit {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -5442,6 +11550,40 @@ impl ::protobuf::Message for Outbound {
                 4 => {
                     ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.settings)?;
                 },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.idle_timeout = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.connect_timeout = tmp;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.disable_tcp_nodelay = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.tcp_keepalive = tmp;
+                },
+                9 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.rate_limit)?;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.dns)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -5466,6 +11608,25 @@ impl ::protobuf::Message for Outbound {
         if !self.settings.is_empty() {
             my_size += ::protobuf::rt::bytes_size(4, &self.settings);
         }
+        if self.idle_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.idle_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.connect_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.connect_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.disable_tcp_nodelay != false {
+            my_size += 2;
+        }
+        if self.tcp_keepalive != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.tcp_keepalive, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.rate_limit.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if !self.dns.is_empty() {
+            my_size += ::protobuf::rt::string_size(10, &self.dns);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -5484,6 +11645,26 @@ impl ::protobuf::Message for Outbound {
         if !self.settings.is_empty() {
             os.write_bytes(4, &self.settings)?;
         }
+        if self.idle_timeout != 0 {
+            os.write_uint32(5, self.idle_timeout)?;
+        }
+        if self.connect_timeout != 0 {
+            os.write_uint32(6, self.connect_timeout)?;
+        }
+        if self.disable_tcp_nodelay != false {
+            os.write_bool(7, self.disable_tcp_nodelay)?;
+        }
+        if self.tcp_keepalive != 0 {
+            os.write_uint32(8, self.tcp_keepalive)?;
+        }
+        if let Some(ref v) = self.rate_limit.as_ref() {
+            os.write_tag(9, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if !self.dns.is_empty() {
+            os.write_string(10, &self.dns)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -5542,6 +11723,36 @@ impl ::protobuf::Message for Outbound {
                 |m: &Outbound| { &m.settings },
                 |m: &mut Outbound| { &mut m.settings },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "idle_timeout",
+                |m: &Outbound| { &m.idle_timeout },
+                |m: &mut Outbound| { &mut m.idle_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "connect_timeout",
+                |m: &Outbound| { &m.connect_timeout },
+                |m: &mut Outbound| { &mut m.connect_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "disable_tcp_nodelay",
+                |m: &Outbound| { &m.disable_tcp_nodelay },
+                |m: &mut Outbound| { &mut m.disable_tcp_nodelay },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "tcp_keepalive",
+                |m: &Outbound| { &m.tcp_keepalive },
+                |m: &mut Outbound| { &mut m.tcp_keepalive },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<RateLimit>>(
+                "rate_limit",
+                |m: &Outbound| { &m.rate_limit },
+                |m: &mut Outbound| { &mut m.rate_limit },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "dns",
+                |m: &Outbound| { &m.dns },
+                |m: &mut Outbound| { &mut m.dns },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<Outbound>(
                 "Outbound",
                 fields,
@@ -5562,6 +11773,12 @@ impl ::protobuf::Clear for Outbound {
         self.protocol.clear();
         self.bind.clear();
         self.settings.clear();
+        self.idle_timeout = 0;
+        self.connect_timeout = 0;
+        self.disable_tcp_nodelay = false;
+        self.tcp_keepalive = 0;
+        self.rate_limit.clear();
+        self.dns.clear();
         self.unknown_fields.clear();
     }
 }
@@ -5586,6 +11803,10 @@ pub struct RoutingRule {
     pub ip_cidrs: ::protobuf::RepeatedField<::std::string::String>,
     pub mmdbs: ::protobuf::RepeatedField<RoutingRule_Mmdb>,
     pub port_ranges: ::protobuf::RepeatedField<::std::string::String>,
+    pub no_log: bool,
+    pub process_names: ::protobuf::RepeatedField<::std::string::String>,
+    pub dns_strategy: DnsStrategy,
+    pub networks: ::protobuf::RepeatedField<::std::string::String>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -5653,79 +11874,159 @@ impl RoutingRule {
         ::std::mem::replace(&mut self.domains, ::protobuf::RepeatedField::new())
     }
 
-    // repeated string ip_cidrs = 3;
+    // repeated string ip_cidrs = 3;
+
+
+    pub fn get_ip_cidrs(&self) -> &[::std::string::String] {
+        &self.ip_cidrs
+    }
+    pub fn clear_ip_cidrs(&mut self) {
+        self.ip_cidrs.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ip_cidrs(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.ip_cidrs = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ip_cidrs(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.ip_cidrs
+    }
+
+    // Take field
+    pub fn take_ip_cidrs(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.ip_cidrs, ::protobuf::RepeatedField::new())
+    }
+
+    // repeated .RoutingRule.Mmdb mmdbs = 4;
+
+
+    pub fn get_mmdbs(&self) -> &[RoutingRule_Mmdb] {
+        &self.mmdbs
+    }
+    pub fn clear_mmdbs(&mut self) {
+        self.mmdbs.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_mmdbs(&mut self, v: ::protobuf::RepeatedField<RoutingRule_Mmdb>) {
+        self.mmdbs = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_mmdbs(&mut self) -> &mut ::protobuf::RepeatedField<RoutingRule_Mmdb> {
+        &mut self.mmdbs
+    }
+
+    // Take field
+    pub fn take_mmdbs(&mut self) -> ::protobuf::RepeatedField<RoutingRule_Mmdb> {
+        ::std::mem::replace(&mut self.mmdbs, ::protobuf::RepeatedField::new())
+    }
+
+    // repeated string port_ranges = 5;
+
+
+    pub fn get_port_ranges(&self) -> &[::std::string::String] {
+        &self.port_ranges
+    }
+    pub fn clear_port_ranges(&mut self) {
+        self.port_ranges.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port_ranges(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.port_ranges = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_port_ranges(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.port_ranges
+    }
+
+    // Take field
+    pub fn take_port_ranges(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.port_ranges, ::protobuf::RepeatedField::new())
+    }
+
+    // bool no_log = 6;
+
+
+    pub fn get_no_log(&self) -> bool {
+        self.no_log
+    }
+    pub fn clear_no_log(&mut self) {
+        self.no_log = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_no_log(&mut self, v: bool) {
+        self.no_log = v;
+    }
+
+    // repeated string process_names = 7;
 
 
-    pub fn get_ip_cidrs(&self) -> &[::std::string::String] {
-        &self.ip_cidrs
+    pub fn get_process_names(&self) -> &[::std::string::String] {
+        &self.process_names
     }
-    pub fn clear_ip_cidrs(&mut self) {
-        self.ip_cidrs.clear();
+    pub fn clear_process_names(&mut self) {
+        self.process_names.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_ip_cidrs(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.ip_cidrs = v;
+    pub fn set_process_names(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.process_names = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_ip_cidrs(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.ip_cidrs
+    pub fn mut_process_names(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.process_names
     }
 
     // Take field
-    pub fn take_ip_cidrs(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.ip_cidrs, ::protobuf::RepeatedField::new())
+    pub fn take_process_names(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.process_names, ::protobuf::RepeatedField::new())
     }
 
-    // repeated .RoutingRule.Mmdb mmdbs = 4;
+    // .DnsStrategy dns_strategy = 8;
 
 
-    pub fn get_mmdbs(&self) -> &[RoutingRule_Mmdb] {
-        &self.mmdbs
+    pub fn get_dns_strategy(&self) -> DnsStrategy {
+        self.dns_strategy
     }
-    pub fn clear_mmdbs(&mut self) {
-        self.mmdbs.clear();
+    pub fn clear_dns_strategy(&mut self) {
+        self.dns_strategy = DnsStrategy::DEFAULT;
     }
 
     // Param is passed by value, moved
-    pub fn set_mmdbs(&mut self, v: ::protobuf::RepeatedField<RoutingRule_Mmdb>) {
-        self.mmdbs = v;
-    }
-
-    // Mutable pointer to the field.
-    pub fn mut_mmdbs(&mut self) -> &mut ::protobuf::RepeatedField<RoutingRule_Mmdb> {
-        &mut self.mmdbs
-    }
-
-    // Take field
-    pub fn take_mmdbs(&mut self) -> ::protobuf::RepeatedField<RoutingRule_Mmdb> {
-        ::std::mem::replace(&mut self.mmdbs, ::protobuf::RepeatedField::new())
+    pub fn set_dns_strategy(&mut self, v: DnsStrategy) {
+        self.dns_strategy = v;
     }
 
-    // repeated string port_ranges = 5;
+    // repeated string networks = 9;
 
 
-    pub fn get_port_ranges(&self) -> &[::std::string::String] {
-        &self.port_ranges
+    pub fn get_networks(&self) -> &[::std::string::String] {
+        &self.networks
     }
-    pub fn clear_port_ranges(&mut self) {
-        self.port_ranges.clear();
+    pub fn clear_networks(&mut self) {
+        self.networks.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_port_ranges(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
-        self.port_ranges = v;
+    pub fn set_networks(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.networks = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_port_ranges(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
-        &mut self.port_ranges
+    pub fn mut_networks(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.networks
     }
 
     // Take field
-    pub fn take_port_ranges(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
-        ::std::mem::replace(&mut self.port_ranges, ::protobuf::RepeatedField::new())
+    pub fn take_networks(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.networks, ::protobuf::RepeatedField::new())
     }
 }
 
@@ -5763,6 +12064,22 @@ impl ::protobuf::Message for RoutingRule {
                 5 => {
                     ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.port_ranges)?;
                 },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.no_log = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.process_names)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.dns_strategy, 8, &mut self.unknown_fields)?
+                },
+                9 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.networks)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -5792,6 +12109,18 @@ impl ::protobuf::Message for RoutingRule {
         for value in &self.port_ranges {
             my_size += ::protobuf::rt::string_size(5, &value);
         };
+        if self.no_log != false {
+            my_size += 2;
+        }
+        for value in &self.process_names {
+            my_size += ::protobuf::rt::string_size(7, &value);
+        };
+        if self.dns_strategy != DnsStrategy::DEFAULT {
+            my_size += ::protobuf::rt::enum_size(8, self.dns_strategy);
+        }
+        for value in &self.networks {
+            my_size += ::protobuf::rt::string_size(9, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -5817,6 +12146,18 @@ impl ::protobuf::Message for RoutingRule {
         for v in &self.port_ranges {
             os.write_string(5, &v)?;
         };
+        if self.no_log != false {
+            os.write_bool(6, self.no_log)?;
+        }
+        for v in &self.process_names {
+            os.write_string(7, &v)?;
+        };
+        if self.dns_strategy != DnsStrategy::DEFAULT {
+            os.write_enum(8, ::protobuf::ProtobufEnum::value(&self.dns_strategy))?;
+        }
+        for v in &self.networks {
+            os.write_string(9, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -5880,6 +12221,26 @@ impl ::protobuf::Message for RoutingRule {
                 |m: &RoutingRule| { &m.port_ranges },
                 |m: &mut RoutingRule| { &mut m.port_ranges },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "no_log",
+                |m: &RoutingRule| { &m.no_log },
+                |m: &mut RoutingRule| { &mut m.no_log },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "process_names",
+                |m: &RoutingRule| { &m.process_names },
+                |m: &mut RoutingRule| { &mut m.process_names },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<DnsStrategy>>(
+                "dns_strategy",
+                |m: &RoutingRule| { &m.dns_strategy },
+                |m: &mut RoutingRule| { &mut m.dns_strategy },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "networks",
+                |m: &RoutingRule| { &m.networks },
+                |m: &mut RoutingRule| { &mut m.networks },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<RoutingRule>(
                 "RoutingRule",
                 fields,
@@ -5901,6 +12262,10 @@ impl ::protobuf::Clear for RoutingRule {
         self.ip_cidrs.clear();
         self.mmdbs.clear();
         self.port_ranges.clear();
+        self.no_log = false;
+        self.process_names.clear();
+        self.dns_strategy = DnsStrategy::DEFAULT;
+        self.networks.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6119,122 +12484,312 @@ impl ::protobuf::ProtobufEnum for RoutingRule_Domain_Type {
         *self as i32
     }
 
-    fn from_i32(value: i32) -> ::std::option::Option<RoutingRule_Domain_Type> {
-        match value {
-            0 => ::std::option::Option::Some(RoutingRule_Domain_Type::PLAIN),
-            1 => ::std::option::Option::Some(RoutingRule_Domain_Type::DOMAIN),
-            2 => ::std::option::Option::Some(RoutingRule_Domain_Type::FULL),
-            _ => ::std::option::Option::None
-        }
+    fn from_i32(value: i32) -> ::std::option::Option<RoutingRule_Domain_Type> {
+        match value {
+            0 => ::std::option::Option::Some(RoutingRule_Domain_Type::PLAIN),
+            1 => ::std::option::Option::Some(RoutingRule_Domain_Type::DOMAIN),
+            2 => ::std::option::Option::Some(RoutingRule_Domain_Type::FULL),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [RoutingRule_Domain_Type] = &[
+            RoutingRule_Domain_Type::PLAIN,
+            RoutingRule_Domain_Type::DOMAIN,
+            RoutingRule_Domain_Type::FULL,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<RoutingRule_Domain_Type>("RoutingRule.Domain.Type", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for RoutingRule_Domain_Type {
+}
+
+impl ::std::default::Default for RoutingRule_Domain_Type {
+    fn default() -> Self {
+        RoutingRule_Domain_Type::PLAIN
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RoutingRule_Domain_Type {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct RoutingRule_Mmdb {
+    // message fields
+    pub file: ::std::string::String,
+    pub country_code: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a RoutingRule_Mmdb {
+    fn default() -> &'a RoutingRule_Mmdb {
+        <RoutingRule_Mmdb as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl RoutingRule_Mmdb {
+    pub fn new() -> RoutingRule_Mmdb {
+        ::std::default::Default::default()
+    }
+
+    // string file = 1;
+
+
+    pub fn get_file(&self) -> &str {
+        &self.file
+    }
+    pub fn clear_file(&mut self) {
+        self.file.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_file(&mut self, v: ::std::string::String) {
+        self.file = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_file(&mut self) -> &mut ::std::string::String {
+        &mut self.file
+    }
+
+    // Take field
+    pub fn take_file(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.file, ::std::string::String::new())
+    }
+
+    // string country_code = 2;
+
+
+    pub fn get_country_code(&self) -> &str {
+        &self.country_code
+    }
+    pub fn clear_country_code(&mut self) {
+        self.country_code.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_country_code(&mut self, v: ::std::string::String) {
+        self.country_code = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_country_code(&mut self) -> &mut ::std::string::String {
+        &mut self.country_code
+    }
+
+    // Take field
+    pub fn take_country_code(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.country_code, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for RoutingRule_Mmdb {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.file)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.country_code)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.file.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.file);
+        }
+        if !self.country_code.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.country_code);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.file.is_empty() {
+            os.write_string(1, &self.file)?;
+        }
+        if !self.country_code.is_empty() {
+            os.write_string(2, &self.country_code)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
     }
 
-    fn values() -> &'static [Self] {
-        static values: &'static [RoutingRule_Domain_Type] = &[
-            RoutingRule_Domain_Type::PLAIN,
-            RoutingRule_Domain_Type::DOMAIN,
-            RoutingRule_Domain_Type::FULL,
-        ];
-        values
+    fn new() -> RoutingRule_Mmdb {
+        RoutingRule_Mmdb::new()
     }
 
-    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
-        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
-            ::protobuf::reflect::EnumDescriptor::new_pb_name::<RoutingRule_Domain_Type>("RoutingRule.Domain.Type", file_descriptor_proto())
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "file",
+                |m: &RoutingRule_Mmdb| { &m.file },
+                |m: &mut RoutingRule_Mmdb| { &mut m.file },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "country_code",
+                |m: &RoutingRule_Mmdb| { &m.country_code },
+                |m: &mut RoutingRule_Mmdb| { &mut m.country_code },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RoutingRule_Mmdb>(
+                "RoutingRule.Mmdb",
+                fields,
+                file_descriptor_proto()
+            )
         })
     }
+
+    fn default_instance() -> &'static RoutingRule_Mmdb {
+        static instance: ::protobuf::rt::LazyV2<RoutingRule_Mmdb> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(RoutingRule_Mmdb::new)
+    }
 }
 
-impl ::std::marker::Copy for RoutingRule_Domain_Type {
+impl ::protobuf::Clear for RoutingRule_Mmdb {
+    fn clear(&mut self) {
+        self.file.clear();
+        self.country_code.clear();
+        self.unknown_fields.clear();
+    }
 }
 
-impl ::std::default::Default for RoutingRule_Domain_Type {
-    fn default() -> Self {
-        RoutingRule_Domain_Type::PLAIN
+impl ::std::fmt::Debug for RoutingRule_Mmdb {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for RoutingRule_Domain_Type {
+impl ::protobuf::reflect::ProtobufValue for RoutingRule_Mmdb {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
-        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+        ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct RoutingRule_Mmdb {
+pub struct Router {
     // message fields
-    pub file: ::std::string::String,
-    pub country_code: ::std::string::String,
+    pub dry_run: bool,
+    pub default_outbound: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a RoutingRule_Mmdb {
-    fn default() -> &'a RoutingRule_Mmdb {
-        <RoutingRule_Mmdb as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a Router {
+    fn default() -> &'a Router {
+        <Router as ::protobuf::Message>::default_instance()
     }
 }
 
-impl RoutingRule_Mmdb {
-    pub fn new() -> RoutingRule_Mmdb {
+impl Router {
+    pub fn new() -> Router {
         ::std::default::Default::default()
     }
 
-    // string file = 1;
+    // bool dry_run = 1;
 
 
-    pub fn get_file(&self) -> &str {
-        &self.file
+    pub fn get_dry_run(&self) -> bool {
+        self.dry_run
     }
-    pub fn clear_file(&mut self) {
-        self.file.clear();
+    pub fn clear_dry_run(&mut self) {
+        self.dry_run = false;
     }
 
     // Param is passed by value, moved
-    pub fn set_file(&mut self, v: ::std::string::String) {
-        self.file = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_file(&mut self) -> &mut ::std::string::String {
-        &mut self.file
-    }
-
-    // Take field
-    pub fn take_file(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.file, ::std::string::String::new())
+    pub fn set_dry_run(&mut self, v: bool) {
+        self.dry_run = v;
     }
 
-    // string country_code = 2;
+    // string default_outbound = 2;
 
 
-    pub fn get_country_code(&self) -> &str {
-        &self.country_code
+    pub fn get_default_outbound(&self) -> &str {
+        &self.default_outbound
     }
-    pub fn clear_country_code(&mut self) {
-        self.country_code.clear();
+    pub fn clear_default_outbound(&mut self) {
+        self.default_outbound.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_country_code(&mut self, v: ::std::string::String) {
-        self.country_code = v;
+    pub fn set_default_outbound(&mut self, v: ::std::string::String) {
+        self.default_outbound = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_country_code(&mut self) -> &mut ::std::string::String {
-        &mut self.country_code
+    pub fn mut_default_outbound(&mut self) -> &mut ::std::string::String {
+        &mut self.default_outbound
     }
 
     // Take field
-    pub fn take_country_code(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.country_code, ::std::string::String::new())
+    pub fn take_default_outbound(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.default_outbound, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for RoutingRule_Mmdb {
+impl ::protobuf::Message for Router {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -6244,10 +12799,14 @@ impl ::protobuf::Message for RoutingRule_Mmdb {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.file)?;
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.dry_run = tmp;
                 },
                 2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.country_code)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.default_outbound)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -6261,11 +12820,11 @@ impl ::protobuf::Message for RoutingRule_Mmdb {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.file.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.file);
+        if self.dry_run != false {
+            my_size += 2;
         }
-        if !self.country_code.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.country_code);
+        if !self.default_outbound.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.default_outbound);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -6273,11 +12832,11 @@ impl ::protobuf::Message for RoutingRule_Mmdb {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.file.is_empty() {
-            os.write_string(1, &self.file)?;
+        if self.dry_run != false {
+            os.write_bool(1, self.dry_run)?;
         }
-        if !self.country_code.is_empty() {
-            os.write_string(2, &self.country_code)?;
+        if !self.default_outbound.is_empty() {
+            os.write_string(2, &self.default_outbound)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -6309,53 +12868,53 @@ impl ::protobuf::Message for RoutingRule_Mmdb {
         Self::descriptor_static()
     }
 
-    fn new() -> RoutingRule_Mmdb {
-        RoutingRule_Mmdb::new()
+    fn new() -> Router {
+        Router::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "file",
-                |m: &RoutingRule_Mmdb| { &m.file },
-                |m: &mut RoutingRule_Mmdb| { &mut m.file },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "dry_run",
+                |m: &Router| { &m.dry_run },
+                |m: &mut Router| { &mut m.dry_run },
             ));
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "country_code",
-                |m: &RoutingRule_Mmdb| { &m.country_code },
-                |m: &mut RoutingRule_Mmdb| { &mut m.country_code },
+                "default_outbound",
+                |m: &Router| { &m.default_outbound },
+                |m: &mut Router| { &mut m.default_outbound },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<RoutingRule_Mmdb>(
-                "RoutingRule.Mmdb",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Router>(
+                "Router",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static RoutingRule_Mmdb {
-        static instance: ::protobuf::rt::LazyV2<RoutingRule_Mmdb> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(RoutingRule_Mmdb::new)
+    fn default_instance() -> &'static Router {
+        static instance: ::protobuf::rt::LazyV2<Router> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Router::new)
     }
 }
 
-impl ::protobuf::Clear for RoutingRule_Mmdb {
+impl ::protobuf::Clear for Router {
     fn clear(&mut self) {
-        self.file.clear();
-        self.country_code.clear();
+        self.dry_run = false;
+        self.default_outbound.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for RoutingRule_Mmdb {
+impl ::std::fmt::Debug for Router {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for RoutingRule_Mmdb {
+impl ::protobuf::reflect::ProtobufValue for Router {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
@@ -6369,6 +12928,10 @@ pub struct Config {
     pub outbounds: ::protobuf::RepeatedField<Outbound>,
     pub routing_rules: ::protobuf::RepeatedField<RoutingRule>,
     pub dns: ::protobuf::SingularPtrField<DNS>,
+    pub control_outbound: ::std::string::String,
+    pub router: ::protobuf::SingularPtrField<Router>,
+    pub udp_session_timeout: u32,
+    pub link_buffer_size: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -6525,6 +13088,95 @@ impl Config {
     pub fn take_dns(&mut self) -> DNS {
         self.dns.take().unwrap_or_else(|| DNS::new())
     }
+
+    // string control_outbound = 6;
+
+
+    pub fn get_control_outbound(&self) -> &str {
+        &self.control_outbound
+    }
+    pub fn clear_control_outbound(&mut self) {
+        self.control_outbound.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_control_outbound(&mut self, v: ::std::string::String) {
+        self.control_outbound = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_control_outbound(&mut self) -> &mut ::std::string::String {
+        &mut self.control_outbound
+    }
+
+    // Take field
+    pub fn take_control_outbound(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.control_outbound, ::std::string::String::new())
+    }
+
+    // .Router router = 7;
+
+
+    pub fn get_router(&self) -> &Router {
+        self.router.as_ref().unwrap_or_else(|| <Router as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_router(&mut self) {
+        self.router.clear();
+    }
+
+    pub fn has_router(&self) -> bool {
+        self.router.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_router(&mut self, v: Router) {
+        self.router = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_router(&mut self) -> &mut Router {
+        if self.router.is_none() {
+            self.router.set_default();
+        }
+        self.router.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_router(&mut self) -> Router {
+        self.router.take().unwrap_or_else(|| Router::new())
+    }
+
+    // uint32 udp_session_timeout = 8;
+
+
+    pub fn get_udp_session_timeout(&self) -> u32 {
+        self.udp_session_timeout
+    }
+    pub fn clear_udp_session_timeout(&mut self) {
+        self.udp_session_timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_udp_session_timeout(&mut self, v: u32) {
+        self.udp_session_timeout = v;
+    }
+
+    // uint32 link_buffer_size = 9;
+
+
+    pub fn get_link_buffer_size(&self) -> u32 {
+        self.link_buffer_size
+    }
+    pub fn clear_link_buffer_size(&mut self) {
+        self.link_buffer_size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_link_buffer_size(&mut self, v: u32) {
+        self.link_buffer_size = v;
+    }
 }
 
 impl ::protobuf::Message for Config {
@@ -6554,6 +13206,11 @@ impl ::protobuf::Message for Config {
                 return false;
             }
         };
+        for v in &self.router {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -6576,6 +13233,26 @@ impl ::protobuf::Message for Config {
                 5 => {
                     ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.dns)?;
                 },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.control_outbound)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.router)?;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.udp_session_timeout = tmp;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.link_buffer_size = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -6608,6 +13285,19 @@ impl ::protobuf::Message for Config {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        if !self.control_outbound.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.control_outbound);
+        }
+        if let Some(ref v) = self.router.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.udp_session_timeout != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.udp_session_timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.link_buffer_size != 0 {
+            my_size += ::protobuf::rt::value_size(9, self.link_buffer_size, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -6639,6 +13329,20 @@ impl ::protobuf::Message for Config {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        if !self.control_outbound.is_empty() {
+            os.write_string(6, &self.control_outbound)?;
+        }
+        if let Some(ref v) = self.router.as_ref() {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.udp_session_timeout != 0 {
+            os.write_uint32(8, self.udp_session_timeout)?;
+        }
+        if self.link_buffer_size != 0 {
+            os.write_uint32(9, self.link_buffer_size)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -6702,6 +13406,26 @@ impl ::protobuf::Message for Config {
                 |m: &Config| { &m.dns },
                 |m: &mut Config| { &mut m.dns },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "control_outbound",
+                |m: &Config| { &m.control_outbound },
+                |m: &mut Config| { &mut m.control_outbound },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Router>>(
+                "router",
+                |m: &Config| { &m.router },
+                |m: &mut Config| { &mut m.router },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "udp_session_timeout",
+                |m: &Config| { &m.udp_session_timeout },
+                |m: &mut Config| { &mut m.udp_session_timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "link_buffer_size",
+                |m: &Config| { &m.link_buffer_size },
+                |m: &mut Config| { &mut m.link_buffer_size },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<Config>(
                 "Config",
                 fields,
@@ -6723,6 +13447,10 @@ impl ::protobuf::Clear for Config {
         self.outbounds.clear();
         self.routing_rules.clear();
         self.dns.clear();
+        self.control_outbound.clear();
+        self.router.clear();
+        self.udp_session_timeout = 0;
+        self.link_buffer_size = 0;
         self.unknown_fields.clear();
     }
 }
@@ -6739,88 +13467,281 @@ impl ::protobuf::reflect::ProtobufValue for Config {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum DnsStrategy {
+    DEFAULT = 0,
+    IPV4_ONLY = 1,
+    IPV6_ONLY = 2,
+    IPV4_FIRST = 3,
+    IPV6_FIRST = 4,
+}
+
+impl ::protobuf::ProtobufEnum for DnsStrategy {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<DnsStrategy> {
+        match value {
+            0 => ::std::option::Option::Some(DnsStrategy::DEFAULT),
+            1 => ::std::option::Option::Some(DnsStrategy::IPV4_ONLY),
+            2 => ::std::option::Option::Some(DnsStrategy::IPV6_ONLY),
+            3 => ::std::option::Option::Some(DnsStrategy::IPV4_FIRST),
+            4 => ::std::option::Option::Some(DnsStrategy::IPV6_FIRST),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [DnsStrategy] = &[
+            DnsStrategy::DEFAULT,
+            DnsStrategy::IPV4_ONLY,
+            DnsStrategy::IPV6_ONLY,
+            DnsStrategy::IPV4_FIRST,
+            DnsStrategy::IPV6_FIRST,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<DnsStrategy>("DnsStrategy", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for DnsStrategy {
+}
+
+impl ::std::default::Default for DnsStrategy {
+    fn default() -> Self {
+        DnsStrategy::DEFAULT
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DnsStrategy {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x20src/config/internal/config.proto\"\xbd\x01\n\x03DNS\x12\x18\n\x07s\
-    ervers\x18\x01\x20\x03(\tR\x07servers\x12\x12\n\x04bind\x18\x02\x20\x01(\
-    \tR\x04bind\x12%\n\x05hosts\x18\x03\x20\x03(\x0b2\x0f.DNS.HostsEntryR\
-    \x05hosts\x1a\x1d\n\x03IPs\x12\x16\n\x06values\x18\x01\x20\x03(\tR\x06va\
-    lues\x1aB\n\nHostsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\
-    \x1e\n\x05value\x18\x02\x20\x01(\x0b2\x08.DNS.IPsR\x05value:\x028\x01\"\
-    \xcc\x01\n\x03Log\x12\x20\n\x05level\x18\x01\x20\x01(\x0e2\n.Log.LevelR\
-    \x05level\x12#\n\x06output\x18\x02\x20\x01(\x0e2\x0b.Log.OutputR\x06outp\
-    ut\x12\x1f\n\x0boutput_file\x18\x03\x20\x01(\tR\noutputFile\"<\n\x05Leve\
-    l\x12\t\n\x05TRACE\x10\0\x12\t\n\x05DEBUG\x10\x01\x12\x08\n\x04INFO\x10\
-    \x02\x12\x08\n\x04WARN\x10\x03\x12\t\n\x05ERROR\x10\x04\"\x1f\n\x06Outpu\
-    t\x12\x0b\n\x07CONSOLE\x10\0\x12\x08\n\x04FILE\x10\x01\"\xec\x01\n\x12TU\
-    NInboundSettings\x12\x0e\n\x02fd\x18\x01\x20\x01(\x05R\x02fd\x12\x12\n\
-    \x04name\x18\x02\x20\x01(\tR\x04name\x12\x18\n\x07address\x18\x03\x20\
-    \x01(\tR\x07address\x12\x18\n\x07gateway\x18\x04\x20\x01(\tR\x07gateway\
-    \x12\x18\n\x07netmask\x18\x05\x20\x01(\tR\x07netmask\x12\x10\n\x03mtu\
-    \x18\x06\x20\x01(\x05R\x03mtu\x12(\n\x10fake_dns_exclude\x18\x07\x20\x03\
-    (\tR\x0efakeDnsExclude\x12(\n\x10fake_dns_include\x18\x08\x20\x03(\tR\
-    \x0efakeDnsInclude\"3\n\x15TrojanInboundSettings\x12\x1a\n\x08password\
-    \x18\x03\x20\x01(\tR\x08password\".\n\x18WebSocketInboundSettings\x12\
-    \x12\n\x04path\x18\x01\x20\x01(\tR\x04path\".\n\x14ChainInboundSettings\
-    \x12\x16\n\x06actors\x18\x01\x20\x03(\tR\x06actors\"\x81\x01\n\x07Inboun\
-    d\x12\x10\n\x03tag\x18\x01\x20\x01(\tR\x03tag\x12\x1a\n\x08protocol\x18\
-    \x02\x20\x01(\tR\x08protocol\x12\x18\n\x07address\x18\x03\x20\x01(\tR\
-    \x07address\x12\x12\n\x04port\x18\x04\x20\x01(\rR\x04port\x12\x1a\n\x08s\
-    ettings\x18\x05\x20\x01(\x0cR\x08settings\"H\n\x18RedirectOutboundSettin\
-    gs\x12\x18\n\x07address\x18\x01\x20\x01(\tR\x07address\x12\x12\n\x04port\
-    \x18\x02\x20\x01(\rR\x04port\"E\n\x15SocksOutboundSettings\x12\x18\n\x07\
-    address\x18\x01\x20\x01(\tR\x07address\x12\x12\n\x04port\x18\x02\x20\x01\
-    (\rR\x04port\"\x7f\n\x1bShadowsocksOutboundSettings\x12\x18\n\x07address\
-    \x18\x01\x20\x01(\tR\x07address\x12\x12\n\x04port\x18\x02\x20\x01(\rR\
-    \x04port\x12\x16\n\x06method\x18\x03\x20\x01(\tR\x06method\x12\x1a\n\x08\
-    password\x18\x04\x20\x01(\tR\x08password\"b\n\x16TrojanOutboundSettings\
-    \x12\x18\n\x07address\x18\x01\x20\x01(\tR\x07address\x12\x12\n\x04port\
-    \x18\x02\x20\x01(\rR\x04port\x12\x1a\n\x08password\x18\x03\x20\x01(\tR\
-    \x08password\"u\n\x15VMessOutboundSettings\x12\x18\n\x07address\x18\x01\
-    \x20\x01(\tR\x07address\x12\x12\n\x04port\x18\x02\x20\x01(\rR\x04port\
-    \x12\x12\n\x04uuid\x18\x03\x20\x01(\tR\x04uuid\x12\x1a\n\x08security\x18\
-    \x04\x20\x01(\tR\x08security\"Y\n\x15VLessOutboundSettings\x12\x18\n\x07\
-    address\x18\x01\x20\x01(\tR\x07address\x12\x12\n\x04port\x18\x02\x20\x01\
-    (\rR\x04port\x12\x12\n\x04uuid\x18\x03\x20\x01(\tR\x04uuid\"J\n\x13TlsOu\
-    tboundSettings\x12\x1f\n\x0bserver_name\x18\x01\x20\x01(\tR\nserverName\
-    \x12\x12\n\x04alpn\x18\x02\x20\x03(\tR\x04alpn\"\xae\x01\n\x19WebSocketO\
-    utboundSettings\x12\x12\n\x04path\x18\x01\x20\x01(\tR\x04path\x12A\n\x07\
-    headers\x18\x02\x20\x03(\x0b2'.WebSocketOutboundSettings.HeadersEntryR\
-    \x07headers\x1a:\n\x0cHeadersEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\
-    \x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"?\n\
-    \x15HTTP2OutboundSettings\x12\x12\n\x04path\x18\x01\x20\x01(\tR\x04path\
-    \x12\x12\n\x04host\x18\x02\x20\x01(\tR\x04host\"O\n\x16TryAllOutboundSet\
-    tings\x12\x16\n\x06actors\x18\x01\x20\x03(\tR\x06actors\x12\x1d\n\ndelay\
-    _base\x18\x02\x20\x01(\rR\tdelayBase\"0\n\x16RandomOutboundSettings\x12\
-    \x16\n\x06actors\x18\x01\x20\x03(\tR\x06actors\"/\n\x15ChainOutboundSett\
-    ings\x12\x16\n\x06actors\x18\x01\x20\x03(\tR\x06actors\"K\n\x15RetryOutb\
-    oundSettings\x12\x16\n\x06actors\x18\x01\x20\x03(\tR\x06actors\x12\x1a\n\
-    \x08attempts\x18\x02\x20\x01(\rR\x08attempts\"\xa6\x02\n\x18FailOverOutb\
-    oundSettings\x12\x16\n\x06actors\x18\x01\x20\x03(\tR\x06actors\x12!\n\
-    \x0cfail_timeout\x18\x02\x20\x01(\rR\x0bfailTimeout\x12!\n\x0chealth_che\
-    ck\x18\x03\x20\x01(\x08R\x0bhealthCheck\x12%\n\x0echeck_interval\x18\x04\
-    \x20\x01(\rR\rcheckInterval\x12\x1a\n\x08failover\x18\x05\x20\x01(\x08R\
-    \x08failover\x12%\n\x0efallback_cache\x18\x06\x20\x01(\x08R\rfallbackCac\
-    he\x12\x1d\n\ncache_size\x18\x07\x20\x01(\rR\tcacheSize\x12#\n\rcache_ti\
-    meout\x18\x08\x20\x01(\rR\x0ccacheTimeout\"D\n\x14StatOutboundSettings\
-    \x12\x18\n\x07address\x18\x01\x20\x01(\tR\x07address\x12\x12\n\x04port\
-    \x18\x02\x20\x01(\rR\x04port\"h\n\x08Outbound\x12\x10\n\x03tag\x18\x01\
-    \x20\x01(\tR\x03tag\x12\x1a\n\x08protocol\x18\x02\x20\x01(\tR\x08protoco\
-    l\x12\x12\n\x04bind\x18\x03\x20\x01(\tR\x04bind\x12\x1a\n\x08settings\
-    \x18\x04\x20\x01(\x0cR\x08settings\"\xf6\x02\n\x0bRoutingRule\x12\x1d\n\
-    \ntarget_tag\x18\x01\x20\x01(\tR\ttargetTag\x12-\n\x07domains\x18\x02\
-    \x20\x03(\x0b2\x13.RoutingRule.DomainR\x07domains\x12\x19\n\x08ip_cidrs\
-    \x18\x03\x20\x03(\tR\x07ipCidrs\x12'\n\x05mmdbs\x18\x04\x20\x03(\x0b2\
-    \x11.RoutingRule.MmdbR\x05mmdbs\x12\x1f\n\x0bport_ranges\x18\x05\x20\x03\
-    (\tR\nportRanges\x1au\n\x06Domain\x12,\n\x04type\x18\x01\x20\x01(\x0e2\
-    \x18.RoutingRule.Domain.TypeR\x04type\x12\x14\n\x05value\x18\x02\x20\x01\
-    (\tR\x05value\"'\n\x04Type\x12\t\n\x05PLAIN\x10\0\x12\n\n\x06DOMAIN\x10\
-    \x01\x12\x08\n\x04FULL\x10\x02\x1a=\n\x04Mmdb\x12\x12\n\x04file\x18\x01\
-    \x20\x01(\tR\x04file\x12!\n\x0ccountry_code\x18\x02\x20\x01(\tR\x0bcount\
-    ryCode\"\xba\x01\n\x06Config\x12\x16\n\x03log\x18\x01\x20\x01(\x0b2\x04.\
-    LogR\x03log\x12$\n\x08inbounds\x18\x02\x20\x03(\x0b2\x08.InboundR\x08inb\
-    ounds\x12'\n\toutbounds\x18\x03\x20\x03(\x0b2\t.OutboundR\toutbounds\x12\
-    1\n\rrouting_rules\x18\x04\x20\x03(\x0b2\x0c.RoutingRuleR\x0croutingRule\
-    s\x12\x16\n\x03dns\x18\x05\x20\x01(\x0b2\x04.DNSR\x03dnsb\x06proto3\
+    \n\x20src/config/internal/config.proto\"\xe7\x0b\n\x03DNS\x12\x1a\n\x07s\
+    ervers\x18\x01\x20\x03(\tR\x07serversB\0\x12\x14\n\x04bind\x18\x02\x20\
+    \x01(\tR\x04bindB\0\x12+\n\x05hosts\x18\x03\x20\x03(\x0b2\x13.DNS.hosts_\
+    MapEntryR\x05hostsB\0\x12%\n\rfilter_bogons\x18\x04\x20\x01(\x08R\x0cfil\
+    terBogonsB\0\x12'\n\x0efilter_private\x18\x05\x20\x01(\x08R\rfilterPriva\
+    teB\0\x12+\n\x10internal_domains\x18\x06\x20\x03(\tR\x0finternalDomainsB\
+    \0\x12K\n\x10server_outbounds\x18\x07\x20\x03(\x0b2\x1e.DNS.server_outbo\
+    unds_MapEntryR\x0fserverOutboundsB\0\x12\x1d\n\tfail_open\x18\x08\x20\
+    \x01(\x08R\x08failOpenB\0\x12!\n\x0bfallback_ip\x18\t\x20\x01(\tR\nfallb\
+    ackIpB\0\x12N\n\x11server_bootstraps\x18\n\x20\x03(\x0b2\x1f.DNS.server_\
+    bootstraps_MapEntryR\x10serverBootstrapsB\0\x12*\n\x10dot_idle_timeout\
+    \x18\x0b\x20\x01(\rR\x0edotIdleTimeoutB\0\x12G\n\x0fserver_ca_files\x18\
+    \x0c\x20\x03(\x0b2\x1d.DNS.server_ca_files_MapEntryR\rserverCaFilesB\0\
+    \x12\x1f\n\ncache_size\x18\r\x20\x01(\rR\tcacheSizeB\0\x12$\n\rmin_cache\
+    _ttl\x18\x0e\x20\x01(\rR\x0bminCacheTtlB\0\x12$\n\rmax_cache_ttl\x18\x0f\
+    \x20\x01(\rR\x0bmaxCacheTtlB\0\x12.\n\x12negative_cache_ttl\x18\x10\x20\
+    \x01(\rR\x10negativeCacheTtlB\0\x12\x1c\n\x08outbound\x18\x11\x20\x01(\t\
+    R\x08outboundB\0\x12*\n\x08strategy\x18\x12\x20\x01(\x0e2\x0c.DnsStrateg\
+    yR\x08strategyB\0\x12.\n\x06groups\x18\x13\x20\x03(\x0b2\x14.DNS.groups_\
+    MapEntryR\x06groupsB\0\x12)\n\x08policies\x18\x14\x20\x03(\x0b2\x0b.DNS.\
+    PolicyR\x08policiesB\0\x12\x1c\n\x08prefetch\x18\x15\x20\x01(\rR\x08pref\
+    etchB\0\x12,\n\x11prefetch_min_hits\x18\x16\x20\x01(\rR\x0fprefetchMinHi\
+    tsB\0\x12%\n\rclient_subnet\x18\x17\x20\x01(\tR\x0cclientSubnetB\0\x1a!\
+    \n\x03IPs\x12\x18\n\x06values\x18\x01\x20\x03(\tR\x06valuesB\0:\0\x1a%\n\
+    \x07Servers\x12\x18\n\x06values\x18\x01\x20\x03(\tR\x06valuesB\0:\0\x1aS\
+    \n\x06Policy\x12/\n\x07domains\x18\x01\x20\x03(\x0b2\x13.RoutingRule.Dom\
+    ainR\x07domainsB\0\x12\x16\n\x05group\x18\x02\x20\x01(\tR\x05groupB\0:\0\
+    \x1aB\n\x0ehosts_MapEntry\x12\x0e\n\x03key\x18\x01(\tR\x03key\x12\x1c\n\
+    \x05value\x18\x02(\x0b2\x08.DNS.IPsR\x05value:\x028\x01\x1aC\n\x19server\
+    _outbounds_MapEntry\x12\x0e\n\x03key\x18\x01(\tR\x03key\x12\x12\n\x05val\
+    ue\x18\x02(\tR\x05value:\x028\x01\x1aD\n\x1aserver_bootstraps_MapEntry\
+    \x12\x0e\n\x03key\x18\x01(\tR\x03key\x12\x12\n\x05value\x18\x02(\tR\x05v\
+    alue:\x028\x01\x1aB\n\x18server_ca_files_MapEntry\x12\x0e\n\x03key\x18\
+    \x01(\tR\x03key\x12\x12\n\x05value\x18\x02(\tR\x05value:\x028\x01\x1aG\n\
+    \x0fgroups_MapEntry\x12\x0e\n\x03key\x18\x01(\tR\x03key\x12\x20\n\x05val\
+    ue\x18\x02(\x0b2\x0c.DNS.ServersR\x05value:\x028\x01:\0\"\x83\x03\n\x03L\
+    og\x12\"\n\x05level\x18\x01\x20\x01(\x0e2\n.Log.LevelR\x05levelB\0\x12%\
+    \n\x06output\x18\x02\x20\x01(\x0e2\x0b.Log.OutputR\x06outputB\0\x12!\n\
+    \x0boutput_file\x18\x03\x20\x01(\tR\noutputFileB\0\x12%\n\x06format\x18\
+    \x04\x20\x01(\x0e2\x0b.Log.FormatR\x06formatB\0\x12-\n\x11disable_timest\
+    amp\x18\x05\x20\x01(\x08R\x10disableTimestampB\0\x12%\n\rdisable_color\
+    \x18\x06\x20\x01(\x08R\x0cdisableColorB\0\">\n\x05Level\x12\t\n\x05TRACE\
+    \x10\0\x12\t\n\x05DEBUG\x10\x01\x12\x08\n\x04INFO\x10\x02\x12\x08\n\x04W\
+    ARN\x10\x03\x12\t\n\x05ERROR\x10\x04\x1a\0\"!\n\x06Output\x12\x0b\n\x07C\
+    ONSOLE\x10\0\x12\x08\n\x04FILE\x10\x01\x1a\0\",\n\x06Format\x12\x0b\n\
+    \x07DEFAULT\x10\0\x12\t\n\x05PLAIN\x10\x01\x12\x08\n\x04JSON\x10\x02\x1a\
+    \0:\0\"\xf6\x03\n\x12TUNInboundSettings\x12\x10\n\x02fd\x18\x01\x20\x01(\
+    \x05R\x02fdB\0\x12\x14\n\x04name\x18\x02\x20\x01(\tR\x04nameB\0\x12\x1a\
+    \n\x07address\x18\x03\x20\x01(\tR\x07addressB\0\x12\x1a\n\x07gateway\x18\
+    \x04\x20\x01(\tR\x07gatewayB\0\x12\x1a\n\x07netmask\x18\x05\x20\x01(\tR\
+    \x07netmaskB\0\x12\x12\n\x03mtu\x18\x06\x20\x01(\x05R\x03mtuB\0\x12*\n\
+    \x10fake_dns_exclude\x18\x07\x20\x03(\tR\x0efakeDnsExcludeB\0\x12*\n\x10\
+    fake_dns_include\x18\x08\x20\x03(\tR\x0efakeDnsIncludeB\0\x125\n\x16fake\
+    _dns_routing_aware\x18\t\x20\x01(\x08R\x13fakeDnsRoutingAwareB\0\x123\n\
+    \x15fake_dns_persist_path\x18\n\x20\x01(\tR\x12fakeDnsPersistPathB\0\x12\
+    1\n\x14fake_dns_persist_ttl\x18\x0b\x20\x01(\rR\x11fakeDnsPersistTtlB\0\
+    \x12/\n\x13fake_dns_ipv6_range\x18\x0c\x20\x01(\tR\x10fakeDnsIpv6RangeB\
+    \0\x12&\n\x0efake_dns_range\x18\r\x20\x01(\tR\x0cfakeDnsRangeB\0:\0\"S\n\
+    \x13HttpInboundSettings\x12\x1c\n\x08username\x18\x01\x20\x01(\tR\x08use\
+    rnameB\0\x12\x1c\n\x08password\x18\x02\x20\x01(\tR\x08passwordB\0:\0\"\
+    \xe6\x01\n\x14SocksInboundSettings\x12\x1b\n\x08udp_bind\x18\x01\x20\x01\
+    (\tR\x07udpBindB\0\x12&\n\x0eudp_port_range\x18\x02\x20\x01(\tR\x0cudpPo\
+    rtRangeB\0\x12%\n\rudp_advertise\x18\x03\x20\x01(\tR\x0cudpAdvertiseB\0\
+    \x12\x1c\n\x08username\x18\x04\x20\x01(\tR\x08usernameB\0\x12\x1c\n\x08p\
+    assword\x18\x05\x20\x01(\tR\x08passwordB\0\x12$\n\rallow_no_auth\x18\x06\
+    \x20\x01(\x08R\x0ballowNoAuthB\0:\0\"s\n\x14MixedInboundSettings\x12-\n\
+    \x05socks\x18\x01\x20\x01(\x0b2\x15.SocksInboundSettingsR\x05socksB\0\
+    \x12*\n\x04http\x18\x02\x20\x01(\x0b2\x14.HttpInboundSettingsR\x04httpB\
+    \0:\0\"7\n\x15TrojanInboundSettings\x12\x1c\n\x08password\x18\x03\x20\
+    \x01(\tR\x08passwordB\0:\0\"2\n\x18WebSocketInboundSettings\x12\x14\n\
+    \x04path\x18\x01\x20\x01(\tR\x04pathB\0:\0\"2\n\x14ChainInboundSettings\
+    \x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06actorsB\0:\0\"\x9f\x03\n\x07\
+    Inbound\x12\x12\n\x03tag\x18\x01\x20\x01(\tR\x03tagB\0\x12\x1c\n\x08prot\
+    ocol\x18\x02\x20\x01(\tR\x08protocolB\0\x12\x1a\n\x07address\x18\x03\x20\
+    \x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x04\x20\x01(\rR\x04portB\0\
+    \x12\x1c\n\x08settings\x18\x05\x20\x01(\x0cR\x08settingsB\0\x128\n\x08re\
+    writes\x18\x06\x20\x03(\x0b2\x1a.Inbound.rewrites_MapEntryR\x08rewritesB\
+    \0\x12\x1a\n\x07backlog\x18\x07\x20\x01(\rR\x07backlogB\0\x120\n\x13udp_\
+    session_timeout\x18\x08\x20\x01(\rR\x11udpSessionTimeoutB\0\x12\x1e\n\ta\
+    ddresses\x18\t\x20\x03(\tR\taddressesB\0\x12+\n\nrate_limit\x18\n\x20\
+    \x01(\x0b2\n.RateLimitR\trateLimitB\0\x1a;\n\x11rewrites_MapEntry\x12\
+    \x0e\n\x03key\x18\x01(\tR\x03key\x12\x12\n\x05value\x18\x02(\tR\x05value\
+    :\x028\x01:\0\"N\n\x18RedirectOutboundSettings\x12\x1a\n\x07address\x18\
+    \x01\x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\x04\
+    portB\0:\0\"K\n\x15SocksOutboundSettings\x12\x1a\n\x07address\x18\x01\
+    \x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\x04port\
+    B\0:\0\"\x9d\x02\n\x1bShadowsocksOutboundSettings\x12\x1a\n\x07address\
+    \x18\x01\x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\
+    \x04portB\0\x12\x18\n\x06method\x18\x03\x20\x01(\tR\x06methodB\0\x12\x1c\
+    \n\x08password\x18\x04\x20\x01(\tR\x08passwordB\0\x12\x18\n\x06plugin\
+    \x18\x05\x20\x01(\tR\x06pluginB\0\x12!\n\x0bplugin_opts\x18\x06\x20\x01(\
+    \tR\npluginOptsB\0\x12\"\n\x0cudp_over_tcp\x18\x07\x20\x01(\x08R\nudpOve\
+    rTcpB\0\x121\n\x14udp_over_tcp_version\x18\x08\x20\x01(\rR\x11udpOverTcp\
+    VersionB\0:\0\"\xac\x02\n\x16TrojanOutboundSettings\x12\x1a\n\x07address\
+    \x18\x01\x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\
+    \x04portB\0\x12\x1c\n\x08password\x18\x03\x20\x01(\tR\x08passwordB\0\x12\
+    \"\n\x0cudp_over_tcp\x18\x04\x20\x01(\x08R\nudpOverTcpB\0\x121\n\x14udp_\
+    over_tcp_version\x18\x05\x20\x01(\rR\x11udpOverTcpVersionB\0\x12\x12\n\
+    \x03tls\x18\x06\x20\x01(\x08R\x03tlsB\0\x12!\n\x0bserver_name\x18\x07\
+    \x20\x01(\tR\nserverNameB\0\x12\x14\n\x04alpn\x18\x08\x20\x03(\tR\x04alp\
+    nB\0\x12\x1c\n\x08insecure\x18\t\x20\x01(\x08R\x08insecureB\0:\0\"\xc7\
+    \x01\n\x15VMessOutboundSettings\x12\x1a\n\x07address\x18\x01\x20\x01(\tR\
+    \x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\x04portB\0\x12\x14\
+    \n\x04uuid\x18\x03\x20\x01(\tR\x04uuidB\0\x12\x1c\n\x08security\x18\x04\
+    \x20\x01(\tR\x08securityB\0\x12\x1f\n\nreduce_rtt\x18\x05\x20\x01(\x08R\
+    \treduceRttB\0\x12%\n\rlegacy_header\x18\x06\x20\x01(\x08R\x0clegacyHead\
+    erB\0:\0\"\x99\x01\n\x15VLessOutboundSettings\x12\x1a\n\x07address\x18\
+    \x01\x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\x04\
+    portB\0\x12\x14\n\x04uuid\x18\x03\x20\x01(\tR\x04uuidB\0\x12\x14\n\x04fl\
+    ow\x18\x04\x20\x01(\tR\x04flowB\0\x12\x20\n\nencryption\x18\x05\x20\x01(\
+    \tR\nencryptionB\0:\0\"\xc1\x02\n\x19WireGuardOutboundSettings\x12\x1a\n\
+    \x07address\x18\x01\x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x02\
+    \x20\x01(\rR\x04portB\0\x12!\n\x0bprivate_key\x18\x03\x20\x01(\tR\npriva\
+    teKeyB\0\x12(\n\x0fpeer_public_key\x18\x04\x20\x01(\tR\rpeerPublicKeyB\0\
+    \x12%\n\rpreshared_key\x18\x05\x20\x01(\tR\x0cpresharedKeyB\0\x12%\n\rlo\
+    cal_address\x18\x06\x20\x01(\tR\x0clocalAddressB\0\x12!\n\x0ballowed_ips\
+    \x18\x07\x20\x03(\tR\nallowedIpsB\0\x12\x12\n\x03mtu\x18\x08\x20\x01(\rR\
+    \x03mtuB\0\x12\x1e\n\tkeepalive\x18\t\x20\x01(\rR\tkeepaliveB\0:\0\"\x94\
+    \x02\n\x13SshOutboundSettings\x12\x1a\n\x07address\x18\x01\x20\x01(\tR\
+    \x07addressB\0\x12\x14\n\x04port\x18\x02\x20\x01(\rR\x04portB\0\x12\x1c\
+    \n\x08username\x18\x03\x20\x01(\tR\x08usernameB\0\x12\x1c\n\x08password\
+    \x18\x04\x20\x01(\tR\x08passwordB\0\x12!\n\x0bprivate_key\x18\x05\x20\
+    \x01(\tR\nprivateKeyB\0\x126\n\x16private_key_passphrase\x18\x06\x20\x01\
+    (\tR\x14privateKeyPassphraseB\0\x122\n\x14host_key_fingerprint\x18\x07\
+    \x20\x01(\tR\x12hostKeyFingerprintB\0:\0\"\x9a\x02\n\x13TlsOutboundSetti\
+    ngs\x12!\n\x0bserver_name\x18\x01\x20\x01(\tR\nserverNameB\0\x12\x14\n\
+    \x04alpn\x18\x02\x20\x03(\tR\x04alpnB\0\x12\x1f\n\nearly_data\x18\x03\
+    \x20\x01(\x08R\tearlyDataB\0\x12\"\n\x0bfingerprint\x18\x04\x20\x01(\tR\
+    \x0bfingerprintB\0\x12\x1c\n\x08insecure\x18\x05\x20\x01(\x08R\x08insecu\
+    reB\0\x12!\n\x0bcert_sha256\x18\x06\x20\x01(\tR\ncertSha256B\0\x12!\n\
+    \x0bclient_cert\x18\x07\x20\x01(\tR\nclientCertB\0\x12\x1f\n\nclient_key\
+    \x18\x08\x20\x01(\tR\tclientKeyB\0:\0\"\x97\x02\n\x19WebSocketOutboundSe\
+    ttings\x12\x14\n\x04path\x18\x01\x20\x01(\tR\x04pathB\0\x12G\n\x07header\
+    s\x18\x02\x20\x03(\x0b2+.WebSocketOutboundSettings.headers_MapEntryR\x07\
+    headersB\0\x12&\n\x0emax_early_data\x18\x03\x20\x01(\rR\x0cmaxEarlyDataB\
+    \0\x125\n\x16early_data_header_name\x18\x04\x20\x01(\tR\x13earlyDataHead\
+    erNameB\0\x1a:\n\x10headers_MapEntry\x12\x0e\n\x03key\x18\x01(\tR\x03key\
+    \x12\x12\n\x05value\x18\x02(\tR\x05value:\x028\x01:\0\"E\n\x15HTTP2Outbo\
+    undSettings\x12\x14\n\x04path\x18\x01\x20\x01(\tR\x04pathB\0\x12\x14\n\
+    \x04host\x18\x02\x20\x01(\tR\x04hostB\0:\0\"U\n\x16TryAllOutboundSetting\
+    s\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06actorsB\0\x12\x1f\n\ndelay_\
+    base\x18\x02\x20\x01(\rR\tdelayBaseB\0:\0\"0\n\x14DropOutboundSettings\
+    \x12\x16\n\x05reset\x18\x01\x20\x01(\x08R\x05resetB\0:\0\"]\n\x16RandomO\
+    utboundSettings\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06actorsB\0\x12\
+    '\n\x0esticky_timeout\x18\x02\x20\x01(\rR\rstickyTimeoutB\0:\0\"\xfd\x01\
+    \n\x17BalanceOutboundSettings\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\
+    \x06actorsB\0\x12?\n\x08strategy\x18\x02\x20\x01(\x0e2!.BalanceOutboundS\
+    ettings.StrategyR\x08strategyB\0\x12\x1a\n\x07weights\x18\x03\x20\x03(\r\
+    R\x07weightsB\0\x12'\n\x0esticky_timeout\x18\x04\x20\x01(\rR\rstickyTime\
+    outB\0\"@\n\x08Strategy\x12\x0f\n\x0bROUND_ROBIN\x10\0\x12\x0c\n\x08WEIG\
+    HTED\x10\x01\x12\x13\n\x0fCONSISTENT_HASH\x10\x02\x1a\0:\0\"4\n\x16Selec\
+    tOutboundSettings\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06actorsB\0:\
+    \0\"\x87\x01\n\x17UrlTestOutboundSettings\x12\x18\n\x06actors\x18\x01\
+    \x20\x03(\tR\x06actorsB\0\x12\x12\n\x03url\x18\x02\x20\x01(\tR\x03urlB\0\
+    \x12\x1c\n\x08interval\x18\x03\x20\x01(\rR\x08intervalB\0\x12\x1e\n\ttol\
+    erance\x18\x04\x20\x01(\rR\ttoleranceB\0:\0\"3\n\x15ChainOutboundSetting\
+    s\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06actorsB\0:\0\"Q\n\x15RetryO\
+    utboundSettings\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06actorsB\0\x12\
+    \x1c\n\x08attempts\x18\x02\x20\x01(\rR\x08attemptsB\0:\0\"\xda\x02\n\x18\
+    FailOverOutboundSettings\x12\x18\n\x06actors\x18\x01\x20\x03(\tR\x06acto\
+    rsB\0\x12#\n\x0cfail_timeout\x18\x02\x20\x01(\rR\x0bfailTimeoutB\0\x12#\
+    \n\x0chealth_check\x18\x03\x20\x01(\x08R\x0bhealthCheckB\0\x12'\n\x0eche\
+    ck_interval\x18\x04\x20\x01(\rR\rcheckIntervalB\0\x12\x1c\n\x08failover\
+    \x18\x05\x20\x01(\x08R\x08failoverB\0\x12'\n\x0efallback_cache\x18\x06\
+    \x20\x01(\x08R\rfallbackCacheB\0\x12\x1f\n\ncache_size\x18\x07\x20\x01(\
+    \rR\tcacheSizeB\0\x12%\n\rcache_timeout\x18\x08\x20\x01(\rR\x0ccacheTime\
+    outB\0\x12\x20\n\npriorities\x18\t\x20\x03(\rR\nprioritiesB\0:\0\"\xdf\
+    \x01\n\"PluggableTransportOutboundSettings\x12\x14\n\x04path\x18\x01\x20\
+    \x01(\tR\x04pathB\0\x12\x14\n\x04args\x18\x02\x20\x03(\tR\x04argsB\0\x12\
+    \x1e\n\ttransport\x18\x03\x20\x01(\tR\ttransportB\0\x12\x1a\n\x07address\
+    \x18\x04\x20\x01(\tR\x07addressB\0\x12\x14\n\x04port\x18\x05\x20\x01(\rR\
+    \x04portB\0\x12\x1a\n\x07options\x18\x06\x20\x01(\tR\x07optionsB\0\x12\
+    \x1d\n\tstate_dir\x18\x07\x20\x01(\tR\x08stateDirB\0:\0\"J\n\x14StatOutb\
+    oundSettings\x12\x1a\n\x07address\x18\x01\x20\x01(\tR\x07addressB\0\x12\
+    \x14\n\x04port\x18\x02\x20\x01(\rR\x04portB\0:\0\"\xa5\x01\n\tRateLimit\
+    \x12+\n\x06uplink\x18\x01\x20\x01(\x0b2\x11.RateLimit.BucketR\x06uplinkB\
+    \0\x12/\n\x08downlink\x18\x02\x20\x01(\x0b2\x11.RateLimit.BucketR\x08dow\
+    nlinkB\0\x1a8\n\x06Bucket\x12\x14\n\x04rate\x18\x01\x20\x01(\x04R\x04rat\
+    eB\0\x12\x16\n\x05burst\x18\x02\x20\x01(\x04R\x05burstB\0:\0:\0\"\xdc\
+    \x02\n\x08Outbound\x12\x12\n\x03tag\x18\x01\x20\x01(\tR\x03tagB\0\x12\
+    \x1c\n\x08protocol\x18\x02\x20\x01(\tR\x08protocolB\0\x12\x14\n\x04bind\
+    \x18\x03\x20\x01(\tR\x04bindB\0\x12\x1c\n\x08settings\x18\x04\x20\x01(\
+    \x0cR\x08settingsB\0\x12#\n\x0cidle_timeout\x18\x05\x20\x01(\rR\x0bidleT\
+    imeoutB\0\x12)\n\x0fconnect_timeout\x18\x06\x20\x01(\rR\x0econnectTimeou\
+    tB\0\x120\n\x13disable_tcp_nodelay\x18\x07\x20\x01(\x08R\x11disableTcpNo\
+    delayB\0\x12%\n\rtcp_keepalive\x18\x08\x20\x01(\rR\x0ctcpKeepaliveB\0\
+    \x12+\n\nrate_limit\x18\t\x20\x01(\x0b2\n.RateLimitR\trateLimitB\0\x12\
+    \x12\n\x03dns\x18\n\x20\x01(\tR\x03dnsB\0:\0\"\xa1\x04\n\x0bRoutingRule\
+    \x12\x1f\n\ntarget_tag\x18\x01\x20\x01(\tR\ttargetTagB\0\x12/\n\x07domai\
+    ns\x18\x02\x20\x03(\x0b2\x13.RoutingRule.DomainR\x07domainsB\0\x12\x1b\n\
+    \x08ip_cidrs\x18\x03\x20\x03(\tR\x07ipCidrsB\0\x12)\n\x05mmdbs\x18\x04\
+    \x20\x03(\x0b2\x11.RoutingRule.MmdbR\x05mmdbsB\0\x12!\n\x0bport_ranges\
+    \x18\x05\x20\x03(\tR\nportRangesB\0\x12\x17\n\x06no_log\x18\x06\x20\x01(\
+    \x08R\x05noLogB\0\x12%\n\rprocess_names\x18\x07\x20\x03(\tR\x0cprocessNa\
+    mesB\0\x121\n\x0cdns_strategy\x18\x08\x20\x01(\x0e2\x0c.DnsStrategyR\x0b\
+    dnsStrategyB\0\x12\x1c\n\x08networks\x18\t\x20\x03(\tR\x08networksB\0\
+    \x1a}\n\x06Domain\x12.\n\x04type\x18\x01\x20\x01(\x0e2\x18.RoutingRule.D\
+    omain.TypeR\x04typeB\0\x12\x16\n\x05value\x18\x02\x20\x01(\tR\x05valueB\
+    \0\")\n\x04Type\x12\t\n\x05PLAIN\x10\0\x12\n\n\x06DOMAIN\x10\x01\x12\x08\
+    \n\x04FULL\x10\x02\x1a\0:\0\x1aC\n\x04Mmdb\x12\x14\n\x04file\x18\x01\x20\
+    \x01(\tR\x04fileB\0\x12#\n\x0ccountry_code\x18\x02\x20\x01(\tR\x0bcountr\
+    yCodeB\0:\0:\0\"R\n\x06Router\x12\x19\n\x07dry_run\x18\x01\x20\x01(\x08R\
+    \x06dryRunB\0\x12+\n\x10default_outbound\x18\x02\x20\x01(\tR\x0fdefaultO\
+    utboundB\0:\0\"\xf4\x02\n\x06Config\x12\x18\n\x03log\x18\x01\x20\x01(\
+    \x0b2\x04.LogR\x03logB\0\x12&\n\x08inbounds\x18\x02\x20\x03(\x0b2\x08.In\
+    boundR\x08inboundsB\0\x12)\n\toutbounds\x18\x03\x20\x03(\x0b2\t.Outbound\
+    R\toutboundsB\0\x123\n\rrouting_rules\x18\x04\x20\x03(\x0b2\x0c.RoutingR\
+    uleR\x0croutingRulesB\0\x12\x18\n\x03dns\x18\x05\x20\x01(\x0b2\x04.DNSR\
+    \x03dnsB\0\x12+\n\x10control_outbound\x18\x06\x20\x01(\tR\x0fcontrolOutb\
+    oundB\0\x12!\n\x06router\x18\x07\x20\x01(\x0b2\x07.RouterR\x06routerB\0\
+    \x120\n\x13udp_session_timeout\x18\x08\x20\x01(\rR\x11udpSessionTimeoutB\
+    \0\x12*\n\x10link_buffer_size\x18\t\x20\x01(\rR\x0elinkBufferSizeB\0:\0*\
+    Z\n\x0bDnsStrategy\x12\x0b\n\x07DEFAULT\x10\0\x12\r\n\tIPV4_ONLY\x10\x01\
+    \x12\r\n\tIPV6_ONLY\x10\x02\x12\x0e\n\nIPV4_FIRST\x10\x03\x12\x0e\n\nIPV\
+    6_FIRST\x10\x04\x1a\0B\0b\x06proto3\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::path::Path;
 
 use anyhow::anyhow;
@@ -9,17 +10,102 @@ use serde_json::value::RawValue;
 
 use crate::config::{external_rule, geosite, internal};
 
+/// A DNS server, either an address, or an object with the outbound to reach
+/// the server, or the bootstrap IP and the CA file of an encrypted server.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum DnsServer {
+    Address(String),
+    WithOutbound {
+        address: String,
+        outbound: Option<String>,
+        bootstrap: Option<String>,
+        ca: Option<String>,
+    },
+}
+
+/// Domains resolved by a group of DNS servers.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DnsPolicy {
+    pub domain: Option<Vec<String>>,
+    #[serde(rename = "domainKeyword")]
+    pub domain_keyword: Option<Vec<String>>,
+    #[serde(rename = "domainSuffix")]
+    pub domain_suffix: Option<Vec<String>>,
+    pub group: String,
+}
+
+/// Listen addresses of an inbound, either an address or a list of them.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum InboundAddress {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DNS {
-    pub servers: Option<Vec<String>>,
+    pub servers: Option<Vec<DnsServer>>,
     pub bind: Option<String>,
     pub hosts: Option<HashMap<String, Vec<String>>>,
+    #[serde(rename = "filterBogons")]
+    pub filter_bogons: Option<bool>,
+    #[serde(rename = "filterPrivate")]
+    pub filter_private: Option<bool>,
+    #[serde(rename = "internalDomains")]
+    pub internal_domains: Option<Vec<String>>,
+    #[serde(rename = "failOpen")]
+    pub fail_open: Option<bool>,
+    #[serde(rename = "fallbackIp")]
+    pub fallback_ip: Option<String>,
+    #[serde(rename = "dotIdleTimeout")]
+    pub dot_idle_timeout: Option<u32>,
+    #[serde(rename = "cacheSize")]
+    pub cache_size: Option<u32>,
+    #[serde(rename = "minCacheTtl")]
+    pub min_cache_ttl: Option<u32>,
+    #[serde(rename = "maxCacheTtl")]
+    pub max_cache_ttl: Option<u32>,
+    #[serde(rename = "negativeCacheTtl")]
+    pub negative_cache_ttl: Option<u32>,
+    pub prefetch: Option<u32>,
+    #[serde(rename = "prefetchMinHits")]
+    pub prefetch_min_hits: Option<u32>,
+    #[serde(rename = "clientSubnet")]
+    pub client_subnet: Option<String>,
+    pub outbound: Option<String>,
+    pub strategy: Option<String>,
+    pub groups: Option<HashMap<String, Vec<DnsServer>>>,
+    pub policies: Option<Vec<DnsPolicy>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Log {
     pub level: Option<String>,
     pub output: Option<String>,
+    pub format: Option<String>,
+    pub timestamp: Option<bool>,
+    pub color: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HttpInboundSettings {
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SocksInboundSettings {
+    #[serde(rename = "udpBind")]
+    pub udp_bind: Option<String>,
+    #[serde(rename = "udpPortRange")]
+    pub udp_port_range: Option<String>,
+    #[serde(rename = "udpAdvertise")]
+    pub udp_advertise: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    #[serde(rename = "allowNoAuth")]
+    pub allow_no_auth: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -49,15 +135,46 @@ pub struct TUNInboundSettings {
     pub fake_dns_exclude: Option<Vec<String>>,
     #[serde(rename = "fakeDnsInclude")]
     pub fake_dns_include: Option<Vec<String>>,
+    #[serde(rename = "fakeDnsRoutingAware")]
+    pub fake_dns_routing_aware: Option<bool>,
+    #[serde(rename = "fakeDnsPersistPath")]
+    pub fake_dns_persist_path: Option<String>,
+    #[serde(rename = "fakeDnsPersistTtl")]
+    pub fake_dns_persist_ttl: Option<u32>,
+    #[serde(rename = "fakeDnsRange")]
+    pub fake_dns_range: Option<String>,
+    #[serde(rename = "fakeDnsIpv6Range")]
+    pub fake_dns_ipv6_range: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Inbound {
     pub protocol: String,
     pub tag: Option<String>,
-    pub address: Option<String>,
+    pub address: Option<InboundAddress>,
     pub port: Option<u16>,
     pub settings: Option<Box<RawValue>>,
+    pub rewrites: Option<HashMap<String, String>>,
+    pub backlog: Option<u32>,
+    #[serde(rename = "udpSessionTimeout")]
+    pub udp_session_timeout: Option<u32>,
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<RateLimit>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RateLimitBucket {
+    pub rate: u64,
+    pub burst: Option<u64>,
+}
+
+/// Rate and burst apply to both directions, unless set for the direction.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RateLimit {
+    pub rate: Option<u64>,
+    pub burst: Option<u64>,
+    pub uplink: Option<RateLimitBucket>,
+    pub downlink: Option<RateLimitBucket>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -66,6 +183,11 @@ pub struct RedirectOutboundSettings {
     pub port: Option<u16>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DropOutboundSettings {
+    pub reset: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SocksOutboundSettings {
     pub address: Option<String>,
@@ -78,6 +200,13 @@ pub struct ShadowsocksOutboundSettings {
     pub port: Option<u16>,
     pub method: Option<String>,
     pub password: Option<String>,
+    pub plugin: Option<String>,
+    #[serde(rename = "pluginOpts")]
+    pub plugin_opts: Option<String>,
+    #[serde(rename = "udpOverTcp")]
+    pub udp_over_tcp: Option<bool>,
+    #[serde(rename = "udpOverTcpVersion")]
+    pub udp_over_tcp_version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -85,6 +214,15 @@ pub struct TrojanOutboundSettings {
     pub address: Option<String>,
     pub port: Option<u16>,
     pub password: Option<String>,
+    #[serde(rename = "udpOverTcp")]
+    pub udp_over_tcp: Option<bool>,
+    #[serde(rename = "udpOverTcpVersion")]
+    pub udp_over_tcp_version: Option<u32>,
+    pub tls: Option<bool>,
+    #[serde(rename = "serverName")]
+    pub server_name: Option<String>,
+    pub alpn: Option<Vec<String>>,
+    pub insecure: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -93,6 +231,10 @@ pub struct VMessOutboundSettings {
     pub port: Option<u16>,
     pub uuid: Option<String>,
     pub security: Option<String>,
+    #[serde(rename = "reduceRtt")]
+    pub reduce_rtt: Option<bool>,
+    #[serde(rename = "legacyHeader")]
+    pub legacy_header: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -100,6 +242,8 @@ pub struct VLessOutboundSettings {
     pub address: Option<String>,
     pub port: Option<u16>,
     pub uuid: Option<String>,
+    pub flow: Option<String>,
+    pub encryption: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -112,6 +256,62 @@ pub struct TryAllOutboundSettings {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RandomOutboundSettings {
     pub actors: Option<Vec<String>>,
+    #[serde(rename = "stickyTimeout")]
+    pub sticky_timeout: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BalanceOutboundSettings {
+    pub actors: Option<Vec<String>>,
+    pub strategy: Option<String>,
+    pub weights: Option<Vec<u32>>,
+    #[serde(rename = "stickyTimeout")]
+    pub sticky_timeout: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SelectOutboundSettings {
+    pub actors: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UrlTestOutboundSettings {
+    pub actors: Option<Vec<String>>,
+    pub url: Option<String>,
+    pub interval: Option<u32>,
+    pub tolerance: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SshOutboundSettings {
+    pub address: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    #[serde(rename = "privateKey")]
+    pub private_key: Option<String>,
+    #[serde(rename = "privateKeyPassphrase")]
+    pub private_key_passphrase: Option<String>,
+    #[serde(rename = "hostKeyFingerprint")]
+    pub host_key_fingerprint: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WireGuardOutboundSettings {
+    pub address: Option<String>,
+    pub port: Option<u16>,
+    #[serde(rename = "privateKey")]
+    pub private_key: Option<String>,
+    #[serde(rename = "publicKey")]
+    pub public_key: Option<String>,
+    #[serde(rename = "presharedKey")]
+    pub preshared_key: Option<String>,
+    #[serde(rename = "localAddress")]
+    pub local_address: Option<String>,
+    #[serde(rename = "allowedIps")]
+    pub allowed_ips: Option<Vec<String>>,
+    pub mtu: Option<u32>,
+    pub keepalive: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -119,12 +319,26 @@ pub struct TlsOutboundSettings {
     #[serde(rename = "serverName")]
     pub server_name: Option<String>,
     pub alpn: Option<Vec<String>>,
+    #[serde(rename = "earlyData")]
+    pub early_data: Option<bool>,
+    pub fingerprint: Option<String>,
+    pub insecure: Option<bool>,
+    #[serde(rename = "certSha256")]
+    pub cert_sha256: Option<String>,
+    #[serde(rename = "clientCert")]
+    pub client_cert: Option<String>,
+    #[serde(rename = "clientKey")]
+    pub client_key: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WebSocketOutboundSettings {
     pub path: Option<String>,
     pub headers: Option<HashMap<String, String>>,
+    #[serde(rename = "maxEarlyData")]
+    pub max_early_data: Option<u32>,
+    #[serde(rename = "earlyDataHeaderName")]
+    pub early_data_header_name: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -160,6 +374,19 @@ pub struct FailOverOutboundSettings {
     pub cache_size: Option<u32>,
     #[serde(rename = "cacheTimeout")]
     pub cache_timeout: Option<u32>,
+    pub priorities: Option<Vec<u32>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PluggableTransportOutboundSettings {
+    pub path: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub transport: Option<String>,
+    pub address: Option<String>,
+    pub port: Option<u16>,
+    pub options: Option<String>,
+    #[serde(rename = "stateDir")]
+    pub state_dir: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -173,6 +400,17 @@ pub struct Outbound {
     pub protocol: String,
     pub tag: Option<String>,
     pub bind: Option<String>,
+    #[serde(rename = "idleTimeout")]
+    pub idle_timeout: Option<u32>,
+    #[serde(rename = "connectTimeout")]
+    pub connect_timeout: Option<u32>,
+    #[serde(rename = "tcpNoDelay")]
+    pub tcp_nodelay: Option<bool>,
+    #[serde(rename = "tcpKeepAlive")]
+    pub tcp_keepalive: Option<u32>,
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<RateLimit>,
+    pub dns: Option<String>,
     pub settings: Option<Box<RawValue>>,
 }
 
@@ -188,7 +426,20 @@ pub struct Rule {
     pub external: Option<Vec<String>>,
     #[serde(rename = "portRange")]
     pub port_range: Option<Vec<String>>,
+    #[serde(rename = "processName")]
+    pub process_name: Option<Vec<String>>,
+    pub network: Option<String>,
     pub target: String,
+    pub log: Option<bool>,
+    #[serde(rename = "dnsStrategy")]
+    pub dns_strategy: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Router {
+    #[serde(rename = "dryRun")]
+    pub dry_run: Option<bool>,
+    pub default: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -198,9 +449,105 @@ pub struct Config {
     pub outbounds: Option<Vec<Outbound>>,
     pub rules: Option<Vec<Rule>>,
     pub dns: Option<DNS>,
+    #[serde(rename = "controlOutbound")]
+    pub control_outbound: Option<String>,
+    pub router: Option<Router>,
+    #[serde(rename = "udpSessionTimeout")]
+    pub udp_session_timeout: Option<u32>,
+    #[serde(rename = "linkBufferSize")]
+    pub link_buffer_size: Option<u32>,
+    #[serde(rename = "connectTimeout")]
+    pub connect_timeout: Option<u32>,
+    #[serde(rename = "tcpNoDelay")]
+    pub tcp_nodelay: Option<bool>,
+    #[serde(rename = "tcpKeepAlive")]
+    pub tcp_keepalive: Option<u32>,
 }
 
+fn to_internal_rate_limit(ext_rate_limit: &RateLimit) -> Result<internal::RateLimit> {
+    let bucket = |ext_bucket: Option<&RateLimitBucket>| -> Result<internal::RateLimit_Bucket> {
+        let mut bucket = internal::RateLimit_Bucket::new();
+        match ext_bucket {
+            Some(ext_bucket) => {
+                bucket.rate = ext_bucket.rate;
+                bucket.burst = ext_bucket.burst.unwrap_or(0);
+            }
+            None => {
+                bucket.rate = ext_rate_limit.rate.unwrap_or(0);
+                bucket.burst = ext_rate_limit.burst.unwrap_or(0);
+            }
+        }
+        if bucket.rate == 0 && bucket.burst > 0 {
+            return Err(anyhow!("invalid rate limit, burst without rate"));
+        }
+        Ok(bucket)
+    };
+    let mut rate_limit = internal::RateLimit::new();
+    rate_limit.set_uplink(bucket(ext_rate_limit.uplink.as_ref())?);
+    rate_limit.set_downlink(bucket(ext_rate_limit.downlink.as_ref())?);
+    Ok(rate_limit)
+}
+
+fn to_http_inbound_settings(ext_settings: &RawValue) -> Result<internal::HttpInboundSettings> {
+    let mut settings = internal::HttpInboundSettings::new();
+    let ext_settings: HttpInboundSettings = serde_json::from_str(ext_settings.get())?;
+    if let Some(ext_username) = ext_settings.username {
+        settings.username = ext_username;
+    }
+    if let Some(ext_password) = ext_settings.password {
+        settings.password = ext_password;
+    }
+    Ok(settings)
+}
+
+fn to_socks_inbound_settings(ext_settings: &RawValue) -> Result<internal::SocksInboundSettings> {
+    let mut settings = internal::SocksInboundSettings::new();
+    let ext_settings: SocksInboundSettings = serde_json::from_str(ext_settings.get())?;
+    if let Some(ext_udp_bind) = ext_settings.udp_bind {
+        if ext_udp_bind.parse::<IpAddr>().is_err() {
+            return Err(anyhow!("invalid socks udp bind {}", ext_udp_bind));
+        }
+        settings.udp_bind = ext_udp_bind;
+    }
+    if let Some(ext_udp_port_range) = ext_settings.udp_port_range {
+        if crate::config::parse_port_range(&ext_udp_port_range).is_none() {
+            return Err(anyhow!(
+                "invalid socks udp port range {}",
+                ext_udp_port_range
+            ));
+        }
+        settings.udp_port_range = ext_udp_port_range;
+    }
+    if let Some(ext_udp_advertise) = ext_settings.udp_advertise {
+        if ext_udp_advertise.parse::<IpAddr>().is_err() {
+            return Err(anyhow!("invalid socks udp advertise {}", ext_udp_advertise));
+        }
+        settings.udp_advertise = ext_udp_advertise;
+    }
+    if let Some(ext_username) = ext_settings.username {
+        settings.username = ext_username;
+    }
+    if let Some(ext_password) = ext_settings.password {
+        settings.password = ext_password;
+    }
+    if let Some(ext_allow_no_auth) = ext_settings.allow_no_auth {
+        settings.allow_no_auth = ext_allow_no_auth;
+    }
+    Ok(settings)
+}
+
+/// Converts the config, errors of an inbound or outbound tell which one it
+/// is.
 pub fn to_internal(json: Config) -> Result<internal::Config> {
+    let mut context = None;
+    to_internal_in_context(json, &mut context).map_err(|e| match context {
+        Some(c) => e.context(c),
+        None => e,
+    })
+}
+
+// Keeps the inbound or outbound being converted in `context`.
+fn to_internal_in_context(json: Config, context: &mut Option<String>) -> Result<internal::Config> {
     let mut log = internal::Log::new();
     if let Some(ext_log) = json.log {
         if let Some(ext_level) = ext_log.level {
@@ -227,6 +574,17 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
         } else {
             log.output = internal::Log_Output::CONSOLE;
         }
+
+        if let Some(ext_format) = ext_log.format {
+            log.format = match ext_format.as_str() {
+                "default" => internal::Log_Format::DEFAULT,
+                "plain" => internal::Log_Format::PLAIN,
+                "json" => internal::Log_Format::JSON,
+                _ => return Err(anyhow!("invalid log format {}", ext_format)),
+            };
+        }
+        log.disable_timestamp = !ext_log.timestamp.unwrap_or(true);
+        log.disable_color = !ext_log.color.unwrap_or(true);
     } else {
         log.level = internal::Log_Level::INFO;
         log.output = internal::Log_Output::CONSOLE;
@@ -240,14 +598,42 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
             if let Some(ext_tag) = ext_inbound.tag {
                 inbound.tag = ext_tag;
             }
-            if let Some(ext_address) = ext_inbound.address {
-                inbound.address = ext_address;
-            } else {
-                inbound.address = "127.0.0.1".to_string();
+            *context = Some(format!("inbound [{}]", &inbound.tag));
+            match ext_inbound.address {
+                Some(InboundAddress::Single(ext_address)) => {
+                    inbound.address = ext_address;
+                }
+                Some(InboundAddress::Multiple(ext_addresses)) => {
+                    if ext_addresses.is_empty() {
+                        return Err(anyhow!("empty address list of inbound {}", &inbound.tag));
+                    }
+                    inbound.address = ext_addresses[0].clone();
+                    if ext_addresses.len() > 1 {
+                        inbound.addresses = protobuf::RepeatedField::from_vec(ext_addresses);
+                    }
+                }
+                None => {
+                    inbound.address = "127.0.0.1".to_string();
+                }
             }
             if let Some(ext_port) = ext_inbound.port {
                 inbound.port = ext_port as u32;
             }
+            if let Some(ext_rewrites) = ext_inbound.rewrites {
+                inbound.rewrites = ext_rewrites;
+            }
+            if let Some(ext_backlog) = ext_inbound.backlog {
+                if !crate::config::is_valid_backlog(ext_backlog) {
+                    return Err(anyhow!("invalid backlog {}", ext_backlog));
+                }
+                inbound.backlog = ext_backlog;
+            }
+            if let Some(ext_udp_session_timeout) = ext_inbound.udp_session_timeout {
+                inbound.udp_session_timeout = ext_udp_session_timeout;
+            }
+            if let Some(ext_rate_limit) = ext_inbound.rate_limit.as_ref() {
+                inbound.set_rate_limit(to_internal_rate_limit(ext_rate_limit)?);
+            }
             match inbound.protocol.as_str() {
                 #[cfg(any(target_os = "ios", target_os = "macos", target_os = "linux"))]
                 "tun" => {
@@ -278,6 +664,30 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                         settings.fake_dns_include = fake_dns_include;
                     }
 
+                    if let Some(ext_routing_aware) = ext_settings.fake_dns_routing_aware {
+                        settings.fake_dns_routing_aware = ext_routing_aware;
+                    }
+                    if let Some(ext_persist_path) = ext_settings.fake_dns_persist_path {
+                        settings.fake_dns_persist_path = ext_persist_path;
+                    }
+                    if let Some(ext_persist_ttl) = ext_settings.fake_dns_persist_ttl {
+                        settings.fake_dns_persist_ttl = ext_persist_ttl;
+                    }
+                    if let Some(ext_range) = ext_settings.fake_dns_range {
+                        match crate::config::parse_ip_range(&ext_range) {
+                            Some((addr, prefix)) if addr.is_ipv4() && prefix <= 30 => (),
+                            _ => return Err(anyhow!("invalid fake dns range {}", ext_range)),
+                        }
+                        settings.fake_dns_range = ext_range;
+                    }
+                    if let Some(ext_ipv6_range) = ext_settings.fake_dns_ipv6_range {
+                        if !matches!(crate::config::parse_ip_range(&ext_ipv6_range), Some((addr, _)) if addr.is_ipv6())
+                        {
+                            return Err(anyhow!("invalid fake dns ipv6 range {}", ext_ipv6_range));
+                        }
+                        settings.fake_dns_ipv6_range = ext_ipv6_range;
+                    }
+
                     if let Some(ext_fd) = ext_settings.fd {
                         settings.fd = ext_fd;
                     } else {
@@ -294,20 +704,51 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                         if let Some(ext_netmask) = ext_settings.netmask {
                             settings.netmask = ext_netmask;
                         }
-                        if let Some(ext_mtu) = ext_settings.mtu {
-                            settings.mtu = ext_mtu;
-                        } else {
-                            settings.mtu = 1500;
+                    }
+                    // The MTU of an existing TUN is set by its creator, it's
+                    // still needed for the stack.
+                    if let Some(ext_mtu) = ext_settings.mtu {
+                        if !crate::config::is_valid_mtu(ext_mtu) {
+                            return Err(anyhow!("invalid tun mtu {}", ext_mtu));
                         }
+                        settings.mtu = ext_mtu;
+                    } else {
+                        settings.mtu = 1500;
                     }
                     let settings = settings.write_to_bytes().unwrap();
                     inbound.settings = settings;
                     inbounds.push(inbound);
                 }
                 "http" => {
+                    if let Some(ext_settings) = ext_inbound.settings {
+                        inbound.settings = to_http_inbound_settings(&ext_settings)?
+                            .write_to_bytes()
+                            .unwrap();
+                    }
+                    inbounds.push(inbound);
+                }
+                "tproxy" => {
                     inbounds.push(inbound);
                 }
                 "socks" => {
+                    if let Some(ext_settings) = ext_inbound.settings {
+                        inbound.settings = to_socks_inbound_settings(&ext_settings)?
+                            .write_to_bytes()
+                            .unwrap();
+                    }
+                    inbounds.push(inbound);
+                }
+                "mixed" => {
+                    let mut settings = internal::MixedInboundSettings::new();
+                    if let Some(ext_settings) = ext_inbound.settings {
+                        settings.socks = protobuf::SingularPtrField::some(
+                            to_socks_inbound_settings(&ext_settings)?,
+                        );
+                        settings.http = protobuf::SingularPtrField::some(to_http_inbound_settings(
+                            &ext_settings,
+                        )?);
+                    }
+                    inbound.settings = settings.write_to_bytes().unwrap();
                     inbounds.push(inbound);
                 }
                 "trojan" => {
@@ -362,6 +803,8 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
         }
     }
 
+    *context = None;
+
     let mut outbounds = protobuf::RepeatedField::new();
     if let Some(ext_outbounds) = json.outbounds {
         for ext_outbound in ext_outbounds {
@@ -370,13 +813,46 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
             if let Some(ext_tag) = ext_outbound.tag {
                 outbound.tag = ext_tag;
             }
+            *context = Some(format!("outbound [{}]", &outbound.tag));
             if let Some(ext_bind) = ext_outbound.bind {
                 outbound.bind = ext_bind;
             } else {
                 outbound.bind = "0.0.0.0".to_string();
             }
+            if let Some(ext_idle_timeout) = ext_outbound.idle_timeout {
+                outbound.idle_timeout = ext_idle_timeout;
+            }
+            if let Some(ext_connect_timeout) = ext_outbound.connect_timeout.or(json.connect_timeout)
+            {
+                outbound.connect_timeout = ext_connect_timeout;
+            }
+            if let Some(ext_tcp_nodelay) = ext_outbound.tcp_nodelay.or(json.tcp_nodelay) {
+                outbound.disable_tcp_nodelay = !ext_tcp_nodelay;
+            }
+            if let Some(ext_tcp_keepalive) = ext_outbound.tcp_keepalive.or(json.tcp_keepalive) {
+                outbound.tcp_keepalive = ext_tcp_keepalive;
+            }
+            if let Some(ext_rate_limit) = ext_outbound.rate_limit.as_ref() {
+                outbound.set_rate_limit(to_internal_rate_limit(ext_rate_limit)?);
+            }
+            if let Some(ext_dns) = ext_outbound.dns {
+                outbound.dns = ext_dns;
+            }
             match outbound.protocol.as_str() {
-                "direct" | "drop" => {
+                "direct" => {
+                    outbounds.push(outbound);
+                }
+                "drop" => {
+                    let mut settings = internal::DropOutboundSettings::new();
+                    if let Some(ext_settings) = ext_outbound.settings {
+                        let ext_settings: DropOutboundSettings =
+                            serde_json::from_str(ext_settings.get())?;
+                        if let Some(ext_reset) = ext_settings.reset {
+                            settings.reset = ext_reset;
+                        }
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
                     outbounds.push(outbound);
                 }
                 "redirect" => {
@@ -434,6 +910,18 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     if let Some(ext_password) = ext_settings.password {
                         settings.password = ext_password;
                     }
+                    if let Some(ext_plugin) = ext_settings.plugin {
+                        settings.plugin = ext_plugin;
+                    }
+                    if let Some(ext_plugin_opts) = ext_settings.plugin_opts {
+                        settings.plugin_opts = ext_plugin_opts;
+                    }
+                    if let Some(ext_udp_over_tcp) = ext_settings.udp_over_tcp {
+                        settings.udp_over_tcp = ext_udp_over_tcp;
+                    }
+                    if let Some(ext_udp_over_tcp_version) = ext_settings.udp_over_tcp_version {
+                        settings.udp_over_tcp_version = ext_udp_over_tcp_version;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -454,6 +942,24 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     if let Some(ext_password) = ext_settings.password {
                         settings.password = ext_password;
                     }
+                    if let Some(ext_udp_over_tcp) = ext_settings.udp_over_tcp {
+                        settings.udp_over_tcp = ext_udp_over_tcp;
+                    }
+                    if let Some(ext_udp_over_tcp_version) = ext_settings.udp_over_tcp_version {
+                        settings.udp_over_tcp_version = ext_udp_over_tcp_version;
+                    }
+                    if let Some(ext_tls) = ext_settings.tls {
+                        settings.tls = ext_tls;
+                    }
+                    if let Some(ext_server_name) = ext_settings.server_name {
+                        settings.server_name = ext_server_name;
+                    }
+                    if let Some(ext_alpns) = ext_settings.alpn {
+                        settings.alpn = protobuf::RepeatedField::from_vec(ext_alpns);
+                    }
+                    if let Some(ext_insecure) = ext_settings.insecure {
+                        settings.insecure = ext_insecure;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -479,6 +985,12 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     } else {
                         settings.security = "chacha20-ietf-poly1305".to_string();
                     }
+                    if let Some(ext_reduce_rtt) = ext_settings.reduce_rtt {
+                        settings.reduce_rtt = ext_reduce_rtt;
+                    }
+                    if let Some(ext_legacy_header) = ext_settings.legacy_header {
+                        settings.legacy_header = ext_legacy_header;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -499,6 +1011,108 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     if let Some(ext_uuid) = ext_settings.uuid {
                         settings.uuid = ext_uuid;
                     }
+                    if let Some(ext_flow) = ext_settings.flow {
+                        settings.flow = ext_flow;
+                    }
+                    if let Some(ext_encryption) = ext_settings.encryption {
+                        if ext_encryption != "none" {
+                            return Err(anyhow!(
+                                "unsupported vless encryption: {}",
+                                ext_encryption
+                            ));
+                        }
+                        settings.encryption = ext_encryption;
+                    } else {
+                        settings.encryption = "none".to_string();
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "wireguard" => {
+                    if ext_outbound.settings.is_none() {
+                        return Err(anyhow!("invalid wireguard outbound settings"));
+                    }
+                    let mut settings = internal::WireGuardOutboundSettings::new();
+                    let ext_settings: WireGuardOutboundSettings =
+                        serde_json::from_str(ext_outbound.settings.unwrap().get()).unwrap();
+                    if let Some(ext_address) = ext_settings.address {
+                        settings.address = ext_address;
+                    }
+                    if let Some(ext_port) = ext_settings.port {
+                        settings.port = ext_port as u32;
+                    }
+                    match (ext_settings.private_key, ext_settings.public_key) {
+                        (Some(private_key), Some(public_key)) => {
+                            settings.private_key = private_key;
+                            settings.peer_public_key = public_key;
+                        }
+                        _ => {
+                            return Err(anyhow!(
+                                "wireguard outbound requires privateKey and publicKey"
+                            ));
+                        }
+                    }
+                    if let Some(ext_preshared_key) = ext_settings.preshared_key {
+                        settings.preshared_key = ext_preshared_key;
+                    }
+                    if let Some(ext_local_address) = ext_settings.local_address {
+                        settings.local_address = ext_local_address;
+                    } else {
+                        return Err(anyhow!("wireguard outbound requires localAddress"));
+                    }
+                    if let Some(ext_allowed_ips) = ext_settings.allowed_ips {
+                        for ext_allowed_ip in ext_allowed_ips {
+                            settings.allowed_ips.push(ext_allowed_ip);
+                        }
+                    }
+                    if let Some(ext_mtu) = ext_settings.mtu {
+                        settings.mtu = ext_mtu;
+                    } else {
+                        settings.mtu = 1420;
+                    }
+                    if let Some(ext_keepalive) = ext_settings.keepalive {
+                        settings.keepalive = ext_keepalive;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "ssh" => {
+                    if ext_outbound.settings.is_none() {
+                        return Err(anyhow!("invalid ssh outbound settings"));
+                    }
+                    let mut settings = internal::SshOutboundSettings::new();
+                    let ext_settings: SshOutboundSettings =
+                        serde_json::from_str(ext_outbound.settings.unwrap().get()).unwrap();
+                    if let Some(ext_address) = ext_settings.address {
+                        settings.address = ext_address;
+                    }
+                    if let Some(ext_port) = ext_settings.port {
+                        settings.port = ext_port as u32;
+                    } else {
+                        settings.port = 22;
+                    }
+                    if let Some(ext_username) = ext_settings.username {
+                        settings.username = ext_username;
+                    } else {
+                        return Err(anyhow!("ssh outbound requires username"));
+                    }
+                    match (ext_settings.password, ext_settings.private_key) {
+                        (Some(password), None) => settings.password = password,
+                        (None, Some(private_key)) => settings.private_key = private_key,
+                        _ => {
+                            return Err(anyhow!(
+                                "ssh outbound requires either password or privateKey"
+                            ));
+                        }
+                    }
+                    if let Some(ext_passphrase) = ext_settings.private_key_passphrase {
+                        settings.private_key_passphrase = ext_passphrase;
+                    }
+                    if let Some(ext_fingerprint) = ext_settings.host_key_fingerprint {
+                        settings.host_key_fingerprint = ext_fingerprint;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -520,6 +1134,31 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                         if alpns.len() > 0 {
                             settings.alpn = alpns;
                         }
+                        if let Some(ext_early_data) = ext_settings.early_data {
+                            settings.early_data = ext_early_data;
+                        }
+                        if let Some(ext_fingerprint) = ext_settings.fingerprint {
+                            settings.fingerprint = ext_fingerprint;
+                        }
+                        if let Some(ext_insecure) = ext_settings.insecure {
+                            settings.insecure = ext_insecure;
+                        }
+                        if let Some(ext_cert_sha256) = ext_settings.cert_sha256 {
+                            // accepts the colon separated form openssl prints
+                            settings.cert_sha256 = ext_cert_sha256.replace(':', "").to_lowercase();
+                        }
+                        match (ext_settings.client_cert, ext_settings.client_key) {
+                            (Some(cert), Some(key)) => {
+                                settings.client_cert = cert;
+                                settings.client_key = key;
+                            }
+                            (None, None) => (),
+                            _ => {
+                                return Err(anyhow!(
+                                    "clientCert and clientKey must be set together"
+                                ))
+                            }
+                        }
                     }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
@@ -539,6 +1178,12 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     if let Some(ext_headers) = ext_settings.headers {
                         settings.headers = ext_headers;
                     }
+                    if let Some(ext_max_early_data) = ext_settings.max_early_data {
+                        settings.max_early_data = ext_max_early_data;
+                    }
+                    if let Some(ext_early_data_header_name) = ext_settings.early_data_header_name {
+                        settings.early_data_header_name = ext_early_data_header_name;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -594,6 +1239,90 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                             settings.actors.push(ext_actor);
                         }
                     }
+                    if let Some(ext_sticky_timeout) = ext_settings.sticky_timeout {
+                        settings.sticky_timeout = ext_sticky_timeout;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "balance" => {
+                    if ext_outbound.settings.is_none() {
+                        return Err(anyhow!("invalid balance outbound settings"));
+                    }
+                    let mut settings = internal::BalanceOutboundSettings::new();
+                    let ext_settings: BalanceOutboundSettings =
+                        serde_json::from_str(ext_outbound.settings.unwrap().get()).unwrap();
+                    if let Some(ext_actors) = ext_settings.actors {
+                        for ext_actor in ext_actors {
+                            settings.actors.push(ext_actor);
+                        }
+                    }
+                    if let Some(ext_strategy) = ext_settings.strategy {
+                        settings.strategy = crate::config::parse_balance_strategy(&ext_strategy)
+                            .ok_or_else(|| anyhow!("invalid balance strategy {}", ext_strategy))?;
+                    }
+                    if let Some(ext_weights) = ext_settings.weights {
+                        if ext_weights.len() != settings.actors.len() {
+                            return Err(anyhow!(
+                                "balance weights must have the same length as actors"
+                            ));
+                        }
+                        settings.weights = ext_weights;
+                    }
+                    if let Some(ext_sticky_timeout) = ext_settings.sticky_timeout {
+                        settings.sticky_timeout = ext_sticky_timeout;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "select" => {
+                    if ext_outbound.settings.is_none() {
+                        return Err(anyhow!("invalid select outbound settings"));
+                    }
+                    let mut settings = internal::SelectOutboundSettings::new();
+                    let ext_settings: SelectOutboundSettings =
+                        serde_json::from_str(ext_outbound.settings.unwrap().get()).unwrap();
+                    if let Some(ext_actors) = ext_settings.actors {
+                        for ext_actor in ext_actors {
+                            settings.actors.push(ext_actor);
+                        }
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                "urltest" => {
+                    if ext_outbound.settings.is_none() {
+                        return Err(anyhow!("invalid urltest outbound settings"));
+                    }
+                    let mut settings = internal::UrlTestOutboundSettings::new();
+                    let ext_settings: UrlTestOutboundSettings =
+                        serde_json::from_str(ext_outbound.settings.unwrap().get()).unwrap();
+                    if let Some(ext_actors) = ext_settings.actors {
+                        for ext_actor in ext_actors {
+                            settings.actors.push(ext_actor);
+                        }
+                    }
+                    if let Some(ext_url) = ext_settings.url {
+                        if !ext_url.starts_with("http://") {
+                            return Err(anyhow!("invalid urltest url {}", ext_url));
+                        }
+                        settings.url = ext_url;
+                    } else {
+                        settings.url = "http://www.gstatic.com/generate_204".to_string();
+                    }
+                    if let Some(ext_interval) = ext_settings.interval {
+                        settings.interval = ext_interval;
+                    } else {
+                        settings.interval = 300;
+                    }
+                    if let Some(ext_tolerance) = ext_settings.tolerance {
+                        settings.tolerance = ext_tolerance;
+                    } else {
+                        settings.tolerance = 50;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -645,6 +1374,14 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     } else {
                         settings.cache_timeout = 60; // in minutes
                     }
+                    if let Some(ext_priorities) = ext_settings.priorities {
+                        if ext_priorities.len() != settings.actors.len() {
+                            return Err(anyhow!(
+                                "failover priorities must have the same length as actors"
+                            ));
+                        }
+                        settings.priorities = ext_priorities;
+                    }
                     let settings = settings.write_to_bytes().unwrap();
                     outbound.settings = settings;
                     outbounds.push(outbound);
@@ -703,6 +1440,49 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     outbound.settings = settings;
                     outbounds.push(outbound);
                 }
+                "pt" => {
+                    if ext_outbound.settings.is_none() {
+                        return Err(anyhow!("invalid pt outbound settings"));
+                    }
+                    let mut settings = internal::PluggableTransportOutboundSettings::new();
+                    let ext_settings: PluggableTransportOutboundSettings =
+                        serde_json::from_str(ext_outbound.settings.unwrap().get()).unwrap();
+                    if let Some(ext_path) = ext_settings.path {
+                        settings.path = ext_path;
+                    } else {
+                        return Err(anyhow!("missing path in pt outbound settings"));
+                    }
+                    if let Some(ext_args) = ext_settings.args {
+                        settings.args = protobuf::RepeatedField::from_vec(ext_args);
+                    }
+                    if let Some(ext_transport) = ext_settings.transport {
+                        settings.transport = ext_transport;
+                    } else {
+                        return Err(anyhow!("missing transport in pt outbound settings"));
+                    }
+                    if let Some(ext_address) = ext_settings.address {
+                        settings.address = ext_address;
+                    }
+                    if let Some(ext_port) = ext_settings.port {
+                        settings.port = ext_port as u32;
+                    }
+                    if let Some(ext_options) = ext_settings.options {
+                        settings.options = ext_options;
+                    }
+                    if let Some(ext_state_dir) = ext_settings.state_dir {
+                        settings.state_dir = ext_state_dir;
+                    }
+                    let settings = settings.write_to_bytes().unwrap();
+                    outbound.settings = settings;
+                    outbounds.push(outbound);
+                }
+                protocol if crate::app::outbound::registry::is_registered(protocol) => {
+                    // Registered protocols parse the JSON settings themselves.
+                    if let Some(ext_settings) = ext_outbound.settings.as_ref() {
+                        outbound.settings = ext_settings.get().as_bytes().to_vec();
+                    }
+                    outbounds.push(outbound);
+                }
                 _ => {
                     // skip outbound with unknown protocol
                 }
@@ -710,6 +1490,8 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
         }
     }
 
+    *context = None;
+
     let mut rules = protobuf::RepeatedField::new();
     if let Some(ext_rules) = json.rules {
         // a map for caching external site so we need not load a same file multiple times
@@ -718,6 +1500,13 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
         for ext_rule in ext_rules {
             let mut rule = internal::RoutingRule::new();
             rule.target_tag = ext_rule.target;
+            if let Some(ext_log) = ext_rule.log {
+                rule.no_log = !ext_log;
+            }
+            if let Some(ext_dns_strategy) = ext_rule.dns_strategy {
+                rule.dns_strategy = crate::config::parse_dns_strategy(&ext_dns_strategy)
+                    .ok_or_else(|| anyhow!("invalid dns strategy {}", ext_dns_strategy))?;
+            }
             if let Some(ext_ips) = ext_rule.ip {
                 for ext_ip in ext_ips {
                     rule.ip_cidrs.push(ext_ip);
@@ -767,17 +1556,29 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                     ) {
                         Ok(_) => (),
                         Err(e) => {
-                            println!("load external rule failed: {}", e);
+                            return Err(anyhow!("load external rule failed: {}", e));
                         }
                     }
                 }
             }
             if let Some(ext_port_ranges) = ext_rule.port_range {
                 for ext_port_range in ext_port_ranges {
-                    // FIXME validate
+                    if crate::config::parse_ports(&ext_port_range).is_none() {
+                        return Err(anyhow!("invalid port range {}", ext_port_range));
+                    }
                     rule.port_ranges.push(ext_port_range);
                 }
             }
+            if let Some(ext_process_names) = ext_rule.process_name {
+                for ext_process_name in ext_process_names {
+                    rule.process_names.push(ext_process_name);
+                }
+            }
+            if let Some(ext_network) = ext_rule.network {
+                let networks = crate::config::parse_networks(&ext_network)
+                    .ok_or_else(|| anyhow!("invalid network {}", ext_network))?;
+                rule.networks = protobuf::RepeatedField::from_vec(networks);
+            }
             rules.push(rule);
         }
         drop(site_group_lists); // make sure it's released
@@ -790,9 +1591,80 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
         if let Some(ext_bind) = ext_dns.bind {
             dns.bind = ext_bind;
         }
+        // Returns the address of the server, the settings of a server object
+        // are kept in the maps of the DNS settings.
+        let mut to_server_address = |ext_server: DnsServer| -> Result<String> {
+            match ext_server {
+                DnsServer::Address(address) => Ok(address),
+                DnsServer::WithOutbound {
+                    address,
+                    outbound,
+                    bootstrap,
+                    ca,
+                } => {
+                    if let Some(outbound) = outbound {
+                        dns.server_outbounds.insert(address.clone(), outbound);
+                    }
+                    if let Some(bootstrap) = bootstrap {
+                        if bootstrap.parse::<IpAddr>().is_err() {
+                            return Err(anyhow!(
+                                "invalid bootstrap ip {} for dns server {}",
+                                bootstrap,
+                                address
+                            ));
+                        }
+                        dns.server_bootstraps.insert(address.clone(), bootstrap);
+                    }
+                    if let Some(ca) = ca {
+                        dns.server_ca_files.insert(address.clone(), ca);
+                    }
+                    Ok(address)
+                }
+            }
+        };
         if let Some(ext_servers) = ext_dns.servers {
             for ext_server in ext_servers {
-                servers.push(ext_server);
+                servers.push(to_server_address(ext_server)?);
+            }
+        }
+        let mut groups = HashMap::new();
+        if let Some(ext_groups) = ext_dns.groups {
+            for (name, ext_servers) in ext_groups {
+                let mut group = internal::DNS_Servers::new();
+                for ext_server in ext_servers {
+                    group.values.push(to_server_address(ext_server)?);
+                }
+                groups.insert(name, group);
+            }
+        }
+        dns.groups = groups;
+        if let Some(ext_policies) = ext_dns.policies {
+            for ext_policy in ext_policies {
+                if !dns.groups.contains_key(&ext_policy.group) {
+                    return Err(anyhow!("dns server group {} not found", ext_policy.group));
+                }
+                let mut policy = internal::DNS_Policy::new();
+                let types = [
+                    (ext_policy.domain, internal::RoutingRule_Domain_Type::FULL),
+                    (
+                        ext_policy.domain_keyword,
+                        internal::RoutingRule_Domain_Type::PLAIN,
+                    ),
+                    (
+                        ext_policy.domain_suffix,
+                        internal::RoutingRule_Domain_Type::DOMAIN,
+                    ),
+                ];
+                for (values, field_type) in types.iter() {
+                    for value in values.iter().flatten() {
+                        let mut domain = internal::RoutingRule_Domain::new();
+                        domain.field_type = *field_type;
+                        domain.value = value.to_owned();
+                        policy.domains.push(domain);
+                    }
+                }
+                policy.group = ext_policy.group;
+                dns.policies.push(policy);
             }
         }
         if let Some(ext_hosts) = ext_dns.hosts {
@@ -806,6 +1678,68 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
                 hosts.insert(name.to_owned(), ips);
             }
         }
+        if let Some(ext_filter_bogons) = ext_dns.filter_bogons {
+            dns.filter_bogons = ext_filter_bogons;
+        }
+        if let Some(ext_filter_private) = ext_dns.filter_private {
+            dns.filter_private = ext_filter_private;
+        }
+        if let Some(ext_internal_domains) = ext_dns.internal_domains {
+            dns.internal_domains = protobuf::RepeatedField::from_vec(ext_internal_domains);
+        }
+        if let Some(ext_fail_open) = ext_dns.fail_open {
+            dns.fail_open = ext_fail_open;
+        }
+        if let Some(ext_fallback_ip) = ext_dns.fallback_ip {
+            if ext_fallback_ip.parse::<IpAddr>().is_err() {
+                return Err(anyhow!("invalid dns fallback ip {}", ext_fallback_ip));
+            }
+            dns.fallback_ip = ext_fallback_ip;
+        }
+        if let Some(ext_dot_idle_timeout) = ext_dns.dot_idle_timeout {
+            dns.dot_idle_timeout = ext_dot_idle_timeout;
+        }
+        if let Some(ext_cache_size) = ext_dns.cache_size {
+            dns.cache_size = ext_cache_size;
+        }
+        if let Some(ext_min_cache_ttl) = ext_dns.min_cache_ttl {
+            dns.min_cache_ttl = ext_min_cache_ttl;
+        }
+        if let Some(ext_max_cache_ttl) = ext_dns.max_cache_ttl {
+            dns.max_cache_ttl = ext_max_cache_ttl;
+        }
+        if dns.max_cache_ttl > 0 && dns.min_cache_ttl > dns.max_cache_ttl {
+            return Err(anyhow!(
+                "dns min cache ttl {} is greater than max cache ttl {}",
+                dns.min_cache_ttl,
+                dns.max_cache_ttl
+            ));
+        }
+        if let Some(ext_negative_cache_ttl) = ext_dns.negative_cache_ttl {
+            dns.negative_cache_ttl = ext_negative_cache_ttl;
+        }
+        if let Some(ext_prefetch) = ext_dns.prefetch {
+            if ext_prefetch >= 100 {
+                return Err(anyhow!("invalid dns prefetch {}", ext_prefetch));
+            }
+            dns.prefetch = ext_prefetch;
+        }
+        if let Some(ext_prefetch_min_hits) = ext_dns.prefetch_min_hits {
+            dns.prefetch_min_hits = ext_prefetch_min_hits;
+        }
+        if let Some(ext_client_subnet) = ext_dns.client_subnet {
+            if crate::app::dns_client::ClientSubnet::from_config(&ext_client_subnet).is_none() {
+                return Err(anyhow!("invalid dns client subnet {}", ext_client_subnet));
+            }
+            dns.client_subnet = ext_client_subnet;
+        }
+        if let Some(ext_outbound) = ext_dns.outbound {
+            dns.outbound = ext_outbound;
+        }
+        if let Some(ext_strategy) = ext_dns.strategy {
+            dns.strategy = crate::config::parse_dns_strategy(&ext_strategy)
+                .ok_or_else(|| anyhow!("invalid dns strategy {}", ext_strategy))?;
+        }
     }
     if dns.bind.is_empty() {
         dns.bind = "0.0.0.0".to_string();
@@ -819,12 +1753,47 @@ pub fn to_internal(json: Config) -> Result<internal::Config> {
         dns.hosts = hosts;
     }
 
+    for (server, outbound) in dns.server_outbounds.iter() {
+        if !outbounds.iter().any(|o| &o.tag == outbound) {
+            return Err(anyhow!(
+                "outbound [{}] for dns server {} not found",
+                outbound,
+                server
+            ));
+        }
+    }
+    if !dns.outbound.is_empty() && !outbounds.iter().any(|o| o.tag == dns.outbound) {
+        return Err(anyhow!("outbound [{}] for dns not found", dns.outbound));
+    }
+
     let mut config = internal::Config::new();
     config.log = protobuf::SingularPtrField::some(log);
     config.inbounds = inbounds;
     config.outbounds = outbounds;
     config.routing_rules = rules;
     config.dns = protobuf::SingularPtrField::some(dns);
+    if let Some(ext_control_outbound) = json.control_outbound {
+        config.control_outbound = ext_control_outbound;
+    }
+    if let Some(ext_router) = json.router {
+        let mut router = internal::Router::new();
+        if let Some(ext_dry_run) = ext_router.dry_run {
+            router.dry_run = ext_dry_run;
+        }
+        if let Some(ext_default) = ext_router.default {
+            router.default_outbound = ext_default;
+        }
+        config.router = protobuf::SingularPtrField::some(router);
+    }
+    if let Some(ext_udp_session_timeout) = json.udp_session_timeout {
+        config.udp_session_timeout = ext_udp_session_timeout;
+    }
+    if let Some(ext_link_buffer_size) = json.link_buffer_size {
+        if !crate::config::is_valid_link_buffer_size(ext_link_buffer_size) {
+            return Err(anyhow!("invalid link buffer size {}", ext_link_buffer_size));
+        }
+        config.link_buffer_size = ext_link_buffer_size;
+    }
     Ok(config)
 }
 
@@ -833,6 +1802,30 @@ pub fn from_string(config: String) -> Result<Config> {
         .map_err(|e| anyhow!("deserialize json config failed: {}", e))
 }
 
+/// Parses a single outbound object, e.g. for adding it to a running instance.
+pub fn outbound_from_string(outbound: &str) -> Result<internal::Outbound> {
+    let outbound: Outbound = serde_json::from_str(outbound)
+        .map_err(|e| anyhow!("deserialize json outbound failed: {}", e))?;
+    let config = Config {
+        log: None,
+        inbounds: None,
+        outbounds: Some(vec![outbound]),
+        rules: None,
+        dns: None,
+        control_outbound: None,
+        router: None,
+        udp_session_timeout: None,
+        connect_timeout: None,
+        tcp_nodelay: None,
+        tcp_keepalive: None,
+    };
+    let mut config = to_internal(config)?;
+    config
+        .outbounds
+        .pop()
+        .ok_or_else(|| anyhow!("invalid outbound"))
+}
+
 pub fn from_file<P>(path: P) -> Result<internal::Config>
 where
     P: AsRef<Path>,
@@ -32,3 +32,110 @@ fn test_dns_hosts() {
         &ips
     );
 }
+
+#[test]
+fn test_outbound_from_string() {
+    use protobuf::Message;
+
+    let json_str = r#"
+    {
+        "protocol": "select",
+        "tag": "select_out",
+        "settings": {
+            "actors": ["a", "b"]
+        }
+    }
+    "#;
+    let outbound = crate::config::json::outbound_from_string(json_str).unwrap();
+    assert_eq!(outbound.tag, "select_out");
+    assert_eq!(outbound.protocol, "select");
+    let settings =
+        crate::config::SelectOutboundSettings::parse_from_bytes(&outbound.settings).unwrap();
+    assert_eq!(
+        settings.actors.as_slice(),
+        &["a".to_string(), "b".to_string()]
+    );
+
+    let json_str = r#"{"protocol": "unknown", "tag": "x"}"#;
+    assert!(crate::config::json::outbound_from_string(json_str).is_err());
+}
+
+#[test]
+fn test_error_context() {
+    let json_str = r#"
+    {
+        "outbounds": [
+            {
+                "protocol": "direct",
+                "tag": "direct"
+            },
+            {
+                "protocol": "vmess",
+                "tag": "vmess_out"
+            }
+        ]
+    }
+    "#;
+    let config = crate::config::json::from_string(json_str.to_string()).unwrap();
+    let err = crate::config::json::to_internal(config).err().unwrap();
+    assert_eq!(
+        format!("{:#}", err),
+        "outbound [vmess_out]: invalid vmess outbound settings"
+    );
+}
+
+#[test]
+fn test_ssh_outbound() {
+    use protobuf::Message;
+
+    let json_str = r#"
+    {
+        "protocol": "ssh",
+        "tag": "ssh_out",
+        "settings": {
+            "address": "example.com",
+            "port": 2222,
+            "username": "user",
+            "privateKey": "/path/to/id_ed25519",
+            "privateKeyPassphrase": "secret",
+            "hostKeyFingerprint": "SHA256:abc"
+        }
+    }
+    "#;
+    let outbound = crate::config::json::outbound_from_string(json_str).unwrap();
+    let settings =
+        crate::config::SshOutboundSettings::parse_from_bytes(&outbound.settings).unwrap();
+    assert_eq!(settings.address, "example.com");
+    assert_eq!(settings.port, 2222);
+    assert_eq!(settings.username, "user");
+    assert_eq!(settings.password, "");
+    assert_eq!(settings.private_key, "/path/to/id_ed25519");
+    assert_eq!(settings.private_key_passphrase, "secret");
+    assert_eq!(settings.host_key_fingerprint, "SHA256:abc");
+
+    // Either a password or a private key.
+    let json_str = r#"
+    {
+        "protocol": "ssh",
+        "tag": "ssh_out",
+        "settings": {
+            "address": "example.com",
+            "username": "user",
+            "password": "pass",
+            "privateKey": "/path/to/id_ed25519"
+        }
+    }
+    "#;
+    assert!(crate::config::json::outbound_from_string(json_str).is_err());
+    let json_str = r#"
+    {
+        "protocol": "ssh",
+        "tag": "ssh_out",
+        "settings": {
+            "address": "example.com",
+            "password": "pass"
+        }
+    }
+    "#;
+    assert!(crate::config::json::outbound_from_string(json_str).is_err());
+}
@@ -1,3 +1,4 @@
+use std::net::IpAddr;
 use std::path::Path;
 
 use anyhow::anyhow;
@@ -13,8 +14,145 @@ pub mod json;
 #[cfg(feature = "config-conf")]
 pub mod conf;
 
+#[cfg(feature = "config-yaml")]
+pub mod yaml;
+
 pub use internal::*;
 
+/// Parses a port range in the form of `start-end`, both inclusive.
+pub fn parse_port_range(s: &str) -> Option<(u16, u16)> {
+    let parts: Vec<&str> = s.split('-').collect();
+    if parts.len() != 2 {
+        return None;
+    }
+    let start = parts[0].trim().parse::<u16>().ok()?;
+    let end = parts[1].trim().parse::<u16>().ok()?;
+    if start == 0 || start > end {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Parses a list of ports and port ranges separated by commas, e.g.
+/// `80,443,1000-2000`, ranges are inclusive.
+pub fn parse_ports(s: &str) -> Option<Vec<(u16, u16)>> {
+    let mut ports = Vec::new();
+    for item in s.split(',') {
+        let parts: Vec<&str> = item.split('-').collect();
+        let (start, end) = match parts.len() {
+            1 => {
+                let port = parts[0].trim().parse::<u16>().ok()?;
+                (port, port)
+            }
+            2 => (
+                parts[0].trim().parse::<u16>().ok()?,
+                parts[1].trim().parse::<u16>().ok()?,
+            ),
+            _ => return None,
+        };
+        if start > end {
+            return None;
+        }
+        ports.push((start, end));
+    }
+    Some(ports)
+}
+
+/// Parses a list of networks separated by commas, e.g. `tcp,udp`.
+pub fn parse_networks(s: &str) -> Option<Vec<String>> {
+    let mut networks = Vec::new();
+    for item in s.split(',') {
+        let network = item.trim().to_lowercase();
+        match network.as_str() {
+            "tcp" | "udp" => networks.push(network),
+            _ => return None,
+        }
+    }
+    Some(networks)
+}
+
+/// Parses an IP range in the CIDR notation, e.g. `198.18.0.0/15` or
+/// `fd00::/112`.
+pub fn parse_ip_range(s: &str) -> Option<(IpAddr, u8)> {
+    let parts: Vec<&str> = s.splitn(2, '/').collect();
+    if parts.len() != 2 {
+        return None;
+    }
+    let addr = parts[0].trim().parse::<IpAddr>().ok()?;
+    let prefix = parts[1].trim().parse::<u8>().ok()?;
+    let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+    if prefix > max_prefix {
+        return None;
+    }
+    Some((addr, prefix))
+}
+
+/// Parses the strategy of a balance outbound, `round-robin`, `weighted` or
+/// `consistent-hash`.
+pub fn parse_balance_strategy(s: &str) -> Option<internal::BalanceOutboundSettings_Strategy> {
+    match s.trim() {
+        "round-robin" => Some(internal::BalanceOutboundSettings_Strategy::ROUND_ROBIN),
+        "weighted" => Some(internal::BalanceOutboundSettings_Strategy::WEIGHTED),
+        "consistent-hash" => Some(internal::BalanceOutboundSettings_Strategy::CONSISTENT_HASH),
+        _ => None,
+    }
+}
+
+/// Parses a DNS strategy, `ipv4_only`, `ipv6_only`, `ipv4_first` or
+/// `ipv6_first`.
+pub fn parse_dns_strategy(s: &str) -> Option<internal::DnsStrategy> {
+    match s.trim() {
+        "ipv4_only" => Some(internal::DnsStrategy::IPV4_ONLY),
+        "ipv6_only" => Some(internal::DnsStrategy::IPV6_ONLY),
+        "ipv4_first" => Some(internal::DnsStrategy::IPV4_FIRST),
+        "ipv6_first" => Some(internal::DnsStrategy::IPV6_FIRST),
+        _ => None,
+    }
+}
+
+/// Tells whether the relay buffer size in KB is in a sane range, 0 is the
+/// default.
+pub fn is_valid_link_buffer_size(size: u32) -> bool {
+    size <= 1024
+}
+
+/// Tells whether the listen backlog is in a sane range, it's further clamped
+/// to the OS maximum when applied.
+pub fn is_valid_backlog(backlog: u32) -> bool {
+    backlog > 0 && backlog <= 65535
+}
+
+/// Tells whether the TUN MTU is in a sane range, from the IPv4 minimum
+/// datagram size to jumbo frames.
+pub fn is_valid_mtu(mtu: i32) -> bool {
+    (576..=9000).contains(&mtu)
+}
+
+/// Parses a subscription, either a list of share links or the base64 encoding
+/// of it, into outbounds. `ss://` (SIP002 and legacy), `vmess://`,
+/// `trojan://` and `vless://` links are supported, other lines are ignored.
+/// A link may result in more than one outbound, they're ordered so that an
+/// outbound comes after the ones it depends on, they can be added to a
+/// running instance one by one.
+#[cfg(feature = "config-conf")]
+pub fn parse_subscription(text: &str) -> Result<Vec<internal::Outbound>> {
+    let links = conf::share_link::decode_subscription(text.as_bytes())?;
+    let mut outbounds = Vec::new();
+    for link in links {
+        outbounds.append(&mut parse_share_link(&link)?);
+    }
+    Ok(outbounds)
+}
+
+/// Parses a share link into outbounds, ordered as `parse_subscription` does.
+#[cfg(feature = "config-conf")]
+pub fn parse_share_link(link: &str) -> Result<Vec<internal::Outbound>> {
+    // The entry outbound of a link comes last, after the ones it depends on.
+    let mut outbounds = conf::share_link::to_outbounds(&[link.to_string()])?.into_vec();
+    outbounds.rotate_left(1);
+    Ok(outbounds)
+}
+
 pub fn from_file(path: &str) -> Result<internal::Config> {
     if let Some(ext) = Path::new(path).extension() {
         if let Some(ext) = ext.to_str() {
@@ -23,9 +161,13 @@ pub fn from_file(path: &str) -> Result<internal::Config> {
                 "json" => return json::from_file(path),
                 #[cfg(feature = "config-conf")]
                 "conf" => return conf::from_file(path),
+                #[cfg(feature = "config-yaml")]
+                "yaml" | "yml" => return yaml::from_file(path),
                 _ => (),
             }
         }
     }
-    Err(anyhow!("config files use extension .json or .conf"))
+    Err(anyhow!(
+        "config files use extension .json, .conf, .yaml or .yml"
+    ))
 }
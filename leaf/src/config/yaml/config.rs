@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+
+use crate::config::conf::{self, Config, General, Proxy, ProxyGroup, Rule};
+use crate::config::internal;
+
+// Clash proxies which are always available to rules and groups.
+const BUILTIN_PROXIES: &[(&str, &str)] = &[("DIRECT", "direct"), ("REJECT", "drop")];
+
+const PROXY_FIELDS: &[&str] = &["name", "type", "server", "port", "udp"];
+
+fn get<'a>(map: &'a Mapping, key: &str) -> Option<&'a Value> {
+    map.get(&Value::String(key.to_string()))
+}
+
+fn get_string(map: &Mapping, key: &str) -> Option<String> {
+    match get(map, key)? {
+        Value::String(v) => Some(v.clone()),
+        Value::Number(v) => Some(v.to_string()),
+        Value::Bool(v) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+fn get_value<T: FromStr>(map: &Mapping, key: &str) -> Option<T> {
+    get_string(map, key)?.parse::<T>().ok()
+}
+
+fn get_strings(map: &Mapping, key: &str) -> Option<Vec<String>> {
+    let values = get(map, key)?.as_sequence()?;
+    Some(
+        values
+            .iter()
+            .filter_map(|v| v.as_str().map(|v| v.to_string()))
+            .collect(),
+    )
+}
+
+fn get_mapping<'a>(map: &'a Mapping, key: &str) -> Option<&'a Mapping> {
+    get(map, key)?.as_mapping()
+}
+
+// Warns about the fields which are not in any of the lists, they are skipped.
+fn warn_unsupported(what: &str, map: &Mapping, supported: &[&[&str]]) {
+    for (k, _) in map.iter() {
+        let k = k.as_str().unwrap_or_default();
+        if !supported.iter().any(|fields| fields.contains(&k)) {
+            println!("unsupported field [{}] of {} is skipped", k, what);
+        }
+    }
+}
+
+fn to_general(root: &Mapping) -> General {
+    let mut general = General::default();
+
+    let interface = if get_value::<bool>(root, "allow-lan").unwrap_or(false) {
+        match get_string(root, "bind-address") {
+            Some(v) if v != "*" => v,
+            _ => "0.0.0.0".to_string(),
+        }
+    } else {
+        "127.0.0.1".to_string()
+    };
+    if let Some(port) = get_value::<u16>(root, "port") {
+        general.interface = Some(interface.clone());
+        general.port = Some(port);
+    }
+    if let Some(port) = get_value::<u16>(root, "socks-port") {
+        general.socks_interface = Some(interface.clone());
+        general.socks_port = Some(port);
+    }
+    if let Some(port) = get_value::<u16>(root, "mixed-port") {
+        general.mixed_interface = Some(interface);
+        general.mixed_port = Some(port);
+    }
+
+    if let Some(level) = get_string(root, "log-level") {
+        let level = match level.as_str() {
+            "warning" => "warn",
+            "silent" => "error",
+            v => v,
+        };
+        general.loglevel = Some(level.to_string());
+    }
+
+    if let Some(mode) = get_string(root, "mode") {
+        if mode.to_lowercase() != "rule" {
+            println!("unsupported mode [{}] is skipped, rules are applied", mode);
+        }
+    }
+
+    let mut servers = Vec::new();
+    if let Some(dns) = get_mapping(root, "dns") {
+        for server in get_strings(dns, "nameserver").unwrap_or_default() {
+            // Only plain DNS servers on the default port.
+            let addr = server.trim_start_matches("udp://");
+            let ip = match addr.parse::<SocketAddr>() {
+                Ok(v) if v.port() == 53 => Some(v.ip()),
+                _ => addr.parse::<IpAddr>().ok(),
+            };
+            match ip {
+                Some(ip) => servers.push(ip.to_string()),
+                None => println!("unsupported dns server [{}] is skipped", server),
+            }
+        }
+        warn_unsupported("dns", dns, &[&["enable", "nameserver"]]);
+    }
+    // The default servers are used if it's empty.
+    general.dns_server = Some(servers);
+
+    general
+}
+
+// Applies the WebSocket transport settings, returns false if the network is
+// not supported.
+fn apply_network(proxy: &mut Proxy, map: &Mapping, what: &str) -> bool {
+    match get_string(map, "network").as_deref() {
+        None | Some("tcp") => true,
+        Some("ws") => {
+            proxy.ws = Some(true);
+            if let Some(opts) = get_mapping(map, "ws-opts") {
+                proxy.ws_path = get_string(opts, "path");
+                if let Some(headers) = get_mapping(opts, "headers") {
+                    proxy.ws_host = get_string(headers, "Host");
+                    warn_unsupported(&format!("ws headers of {}", what), headers, &[&["Host"]]);
+                }
+                warn_unsupported(
+                    &format!("ws-opts of {}", what),
+                    opts,
+                    &[&["path", "headers"]],
+                );
+            }
+            // The deprecated style.
+            if let Some(path) = get_string(map, "ws-path") {
+                proxy.ws_path = Some(path);
+            }
+            if let Some(headers) = get_mapping(map, "ws-headers") {
+                if let Some(host) = get_string(headers, "Host") {
+                    proxy.ws_host = Some(host);
+                }
+            }
+            true
+        }
+        Some(network) => {
+            println!("{} with network [{}] is skipped", what, network);
+            false
+        }
+    }
+}
+
+const WS_FIELDS: &[&str] = &["network", "ws-opts", "ws-path", "ws-headers"];
+
+fn to_proxy(map: &Mapping) -> Option<Proxy> {
+    let name = match get_string(map, "name") {
+        Some(v) => v,
+        None => {
+            println!("proxy without a name is skipped");
+            return None;
+        }
+    };
+    let what = format!("proxy [{}]", &name);
+    let mut proxy = Proxy::default();
+    proxy.tag = name;
+    proxy.address = get_string(map, "server");
+    proxy.port = get_value::<u16>(map, "port");
+    if proxy.address.is_none() || proxy.port.is_none() {
+        println!("{} without a server or port is skipped", what);
+        return None;
+    }
+
+    match get_string(map, "type").unwrap_or_default().as_str() {
+        "ss" => {
+            if get(map, "plugin").is_some() {
+                println!("{} with a plugin is skipped", what);
+                return None;
+            }
+            proxy.protocol = "ss".to_string();
+            if let Some(cipher) = get_string(map, "cipher") {
+                proxy.encrypt_method = Some(cipher);
+            }
+            proxy.password = get_string(map, "password");
+            warn_unsupported(&what, map, &[PROXY_FIELDS, &["cipher", "password"]]);
+        }
+        "trojan" => {
+            proxy.protocol = "trojan".to_string();
+            proxy.password = get_string(map, "password");
+            proxy.sni = get_string(map, "sni");
+            if !apply_network(&mut proxy, map, &what) {
+                return None;
+            }
+            warn_unsupported(&what, map, &[PROXY_FIELDS, WS_FIELDS, &["password", "sni"]]);
+        }
+        "vmess" => {
+            proxy.protocol = "vmess".to_string();
+            proxy.username = get_string(map, "uuid");
+            if proxy.username.is_none() {
+                println!("{} without an uuid is skipped", what);
+                return None;
+            }
+            if get_value::<u32>(map, "alterId").unwrap_or(0) > 0 {
+                println!("alterId of {} is ignored, only AEAD is supported", what);
+            }
+            match get_string(map, "cipher").as_deref() {
+                None | Some("auto") => (),
+                Some(cipher @ "aes-128-gcm") | Some(cipher @ "chacha20-poly1305") => {
+                    proxy.encrypt_method = Some(cipher.to_string());
+                }
+                Some(cipher) => {
+                    println!("unsupported cipher [{}] of {} is skipped", cipher, what);
+                }
+            }
+            proxy.tls = Some(get_value::<bool>(map, "tls").unwrap_or(false));
+            proxy.sni = get_string(map, "servername");
+            if !apply_network(&mut proxy, map, &what) {
+                return None;
+            }
+            warn_unsupported(
+                &what,
+                map,
+                &[
+                    PROXY_FIELDS,
+                    WS_FIELDS,
+                    &["uuid", "alterId", "cipher", "tls", "servername"],
+                ],
+            );
+        }
+        "socks5" => {
+            proxy.protocol = "socks".to_string();
+            warn_unsupported(&what, map, &[PROXY_FIELDS]);
+        }
+        t => {
+            println!("{} of unsupported type [{}] is skipped", what, t);
+            return None;
+        }
+    }
+    Some(proxy)
+}
+
+fn to_proxy_group(map: &Mapping) -> Option<ProxyGroup> {
+    let name = match get_string(map, "name") {
+        Some(v) => v,
+        None => {
+            println!("proxy group without a name is skipped");
+            return None;
+        }
+    };
+    let what = format!("proxy group [{}]", &name);
+    let mut group = ProxyGroup::default();
+    group.tag = name;
+    group.actors = get_strings(map, "proxies");
+    if group.actors.as_ref().map(|v| v.is_empty()).unwrap_or(true) {
+        println!("{} without proxies is skipped", what);
+        return None;
+    }
+
+    const GROUP_FIELDS: &[&str] = &["name", "type", "proxies"];
+    match get_string(map, "type").unwrap_or_default().as_str() {
+        "select" => {
+            group.protocol = "select".to_string();
+            warn_unsupported(&what, map, &[GROUP_FIELDS]);
+        }
+        "url-test" => {
+            group.protocol = "urltest".to_string();
+            if let Some(url) = get_string(map, "url") {
+                if url.starts_with("http://") {
+                    group.url = Some(url);
+                } else {
+                    println!("unsupported url [{}] of {} is skipped", url, what);
+                }
+            }
+            if let Some(interval) = get_value::<i32>(map, "interval") {
+                group.interval = Some(interval);
+            }
+            if let Some(tolerance) = get_value::<i32>(map, "tolerance") {
+                group.tolerance = Some(tolerance);
+            }
+            warn_unsupported(
+                &what,
+                map,
+                &[GROUP_FIELDS, &["url", "interval", "tolerance"]],
+            );
+        }
+        "fallback" => {
+            group.protocol = "failover".to_string();
+            if let Some(interval) = get_value::<i32>(map, "interval") {
+                group.check_interval = Some(interval);
+            }
+            // The health check of failover doesn't use an URL.
+            warn_unsupported(&what, map, &[GROUP_FIELDS, &["url", "interval"]]);
+        }
+        t => {
+            println!("{} of unsupported type [{}] is skipped", what, t);
+            return None;
+        }
+    }
+    Some(group)
+}
+
+fn to_rule(line: &str) -> Option<Rule> {
+    let params: Vec<&str> = line.split(',').map(|v| v.trim()).collect();
+    let mut rule = Rule::default();
+    if params.len() == 2 && params[0] == "MATCH" {
+        rule.type_field = "FINAL".to_string();
+        rule.target = params[1].to_string();
+        return Some(rule);
+    }
+    if params.len() < 3 {
+        println!("invalid rule [{}] is skipped", line);
+        return None;
+    }
+    let filter = params[1].to_string();
+    let (type_field, filter) = match params[0] {
+        "DOMAIN" | "DOMAIN-SUFFIX" | "DOMAIN-KEYWORD" | "GEOIP" | "PROCESS-NAME" | "NETWORK" => {
+            (params[0], filter)
+        }
+        "IP-CIDR" | "IP-CIDR6" => ("IP-CIDR", filter),
+        "DST-PORT" => ("PORT-RANGE", format!("{}-{}", filter, filter)),
+        _ => {
+            println!("unsupported rule [{}] is skipped", line);
+            return None;
+        }
+    };
+    rule.type_field = type_field.to_string();
+    rule.filter = Some(filter);
+    rule.target = params[2].to_string();
+    // Destinations are never resolved for rules.
+    for param in params.iter().skip(3) {
+        if *param != "no-resolve" {
+            println!(
+                "unsupported option [{}] of rule [{}] is skipped",
+                param, line
+            );
+        }
+    }
+    Some(rule)
+}
+
+pub fn from_string(config: &str) -> Result<Config> {
+    let root: Value = serde_yaml::from_str(config)
+        .map_err(|e| anyhow!("deserialize yaml config failed: {}", e))?;
+    let root = root
+        .as_mapping()
+        .ok_or_else(|| anyhow!("invalid yaml config"))?;
+    warn_unsupported(
+        "config",
+        root,
+        &[&[
+            "port",
+            "socks-port",
+            "mixed-port",
+            "allow-lan",
+            "bind-address",
+            "log-level",
+            "mode",
+            "dns",
+            "hosts",
+            "proxies",
+            "proxy-groups",
+            "rules",
+        ]],
+    );
+
+    let mut proxies = Vec::new();
+    if let Some(items) = get(root, "proxies").and_then(|v| v.as_sequence()) {
+        for item in items {
+            if let Some(proxy) = item.as_mapping().and_then(to_proxy) {
+                proxies.push(proxy);
+            }
+        }
+    }
+    for (tag, protocol) in BUILTIN_PROXIES {
+        if !proxies.iter().any(|p| p.tag == *tag) {
+            let mut proxy = Proxy::default();
+            proxy.tag = tag.to_string();
+            proxy.protocol = protocol.to_string();
+            proxies.push(proxy);
+        }
+    }
+
+    let mut proxy_groups = Vec::new();
+    if let Some(items) = get(root, "proxy-groups").and_then(|v| v.as_sequence()) {
+        for item in items {
+            if let Some(group) = item.as_mapping().and_then(to_proxy_group) {
+                proxy_groups.push(group);
+            }
+        }
+    }
+
+    let mut rules = Vec::new();
+    for line in get_strings(root, "rules").unwrap_or_default() {
+        if let Some(rule) = to_rule(&line) {
+            rules.push(rule);
+        }
+    }
+
+    let mut hosts = HashMap::new();
+    if let Some(items) = get_mapping(root, "hosts") {
+        for (name, ip) in items.iter() {
+            if let (Some(name), Some(ip)) = (name.as_str(), ip.as_str()) {
+                hosts.insert(name.to_string(), vec![ip.to_string()]);
+            }
+        }
+    }
+
+    let mut config = Config::default();
+    config.general = Some(to_general(root));
+    config.proxy = Some(proxies);
+    config.proxy_group = Some(proxy_groups);
+    config.rule = Some(rules);
+    config.host = Some(hosts);
+    Ok(config)
+}
+
+pub fn from_file<P>(path: P) -> Result<internal::Config>
+where
+    P: AsRef<Path>,
+{
+    let config = std::fs::read_to_string(path)?;
+    let config = from_string(&config)?;
+    conf::to_internal(config)
+}
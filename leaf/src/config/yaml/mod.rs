@@ -0,0 +1,6 @@
+mod config;
+
+pub use config::*;
+
+#[cfg(test)]
+mod tests;
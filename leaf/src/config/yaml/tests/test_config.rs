@@ -0,0 +1,157 @@
+use protobuf::Message;
+
+use crate::config::internal;
+
+#[test]
+fn test_clash_config() {
+    let yaml_str = r#"
+port: 7890
+socks-port: 7891
+allow-lan: false
+mode: rule
+log-level: warning
+external-controller: 127.0.0.1:9090
+dns:
+  enable: true
+  nameserver:
+    - 223.5.5.5
+    - udp://8.8.8.8:53
+    - https://doh.pub/dns-query
+proxies:
+  - name: "ss1"
+    type: ss
+    server: 1.2.3.4
+    port: 8388
+    cipher: aes-128-gcm
+    password: "password"
+    udp: true
+  - name: "ss-obfs"
+    type: ss
+    server: 1.2.3.4
+    port: 8389
+    cipher: aes-128-gcm
+    password: "password"
+    plugin: obfs
+  - name: "trojan1"
+    type: trojan
+    server: trojan.example.com
+    port: 443
+    password: "password"
+    sni: trojan.example.com
+    skip-cert-verify: true
+    network: ws
+    ws-opts:
+      path: /ws
+      headers:
+        Host: trojan.example.com
+  - name: "vmess1"
+    type: vmess
+    server: vmess.example.com
+    port: 443
+    uuid: 0eb5486e-e1b5-49c5-aa75-d15e54dfac9d
+    alterId: 0
+    cipher: auto
+    tls: true
+    servername: vmess.example.com
+  - name: "socks1"
+    type: socks5
+    server: 127.0.0.1
+    port: 1080
+  - name: "http1"
+    type: http
+    server: 127.0.0.1
+    port: 8080
+proxy-groups:
+  - name: Proxy
+    type: select
+    proxies:
+      - ss1
+      - trojan1
+      - vmess1
+      - socks1
+  - name: Auto
+    type: url-test
+    proxies:
+      - ss1
+      - vmess1
+    url: http://www.gstatic.com/generate_204
+    interval: 300
+  - name: Fallback
+    type: fallback
+    proxies:
+      - trojan1
+      - ss1
+    url: http://www.gstatic.com/generate_204
+    interval: 600
+  - name: Balance
+    type: load-balance
+    proxies:
+      - ss1
+rules:
+  - DOMAIN-SUFFIX,google.com,Proxy
+  - DOMAIN,www.example.com,Auto
+  - DOMAIN-KEYWORD,ads,REJECT
+  - IP-CIDR,192.168.0.0/16,DIRECT,no-resolve
+  - IP-CIDR6,fd00::/8,DIRECT
+  - GEOIP,CN,DIRECT
+  - DST-PORT,22,DIRECT
+  - RULE-SET,apple,DIRECT
+  - MATCH,Fallback
+"#;
+    let config = crate::config::yaml::from_string(yaml_str).unwrap();
+    let general = config.general.as_ref().unwrap();
+    assert_eq!(general.interface.as_deref(), Some("127.0.0.1"));
+    assert_eq!(general.port, Some(7890));
+    assert_eq!(general.socks_port, Some(7891));
+    assert_eq!(general.loglevel.as_deref(), Some("warn"));
+    assert_eq!(
+        general.dns_server.as_ref().unwrap(),
+        &vec!["223.5.5.5".to_string(), "8.8.8.8".to_string()]
+    );
+    let proxies: Vec<_> = config
+        .proxy
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|p| p.tag.as_str())
+        .collect();
+    assert_eq!(
+        proxies,
+        vec!["ss1", "trojan1", "vmess1", "socks1", "DIRECT", "REJECT"]
+    );
+    let groups: Vec<_> = config
+        .proxy_group
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|g| (g.tag.as_str(), g.protocol.as_str()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            ("Proxy", "select"),
+            ("Auto", "urltest"),
+            ("Fallback", "failover")
+        ]
+    );
+    assert_eq!(config.rule.as_ref().unwrap().len(), 8);
+
+    let config = crate::config::conf::to_internal(config).unwrap();
+    // MATCH makes the Fallback group the default outbound.
+    assert_eq!(config.outbounds[0].tag, "Fallback");
+    let ss = config.outbounds.iter().find(|o| o.tag == "ss1").unwrap();
+    assert_eq!(ss.protocol, "shadowsocks");
+    let settings = internal::ShadowsocksOutboundSettings::parse_from_bytes(&ss.settings).unwrap();
+    assert_eq!(settings.method, "aes-128-gcm");
+    assert_eq!(settings.port, 8388);
+    assert!(config
+        .outbounds
+        .iter()
+        .any(|o| o.tag == "trojan1_ws_xxx" && o.protocol == "ws"));
+    assert!(config
+        .outbounds
+        .iter()
+        .any(|o| o.tag == "vmess1_tls_xxx" && o.protocol == "tls"));
+    assert_eq!(config.routing_rules.len(), 7);
+    assert_eq!(config.routing_rules[6].port_ranges[0], "22-22");
+}
@@ -1,3 +1,5 @@
+use anyhow::{anyhow, Result};
+
 pub mod app;
 pub mod common;
 pub mod config;
@@ -6,4 +8,184 @@ pub mod proxy;
 pub mod session;
 pub mod util;
 
+pub use app::outbound::registry::{register_outbound, OutboundFactory};
+use app::runtime::RuntimeId;
+
 pub type Runner = std::pin::Pin<Box<dyn std::future::Future<Output = ()>>>;
+
+/// Where the config of an instance comes from.
+pub enum Config {
+    /// A config file, the format is determined by the extension.
+    File(String),
+    /// A config built in code, e.g. by an app embedding leaf.
+    Internal(config::Config),
+}
+
+pub struct StartOptions {
+    pub config: Config,
+}
+
+/// Starts an instance with the options, registered under `rt_id` so it can
+/// be operated while running, blocks until the instance stops.
+pub fn start(rt_id: RuntimeId, opts: StartOptions) -> Result<()> {
+    let config = match opts.config {
+        Config::File(path) => config::from_file(&path)?,
+        Config::Internal(config) => config,
+    };
+    validate(&config)?;
+    util::run_with_id(rt_id, config)
+}
+
+/// Checks the config file without starting an instance, the error tells
+/// what's wrong, e.g. which outbound has invalid settings.
+pub fn test_config(path: &str) -> Result<()> {
+    let config = config::from_file(path)?;
+    validate(&config)?;
+    // The handlers are created as starting does, invalid outbound settings
+    // fail here instead of being skipped at runtime.
+    app::outbound::manager::OutboundManager::try_new(
+        &config.outbounds,
+        config.dns.as_ref().unwrap(),
+    )?;
+    Ok(())
+}
+
+fn validate(config: &config::Config) -> Result<()> {
+    if config.dns.is_none() {
+        return Err(anyhow!("missing dns settings"));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "outbound-direct"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_internal_config() {
+        let mut outbound = config::Outbound::new();
+        outbound.tag = "direct".to_string();
+        outbound.protocol = "direct".to_string();
+        outbound.bind = "0.0.0.0".to_string();
+        let mut dns = config::DNS::new();
+        dns.bind = "0.0.0.0".to_string();
+        dns.servers.push("127.0.0.1".to_string());
+        let mut config = config::Config::new();
+        config.outbounds.push(outbound);
+        config.dns = protobuf::SingularPtrField::some(dns);
+
+        // Without inbounds the instance stops right after it's started.
+        let rt_id = 283;
+        let res = start(
+            rt_id,
+            StartOptions {
+                config: Config::Internal(config),
+            },
+        );
+        assert!(res.is_ok());
+        assert!(app::runtime::get(rt_id).is_none());
+
+        let res = start(
+            rt_id,
+            StartOptions {
+                config: Config::Internal(config::Config::new()),
+            },
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_config_str(name: &str, content: &str) -> Result<()> {
+        let path = std::env::temp_dir().join(format!("leaf-{}-{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        let res = test_config(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        res
+    }
+
+    #[cfg(all(
+        feature = "config-json",
+        feature = "outbound-tls",
+        feature = "outbound-ws"
+    ))]
+    #[test]
+    fn test_validate_outbound_settings() {
+        let config = |outbound: &str| {
+            format!(
+                r#"
+                {{
+                    "dns": {{ "servers": ["127.0.0.1"] }},
+                    "outbounds": [
+                        {{ "protocol": "direct", "tag": "direct" }},
+                        {}
+                    ]
+                }}
+                "#,
+                outbound
+            )
+        };
+        assert!(test_config_str(
+            "tls_ok.json",
+            &config(r#"{ "protocol": "tls", "tag": "tls_out", "settings": {} }"#)
+        )
+        .is_ok());
+        let err = test_config_str(
+            "cert.json",
+            &config(
+                r#"{ "protocol": "tls", "tag": "tls_out", "settings": { "certSha256": "00" } }"#,
+            ),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid [tls_out] outbound settings: invalid certificate sha256"
+        );
+        let err = test_config_str(
+            "fingerprint.json",
+            &config(
+                r#"{ "protocol": "tls", "tag": "tls_out", "settings": { "fingerprint": "netscape" } }"#,
+            ),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid [tls_out] outbound settings"));
+        let err = test_config_str(
+            "ws.json",
+            &config(
+                r#"{ "protocol": "ws", "tag": "ws_out", "settings": { "headers": { "bad header": "x" } } }"#,
+            ),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid [ws_out] outbound settings"));
+        assert!(test_config_str(
+            "ws_ok.json",
+            &config(r#"{ "protocol": "ws", "tag": "ws_out", "settings": { "path": "/ws" } }"#)
+        )
+        .is_ok());
+    }
+
+    #[cfg(all(feature = "config-conf", feature = "outbound-failover"))]
+    #[test]
+    fn test_validate_conf_proxy_group() {
+        let err = test_config_str(
+            "group.conf",
+            r#"
+[General]
+dns-server = 127.0.0.1
+
+[Proxy]
+Direct = direct
+
+[Proxy Group]
+Failover = failover, Direct, priorities=1:2
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "proxy group [Failover]: failover priorities must have the same length as actors"
+        );
+    }
+}
@@ -48,8 +48,25 @@ lazy_static! {
     pub static ref LINK_BUFFER_SIZE: usize = {
         get_env_var("LINK_BUFFER_SIZE", 2)
     };
+
+    /// Buffer size for receiving UDP datagrams from outbounds, in KB, larger
+    /// datagrams are truncated.
+    pub static ref UDP_BUFFER_SIZE: usize = {
+        get_env_var("UDP_BUFFER_SIZE", 64)
+    };
+
+    /// Firewall mark set on outbound sockets on Linux, 0 for none. Policy
+    /// routing can match it to keep leaf's own traffic from being redirected
+    /// back to a TPROXY inbound.
+    pub static ref OUTBOUND_MARK: u32 = {
+        get_env_var("OUTBOUND_MARK", 0)
+    };
 }
 
+/// Default timeout in seconds for establishing a connection through an
+/// outbound, including the handshakes done before the stream is returned.
+pub static CONNECT_TIMEOUT: u64 = 10;
+
 /// Maximum outbound dial concurrency.
 pub static OUTBOUND_DIAL_CONCURRENCY: usize = 1;
 
@@ -67,3 +84,38 @@ pub static MAX_DNS_RETRIES: usize = 4;
 
 /// Timeout for a DNS query for the built-in DNS client.
 pub static DNS_TIMEOUT: u64 = 4;
+
+/// Duration in seconds to cache a non-existent domain in the built-in DNS
+/// client.
+pub static DNS_NEGATIVE_CACHE_TTL: u64 = 30;
+
+/// Default number of hits before a cached DNS answer is prefetched.
+pub static DNS_PREFETCH_MIN_HITS: u32 = 3;
+
+/// Default duration in seconds to keep an idle DNS-over-TLS connection for
+/// reuse.
+pub static DNS_DOT_IDLE_TIMEOUT: u64 = 30;
+
+/// Minimum interval in seconds between saves of the fake DNS allocations.
+pub static FAKE_DNS_SAVE_INTERVAL: u64 = 10;
+
+/// Default duration in seconds to keep an unused fake DNS allocation in the
+/// saved file.
+pub static FAKE_DNS_PERSIST_TTL: u64 = 86400;
+
+/// Default range to allocate fake IPv4 addresses from.
+pub static FAKE_DNS_RANGE: &str = "240.255.0.0/16";
+
+/// Maximum number of fake IPv6 addresses used from the configured range.
+pub static FAKE_DNS_IPV6_POOL_SIZE: u64 = 65536;
+
+/// Timeout in seconds of a probe of the urltest outbound, including the
+/// handshake with the actor.
+pub static URL_TEST_TIMEOUT: u64 = 5;
+
+/// How long in seconds the owning process of a local socket is cached for
+/// process name rules.
+pub static PROCESS_LOOKUP_CACHE_TTL: u64 = 2;
+
+/// Maximum number of cached owning processes of local sockets.
+pub static PROCESS_LOOKUP_CACHE_SIZE: usize = 1024;
@@ -0,0 +1,184 @@
+use std::{
+    io,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::session::Session;
+
+pub mod tcp;
+pub mod udp;
+
+pub use tcp::Handler as TcpHandler;
+pub use udp::Handler as UdpHandler;
+
+pub static NAME: &str = "balance";
+
+// Points of an actor of weight 1 on the hash ring.
+const VIRTUAL_NODES: u32 = 64;
+
+/// How an actor is picked for a connection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    /// Each actor in turn, regardless of the weights.
+    RoundRobin,
+    /// Randomly in proportion to the weights.
+    Weighted,
+    /// By the destination host, so connections to a host keep going through
+    /// the same actor while the actors don't change.
+    ConsistentHash,
+}
+
+impl FromStr for Strategy {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "round-robin" => Ok(Strategy::RoundRobin),
+            "weighted" => Ok(Strategy::Weighted),
+            "consistent-hash" => Ok(Strategy::ConsistentHash),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown balance strategy: {}", s),
+            )),
+        }
+    }
+}
+
+// FNV-1a, stable across runs and platforms unlike the std hasher.
+fn hash(data: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in data {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
+
+/// Picks the actor for a session, shared by the TCP and UDP handlers.
+pub struct Balancer {
+    strategy: Strategy,
+    weights: Vec<u32>,
+    total_weight: u64,
+    next: AtomicUsize,
+    // (point, actor index) sorted by point
+    ring: Vec<(u64, usize)>,
+}
+
+impl Balancer {
+    /// The actors are identified by the tags on the hash ring, so adding or
+    /// removing one moves only the hosts around it. Actors of weight 0 are
+    /// never picked, except in turn by the round-robin strategy.
+    pub fn new(strategy: Strategy, tags: &[String], weights: Vec<u32>) -> io::Result<Self> {
+        if tags.is_empty() || tags.len() != weights.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "weights and actors mismatch",
+            ));
+        }
+        let total_weight = weights.iter().map(|w| *w as u64).sum();
+        if total_weight == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "all actors have zero weight",
+            ));
+        }
+        let mut ring = Vec::new();
+        if strategy == Strategy::ConsistentHash {
+            for (i, tag) in tags.iter().enumerate() {
+                for n in 0..weights[i].saturating_mul(VIRTUAL_NODES) {
+                    ring.push((hash(format!("{}#{}", tag, n).as_bytes()), i));
+                }
+            }
+            ring.sort_unstable();
+        }
+        Ok(Balancer {
+            strategy,
+            weights,
+            total_weight,
+            next: AtomicUsize::new(0),
+            ring,
+        })
+    }
+
+    /// Returns the index of the actor to use.
+    pub fn pick(&self, sess: &Session) -> usize {
+        match self.strategy {
+            Strategy::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % self.weights.len(),
+            Strategy::Weighted => {
+                let mut rng = StdRng::from_entropy();
+                let mut n = rng.gen_range(0, self.total_weight);
+                for (i, w) in self.weights.iter().enumerate() {
+                    if n < *w as u64 {
+                        return i;
+                    }
+                    n -= *w as u64;
+                }
+                unreachable!()
+            }
+            Strategy::ConsistentHash => {
+                let h = hash(sess.destination.host().as_bytes());
+                let i = match self.ring.binary_search(&(h, 0)) {
+                    Ok(i) | Err(i) => i % self.ring.len(),
+                };
+                self.ring[i].1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::session::SocksAddr;
+
+    use super::*;
+
+    fn session(host: &str, port: u16) -> Session {
+        let mut sess = Session::default();
+        sess.destination = SocksAddr::Domain(host.to_string(), port);
+        sess
+    }
+
+    #[test]
+    fn test_balancer() {
+        let tags: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+        assert!(Balancer::new(Strategy::Weighted, &tags, vec![1, 1]).is_err());
+        assert!(Balancer::new(Strategy::Weighted, &tags, vec![0, 0, 0]).is_err());
+        assert!("random".parse::<Strategy>().is_err());
+
+        let b = Balancer::new(Strategy::RoundRobin, &tags, vec![1, 1, 1]).unwrap();
+        let picks: Vec<usize> = (0..4).map(|_| b.pick(&session("x.com", 80))).collect();
+        assert_eq!(picks, vec![0, 1, 2, 0]);
+
+        let b = Balancer::new(Strategy::Weighted, &tags, vec![0, 3, 1]).unwrap();
+        let mut counts = [0; 3];
+        for _ in 0..400 {
+            counts[b.pick(&session("x.com", 80))] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert!(counts[1] > counts[2]);
+
+        // The same host sticks to an actor regardless of the port.
+        let b = Balancer::new(Strategy::ConsistentHash, &tags, vec![1, 1, 1]).unwrap();
+        let mut used = [false; 3];
+        for i in 0..100 {
+            let host = format!("host{}.com", i);
+            let picked = b.pick(&session(&host, 80));
+            assert_eq!(b.pick(&session(&host, 443)), picked);
+            used[picked] = true;
+        }
+        assert!(used.iter().all(|u| *u));
+
+        // Removing an actor only moves the hosts on it.
+        let b2 = Balancer::new(Strategy::ConsistentHash, &tags[..2], vec![1, 1]).unwrap();
+        for i in 0..100 {
+            let sess = session(&format!("host{}.com", i), 80);
+            let picked = b.pick(&sess);
+            if picked != 2 {
+                assert_eq!(b2.pick(&sess), picked);
+            }
+        }
+    }
+}
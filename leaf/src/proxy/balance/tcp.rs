@@ -0,0 +1,45 @@
+use std::{io, sync::Arc};
+
+use async_trait::async_trait;
+use log::*;
+
+use super::Balancer;
+use crate::{
+    proxy::sticky::StickyTable,
+    proxy::{OutboundConnect, OutboundHandler, ProxyStream, TcpOutboundHandler},
+    session::Session,
+};
+
+pub struct Handler {
+    pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub balancer: Arc<Balancer>,
+    pub sticky: Option<Arc<StickyTable>>,
+}
+
+#[async_trait]
+impl TcpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    async fn handle_tcp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _stream: Option<Box<dyn ProxyStream>>,
+    ) -> io::Result<Box<dyn ProxyStream>> {
+        let i = match &self.sticky {
+            Some(sticky) => sticky.pick(sess, || self.balancer.pick(sess)),
+            None => self.balancer.pick(sess),
+        };
+        debug!(
+            "balance handles tcp [{}] to [{}]",
+            sess.destination,
+            self.actors[i].tag()
+        );
+        self.actors[i].handle_tcp(sess, None).await
+    }
+}
@@ -0,0 +1,53 @@
+use std::io;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::*;
+
+use super::Balancer;
+use crate::{
+    proxy::sticky::StickyTable,
+    proxy::{
+        OutboundConnect, OutboundDatagram, OutboundHandler, OutboundTransport, UdpOutboundHandler,
+        UdpTransportType,
+    },
+    session::Session,
+};
+
+pub struct Handler {
+    pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub balancer: Arc<Balancer>,
+    pub sticky: Option<Arc<StickyTable>>,
+}
+
+#[async_trait]
+impl UdpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn udp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    fn udp_transport_type(&self) -> UdpTransportType {
+        UdpTransportType::Unknown
+    }
+
+    async fn handle_udp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _transport: Option<OutboundTransport>,
+    ) -> io::Result<Box<dyn OutboundDatagram>> {
+        let i = match &self.sticky {
+            Some(sticky) => sticky.pick(sess, || self.balancer.pick(sess)),
+            None => self.balancer.pick(sess),
+        };
+        debug!(
+            "balance handles udp [{}] to [{}]",
+            sess.destination,
+            self.actors[i].tag()
+        );
+        self.actors[i].handle_udp(sess, None).await
+    }
+}
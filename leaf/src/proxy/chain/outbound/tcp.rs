@@ -7,13 +7,14 @@ use crate::{
     app::dns_client::DnsClient,
     proxy::{
         stream::SimpleProxyStream, OutboundConnect, OutboundHandler, ProxyStream, TcpConnector,
-        TcpOutboundHandler,
+        TcpOptions, TcpOutboundHandler,
     },
     session::{Session, SocksAddr},
 };
 
 pub struct Handler {
     pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
@@ -28,7 +29,11 @@ impl Handler {
     }
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
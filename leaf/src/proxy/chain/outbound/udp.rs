@@ -13,7 +13,7 @@ use crate::{
     proxy::{
         stream::SimpleProxyStream, OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf,
         OutboundDatagramSendHalf, OutboundHandler, OutboundTransport, SimpleOutboundDatagram,
-        TcpConnector, UdpConnector, UdpOutboundHandler, UdpTransportType,
+        TcpConnector, TcpOptions, UdpConnector, UdpOutboundHandler, UdpTransportType,
     },
     session::{Session, SocksAddr},
 };
@@ -65,6 +65,7 @@ impl AsyncWrite for DatagramToStream {
 
 pub struct Handler {
     pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
@@ -79,7 +80,11 @@ impl Handler {
     }
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 impl UdpConnector for Handler {}
 
 #[async_trait]
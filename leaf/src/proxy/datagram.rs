@@ -7,7 +7,10 @@ use tokio::net::{
     UdpSocket,
 };
 
-use crate::{app::dns_client::DnsClient, session::SocksAddr};
+use crate::{
+    app::dns_client::{DnsClient, DnsStrategy},
+    session::SocksAddr,
+};
 
 use super::{
     InboundDatagram, InboundDatagramRecvHalf, InboundDatagramSendHalf, OutboundDatagram,
@@ -20,6 +23,7 @@ pub struct SimpleOutboundDatagram {
     destination: Option<SocksAddr>,
     dns_client: Arc<DnsClient>,
     bind_addr: SocketAddr,
+    dns_strategy: Option<DnsStrategy>,
 }
 
 impl SimpleOutboundDatagram {
@@ -34,8 +38,16 @@ impl SimpleOutboundDatagram {
             destination,
             dns_client,
             bind_addr,
+            dns_strategy: None,
         }
     }
+
+    /// Sets the strategy to resolve domain targets with, the global one if
+    /// `None`. Only the addresses of the family of the bind address are
+    /// sent to.
+    pub fn set_dns_strategy(&mut self, dns_strategy: Option<DnsStrategy>) {
+        self.dns_strategy = dns_strategy;
+    }
 }
 
 impl OutboundDatagram for SimpleOutboundDatagram {
@@ -52,6 +64,7 @@ impl OutboundDatagram for SimpleOutboundDatagram {
                 s,
                 self.dns_client,
                 self.bind_addr,
+                self.dns_strategy,
             )),
         )
     }
@@ -75,7 +88,12 @@ impl OutboundDatagramRecvHalf for SimpleOutboundDatagramRecvHalf {
     }
 }
 
-pub struct SimpleOutboundDatagramSendHalf(SendHalf, Arc<DnsClient>, SocketAddr);
+pub struct SimpleOutboundDatagramSendHalf(
+    SendHalf,
+    Arc<DnsClient>,
+    SocketAddr,
+    Option<DnsStrategy>,
+);
 
 #[async_trait]
 impl OutboundDatagramSendHalf for SimpleOutboundDatagramSendHalf {
@@ -84,7 +102,7 @@ impl OutboundDatagramSendHalf for SimpleOutboundDatagramSendHalf {
             SocksAddr::Domain(domain, port) => {
                 let ips = self
                     .1
-                    .lookup_with_bind(domain.to_owned(), &self.2)
+                    .lookup_with_strategy(domain.to_owned(), &self.2, self.3)
                     .map_err(|e| {
                         io::Error::new(
                             io::ErrorKind::Other,
@@ -92,13 +110,17 @@ impl OutboundDatagramSendHalf for SimpleOutboundDatagramSendHalf {
                         )
                     })
                     .await?;
-                if ips.is_empty() {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidInput,
-                        "could not resolve to any address",
-                    ));
-                }
-                SocketAddr::new(ips[0], port.to_owned())
+                // The socket can only send to its own family.
+                let ip = match ips.iter().find(|ip| ip.is_ipv4() == self.2.is_ipv4()) {
+                    Some(ip) => *ip,
+                    None => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "could not resolve to any address",
+                        ))
+                    }
+                };
+                SocketAddr::new(ip, port.to_owned())
             }
             SocksAddr::Ip(a) => a.to_owned(),
         };
@@ -4,25 +4,31 @@ use async_trait::async_trait;
 
 use crate::{
     app::dns_client::DnsClient,
-    proxy::{OutboundConnect, ProxyStream, TcpConnector, TcpOutboundHandler},
+    proxy::{OutboundConnect, ProxyStream, TcpConnector, TcpOptions, TcpOutboundHandler},
     session::Session,
 };
 
 pub struct Handler {
     bind_addr: SocketAddr,
+    tcp_options: TcpOptions,
     dns_client: Arc<DnsClient>,
 }
 
 impl Handler {
-    pub fn new(bind_addr: SocketAddr, dns_client: Arc<DnsClient>) -> Self {
+    pub fn new(bind_addr: SocketAddr, tcp_options: TcpOptions, dns_client: Arc<DnsClient>) -> Self {
         Handler {
             bind_addr,
+            tcp_options,
             dns_client,
         }
     }
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
@@ -40,11 +46,12 @@ impl TcpOutboundHandler for Handler {
         _stream: Option<Box<dyn ProxyStream>>,
     ) -> io::Result<Box<dyn ProxyStream>> {
         Ok(self
-            .dial_tcp_stream(
+            .dial_tcp_stream_with_strategy(
                 self.dns_client.clone(),
                 &self.bind_addr,
                 &sess.destination.host(),
                 &sess.destination.port(),
+                sess.dns_strategy,
             )
             .await?)
     }
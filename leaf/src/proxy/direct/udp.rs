@@ -5,27 +5,37 @@ use async_trait::async_trait;
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
-        OutboundConnect, OutboundDatagram, OutboundTransport, SimpleOutboundDatagram, UdpConnector,
-        UdpOutboundHandler, UdpTransportType,
+        dial_bind_addr, OutboundConnect, OutboundDatagram, OutboundTransport,
+        SimpleOutboundDatagram, UdpConnector, UdpOutboundHandler, UdpTransportType,
     },
     session::{Session, SocksAddr},
 };
 
 pub struct Handler {
     bind_addr: SocketAddr,
+    interface: Option<String>,
     dns_client: Arc<DnsClient>,
 }
 
 impl Handler {
-    pub fn new(bind_addr: SocketAddr, dns_client: Arc<DnsClient>) -> Self {
+    pub fn new(
+        bind_addr: SocketAddr,
+        interface: Option<String>,
+        dns_client: Arc<DnsClient>,
+    ) -> Self {
         Handler {
             bind_addr,
+            interface,
             dns_client,
         }
     }
 }
 
-impl UdpConnector for Handler {}
+impl UdpConnector for Handler {
+    fn bind_interface(&self) -> Option<&str> {
+        self.interface.as_deref()
+    }
+}
 
 #[async_trait]
 impl UdpOutboundHandler for Handler {
@@ -46,18 +56,22 @@ impl UdpOutboundHandler for Handler {
         sess: &'a Session,
         _transport: Option<OutboundTransport>,
     ) -> io::Result<Box<dyn OutboundDatagram>> {
-        let socket = self.create_udp_socket(&self.bind_addr).await?;
+        // The unspecified address, e.g. with an interface, follows the
+        // family of an IP destination.
+        let bind_addr = match sess.destination.ip() {
+            Some(ip) => dial_bind_addr(&SocketAddr::new(ip, 0), &self.bind_addr)?,
+            None => self.bind_addr,
+        };
+        let socket = self.create_udp_socket(&bind_addr).await?;
         let destination = match &sess.destination {
             SocksAddr::Domain(domain, port) => {
                 Some(SocksAddr::Domain(domain.to_owned(), port.to_owned()))
             }
             _ => None,
         };
-        Ok(Box::new(SimpleOutboundDatagram::new(
-            socket,
-            destination,
-            self.dns_client.clone(),
-            self.bind_addr,
-        )))
+        let mut dgram =
+            SimpleOutboundDatagram::new(socket, destination, self.dns_client.clone(), bind_addr);
+        dgram.set_dns_strategy(sess.dns_strategy);
+        Ok(Box::new(dgram))
     }
 }
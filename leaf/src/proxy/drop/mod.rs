@@ -1,3 +1,5 @@
+use std::io;
+
 pub mod tcp;
 pub mod udp;
 
@@ -5,3 +7,13 @@ pub use tcp::Handler as TcpHandler;
 pub use udp::Handler as UdpHandler;
 
 pub static NAME: &str = "drop";
+
+// A connection reset error makes the dispatcher reset the inbound
+// connection.
+fn dropped(reset: bool) -> io::Error {
+    if reset {
+        io::Error::new(io::ErrorKind::ConnectionReset, "dropped with reset")
+    } else {
+        io::Error::new(io::ErrorKind::Other, "dropped")
+    }
+}
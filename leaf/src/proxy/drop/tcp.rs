@@ -7,7 +7,10 @@ use crate::{
     session::Session,
 };
 
-pub struct Handler {}
+pub struct Handler {
+    /// Resets the inbound connection rather than closing it.
+    pub reset: bool,
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
@@ -24,6 +27,6 @@ impl TcpOutboundHandler for Handler {
         _sess: &'a Session,
         _stream: Option<Box<dyn ProxyStream>>,
     ) -> io::Result<Box<dyn ProxyStream>> {
-        Err(io::Error::new(io::ErrorKind::Other, "dropped"))
+        Err(super::dropped(self.reset))
     }
 }
@@ -9,7 +9,8 @@ use crate::{
     session::Session,
 };
 
-pub struct Handler {}
+/// Datagrams are always dropped silently, there's no reset for UDP.
+pub struct Handler;
 
 #[async_trait]
 impl UdpOutboundHandler for Handler {
@@ -30,6 +31,6 @@ impl UdpOutboundHandler for Handler {
         _sess: &'a Session,
         _transport: Option<OutboundTransport>,
     ) -> io::Result<Box<dyn OutboundDatagram>> {
-        Err(io::Error::new(io::ErrorKind::Other, "dropped"))
+        Err(super::dropped(false))
     }
 }
@@ -1,3 +1,5 @@
+use futures::future::{self, AbortHandle, BoxFuture, FutureExt};
+
 pub mod tcp;
 pub mod udp;
 
@@ -5,3 +7,59 @@ pub use tcp::Handler as TcpHandler;
 pub use udp::Handler as UdpHandler;
 
 pub static NAME: &str = "failover";
+
+// Health check results of this or greater are failures.
+const UNHEALTHY: u128 = u128::MAX - 3;
+
+/// Returns the initial schedule, actors ordered by priority, config order is
+/// kept within a tier.
+fn initial_schedule(num_actors: usize, priorities: &[u32]) -> Vec<usize> {
+    let mut schedule: Vec<usize> = (0..num_actors).collect();
+    schedule.sort_by_key(|i| priorities.get(*i).cloned().unwrap_or(0));
+    schedule
+}
+
+/// Wraps the health check task so it can be aborted, whether it has been
+/// spawned or not.
+fn abortable(task: BoxFuture<'static, ()>) -> (BoxFuture<'static, ()>, AbortHandle) {
+    let (task, abort_handle) = future::abortable(task);
+    (Box::pin(task.map(|_| ())), abort_handle)
+}
+
+/// Sort key for a health check result of an actor, healthy actors come first,
+/// then by priority, RTT only breaks ties within a tier.
+fn schedule_key(priorities: &[u32], idx: usize, rtt: u128) -> (bool, u32, u128) {
+    (
+        rtt >= UNHEALTHY,
+        priorities.get(idx).cloned().unwrap_or(0),
+        rtt,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_by_priority() {
+        let priorities = vec![2, 1, 1, 2];
+        assert_eq!(initial_schedule(4, &priorities), vec![1, 2, 0, 3]);
+        assert_eq!(initial_schedule(3, &[]), vec![0, 1, 2]);
+
+        // (index, rtt)
+        let mut measures = vec![(0, 10), (1, 300), (2, 200), (3, 20)];
+        measures.sort_by_key(|m| schedule_key(&priorities, m.0, m.1));
+        assert_eq!(
+            measures.iter().map(|m| m.0).collect::<Vec<_>>(),
+            vec![2, 1, 0, 3]
+        );
+
+        // tier-1 actors are down
+        let mut measures = vec![(0, 10), (1, u128::MAX), (2, u128::MAX - 1), (3, 20)];
+        measures.sort_by_key(|m| schedule_key(&priorities, m.0, m.1));
+        assert_eq!(
+            measures.iter().map(|m| m.0).collect::<Vec<_>>(),
+            vec![0, 3, 2, 1]
+        );
+    }
+}
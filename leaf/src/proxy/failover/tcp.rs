@@ -1,7 +1,7 @@
 use std::{io, sync::Arc, time};
 
 use async_trait::async_trait;
-use futures::future::BoxFuture;
+use futures::future::{AbortHandle, BoxFuture};
 use log::*;
 use lru_time_cache::LruCache;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -9,6 +9,7 @@ use tokio::sync::Mutex as TokioMutex;
 use tokio::time::timeout;
 
 use crate::{
+    app::outbound::health::{Health, HealthStatus},
     proxy::{OutboundConnect, OutboundHandler, ProxyStream, TcpOutboundHandler},
     session::{Session, SocksAddr},
 };
@@ -18,7 +19,9 @@ pub struct Handler {
     pub fail_timeout: u32,
     pub schedule: Arc<TokioMutex<Vec<usize>>>,
     pub health_check_task: TokioMutex<Option<BoxFuture<'static, ()>>>,
+    pub abort_handle: Option<AbortHandle>,
     pub cache: Option<Arc<TokioMutex<LruCache<String, usize>>>>,
+    pub health_status: Arc<HealthStatus>,
 }
 
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
@@ -34,16 +37,17 @@ impl Handler {
         fallback_cache: bool,
         cache_size: usize,
         cache_timeout: u64, // in minutes
+        priorities: Vec<u32>,
+        health_status: Arc<HealthStatus>,
     ) -> Self {
-        let mut schedule = Vec::new();
-        for i in 0..actors.len() {
-            schedule.push(i);
-        }
+        let schedule = super::initial_schedule(actors.len(), &priorities);
+        health_status.set_preferred(schedule[0]);
         let schedule = Arc::new(TokioMutex::new(schedule));
 
+        let health_status2 = health_status.clone();
         let schedule2 = schedule.clone();
         let actors2 = actors.clone();
-        let task = if health_check {
+        let (task, abort_handle) = if health_check {
             let health_check_task: BoxFuture<'static, ()> = Box::pin(async move {
                 loop {
                     let mut measures: Vec<Measure> = Vec::new();
@@ -84,8 +88,18 @@ impl Handler {
                         }
                     }
 
-                    measures.sort_by(|a, b| a.1.cmp(&b.1));
+                    for m in measures.iter() {
+                        let health = if m.1 < super::UNHEALTHY {
+                            Health::Up(time::Duration::from_millis(m.1 as u64))
+                        } else {
+                            Health::Down
+                        };
+                        health_status2.set_health(m.0, health);
+                    }
+
+                    measures.sort_by_key(|m| super::schedule_key(&priorities, m.0, m.1));
                     trace!("sorted tcp health check results:\n{:#?}", measures);
+                    health_status2.set_preferred(measures[0].0);
 
                     let priorities: Vec<String> = measures
                         .iter()
@@ -122,9 +136,10 @@ impl Handler {
                     tokio::time::delay_for(time::Duration::from_secs(check_interval as u64)).await;
                 }
             });
-            Some(health_check_task)
+            let (task, abort_handle) = super::abortable(health_check_task);
+            (Some(task), Some(abort_handle))
         } else {
-            None
+            (None, None)
         };
 
         let cache = if fallback_cache {
@@ -143,9 +158,22 @@ impl Handler {
             fail_timeout,
             schedule,
             health_check_task: TokioMutex::new(task),
+            abort_handle,
             cache,
+            health_status,
         }
     }
+
+    /// Returns the handle to stop the health check task, `None` if health
+    /// check is disabled.
+    pub fn abort_handle(&self) -> Option<AbortHandle> {
+        self.abort_handle.clone()
+    }
+
+    /// Returns the results of the last TCP health check of the actors.
+    pub fn health_status(&self) -> Arc<HealthStatus> {
+        self.health_status.clone()
+    }
 }
 
 #[async_trait]
@@ -7,7 +7,7 @@ use std::{
 };
 
 use async_trait::async_trait;
-use futures::future::BoxFuture;
+use futures::future::{AbortHandle, BoxFuture};
 use log::*;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use tokio::sync::Mutex as TokioMutex;
@@ -30,6 +30,7 @@ pub struct Handler {
     pub fail_timeout: u32,
     pub schedule: Arc<TokioMutex<Vec<usize>>>,
     pub health_check_task: TokioMutex<Option<BoxFuture<'static, ()>>>,
+    pub abort_handle: Option<AbortHandle>,
 }
 
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
@@ -42,16 +43,14 @@ impl Handler {
         health_check: bool,
         check_interval: u32,
         failover: bool,
+        priorities: Vec<u32>,
     ) -> Self {
-        let mut schedule = Vec::new();
-        for i in 0..actors.len() {
-            schedule.push(i);
-        }
+        let schedule = super::initial_schedule(actors.len(), &priorities);
         let schedule = Arc::new(TokioMutex::new(schedule));
 
         let schedule2 = schedule.clone();
         let actors2 = actors.clone();
-        let task = if health_check {
+        let (task, abort_handle) = if health_check {
             let health_check_task: BoxFuture<'static, ()> = Box::pin(async move {
                 loop {
                     let mut measures: Vec<Measure> = Vec::new();
@@ -125,7 +124,7 @@ impl Handler {
                         }
                     }
 
-                    measures.sort_by(|a, b| a.1.cmp(&b.1));
+                    measures.sort_by_key(|m| super::schedule_key(&priorities, m.0, m.1));
                     trace!("sorted udp health check results:\n{:#?}", measures);
 
                     let priorities: Vec<String> = measures
@@ -162,9 +161,10 @@ impl Handler {
                     tokio::time::delay_for(time::Duration::from_secs(check_interval as u64)).await;
                 }
             });
-            Some(health_check_task)
+            let (task, abort_handle) = super::abortable(health_check_task);
+            (Some(task), Some(abort_handle))
         } else {
-            None
+            (None, None)
         };
 
         Handler {
@@ -172,8 +172,15 @@ impl Handler {
             fail_timeout,
             schedule,
             health_check_task: TokioMutex::new(task),
+            abort_handle,
         }
     }
+
+    /// Returns the handle to stop the health check task, `None` if health
+    /// check is disabled.
+    pub fn abort_handle(&self) -> Option<AbortHandle> {
+        self.abort_handle.clone()
+    }
 }
 
 #[async_trait]
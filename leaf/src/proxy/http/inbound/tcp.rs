@@ -1,57 +1,162 @@
 use std::convert::TryFrom;
 use std::io;
-use std::{net::IpAddr, pin::Pin, task::Poll};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
-use anyhow::Result;
 use async_trait::async_trait;
-use futures::future::{self, Future};
-use hyper::{server::conn::Http, service::Service, Body, Request, Response};
+use bytes::{Buf, BytesMut};
+use futures::ready;
 use log::*;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use crate::{
-    proxy::{InboundTransport, SimpleProxyStream, TcpInboundHandler},
+    config::HttpInboundSettings,
+    proxy::{InboundTransport, ProxyStream, TcpInboundHandler},
     session::SocksAddr,
 };
 
-struct ProxyService {
-    uri: String,
+// Maximum size of a request head.
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+const MAX_HEADERS: usize = 64;
+
+struct RequestHead {
+    method: String,
+    target: String,
+    version: u8,
+    headers: Vec<(String, Vec<u8>)>,
 }
 
-impl ProxyService {
-    pub fn new() -> Self {
-        ProxyService {
-            uri: "".to_string(),
+impl RequestHead {
+    fn header(&self, name: &str) -> Option<&[u8]> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| &v[..])
+    }
+}
+
+// Parses a request head at the beginning of the buffer, returns the head and
+// its length if complete.
+fn parse_head(buf: &[u8]) -> io::Result<Option<(RequestHead, usize)>> {
+    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut req = httparse::Request::new(&mut headers);
+    match req.parse(buf) {
+        Ok(httparse::Status::Complete(n)) => Ok(Some((
+            RequestHead {
+                method: req.method.unwrap_or_default().to_string(),
+                target: req.path.unwrap_or_default().to_string(),
+                version: req.version.unwrap_or(1),
+                headers: req
+                    .headers
+                    .iter()
+                    .map(|h| (h.name.to_string(), h.value.to_vec()))
+                    .collect(),
+            },
+            n,
+        ))),
+        Ok(httparse::Status::Partial) => {
+            if buf.len() >= MAX_HEAD_SIZE {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "request head too large",
+                ))
+            } else {
+                Ok(None)
+            }
         }
+        Err(e) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid request: {}", e),
+        )),
     }
+}
 
-    pub fn get_uri(&self) -> &String {
-        &self.uri
+// Parses an authority of the form `host:port` or `[v6]:port`, with the
+// default port if missing.
+fn parse_authority(authority: &str, default_port: Option<u16>) -> Option<SocksAddr> {
+    let (host, port) = if authority.starts_with('[') {
+        let end = authority.find(']')?;
+        let port = match &authority[end + 1..] {
+            "" => None,
+            p if p.starts_with(':') => Some(p[1..].parse::<u16>().ok()?),
+            _ => return None,
+        };
+        (&authority[1..end], port)
+    } else {
+        let mut parts = authority.rsplitn(2, ':');
+        let last = parts.next()?;
+        match parts.next() {
+            Some(host) => (host, Some(last.parse::<u16>().ok()?)),
+            None => (last, None),
+        }
+    };
+    let port = port.or(default_port)?;
+    if host.is_empty() {
+        return None;
     }
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Some(SocksAddr::from((ip, port)));
+    }
+    SocksAddr::try_from(format!("{}:{}", host, port)).ok()
 }
 
-#[allow(clippy::type_complexity)]
-impl Service<Request<Body>> for ProxyService {
-    type Error = Box<dyn std::error::Error + Send + Sync>;
-    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
-    type Response = Response<Body>;
+// Splits an absolute `http://` URI into the authority and the path in origin
+// form.
+fn split_absolute_uri(uri: &str) -> Option<(&str, &str)> {
+    if uri.len() < 7 || !uri[..7].eq_ignore_ascii_case("http://") {
+        return None;
+    }
+    let rest = &uri[7..];
+    match rest.find(|c| c == '/' || c == '?') {
+        Some(i) if rest.as_bytes()[i] == b'/' => Some((&rest[..i], &rest[i..])),
+        // The path of `http://host?q` is `/?q`, not expected in practice.
+        Some(_) => None,
+        None => Some((rest, "/")),
+    }
+}
 
-    fn call(&mut self, req: Request<Body>) -> Self::Future {
-        self.uri = req.uri().to_string();
-        Box::pin(future::ready(Ok(Response::builder()
-            .status(200)
-            .body(hyper::Body::empty())
-            .unwrap())))
+pub struct Handler {
+    // Expected `Proxy-Authorization` value if authentication is required.
+    credentials: Option<String>,
+}
+
+impl Handler {
+    pub fn new(settings: &HttpInboundSettings) -> Self {
+        let credentials = if settings.username.is_empty() {
+            None
+        } else {
+            Some(format!(
+                "Basic {}",
+                base64::encode(format!("{}:{}", settings.username, settings.password))
+            ))
+        };
+        Handler { credentials }
     }
 
-    fn poll_ready(
-        &mut self,
-        _cx: &mut std::task::Context<'_>,
-    ) -> Poll<std::result::Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
+    fn authorized(&self, head: &RequestHead) -> bool {
+        match &self.credentials {
+            Some(credentials) => head
+                .header("Proxy-Authorization")
+                .map(|v| v == credentials.as_bytes())
+                .unwrap_or(false),
+            None => true,
+        }
     }
 }
 
-pub struct Handler;
+// Replies with an empty response.
+async fn reply(stream: &mut dyn ProxyStream, status: &str, headers: &str) -> io::Result<()> {
+    stream
+        .write_all(
+            format!(
+                "HTTP/1.1 {}\r\n{}Content-Length: 0\r\n\r\n",
+                status, headers
+            )
+            .as_bytes(),
+        )
+        .await
+}
 
 #[async_trait]
 impl TcpInboundHandler for Handler {
@@ -59,52 +164,362 @@ impl TcpInboundHandler for Handler {
         &'a self,
         transport: InboundTransport,
     ) -> std::io::Result<InboundTransport> {
-        if let InboundTransport::Stream(stream, mut sess) = transport {
-            let http = Http::new();
-            let proxy_service = ProxyService::new();
-            let conn = http
-                .serve_connection(stream, proxy_service)
-                .without_shutdown();
-            let parts = match conn.await {
-                Ok(v) => v,
-                Err(err) => {
-                    debug!("accept conn failed: {}", err);
-                    return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+        let (mut stream, mut sess) = match transport {
+            InboundTransport::Stream(stream, sess) => (stream, sess),
+            _ => return Err(io::Error::new(io::ErrorKind::Other, "invalid transport")),
+        };
+
+        let mut buf = BytesMut::new();
+        let (head, head_len) = loop {
+            if let Some(v) = parse_head(&buf)? {
+                break v;
+            }
+            if stream.read_buf(&mut buf).await? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "incomplete request",
+                ));
+            }
+        };
+
+        if !self.authorized(&head) {
+            debug!("unauthorized http proxy request from {}", &sess.source);
+            reply(
+                &mut stream,
+                "407 Proxy Authentication Required",
+                "Proxy-Authenticate: Basic realm=\"leaf\"\r\n",
+            )
+            .await?;
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "proxy authentication required",
+            ));
+        }
+
+        if head.method.eq_ignore_ascii_case("CONNECT") {
+            let destination = match parse_authority(&head.target, None) {
+                Some(v) => v,
+                None => {
+                    reply(&mut stream, "400 Bad Request", "").await?;
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid target {}", &head.target),
+                    ));
                 }
             };
+            stream
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                .await?;
+            // The client may send data right after the request without
+            // waiting for the reply.
+            buf.advance(head_len);
+            sess.destination = destination;
+            return Ok(InboundTransport::Stream(
+                Box::new(ForwardStream {
+                    inner: stream,
+                    authority: String::new(),
+                    input: buf,
+                    output: BytesMut::new(),
+                    body_remaining: 0,
+                    passthrough: true,
+                    eof: false,
+                }),
+                sess,
+            ));
+        }
 
-            let uri = parts.service.get_uri();
-            let host_port: Vec<&str> = uri.split(':').collect();
-            if host_port.len() != 2 {
-                debug!("invalid target {:?}", uri);
-                return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+        let authority = match split_absolute_uri(&head.target)
+            .and_then(|(authority, _)| Some((authority, parse_authority(authority, Some(80))?)))
+        {
+            Some((authority, destination)) => {
+                sess.destination = destination;
+                authority.to_string()
             }
+            None => {
+                reply(&mut stream, "400 Bad Request", "").await?;
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid target {}", &head.target),
+                ));
+            }
+        };
 
-            let destination = if let Ok(port) = host_port[1].parse::<u16>() {
-                if let Ok(ip) = host_port[0].parse::<IpAddr>() {
-                    SocksAddr::from((ip, port))
-                } else {
-                    match SocksAddr::try_from((host_port[0], port)) {
-                        Ok(v) => v,
-                        Err(err) => {
-                            debug!("invalid target {:?}: {}", uri, err);
-                            return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
-                        }
-                    }
+        // The request is left in the buffer and rewritten by the stream, as
+        // well as the following ones on the same connection.
+        Ok(InboundTransport::Stream(
+            Box::new(ForwardStream {
+                inner: stream,
+                authority,
+                input: buf,
+                output: BytesMut::new(),
+                body_remaining: 0,
+                passthrough: false,
+                eof: false,
+            }),
+            sess,
+        ))
+    }
+}
+
+/// A client stream of forwarded plain HTTP requests, the requests read from it
+/// are rewritten to origin form without the proxy headers. Requests on a
+/// kept-alive connection are forwarded as long as they go to the same
+/// authority, the stream ends at the first one for another authority, and
+/// clients retry it on a new connection.
+struct ForwardStream {
+    inner: Box<dyn ProxyStream>,
+    authority: String,
+    // Bytes read from the client but not processed yet.
+    input: BytesMut,
+    // Processed bytes to be returned.
+    output: BytesMut,
+    body_remaining: u64,
+    // Forwards anything as is, after a CONNECT or a request with a chunked
+    // body.
+    passthrough: bool,
+    eof: bool,
+}
+
+impl ForwardStream {
+    // Processes the buffered input, returns false if more input is needed.
+    fn process(&mut self) -> io::Result<bool> {
+        if self.passthrough {
+            if self.input.is_empty() {
+                return Ok(false);
+            }
+            let input = self.input.split();
+            self.output.extend_from_slice(&input);
+            return Ok(true);
+        }
+        if self.body_remaining > 0 {
+            if self.input.is_empty() {
+                return Ok(false);
+            }
+            let n = std::cmp::min(self.body_remaining, self.input.len() as u64) as usize;
+            let body = self.input.split_to(n);
+            self.output.extend_from_slice(&body);
+            self.body_remaining -= n as u64;
+            return Ok(true);
+        }
+        let (head, n) = match parse_head(&self.input)? {
+            Some(v) => v,
+            None => return Ok(false),
+        };
+        let (authority, path) = match split_absolute_uri(&head.target) {
+            Some(v) => v,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid target {}", &head.target),
+                ))
+            }
+        };
+        if !authority.eq_ignore_ascii_case(&self.authority) {
+            debug!(
+                "http request to {} on connection to {}, closing",
+                authority, &self.authority
+            );
+            self.eof = true;
+            return Ok(true);
+        }
+
+        self.output.extend_from_slice(
+            format!("{} {} HTTP/1.{}\r\n", &head.method, path, head.version).as_bytes(),
+        );
+        if head.header("Host").is_none() {
+            self.output
+                .extend_from_slice(format!("Host: {}\r\n", authority).as_bytes());
+        }
+        let has_connection = head.header("Connection").is_some();
+        for (name, value) in head.headers.iter() {
+            let name = if name.eq_ignore_ascii_case("Proxy-Authorization") {
+                continue;
+            } else if name.eq_ignore_ascii_case("Proxy-Connection") {
+                // Sent by some HTTP/1.0 clients for keep-alive.
+                if has_connection {
+                    continue;
                 }
+                "Connection"
             } else {
-                debug!("invalid target {:?}", uri);
-                return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+                name.as_str()
             };
+            self.output.extend_from_slice(name.as_bytes());
+            self.output.extend_from_slice(b": ");
+            self.output.extend_from_slice(value);
+            self.output.extend_from_slice(b"\r\n");
+        }
+        self.output.extend_from_slice(b"\r\n");
 
-            sess.destination = destination;
+        if let Some(te) = head.header("Transfer-Encoding") {
+            if !te.eq_ignore_ascii_case(b"identity") {
+                // Following requests can't be found without decoding the
+                // chunks, they're forwarded as is.
+                self.passthrough = true;
+            }
+        } else if let Some(len) = head.header("Content-Length") {
+            self.body_remaining = std::str::from_utf8(len)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid content length")
+                })?;
+        }
+        self.input.advance(n);
+        Ok(true)
+    }
+}
 
-            Ok(InboundTransport::Stream(
-                Box::new(SimpleProxyStream(parts.io)),
-                sess,
-            ))
-        } else {
-            Err(io::Error::new(io::ErrorKind::Other, "invalid transport"))
+impl ProxyStream for ForwardStream {}
+
+impl AsyncRead for ForwardStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let me = &mut *self;
+        loop {
+            if !me.output.is_empty() {
+                let n = std::cmp::min(buf.len(), me.output.len());
+                buf[..n].copy_from_slice(&me.output[..n]);
+                me.output.advance(n);
+                return Poll::Ready(Ok(n));
+            }
+            if me.eof {
+                return Poll::Ready(Ok(0));
+            }
+            if me.process()? {
+                continue;
+            }
+            let mut tmp = [0u8; 4 * 1024];
+            let n = ready!(Pin::new(&mut me.inner).poll_read(cx, &mut tmp))?;
+            if n == 0 {
+                me.eof = true;
+                continue;
+            }
+            me.input.extend_from_slice(&tmp[..n]);
         }
     }
 }
+
+impl AsyncWrite for ForwardStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::{proxy::SimpleProxyStream, session::Session};
+
+    async fn handle(
+        handler: &Handler,
+        request: &'static [u8],
+    ) -> (io::Result<InboundTransport>, TcpStream) {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        client.write_all(request).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let transport =
+            InboundTransport::Stream(Box::new(SimpleProxyStream(stream)), Session::default());
+        (handler.handle_tcp(transport).await, client)
+    }
+
+    #[test]
+    fn test_forward_keep_alive() {
+        let mut settings = HttpInboundSettings::new();
+        settings.username = "u".to_string();
+        settings.password = "p".to_string();
+        let handler = Handler::new(&settings);
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let (res, _client) = handle(
+                &handler,
+                b"GET http://example.com/a HTTP/1.1\r\nHost: example.com\r\nProxy-Connection: keep-alive\r\nProxy-Authorization: Basic dTpw\r\n\r\n\
+                  POST http://example.com/b HTTP/1.1\r\nHost: example.com\r\nProxy-Authorization: Basic dTpw\r\nContent-Length: 3\r\n\r\nabc\
+                  GET http://other.com/ HTTP/1.1\r\nHost: other.com\r\n\r\n",
+            )
+            .await;
+            let (mut stream, sess) = match res.unwrap() {
+                InboundTransport::Stream(stream, sess) => (stream, sess),
+                _ => panic!("unexpected transport"),
+            };
+            assert_eq!(sess.destination.to_string(), "example.com:80");
+            // Ends at the request to another authority.
+            let mut buf = Vec::new();
+            stream.read_to_end(&mut buf).await.unwrap();
+            assert_eq!(
+                String::from_utf8(buf).unwrap(),
+                "GET /a HTTP/1.1\r\nHost: example.com\r\nConnection: keep-alive\r\n\r\n\
+                 POST /b HTTP/1.1\r\nHost: example.com\r\nContent-Length: 3\r\n\r\nabc"
+            );
+        });
+    }
+
+    #[test]
+    fn test_connect() {
+        let handler = Handler::new(&HttpInboundSettings::new());
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let (res, mut client) =
+                handle(&handler, b"CONNECT [::1]:443 HTTP/1.1\r\n\r\nearly").await;
+            let (mut stream, sess) = match res.unwrap() {
+                InboundTransport::Stream(stream, sess) => (stream, sess),
+                _ => panic!("unexpected transport"),
+            };
+            assert_eq!(sess.destination.to_string(), "[::1]:443");
+            let mut buf = [0u8; 5];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"early");
+            let mut buf = [0u8; 12];
+            client.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"HTTP/1.1 200");
+        });
+    }
+
+    #[test]
+    fn test_unauthorized() {
+        let mut settings = HttpInboundSettings::new();
+        settings.username = "u".to_string();
+        settings.password = "p".to_string();
+        let handler = Handler::new(&settings);
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let (res, mut client) =
+                handle(&handler, b"CONNECT example.com:443 HTTP/1.1\r\n\r\n").await;
+            assert!(res.is_err());
+            let mut buf = [0u8; 12];
+            client.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"HTTP/1.1 407");
+        });
+    }
+}
@@ -0,0 +1,106 @@
+use std::io;
+
+use async_trait::async_trait;
+
+use crate::{
+    common::stream::SniffingStream,
+    config::MixedInboundSettings,
+    proxy::{http, socks, InboundTransport, SimpleProxyStream, TcpInboundHandler},
+};
+
+/// Serves both SOCKS5 and HTTP proxy clients on the same port, by the first
+/// byte of the connection, the version of a SOCKS5 greeting is 0x05 while an
+/// HTTP request starts with the method.
+pub struct Handler {
+    socks: socks::inbound::TcpHandler,
+    http: http::inbound::TcpHandler,
+}
+
+impl Handler {
+    pub fn new(settings: &MixedInboundSettings) -> Self {
+        Handler {
+            socks: socks::inbound::TcpHandler::new(settings.get_socks()),
+            http: http::inbound::TcpHandler::new(settings.get_http()),
+        }
+    }
+}
+
+#[async_trait]
+impl TcpInboundHandler for Handler {
+    async fn handle_tcp<'a>(
+        &'a self,
+        transport: InboundTransport,
+    ) -> std::io::Result<InboundTransport> {
+        let (stream, sess) = match transport {
+            InboundTransport::Stream(stream, sess) => (stream, sess),
+            _ => return Err(io::Error::new(io::ErrorKind::Other, "invalid transport")),
+        };
+        // The peeked byte is kept in the stream for the handlers.
+        let mut stream = SniffingStream::new(stream);
+        let first = stream
+            .peek()
+            .await?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed"))?;
+        let transport = InboundTransport::Stream(Box::new(SimpleProxyStream(stream)), sess);
+        if first == 0x05 {
+            self.socks.handle_tcp(transport).await
+        } else {
+            self.http.handle_tcp(transport).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::session::Session;
+
+    async fn handle(request: &'static [u8]) -> (InboundTransport, TcpStream) {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        client.write_all(request).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let transport =
+            InboundTransport::Stream(Box::new(SimpleProxyStream(stream)), Session::default());
+        let handler = Handler::new(&MixedInboundSettings::new());
+        (handler.handle_tcp(transport).await.unwrap(), client)
+    }
+
+    fn destination(transport: &InboundTransport) -> String {
+        match transport {
+            InboundTransport::Stream(_, sess) => sess.destination.to_string(),
+            _ => panic!("unexpected transport"),
+        }
+    }
+
+    #[test]
+    fn test_detect_protocol() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            // greeting without authentication, connect 1.2.3.4:443
+            let (transport, mut client) = handle(&[
+                0x05, 0x01, 0x00, 0x05, 0x01, 0x00, 0x01, 1, 2, 3, 4, 0x01, 0xbb,
+            ])
+            .await;
+            assert_eq!(destination(&transport), "1.2.3.4:443");
+            let mut buf = [0u8; 2];
+            client.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, [0x05, 0x00]);
+
+            let (transport, mut client) = handle(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n").await;
+            assert_eq!(destination(&transport), "example.com:443");
+            let mut buf = [0u8; 12];
+            client.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"HTTP/1.1 200");
+        });
+    }
+}
@@ -1,5 +1,9 @@
 use std::sync::Arc;
-use std::{io, net::SocketAddr};
+use std::time::Duration;
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
 
 use async_trait::async_trait;
 use futures::future::select_ok;
@@ -10,8 +14,8 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::{TcpStream, UdpSocket};
 
 use crate::{
-    app::dns_client::DnsClient,
-    common::resolver::Resolver,
+    app::dns_client::{DnsClient, DnsStrategy},
+    common::{resolver::Resolver, sys},
     option,
     session::{Session, SocksAddr},
 };
@@ -23,6 +27,10 @@ pub mod stream;
 
 #[cfg(feature = "inbound-http")]
 pub mod http;
+#[cfg(feature = "inbound-mixed")]
+pub mod mixed;
+#[cfg(all(feature = "inbound-tproxy", target_os = "linux"))]
+pub mod tproxy;
 #[cfg(all(
     feature = "inbound-tun",
     any(target_os = "ios", target_os = "macos", target_os = "linux")
@@ -41,6 +49,8 @@ pub mod redirect;
 pub mod shadowsocks;
 #[cfg(any(feature = "inbound-socks", feature = "outbound-socks"))]
 pub mod socks;
+#[cfg(feature = "outbound-ssh")]
+pub mod ssh;
 #[cfg(feature = "outbound-tls")]
 pub mod tls;
 #[cfg(any(feature = "inbound-trojan", feature = "outbound-trojan"))]
@@ -49,9 +59,13 @@ pub mod trojan;
 pub mod vless;
 #[cfg(feature = "outbound-vmess")]
 pub mod vmess;
+#[cfg(feature = "outbound-wireguard")]
+pub mod wireguard;
 #[cfg(any(feature = "inbound-ws", feature = "outbound-ws"))]
 pub mod ws;
 
+#[cfg(feature = "outbound-balance")]
+pub mod balance;
 #[cfg(any(feature = "inbound-chain", feature = "outbound-chain"))]
 pub mod chain;
 #[cfg(feature = "outbound-failover")]
@@ -60,12 +74,24 @@ pub mod failover;
 pub mod random;
 #[cfg(feature = "outbound-retry")]
 pub mod retry;
+#[cfg(feature = "outbound-select")]
+pub mod select;
+#[cfg(any(feature = "outbound-random", feature = "outbound-balance"))]
+pub mod sticky;
 #[cfg(feature = "outbound-tryall")]
 pub mod tryall;
+#[cfg(feature = "outbound-urltest")]
+pub mod urltest;
 
 #[cfg(feature = "outbound-stat")]
 pub mod stat;
 
+#[cfg(feature = "outbound-pt")]
+pub mod pt;
+
+#[cfg(any(feature = "outbound-shadowsocks", feature = "outbound-trojan"))]
+pub mod uot;
+
 pub use datagram::{
     SimpleInboundDatagram, SimpleInboundDatagramRecvHalf, SimpleInboundDatagramSendHalf,
     SimpleOutboundDatagram, SimpleOutboundDatagramRecvHalf, SimpleOutboundDatagramSendHalf,
@@ -99,22 +125,84 @@ pub trait HandlerTyped {
 }
 
 // New UDP socket.
-async fn create_udp_socket(bind_addr: &SocketAddr) -> io::Result<UdpSocket> {
-    UdpSocket::bind(bind_addr).await
+async fn create_udp_socket(
+    bind_addr: &SocketAddr,
+    interface: Option<&str>,
+) -> io::Result<UdpSocket> {
+    let socket = UdpSocket::bind(bind_addr).await?;
+    sys::mark_outbound_socket(&socket)?;
+    sys::protect_outbound_socket(&socket)?;
+    if let Some(interface) = interface {
+        sys::bind_to_interface(&socket, interface, bind_addr.is_ipv6())?;
+    }
+    Ok(socket)
+}
+
+/// Socket options applied to outgoing TCP connections.
+#[derive(Clone, Debug)]
+pub struct TcpOptions {
+    pub nodelay: bool,
+    pub keepalive: Option<Duration>,
+    /// Name of the network interface the connections are bound to.
+    pub interface: Option<String>,
+}
+
+impl Default for TcpOptions {
+    fn default() -> Self {
+        TcpOptions {
+            nodelay: true,
+            keepalive: None,
+            interface: None,
+        }
+    }
+}
+
+// Returns the address to bind to for dialing the address, an unspecified
+// bind address is switched to the family of the dial address.
+fn dial_bind_addr(dial_addr: &SocketAddr, bind_addr: &SocketAddr) -> io::Result<SocketAddr> {
+    if dial_addr.is_ipv4() == bind_addr.is_ipv4() {
+        return Ok(*bind_addr);
+    }
+    if !bind_addr.ip().is_unspecified() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("can not dial {} from {}", dial_addr, bind_addr),
+        ));
+    }
+    let ip = if dial_addr.is_ipv6() {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    };
+    Ok(SocketAddr::new(ip, bind_addr.port()))
 }
 
 // A single TCP dial.
 async fn tcp_dial_task(
     dial_addr: SocketAddr,
     bind_addr: &SocketAddr,
+    options: &TcpOptions,
 ) -> io::Result<(Box<dyn ProxyStream>, SocketAddr)> {
-    let socket = Socket::new(Domain::ipv4(), Type::stream(), None)?;
-    socket.bind(&bind_addr.clone().into())?;
+    let bind_addr = dial_bind_addr(&dial_addr, bind_addr)?;
+    let domain = if dial_addr.is_ipv6() {
+        Domain::ipv6()
+    } else {
+        Domain::ipv4()
+    };
+    let socket = Socket::new(domain, Type::stream(), None)?;
+    sys::mark_outbound_socket(&socket)?;
+    sys::protect_outbound_socket(&socket)?;
+    socket.set_nodelay(options.nodelay)?;
+    socket.set_keepalive(options.keepalive)?;
+    if let Some(interface) = &options.interface {
+        sys::bind_to_interface(&socket, interface, dial_addr.is_ipv6())?;
+    }
+    socket.bind(&bind_addr.into())?;
     trace!("dialing tcp {}", &dial_addr);
     match TcpStream::connect_std(socket.into_tcp_stream(), &dial_addr).await {
         Ok(stream) => {
             trace!("connected tcp {}", &dial_addr);
-            Ok((Box::new(SimpleProxyStream(stream)), dial_addr))
+            Ok((Box::new(stream), dial_addr))
         }
         Err(e) => Err(io::Error::new(
             io::ErrorKind::Other,
@@ -123,14 +211,17 @@ async fn tcp_dial_task(
     }
 }
 
-// Dials a TCP stream.
+// Dials a TCP stream, a domain address is resolved with the DNS strategy,
+// or the global one if `None`.
 async fn dial_tcp_stream(
     dns_client: Arc<DnsClient>,
     bind_addr: &SocketAddr,
     address: &str,
     port: &u16,
+    options: TcpOptions,
+    dns_strategy: Option<DnsStrategy>,
 ) -> io::Result<Box<dyn ProxyStream>> {
-    let mut resolver = Resolver::new(dns_client.clone(), bind_addr, address, port)
+    let mut resolver = Resolver::new(dns_client.clone(), bind_addr, address, port, dns_strategy)
         .map_err(|e| {
             io::Error::new(
                 io::ErrorKind::Other,
@@ -153,7 +244,7 @@ async fn dial_tcp_stream(
                     break; // break and execute tasks if there're any
                 }
             };
-            let t = tcp_dial_task(dial_addr, bind_addr);
+            let t = tcp_dial_task(dial_addr, bind_addr, &options);
             tasks.push(Box::pin(t));
         }
         if !tasks.is_empty() {
@@ -185,6 +276,11 @@ async fn dial_tcp_stream(
 /// An interface with the ability to dial TCP connections.
 #[async_trait]
 pub trait TcpConnector: Send + Sync + Unpin {
+    /// Returns the socket options for the connections it dials.
+    fn tcp_options(&self) -> TcpOptions {
+        TcpOptions::default()
+    }
+
     /// Dials a TCP connection.
     async fn dial_tcp_stream(
         &self,
@@ -193,21 +289,67 @@ pub trait TcpConnector: Send + Sync + Unpin {
         address: &str,
         port: &u16,
     ) -> io::Result<Box<dyn ProxyStream>> {
-        dial_tcp_stream(dns_client, bind_addr, address, port).await
+        dial_tcp_stream(
+            dns_client,
+            bind_addr,
+            address,
+            port,
+            self.tcp_options(),
+            None,
+        )
+        .await
+    }
+
+    /// Dials a TCP connection, resolving a domain address with the DNS
+    /// strategy rather than the global one.
+    async fn dial_tcp_stream_with_strategy(
+        &self,
+        dns_client: Arc<DnsClient>,
+        bind_addr: &SocketAddr,
+        address: &str,
+        port: &u16,
+        dns_strategy: Option<DnsStrategy>,
+    ) -> io::Result<Box<dyn ProxyStream>> {
+        dial_tcp_stream(
+            dns_client,
+            bind_addr,
+            address,
+            port,
+            self.tcp_options(),
+            dns_strategy,
+        )
+        .await
     }
 }
 
 /// An interface with the ability to create UDP sockets.
 #[async_trait]
 pub trait UdpConnector: Send + Sync + Unpin {
+    /// Returns the name of the network interface UDP sockets are bound to.
+    fn bind_interface(&self) -> Option<&str> {
+        None
+    }
+
     /// Creates a UDP socket.
     async fn create_udp_socket(&self, bind_addr: &SocketAddr) -> io::Result<UdpSocket> {
-        create_udp_socket(bind_addr).await
+        create_udp_socket(bind_addr, self.bind_interface()).await
     }
 }
 
 /// A reliable transport for both inbound and outbound handlers.
-pub trait ProxyStream: AsyncRead + AsyncWrite + Send + Sync + Unpin {}
+pub trait ProxyStream: AsyncRead + AsyncWrite + Send + Sync + Unpin {
+    /// Returns the TCP stream if the bytes go to it as they are, with no
+    /// transformation, e.g. so they can be spliced.
+    fn as_tcp_stream(&self) -> Option<&TcpStream> {
+        None
+    }
+}
+
+impl ProxyStream for TcpStream {
+    fn as_tcp_stream(&self) -> Option<&TcpStream> {
+        Some(self)
+    }
+}
 
 /// An outbound handler for both UDP and TCP outgoing connections.
 pub trait OutboundHandler:
@@ -215,6 +357,7 @@ pub trait OutboundHandler:
 {
     fn has_tcp(&self) -> bool;
     fn has_udp(&self) -> bool;
+    fn stats(&self) -> &Arc<outbound::Stats>;
 }
 
 pub enum OutboundConnect {
@@ -1,7 +1,11 @@
 use std::io::{self, Result};
+use std::sync::atomic::{AtomicU64, AtomicUsize};
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use futures::Future;
+use tokio::time::timeout;
 
 use crate::session::Session;
 
@@ -12,6 +16,17 @@ use super::{
 
 pub static NAME: &str = "handler";
 
+/// Traffic carried by an outbound handler, updated by the dispatcher on the
+/// handler picked for a session, the actors of an ensemble handler are not
+/// counted.
+#[derive(Default)]
+pub struct Stats {
+    pub tx_bytes: AtomicU64,
+    pub rx_bytes: AtomicU64,
+    /// TCP connections and UDP sessions being relayed.
+    pub active_conns: AtomicUsize,
+}
+
 /// An outbound handler groups a TCP outbound handler and a UDP outbound
 /// handler.
 pub struct Handler {
@@ -20,6 +35,10 @@ pub struct Handler {
     handler_type: ProxyHandlerType,
     tcp_handler: Option<Box<dyn TcpOutboundHandler>>,
     udp_handler: Option<Box<dyn UdpOutboundHandler>>,
+    stats: Arc<Stats>,
+    // Applies when the handler establishes the connection itself, i.e. no
+    // stream or transport is given.
+    connect_timeout: Option<Duration>,
 }
 
 impl Handler {
@@ -29,6 +48,7 @@ impl Handler {
         handler_type: ProxyHandlerType,
         tcp: Option<Box<dyn TcpOutboundHandler>>,
         udp: Option<Box<dyn UdpOutboundHandler>>,
+        connect_timeout: Option<Duration>,
     ) -> Arc<Self> {
         Arc::new(Handler {
             tag,
@@ -36,8 +56,25 @@ impl Handler {
             handler_type,
             tcp_handler: tcp,
             udp_handler: udp,
+            stats: Arc::new(Stats::default()),
+            connect_timeout,
         })
     }
+
+    async fn connect<T, F>(&self, f: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        match self.connect_timeout {
+            Some(t) => timeout(t, f).await.map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("connect timed out after {}s", t.as_secs()),
+                )
+            })?,
+            None => f.await,
+        }
+    }
 }
 
 impl OutboundHandler for Handler {
@@ -48,6 +85,10 @@ impl OutboundHandler for Handler {
     fn has_udp(&self) -> bool {
         self.udp_handler.is_some()
     }
+
+    fn stats(&self) -> &Arc<Stats> {
+        &self.stats
+    }
 }
 
 impl Tag for Handler {
@@ -92,7 +133,11 @@ impl TcpOutboundHandler for Handler {
         stream: Option<Box<dyn ProxyStream>>,
     ) -> Result<Box<dyn ProxyStream>> {
         if let Some(handler) = &self.tcp_handler {
-            handler.handle_tcp(sess, stream).await
+            if stream.is_none() {
+                self.connect(handler.handle_tcp(sess, stream)).await
+            } else {
+                handler.handle_tcp(sess, stream).await
+            }
         } else {
             Err(io::Error::new(io::ErrorKind::Other, "unimplemented"))
         }
@@ -127,9 +172,61 @@ impl UdpOutboundHandler for Handler {
         transport: Option<OutboundTransport>,
     ) -> Result<Box<dyn OutboundDatagram>> {
         if let Some(handler) = &self.udp_handler {
-            handler.handle_udp(sess, transport).await
+            if transport.is_none() {
+                self.connect(handler.handle_udp(sess, transport)).await
+            } else {
+                handler.handle_udp(sess, transport).await
+            }
         } else {
             Err(io::Error::new(io::ErrorKind::Other, "unimplemented"))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pending;
+
+    #[async_trait]
+    impl TcpOutboundHandler for Pending {
+        fn name(&self) -> &str {
+            "pending"
+        }
+
+        fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+            None
+        }
+
+        async fn handle_tcp<'a>(
+            &'a self,
+            _sess: &'a Session,
+            _stream: Option<Box<dyn ProxyStream>>,
+        ) -> Result<Box<dyn ProxyStream>> {
+            futures::future::pending().await
+        }
+    }
+
+    #[test]
+    fn test_connect_timeout() {
+        let handler = Handler::new(
+            "pending".to_string(),
+            colored::Color::White,
+            ProxyHandlerType::Endpoint,
+            Some(Box::new(Pending)),
+            None,
+            Some(Duration::from_millis(100)),
+        );
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        let err = rt
+            .block_on(handler.handle_tcp(&Session::default(), None))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+}
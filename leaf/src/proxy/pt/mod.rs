@@ -0,0 +1,5 @@
+pub mod tcp;
+
+pub use tcp::Handler as TcpHandler;
+
+pub static NAME: &str = "pt";
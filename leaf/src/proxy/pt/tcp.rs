@@ -0,0 +1,192 @@
+use std::{
+    io::{self, BufRead, BufReader},
+    net::SocketAddr,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::future::TryFutureExt;
+use log::*;
+use tokio::net::TcpStream;
+
+use crate::{
+    proxy::{OutboundConnect, ProxyStream, SimpleProxyStream, TcpOutboundHandler},
+    session::Session,
+};
+
+// Time to wait for the PT client to report its SOCKS listener.
+const LAUNCH_TIMEOUT: u64 = 10;
+
+/// Handler for a managed pluggable transport client, e.g. obfs4proxy. The
+/// client process is launched when the handler is created and killed when
+/// it's dropped, connections are handed to the client through its SOCKS5
+/// listener as specified in the Tor pluggable transport spec.
+pub struct Handler {
+    transport: String,
+    address: String,
+    port: u16,
+    options: String,
+    socks_addr: Arc<Mutex<Option<SocketAddr>>>,
+    child: Mutex<Child>,
+}
+
+impl Handler {
+    pub fn new(
+        path: &str,
+        args: &[String],
+        transport: String,
+        address: String,
+        port: u16,
+        options: String,
+        state_dir: &str,
+    ) -> io::Result<Self> {
+        let state_dir = if state_dir.is_empty() {
+            std::env::temp_dir()
+                .join("leaf-pt-state")
+                .to_string_lossy()
+                .to_string()
+        } else {
+            state_dir.to_string()
+        };
+        let mut child = Command::new(path)
+            .args(args)
+            .env("TOR_PT_MANAGED_TRANSPORT_VER", "1")
+            .env("TOR_PT_CLIENT_TRANSPORTS", &transport)
+            .env("TOR_PT_STATE_LOCATION", &state_dir)
+            // The client exits if we die without killing it.
+            .env("TOR_PT_EXIT_ON_STDIN_CLOSE", "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        debug!("launched pt client {} pid {}", path, child.id());
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "no stdout"))?;
+        let socks_addr = Arc::new(Mutex::new(None));
+        let socks_addr2 = socks_addr.clone();
+        let transport2 = transport.clone();
+        // Keeps reading until the client exits, so it never blocks on a full pipe.
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let line = match line {
+                    Ok(v) => v,
+                    Err(_) => break,
+                };
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                match parts.as_slice() {
+                    ["CMETHOD", name, "socks5", addr] if *name == transport2 => {
+                        match addr.parse::<SocketAddr>() {
+                            Ok(addr) => {
+                                debug!("pt client {} listens on {}", &transport2, &addr);
+                                *socks_addr2.lock().unwrap() = Some(addr);
+                            }
+                            Err(e) => warn!("invalid pt client address {}: {}", addr, e),
+                        }
+                    }
+                    ["CMETHOD-ERROR", ..] | ["ENV-ERROR", ..] | ["VERSION-ERROR", ..] => {
+                        warn!("pt client error: {}", &line);
+                    }
+                    _ => trace!("pt client: {}", &line),
+                }
+            }
+            debug!("pt client {} exited", &transport2);
+            *socks_addr2.lock().unwrap() = None;
+        });
+
+        Ok(Handler {
+            transport,
+            address,
+            port,
+            options,
+            socks_addr,
+            child: Mutex::new(child),
+        })
+    }
+
+    async fn wait_socks_addr(&self) -> io::Result<SocketAddr> {
+        for _ in 0..LAUNCH_TIMEOUT * 10 {
+            if let Some(addr) = *self.socks_addr.lock().unwrap() {
+                return Ok(addr);
+            }
+            tokio::time::delay_for(Duration::from_millis(100)).await;
+        }
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("pt client {} not ready", &self.transport),
+        ))
+    }
+
+    // Per-connection arguments are passed in the SOCKS5 username and
+    // password fields, a NUL byte is used as the password if it fits in
+    // the username.
+    fn auth(&self) -> Option<async_socks5::Auth> {
+        if self.options.is_empty() {
+            return None;
+        }
+        let options = self.options.as_bytes();
+        let (username, password) = if options.len() <= 255 {
+            (options, &b"\0"[..])
+        } else {
+            options.split_at(255)
+        };
+        Some(async_socks5::Auth::new(
+            String::from_utf8_lossy(username),
+            String::from_utf8_lossy(password),
+        ))
+    }
+}
+
+impl Drop for Handler {
+    fn drop(&mut self) {
+        if let Ok(mut child) = self.child.lock() {
+            debug!("killing pt client {} pid {}", &self.transport, child.id());
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[async_trait]
+impl TcpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    async fn handle_tcp<'a>(
+        &'a self,
+        _sess: &'a Session,
+        stream: Option<Box<dyn ProxyStream>>,
+    ) -> io::Result<Box<dyn ProxyStream>> {
+        if stream.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "pt outbound can't be chained after another outbound",
+            ));
+        }
+        let socks_addr = self.wait_socks_addr().await?;
+        let mut stream = TcpStream::connect(socks_addr).await?;
+        match self.address.parse::<std::net::IpAddr>() {
+            Ok(ip) => {
+                async_socks5::connect(&mut stream, SocketAddr::new(ip, self.port), self.auth())
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                    .await?;
+            }
+            Err(_) => {
+                async_socks5::connect(&mut stream, (self.address.clone(), self.port), self.auth())
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                    .await?;
+            }
+        }
+        Ok(Box::new(SimpleProxyStream(stream)))
+    }
+}
@@ -5,12 +5,14 @@ use log::*;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::{
+    proxy::sticky::StickyTable,
     proxy::{OutboundConnect, OutboundHandler, ProxyStream, TcpOutboundHandler},
     session::Session,
 };
 
 pub struct Handler {
     pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub sticky: Option<Arc<StickyTable>>,
 }
 
 #[async_trait]
@@ -28,8 +30,14 @@ impl TcpOutboundHandler for Handler {
         sess: &'a Session,
         _stream: Option<Box<dyn ProxyStream>>,
     ) -> io::Result<Box<dyn ProxyStream>> {
-        let mut rng = StdRng::from_entropy();
-        let i: usize = rng.gen_range(0, self.actors.len());
+        let pick = || {
+            let mut rng = StdRng::from_entropy();
+            rng.gen_range(0, self.actors.len())
+        };
+        let i = match &self.sticky {
+            Some(sticky) => sticky.pick(sess, pick),
+            None => pick(),
+        };
         debug!(
             "random handles tcp [{}] to [{}]",
             sess.destination,
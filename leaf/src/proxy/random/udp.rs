@@ -6,6 +6,7 @@ use log::*;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::{
+    proxy::sticky::StickyTable,
     proxy::{
         OutboundConnect, OutboundDatagram, OutboundHandler, OutboundTransport, UdpOutboundHandler,
         UdpTransportType,
@@ -15,6 +16,7 @@ use crate::{
 
 pub struct Handler {
     pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub sticky: Option<Arc<StickyTable>>,
 }
 
 #[async_trait]
@@ -36,8 +38,14 @@ impl UdpOutboundHandler for Handler {
         sess: &'a Session,
         _transport: Option<OutboundTransport>,
     ) -> io::Result<Box<dyn OutboundDatagram>> {
-        let mut rng = StdRng::from_entropy();
-        let i: usize = rng.gen_range(0, self.actors.len());
+        let pick = || {
+            let mut rng = StdRng::from_entropy();
+            rng.gen_range(0, self.actors.len())
+        };
+        let i = match &self.sticky {
+            Some(sticky) => sticky.pick(sess, pick),
+            None => pick(),
+        };
         debug!(
             "random handles udp [{}] to [{}]",
             sess.destination,
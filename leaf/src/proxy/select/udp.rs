@@ -0,0 +1,44 @@
+use std::io;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::*;
+
+use crate::{
+    app::outbound::selector::OutboundSelector,
+    proxy::{
+        OutboundConnect, OutboundDatagram, OutboundHandler, OutboundTransport, UdpOutboundHandler,
+        UdpTransportType,
+    },
+    session::Session,
+};
+
+pub struct Handler {
+    pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub selector: Arc<OutboundSelector>,
+}
+
+#[async_trait]
+impl UdpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn udp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    fn udp_transport_type(&self) -> UdpTransportType {
+        UdpTransportType::Unknown
+    }
+
+    async fn handle_udp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _transport: Option<OutboundTransport>,
+    ) -> io::Result<Box<dyn OutboundDatagram>> {
+        let a = &self.actors[self.selector.selected_index()];
+        debug!("select handles udp [{}] to [{}]", sess.destination, a.tag());
+        a.handle_udp(sess, None).await
+    }
+}
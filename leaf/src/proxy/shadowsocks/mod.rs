@@ -1,9 +1,13 @@
 mod crypto;
+mod obfs;
 mod shadow;
+mod shadow2022;
 
+pub use obfs::{ObfsMode, ObfsSettings, ObfsStream};
 pub use shadow::{
     ShadowedDatagram, ShadowedDatagramRecvHalf, ShadowedDatagramSendHalf, ShadowedStream,
 };
+pub use shadow2022::{aead_cipher_2022, Shadowed2022Stream};
 
 pub mod tcp;
 pub mod udp;
@@ -0,0 +1,515 @@
+use std::{
+    cmp::min,
+    io,
+    pin::Pin,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use byteorder::{BigEndian, ByteOrder};
+use bytes::{Buf, BufMut, BytesMut};
+use futures::{
+    ready,
+    task::{Context, Poll},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+// The largest payload of a TLS record.
+const TLS_MAX_RECORD: usize = 16 * 1024;
+
+// Leaves enough room for the rest of the ClientHello.
+const TLS_MAX_HELLO_PAYLOAD: usize = TLS_MAX_RECORD - 512;
+
+const TLS_CIPHER_SUITES: [u8; 56] = [
+    0xc0, 0x2c, 0xc0, 0x30, 0x00, 0x9f, 0xcc, 0xa9, 0xcc, 0xa8, 0xcc, 0xaa, 0xc0, 0x2b, 0xc0, 0x2f,
+    0x00, 0x9e, 0xc0, 0x24, 0xc0, 0x28, 0x00, 0x6b, 0xc0, 0x23, 0xc0, 0x27, 0x00, 0x67, 0xc0, 0x0a,
+    0xc0, 0x14, 0x00, 0x39, 0xc0, 0x09, 0xc0, 0x13, 0x00, 0x33, 0x00, 0x9d, 0x00, 0x9c, 0x00, 0x3d,
+    0x00, 0x3c, 0x00, 0x35, 0x00, 0x2f, 0x00, 0xff,
+];
+
+// ec_point_formats, elliptic_curves, signature_algorithms, encrypt_then_mac
+// and extended_master_secret, as sent by simple-obfs.
+const TLS_OTHER_EXTENSIONS: [u8; 66] = [
+    0x00, 0x0b, 0x00, 0x04, 0x03, 0x01, 0x00, 0x02, 0x00, 0x0a, 0x00, 0x0a, 0x00, 0x08, 0x00, 0x1d,
+    0x00, 0x17, 0x00, 0x19, 0x00, 0x18, 0x00, 0x0d, 0x00, 0x20, 0x00, 0x1e, 0x06, 0x01, 0x06, 0x02,
+    0x06, 0x03, 0x05, 0x01, 0x05, 0x02, 0x05, 0x03, 0x04, 0x01, 0x04, 0x02, 0x04, 0x03, 0x03, 0x01,
+    0x03, 0x02, 0x03, 0x03, 0x02, 0x01, 0x02, 0x02, 0x02, 0x03, 0x00, 0x16, 0x00, 0x00, 0x00, 0x17,
+    0x00, 0x00,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObfsMode {
+    Http,
+    Tls,
+}
+
+/// The obfuscation of the simple-obfs plugin (`obfs-local`), done natively
+/// instead of running the plugin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObfsSettings {
+    pub mode: ObfsMode,
+    pub host: String,
+    pub uri: String,
+}
+
+impl ObfsSettings {
+    /// Parses a SIP003 plugin and its options, e.g. `obfs-local` with
+    /// `obfs=http;obfs-host=www.bing.com`.
+    pub fn parse(plugin: &str, opts: &str) -> io::Result<Self> {
+        match plugin {
+            "obfs-local" | "simple-obfs" => (),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unsupported shadowsocks plugin: {}", plugin),
+                ));
+            }
+        }
+        let mut mode = None;
+        let mut host = "cloudfront.net".to_string();
+        let mut uri = "/".to_string();
+        for opt in opts.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let parts: Vec<&str> = opt.splitn(2, '=').collect();
+            let value = parts.get(1).map(|v| v.trim()).unwrap_or("");
+            match parts[0].trim() {
+                "obfs" => {
+                    mode = match value {
+                        "http" => Some(ObfsMode::Http),
+                        "tls" => Some(ObfsMode::Tls),
+                        _ => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                format!("unsupported obfs mode: {}", value),
+                            ));
+                        }
+                    }
+                }
+                "obfs-host" => host = value.to_string(),
+                "obfs-uri" => uri = value.to_string(),
+                _ => (),
+            }
+        }
+        let mode =
+            mode.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing obfs mode"))?;
+        Ok(ObfsSettings { mode, host, uri })
+    }
+}
+
+enum ReadState {
+    // Waiting for the HTTP response header.
+    HttpResponse,
+    // Waiting for the ServerHello and ChangeCipherSpec.
+    TlsServerHello,
+    // Reading a TLS record with the remaining length, 0 for the next header.
+    TlsRecord(usize),
+    Raw,
+}
+
+pub struct ObfsStream<T> {
+    inner: T,
+    settings: ObfsSettings,
+    port: u16,
+    request_sent: bool,
+    read_state: ReadState,
+    read_buf: BytesMut,
+    write_buf: BytesMut,
+    // Bytes of the caller's buffer in write_buf.
+    write_consumed: usize,
+}
+
+impl<T> ObfsStream<T> {
+    /// Creates an obfuscated stream to a server on `port`.
+    pub fn new(inner: T, settings: ObfsSettings, port: u16) -> Self {
+        let read_state = match settings.mode {
+            ObfsMode::Http => ReadState::HttpResponse,
+            ObfsMode::Tls => ReadState::TlsServerHello,
+        };
+        ObfsStream {
+            inner,
+            settings,
+            port,
+            request_sent: false,
+            read_state,
+            read_buf: BytesMut::new(),
+            write_buf: BytesMut::new(),
+            write_consumed: 0,
+        }
+    }
+
+    // Encodes the data into write_buf, returns how much of it is consumed.
+    fn encode(&mut self, buf: &[u8]) -> usize {
+        let mut rng = StdRng::from_entropy();
+        if !self.request_sent {
+            self.request_sent = true;
+            match self.settings.mode {
+                ObfsMode::Http => {
+                    let host = if self.port == 80 {
+                        self.settings.host.clone()
+                    } else {
+                        format!("{}:{}", self.settings.host, self.port)
+                    };
+                    let key: [u8; 16] = rng.gen();
+                    let header = format!(
+                        "GET {} HTTP/1.1\r\n\
+                         Host: {}\r\n\
+                         User-Agent: curl/7.{}.{}\r\n\
+                         Upgrade: websocket\r\n\
+                         Connection: Upgrade\r\n\
+                         Sec-WebSocket-Key: {}\r\n\
+                         Content-Length: {}\r\n\r\n",
+                        self.settings.uri,
+                        host,
+                        rng.gen_range(0, 51),
+                        rng.gen_range(0, 2),
+                        base64::encode(&key),
+                        buf.len()
+                    );
+                    self.write_buf.put_slice(header.as_bytes());
+                    self.write_buf.put_slice(buf);
+                    return buf.len();
+                }
+                ObfsMode::Tls => {
+                    let n = min(buf.len(), TLS_MAX_HELLO_PAYLOAD);
+                    client_hello(&mut self.write_buf, &self.settings.host, &buf[..n]);
+                    return n;
+                }
+            }
+        }
+        match self.settings.mode {
+            ObfsMode::Http => {
+                self.write_buf.put_slice(buf);
+                buf.len()
+            }
+            ObfsMode::Tls => {
+                let n = min(buf.len(), TLS_MAX_RECORD);
+                self.write_buf.put_slice(&[0x17, 0x03, 0x03]);
+                self.write_buf.put_u16(n as u16);
+                self.write_buf.put_slice(&buf[..n]);
+                n
+            }
+        }
+    }
+
+    // Parses the buffered response header, returns false if more is needed.
+    fn parse_response(&mut self) -> io::Result<bool> {
+        match self.read_state {
+            ReadState::HttpResponse => {
+                let end = match self.read_buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    Some(pos) => pos + 4,
+                    None => return Ok(false),
+                };
+                if !self.read_buf.starts_with(b"HTTP/1.1 101") {
+                    return Err(invalid_response());
+                }
+                self.read_buf.advance(end);
+                self.read_state = ReadState::Raw;
+            }
+            ReadState::TlsServerHello => {
+                if self.read_buf.len() < 5 {
+                    return Ok(false);
+                }
+                if self.read_buf[0] != 0x16 {
+                    return Err(invalid_response());
+                }
+                let hello_len = 5 + BigEndian::read_u16(&self.read_buf[3..5]) as usize;
+                // ChangeCipherSpec follows.
+                if self.read_buf.len() < hello_len + 6 {
+                    return Ok(false);
+                }
+                if self.read_buf[hello_len] != 0x14 {
+                    return Err(invalid_response());
+                }
+                self.read_buf.advance(hello_len + 6);
+                self.read_state = ReadState::TlsRecord(0);
+            }
+            ReadState::TlsRecord(0) => {
+                if self.read_buf.len() < 5 {
+                    return Ok(false);
+                }
+                // The first record is a Finished handshake message carrying
+                // data, then application data.
+                if (self.read_buf[0] != 0x16 && self.read_buf[0] != 0x17)
+                    || self.read_buf[1..3] != [0x03, 0x03]
+                {
+                    return Err(invalid_response());
+                }
+                let len = BigEndian::read_u16(&self.read_buf[3..5]) as usize;
+                self.read_buf.advance(5);
+                self.read_state = ReadState::TlsRecord(len);
+            }
+            _ => (),
+        }
+        Ok(true)
+    }
+}
+
+fn client_hello(buf: &mut BytesMut, host: &str, payload: &[u8]) {
+    let mut rng = StdRng::from_entropy();
+    let ext_len = 4 + payload.len() + 9 + host.len() + TLS_OTHER_EXTENSIONS.len();
+    let hello_len = 2 + 32 + 1 + 32 + 2 + TLS_CIPHER_SUITES.len() + 2 + 2 + ext_len;
+
+    buf.reserve(9 + hello_len);
+    // record
+    buf.put_u8(0x16);
+    buf.put_u16(0x0301);
+    buf.put_u16((4 + hello_len) as u16);
+    // handshake
+    buf.put_u8(1);
+    buf.put_u8(0);
+    buf.put_u16(hello_len as u16);
+    buf.put_u16(0x0303);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    buf.put_u32(now as u32);
+    let random: [u8; 28] = rng.gen();
+    buf.put_slice(&random);
+    let session_id: [u8; 32] = rng.gen();
+    buf.put_u8(32);
+    buf.put_slice(&session_id);
+    buf.put_u16(TLS_CIPHER_SUITES.len() as u16);
+    buf.put_slice(&TLS_CIPHER_SUITES);
+    buf.put_u8(1);
+    buf.put_u8(0);
+    // extensions, the payload is sent as a session ticket
+    buf.put_u16(ext_len as u16);
+    buf.put_u16(0x0023);
+    buf.put_u16(payload.len() as u16);
+    buf.put_slice(payload);
+    buf.put_u16(0x0000);
+    buf.put_u16((host.len() + 5) as u16);
+    buf.put_u16((host.len() + 3) as u16);
+    buf.put_u8(0);
+    buf.put_u16(host.len() as u16);
+    buf.put_slice(host.as_bytes());
+    buf.put_slice(&TLS_OTHER_EXTENSIONS);
+}
+
+fn invalid_response() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid obfs response")
+}
+
+impl<T> AsyncRead for ObfsStream<T>
+where
+    T: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let me = &mut *self;
+        loop {
+            match me.read_state {
+                ReadState::Raw => {
+                    if !me.read_buf.is_empty() {
+                        let n = min(buf.len(), me.read_buf.len());
+                        buf[..n].copy_from_slice(&me.read_buf[..n]);
+                        me.read_buf.advance(n);
+                        return Poll::Ready(Ok(n));
+                    }
+                    return Pin::new(&mut me.inner).poll_read(cx, buf);
+                }
+                ReadState::TlsRecord(remaining) if remaining > 0 => {
+                    let n = if !me.read_buf.is_empty() {
+                        let n = min(min(buf.len(), remaining), me.read_buf.len());
+                        buf[..n].copy_from_slice(&me.read_buf[..n]);
+                        me.read_buf.advance(n);
+                        n
+                    } else {
+                        let n = min(buf.len(), remaining);
+                        let n = ready!(Pin::new(&mut me.inner).poll_read(cx, &mut buf[..n]))?;
+                        if n == 0 {
+                            return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+                        }
+                        n
+                    };
+                    me.read_state = ReadState::TlsRecord(remaining - n);
+                    return Poll::Ready(Ok(n));
+                }
+                _ => {
+                    if me.parse_response()? {
+                        continue;
+                    }
+                    let mut tmp = [0u8; 4096];
+                    let n = ready!(Pin::new(&mut me.inner).poll_read(cx, &mut tmp))?;
+                    if n == 0 {
+                        // EOF between records is a clean close.
+                        if let ReadState::TlsRecord(0) = me.read_state {
+                            if me.read_buf.is_empty() {
+                                return Poll::Ready(Ok(0));
+                            }
+                        }
+                        return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+                    }
+                    me.read_buf.put_slice(&tmp[..n]);
+                }
+            }
+        }
+    }
+}
+
+impl<T> ObfsStream<T>
+where
+    T: AsyncWrite + Unpin,
+{
+    fn poll_write_buf(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.write_buf))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.write_buf.advance(n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> AsyncWrite for ObfsStream<T>
+where
+    T: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let me = &mut *self;
+        if me.write_buf.is_empty() {
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+            if me.request_sent && me.settings.mode == ObfsMode::Http {
+                return Pin::new(&mut me.inner).poll_write(cx, buf);
+            }
+            me.write_consumed = me.encode(buf);
+        }
+        ready!(me.poll_write_buf(cx))?;
+        Poll::Ready(Ok(me.write_consumed))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_obfs_settings() {
+        let s = ObfsSettings::parse("obfs-local", "obfs=tls;obfs-host=www.bing.com").unwrap();
+        assert_eq!(s.mode, ObfsMode::Tls);
+        assert_eq!(s.host, "www.bing.com");
+        assert_eq!(s.uri, "/");
+        assert!(ObfsSettings::parse("obfs-local", "obfs-host=www.bing.com").is_err());
+        assert!(ObfsSettings::parse("v2ray-plugin", "obfs=http").is_err());
+    }
+
+    // Reads the session ticket of a ClientHello.
+    fn session_ticket(hello: &[u8]) -> Vec<u8> {
+        let mut pos = 5 + 4 + 2 + 32;
+        pos += 1 + hello[pos] as usize;
+        pos += 2 + BigEndian::read_u16(&hello[pos..]) as usize;
+        pos += 1 + hello[pos] as usize;
+        pos += 2;
+        while pos < hello.len() {
+            let ty = BigEndian::read_u16(&hello[pos..]);
+            let len = BigEndian::read_u16(&hello[pos + 2..]) as usize;
+            if ty == 0x0023 {
+                return hello[pos + 4..pos + 4 + len].to_vec();
+            }
+            pos += 4 + len;
+        }
+        panic!("no session ticket");
+    }
+
+    #[test]
+    fn test_obfs_stream() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            // http
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut req = Vec::new();
+                while !req.ends_with(b"\r\nhello") {
+                    let mut b = [0u8; 1];
+                    stream.read_exact(&mut b).await.unwrap();
+                    req.push(b[0]);
+                }
+                let req = String::from_utf8(req).unwrap();
+                assert!(req.starts_with("GET /path HTTP/1.1\r\n"));
+                assert!(req.contains(&format!("Host: example.com:{}\r\n", addr.port())));
+                assert!(req.contains("Content-Length: 5\r\n"));
+                stream
+                    .write_all(b"HTTP/1.1 101 Switching Protocols\r\n\r\nworld")
+                    .await
+                    .unwrap();
+                let mut buf = [0u8; 3];
+                stream.read_exact(&mut buf).await.unwrap();
+                assert_eq!(&buf, b"foo");
+            });
+            let settings = ObfsSettings::parse(
+                "obfs-local",
+                "obfs=http;obfs-host=example.com;obfs-uri=/path",
+            )
+            .unwrap();
+            let stream = TcpStream::connect(addr).await.unwrap();
+            let mut stream = ObfsStream::new(stream, settings, addr.port());
+            stream.write_all(b"hello").await.unwrap();
+            let mut buf = [0u8; 5];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"world");
+            stream.write_all(b"foo").await.unwrap();
+
+            // tls
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut hello = vec![0u8; 5];
+                stream.read_exact(&mut hello).await.unwrap();
+                assert_eq!(hello[0], 0x16);
+                let len = BigEndian::read_u16(&hello[3..5]) as usize;
+                hello.resize(5 + len, 0);
+                stream.read_exact(&mut hello[5..]).await.unwrap();
+                assert_eq!(session_ticket(&hello), b"hello");
+                let mut resp = vec![0x16, 0x03, 0x01, 0x00, 0x5b];
+                resp.resize(5 + 0x5b, 0);
+                resp.extend_from_slice(&[0x14, 0x03, 0x03, 0x00, 0x01, 0x01]);
+                resp.extend_from_slice(&[0x16, 0x03, 0x03, 0x00, 0x05]);
+                resp.extend_from_slice(b"world");
+                stream.write_all(&resp).await.unwrap();
+                let mut buf = [0u8; 8];
+                stream.read_exact(&mut buf).await.unwrap();
+                assert_eq!(&buf, &[0x17, 0x03, 0x03, 0x00, 0x03, b'f', b'o', b'o']);
+            });
+            let settings = ObfsSettings::parse("obfs-local", "obfs=tls").unwrap();
+            let stream = TcpStream::connect(addr).await.unwrap();
+            let mut stream = ObfsStream::new(stream, settings, addr.port());
+            stream.write_all(b"hello").await.unwrap();
+            let mut buf = [0u8; 5];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"world");
+            stream.write_all(b"foo").await.unwrap();
+            stream.flush().await.unwrap();
+        });
+    }
+}
@@ -0,0 +1,462 @@
+use std::{
+    cmp::min,
+    io,
+    pin::Pin,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+use byteorder::{BigEndian, ByteOrder};
+use bytes::{Buf, BufMut, BytesMut};
+use futures::{
+    ready,
+    task::{Context, Poll},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::{
+    common::crypto::{
+        aead::{AeadCipher, AeadDecryptor, AeadEncryptor},
+        Cipher, Decryptor, Encryptor, SizedCipher,
+    },
+    session::{SocksAddr, SocksAddrWireType},
+};
+
+use super::crypto::ShadowsocksNonceSequence;
+
+const SUBKEY_CONTEXT: &str = "shadowsocks 2022 session subkey";
+
+// Stream types in the fixed-length header.
+const CLIENT_STREAM: u8 = 0;
+const SERVER_STREAM: u8 = 1;
+
+// How far the server timestamp may be off.
+const MAX_TIME_DIFF: u64 = 30;
+
+const MAX_PADDING: usize = 900;
+
+const MAX_PAYLOAD: usize = 0xffff;
+
+/// Returns the AEAD cipher of a Shadowsocks 2022 method, None if it's not a
+/// 2022 method.
+pub fn aead_cipher_2022(method: &str) -> Option<&'static str> {
+    match method {
+        "2022-blake3-aes-128-gcm" => Some("aes-128-gcm"),
+        "2022-blake3-aes-256-gcm" => Some("aes-256-gcm"),
+        "2022-blake3-chacha20-poly1305" => Some("chacha20-ietf-poly1305"),
+        _ => None,
+    }
+}
+
+/// Decodes the base64 PSK, its length must be the key length of the cipher.
+pub fn decode_psk(password: &str, key_len: usize) -> Result<Vec<u8>> {
+    if password.contains(':') {
+        return Err(anyhow!("multiple shadowsocks 2022 keys are not supported"));
+    }
+    let psk = base64::decode(password).map_err(|_| anyhow!("invalid base64 key"))?;
+    if psk.len() != key_len {
+        return Err(anyhow!(
+            "invalid key length {}, expected {}",
+            psk.len(),
+            key_len
+        ));
+    }
+    Ok(psk)
+}
+
+// BLAKE3 in key derivation mode, the key is filled with the output.
+fn derive_key(context: &str, material: &[&[u8]], key: &mut [u8]) {
+    let mut hasher = blake3::Hasher::new_derive_key(context);
+    for m in material {
+        hasher.update(m);
+    }
+    hasher.finalize_xof().fill(key);
+}
+
+/// Derives the session subkey from the PSK and the salt.
+pub fn session_subkey(psk: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut key = vec![0u8; psk.len()];
+    derive_key(SUBKEY_CONTEXT, &[psk, salt], &mut key);
+    key
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn crypto_err() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "crypto error")
+}
+
+fn seal(
+    enc: &mut AeadEncryptor<ShadowsocksNonceSequence>,
+    buf: &mut BytesMut,
+    plaintext: &[u8],
+) -> io::Result<()> {
+    let mut piece = BytesMut::with_capacity(plaintext.len() + 16);
+    piece.put_slice(plaintext);
+    enc.encrypt(&mut piece).map_err(|_| crypto_err())?;
+    buf.put_slice(&piece);
+    Ok(())
+}
+
+enum ReadState {
+    WaitingHeader,
+    WaitingLength,
+    WaitingData(usize),
+}
+
+/// A client stream of the Shadowsocks 2022 edition (SIP022). The request
+/// header is sent with the first write or flush, the response header is
+/// verified on the first read.
+pub struct Shadowed2022Stream<T> {
+    inner: T,
+    cipher: AeadCipher,
+    psk: Vec<u8>,
+    request_salt: Vec<u8>,
+    enc: AeadEncryptor<ShadowsocksNonceSequence>,
+    dec: Option<AeadDecryptor<ShadowsocksNonceSequence>>,
+    read_state: ReadState,
+    read_buf: BytesMut,
+    // Decrypted payload not yet read.
+    payload: BytesMut,
+    write_buf: BytesMut,
+    // Bytes of the caller's buffer in write_buf.
+    write_consumed: usize,
+}
+
+impl<T> Shadowed2022Stream<T> {
+    pub fn new(s: T, method: &str, password: &str, destination: &SocksAddr) -> Result<Self> {
+        let cipher = aead_cipher_2022(method)
+            .ok_or_else(|| anyhow!("unsupported cipher: {}", method))
+            .and_then(AeadCipher::new)?;
+        let psk = decode_psk(password, cipher.key_len())?;
+
+        let mut rng = StdRng::from_entropy();
+        let mut request_salt = vec![0u8; cipher.key_len()];
+        rng.fill(&mut request_salt[..]);
+        let nonce = ShadowsocksNonceSequence::new(cipher.nonce_len());
+        let mut enc = cipher.encryptor(&session_subkey(&psk, &request_salt), nonce)?;
+
+        // The variable-length header has no initial payload, so it's padded.
+        let mut var_header = BytesMut::new();
+        destination.write_buf(&mut var_header, SocksAddrWireType::PortLast)?;
+        let padding_len = rng.gen_range(1, MAX_PADDING + 1);
+        var_header.put_u16(padding_len as u16);
+        var_header.resize(var_header.len() + padding_len, 0);
+
+        let mut fixed_header = BytesMut::with_capacity(11);
+        fixed_header.put_u8(CLIENT_STREAM);
+        fixed_header.put_u64(unix_time());
+        fixed_header.put_u16(var_header.len() as u16);
+
+        let mut write_buf = BytesMut::new();
+        write_buf.put_slice(&request_salt);
+        seal(&mut enc, &mut write_buf, &fixed_header)?;
+        seal(&mut enc, &mut write_buf, &var_header)?;
+
+        Ok(Shadowed2022Stream {
+            inner: s,
+            cipher,
+            psk,
+            request_salt,
+            enc,
+            dec: None,
+            read_state: ReadState::WaitingHeader,
+            read_buf: BytesMut::new(),
+            payload: BytesMut::new(),
+            write_buf,
+            write_consumed: 0,
+        })
+    }
+
+    // Decrypts the next chunk in read_buf, returns false if more is needed.
+    fn decrypt_chunk(&mut self) -> io::Result<bool> {
+        let salt_len = self.cipher.key_len();
+        let tag_len = self.cipher.tag_len();
+        match self.read_state {
+            ReadState::WaitingHeader => {
+                let header_len = 1 + 8 + salt_len + 2;
+                if self.read_buf.len() < salt_len + header_len + tag_len {
+                    return Ok(false);
+                }
+                let salt = self.read_buf.split_to(salt_len);
+                let nonce = ShadowsocksNonceSequence::new(self.cipher.nonce_len());
+                let dec = self
+                    .cipher
+                    .decryptor(&session_subkey(&self.psk, &salt), nonce)
+                    .map_err(|_| crypto_err())?;
+                let dec = self.dec.get_or_insert(dec);
+                let mut header = self.read_buf.split_to(header_len + tag_len);
+                dec.decrypt(&mut header).map_err(|_| crypto_err())?;
+                if header[0] != SERVER_STREAM {
+                    return Err(invalid_header("invalid stream type"));
+                }
+                let timestamp = BigEndian::read_u64(&header[1..9]);
+                let now = unix_time();
+                if timestamp.max(now) - timestamp.min(now) > MAX_TIME_DIFF {
+                    return Err(invalid_header("bad timestamp"));
+                }
+                if header[9..9 + salt_len] != self.request_salt[..] {
+                    return Err(invalid_header("request salt mismatch"));
+                }
+                let len = BigEndian::read_u16(&header[9 + salt_len..]) as usize;
+                self.read_state = ReadState::WaitingData(len);
+            }
+            ReadState::WaitingLength => {
+                if self.read_buf.len() < 2 + tag_len {
+                    return Ok(false);
+                }
+                let mut chunk = self.read_buf.split_to(2 + tag_len);
+                let dec = self.dec.as_mut().expect("uninitialized cipher");
+                dec.decrypt(&mut chunk).map_err(|_| crypto_err())?;
+                let len = BigEndian::read_u16(&chunk) as usize;
+                self.read_state = ReadState::WaitingData(len);
+            }
+            ReadState::WaitingData(len) => {
+                if self.read_buf.len() < len + tag_len {
+                    return Ok(false);
+                }
+                let mut chunk = self.read_buf.split_to(len + tag_len);
+                let dec = self.dec.as_mut().expect("uninitialized cipher");
+                dec.decrypt(&mut chunk).map_err(|_| crypto_err())?;
+                self.payload.put_slice(&chunk[..len]);
+                self.read_state = ReadState::WaitingLength;
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn invalid_header(msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid shadowsocks 2022 response: {}", msg),
+    )
+}
+
+impl<T> AsyncRead for Shadowed2022Stream<T>
+where
+    T: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let me = &mut *self;
+        loop {
+            if !me.payload.is_empty() {
+                let n = min(buf.len(), me.payload.len());
+                buf[..n].copy_from_slice(&me.payload[..n]);
+                me.payload.advance(n);
+                return Poll::Ready(Ok(n));
+            }
+            if me.decrypt_chunk()? {
+                continue;
+            }
+            let mut tmp = [0u8; 8192];
+            let n = ready!(Pin::new(&mut me.inner).poll_read(cx, &mut tmp))?;
+            if n == 0 {
+                // EOF between chunks is a clean close.
+                if let ReadState::WaitingLength = me.read_state {
+                    if me.read_buf.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+                }
+                return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+            }
+            me.read_buf.put_slice(&tmp[..n]);
+        }
+    }
+}
+
+impl<T> Shadowed2022Stream<T>
+where
+    T: AsyncWrite + Unpin,
+{
+    fn poll_write_buf(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.write_buf))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.write_buf.advance(n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> AsyncWrite for Shadowed2022Stream<T>
+where
+    T: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let me = &mut *self;
+        if me.write_consumed == 0 && !buf.is_empty() {
+            let n = min(buf.len(), MAX_PAYLOAD);
+            let mut len = [0u8; 2];
+            BigEndian::write_u16(&mut len, n as u16);
+            seal(&mut me.enc, &mut me.write_buf, &len)?;
+            seal(&mut me.enc, &mut me.write_buf, &buf[..n])?;
+            me.write_consumed = n;
+        }
+        ready!(me.poll_write_buf(cx))?;
+        let n = me.write_consumed;
+        me.write_consumed = 0;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    fn to_hex(b: &[u8]) -> String {
+        b.iter().map(|x| format!("{:02x}", x)).collect()
+    }
+
+    #[test]
+    fn test_session_subkey() {
+        // The derive_key vector of the empty input in the BLAKE3 test
+        // vectors (test_vectors.json of the reference implementation).
+        let mut key = [0u8; 131];
+        derive_key(
+            "BLAKE3 2019-12-27 16:29:52 test vectors context",
+            &[],
+            &mut key,
+        );
+        assert_eq!(
+            to_hex(&key),
+            "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d\
+             905630c8be290dfcf3e6842f13bddd573c098c3f17361f1f206b8cad9d088aa4\
+             a3f746752c6b0ce6a83b0da81d59649257cdf8eb3e9f7d4998e41021fac119de\
+             efb896224ac99f860011f73609e6e0e4540f93b273e56547dfd3aa1a035ba668\
+             9d89a0"
+        );
+
+        // SIP022: session_subkey := blake3::derive_key(context: "shadowsocks
+        // 2022 session subkey", key_material: key + salt), truncated to the
+        // key length.
+        for (password, key_len) in &[
+            ("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=", 32),
+            ("AAECAwQFBgcICQoLDA0ODw==", 16),
+        ] {
+            let psk = decode_psk(password, *key_len).unwrap();
+            let salt: Vec<u8> = (0..*key_len as u8).map(|i| i + 0x80).collect();
+            let mut material = psk.clone();
+            material.extend_from_slice(&salt);
+            let mut expected = vec![0u8; 64];
+            derive_key(
+                "shadowsocks 2022 session subkey",
+                &[&material],
+                &mut expected,
+            );
+            assert_eq!(session_subkey(&psk, &salt), &expected[..*key_len]);
+        }
+        assert!(decode_psk("AAECAwQFBgcICQoLDA0ODw==", 32).is_err());
+    }
+
+    #[test]
+    fn test_shadowed_2022_stream() {
+        let method = "2022-blake3-aes-128-gcm";
+        let password = "AAECAwQFBgcICQoLDA0ODw==";
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            // A server decrypting the request and sending a response.
+            tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let cipher = AeadCipher::new(aead_cipher_2022(method).unwrap()).unwrap();
+                let psk = decode_psk(password, 16).unwrap();
+                let mut salt = vec![0u8; 16];
+                stream.read_exact(&mut salt).await.unwrap();
+                let mut dec = cipher
+                    .decryptor(
+                        &session_subkey(&psk, &salt),
+                        ShadowsocksNonceSequence::new(cipher.nonce_len()),
+                    )
+                    .unwrap();
+                let mut fixed = BytesMut::new();
+                fixed.resize(11 + 16, 0);
+                stream.read_exact(&mut fixed).await.unwrap();
+                dec.decrypt(&mut fixed).unwrap();
+                assert_eq!(fixed[0], CLIENT_STREAM);
+                let var_len = BigEndian::read_u16(&fixed[9..11]) as usize;
+                let mut var = BytesMut::new();
+                var.resize(var_len + 16, 0);
+                stream.read_exact(&mut var).await.unwrap();
+                dec.decrypt(&mut var).unwrap();
+                // 1.2.3.4:443
+                assert_eq!(&var[..7], &[1, 1, 2, 3, 4, 1, 187]);
+                let padding_len = BigEndian::read_u16(&var[7..9]) as usize;
+                assert!(padding_len >= 1 && padding_len <= MAX_PADDING);
+                assert_eq!(var_len, 9 + padding_len);
+
+                let mut chunk = BytesMut::new();
+                chunk.resize(2 + 16, 0);
+                stream.read_exact(&mut chunk).await.unwrap();
+                dec.decrypt(&mut chunk).unwrap();
+                assert_eq!(BigEndian::read_u16(&chunk), 5);
+                chunk.resize(5 + 16, 0);
+                stream.read_exact(&mut chunk).await.unwrap();
+                dec.decrypt(&mut chunk).unwrap();
+                assert_eq!(&chunk[..5], b"hello");
+
+                let response_salt = vec![7u8; 16];
+                let mut enc = cipher
+                    .encryptor(
+                        &session_subkey(&psk, &response_salt),
+                        ShadowsocksNonceSequence::new(cipher.nonce_len()),
+                    )
+                    .unwrap();
+                let mut header = BytesMut::new();
+                header.put_u8(SERVER_STREAM);
+                header.put_u64(unix_time());
+                header.put_slice(&salt);
+                header.put_u16(5);
+                let mut resp = BytesMut::new();
+                resp.put_slice(&response_salt);
+                seal(&mut enc, &mut resp, &header).unwrap();
+                seal(&mut enc, &mut resp, b"world").unwrap();
+                stream.write_all(&resp).await.unwrap();
+            });
+
+            let stream = TcpStream::connect(addr).await.unwrap();
+            let destination =
+                SocksAddr::from(("1.2.3.4".parse::<std::net::IpAddr>().unwrap(), 443));
+            let mut stream =
+                Shadowed2022Stream::new(stream, method, password, &destination).unwrap();
+            stream.write_all(b"hello").await.unwrap();
+            let mut buf = [0u8; 5];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"world");
+        });
+    }
+}
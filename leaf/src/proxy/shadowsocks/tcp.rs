@@ -1,14 +1,16 @@
 use std::{io, net::SocketAddr, sync::Arc};
 
 use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
 
-use super::ShadowedStream;
+use super::{ObfsSettings, ObfsStream, Shadowed2022Stream, ShadowedStream};
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
-        stream::SimpleProxyStream, OutboundConnect, ProxyStream, TcpConnector, TcpOutboundHandler,
+        stream::SimpleProxyStream, OutboundConnect, ProxyStream, TcpConnector, TcpOptions,
+        TcpOutboundHandler,
     },
-    session::{Session, SocksAddrWireType},
+    session::{Session, SocksAddr, SocksAddrWireType},
 };
 
 pub struct Handler {
@@ -16,11 +18,17 @@ pub struct Handler {
     pub port: u16,
     pub cipher: String,
     pub password: String,
+    pub obfs: Option<ObfsSettings>,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
@@ -52,16 +60,57 @@ impl TcpOutboundHandler for Handler {
             )
             .await?
         };
+        connect_stream(
+            stream,
+            &self.cipher,
+            &self.password,
+            self.obfs.as_ref(),
+            self.port,
+            &sess.destination,
+        )
+        .await
+    }
+}
+
+/// Wraps a stream to the server in the plugin and the cipher, and requests
+/// `destination`. Also used to carry UDP over TCP.
+pub(super) async fn connect_stream(
+    stream: Box<dyn ProxyStream>,
+    cipher: &str,
+    password: &str,
+    obfs: Option<&ObfsSettings>,
+    port: u16,
+    destination: &SocksAddr,
+) -> io::Result<Box<dyn ProxyStream>> {
+    let stream: Box<dyn ProxyStream> = if let Some(obfs) = obfs {
+        Box::new(SimpleProxyStream(ObfsStream::new(
+            stream,
+            obfs.clone(),
+            port,
+        )))
+    } else {
+        stream
+    };
+    if super::aead_cipher_2022(cipher).is_some() {
         let mut stream =
-            ShadowedStream::new(stream, &self.cipher, &self.password).map_err(|e| {
+            Shadowed2022Stream::new(stream, cipher, password, destination).map_err(|e| {
                 io::Error::new(
                     io::ErrorKind::Other,
                     format!("create shadowsocks stream failed: {}", e),
                 )
             })?;
-        sess.destination
-            .write_to(&mut stream, SocksAddrWireType::PortLast)
-            .await?;
-        Ok(Box::new(SimpleProxyStream(stream)))
+        // Sends the request header, it doesn't wait for the payload.
+        stream.flush().await?;
+        return Ok(Box::new(SimpleProxyStream(stream)));
     }
+    let mut stream = ShadowedStream::new(stream, cipher, password).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("create shadowsocks stream failed: {}", e),
+        )
+    })?;
+    destination
+        .write_to(&mut stream, SocksAddrWireType::PortLast)
+        .await?;
+    Ok(Box::new(SimpleProxyStream(stream)))
 }
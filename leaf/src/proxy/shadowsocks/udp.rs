@@ -10,13 +10,14 @@ use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
 use log::*;
 
-use super::{ShadowedDatagram, ShadowedDatagramRecvHalf, ShadowedDatagramSendHalf};
+use super::{ObfsSettings, ShadowedDatagram, ShadowedDatagramRecvHalf, ShadowedDatagramSendHalf};
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
+        uot::{self, UotDatagram},
         OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf,
-        OutboundTransport, SimpleOutboundDatagram, UdpConnector, UdpOutboundHandler,
-        UdpTransportType,
+        OutboundTransport, SimpleOutboundDatagram, TcpConnector, TcpOptions, UdpConnector,
+        UdpOutboundHandler, UdpTransportType,
     },
     session::{Session, SocksAddr, SocksAddrWireType},
 };
@@ -26,11 +27,63 @@ pub struct Handler {
     pub port: u16,
     pub cipher: String,
     pub password: String,
+    /// The plugin in use, UDP is not supported with it unless over TCP.
+    pub plugin: Option<String>,
+    pub obfs: Option<ObfsSettings>,
+    /// The UDP over TCP version, UDP is relayed over TCP if set.
+    pub udp_over_tcp: Option<u32>,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl UdpConnector for Handler {}
+impl UdpConnector for Handler {
+    fn bind_interface(&self) -> Option<&str> {
+        self.tcp_options.interface.as_deref()
+    }
+}
+
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
+
+impl Handler {
+    async fn handle_udp_over_tcp(
+        &self,
+        version: u32,
+        sess: &Session,
+        transport: Option<OutboundTransport>,
+    ) -> io::Result<Box<dyn OutboundDatagram>> {
+        let stream = if let Some(OutboundTransport::Stream(stream)) = transport {
+            stream
+        } else {
+            self.dial_tcp_stream(
+                self.dns_client.clone(),
+                &self.bind_addr,
+                &self.address,
+                &self.port,
+            )
+            .await?
+        };
+        let stream = super::tcp::connect_stream(
+            stream,
+            &self.cipher,
+            &self.password,
+            self.obfs.as_ref(),
+            self.port,
+            &uot::magic_address(version)?,
+        )
+        .await?;
+        Ok(Box::new(UotDatagram::new(
+            stream,
+            version,
+            None,
+            &sess.destination,
+        )?))
+    }
+}
 
 #[async_trait]
 impl UdpOutboundHandler for Handler {
@@ -47,7 +100,11 @@ impl UdpOutboundHandler for Handler {
     }
 
     fn udp_transport_type(&self) -> UdpTransportType {
-        UdpTransportType::Packet
+        if self.udp_over_tcp.is_some() {
+            UdpTransportType::Stream
+        } else {
+            UdpTransportType::Packet
+        }
     }
 
     async fn handle_udp<'a>(
@@ -55,6 +112,21 @@ impl UdpOutboundHandler for Handler {
         sess: &'a Session,
         transport: Option<OutboundTransport>,
     ) -> io::Result<Box<dyn OutboundDatagram>> {
+        if let Some(version) = self.udp_over_tcp {
+            return self.handle_udp_over_tcp(version, sess, transport).await;
+        }
+        if let Some(plugin) = &self.plugin {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("udp is not supported with shadowsocks plugin {}", plugin),
+            ));
+        }
+        if super::aead_cipher_2022(&self.cipher).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("udp is not supported with {}", &self.cipher),
+            ));
+        }
         let server_addr = if let Ok(ip) = self.address.parse::<IpAddr>() {
             SocksAddr::Ip(SocketAddr::new(ip, self.port))
         } else {
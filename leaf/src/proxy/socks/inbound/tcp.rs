@@ -1,17 +1,145 @@
 use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
 use log::*;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rand::Rng;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::UdpSocket;
 
 use crate::{
+    config::{self, SocksInboundSettings},
     proxy::InboundTransport,
     proxy::TcpInboundHandler,
     session::{SocksAddr, SocksAddrWireType},
 };
 
-pub struct Handler;
+// Authentication methods.
+const METHOD_NO_AUTH: u8 = 0x00;
+const METHOD_USERPASS: u8 = 0x02;
+const METHOD_NOT_ACCEPTABLE: u8 = 0xff;
+
+#[derive(Default)]
+pub struct Handler {
+    udp_bind: Option<IpAddr>,
+    udp_port_range: Option<(u16, u16)>,
+    udp_advertise: Option<IpAddr>,
+    // Username and password if authentication is required.
+    credentials: Option<(String, String)>,
+    allow_no_auth: bool,
+}
+
+impl Handler {
+    pub fn new(settings: &SocksInboundSettings) -> Self {
+        let credentials = if settings.username.is_empty() {
+            None
+        } else {
+            Some((settings.username.clone(), settings.password.clone()))
+        };
+        Handler {
+            udp_bind: settings.udp_bind.parse().ok(),
+            udp_port_range: config::parse_port_range(&settings.udp_port_range),
+            udp_advertise: settings.udp_advertise.parse().ok(),
+            credentials,
+            allow_no_auth: settings.allow_no_auth,
+        }
+    }
+
+    // Picks the method from the ones offered by the client.
+    fn select_method(&self, methods: &[u8]) -> u8 {
+        if self.credentials.is_some() && methods.contains(&METHOD_USERPASS) {
+            METHOD_USERPASS
+        } else if (self.credentials.is_none() || self.allow_no_auth)
+            && methods.contains(&METHOD_NO_AUTH)
+        {
+            METHOD_NO_AUTH
+        } else {
+            METHOD_NOT_ACCEPTABLE
+        }
+    }
+
+    // Verifies the username and password as in RFC 1929, returns whether
+    // the client is authenticated.
+    async fn authenticate<S>(&self, stream: &mut S) -> io::Result<bool>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut buf = [0u8; 255];
+        // ver, ulen
+        stream.read_exact(&mut buf[..2]).await?;
+        if buf[0] != 0x01 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("unknown socks5 userpass version {}", buf[0]),
+            ));
+        }
+        let ulen = buf[1] as usize;
+        stream.read_exact(&mut buf[..ulen]).await?;
+        let username = buf[..ulen].to_vec();
+        // plen
+        stream.read_exact(&mut buf[..1]).await?;
+        let plen = buf[0] as usize;
+        stream.read_exact(&mut buf[..plen]).await?;
+        let password = &buf[..plen];
+        let ok = match &self.credentials {
+            Some((u, p)) => u.as_bytes() == &username[..] && p.as_bytes() == password,
+            None => false,
+        };
+        stream
+            .write_all(&[0x01, if ok { 0x00 } else { 0x01 }])
+            .await?;
+        Ok(ok)
+    }
+
+    // Binds a UDP relay on the IP, on a free port in the port range if any,
+    // starting from a random one.
+    async fn bind_relay(&self, ip: IpAddr) -> io::Result<UdpSocket> {
+        let (start, end) = match self.udp_port_range {
+            Some(v) => v,
+            None => return UdpSocket::bind((ip, 0)).await,
+        };
+        let n = (end - start) as u32 + 1;
+        let offset = rand::thread_rng().gen_range(0, n);
+        for i in 0..n {
+            let port = start + ((offset + i) % n) as u16;
+            if let Ok(socket) = UdpSocket::bind((ip, port)).await {
+                return Ok(socket);
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("no free udp port in {}-{}", start, end),
+        ))
+    }
+
+    // The address to report in the reply, which the client must be able to
+    // reach.
+    fn relay_addr(&self, bound: SocketAddr, local_addr: &SocketAddr) -> SocketAddr {
+        let ip = if let Some(ip) = self.udp_advertise {
+            ip
+        } else if bound.ip().is_unspecified() {
+            local_addr.ip()
+        } else {
+            bound.ip()
+        };
+        // Clients may not understand the IPv4-mapped addresses from dual-stack
+        // sockets.
+        let ip = match ip {
+            IpAddr::V6(v6) => {
+                let seg = v6.segments();
+                if seg[..5].iter().all(|s| *s == 0) && seg[5] == 0xffff {
+                    let o = v6.octets();
+                    IpAddr::V4(Ipv4Addr::new(o[12], o[13], o[14], o[15]))
+                } else {
+                    ip
+                }
+            }
+            _ => ip,
+        };
+        SocketAddr::new(ip, bound.port())
+    }
+}
 
 #[async_trait]
 impl TcpInboundHandler for Handler {
@@ -44,26 +172,32 @@ impl TcpInboundHandler for Handler {
                 debug!("read methods failed: {}", e);
                 return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
             };
-            let mut method_accepted = false;
-            let mut method_idx: u8 = 0;
-            let supported_method: u8 = 0x0;
-            for (idx, method) in buf[..].iter().enumerate() {
-                if method == &supported_method {
-                    method_accepted = true;
-                    method_idx = idx as u8;
-                    break;
-                }
-            }
-            if !method_accepted {
-                warn!("unsupported socks5 authentication methods");
-                if let Err(e) = stream.write_all(&[0x05, 0xff]).await {
-                    debug!("write auth response failed: {}", e);
-                };
-                return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
-            } else if let Err(e) = stream.write_all(&[0x05, method_idx]).await {
+            let method = self.select_method(&buf[..]);
+            if let Err(e) = stream.write_all(&[0x05, method]).await {
                 debug!("write auth response failed: {}", e);
                 return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
             };
+            match method {
+                METHOD_NOT_ACCEPTABLE => {
+                    warn!(
+                        "unsupported socks5 authentication methods from {}",
+                        &sess.source
+                    );
+                    return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+                }
+                METHOD_USERPASS => match self.authenticate(&mut stream).await {
+                    Ok(true) => (),
+                    Ok(false) => {
+                        warn!("socks5 authentication failed from {}", &sess.source);
+                        return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+                    }
+                    Err(e) => {
+                        debug!("socks5 authentication failed: {}", e);
+                        return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+                    }
+                },
+                _ => (),
+            }
 
             // handle request
             buf.resize(3, 0);
@@ -125,11 +259,22 @@ impl TcpInboundHandler for Handler {
                     Ok(InboundTransport::Stream(stream, sess))
                 }
                 0x03 => {
+                    // Each association has its own relay socket, which is
+                    // released as soon as the control connection closes.
+                    let bind_ip = self.udp_bind.unwrap_or_else(|| sess.local_addr.ip());
+                    let socket = match self.bind_relay(bind_ip).await {
+                        Ok(v) => v,
+                        Err(e) => {
+                            debug!("bind udp relay failed: {}", e);
+                            return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
+                        }
+                    };
+                    let relay_addr =
+                        SocksAddr::from(self.relay_addr(socket.local_addr()?, &sess.local_addr));
                     buf.clear();
                     buf.put_u8(0x05); // version 5
                     buf.put_u8(0x0); // succeeded
                     buf.put_u8(0x0); // rsv
-                    let relay_addr = SocksAddr::from(sess.local_addr);
                     if let Err(e) = relay_addr.write_buf(&mut buf, SocksAddrWireType::PortLast) {
                         debug!("write address buffer: {}", e);
                         return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
@@ -138,19 +283,13 @@ impl TcpInboundHandler for Handler {
                         debug!("write response failed: {}", e);
                         return Err(io::Error::new(io::ErrorKind::Other, "unspecified"));
                     };
-                    tokio::spawn(async move {
-                        let mut buf = [0u8; 1];
-                        // TODO explicitly drop resources allocated above before waiting?
-                        // if stream.read_exact(&mut buf).await.is_err() {
-                        //     // perhaps explicitly notifies the NAT manager?
-                        //     debug!("udp association end");
-                        // }
-                        if let Err(e) = stream.read_exact(&mut buf).await {
-                            // perhaps explicitly notifies the NAT manager?
-                            debug!("udp association end: {}", e);
-                        }
-                    });
-                    Ok(InboundTransport::Empty)
+                    debug!(
+                        "udp association of {} relays on {}",
+                        &sess.source, &relay_addr
+                    );
+                    Ok(InboundTransport::Datagram(Box::new(
+                        super::udp::Datagram::new_associated(socket, stream, sess.source),
+                    )))
                 }
                 _ => Err(io::Error::new(io::ErrorKind::Other, "invalid cmd")),
             }
@@ -159,3 +298,190 @@ impl TcpInboundHandler for Handler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::{proxy::SimpleProxyStream, session::Session};
+
+    #[test]
+    fn test_udp_association_lifetime() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let listen_addr = listener.local_addr().unwrap();
+            let client = tokio::spawn(async move {
+                let mut stream = TcpStream::connect(listen_addr).await.unwrap();
+                stream.write_all(&[0x05, 0x01, 0x00]).await.unwrap();
+                let mut buf = [0u8; 2];
+                stream.read_exact(&mut buf).await.unwrap();
+                // udp associate 0.0.0.0:0
+                stream
+                    .write_all(&[0x05, 0x03, 0x00, 0x01, 0, 0, 0, 0, 0, 0])
+                    .await
+                    .unwrap();
+                let mut buf = [0u8; 10];
+                stream.read_exact(&mut buf).await.unwrap();
+                assert_eq!(buf[1], 0x0);
+                let relay_addr = SocketAddr::from((
+                    [buf[4], buf[5], buf[6], buf[7]],
+                    u16::from_be_bytes([buf[8], buf[9]]),
+                ));
+                (stream, relay_addr)
+            });
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut sess = Session::default();
+            sess.source = stream.peer_addr().unwrap();
+            sess.local_addr = stream.local_addr().unwrap();
+            let transport = Handler::default()
+                .handle_tcp(InboundTransport::Stream(
+                    Box::new(SimpleProxyStream(stream)),
+                    sess,
+                ))
+                .await
+                .unwrap();
+            let (control, relay_addr) = client.await.unwrap();
+            let (mut recv, send) = match transport {
+                InboundTransport::Datagram(socket) => socket.split(),
+                _ => panic!("unexpected transport"),
+            };
+
+            // rsv, frag, 1.2.3.4:53, payload
+            let mut client_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            client_socket
+                .send_to(&[0, 0, 0, 0x01, 1, 2, 3, 4, 0, 53, 0xab], &relay_addr)
+                .await
+                .unwrap();
+            let mut buf = [0u8; 64];
+            let (n, _, dst_addr) = recv.recv_from(&mut buf).await.unwrap();
+            assert_eq!(&buf[..n], &[0xab]);
+            assert_eq!(dst_addr.unwrap().to_string(), "1.2.3.4:53");
+
+            // Closing the control connection ends the association.
+            drop(control);
+            assert!(recv.recv_from(&mut buf).await.is_err());
+            drop(recv);
+            drop(send);
+            assert!(UdpSocket::bind(relay_addr).await.is_ok());
+        });
+    }
+
+    // Runs the handshake of a client with the greeting, returns the method
+    // selected and whether the connect request is accepted.
+    async fn handshake(
+        handler: Handler,
+        greeting: &'static [u8],
+        auth: &'static [u8],
+    ) -> (u8, bool) {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let listen_addr = listener.local_addr().unwrap();
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(listen_addr).await.unwrap();
+            stream.write_all(greeting).await.unwrap();
+            let mut buf = [0u8; 2];
+            stream.read_exact(&mut buf).await.unwrap();
+            let method = buf[1];
+            if method == METHOD_USERPASS {
+                stream.write_all(auth).await.unwrap();
+                stream.read_exact(&mut buf).await.unwrap();
+                if buf[1] != 0x00 {
+                    return (method, false);
+                }
+            } else if method == METHOD_NOT_ACCEPTABLE {
+                return (method, false);
+            }
+            // connect 1.2.3.4:80
+            stream
+                .write_all(&[0x05, 0x01, 0x00, 0x01, 1, 2, 3, 4, 0, 80])
+                .await
+                .unwrap();
+            let mut buf = [0u8; 10];
+            stream.read_exact(&mut buf).await.unwrap();
+            (method, buf[1] == 0x00)
+        });
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut sess = Session::default();
+        sess.source = stream.peer_addr().unwrap();
+        sess.local_addr = stream.local_addr().unwrap();
+        let res = handler
+            .handle_tcp(InboundTransport::Stream(
+                Box::new(SimpleProxyStream(stream)),
+                sess,
+            ))
+            .await;
+        let (method, accepted) = client.await.unwrap();
+        assert_eq!(res.is_ok(), accepted);
+        (method, accepted)
+    }
+
+    #[test]
+    fn test_authentication() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut settings = SocksInboundSettings::new();
+            settings.username = "u".to_string();
+            settings.password = "p".to_string();
+            let auth_ok: &[u8] = &[0x01, 1, b'u', 1, b'p'];
+            let auth_bad: &[u8] = &[0x01, 1, b'u', 1, b'x'];
+
+            // No authentication required.
+            let r = handshake(Handler::default(), &[0x05, 2, 0x02, 0x00], auth_ok).await;
+            assert_eq!(r, (METHOD_NO_AUTH, true));
+            let r = handshake(Handler::default(), &[0x05, 1, 0x02], auth_ok).await;
+            assert_eq!(r, (METHOD_NOT_ACCEPTABLE, false));
+
+            let r = handshake(Handler::new(&settings), &[0x05, 2, 0x00, 0x02], auth_ok).await;
+            assert_eq!(r, (METHOD_USERPASS, true));
+            let r = handshake(Handler::new(&settings), &[0x05, 1, 0x02], auth_bad).await;
+            assert_eq!(r, (METHOD_USERPASS, false));
+            let r = handshake(Handler::new(&settings), &[0x05, 1, 0x00], auth_ok).await;
+            assert_eq!(r, (METHOD_NOT_ACCEPTABLE, false));
+
+            settings.allow_no_auth = true;
+            let r = handshake(Handler::new(&settings), &[0x05, 1, 0x00], auth_ok).await;
+            assert_eq!(r, (METHOD_NO_AUTH, true));
+            let r = handshake(Handler::new(&settings), &[0x05, 2, 0x00, 0x02], auth_bad).await;
+            assert_eq!(r, (METHOD_USERPASS, false));
+        });
+    }
+
+    #[test]
+    fn test_relay_addr() {
+        let local_addr: SocketAddr = "192.168.1.2:1080".parse().unwrap();
+        let handler = Handler::default();
+        assert_eq!(
+            handler.relay_addr("0.0.0.0:5000".parse().unwrap(), &local_addr),
+            "192.168.1.2:5000".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(
+            handler.relay_addr("[::ffff:10.0.0.1]:5000".parse().unwrap(), &local_addr),
+            "10.0.0.1:5000".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(
+            handler.relay_addr("[::1]:5000".parse().unwrap(), &local_addr),
+            "[::1]:5000".parse::<SocketAddr>().unwrap()
+        );
+
+        let mut settings = SocksInboundSettings::new();
+        settings.udp_advertise = "1.2.3.4".to_string();
+        let handler = Handler::new(&settings);
+        assert_eq!(
+            handler.relay_addr("0.0.0.0:5000".parse().unwrap(), &local_addr),
+            "1.2.3.4:5000".parse::<SocketAddr>().unwrap()
+        );
+    }
+}
@@ -6,9 +6,15 @@ use std::net::SocketAddr;
 use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
 use log::*;
+use tokio::io::AsyncReadExt;
+use tokio::net::udp::{RecvHalf, SendHalf};
+use tokio::net::UdpSocket;
 
 use crate::{
-    proxy::{InboundDatagram, InboundDatagramRecvHalf, InboundDatagramSendHalf, UdpInboundHandler},
+    proxy::{
+        InboundDatagram, InboundDatagramRecvHalf, InboundDatagramSendHalf, ProxyStream,
+        UdpInboundHandler,
+    },
     session::{SocksAddr, SocksAddrWireType},
 };
 
@@ -32,6 +38,25 @@ pub struct Datagram {
     socket: Box<dyn InboundDatagram>,
 }
 
+impl Datagram {
+    /// Creates the relay of a UDP association, the relay is torn down once
+    /// the control TCP connection closes, only datagrams from the client IP
+    /// are accepted.
+    pub fn new_associated(
+        socket: UdpSocket,
+        control: Box<dyn ProxyStream>,
+        client_addr: SocketAddr,
+    ) -> Self {
+        Datagram {
+            socket: Box::new(AssociatedDatagram {
+                socket,
+                control,
+                client_addr,
+            }),
+        }
+    }
+}
+
 impl InboundDatagram for Datagram {
     fn split(
         self: Box<Self>,
@@ -107,3 +132,84 @@ impl InboundDatagramSendHalf for DatagramSendHalf {
         self.0.send_to(&send_buf[..], None, dst_addr).await
     }
 }
+
+// A per-association UDP socket which lives as long as the control connection.
+struct AssociatedDatagram {
+    socket: UdpSocket,
+    control: Box<dyn ProxyStream>,
+    client_addr: SocketAddr,
+}
+
+impl InboundDatagram for AssociatedDatagram {
+    fn split(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn InboundDatagramRecvHalf>,
+        Box<dyn InboundDatagramSendHalf>,
+    ) {
+        let (r, s) = self.socket.split();
+        (
+            Box::new(AssociatedDatagramRecvHalf {
+                recv: r,
+                control: self.control,
+                client_addr: self.client_addr,
+            }),
+            Box::new(AssociatedDatagramSendHalf(s)),
+        )
+    }
+}
+
+struct AssociatedDatagramRecvHalf {
+    recv: RecvHalf,
+    control: Box<dyn ProxyStream>,
+    client_addr: SocketAddr,
+}
+
+#[async_trait]
+impl InboundDatagramRecvHalf for AssociatedDatagramRecvHalf {
+    async fn recv_from(
+        &mut self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr, Option<SocksAddr>)> {
+        let mut control_buf = [0u8; 1];
+        loop {
+            tokio::select! {
+                res = self.recv.recv_from(buf) => {
+                    let (n, src_addr) = res?;
+                    if src_addr.ip() != self.client_addr.ip() {
+                        debug!("drop udp pkt from unexpected source {}", &src_addr);
+                        continue;
+                    }
+                    return Ok((n, src_addr, None));
+                }
+                res = self.control.read(&mut control_buf) => {
+                    match res {
+                        // Nothing is expected on the control connection.
+                        Ok(n) if n > 0 => continue,
+                        _ => {
+                            debug!("udp association of {} ended", &self.client_addr);
+                            return Err(io::Error::new(
+                                io::ErrorKind::Other,
+                                "udp association ended",
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct AssociatedDatagramSendHalf(SendHalf);
+
+#[async_trait]
+impl InboundDatagramSendHalf for AssociatedDatagramSendHalf {
+    async fn send_to(
+        &mut self,
+        buf: &[u8],
+        _src_addr: Option<&SocksAddr>,
+        dst_addr: &SocketAddr,
+    ) -> io::Result<usize> {
+        self.0.send_to(buf, dst_addr).await
+    }
+}
@@ -9,7 +9,7 @@ use futures::future::TryFutureExt;
 
 use crate::{
     app::dns_client::DnsClient,
-    proxy::{OutboundConnect, ProxyStream, TcpConnector, TcpOutboundHandler},
+    proxy::{OutboundConnect, ProxyStream, TcpConnector, TcpOptions, TcpOutboundHandler},
     session::{Session, SocksAddr},
 };
 
@@ -17,10 +17,15 @@ pub struct Handler {
     pub address: String,
     pub port: u16,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
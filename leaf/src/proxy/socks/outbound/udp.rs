@@ -1,32 +1,61 @@
 use std::{
+    cmp::min,
+    convert::TryFrom,
     io::{Error, ErrorKind, Result},
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     sync::Arc,
 };
 
-use async_socks5::{AddrKind, Auth, SocksDatagram, SocksDatagramRecvHalf, SocksDatagramSendHalf};
 use async_trait::async_trait;
-use futures::future::TryFutureExt;
-use tokio::io::{AsyncRead, AsyncWrite};
+use bytes::{BufMut, BytesMut};
+use futures::future::{self, Either};
+use log::*;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::udp::{RecvHalf, SendHalf};
+use tokio::net::UdpSocket;
+use tokio::sync::oneshot;
 
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
         OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf,
-        OutboundTransport, TcpConnector, UdpConnector, UdpOutboundHandler, UdpTransportType,
+        OutboundTransport, TcpConnector, TcpOptions, UdpConnector, UdpOutboundHandler,
+        UdpTransportType,
     },
-    session::{Session, SocksAddr},
+    session::{Session, SocksAddr, SocksAddrWireType},
 };
 
 pub struct Handler {
     pub address: String,
     pub port: u16,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
-impl UdpConnector for Handler {}
+impl Handler {
+    async fn resolve(&self, host: &str) -> Result<IpAddr> {
+        let ips = self
+            .dns_client
+            .lookup_with_bind(host.to_owned(), &self.bind_addr)
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("lookup {} failed: {}", host, e)))?;
+        ips.first()
+            .copied()
+            .ok_or_else(|| Error::new(ErrorKind::Other, format!("no ips for {}", host)))
+    }
+}
+
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
+impl UdpConnector for Handler {
+    fn bind_interface(&self) -> Option<&str> {
+        self.tcp_options.interface.as_deref()
+    }
+}
 
 #[async_trait]
 impl UdpOutboundHandler for Handler {
@@ -51,8 +80,8 @@ impl UdpOutboundHandler for Handler {
         _sess: &'a Session,
         _transport: Option<OutboundTransport>,
     ) -> Result<Box<dyn OutboundDatagram>> {
-        // TODO support chaining, this requires implementing our own socks5 client
-        let stream = self
+        // TODO support chaining
+        let mut stream = self
             .dial_tcp_stream(
                 self.dns_client.clone(),
                 &self.bind_addr,
@@ -60,16 +89,94 @@ impl UdpOutboundHandler for Handler {
                 &self.port,
             )
             .await?;
+        let relay_addr = match request_association(&mut stream).await? {
+            // The relay is on the server.
+            SocksAddr::Ip(addr) if addr.ip().is_unspecified() => {
+                SocketAddr::new(self.resolve(&self.address).await?, addr.port())
+            }
+            SocksAddr::Ip(addr) => addr,
+            SocksAddr::Domain(domain, port) => SocketAddr::new(self.resolve(&domain).await?, port),
+        };
+        debug!("socks udp association relays on {}", &relay_addr);
         let socket = self.create_udp_socket(&self.bind_addr).await?;
-        let socket = SocksDatagram::associate(stream, socket, None::<Auth>, None::<AddrKind>)
-            .map_err(|x| Error::new(ErrorKind::Other, x))
-            .await?;
-        Ok(Box::new(Datagram { socket }))
+        Ok(Box::new(Datagram::new(stream, socket, relay_addr)))
     }
 }
 
+// Requests a UDP association without authentication, returns the relay
+// address from the reply.
+async fn request_association<S>(stream: &mut S) -> Result<SocksAddr>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = [0u8; 3];
+    stream.write_all(&[0x05, 0x01, 0x00]).await?;
+    stream.read_exact(&mut buf[..2]).await?;
+    if buf[0] != 0x05 || buf[1] != 0x00 {
+        return Err(Error::new(
+            ErrorKind::Other,
+            "no acceptable socks5 authentication methods",
+        ));
+    }
+    // The client address is not known before sending, e.g. behind NAT.
+    let mut req = BytesMut::new();
+    req.put_slice(&[0x05, 0x03, 0x00]);
+    SocksAddr::empty_ipv4().write_buf(&mut req, SocksAddrWireType::PortLast)?;
+    stream.write_all(&req).await?;
+    stream.read_exact(&mut buf).await?;
+    if buf[0] != 0x05 {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("unknown socks version {}", buf[0]),
+        ));
+    }
+    if buf[1] != 0x00 {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("socks5 udp associate failed with reply {}", buf[1]),
+        ));
+    }
+    SocksAddr::read_from(stream, SocksAddrWireType::PortLast).await
+}
+
+/// A UDP association, which lasts as long as the control connection. The
+/// control connection is held open until both halves are dropped.
 pub struct Datagram<S> {
-    pub socket: SocksDatagram<S>,
+    control: S,
+    socket: UdpSocket,
+    relay_addr: SocketAddr,
+}
+
+impl<S> Datagram<S> {
+    pub fn new(control: S, socket: UdpSocket, relay_addr: SocketAddr) -> Self {
+        Datagram {
+            control,
+            socket,
+            relay_addr,
+        }
+    }
+}
+
+// Holds the control connection until the halves are dropped, or the server
+// closes it which ends the association.
+async fn hold_control<S>(
+    mut control: S,
+    dropped: oneshot::Receiver<()>,
+    closed: oneshot::Sender<()>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = [0u8; 1];
+    // Nothing is expected from the server, EOF or an error closes it.
+    let closed_by_server = match future::select(Box::pin(control.read(&mut buf)), dropped).await {
+        Either::Left(_) => true,
+        Either::Right(_) => false,
+    };
+    if closed_by_server {
+        let _ = closed.send(());
+    } else {
+        let _ = control.shutdown().await;
+    }
 }
 
 impl<S> OutboundDatagram for Datagram<S>
@@ -82,54 +189,226 @@ where
         Box<dyn OutboundDatagramRecvHalf>,
         Box<dyn OutboundDatagramSendHalf>,
     ) {
+        let (dropped_tx, dropped_rx) = oneshot::channel();
+        let (closed_tx, closed_rx) = oneshot::channel();
+        tokio::spawn(hold_control(self.control, dropped_rx, closed_tx));
+        let guard = Arc::new(dropped_tx);
         let (rh, sh) = self.socket.split();
         (
-            Box::new(DatagramRecvHalf(rh)),
-            Box::new(DatagramSendHalf(sh)),
+            Box::new(DatagramRecvHalf {
+                socket: rh,
+                relay_addr: self.relay_addr,
+                closed: Some(closed_rx),
+                _guard: guard.clone(),
+            }),
+            Box::new(DatagramSendHalf {
+                socket: sh,
+                relay_addr: self.relay_addr,
+                _guard: guard,
+            }),
         )
     }
 }
 
-pub struct DatagramRecvHalf<S>(SocksDatagramRecvHalf<S>);
+pub struct DatagramRecvHalf {
+    socket: RecvHalf,
+    relay_addr: SocketAddr,
+    closed: Option<oneshot::Receiver<()>>,
+    _guard: Arc<oneshot::Sender<()>>,
+}
 
 #[async_trait]
-impl<S> OutboundDatagramRecvHalf for DatagramRecvHalf<S>
-where
-    S: 'static + AsyncRead + AsyncWrite + Send + Unpin + Sync,
-{
+impl OutboundDatagramRecvHalf for DatagramRecvHalf {
     async fn recv_from(&mut self, buf: &mut [u8]) -> Result<(usize, SocksAddr)> {
-        let (n, addr) = self
-            .0
-            .recv_from(buf)
-            .map_err(|x| Error::new(ErrorKind::Other, x))
-            .await?;
-        match addr {
-            AddrKind::Ip(addr) => Ok((n, SocksAddr::Ip(addr))),
-            AddrKind::Domain(domain, port) => Ok((n, SocksAddr::Domain(domain, port))),
+        let mut recv_buf = [0u8; 2 * 1024];
+        loop {
+            let closed = match self.closed.as_mut() {
+                Some(v) => v,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::ConnectionAborted,
+                        "socks udp association closed",
+                    ))
+                }
+            };
+            let res = match future::select(Box::pin(self.socket.recv_from(&mut recv_buf)), closed)
+                .await
+            {
+                Either::Left((res, _)) => Some(res),
+                Either::Right(_) => None,
+            };
+            let (n, from) = match res {
+                Some(res) => res?,
+                None => {
+                    self.closed = None;
+                    continue;
+                }
+            };
+            if from != self.relay_addr {
+                debug!("dropped udp packet from {} not the relay", &from);
+                continue;
+            }
+            if n < 3 {
+                debug!("dropped short udp packet");
+                continue;
+            }
+            if recv_buf[2] != 0 {
+                debug!("dropped fragmented udp packet");
+                continue;
+            }
+            let addr = match SocksAddr::try_from((&recv_buf[3..n], SocksAddrWireType::PortLast)) {
+                Ok(v) => v,
+                Err(e) => {
+                    debug!("dropped udp packet: read address failed: {}", e);
+                    continue;
+                }
+            };
+            let header_size = 3 + addr.size();
+            let payload_size = n - header_size;
+            let to_recv = min(buf.len(), payload_size);
+            if to_recv < payload_size {
+                warn!("truncated pkt");
+            }
+            buf[..to_recv].copy_from_slice(&recv_buf[header_size..header_size + to_recv]);
+            return Ok((to_recv, addr));
         }
     }
 }
 
-pub struct DatagramSendHalf<S>(SocksDatagramSendHalf<S>);
+pub struct DatagramSendHalf {
+    socket: SendHalf,
+    relay_addr: SocketAddr,
+    _guard: Arc<oneshot::Sender<()>>,
+}
 
 #[async_trait]
-impl<S> OutboundDatagramSendHalf for DatagramSendHalf<S>
-where
-    S: 'static + AsyncRead + AsyncWrite + Send + Unpin + Sync,
-{
+impl OutboundDatagramSendHalf for DatagramSendHalf {
     async fn send_to(&mut self, buf: &[u8], target: &SocksAddr) -> Result<usize> {
-        match target {
-            SocksAddr::Ip(a) => {
-                self.0
-                    .send_to(buf, a.to_owned())
-                    .map_err(|x| Error::new(ErrorKind::Other, x))
-                    .await
+        let mut data = BytesMut::with_capacity(3 + target.size() + buf.len());
+        data.put_u16(0); // rsv
+        data.put_u8(0); // frag
+        target.write_buf(&mut data, SocksAddrWireType::PortLast)?;
+        data.put_slice(buf);
+        self.socket.send_to(&data, &self.relay_addr).await?;
+        Ok(buf.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    // Accepts a UDP association, the relay is reported as unspecified.
+    async fn accept_association(listener: &mut TcpListener) -> (TcpStream, UdpSocket) {
+        let (mut control, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 10];
+        control.read_exact(&mut buf[..3]).await.unwrap();
+        assert_eq!(&buf[..3], &[0x05, 0x01, 0x00]);
+        control.write_all(&[0x05, 0x00]).await.unwrap();
+        control.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf[..3], &[0x05, 0x03, 0x00]);
+        let relay = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = relay.local_addr().unwrap().port().to_be_bytes();
+        control
+            .write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, port[0], port[1]])
+            .await
+            .unwrap();
+        (control, relay)
+    }
+
+    async fn associate(server_addr: SocketAddr) -> Box<dyn OutboundDatagram> {
+        let mut stream = TcpStream::connect(server_addr).await.unwrap();
+        let relay_addr = match request_association(&mut stream).await.unwrap() {
+            SocksAddr::Ip(addr) if addr.ip().is_unspecified() => {
+                SocketAddr::new(server_addr.ip(), addr.port())
             }
-            // FIXME for this, we need our own socks5 impl
-            _ => Err(Error::new(
-                ErrorKind::Other,
-                "socks outbound does not support sending UDP packets to domain address",
-            )),
-        }
+            addr => panic!("unexpected relay address {}", addr),
+        };
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        Box::new(Datagram::new(stream, socket, relay_addr))
+    }
+
+    #[test]
+    fn test_udp_association() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = listener.local_addr().unwrap();
+            let (eof_tx, mut eof_rx) = oneshot::channel();
+            // A strict server, which relays until the control connection
+            // closes, echoing the packets back.
+            tokio::spawn(async move {
+                let (mut control, mut relay) = accept_association(&mut listener).await;
+                let mut buf = [0u8; 1];
+                let mut pkt = [0u8; 64];
+                loop {
+                    let recv = Box::pin(relay.recv_from(&mut pkt));
+                    let res = match future::select(recv, Box::pin(control.read(&mut buf))).await {
+                        Either::Left((res, _)) => Either::Left(res.unwrap()),
+                        Either::Right((res, _)) => Either::Right(res.unwrap()),
+                    };
+                    match res {
+                        Either::Left((n, from)) => {
+                            relay.send_to(&pkt[..n], &from).await.unwrap();
+                        }
+                        Either::Right(n) => {
+                            assert_eq!(n, 0);
+                            break;
+                        }
+                    }
+                }
+                eof_tx.send(()).unwrap();
+            });
+
+            let (mut r, mut s) = associate(server_addr).await.split();
+            let target = SocksAddr::Domain("example.com".to_string(), 53);
+            assert_eq!(s.send_to(b"ping", &target).await.unwrap(), 4);
+            let mut buf = [0u8; 64];
+            let (n, from) = r.recv_from(&mut buf).await.unwrap();
+            assert_eq!(&buf[..n], b"ping");
+            assert_eq!(from.to_string(), target.to_string());
+
+            // The control connection is held until both halves are dropped.
+            drop(r);
+            assert!(
+                tokio::time::timeout(Duration::from_millis(100), &mut eof_rx)
+                    .await
+                    .is_err()
+            );
+            assert_eq!(s.send_to(b"ping", &target).await.unwrap(), 4);
+            drop(s);
+            eof_rx.await.unwrap();
+        });
+    }
+
+    #[test]
+    fn test_udp_association_closed_by_server() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (control, relay) = accept_association(&mut listener).await;
+                drop(control);
+                drop(relay);
+            });
+
+            let (mut r, _s) = associate(server_addr).await.split();
+            let mut buf = [0u8; 64];
+            assert!(r.recv_from(&mut buf).await.is_err());
+            assert!(r.recv_from(&mut buf).await.is_err());
+        });
     }
 }
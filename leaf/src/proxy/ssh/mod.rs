@@ -0,0 +1,6 @@
+mod stream;
+pub mod tcp;
+
+pub use tcp::{Auth, Handler as TcpHandler};
+
+pub static NAME: &str = "ssh";
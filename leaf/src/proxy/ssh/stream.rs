@@ -0,0 +1,272 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use futures::ready;
+use thrussh::{client::Channel, ChannelMsg};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+
+use crate::proxy::ProxyStream;
+
+/// What the stream gets from a channel.
+#[derive(Debug)]
+pub enum ChannelEvent {
+    Data(Vec<u8>),
+    Eof,
+    Close,
+}
+
+/// The operations of a channel the stream is driven by.
+#[async_trait]
+pub trait RawChannel: Send + 'static {
+    /// Returns `None` once the connection is gone.
+    async fn recv(&mut self) -> Option<ChannelEvent>;
+    async fn send(&mut self, data: &[u8]) -> io::Result<()>;
+    async fn send_eof(&mut self) -> io::Result<()>;
+}
+
+#[async_trait]
+impl RawChannel for Channel {
+    async fn recv(&mut self) -> Option<ChannelEvent> {
+        loop {
+            match self.wait().await? {
+                ChannelMsg::Data { data } => return Some(ChannelEvent::Data(data.to_vec())),
+                ChannelMsg::Eof => return Some(ChannelEvent::Eof),
+                ChannelMsg::Close => return Some(ChannelEvent::Close),
+                _ => (),
+            }
+        }
+    }
+
+    async fn send(&mut self, data: &[u8]) -> io::Result<()> {
+        self.data(data).await.map_err(super::tcp::to_io)
+    }
+
+    async fn send_eof(&mut self) -> io::Result<()> {
+        self.eof().await.map_err(super::tcp::to_io)
+    }
+}
+
+/// A direct-tcpip channel as a stream. The channel is driven by a task, as
+/// both receiving and sending on it need exclusive access.
+pub struct ChannelStream {
+    read_rx: mpsc::Receiver<Vec<u8>>,
+    read_buf: Vec<u8>,
+    read_pos: usize,
+    // An empty buffer sends EOF.
+    write_tx: mpsc::Sender<Vec<u8>>,
+}
+
+impl ProxyStream for ChannelStream {}
+
+impl ChannelStream {
+    pub fn new<C: RawChannel>(channel: C) -> Self {
+        let (read_tx, read_rx) = mpsc::channel(16);
+        let (write_tx, write_rx) = mpsc::channel(16);
+        tokio::spawn(drive(channel, read_tx, write_rx));
+        ChannelStream {
+            read_rx,
+            read_buf: Vec::new(),
+            read_pos: 0,
+            write_tx,
+        }
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, data: Vec<u8>) -> Poll<io::Result<()>> {
+        ready!(self.write_tx.poll_ready(cx))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        self.write_tx
+            .try_send(data)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        Poll::Ready(Ok(()))
+    }
+}
+
+// Runs until the channel is closed, or both sides have sent EOF. Either
+// side may send EOF first and keep the other direction open.
+async fn drive<C: RawChannel>(
+    mut channel: C,
+    read_tx: mpsc::Sender<Vec<u8>>,
+    mut write_rx: mpsc::Receiver<Vec<u8>>,
+) {
+    // Dropped on EOF from the server, the stream reads EOF then.
+    let mut read_tx = Some(read_tx);
+    let mut writing = true;
+    loop {
+        tokio::select! {
+            event = channel.recv() => match event {
+                Some(ChannelEvent::Data(data)) => {
+                    if let Some(tx) = read_tx.as_mut() {
+                        if tx.send(data).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Some(ChannelEvent::Eof) => {
+                    read_tx.take();
+                    if !writing {
+                        break;
+                    }
+                }
+                Some(ChannelEvent::Close) | None => break,
+            },
+            data = write_rx.recv(), if writing => match data {
+                Some(data) if !data.is_empty() => {
+                    if channel.send(&data[..]).await.is_err() {
+                        break;
+                    }
+                }
+                Some(_) => {
+                    // Half closed, the server may still send.
+                    writing = false;
+                    let _ = channel.send_eof().await;
+                    if read_tx.is_none() {
+                        break;
+                    }
+                }
+                // The stream is dropped.
+                None => break,
+            },
+        }
+    }
+}
+
+impl AsyncRead for ChannelStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.read_pos == self.read_buf.len() {
+            match ready!(self.read_rx.poll_recv(cx)) {
+                Some(data) => {
+                    self.read_buf = data;
+                    self.read_pos = 0;
+                }
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+        let me = &mut *self;
+        let n = std::cmp::min(buf.len(), me.read_buf.len() - me.read_pos);
+        buf[..n].copy_from_slice(&me.read_buf[me.read_pos..me.read_pos + n]);
+        me.read_pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncWrite for ChannelStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        ready!(self.poll_send(cx, buf.to_vec()))?;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match ready!(self.poll_send(cx, Vec::new())) {
+            // Already closed by the server.
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Poll::Ready(Ok(())),
+            res => Poll::Ready(res),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Sent {
+        Data(Vec<u8>),
+        Eof,
+    }
+
+    // Scripted events from the server, records what the stream sends.
+    struct FakeChannel {
+        events: mpsc::UnboundedReceiver<ChannelEvent>,
+        sent: mpsc::UnboundedSender<Sent>,
+    }
+
+    #[async_trait]
+    impl RawChannel for FakeChannel {
+        async fn recv(&mut self) -> Option<ChannelEvent> {
+            self.events.recv().await
+        }
+
+        async fn send(&mut self, data: &[u8]) -> io::Result<()> {
+            let _ = self.sent.send(Sent::Data(data.to_vec()));
+            Ok(())
+        }
+
+        async fn send_eof(&mut self) -> io::Result<()> {
+            let _ = self.sent.send(Sent::Eof);
+            Ok(())
+        }
+    }
+
+    fn new_stream() -> (
+        ChannelStream,
+        mpsc::UnboundedSender<ChannelEvent>,
+        mpsc::UnboundedReceiver<Sent>,
+    ) {
+        let (events_tx, events) = mpsc::unbounded_channel();
+        let (sent, sent_rx) = mpsc::unbounded_channel();
+        let stream = ChannelStream::new(FakeChannel { events, sent });
+        (stream, events_tx, sent_rx)
+    }
+
+    #[test]
+    fn test_half_close() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            // The server sends EOF first, the stream keeps writing.
+            let (mut stream, events, mut sent) = new_stream();
+            events
+                .send(ChannelEvent::Data(b"response".to_vec()))
+                .unwrap();
+            events.send(ChannelEvent::Eof).unwrap();
+            let mut buf = Vec::new();
+            stream.read_to_end(&mut buf).await.unwrap();
+            assert_eq!(buf, b"response");
+            stream.write_all(b"request").await.unwrap();
+            assert_eq!(sent.recv().await, Some(Sent::Data(b"request".to_vec())));
+            stream.shutdown().await.unwrap();
+            assert_eq!(sent.recv().await, Some(Sent::Eof));
+            // Both sides are closed, the channel is dropped.
+            assert_eq!(sent.recv().await, None);
+
+            // The stream sends EOF first, the server keeps sending.
+            let (mut stream, events, mut sent) = new_stream();
+            stream.write_all(b"request").await.unwrap();
+            stream.shutdown().await.unwrap();
+            assert_eq!(sent.recv().await, Some(Sent::Data(b"request".to_vec())));
+            assert_eq!(sent.recv().await, Some(Sent::Eof));
+            events
+                .send(ChannelEvent::Data(b"response".to_vec()))
+                .unwrap();
+            events.send(ChannelEvent::Close).unwrap();
+            let mut buf = Vec::new();
+            stream.read_to_end(&mut buf).await.unwrap();
+            assert_eq!(buf, b"response");
+        });
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::future::{ready, Ready};
+use log::*;
+use thrussh::client;
+use thrussh_keys::key;
+
+use crate::{
+    app::dns_client::DnsClient,
+    proxy::{OutboundConnect, ProxyStream, TcpConnector, TcpOptions, TcpOutboundHandler},
+    session::{Session, SocksAddr},
+};
+
+use super::stream::ChannelStream;
+
+// Idle connections kept for reuse, a connection opened while all of them are
+// busy is not kept once its channel is opened.
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+pub(super) fn to_io(e: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("ssh: {}", e))
+}
+
+pub enum Auth {
+    Password(String),
+    PublicKey(Arc<key::KeyPair>),
+}
+
+// Verifies the host key of the server.
+struct Client {
+    host_key_fingerprint: Option<String>,
+}
+
+impl client::Handler for Client {
+    type Error = thrussh::Error;
+    type FutureUnit = Ready<Result<(Self, client::Session), Self::Error>>;
+    type FutureBool = Ready<Result<(Self, bool), Self::Error>>;
+
+    fn finished_bool(self, b: bool) -> Self::FutureBool {
+        ready(Ok((self, b)))
+    }
+
+    fn finished(self, session: client::Session) -> Self::FutureUnit {
+        ready(Ok((self, session)))
+    }
+
+    fn check_server_key(self, server_public_key: &key::PublicKey) -> Self::FutureBool {
+        let ok = match &self.host_key_fingerprint {
+            Some(expected) => {
+                let fingerprint = server_public_key.fingerprint();
+                let ok = expected.trim_start_matches("SHA256:") == fingerprint;
+                if !ok {
+                    warn!("ssh host key mismatch: SHA256:{}", fingerprint);
+                }
+                ok
+            }
+            None => {
+                warn!(
+                    "ssh host key SHA256:{} accepted without verification",
+                    server_public_key.fingerprint()
+                );
+                true
+            }
+        };
+        self.finished_bool(ok)
+    }
+}
+
+/// Proxies TCP connections through direct-tcpip channels on shared SSH
+/// connections. A connection is taken from the idle ones to open a channel,
+/// so opening channels concurrently doesn't wait for each other, and put
+/// back once the channel is opened. A new connection is established if
+/// there are no idle ones, at most `MAX_IDLE_CONNECTIONS` are kept.
+pub struct Handler {
+    pub address: String,
+    pub port: u16,
+    pub username: String,
+    pub auth: Auth,
+    pub host_key_fingerprint: Option<String>,
+    pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
+    pub dns_client: Arc<DnsClient>,
+    pub connections: Mutex<Vec<client::Handle>>,
+}
+
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
+
+impl Handler {
+    fn put_idle(&self, handle: client::Handle) {
+        let mut connections = self.connections.lock().unwrap();
+        if connections.len() < MAX_IDLE_CONNECTIONS {
+            connections.push(handle);
+        }
+    }
+
+    async fn connect(&self) -> io::Result<client::Handle> {
+        let stream = self
+            .dial_tcp_stream(
+                self.dns_client.clone(),
+                &self.bind_addr,
+                &self.address,
+                &self.port,
+            )
+            .await?;
+        let config = Arc::new(client::Config::default());
+        let client = Client {
+            host_key_fingerprint: self.host_key_fingerprint.clone(),
+        };
+        let mut handle = client::connect_stream(config, stream, client)
+            .await
+            .map_err(to_io)?;
+        let authenticated = match &self.auth {
+            Auth::Password(password) => {
+                handle
+                    .authenticate_password(self.username.clone(), password.clone())
+                    .await
+            }
+            Auth::PublicKey(key) => {
+                handle
+                    .authenticate_publickey(self.username.clone(), key.clone())
+                    .await
+            }
+        }
+        .map_err(to_io)?;
+        if !authenticated {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "ssh: authentication failed",
+            ));
+        }
+        debug!("ssh connected to {}:{}", &self.address, self.port);
+        Ok(handle)
+    }
+}
+
+#[async_trait]
+impl TcpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    async fn handle_tcp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _stream: Option<Box<dyn ProxyStream>>,
+    ) -> io::Result<Box<dyn ProxyStream>> {
+        let (host, port) = match &sess.destination {
+            SocksAddr::Ip(addr) => (addr.ip().to_string(), addr.port()),
+            SocksAddr::Domain(domain, port) => (domain.to_owned(), *port),
+        };
+        loop {
+            // The lock is not held while connecting or opening the channel.
+            let idle = self.connections.lock().unwrap().pop();
+            let reused = idle.is_some();
+            let mut handle = match idle {
+                Some(h) => h,
+                None => self.connect().await?,
+            };
+            match handle
+                .channel_open_direct_tcpip(
+                    host.clone(),
+                    port as u32,
+                    sess.source.ip().to_string(),
+                    sess.source.port() as u32,
+                )
+                .await
+            {
+                Ok(channel) => {
+                    self.put_idle(handle);
+                    return Ok(Box::new(ChannelStream::new(channel)));
+                }
+                // Refused by the server, the connection is fine.
+                Err(e @ thrussh::Error::ChannelOpenFailure(_)) => {
+                    self.put_idle(handle);
+                    return Err(to_io(e));
+                }
+                // A reused connection may have been closed by the server,
+                // it's dropped and the next one is tried.
+                Err(e) => {
+                    debug!("ssh open channel to {}:{} failed: {}", &host, port, e);
+                    if !reused {
+                        return Err(to_io(e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use thrussh::client::Handler as _;
+
+    use super::*;
+
+    fn check(host_key_fingerprint: Option<String>, key: &key::PublicKey) -> bool {
+        let client = Client {
+            host_key_fingerprint,
+        };
+        block_on(client.check_server_key(key)).unwrap().1
+    }
+
+    #[test]
+    fn test_check_server_key() {
+        let key = key::KeyPair::generate_ed25519().unwrap().clone_public_key();
+        let fingerprint = key.fingerprint();
+        assert!(check(None, &key));
+        assert!(check(Some(fingerprint.clone()), &key));
+        assert!(check(Some(format!("SHA256:{}", &fingerprint)), &key));
+
+        let other = key::KeyPair::generate_ed25519().unwrap().clone_public_key();
+        assert!(!check(Some(fingerprint.clone()), &other));
+        assert!(!check(Some(format!("SHA256:{}", &fingerprint)), &other));
+    }
+}
@@ -0,0 +1,63 @@
+use std::{net::IpAddr, sync::Mutex, time::Duration};
+
+use lru_time_cache::LruCache;
+
+use crate::session::Session;
+
+// Clients remembered at most, the least recently seen are dropped first.
+const STICKY_CAPACITY: usize = 4096;
+
+/// Remembers the actor picked for a source IP, so later connections from the
+/// same client go through the same actor. An entry expires once no
+/// connection from the client is seen for the timeout, the client is then
+/// balanced again.
+pub struct StickyTable {
+    cache: Mutex<LruCache<IpAddr, usize>>,
+}
+
+impl StickyTable {
+    pub fn new(timeout: Duration) -> Self {
+        StickyTable {
+            cache: Mutex::new(LruCache::with_expiry_duration_and_capacity(
+                timeout,
+                STICKY_CAPACITY,
+            )),
+        }
+    }
+
+    /// Returns the actor remembered for the source of the session, or the
+    /// one returned by `pick` which is then remembered.
+    pub fn pick<F>(&self, sess: &Session, pick: F) -> usize
+    where
+        F: FnOnce() -> usize,
+    {
+        let mut cache = self.cache.lock().unwrap();
+        let source = sess.source.ip();
+        if let Some(i) = cache.get(&source) {
+            return *i;
+        }
+        let i = pick();
+        cache.insert(source, i);
+        i
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sticky_table() {
+        let table = StickyTable::new(Duration::from_millis(50));
+        let mut sess = Session::default();
+        sess.source = "10.0.0.1:1000".parse().unwrap();
+        assert_eq!(table.pick(&sess, || 1), 1);
+        sess.source = "10.0.0.1:2000".parse().unwrap();
+        assert_eq!(table.pick(&sess, || 2), 1);
+        sess.source = "10.0.0.2:1000".parse().unwrap();
+        assert_eq!(table.pick(&sess, || 2), 2);
+        std::thread::sleep(Duration::from_millis(100));
+        sess.source = "10.0.0.1:1000".parse().unwrap();
+        assert_eq!(table.pick(&sess, || 0), 0);
+    }
+}
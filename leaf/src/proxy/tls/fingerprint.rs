@@ -0,0 +1,150 @@
+use std::{fmt, io, str::FromStr};
+
+/// A browser whose ClientHello is mimicked. Only the cipher suite order and
+/// the default ALPN list are applied, the TLS libraries don't allow setting
+/// the extension order or sending GREASE values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fingerprint {
+    Chrome,
+    Firefox,
+    Safari,
+}
+
+/// Cipher suites available to both rustls and openssl.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CipherSuite {
+    Tls13Aes128GcmSha256,
+    Tls13Aes256GcmSha384,
+    Tls13Chacha20Poly1305Sha256,
+    EcdheEcdsaAes128GcmSha256,
+    EcdheEcdsaAes256GcmSha384,
+    EcdheEcdsaChacha20Poly1305,
+    EcdheRsaAes128GcmSha256,
+    EcdheRsaAes256GcmSha384,
+    EcdheRsaChacha20Poly1305,
+}
+
+impl CipherSuite {
+    pub fn is_tls13(&self) -> bool {
+        matches!(
+            self,
+            CipherSuite::Tls13Aes128GcmSha256
+                | CipherSuite::Tls13Aes256GcmSha384
+                | CipherSuite::Tls13Chacha20Poly1305Sha256
+        )
+    }
+
+    /// Returns the name used in openssl cipher lists.
+    pub fn openssl_name(&self) -> &'static str {
+        match self {
+            CipherSuite::Tls13Aes128GcmSha256 => "TLS_AES_128_GCM_SHA256",
+            CipherSuite::Tls13Aes256GcmSha384 => "TLS_AES_256_GCM_SHA384",
+            CipherSuite::Tls13Chacha20Poly1305Sha256 => "TLS_CHACHA20_POLY1305_SHA256",
+            CipherSuite::EcdheEcdsaAes128GcmSha256 => "ECDHE-ECDSA-AES128-GCM-SHA256",
+            CipherSuite::EcdheEcdsaAes256GcmSha384 => "ECDHE-ECDSA-AES256-GCM-SHA384",
+            CipherSuite::EcdheEcdsaChacha20Poly1305 => "ECDHE-ECDSA-CHACHA20-POLY1305",
+            CipherSuite::EcdheRsaAes128GcmSha256 => "ECDHE-RSA-AES128-GCM-SHA256",
+            CipherSuite::EcdheRsaAes256GcmSha384 => "ECDHE-RSA-AES256-GCM-SHA384",
+            CipherSuite::EcdheRsaChacha20Poly1305 => "ECDHE-RSA-CHACHA20-POLY1305",
+        }
+    }
+}
+
+impl Fingerprint {
+    /// Returns the cipher suites in the order the browser offers them.
+    pub fn cipher_suites(&self) -> &'static [CipherSuite] {
+        use CipherSuite::*;
+        match self {
+            Fingerprint::Chrome => &[
+                Tls13Aes128GcmSha256,
+                Tls13Aes256GcmSha384,
+                Tls13Chacha20Poly1305Sha256,
+                EcdheEcdsaAes128GcmSha256,
+                EcdheRsaAes128GcmSha256,
+                EcdheEcdsaAes256GcmSha384,
+                EcdheRsaAes256GcmSha384,
+                EcdheEcdsaChacha20Poly1305,
+                EcdheRsaChacha20Poly1305,
+            ],
+            Fingerprint::Firefox => &[
+                Tls13Aes128GcmSha256,
+                Tls13Chacha20Poly1305Sha256,
+                Tls13Aes256GcmSha384,
+                EcdheEcdsaAes128GcmSha256,
+                EcdheRsaAes128GcmSha256,
+                EcdheEcdsaChacha20Poly1305,
+                EcdheRsaChacha20Poly1305,
+                EcdheEcdsaAes256GcmSha384,
+                EcdheRsaAes256GcmSha384,
+            ],
+            Fingerprint::Safari => &[
+                Tls13Aes128GcmSha256,
+                Tls13Aes256GcmSha384,
+                Tls13Chacha20Poly1305Sha256,
+                EcdheEcdsaAes256GcmSha384,
+                EcdheEcdsaAes128GcmSha256,
+                EcdheEcdsaChacha20Poly1305,
+                EcdheRsaAes256GcmSha384,
+                EcdheRsaAes128GcmSha256,
+                EcdheRsaChacha20Poly1305,
+            ],
+        }
+    }
+
+    /// Returns the ALPN list the browser sends, used if none is configured.
+    pub fn alpns(&self) -> Vec<String> {
+        vec!["h2".to_string(), "http/1.1".to_string()]
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "chrome" => Ok(Fingerprint::Chrome),
+            "firefox" => Ok(Fingerprint::Firefox),
+            "safari" | "ios" => Ok(Fingerprint::Safari),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown tls fingerprint: {}", s),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fingerprint::Chrome => write!(f, "chrome"),
+            Fingerprint::Firefox => write!(f, "firefox"),
+            Fingerprint::Safari => write!(f, "safari"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(
+            "Chrome".parse::<Fingerprint>().unwrap(),
+            Fingerprint::Chrome
+        );
+        assert_eq!("ios".parse::<Fingerprint>().unwrap(), Fingerprint::Safari);
+        assert!("edge".parse::<Fingerprint>().is_err());
+        for fp in &[
+            Fingerprint::Chrome,
+            Fingerprint::Firefox,
+            Fingerprint::Safari,
+        ] {
+            let suites = fp.cipher_suites();
+            // TLS 1.3 suites come first, as browsers do.
+            let n = suites.iter().take_while(|s| s.is_tls13()).count();
+            assert_eq!(n, 3);
+            assert!(suites[n..].iter().all(|s| !s.is_tls13()));
+        }
+    }
+}
@@ -1,7 +1,10 @@
+pub mod fingerprint;
 pub mod tcp;
 
+pub use fingerprint::Fingerprint;
+pub use stream::ClientOptions;
 pub use tcp::Handler as TcpHandler;
 
 pub static NAME: &str = "tls";
 
-mod stream;
+pub(crate) mod stream;
@@ -4,51 +4,247 @@ use tokio::io::{AsyncRead, AsyncWrite};
 
 use crate::proxy::{ProxyStream, SimpleProxyStream};
 
+use super::fingerprint::Fingerprint;
+
+/// Options of a TLS client connection.
+#[derive(Clone, Default)]
+pub struct ClientOptions {
+    pub alpns: Vec<String>,
+    /// PEM file of certificates trusted in addition to the default ones.
+    pub ca_file: Option<String>,
+    pub fingerprint: Option<Fingerprint>,
+    /// Skips verifying the certificate chain and the server name.
+    pub insecure: bool,
+    /// SHA-256 of the server certificate in DER, the handshake fails if it
+    /// doesn't match, whether `insecure` is set or not.
+    pub cert_sha256: Option<Vec<u8>>,
+    /// Certificate chain and private key presented when the server requests
+    /// client authentication.
+    pub client_identity: Option<wrapper::ClientIdentity>,
+}
+
+impl ClientOptions {
+    /// Loads the client certificate chain and the private key from PEM files,
+    /// fails if either can't be loaded.
+    pub fn load_client_cert(&mut self, cert_file: &str, key_file: &str) -> Result<()> {
+        self.client_identity = Some(wrapper::ClientIdentity::load(cert_file, key_file)?);
+        Ok(())
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| anyhow!(format!("read {} failed: {}", path, e)))
+}
+
+#[cfg(any(feature = "rustls-tls", feature = "openssl-tls"))]
+fn check_cert_sha256(cert: &[u8], expected: &[u8]) -> bool {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(cert).as_slice() == expected
+}
+
 #[cfg(feature = "rustls-tls")]
 pub mod wrapper {
     use std::sync::Arc;
 
-    use tokio_rustls::{rustls::ClientConfig, webpki::DNSNameRef, TlsConnector};
+    use tokio_rustls::{
+        rustls::{self, internal::pemfile, ClientConfig, SupportedCipherSuite},
+        webpki::DNSNameRef,
+        TlsConnector,
+    };
 
+    use super::super::fingerprint::CipherSuite;
     use super::*;
 
-    // struct InsecureVerifier;
+    #[derive(Clone)]
+    pub struct ClientIdentity {
+        certs: Vec<rustls::Certificate>,
+        key: rustls::PrivateKey,
+    }
+
+    impl ClientIdentity {
+        pub fn load(cert_file: &str, key_file: &str) -> Result<Self> {
+            let certs = pemfile::certs(&mut &read_file(cert_file)?[..])
+                .map_err(|_| anyhow!(format!("invalid certificate file {}", cert_file)))?;
+            if certs.is_empty() {
+                return Err(anyhow!(format!("no certificates in {}", cert_file)));
+            }
+            let pem = read_file(key_file)?;
+            let mut keys = pemfile::pkcs8_private_keys(&mut &pem[..])
+                .map_err(|_| anyhow!(format!("invalid key file {}", key_file)))?;
+            if keys.is_empty() {
+                keys = pemfile::rsa_private_keys(&mut &pem[..])
+                    .map_err(|_| anyhow!(format!("invalid key file {}", key_file)))?;
+            }
+            let key = keys
+                .pop()
+                .ok_or_else(|| anyhow!(format!("no private key in {}", key_file)))?;
+            // rustls accepts any key when configured, check it's usable now.
+            rustls::sign::any_supported_type(&key)
+                .map_err(|_| anyhow!(format!("unsupported private key in {}", key_file)))?;
+            Ok(ClientIdentity { certs, key })
+        }
+    }
 
-    // impl rustls::ServerCertVerifier for InsecureVerifier {
-    //     fn verify_server_cert(
-    //         &self,
-    //         _roots: &rustls::RootCertStore,
-    //         _presented_certs: &[rustls::Certificate],
-    //         _dns_name: DNSNameRef<'_>,
-    //         _ocsp_response: &[u8],
-    //     ) -> Result<rustls::ServerCertVerified, rustls::TLSError> {
-    //         Ok(rustls::ServerCertVerified::assertion())
-    //     }
-    // }
+    struct Verifier {
+        insecure: bool,
+        cert_sha256: Option<Vec<u8>>,
+    }
+
+    impl rustls::ServerCertVerifier for Verifier {
+        fn verify_server_cert(
+            &self,
+            roots: &rustls::RootCertStore,
+            presented_certs: &[rustls::Certificate],
+            dns_name: DNSNameRef<'_>,
+            ocsp_response: &[u8],
+        ) -> Result<rustls::ServerCertVerified, rustls::TLSError> {
+            if let Some(expected) = self.cert_sha256.as_ref() {
+                let cert = presented_certs
+                    .first()
+                    .ok_or(rustls::TLSError::NoCertificatesPresented)?;
+                if !check_cert_sha256(&cert.0, expected) {
+                    return Err(rustls::TLSError::General(
+                        "certificate sha256 mismatch".to_string(),
+                    ));
+                }
+            }
+            if self.insecure {
+                return Ok(rustls::ServerCertVerified::assertion());
+            }
+            rustls::WebPKIVerifier::new().verify_server_cert(
+                roots,
+                presented_certs,
+                dns_name,
+                ocsp_response,
+            )
+        }
+    }
 
     pub async fn wrap_tls<S>(
         stream: S,
         domain: &str,
         alpns: Vec<String>,
-        // insecure: bool,
     ) -> Result<Box<dyn ProxyStream>>
     where
         S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
     {
+        let options = ClientOptions {
+            alpns,
+            ..Default::default()
+        };
+        wrap_tls_with_options(stream, domain, &options).await
+    }
+
+    /// Same as `wrap_tls`, the certificates in the PEM file are trusted in
+    /// addition to the built-in roots.
+    pub async fn wrap_tls_with_ca<S>(
+        stream: S,
+        domain: &str,
+        alpns: Vec<String>,
+        ca_file: Option<&str>,
+    ) -> Result<Box<dyn ProxyStream>>
+    where
+        S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
+    {
+        let options = ClientOptions {
+            alpns,
+            ca_file: ca_file.map(str::to_string),
+            ..Default::default()
+        };
+        wrap_tls_with_options(stream, domain, &options).await
+    }
+
+    fn rustls_suite(suite: CipherSuite) -> Option<&'static SupportedCipherSuite> {
+        let suite = match suite {
+            CipherSuite::Tls13Aes128GcmSha256 => rustls::CipherSuite::TLS13_AES_128_GCM_SHA256,
+            CipherSuite::Tls13Aes256GcmSha384 => rustls::CipherSuite::TLS13_AES_256_GCM_SHA384,
+            CipherSuite::Tls13Chacha20Poly1305Sha256 => {
+                rustls::CipherSuite::TLS13_CHACHA20_POLY1305_SHA256
+            }
+            CipherSuite::EcdheEcdsaAes128GcmSha256 => {
+                rustls::CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256
+            }
+            CipherSuite::EcdheEcdsaAes256GcmSha384 => {
+                rustls::CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384
+            }
+            CipherSuite::EcdheEcdsaChacha20Poly1305 => {
+                rustls::CipherSuite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256
+            }
+            CipherSuite::EcdheRsaAes128GcmSha256 => {
+                rustls::CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256
+            }
+            CipherSuite::EcdheRsaAes256GcmSha384 => {
+                rustls::CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384
+            }
+            CipherSuite::EcdheRsaChacha20Poly1305 => {
+                rustls::CipherSuite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256
+            }
+        };
+        rustls::ALL_CIPHERSUITES
+            .iter()
+            .find(|s| s.suite == suite)
+            .copied()
+    }
+
+    // A fingerprint only changes what's offered in the ClientHello, the
+    // certificate is verified as usual unless it's insecure.
+    fn client_config(options: &ClientOptions) -> Result<ClientConfig> {
         let mut config = ClientConfig::new();
         config
             .root_store
             .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+        if let Some(ca_file) = options.ca_file.as_ref() {
+            let file = std::fs::File::open(ca_file)
+                .map_err(|e| anyhow!(format!("open ca file {} failed: {}", ca_file, e)))?;
+            let (added, _) = config
+                .root_store
+                .add_pem_file(&mut std::io::BufReader::new(file))
+                .map_err(|_| anyhow!(format!("invalid ca file {}", ca_file)))?;
+            if added == 0 {
+                return Err(anyhow!(format!("no certificates in ca file {}", ca_file)));
+            }
+        }
 
+        let alpns = match options.fingerprint {
+            Some(fp) if options.alpns.is_empty() => fp.alpns(),
+            _ => options.alpns.clone(),
+        };
         for alpn in alpns {
             config.alpn_protocols.push(alpn.as_bytes().to_vec());
         }
+        if let Some(fp) = options.fingerprint {
+            config.ciphersuites = fp
+                .cipher_suites()
+                .iter()
+                .filter_map(|s| rustls_suite(*s))
+                .collect();
+        }
 
-        // if insecure {
-        //     let mut dangerous_config = config.dangerous();
-        //     dangerous_config.set_certificate_verifier(Arc::new(InsecureVerifier));
-        // }
+        if let Some(identity) = options.client_identity.as_ref() {
+            config.set_single_client_cert(identity.certs.clone(), identity.key.clone());
+        }
+
+        if options.insecure || options.cert_sha256.is_some() {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(Verifier {
+                    insecure: options.insecure,
+                    cert_sha256: options.cert_sha256.clone(),
+                }));
+        }
+
+        Ok(config)
+    }
 
+    pub async fn wrap_tls_with_options<S>(
+        stream: S,
+        domain: &str,
+        options: &ClientOptions,
+    ) -> Result<Box<dyn ProxyStream>>
+    where
+        S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
+    {
+        let config = client_config(options)?;
         let config = TlsConnector::from(Arc::new(config));
         let dnsname = DNSNameRef::try_from_ascii_str(domain)
             .map_err(|e| anyhow!(format!("invalid domain: {}", e)))?;
@@ -59,21 +255,127 @@ pub mod wrapper {
         // FIXME check negotiated alpn
         Ok(Box::new(SimpleProxyStream(tls_stream)))
     }
+
+    pub fn early_data_config(options: &ClientOptions) -> Result<Arc<ClientConfig>> {
+        let mut config = client_config(options)?;
+        config.enable_early_data = true;
+        Ok(Arc::new(config))
+    }
+
+    /// Returns a stream writable before the handshake completes, data written
+    /// by then are sent as TLS 1.3 early data if the session can be resumed,
+    /// and are written again after the handshake if the server rejects them.
+    pub async fn wrap_tls_early_data<S>(
+        stream: S,
+        domain: &str,
+        config: Arc<ClientConfig>,
+    ) -> Result<Box<dyn ProxyStream>>
+    where
+        S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
+    {
+        let tls_stream = connect_early_data(stream, domain, config).await?;
+        Ok(Box::new(SimpleProxyStream(tls_stream)))
+    }
+
+    // The session of the stream tells whether the early data are accepted.
+    pub(super) async fn connect_early_data<S>(
+        stream: S,
+        domain: &str,
+        config: Arc<ClientConfig>,
+    ) -> Result<tokio_rustls::client::TlsStream<S>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let connector = TlsConnector::from(config).early_data(true);
+        let dnsname = DNSNameRef::try_from_ascii_str(domain)
+            .map_err(|e| anyhow!(format!("invalid domain: {}", e)))?;
+        connector
+            .connect(dnsname, stream)
+            .map_err(|e| anyhow!(format!("tls connect failed: {}", e)))
+            .await
+    }
 }
 
 #[cfg(feature = "openssl-tls")]
 pub mod wrapper {
     use std::sync::Once;
 
-    use openssl::ssl::{SslConnector, SslMethod};
+    use openssl::{
+        pkey::{PKey, Private},
+        ssl::{SslConnector, SslMethod, SslVerifyMode},
+        x509::X509,
+    };
 
+    use super::super::fingerprint::CipherSuite;
     use super::*;
 
+    #[derive(Clone)]
+    pub struct ClientIdentity {
+        certs: Vec<X509>,
+        key: PKey<Private>,
+    }
+
+    impl ClientIdentity {
+        pub fn load(cert_file: &str, key_file: &str) -> Result<Self> {
+            let certs = X509::stack_from_pem(&read_file(cert_file)?)
+                .map_err(|e| anyhow!(format!("invalid certificate file {}: {}", cert_file, e)))?;
+            let key = PKey::private_key_from_pem(&read_file(key_file)?)
+                .map_err(|e| anyhow!(format!("invalid key file {}: {}", key_file, e)))?;
+            let cert = certs
+                .first()
+                .ok_or_else(|| anyhow!(format!("no certificates in {}", cert_file)))?;
+            let matched = cert
+                .public_key()
+                .map(|k| k.public_eq(&key))
+                .unwrap_or(false);
+            if !matched {
+                return Err(anyhow!(format!(
+                    "private key in {} doesn't match the certificate in {}",
+                    key_file, cert_file
+                )));
+            }
+            Ok(ClientIdentity { certs, key })
+        }
+    }
+
     pub async fn wrap_tls<S>(
         stream: S,
         domain: &str,
         alpns: Vec<String>,
-        // insecure: bool,
+    ) -> Result<Box<dyn ProxyStream>>
+    where
+        S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
+    {
+        let options = ClientOptions {
+            alpns,
+            ..Default::default()
+        };
+        wrap_tls_with_options(stream, domain, &options).await
+    }
+
+    /// Same as `wrap_tls`, the certificates in the PEM file are trusted in
+    /// addition to the default locations.
+    pub async fn wrap_tls_with_ca<S>(
+        stream: S,
+        domain: &str,
+        alpns: Vec<String>,
+        ca_file: Option<&str>,
+    ) -> Result<Box<dyn ProxyStream>>
+    where
+        S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
+    {
+        let options = ClientOptions {
+            alpns,
+            ca_file: ca_file.map(str::to_string),
+            ..Default::default()
+        };
+        wrap_tls_with_options(stream, domain, &options).await
+    }
+
+    pub async fn wrap_tls_with_options<S>(
+        stream: S,
+        domain: &str,
+        options: &ClientOptions,
     ) -> Result<Box<dyn ProxyStream>>
     where
         S: 'static + AsyncRead + AsyncWrite + Unpin + Sync + Send,
@@ -86,6 +388,10 @@ pub mod wrapper {
         let mut builder = SslConnector::builder(SslMethod::tls())
             .map_err(|e| anyhow!(format!("create tls builder failed: {}", e)))?;
 
+        let alpns = match options.fingerprint {
+            Some(fp) if options.alpns.is_empty() => fp.alpns(),
+            _ => options.alpns.clone(),
+        };
         if alpns.len() > 0 {
             let wire = alpns
                 .into_iter()
@@ -97,10 +403,70 @@ pub mod wrapper {
                 .map_err(|e| anyhow!(format!("set alpn failed: {}", e)))?;
         }
 
-        let config = builder
+        if let Some(fp) = options.fingerprint {
+            let (tls13, tls12): (Vec<_>, Vec<_>) =
+                fp.cipher_suites().iter().partition(|s| s.is_tls13());
+            let names = |suites: Vec<&CipherSuite>| {
+                suites
+                    .iter()
+                    .map(|s| s.openssl_name())
+                    .collect::<Vec<_>>()
+                    .join(":")
+            };
+            builder
+                .set_ciphersuites(&names(tls13))
+                .map_err(|e| anyhow!(format!("set tls 1.3 cipher suites failed: {}", e)))?;
+            builder
+                .set_cipher_list(&names(tls12))
+                .map_err(|e| anyhow!(format!("set cipher list failed: {}", e)))?;
+        }
+
+        if let Some(ca_file) = options.ca_file.as_ref() {
+            builder
+                .set_ca_file(ca_file)
+                .map_err(|e| anyhow!(format!("load ca file {} failed: {}", ca_file, e)))?;
+        }
+
+        if let Some(identity) = options.client_identity.as_ref() {
+            builder
+                .set_certificate(&identity.certs[0])
+                .map_err(|e| anyhow!(format!("set client certificate failed: {}", e)))?;
+            for cert in identity.certs.iter().skip(1) {
+                builder
+                    .add_extra_chain_cert(cert.clone())
+                    .map_err(|e| anyhow!(format!("set client certificate failed: {}", e)))?;
+            }
+            builder
+                .set_private_key(&identity.key)
+                .map_err(|e| anyhow!(format!("set client key failed: {}", e)))?;
+        }
+
+        if options.insecure || options.cert_sha256.is_some() {
+            let insecure = options.insecure;
+            let cert_sha256 = options.cert_sha256.clone();
+            builder.set_verify_callback(SslVerifyMode::PEER, move |preverified, ctx| {
+                // Called for each certificate in the chain, the server
+                // certificate is at depth 0.
+                if ctx.error_depth() == 0 {
+                    if let Some(expected) = cert_sha256.as_ref() {
+                        let matched = ctx
+                            .current_cert()
+                            .and_then(|cert| cert.to_der().ok())
+                            .map_or(false, |der| check_cert_sha256(&der, expected));
+                        if !matched {
+                            return false;
+                        }
+                    }
+                }
+                insecure || preverified
+            });
+        }
+
+        let mut config = builder
             .build()
             .configure()
             .map_err(|e| anyhow!(format!("configure tls failed: {}", e)))?;
+        config.set_verify_hostname(!options.insecure);
         let stream = tokio_openssl::connect(config, domain, stream)
             .map_err(|_| anyhow!(format!("connect tls failed")))
             .await?;
@@ -123,4 +489,175 @@ mod tests {
             .concat();
         assert_eq!(wire, expected);
     }
+
+    #[cfg(any(feature = "rustls-tls", feature = "openssl-tls"))]
+    #[test]
+    fn test_check_cert_sha256() {
+        let expected =
+            hex::decode("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+                .unwrap();
+        assert!(super::check_cert_sha256(b"hello", &expected));
+        assert!(!super::check_cert_sha256(b"hello!", &expected));
+    }
+
+    #[cfg(feature = "rustls-tls")]
+    fn testdata(name: &str) -> String {
+        format!(
+            "{}/src/proxy/tls/testdata/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        )
+    }
+
+    #[cfg(feature = "rustls-tls")]
+    fn set_server_cert(config: &mut tokio_rustls::rustls::ServerConfig) {
+        use tokio_rustls::rustls::internal::pemfile;
+
+        let certs =
+            pemfile::certs(&mut &std::fs::read(testdata("server.pem")).unwrap()[..]).unwrap();
+        let mut keys =
+            pemfile::pkcs8_private_keys(&mut &std::fs::read(testdata("server.key")).unwrap()[..])
+                .unwrap();
+        config.set_single_cert(certs, keys.remove(0)).unwrap();
+    }
+
+    #[cfg(feature = "rustls-tls")]
+    #[test]
+    fn test_client_cert() {
+        use std::sync::Arc;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::{TcpListener, TcpStream};
+        use tokio_rustls::{
+            rustls::{AllowAnyAuthenticatedClient, RootCertStore, ServerConfig},
+            TlsAcceptor,
+        };
+
+        use super::{wrapper, ClientOptions};
+
+        let path = testdata;
+
+        let mut roots = RootCertStore::empty();
+        roots
+            .add_pem_file(&mut &std::fs::read(path("ca.pem")).unwrap()[..])
+            .unwrap();
+        let mut server_config = ServerConfig::new(AllowAnyAuthenticatedClient::new(roots));
+        set_server_cert(&mut server_config);
+        let acceptor = TlsAcceptor::from(Arc::new(server_config));
+
+        let mut options = ClientOptions {
+            ca_file: Some(path("ca.pem")),
+            ..Default::default()
+        };
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        let mut handshake = |options: ClientOptions| {
+            let acceptor = acceptor.clone();
+            rt.block_on(async move {
+                let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let addr = listener.local_addr().unwrap();
+                let server = tokio::spawn(async move {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    let mut stream = acceptor.accept(stream).await?;
+                    stream.write_all(b"hello").await?;
+                    stream.flush().await
+                });
+                let stream = TcpStream::connect(addr).await.unwrap();
+                let client = async move {
+                    let mut stream =
+                        wrapper::wrap_tls_with_options(stream, "localhost", &options).await?;
+                    let mut buf = [0u8; 5];
+                    stream.read_exact(&mut buf).await?;
+                    Ok::<_, anyhow::Error>(buf)
+                };
+                let client = client.await;
+                (server.await.unwrap().is_ok(), client.ok())
+            })
+        };
+
+        // The server rejects the handshake without a client certificate.
+        let (server_ok, client) = handshake(options.clone());
+        assert!(!server_ok);
+        assert!(client.is_none());
+
+        options
+            .load_client_cert(&path("client.pem"), &path("client.key"))
+            .unwrap();
+        let (server_ok, client) = handshake(options);
+        assert!(server_ok);
+        assert_eq!(&client.unwrap(), b"hello");
+
+        // A certificate is not a private key.
+        assert!(ClientOptions::default()
+            .load_client_cert(&path("client.pem"), &path("client.pem"))
+            .is_err());
+    }
+
+    #[cfg(feature = "rustls-tls")]
+    #[test]
+    fn test_early_data() {
+        use std::sync::Arc;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::{TcpListener, TcpStream};
+        use tokio_rustls::{
+            rustls::{self, NoClientAuth, ServerConfig},
+            TlsAcceptor,
+        };
+
+        use super::{wrapper, ClientOptions};
+
+        let mut server_config = ServerConfig::new(NoClientAuth::new());
+        set_server_cert(&mut server_config);
+        // Tickets allowing early data are issued for resumption.
+        server_config.ticketer = rustls::Ticketer::new();
+        server_config.max_early_data_size = 1024;
+        let acceptor = TlsAcceptor::from(Arc::new(server_config));
+
+        let options = ClientOptions {
+            ca_file: Some(testdata("ca.pem")),
+            ..Default::default()
+        };
+        // Shared by the connections, as the sessions are resumed from it.
+        let config = wrapper::early_data_config(&options).unwrap();
+
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    let mut stream = acceptor.accept(stream).await.unwrap();
+                    let mut buf = [0u8; 5];
+                    stream.read_exact(&mut buf).await.unwrap();
+                    assert_eq!(&buf, b"hello");
+                    stream.write_all(b"world").await.unwrap();
+                    stream.flush().await.unwrap();
+                }
+            });
+
+            // The first connection takes a full handshake, the second one
+            // resumes the session and sends the request as early data.
+            for accepted in &[false, true] {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                let mut stream = wrapper::connect_early_data(stream, "localhost", config.clone())
+                    .await
+                    .unwrap();
+                stream.write_all(b"hello").await.unwrap();
+                stream.flush().await.unwrap();
+                let mut buf = [0u8; 5];
+                stream.read_exact(&mut buf).await.unwrap();
+                assert_eq!(&buf, b"world");
+                assert_eq!(stream.get_ref().1.is_early_data_accepted(), *accepted);
+            }
+        });
+    }
 }
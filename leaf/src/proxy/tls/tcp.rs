@@ -9,11 +9,60 @@ use crate::{
     session::Session,
 };
 
-use super::stream;
+use super::stream::{self, ClientOptions};
 
 pub struct Handler {
-    pub server_name: String,
-    pub alpns: Vec<String>,
+    server_name: String,
+    options: ClientOptions,
+    #[cfg(feature = "rustls-tls")]
+    early_data_config: Option<std::sync::Arc<tokio_rustls::rustls::ClientConfig>>,
+}
+
+impl Handler {
+    pub fn new(server_name: String, options: ClientOptions, early_data: bool) -> Self {
+        if options.insecure {
+            warn!(
+                "INSECURE: tls certificate verification is disabled for [{}], don't use it in production",
+                &server_name
+            );
+        }
+        // The config must be shared across connections to resume sessions,
+        // which is required for sending early data.
+        #[cfg(feature = "rustls-tls")]
+        let early_data_config = if early_data {
+            match stream::wrapper::early_data_config(&options) {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    warn!("tls early data disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        #[cfg(not(feature = "rustls-tls"))]
+        {
+            if early_data {
+                warn!("tls early data is supported only with rustls");
+            }
+        }
+        Handler {
+            server_name,
+            options,
+            #[cfg(feature = "rustls-tls")]
+            early_data_config,
+        }
+    }
+
+    /// Returns the server name to send, the configured one if set, otherwise
+    /// the destination host.
+    pub fn server_name(&self, sess: &Session) -> String {
+        if !self.server_name.is_empty() {
+            self.server_name.clone()
+        } else {
+            sess.destination.host()
+        }
+    }
 }
 
 #[async_trait]
@@ -32,19 +81,29 @@ impl TcpOutboundHandler for Handler {
         stream: Option<Box<dyn ProxyStream>>,
     ) -> io::Result<Box<dyn ProxyStream>> {
         // TODO optimize, dont need copy
-        let name = if !&self.server_name.is_empty() {
-            self.server_name.clone()
-        } else {
-            sess.destination.host()
-        };
+        let name = self.server_name(sess);
         trace!("wrapping tls with name {}", &name);
         match stream {
             Some(stream) => {
-                let tls_stream = stream::wrapper::wrap_tls(stream, &name, self.alpns.clone())
-                    .map_err(|e| {
-                        io::Error::new(io::ErrorKind::Other, format!("wrap tls failed: {}", e))
-                    })
-                    .await?;
+                #[cfg(feature = "rustls-tls")]
+                {
+                    if let Some(config) = self.early_data_config.as_ref() {
+                        return stream::wrapper::wrap_tls_early_data(stream, &name, config.clone())
+                            .map_err(|e| {
+                                io::Error::new(
+                                    io::ErrorKind::Other,
+                                    format!("wrap tls failed: {}", e),
+                                )
+                            })
+                            .await;
+                    }
+                }
+                let tls_stream =
+                    stream::wrapper::wrap_tls_with_options(stream, &name, &self.options)
+                        .map_err(|e| {
+                            io::Error::new(io::ErrorKind::Other, format!("wrap tls failed: {}", e))
+                        })
+                        .await?;
                 Ok(tls_stream)
             }
             None => Err(io::Error::new(io::ErrorKind::Other, "invalid tls input")),
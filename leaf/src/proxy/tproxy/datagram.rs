@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::io::AsRawFd;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use futures::future::poll_fn;
+use futures::ready;
+use mio::unix::EventedFd;
+use mio::{Evented, PollOpt, Ready, Token};
+use socket2::Socket;
+use tokio::io::PollEvented;
+use tokio::net::UdpSocket;
+
+use crate::proxy::{InboundDatagram, InboundDatagramRecvHalf, InboundDatagramSendHalf};
+use crate::session::SocksAddr;
+
+use super::{bind_udp_reply, recv_msg};
+
+// Maximum number of reply sockets kept for reuse, they're all closed when
+// exceeded.
+const MAX_REPLY_SOCKETS: usize = 256;
+
+// Registers the socket to the reactor for readiness events.
+struct EventedSocket(Socket);
+
+impl Evented for EventedSocket {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).deregister(poll)
+    }
+}
+
+/// An inbound datagram receiving UDP packets redirected by TPROXY, replies
+/// are sent to the clients from the original destinations.
+pub struct TproxyInboundDatagram(PollEvented<EventedSocket>);
+
+impl TproxyInboundDatagram {
+    /// Wraps a socket from `bind_udp`, must be called within the runtime.
+    pub fn new(socket: Socket) -> io::Result<Self> {
+        Ok(TproxyInboundDatagram(PollEvented::new(EventedSocket(
+            socket,
+        ))?))
+    }
+}
+
+impl InboundDatagram for TproxyInboundDatagram {
+    fn split(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn InboundDatagramRecvHalf>,
+        Box<dyn InboundDatagramSendHalf>,
+    ) {
+        (
+            Box::new(TproxyInboundDatagramRecvHalf(self.0)),
+            Box::new(TproxyInboundDatagramSendHalf(HashMap::new())),
+        )
+    }
+}
+
+pub struct TproxyInboundDatagramRecvHalf(PollEvented<EventedSocket>);
+
+impl TproxyInboundDatagramRecvHalf {
+    fn poll_recv(
+        &self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr, SocketAddr)>> {
+        ready!(self.0.poll_read_ready(cx, Ready::readable()))?;
+        match recv_msg(self.0.get_ref().0.as_raw_fd(), buf) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.0.clear_read_ready(cx, Ready::readable())?;
+                Poll::Pending
+            }
+            res => Poll::Ready(res),
+        }
+    }
+}
+
+#[async_trait]
+impl InboundDatagramRecvHalf for TproxyInboundDatagramRecvHalf {
+    async fn recv_from(
+        &mut self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr, Option<SocksAddr>)> {
+        let (n, src_addr, dst_addr) = poll_fn(|cx| self.poll_recv(cx, &mut buf[..])).await?;
+        Ok((n, src_addr, Some(SocksAddr::Ip(dst_addr))))
+    }
+}
+
+// Sockets bound to the original destinations, by the address.
+pub struct TproxyInboundDatagramSendHalf(HashMap<SocketAddr, UdpSocket>);
+
+#[async_trait]
+impl InboundDatagramSendHalf for TproxyInboundDatagramSendHalf {
+    async fn send_to(
+        &mut self,
+        buf: &[u8],
+        src_addr: Option<&SocksAddr>,
+        dst_addr: &SocketAddr,
+    ) -> io::Result<usize> {
+        let src_addr = match src_addr {
+            Some(SocksAddr::Ip(a)) => *a,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "reply without source ip address",
+                ))
+            }
+        };
+        if !self.0.contains_key(&src_addr) {
+            if self.0.len() >= MAX_REPLY_SOCKETS {
+                self.0.clear();
+            }
+            self.0.insert(src_addr, bind_udp_reply(&src_addr)?);
+        }
+        self.0
+            .get_mut(&src_addr)
+            .unwrap()
+            .send_to(buf, dst_addr)
+            .await
+    }
+}
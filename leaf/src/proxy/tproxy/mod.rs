@@ -0,0 +1,208 @@
+//! Transparent proxying with TPROXY on Linux. Connections and datagrams
+//! redirected by TPROXY rules keep their original destination, which is the
+//! local address of an accepted TCP connection, and is carried in the
+//! `IP_ORIGDSTADDR` control message of a received UDP datagram.
+
+use std::io;
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use libc::c_int;
+use socket2::{Domain, Socket, Type};
+use tokio::net::{TcpListener, UdpSocket};
+
+use crate::common::sys;
+
+mod datagram;
+
+pub use datagram::TproxyInboundDatagram;
+
+pub static NAME: &str = "tproxy";
+
+// From <linux/in.h> and <linux/in6.h>, the ORIGDSTADDR control message types
+// share the values of the socket options.
+const IP_TRANSPARENT: c_int = 19;
+const IP_RECVORIGDSTADDR: c_int = 20;
+const IPV6_RECVORIGDSTADDR: c_int = 74;
+const IPV6_TRANSPARENT: c_int = 75;
+
+fn set_int_opt(fd: RawFd, level: c_int, name: c_int, value: c_int) -> io::Result<()> {
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const c_int as *const libc::c_void,
+            mem::size_of::<c_int>() as libc::socklen_t,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+// Creates a socket which can accept traffic to non-local addresses redirected
+// by TPROXY, and bind to non-local addresses. Requires CAP_NET_ADMIN.
+fn transparent_socket(addr: &SocketAddr, ty: Type) -> io::Result<Socket> {
+    let socket = if addr.is_ipv4() {
+        let socket = Socket::new(Domain::ipv4(), ty, None)?;
+        set_int_opt(socket.as_raw_fd(), libc::SOL_IP, IP_TRANSPARENT, 1)?;
+        socket
+    } else {
+        let socket = Socket::new(Domain::ipv6(), ty, None)?;
+        set_int_opt(socket.as_raw_fd(), libc::SOL_IPV6, IPV6_TRANSPARENT, 1)?;
+        socket
+    };
+    socket.set_reuse_address(true)?;
+    Ok(socket)
+}
+
+// Asks for the original destination of received datagrams.
+fn set_recv_orig_dst(socket: &Socket, ipv6: bool) -> io::Result<()> {
+    if ipv6 {
+        set_int_opt(socket.as_raw_fd(), libc::SOL_IPV6, IPV6_RECVORIGDSTADDR, 1)
+    } else {
+        set_int_opt(socket.as_raw_fd(), libc::SOL_IP, IP_RECVORIGDSTADDR, 1)
+    }
+}
+
+/// Binds a TCP listener accepting connections redirected by TPROXY, the
+/// original destination of a connection is its local address.
+pub fn bind_tcp(addr: &SocketAddr, backlog: u32) -> io::Result<TcpListener> {
+    let socket = transparent_socket(addr, Type::stream())?;
+    socket.bind(&(*addr).into())?;
+    // The default is the same as TcpListener::bind.
+    let backlog = if backlog > 0 {
+        backlog.min(sys::max_listen_backlog())
+    } else {
+        128
+    };
+    socket.listen(backlog as i32)?;
+    let listener = socket.into_tcp_listener();
+    listener.set_nonblocking(true)?;
+    TcpListener::from_std(listener)
+}
+
+/// Binds a UDP socket receiving datagrams redirected by TPROXY.
+pub fn bind_udp(addr: &SocketAddr) -> io::Result<Socket> {
+    let socket = transparent_socket(addr, Type::dgram())?;
+    set_recv_orig_dst(&socket, addr.is_ipv6())?;
+    socket.bind(&(*addr).into())?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+// Binds a UDP socket to the non-local address, for sending replies to the
+// client as if they're from the original destination.
+fn bind_udp_reply(addr: &SocketAddr) -> io::Result<UdpSocket> {
+    let socket = transparent_socket(addr, Type::dgram())?;
+    // Leaf's own traffic, must not be redirected again.
+    sys::mark_outbound_socket(&socket)?;
+    socket.bind(&(*addr).into())?;
+    socket.set_nonblocking(true)?;
+    UdpSocket::from_std(socket.into_udp_socket())
+}
+
+fn from_sockaddr_in(addr: &libc::sockaddr_in) -> SocketAddr {
+    SocketAddr::V4(SocketAddrV4::new(
+        Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)),
+        u16::from_be(addr.sin_port),
+    ))
+}
+
+fn from_sockaddr_in6(addr: &libc::sockaddr_in6) -> SocketAddr {
+    SocketAddr::V6(SocketAddrV6::new(
+        Ipv6Addr::from(addr.sin6_addr.s6_addr),
+        u16::from_be(addr.sin6_port),
+        addr.sin6_flowinfo,
+        addr.sin6_scope_id,
+    ))
+}
+
+fn from_sockaddr_storage(addr: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match addr.ss_family as c_int {
+        libc::AF_INET => Ok(from_sockaddr_in(unsafe {
+            &*(addr as *const libc::sockaddr_storage as *const libc::sockaddr_in)
+        })),
+        libc::AF_INET6 => Ok(from_sockaddr_in6(unsafe {
+            &*(addr as *const libc::sockaddr_storage as *const libc::sockaddr_in6)
+        })),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unsupported address family",
+        )),
+    }
+}
+
+// Receives a datagram with its source and original destination, the socket
+// must have the ORIGDSTADDR option set.
+fn recv_msg(fd: RawFd, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, SocketAddr)> {
+    let mut src: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    // u64 for the alignment of cmsghdr.
+    let mut control = [0u64; 16];
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = &mut src as *mut libc::sockaddr_storage as *mut libc::c_void;
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    let n = unsafe { libc::recvmsg(fd, &mut msg, 0) };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let src = from_sockaddr_storage(&src)?;
+
+    let mut dst = None;
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            let hdr = &*cmsg;
+            let data = libc::CMSG_DATA(cmsg);
+            if hdr.cmsg_level == libc::SOL_IP && hdr.cmsg_type == IP_RECVORIGDSTADDR {
+                let addr = std::ptr::read_unaligned(data as *const libc::sockaddr_in);
+                dst = Some(from_sockaddr_in(&addr));
+            } else if hdr.cmsg_level == libc::SOL_IPV6 && hdr.cmsg_type == IPV6_RECVORIGDSTADDR {
+                let addr = std::ptr::read_unaligned(data as *const libc::sockaddr_in6);
+                dst = Some(from_sockaddr_in6(&addr));
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+    let dst = dst.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing original destination")
+    })?;
+    Ok((n as usize, src, dst))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recv_orig_dst() {
+        // Not redirected, the original destination is the bound address.
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let socket = Socket::new(Domain::ipv4(), Type::dgram(), None).unwrap();
+        set_recv_orig_dst(&socket, false).unwrap();
+        socket.bind(&addr.into()).unwrap();
+        let socket = socket.into_udp_socket();
+        let local_addr = socket.local_addr().unwrap();
+
+        let client = std::net::UdpSocket::bind(addr).unwrap();
+        client.send_to(b"hello", local_addr).unwrap();
+
+        let mut buf = [0u8; 16];
+        let (n, src, dst) = recv_msg(socket.as_raw_fd(), &mut buf).unwrap();
+        assert_eq!(&buf[..n], b"hello");
+        assert_eq!(src, client.local_addr().unwrap());
+        assert_eq!(dst, local_addr);
+    }
+}
@@ -8,7 +8,10 @@ use sha2::{Digest, Sha224};
 
 use crate::{
     app::dns_client::DnsClient,
-    proxy::{BufHeadProxyStream, OutboundConnect, ProxyStream, TcpConnector, TcpOutboundHandler},
+    proxy::{
+        BufHeadProxyStream, OutboundConnect, ProxyStream, TcpConnector, TcpOptions,
+        TcpOutboundHandler,
+    },
     session::{Session, SocksAddrWireType},
 };
 
@@ -16,11 +19,19 @@ pub struct Handler {
     pub address: String,
     pub port: u16,
     pub password: String,
+    /// Wraps the stream in TLS before the trojan request if set, otherwise
+    /// TLS is expected to be done by a preceding outbound in a chain.
+    pub tls: Option<Arc<dyn TcpOutboundHandler>>,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
@@ -52,6 +63,10 @@ impl TcpOutboundHandler for Handler {
             )
             .await?
         };
+        let stream = match &self.tls {
+            Some(tls) => tls.handle_tcp(sess, Some(stream)).await?,
+            None => stream,
+        };
         let mut buf = BytesMut::new();
         let password = Sha224::digest(self.password.as_bytes());
         let password = hex::encode(&password[..]);
@@ -14,8 +14,10 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, Wr
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
+        uot::{self, UotDatagram},
         OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf,
-        OutboundTransport, TcpConnector, UdpOutboundHandler, UdpTransportType,
+        OutboundTransport, TcpConnector, TcpOptions, TcpOutboundHandler, UdpOutboundHandler,
+        UdpTransportType,
     },
     session::{Session, SocksAddr, SocksAddrWireType},
 };
@@ -24,11 +26,21 @@ pub struct Handler {
     pub address: String,
     pub port: u16,
     pub password: String,
+    /// Wraps the stream in TLS before the trojan request if set.
+    pub tls: Option<Arc<dyn TcpOutboundHandler>>,
+    /// The UDP over TCP version, relays UDP in the UoT framing over a TCP
+    /// request instead of the trojan UDP associate command if set.
+    pub udp_over_tcp: Option<u32>,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl UdpOutboundHandler for Handler {
@@ -64,11 +76,26 @@ impl UdpOutboundHandler for Handler {
             )
             .await?
         };
+        let stream = match &self.tls {
+            Some(tls) => tls.handle_tcp(sess, Some(stream)).await?,
+            None => stream,
+        };
         let mut buf = BytesMut::new();
         let password = Sha224::digest(self.password.as_bytes());
         let password = hex::encode(&password[..]);
         buf.put_slice(password.as_bytes());
         buf.put_slice(b"\r\n");
+        if let Some(version) = self.udp_over_tcp {
+            buf.put_u8(0x01); // tcp
+            uot::magic_address(version)?.write_buf(&mut buf, SocksAddrWireType::PortLast)?;
+            buf.put_slice(b"\r\n");
+            return Ok(Box::new(UotDatagram::new(
+                stream,
+                version,
+                Some(buf),
+                &sess.destination,
+            )?));
+        }
         buf.put_u8(0x03); // udp
         sess.destination
             .write_buf(&mut buf, SocksAddrWireType::PortLast)?;
@@ -3,8 +3,10 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures::future::select_ok;
+use log::*;
 
 use crate::{
+    app::outbound::race::RaceStats,
     proxy::{
         OutboundConnect, OutboundDatagram, OutboundHandler, OutboundTransport, UdpOutboundHandler,
         UdpTransportType,
@@ -15,6 +17,7 @@ use crate::{
 pub struct Handler {
     pub actors: Vec<Arc<dyn OutboundHandler>>,
     pub delay_base: u32,
+    pub race_stats: Arc<RaceStats>,
 }
 
 #[async_trait]
@@ -36,6 +39,7 @@ impl UdpOutboundHandler for Handler {
         sess: &'a Session,
         _transport: Option<OutboundTransport>,
     ) -> io::Result<Box<dyn OutboundDatagram>> {
+        let start = tokio::time::Instant::now();
         let mut tasks = Vec::new();
         for (i, a) in self.actors.iter().enumerate() {
             let t = async move {
@@ -45,12 +49,22 @@ impl UdpOutboundHandler for Handler {
                     ))
                     .await;
                 }
-                a.handle_udp(sess, None).await
+                a.handle_udp(sess, None).await.map(|v| (i, v))
             };
             tasks.push(Box::pin(t));
         }
         match select_ok(tasks.into_iter()).await {
-            Ok(v) => Ok(v.0),
+            Ok(((i, v), _)) => {
+                let latency = tokio::time::Instant::now().duration_since(start);
+                self.race_stats.record_win(i, latency);
+                debug!(
+                    "tryall handles udp [{}] to [{}] in {}ms",
+                    sess.destination,
+                    self.actors[i].tag(),
+                    latency.as_millis()
+                );
+                Ok(v)
+            }
             Err(e) => Err(io::Error::new(
                 io::ErrorKind::Other,
                 format!("all outbound attempts failed, last error: {}", e),
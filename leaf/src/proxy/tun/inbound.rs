@@ -1,4 +1,6 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use futures::{sink::SinkExt, stream::StreamExt};
@@ -13,12 +15,77 @@ use crate::{
     app::fake_dns::{FakeDns, FakeDnsMode},
     app::nat_manager::NatManager,
     config::{Inbound, TUNInboundSettings},
-    Runner,
+    option, Runner,
 };
 
-use super::netstack::NetStack;
+use super::netstack::{self, NetStack};
 
-const MTU: usize = 1500;
+const DEFAULT_MTU: i32 = 1500;
+
+// Saves the fake DNS allocations if changed since the last save.
+async fn save_fake_dns(fakedns: &TokioMutex<FakeDns>, path: &Path) {
+    let snapshot = fakedns.lock().await.snapshot();
+    if let Some(snapshot) = snapshot {
+        if let Err(e) = FakeDns::save(path, &snapshot) {
+            warn!("save fake dns to {} failed: {}", path.display(), e);
+        }
+    }
+}
+
+// Relays packets between the stack and a device with packet information,
+// which the framed codec strips and adds.
+async fn run_framed(stack: NetStack, tun: tun::AsyncDevice, mtu: usize) {
+    let framed = tun.into_framed();
+    let (mut tun_sink, mut tun_stream) = framed.split();
+    let (mut stack_reader, mut stack_writer) = io::split(stack);
+
+    let s2t = async move {
+        // Packets from the stack are never larger than the MTU.
+        let mut buf = vec![0; mtu];
+        loop {
+            match stack_reader.read(&mut buf).await {
+                Ok(0) => {
+                    debug!("read stack eof");
+                    return;
+                }
+                Ok(n) => match tun_sink.send(TunPacket::new((&buf[..n]).to_vec())).await {
+                    Ok(_) => (),
+                    Err(e) => {
+                        warn!("send pkt to tun failed: {}", e);
+                        return;
+                    }
+                },
+                Err(err) => {
+                    warn!("read stack failed {:?}", err);
+                    return;
+                }
+            }
+        }
+    };
+
+    let t2s = async move {
+        while let Some(packet) = tun_stream.next().await {
+            match packet {
+                Ok(packet) => match stack_writer.write(packet.get_bytes()).await {
+                    Ok(_) => (),
+                    Err(e) => {
+                        warn!("write pkt to stack failed: {}", e);
+                        return;
+                    }
+                },
+                Err(err) => {
+                    warn!("read tun failed {:?}", err);
+                    return;
+                }
+            }
+        }
+    };
+
+    tokio::select! {
+        r1 = t2s => debug!("t2s ended {:?}", r1),
+        r2 = s2t => debug!("s2t ended {:?}", r2),
+    }
+}
 
 pub fn new(
     inbound: Inbound,
@@ -26,6 +93,14 @@ pub fn new(
     nat_manager: Arc<NatManager>,
 ) -> Result<Runner> {
     let settings = TUNInboundSettings::parse_from_bytes(&inbound.settings).unwrap();
+    let mtu = if settings.mtu > 0 {
+        settings.mtu
+    } else {
+        DEFAULT_MTU
+    };
+    if !crate::config::is_valid_mtu(mtu) {
+        return Err(anyhow!("invalid tun mtu {}", mtu));
+    }
 
     let cfg = if settings.fd >= 0 {
         let mut cfg = tun::Configuration::default();
@@ -36,7 +111,7 @@ pub fn new(
         cfg.name(settings.name)
             .address(settings.address)
             .destination(settings.gateway)
-            .mtu(settings.mtu);
+            .mtu(mtu);
 
         #[cfg(not(any(
             target_arch = "mips",
@@ -70,69 +145,96 @@ pub fn new(
     } else {
         (FakeDnsMode::Exclude, fake_dns_exclude)
     };
+    let fake_dns_range = if !settings.fake_dns_range.is_empty() {
+        settings.fake_dns_range.as_str()
+    } else {
+        option::FAKE_DNS_RANGE
+    };
+    let mut fakedns = FakeDns::new(fake_dns_mode, fake_dns_range)?;
+    fakedns.set_hosts(dispatcher.dns_client().hosts().clone());
+    let fake_dns_routing_aware = settings.fake_dns_routing_aware;
+    let fake_dns_ipv6_range = settings.fake_dns_ipv6_range;
+    let fake_dns_persist_path = if !settings.fake_dns_persist_path.is_empty() {
+        Some(PathBuf::from(&settings.fake_dns_persist_path))
+    } else {
+        None
+    };
+    let fake_dns_persist_ttl = if settings.fake_dns_persist_ttl > 0 {
+        settings.fake_dns_persist_ttl as u64
+    } else {
+        option::FAKE_DNS_PERSIST_TTL
+    };
 
     Ok(Box::pin(async move {
-        let tun = tun::create_as_async(&cfg).unwrap();
+        let mut tun = tun::create_as_async(&cfg).unwrap();
 
-        let fakedns = Arc::new(TokioMutex::new(FakeDns::new(fake_dns_mode)));
+        let fakedns = Arc::new(TokioMutex::new(fakedns));
 
         for filter in fake_dns_filters.into_iter() {
             fakedns.lock().await.add_filter(filter);
         }
 
-        let stack = NetStack::new(inbound.tag.clone(), dispatcher, nat_manager, fakedns);
+        if fake_dns_routing_aware {
+            fakedns.lock().await.set_routing_aware(dispatcher.clone());
+        }
 
-        let mtu = tun.get_ref().mtu().unwrap_or(MTU as i32);
-        let framed = tun.into_framed();
-        let (mut tun_sink, mut tun_stream) = framed.split();
-        let (mut stack_reader, mut stack_writer) = io::split(stack);
+        if !fake_dns_ipv6_range.is_empty() {
+            if let Err(e) = fakedns.lock().await.set_ipv6_range(&fake_dns_ipv6_range) {
+                warn!("{}", e);
+            }
+        }
 
-        let s2t = async move {
-            let mut buf = vec![0; mtu as usize];
-            loop {
-                match stack_reader.read(&mut buf).await {
-                    Ok(0) => {
-                        debug!("read stack eof");
-                        return;
-                    }
-                    Ok(n) => match tun_sink.send(TunPacket::new((&buf[..n]).to_vec())).await {
-                        Ok(_) => (),
-                        Err(e) => {
-                            warn!("send pkt to tun failed: {}", e);
-                            return;
-                        }
-                    },
-                    Err(err) => {
-                        warn!("read stack failed {:?}", err);
-                        return;
-                    }
-                }
+        if let Some(path) = fake_dns_persist_path.as_ref() {
+            match fakedns.lock().await.load(path, fake_dns_persist_ttl) {
+                Ok(n) => info!("loaded {} fake dns entries from {}", n, path.display()),
+                Err(e) => debug!("load fake dns from {} failed: {}", path.display(), e),
             }
-        };
+        }
 
-        let t2s = async move {
-            while let Some(packet) = tun_stream.next().await {
-                match packet {
-                    Ok(packet) => match stack_writer.write(packet.get_bytes()).await {
-                        Ok(_) => (),
-                        Err(e) => {
-                            warn!("write pkt to stack failed: {}", e);
-                            return;
-                        }
-                    },
-                    Err(err) => {
-                        warn!("read tun failed {:?}", err);
-                        return;
-                    }
-                }
+        // Saves at most once per interval, and once more when the tun ends.
+        let fakedns_persist = fakedns.clone();
+        let persist_path = fake_dns_persist_path.clone();
+        let persist = async move {
+            let path = match persist_path {
+                Some(path) => path,
+                None => return futures::future::pending().await,
+            };
+            loop {
+                tokio::time::delay_for(Duration::from_secs(option::FAKE_DNS_SAVE_INTERVAL)).await;
+                save_fake_dns(&fakedns_persist, &path).await;
             }
         };
+        let fakedns_final = fakedns.clone();
+
+        let stack = NetStack::new(
+            inbound.tag.clone(),
+            dispatcher,
+            nat_manager,
+            fakedns,
+            mtu as u16,
+        );
 
         info!("tun inbound started");
 
+        let relay = async move {
+            if tun.get_mut().has_packet_information() {
+                run_framed(stack, tun, mtu as usize).await;
+                return;
+            }
+            // Each read or write on the device is a single IP packet.
+            match netstack::run(stack, tun, mtu as usize).await {
+                Ok(()) => debug!("tun relay ended"),
+                Err(e) => warn!("tun relay failed: {}", e),
+            }
+        };
+
         tokio::select! {
-            r1 = t2s => debug!("s2t ended {:?}", r1),
-            r2 = s2t => debug!("s2t ended {:?}", r2)
+            _ = relay => (),
+            _ = persist => (),
+        }
+
+        if let Some(path) = fake_dns_persist_path.as_ref() {
+            save_fake_dns(&fakedns_final, path).await;
         }
     }))
 }
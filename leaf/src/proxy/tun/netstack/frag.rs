@@ -0,0 +1,353 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+
+use log::*;
+
+// Fragments of a packet must all arrive within this period.
+const REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(15);
+
+// Maximum number of packets being reassembled at the same time.
+const MAX_REASSEMBLIES: usize = 64;
+
+// Maximum size of a reassembled packet, limited by the total length field.
+const MAX_PACKET_SIZE: usize = 65535;
+
+const FLAG_DF: u16 = 0x4000;
+const FLAG_MF: u16 = 0x2000;
+const OFFSET_MASK: u16 = 0x1fff;
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+struct Key {
+    src: [u8; 4],
+    dst: [u8; 4],
+    id: u16,
+    proto: u8,
+}
+
+struct Reassembly {
+    created: Instant,
+    // Header of the first fragment, empty until it arrives.
+    header: Vec<u8>,
+    payload: Vec<u8>,
+    // Received ranges of the payload.
+    ranges: Vec<(usize, usize)>,
+    // Payload length, known once the last fragment arrives.
+    total: Option<usize>,
+}
+
+impl Reassembly {
+    fn new() -> Self {
+        Reassembly {
+            created: Instant::now(),
+            header: Vec::new(),
+            payload: Vec::new(),
+            ranges: Vec::new(),
+            total: None,
+        }
+    }
+
+    fn is_complete(&mut self) -> bool {
+        let total = match self.total {
+            Some(v) => v,
+            None => return false,
+        };
+        if self.header.is_empty() {
+            return false;
+        }
+        self.ranges.sort_unstable();
+        let mut end = 0;
+        for (start, stop) in self.ranges.iter() {
+            if *start > end {
+                return false;
+            }
+            end = end.max(*stop);
+        }
+        end == total
+    }
+}
+
+struct Header {
+    len: usize,
+    total_len: usize,
+    flags_offset: u16,
+    key: Key,
+}
+
+fn parse_ipv4_header(pkt: &[u8]) -> Option<Header> {
+    if pkt.len() < 20 || pkt[0] >> 4 != 4 {
+        return None;
+    }
+    let len = ((pkt[0] & 0x0f) as usize) * 4;
+    let total_len = u16::from_be_bytes([pkt[2], pkt[3]]) as usize;
+    if len < 20 || total_len < len || total_len > pkt.len() {
+        return None;
+    }
+    let mut src = [0u8; 4];
+    let mut dst = [0u8; 4];
+    src.copy_from_slice(&pkt[12..16]);
+    dst.copy_from_slice(&pkt[16..20]);
+    Some(Header {
+        len,
+        total_len,
+        flags_offset: u16::from_be_bytes([pkt[6], pkt[7]]),
+        key: Key {
+            src,
+            dst,
+            id: u16::from_be_bytes([pkt[4], pkt[5]]),
+            proto: pkt[9],
+        },
+    })
+}
+
+fn checksum(header: &[u8]) -> u16 {
+    let mut sum = 0u32;
+    for chunk in header.chunks(2) {
+        let word = if chunk.len() == 2 {
+            u16::from_be_bytes([chunk[0], chunk[1]])
+        } else {
+            u16::from_be_bytes([chunk[0], 0])
+        };
+        sum += word as u32;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+fn set_header_fields(header: &mut [u8], total_len: usize, flags_offset: u16) {
+    header[2..4].copy_from_slice(&(total_len as u16).to_be_bytes());
+    header[6..8].copy_from_slice(&flags_offset.to_be_bytes());
+    header[10..12].copy_from_slice(&[0, 0]);
+    let sum = checksum(header);
+    header[10..12].copy_from_slice(&sum.to_be_bytes());
+}
+
+/// Reassembles IPv4 fragments read from TUN, so that datagrams larger than
+/// the MTU reach the stack intact.
+#[derive(Default)]
+pub struct Reassembler {
+    pending: HashMap<Key, Reassembly>,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Reassembler {
+            pending: HashMap::new(),
+        }
+    }
+
+    fn expire(&mut self) {
+        let now = Instant::now();
+        self.pending.retain(|key, r| {
+            if now.duration_since(r.created) < REASSEMBLY_TIMEOUT {
+                return true;
+            }
+            warn!(
+                "ip reassembly {} -> {} id {} failed: timed out",
+                Ipv4Addr::from(key.src),
+                Ipv4Addr::from(key.dst),
+                key.id,
+            );
+            false
+        });
+    }
+
+    fn fail(&mut self, key: &Key, reason: &str) {
+        warn!(
+            "ip reassembly {} -> {} id {} failed: {}",
+            Ipv4Addr::from(key.src),
+            Ipv4Addr::from(key.dst),
+            key.id,
+            reason
+        );
+        self.pending.remove(key);
+    }
+
+    /// Returns the packet to input to the stack, the packet itself if it's
+    /// not a fragment, or the reassembled packet once all the fragments have
+    /// arrived. Returns `None` if more fragments are expected or the fragment
+    /// is dropped.
+    pub fn push<'a>(&mut self, pkt: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+        let header = match parse_ipv4_header(pkt) {
+            Some(v) => v,
+            // Not IPv4, or malformed which the stack drops.
+            None => return Some(Cow::Borrowed(pkt)),
+        };
+        let more = header.flags_offset & FLAG_MF != 0;
+        let offset = ((header.flags_offset & OFFSET_MASK) as usize) * 8;
+        if !more && offset == 0 {
+            return Some(Cow::Borrowed(pkt));
+        }
+
+        self.expire();
+
+        let key = header.key;
+        if !self.pending.contains_key(&key) && self.pending.len() >= MAX_REASSEMBLIES {
+            self.fail(&key, "too many pending reassemblies");
+            return None;
+        }
+
+        let data = &pkt[header.len..header.total_len];
+        let end = offset + data.len();
+        if header.len + end > MAX_PACKET_SIZE {
+            self.fail(&key, "packet too large");
+            return None;
+        }
+        if more && data.len() % 8 != 0 {
+            self.fail(&key, "invalid fragment length");
+            return None;
+        }
+
+        let r = self.pending.entry(key).or_insert_with(Reassembly::new);
+        if let Some(total) = r.total {
+            if end > total || (!more && end != total) {
+                self.fail(&key, "inconsistent fragments");
+                return None;
+            }
+        } else if !more {
+            if r.ranges.iter().any(|(_, stop)| *stop > end) {
+                self.fail(&key, "inconsistent fragments");
+                return None;
+            }
+            r.total = Some(end);
+        }
+        if offset == 0 {
+            r.header = pkt[..header.len].to_vec();
+        }
+        if r.payload.len() < end {
+            r.payload.resize(end, 0);
+        }
+        r.payload[offset..end].copy_from_slice(data);
+        r.ranges.push((offset, end));
+
+        if !r.is_complete() {
+            return None;
+        }
+
+        let r = self.pending.remove(&key)?;
+        let mut out = r.header;
+        let total_len = out.len() + r.payload.len();
+        set_header_fields(&mut out, total_len, 0);
+        out.extend_from_slice(&r.payload);
+        Some(Cow::Owned(out))
+    }
+}
+
+/// Splits an IPv4 packet into fragments fitting in the MTU, the packet is
+/// returned as is if it fits, or it's not IPv4, or it has the DF flag set.
+pub fn fragment(pkt: Vec<u8>, mtu: usize) -> Vec<Vec<u8>> {
+    if pkt.len() <= mtu {
+        return vec![pkt];
+    }
+    let header = match parse_ipv4_header(&pkt) {
+        Some(v) => v,
+        None => return vec![pkt],
+    };
+    if header.flags_offset & FLAG_DF != 0 || mtu < header.len + 8 {
+        return vec![pkt];
+    }
+    let max_data = (mtu - header.len) & !7;
+    let data = &pkt[header.len..header.total_len];
+    let base_offset = ((header.flags_offset & OFFSET_MASK) as usize) * 8;
+    let more = header.flags_offset & FLAG_MF != 0;
+
+    let mut frags = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let end = (pos + max_data).min(data.len());
+        let last = end == data.len();
+        let mut flags_offset = (((base_offset + pos) / 8) as u16) & OFFSET_MASK;
+        if !last || more {
+            flags_offset |= FLAG_MF;
+        }
+        let mut frag = pkt[..header.len].to_vec();
+        set_header_fields(&mut frag, header.len + end - pos, flags_offset);
+        frag.extend_from_slice(&data[pos..end]);
+        frags.push(frag);
+        pos = end;
+    }
+    frags
+}
+
+/// Builds an IPv4 UDP packet, without the UDP checksum which is optional.
+#[cfg(test)]
+pub fn ipv4_udp_packet(
+    src: std::net::SocketAddrV4,
+    dst: std::net::SocketAddrV4,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut pkt = vec![0x45, 0, 0, 0, 0x12, 0x34, 0, 0, 64, 17, 0, 0];
+    pkt.extend_from_slice(&src.ip().octets());
+    pkt.extend_from_slice(&dst.ip().octets());
+    set_header_fields(&mut pkt, 20 + 8 + payload.len(), 0);
+    pkt.extend_from_slice(&src.port().to_be_bytes());
+    pkt.extend_from_slice(&dst.port().to_be_bytes());
+    pkt.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+    pkt.extend_from_slice(&[0, 0]);
+    pkt.extend_from_slice(payload);
+    pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn udp_packet(payload_len: usize) -> Vec<u8> {
+        let payload: Vec<u8> = (0..payload_len).map(|i| i as u8).collect();
+        ipv4_udp_packet(
+            "10.0.0.1:12345".parse().unwrap(),
+            "10.0.0.2:53".parse().unwrap(),
+            &payload,
+        )
+    }
+
+    #[test]
+    fn test_fragment_and_reassemble() {
+        let pkt = udp_packet(20 * 1024);
+        let frags = fragment(pkt.clone(), 1500);
+        assert_eq!(frags.len(), 14);
+        for frag in frags.iter() {
+            assert!(frag.len() <= 1500);
+            assert_eq!(checksum(&frag[..20]), 0);
+        }
+
+        // Fragments may arrive out of order.
+        let mut reassembler = Reassembler::new();
+        for frag in frags.iter().skip(1) {
+            assert!(reassembler.push(frag).is_none());
+        }
+        let out = reassembler.push(&frags[0]).unwrap();
+        assert_eq!(out.as_ref(), pkt.as_slice());
+        assert!(reassembler.pending.is_empty());
+    }
+
+    #[test]
+    fn test_pass_through() {
+        let pkt = udp_packet(100);
+        assert_eq!(fragment(pkt.clone(), 1500), vec![pkt.clone()]);
+        let mut reassembler = Reassembler::new();
+        assert_eq!(reassembler.push(&pkt).unwrap().as_ref(), pkt.as_slice());
+
+        // DF set
+        let mut pkt = udp_packet(2000);
+        set_header_fields(&mut pkt[..20], 2028, FLAG_DF);
+        assert_eq!(fragment(pkt.clone(), 1500).len(), 1);
+    }
+
+    #[test]
+    fn test_inconsistent_fragments() {
+        let frags = fragment(udp_packet(4000), 1500);
+        let mut reassembler = Reassembler::new();
+        assert!(reassembler.push(&frags[2]).is_none());
+        // a last fragment ending before data already received
+        let mut bad = frags[0].clone();
+        set_header_fields(&mut bad[..20], 1500, 0x0001);
+        assert!(reassembler.push(&bad).is_none());
+        assert!(reassembler.pending.is_empty());
+    }
+}
@@ -1,4 +1,6 @@
+mod frag;
 mod lwip;
+mod ndp;
 mod output;
 mod stack;
 mod stack_impl;
@@ -9,4 +11,5 @@ mod tcp_stream_impl;
 mod udp;
 mod util;
 
-pub use stack::NetStack;
+pub use stack::{run, NetStack};
+pub use tcp_stream::TcpStream;
@@ -0,0 +1,153 @@
+use std::net::Ipv6Addr;
+
+const IPV6_HEADER_LEN: usize = 40;
+const NEXT_HEADER_ICMPV6: u8 = 58;
+// Hop limit of all neighbor discovery messages, others are not valid.
+const NDP_HOP_LIMIT: u8 = 255;
+
+const ROUTER_SOLICITATION: u8 = 133;
+const NEIGHBOR_SOLICITATION: u8 = 135;
+const NEIGHBOR_ADVERTISEMENT: u8 = 136;
+const REDIRECT: u8 = 137;
+
+// Solicited flag of neighbor advertisements.
+const FLAG_SOLICITED: u8 = 0x40;
+
+/// Returns whether the packet is an ICMPv6 neighbor discovery message, which
+/// is handled here instead of by the stack as there's no link layer on TUN.
+pub fn is_neighbor_discovery(pkt: &[u8]) -> bool {
+    pkt.len() >= IPV6_HEADER_LEN + 4
+        && pkt[0] >> 4 == 6
+        && pkt[6] == NEXT_HEADER_ICMPV6
+        && (ROUTER_SOLICITATION..=REDIRECT).contains(&pkt[IPV6_HEADER_LEN])
+}
+
+/// Returns the advertisement answering a neighbor solicitation, so neighbor
+/// unreachability detection succeeds for any address behind the tunnel.
+/// Solicitations for duplicate address detection are not answered.
+pub fn advertise(pkt: &[u8]) -> Option<Vec<u8>> {
+    if !is_neighbor_discovery(pkt)
+        || pkt.len() < IPV6_HEADER_LEN + 24
+        || pkt[7] != NDP_HOP_LIMIT
+        || pkt[IPV6_HEADER_LEN] != NEIGHBOR_SOLICITATION
+        || pkt[IPV6_HEADER_LEN + 1] != 0
+    {
+        return None;
+    }
+    let src = &pkt[8..24];
+    let target = &pkt[IPV6_HEADER_LEN + 8..IPV6_HEADER_LEN + 24];
+    if Ipv6Addr::from(to_octets(src)).is_unspecified()
+        || Ipv6Addr::from(to_octets(target)).is_multicast()
+    {
+        return None;
+    }
+
+    let mut out = vec![0u8; IPV6_HEADER_LEN + 24];
+    out[0] = 0x60;
+    out[4..6].copy_from_slice(&24u16.to_be_bytes());
+    out[6] = NEXT_HEADER_ICMPV6;
+    out[7] = NDP_HOP_LIMIT;
+    out[8..24].copy_from_slice(target);
+    out[24..40].copy_from_slice(src);
+    let icmp = &mut out[IPV6_HEADER_LEN..];
+    icmp[0] = NEIGHBOR_ADVERTISEMENT;
+    // No target link-layer address option, so the override flag is unset.
+    icmp[4] = FLAG_SOLICITED;
+    icmp[8..24].copy_from_slice(target);
+    let sum = checksum(&out);
+    out[IPV6_HEADER_LEN + 2..IPV6_HEADER_LEN + 4].copy_from_slice(&sum.to_be_bytes());
+    Some(out)
+}
+
+fn to_octets(b: &[u8]) -> [u8; 16] {
+    let mut octets = [0u8; 16];
+    octets.copy_from_slice(b);
+    octets
+}
+
+// ICMPv6 checksum of the packet, with the pseudo-header.
+fn checksum(pkt: &[u8]) -> u16 {
+    let payload = &pkt[IPV6_HEADER_LEN..];
+    let mut sum = 0u32;
+    let mut add = |data: &[u8]| {
+        for chunk in data.chunks(2) {
+            let word = if chunk.len() == 2 {
+                u16::from_be_bytes([chunk[0], chunk[1]])
+            } else {
+                u16::from_be_bytes([chunk[0], 0])
+            };
+            sum += word as u32;
+        }
+    };
+    add(&pkt[8..40]);
+    add(&(payload.len() as u32).to_be_bytes());
+    add(&[0, 0, 0, NEXT_HEADER_ICMPV6]);
+    add(payload);
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solicitation(src: &str, dst: &str, target: &str) -> Vec<u8> {
+        let mut pkt = vec![0u8; IPV6_HEADER_LEN + 24];
+        pkt[0] = 0x60;
+        pkt[4..6].copy_from_slice(&24u16.to_be_bytes());
+        pkt[6] = NEXT_HEADER_ICMPV6;
+        pkt[7] = NDP_HOP_LIMIT;
+        pkt[8..24].copy_from_slice(&src.parse::<Ipv6Addr>().unwrap().octets());
+        pkt[24..40].copy_from_slice(&dst.parse::<Ipv6Addr>().unwrap().octets());
+        pkt[IPV6_HEADER_LEN] = NEIGHBOR_SOLICITATION;
+        pkt[IPV6_HEADER_LEN + 8..].copy_from_slice(&target.parse::<Ipv6Addr>().unwrap().octets());
+        let sum = checksum(&pkt);
+        pkt[IPV6_HEADER_LEN + 2..IPV6_HEADER_LEN + 4].copy_from_slice(&sum.to_be_bytes());
+        pkt
+    }
+
+    #[test]
+    fn test_advertise() {
+        let ns = solicitation("fd00::2", "ff02::1:ff00:1", "fd00::1");
+        assert_eq!(checksum(&ns), 0);
+        assert!(is_neighbor_discovery(&ns));
+        let na = advertise(&ns).unwrap();
+        assert_eq!(na[IPV6_HEADER_LEN], NEIGHBOR_ADVERTISEMENT);
+        assert_eq!(na[IPV6_HEADER_LEN + 4], FLAG_SOLICITED);
+        assert_eq!(&na[8..24], &ns[IPV6_HEADER_LEN + 8..]);
+        assert_eq!(&na[24..40], &ns[8..24]);
+        assert_eq!(&na[IPV6_HEADER_LEN + 8..], &ns[IPV6_HEADER_LEN + 8..]);
+        assert_eq!(checksum(&na), 0);
+
+        // duplicate address detection
+        let ns = solicitation("::", "ff02::1:ff00:2", "fd00::2");
+        assert!(is_neighbor_discovery(&ns));
+        assert!(advertise(&ns).is_none());
+
+        // invalid hop limit
+        let mut ns = solicitation("fd00::2", "ff02::1:ff00:1", "fd00::1");
+        ns[7] = 64;
+        assert!(advertise(&ns).is_none());
+    }
+
+    #[test]
+    fn test_not_neighbor_discovery() {
+        // echo request
+        let mut pkt = solicitation("fd00::2", "2001:db8::1", "fd00::1");
+        pkt[IPV6_HEADER_LEN] = 128;
+        assert!(!is_neighbor_discovery(&pkt));
+        assert!(advertise(&pkt).is_none());
+
+        // UDP
+        let mut pkt = solicitation("fd00::2", "2001:db8::1", "fd00::1");
+        pkt[6] = 17;
+        assert!(!is_neighbor_discovery(&pkt));
+
+        // IPv4
+        let mut pkt = vec![0u8; 64];
+        pkt[0] = 0x45;
+        assert!(!is_neighbor_discovery(&pkt));
+    }
+}
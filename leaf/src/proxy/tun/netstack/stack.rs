@@ -1,7 +1,7 @@
 use std::{io, pin::Pin, sync::Arc};
 
 use futures::task::{Context, Poll};
-use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::sync::Mutex as TokioMutex;
 
 use crate::app::dispatcher::Dispatcher;
@@ -10,24 +10,68 @@ use crate::app::nat_manager::NatManager;
 
 use super::stack_impl::NetStackImpl;
 
+/// A TCP/IP stack in user space. Packets read from TUN are written to it,
+/// and packets read from it are to be written to TUN, connections and UDP
+/// sessions in the packets are dispatched to the outbounds.
 pub struct NetStack(Box<NetStackImpl>);
 
 impl NetStack {
+    /// Creates the stack for an inbound, packets it sends are fragmented to
+    /// fit in the MTU.
     pub fn new(
         inbound_tag: String,
         dispatcher: Arc<Dispatcher>,
         nat_manager: Arc<NatManager>,
         fakedns: Arc<TokioMutex<FakeDns>>,
+        mtu: u16,
     ) -> Self {
         NetStack(NetStackImpl::new(
             inbound_tag,
             dispatcher,
             nat_manager,
             fakedns,
+            mtu,
         ))
     }
 }
 
+// Copies packets until the reader ends, each read returns a single packet
+// of at most `mtu` bytes and a write takes a whole packet.
+async fn relay<R, W>(mut r: R, mut w: W, mtu: usize) -> io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = vec![0; mtu];
+    loop {
+        let n = r.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        w.write_all(&buf[..n]).await?;
+    }
+}
+
+/// Relays packets between the stack and a TUN device until either side ends
+/// or fails.
+///
+/// Each read from the device must return a single IP packet, and each write
+/// to it takes one, as with the file descriptor of a TUN device without
+/// packet information. Embedders having their own device, e.g. the one
+/// given by a platform VPN API, can run the stack on it directly. `mtu` is
+/// the MTU of the device, which should also be the one of the stack.
+pub async fn run<T>(stack: NetStack, device: T, mtu: usize) -> io::Result<()>
+where
+    T: AsyncRead + AsyncWrite,
+{
+    let (stack_reader, stack_writer) = tokio::io::split(stack);
+    let (device_reader, device_writer) = tokio::io::split(device);
+    tokio::select! {
+        r = relay(device_reader, stack_writer, mtu) => r,
+        r = relay(stack_reader, device_writer, mtu) => r,
+    }
+}
+
 impl AsyncRead for NetStack {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -55,3 +99,204 @@ impl AsyncWrite for NetStack {
         AsyncWrite::poll_shutdown(Pin::new(&mut self.0), cx)
     }
 }
+
+#[cfg(all(test, feature = "config-json", feature = "outbound-direct"))]
+mod tests {
+    use std::net::{SocketAddr, SocketAddrV4};
+    use std::sync::{Mutex, RwLock};
+    use std::time::Duration;
+
+    use lazy_static::lazy_static;
+    use tokio::net::UdpSocket;
+    use tokio::sync::mpsc;
+    use tokio::time::timeout;
+
+    use super::super::frag::{self, Reassembler};
+    use super::*;
+    use crate::app::{
+        fake_dns::FakeDnsMode, outbound::manager::OutboundManager, rate_limit::RateLimiter,
+        rewriter::Rewriter, router::Router,
+    };
+    use crate::config::json;
+    use crate::option;
+
+    const MTU: usize = 1500;
+
+    lazy_static! {
+        // lwIP has global states, one stack at a time.
+        static ref LWIP: Mutex<()> = Mutex::new(());
+    }
+
+    // A stack dispatching all to a direct outbound, must be called within
+    // the runtime.
+    fn new_stack() -> NetStack {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{
+                    "outbounds":[{"protocol":"direct","tag":"direct"}],
+                    "dns":{"servers":["127.0.0.1"]}
+                }"#
+                .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let outbound_manager = Arc::new(RwLock::new(OutboundManager::new(
+            &config.outbounds,
+            config.dns.as_ref().unwrap(),
+        )));
+        let dispatcher = Arc::new(Dispatcher::new(
+            outbound_manager,
+            Router::new(&config.routing_rules),
+            Rewriter::new(&config.inbounds),
+            RateLimiter::new(&config.inbounds, &config.outbounds),
+        ));
+        let nat_manager = Arc::new(NatManager::new(dispatcher.clone(), &config));
+        let fakedns = FakeDns::new(FakeDnsMode::Exclude, option::FAKE_DNS_RANGE).unwrap();
+        NetStack::new(
+            "tun".to_string(),
+            dispatcher,
+            nat_manager,
+            Arc::new(TokioMutex::new(fakedns)),
+            MTU as u16,
+        )
+    }
+
+    // Echoes the datagrams back, returns the address it's bound to.
+    async fn udp_echo() -> SocketAddrV4 {
+        let mut socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = match socket.local_addr().unwrap() {
+            SocketAddr::V4(a) => a,
+            _ => unreachable!(),
+        };
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 65536];
+            while let Ok((n, src)) = socket.recv_from(&mut buf).await {
+                let _ = socket.send_to(&buf[..n], &src).await;
+            }
+        });
+        addr
+    }
+
+    // A TUN device in memory, a read returns a packet sent to `rx`, a write
+    // sends the packet to `tx`. Reads end once `rx` is closed.
+    struct MemDevice {
+        rx: mpsc::UnboundedReceiver<Vec<u8>>,
+        tx: mpsc::UnboundedSender<Vec<u8>>,
+    }
+
+    impl AsyncRead for MemDevice {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            match futures::ready!(self.rx.poll_recv(cx)) {
+                Some(pkt) => {
+                    buf[..pkt.len()].copy_from_slice(&pkt);
+                    Poll::Ready(Ok(pkt.len()))
+                }
+                None => Poll::Ready(Ok(0)),
+            }
+        }
+    }
+
+    impl AsyncWrite for MemDevice {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let _ = self.tx.send(buf.to_vec());
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let _g = LWIP.lock().unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let server = udp_echo().await;
+            let (in_tx, rx) = mpsc::unbounded_channel();
+            let (tx, mut out_rx) = mpsc::unbounded_channel();
+            let device = MemDevice { rx, tx };
+            let client: SocketAddrV4 = "10.0.0.2:40001".parse().unwrap();
+            let echo = async move {
+                in_tx
+                    .send(frag::ipv4_udp_packet(client, server, b"hello"))
+                    .unwrap();
+                let pkt = out_rx.recv().await.unwrap();
+                assert_eq!(pkt[9], 17);
+                assert_eq!(&pkt[12..16], &server.ip().octets());
+                assert_eq!(&pkt[16..20], &client.ip().octets());
+                assert_eq!(&pkt[28..], b"hello");
+                // Ends with the device.
+                drop(in_tx);
+            };
+            let (res, _) = timeout(
+                Duration::from_secs(5),
+                futures::future::join(run(new_stack(), device, MTU), echo),
+            )
+            .await
+            .unwrap();
+            assert!(res.is_ok());
+        });
+    }
+
+    #[test]
+    fn test_large_udp_datagram() {
+        let _g = LWIP.lock().unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let server = udp_echo().await;
+            let mut stack = new_stack();
+            let client: SocketAddrV4 = "10.0.0.2:40000".parse().unwrap();
+            let payload: Vec<u8> = (0..20 * 1024).map(|i| i as u8).collect();
+
+            // Sent as fragments as TUN gives them.
+            let frags = frag::fragment(frag::ipv4_udp_packet(client, server, &payload), MTU);
+            assert!(frags.len() > 1);
+            for pkt in frags.iter() {
+                assert_eq!(stack.write(pkt).await.unwrap(), pkt.len());
+            }
+
+            // The echo comes back fragmented to fit in the MTU.
+            let mut reassembler = Reassembler::new();
+            let mut buf = vec![0u8; MTU];
+            let pkt = timeout(Duration::from_secs(5), async {
+                loop {
+                    let n = stack.read(&mut buf).await.unwrap();
+                    assert!(n <= MTU);
+                    if let Some(pkt) = reassembler.push(&buf[..n]) {
+                        return pkt.into_owned();
+                    }
+                }
+            })
+            .await
+            .unwrap();
+            assert_eq!(pkt[9], 17);
+            assert_eq!(&pkt[12..16], &server.ip().octets());
+            assert_eq!(&pkt[16..20], &client.ip().octets());
+            assert_eq!(&pkt[20..22], &server.port().to_be_bytes());
+            assert_eq!(&pkt[22..24], &client.port().to_be_bytes());
+            assert_eq!(&pkt[28..], &payload[..]);
+        });
+    }
+}
@@ -32,8 +32,10 @@ use crate::{
     session::{Session, SocksAddr},
 };
 
+use super::frag::Reassembler;
 use super::lwip::*;
-use super::output::{output_ip4, OUTPUT_CB_PTR};
+use super::ndp;
+use super::output::{output_ip4, output_ip6, OUTPUT_CB_PTR};
 use super::tcp_listener::TcpListener;
 use super::tcp_stream::TcpStream;
 use super::udp::{send_udp, UdpListener};
@@ -48,6 +50,7 @@ pub struct NetStackImpl {
     dispatcher: Arc<Dispatcher>,
     nat_manager: Arc<NatManager>,
     fakedns: Arc<TokioMutex<FakeDns>>,
+    reassembler: Reassembler,
 }
 
 unsafe impl Sync for NetStackImpl {}
@@ -59,13 +62,16 @@ impl NetStackImpl {
         dispatcher: Arc<Dispatcher>,
         nat_manager: Arc<NatManager>,
         fakedns: Arc<TokioMutex<FakeDns>>,
+        mtu: u16,
     ) -> Box<Self> {
         LWIP_INIT.call_once(|| unsafe { lwip_init() });
 
         unsafe {
             (*netif_list).output = Some(output_ip4);
-            (*netif_list).mtu = 1500;
-            // (*netif_list).output_ip6 = Some(output_ip6);
+            (*netif_list).output_ip6 = Some(output_ip6);
+            // Packets from the stack are fragmented to fit in the MTU.
+            (*netif_list).mtu = mtu;
+            (*netif_list).mtu6 = mtu;
         }
 
         let (tx, rx): (Sender<Vec<u8>>, Receiver<Vec<u8>>) = mpsc::channel();
@@ -78,6 +84,7 @@ impl NetStackImpl {
             dispatcher,
             nat_manager,
             fakedns,
+            reassembler: Reassembler::new(),
         });
 
         unsafe {
@@ -135,6 +142,7 @@ impl NetStackImpl {
         let lwip_lock = stack.lwip_lock.clone();
         let nat_manager = stack.nat_manager.clone();
         let fakedns = stack.fakedns.clone();
+        let dispatcher = stack.dispatcher.clone();
         tokio::spawn(async move {
             let mut listener = UdpListener::new();
             let nat_manager = nat_manager.clone();
@@ -180,7 +188,12 @@ impl NetStackImpl {
                         SocksAddr::Domain(domain, port) => {
                             // TODO we're doing this for every packet! optimize needed
                             // trace!("downlink querying fake ip for domain {}", &domain);
-                            if let Some(ip) = fakedns2.lock().await.query_fake_ip(&domain) {
+                            // The fake IP of the family the client sent to.
+                            if let Some(ip) = fakedns2
+                                .lock()
+                                .await
+                                .query_fake_ip(&domain, dst_addr.is_ipv6())
+                            {
                                 SocketAddr::new(ip, port)
                             } else {
                                 warn!(
@@ -237,6 +250,31 @@ impl NetStackImpl {
                             trace!("generate fake ip failed: {}", err);
                         }
                     }
+                    // Domains not given fake IPs are answered here if a DNS
+                    // policy chooses their servers, the others are forwarded.
+                    let dns_client = dispatcher.dns_client();
+                    match FakeDns::request_domain(&pkt.data) {
+                        Ok(domain) if dns_client.has_policy(&domain) => {
+                            let mut client_ch_tx = client_ch_tx.clone();
+                            tokio::spawn(async move {
+                                match FakeDns::resolve(&dns_client, &pkt.data).await {
+                                    Ok(resp) => {
+                                        let pkt = UdpPacket {
+                                            data: resp,
+                                            src_addr: Some(SocksAddr::Ip(dst_addr)),
+                                            dst_addr: Some(SocksAddr::Ip(src_addr)),
+                                        };
+                                        let _ = client_ch_tx.send(pkt).await;
+                                    }
+                                    Err(e) => {
+                                        debug!("answer {} by dns policy failed: {}", domain, e);
+                                    }
+                                }
+                            });
+                            continue;
+                        }
+                        _ => (),
+                    }
                 }
 
                 // We're sending UDP packets to a fake IP, and there should be a paired domain,
@@ -332,21 +370,40 @@ impl AsyncRead for NetStackImpl {
 }
 
 impl AsyncWrite for NetStackImpl {
-    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let n = buf.len();
+        if ndp::is_neighbor_discovery(buf) {
+            if let Some(pkt) = ndp::advertise(buf) {
+                let _ = self.output(pkt);
+            }
+            return Poll::Ready(Ok(n));
+        }
+        // Fragments are held until the whole packet arrives.
+        let pkt = match self.reassembler.push(buf) {
+            Some(v) => v,
+            None => return Poll::Ready(Ok(n)),
+        };
         unsafe {
             let _g = self.lwip_lock.lock();
 
-            let pbuf = pbuf_alloc(pbuf_layer_PBUF_RAW, buf.len() as u16_t, pbuf_type_PBUF_RAM);
+            let pbuf = pbuf_alloc(pbuf_layer_PBUF_RAW, pkt.len() as u16_t, pbuf_type_PBUF_RAM);
             if pbuf.is_null() {
-                warn!("alloc null pbuf");
-                return Poll::Pending;
+                // Nothing wakes the writer once memory is freed, and the
+                // fragments are already consumed, so the packet is dropped
+                // rather than stalling the loop writing packets.
+                warn!("alloc null pbuf, dropped {} bytes", pkt.len());
+                return Poll::Ready(Ok(n));
             }
-            pbuf_take(pbuf, buf.as_ptr() as *const raw::c_void, buf.len() as u16_t);
+            pbuf_take(pbuf, pkt.as_ptr() as *const raw::c_void, pkt.len() as u16_t);
 
             if let Some(input_fn) = (*netif_list).input {
                 let err = input_fn(pbuf, netif_list);
                 if err == err_enum_t_ERR_OK as err_t {
-                    Poll::Ready(Ok(buf.len()))
+                    Poll::Ready(Ok(n))
                 } else {
                     pbuf_free(pbuf);
                     Poll::Ready(Err(io::Error::new(
@@ -13,6 +13,11 @@ impl TcpStream {
     pub fn new(stream: Box<TcpStreamImpl>) -> Self {
         TcpStream { inner: stream }
     }
+
+    /// Aborts the connection with a RST when dropped, rather than closing it.
+    pub fn reset_on_drop(&mut self) {
+        self.inner.reset_on_drop();
+    }
 }
 
 impl AsyncRead for TcpStream {
@@ -119,6 +119,8 @@ pub struct TcpStreamImpl {
     rx: Receiver<Vec<u8>>,
     errored: bool,
     local_closed: bool,
+    // aborts rather than closes the pcb on drop
+    reset: bool,
     write_buf: BytesMut,
 }
 
@@ -139,6 +141,7 @@ impl TcpStreamImpl {
                 rx,
                 errored: false,
                 local_closed: false,
+                reset: false,
                 write_buf: BytesMut::with_capacity(4 * 1024),
             });
             let arg = &*stream as *const TcpStreamImpl as *mut raw::c_void;
@@ -172,12 +175,25 @@ impl TcpStreamImpl {
     }
 }
 
+impl TcpStreamImpl {
+    pub fn reset_on_drop(&mut self) {
+        self.reset = true;
+    }
+}
+
 impl Drop for TcpStreamImpl {
     fn drop(&mut self) {
         trace!("tcp drop {}", self.local_addr());
         unsafe {
             let _g = self.lwip_lock.lock();
-            if !self.errored {
+            if !self.errored && self.reset {
+                // The pcb is freed by tcp_abort, clear the callbacks first.
+                tcp_arg(self.pcb, std::ptr::null_mut());
+                tcp_recv(self.pcb, None);
+                tcp_sent(self.pcb, None);
+                tcp_err(self.pcb, None);
+                tcp_abort(self.pcb);
+            } else if !self.errored {
                 // TODO
                 let err = tcp_close(self.pcb);
                 if err != err_enum_t_ERR_OK as err_t {
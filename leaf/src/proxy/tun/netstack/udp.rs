@@ -87,6 +87,17 @@ pub fn send_udp(
     pcb: usize,
     data: &[u8],
 ) {
+    // IPv4 total length - IP header - UDP header, the IPv6 payload length
+    // doesn't include the IP header.
+    let max_len = if dst_addr.is_ipv6() {
+        65535 - 8
+    } else {
+        65535 - 20 - 8
+    };
+    if data.len() > max_len {
+        warn!("udp datagram too large: {} bytes", data.len());
+        return;
+    }
     unsafe {
         let _g = lwip_lock.lock();
         let pbuf = pbuf_alloc_reference(
@@ -1,7 +1,6 @@
 use std::{
-    ffi, mem,
-    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
-    str::FromStr,
+    mem,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
 };
 
 use anyhow::anyhow;
@@ -9,32 +8,77 @@ use anyhow::Result;
 
 use super::lwip::*;
 
+// Addresses in ip_addr_t are in network byte order, each u32 as it's laid
+// out in the packet.
 pub fn to_socket_addr(addr: &ip_addr_t, port: u16_t) -> Result<SocketAddr> {
-    unsafe {
-        let src_ip = ffi::CStr::from_ptr(ipaddr_ntoa(addr))
-            .to_str()
-            .map_err(|_| anyhow!("to_sockset_addr failed"))?;
-        Ok(SocketAddr::from(SocketAddrV4::new(
-            Ipv4Addr::from_str(src_ip).map_err(|_| anyhow!("to_sockset_addr failed"))?,
-            port as u16,
-        )))
-    }
+    let ip = unsafe {
+        match addr.type_ as lwip_ip_addr_type {
+            lwip_ip_addr_type_IPADDR_TYPE_V4 => {
+                IpAddr::V4(Ipv4Addr::from(u32::from_be(addr.u_addr.ip4.addr)))
+            }
+            lwip_ip_addr_type_IPADDR_TYPE_V6 => {
+                let mut octets = [0u8; 16];
+                for (i, word) in addr.u_addr.ip6.addr.iter().enumerate() {
+                    octets[i * 4..i * 4 + 4].copy_from_slice(&word.to_ne_bytes());
+                }
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            t => return Err(anyhow!("to_socket_addr failed: unexpected type {}", t)),
+        }
+    };
+    Ok(SocketAddr::new(ip, port as u16))
 }
 
 pub fn to_ip_addr_t(ip: &IpAddr) -> Result<ip_addr_t> {
     let mut ip_addr = ip_addr_t {
         u_addr: unsafe { mem::zeroed() },
-        type_: unsafe { mem::zeroed() },
+        type_: 0,
     };
-    let addr_str = ip.to_string();
-    let addr_str_bytes = addr_str.as_bytes();
-    let addr_cstring =
-        ffi::CString::new(addr_str_bytes).map_err(|_| anyhow!("to_ip_addr_t failed"))?;
-    let addr_cstring_bytes = addr_cstring.to_bytes_with_nul();
-    let cp = unsafe { ffi::CStr::from_bytes_with_nul_unchecked(addr_cstring_bytes).as_ptr() };
-    let ret = unsafe { ipaddr_aton(cp, &mut ip_addr as *mut ip_addr_t) };
-    if ret == 0 {
-        return Err(anyhow!("ipaddr_aton failed"));
+    match ip {
+        IpAddr::V4(ip) => {
+            ip_addr.u_addr.ip4 = ip4_addr_t {
+                addr: u32::from_ne_bytes(ip.octets()),
+            };
+            ip_addr.type_ = lwip_ip_addr_type_IPADDR_TYPE_V4 as u8_t;
+        }
+        IpAddr::V6(ip) => {
+            let octets = ip.octets();
+            let mut addr = [0u32; 4];
+            for (i, word) in addr.iter_mut().enumerate() {
+                *word = u32::from_ne_bytes([
+                    octets[i * 4],
+                    octets[i * 4 + 1],
+                    octets[i * 4 + 2],
+                    octets[i * 4 + 3],
+                ]);
+            }
+            // Zone 0 is IP6_NO_ZONE.
+            ip_addr.u_addr.ip6 = ip6_addr_t { addr, zone: 0 };
+            ip_addr.type_ = lwip_ip_addr_type_IPADDR_TYPE_V6 as u8_t;
+        }
     }
     Ok(ip_addr)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_addr_conversion() {
+        for s in &["10.0.0.1:443", "[2001:db8::1]:53", "[::ffff:1.2.3.4]:80"] {
+            let addr: SocketAddr = s.parse().unwrap();
+            let ip_addr = to_ip_addr_t(&addr.ip()).unwrap();
+            assert_eq!(to_socket_addr(&ip_addr, addr.port()).unwrap(), addr);
+        }
+
+        let ip_addr = to_ip_addr_t(&"2001:db8::1".parse().unwrap()).unwrap();
+        assert_eq!(
+            ip_addr.type_ as lwip_ip_addr_type,
+            lwip_ip_addr_type_IPADDR_TYPE_V6
+        );
+        // network byte order
+        let first = unsafe { ip_addr.u_addr.ip6.addr[0] };
+        assert_eq!(first.to_ne_bytes(), [0x20, 0x01, 0x0d, 0xb8]);
+    }
+}
@@ -0,0 +1,204 @@
+//! UDP over TCP (UoT), the framing used by sing-box and shadowsocks-rust to
+//! relay datagrams over a stream connected to a magic address.
+
+use std::{cmp::min, io};
+
+use async_trait::async_trait;
+use byteorder::{BigEndian, ByteOrder};
+use bytes::{BufMut, BytesMut};
+use futures::TryFutureExt;
+use log::*;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+
+use crate::session::{SocksAddr, SocksAddrWireType};
+
+use super::{OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf};
+
+pub static MAGIC_ADDRESS_V1: &str = "sp.udp-over-tcp.arpa";
+pub static MAGIC_ADDRESS_V2: &str = "sp.v2.udp-over-tcp.arpa";
+
+/// Returns the address a stream connects to for the UoT version, 1 or 2.
+pub fn magic_address(version: u32) -> io::Result<SocksAddr> {
+    match version {
+        1 => Ok(SocksAddr::Domain(MAGIC_ADDRESS_V1.to_string(), 0)),
+        2 => Ok(SocksAddr::Domain(MAGIC_ADDRESS_V2.to_string(), 0)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported udp over tcp version {}", version),
+        )),
+    }
+}
+
+/// A datagram framed over a stream. Each packet is the address, the length
+/// and the payload. In version 2 the stream starts with a request, which is
+/// always in the non-connect mode so packets can go to any address.
+pub struct UotDatagram<S> {
+    stream: S,
+    head: Option<BytesMut>,
+    destination: Option<SocksAddr>,
+}
+
+impl<S> UotDatagram<S> {
+    /// Creates a datagram over a stream already connected to the magic
+    /// address, `head` is sent before the first packet, e.g. a handshake not
+    /// sent yet by the stream.
+    pub fn new(
+        stream: S,
+        version: u32,
+        mut head: Option<BytesMut>,
+        destination: &SocksAddr,
+    ) -> io::Result<Self> {
+        if version == 2 {
+            let head = head.get_or_insert_with(BytesMut::new);
+            head.put_u8(0); // not connect
+            destination.write_buf(head, SocksAddrWireType::PortLast)?;
+        }
+        // Packets from a domain destination are reported as from the domain.
+        let destination = match destination {
+            SocksAddr::Domain(..) => Some(destination.clone()),
+            _ => None,
+        };
+        Ok(UotDatagram {
+            stream,
+            head,
+            destination,
+        })
+    }
+}
+
+impl<S> OutboundDatagram for UotDatagram<S>
+where
+    S: 'static + AsyncRead + AsyncWrite + Unpin + Send + Sync,
+{
+    fn split(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn OutboundDatagramRecvHalf>,
+        Box<dyn OutboundDatagramSendHalf>,
+    ) {
+        let (r, w) = tokio::io::split(self.stream);
+        (
+            Box::new(UotDatagramRecvHalf {
+                inner: r,
+                destination: self.destination,
+                received: false,
+            }),
+            Box::new(UotDatagramSendHalf(w, self.head)),
+        )
+    }
+}
+
+pub struct UotDatagramRecvHalf<T> {
+    inner: ReadHalf<T>,
+    destination: Option<SocksAddr>,
+    received: bool,
+}
+
+impl<T> UotDatagramRecvHalf<T>
+where
+    T: AsyncRead + AsyncWrite + Send + Sync,
+{
+    async fn read_packet(&mut self) -> io::Result<(SocksAddr, BytesMut)> {
+        let addr = SocksAddr::read_from(&mut self.inner, SocksAddrWireType::PortLast).await?;
+        let mut buf = BytesMut::new();
+        buf.resize(2, 0);
+        self.inner.read_exact(&mut buf).await?;
+        let len = BigEndian::read_u16(&buf) as usize;
+        buf.resize(len, 0);
+        self.inner.read_exact(&mut buf).await?;
+        Ok((addr, buf))
+    }
+}
+
+#[async_trait]
+impl<T> OutboundDatagramRecvHalf for UotDatagramRecvHalf<T>
+where
+    T: AsyncRead + AsyncWrite + Send + Sync,
+{
+    async fn recv_from(&mut self, buf: &mut [u8]) -> io::Result<(usize, SocksAddr)> {
+        let (addr, payload) = match self.read_packet().await {
+            Ok(v) => v,
+            Err(e) if !self.received && e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "udp over tcp stream closed, the server may not support it",
+                ));
+            }
+            Err(e) => return Err(e),
+        };
+        self.received = true;
+        let to_write = min(payload.len(), buf.len());
+        if to_write < payload.len() {
+            warn!(
+                "truncated udp payload, buf size too small: {} < {}",
+                buf.len(),
+                payload.len()
+            );
+        }
+        buf[..to_write].copy_from_slice(&payload[..to_write]);
+        match &self.destination {
+            Some(destination) => Ok((to_write, destination.clone())),
+            None => Ok((to_write, addr)),
+        }
+    }
+}
+
+pub struct UotDatagramSendHalf<T>(WriteHalf<T>, Option<BytesMut>);
+
+#[async_trait]
+impl<T> OutboundDatagramSendHalf for UotDatagramSendHalf<T>
+where
+    T: AsyncRead + AsyncWrite + Send + Sync,
+{
+    async fn send_to(&mut self, buf: &[u8], target: &SocksAddr) -> io::Result<usize> {
+        let mut data = self.1.take().unwrap_or_else(BytesMut::new);
+        target.write_buf(&mut data, SocksAddrWireType::PortLast)?;
+        data.put_u16(buf.len() as u16);
+        data.put_slice(buf);
+        self.0.write_all(&data).map_ok(|_| buf.len()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    #[test]
+    fn test_uot_datagram() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let target = SocksAddr::from(("1.2.3.4".parse::<std::net::IpAddr>().unwrap(), 53));
+            tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                // The v2 request, then the packet.
+                let mut buf = [0u8; 8 + 7 + 2 + 4];
+                stream.read_exact(&mut buf).await.unwrap();
+                assert_eq!(&buf[..8], &[0, 1, 1, 2, 3, 4, 0, 53]);
+                assert_eq!(&buf[8..15], &[1, 1, 2, 3, 4, 0, 53]);
+                assert_eq!(&buf[15..17], &[0, 4]);
+                assert_eq!(&buf[17..], b"ping");
+                stream
+                    .write_all(&[1, 1, 2, 3, 4, 0, 53, 0, 4, b'p', b'o', b'n', b'g'])
+                    .await
+                    .unwrap();
+            });
+            let stream = TcpStream::connect(addr).await.unwrap();
+            let dgram = Box::new(UotDatagram::new(stream, 2, None, &target).unwrap());
+            let (mut r, mut s) = dgram.split();
+            assert_eq!(s.send_to(b"ping", &target).await.unwrap(), 4);
+            let mut buf = [0u8; 16];
+            let (n, from) = r.recv_from(&mut buf).await.unwrap();
+            assert_eq!(&buf[..n], b"pong");
+            assert_eq!(from.to_string(), target.to_string());
+            assert!(r.recv_from(&mut buf).await.is_err());
+        });
+    }
+}
@@ -0,0 +1,173 @@
+use std::{io, sync::Arc, time::Duration};
+
+use futures::future::{self, AbortHandle, BoxFuture, FutureExt};
+use log::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex as TokioMutex;
+use tokio::time::{timeout, Instant};
+
+use crate::{
+    app::outbound::selector::OutboundSelector,
+    option,
+    proxy::OutboundHandler,
+    session::{Session, SocksAddr},
+};
+
+pub mod tcp;
+pub mod udp;
+
+pub use tcp::Handler as TcpHandler;
+pub use udp::Handler as UdpHandler;
+
+pub static NAME: &str = "urltest";
+
+/// Parses an HTTP URL into the host, port and path, HTTPS is not supported.
+pub fn parse_url(url: &str) -> Option<(String, u16, String)> {
+    if !url.starts_with("http://") {
+        return None;
+    }
+    let url = &url["http://".len()..];
+    let (authority, path) = match url.find('/') {
+        Some(i) => (&url[..i], &url[i..]),
+        None => (url, "/"),
+    };
+    let parts: Vec<&str> = authority.rsplitn(2, ':').collect();
+    let (host, port) = if parts.len() == 2 {
+        (parts[1], parts[0].parse::<u16>().ok()?)
+    } else {
+        (authority, 80)
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), port, path.to_string()))
+}
+
+/// Probes the actors on an interval and selects the fastest one, started by
+/// the first session handled by either the TCP or the UDP handler.
+pub struct Checker {
+    task: TokioMutex<Option<BoxFuture<'static, ()>>>,
+    abort_handle: AbortHandle,
+}
+
+impl Checker {
+    pub fn new(
+        actors: Vec<Arc<dyn OutboundHandler>>,
+        selector: Arc<OutboundSelector>,
+        url: (String, u16, String),
+        interval: Duration,
+        tolerance: Duration,
+    ) -> Self {
+        let (task, abort_handle) = future::abortable(async move {
+            let (host, port, path) = url;
+            loop {
+                let rtts = future::join_all(actors.iter().map(|a| async {
+                    match timeout(
+                        Duration::from_secs(option::URL_TEST_TIMEOUT),
+                        probe(a, &host, port, &path),
+                    )
+                    .await
+                    {
+                        Ok(Ok(rtt)) => Some(rtt),
+                        Ok(Err(e)) => {
+                            debug!("urltest probe [{}] failed: {}", a.tag(), e);
+                            None
+                        }
+                        Err(_) => {
+                            debug!("urltest probe [{}] timeout", a.tag());
+                            None
+                        }
+                    }
+                }))
+                .await;
+                for (i, rtt) in rtts.into_iter().enumerate() {
+                    selector.set_rtt(i, rtt);
+                }
+                match selector.select_fastest(tolerance) {
+                    Some(i) => debug!(
+                        "urltest selects [{}] ({}ms)",
+                        actors[i].tag(),
+                        selector.get_rtt(i).unwrap_or_default().as_millis()
+                    ),
+                    None => debug!("urltest probes failed on all actors"),
+                }
+                tokio::time::delay_for(interval).await;
+            }
+        });
+        Checker {
+            task: TokioMutex::new(Some(Box::pin(task.map(|_| ())))),
+            abort_handle,
+        }
+    }
+
+    /// Returns the handle to stop the probing task, whether it has been
+    /// spawned or not.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.abort_handle.clone()
+    }
+
+    /// Spawns the probing task if not yet.
+    pub async fn start(&self) {
+        if let Some(task) = self.task.lock().await.take() {
+            tokio::spawn(task);
+        }
+    }
+}
+
+// Times an HTTP GET through the actor till the status line is received, the
+// handshake with the actor included.
+async fn probe(
+    actor: &Arc<dyn OutboundHandler>,
+    host: &str,
+    port: u16,
+    path: &str,
+) -> io::Result<Duration> {
+    let mut sess = Session::default();
+    sess.destination = SocksAddr::Domain(host.to_string(), port);
+    let start = Instant::now();
+    let mut stream = actor.handle_tcp(&sess, None).await?;
+    let host_header = if port == 80 {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, port)
+    };
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: leaf\r\nConnection: close\r\n\r\n",
+        path, host_header
+    );
+    stream.write_all(request.as_bytes()).await?;
+    // e.g. HTTP/1.1 204
+    let mut buf = [0u8; 12];
+    stream.read_exact(&mut buf).await?;
+    if !buf.starts_with(b"HTTP/1.") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid http response",
+        ));
+    }
+    Ok(Instant::now().duration_since(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(
+            parse_url("http://www.gstatic.com/generate_204"),
+            Some((
+                "www.gstatic.com".to_string(),
+                80,
+                "/generate_204".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_url("http://1.2.3.4:8080"),
+            Some(("1.2.3.4".to_string(), 8080, "/".to_string()))
+        );
+        assert_eq!(parse_url("https://www.gstatic.com/generate_204"), None);
+        assert_eq!(parse_url("http://:80/"), None);
+        assert_eq!(parse_url("http://a.com:x/"), None);
+    }
+}
@@ -0,0 +1,44 @@
+use std::{io, sync::Arc};
+
+use async_trait::async_trait;
+use log::*;
+
+use crate::{
+    app::outbound::selector::OutboundSelector,
+    proxy::{OutboundConnect, OutboundHandler, ProxyStream, TcpOutboundHandler},
+    session::Session,
+};
+
+use super::Checker;
+
+pub struct Handler {
+    pub actors: Vec<Arc<dyn OutboundHandler>>,
+    pub selector: Arc<OutboundSelector>,
+    pub checker: Arc<Checker>,
+}
+
+#[async_trait]
+impl TcpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    async fn handle_tcp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _stream: Option<Box<dyn ProxyStream>>,
+    ) -> io::Result<Box<dyn ProxyStream>> {
+        self.checker.start().await;
+        let a = &self.actors[self.selector.selected_index()];
+        debug!(
+            "urltest handles tcp [{}] to [{}]",
+            sess.destination,
+            a.tag()
+        );
+        a.handle_tcp(sess, None).await
+    }
+}
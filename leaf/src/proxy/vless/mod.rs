@@ -1,4 +1,6 @@
+mod protocol;
 mod stream;
+pub use protocol::Flow;
 pub use stream::*;
 
 pub mod tcp;
@@ -0,0 +1,112 @@
+use std::{io, str::FromStr};
+
+use bytes::{BufMut, BytesMut};
+use uuid::Uuid;
+
+use crate::session::{SocksAddr, SocksAddrWireType};
+
+pub const VERSION: u8 = 0x0;
+
+type RequestCommand = u8;
+
+pub const REQUEST_COMMAND_TCP: RequestCommand = 0x01;
+pub const REQUEST_COMMAND_UDP: RequestCommand = 0x02;
+
+// Field number and wire type of the flow in the protobuf encoded addons.
+const ADDONS_FLOW_TAG: u8 = 0x0a;
+
+/// Flow control of a connection, carried in the request addons.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flow {
+    None,
+    XtlsRprxVision,
+}
+
+impl Flow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Flow::None => "",
+            Flow::XtlsRprxVision => "xtls-rprx-vision",
+        }
+    }
+}
+
+impl FromStr for Flow {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" | "none" => Ok(Flow::None),
+            "xtls-rprx-vision" => Ok(Flow::XtlsRprxVision),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported vless flow: {}", s),
+            )),
+        }
+    }
+}
+
+pub struct RequestHeader<'a> {
+    pub uuid: Uuid,
+    pub flow: Flow,
+    pub command: RequestCommand,
+    pub address: &'a SocksAddr,
+}
+
+impl<'a> RequestHeader<'a> {
+    pub fn encode(&self, buf: &mut BytesMut) -> io::Result<()> {
+        buf.put_u8(VERSION);
+        buf.put_slice(self.uuid.as_bytes());
+        let flow = self.flow.as_str();
+        if flow.is_empty() {
+            buf.put_u8(0x0);
+        } else {
+            buf.put_u8((2 + flow.len()) as u8);
+            buf.put_u8(ADDONS_FLOW_TAG);
+            buf.put_u8(flow.len() as u8);
+            buf.put_slice(flow.as_bytes());
+        }
+        buf.put_u8(self.command);
+        self.address.write_buf(buf, SocksAddrWireType::PortFirst)
+    }
+}
+
+pub fn parse_uuid(uuid: &str) -> io::Result<Uuid> {
+    Uuid::parse_str(uuid)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("parse uuid failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_request_header() {
+        let uuid = parse_uuid("b831381d-6324-4d53-ad4f-8cda48b30811").unwrap();
+        let address = SocksAddr::Domain("example.com".to_string(), 443);
+        let mut header = RequestHeader {
+            uuid,
+            flow: Flow::None,
+            command: REQUEST_COMMAND_TCP,
+            address: &address,
+        };
+        let mut buf = BytesMut::new();
+        header.encode(&mut buf).unwrap();
+        assert_eq!(buf[0], VERSION);
+        assert_eq!(&buf[1..17], uuid.as_bytes());
+        // no addons, tcp, port, domain
+        assert_eq!(&buf[17..22], &[0x0, 0x01, 0x01, 0xbb, 0x02]);
+        assert_eq!(buf[22] as usize, "example.com".len());
+        assert_eq!(&buf[23..], b"example.com");
+
+        header.flow = "xtls-rprx-vision".parse().unwrap();
+        header.command = REQUEST_COMMAND_UDP;
+        let mut buf = BytesMut::new();
+        header.encode(&mut buf).unwrap();
+        assert_eq!(&buf[17..20], &[18, ADDONS_FLOW_TAG, 16]);
+        assert_eq!(&buf[20..36], b"xtls-rprx-vision");
+        assert_eq!(buf[36], REQUEST_COMMAND_UDP);
+
+        assert!("xtls-rprx-direct".parse::<Flow>().is_err());
+    }
+}
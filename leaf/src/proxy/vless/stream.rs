@@ -7,8 +7,11 @@ use futures::{
 };
 use tokio::io::{AsyncRead, AsyncWrite};
 
+use super::protocol::VERSION;
+
 enum ReadState {
     WaitingResponseHeader,
+    WaitingResponseAddons(usize),
     Streaming,
 }
 
@@ -71,19 +74,23 @@ impl<T: AsyncRead + Unpin> AsyncRead for VLessAuthStream<T> {
                 ReadState::WaitingResponseHeader => {
                     let me = &mut *self;
                     ready!(me.poll_read_exact(cx, 2))?;
-                    if me.read_buf[0] != 0x0 {
+                    if me.read_buf[0] != VERSION {
                         return Poll::Ready(Err(io::Error::new(
                             io::ErrorKind::Other,
                             format!("invalid vless version: {}", me.read_buf[0]),
                         )));
                     }
-                    if me.read_buf[1] != 0x0 {
-                        return Poll::Ready(Err(io::Error::new(
-                            io::ErrorKind::Other,
-                            format!("unsupport vless addons: {}", me.read_buf[1]),
-                        )));
+                    let addons_len = me.read_buf[1] as usize;
+                    if addons_len > 0 {
+                        me.read_state = ReadState::WaitingResponseAddons(addons_len);
+                    } else {
+                        me.read_state = ReadState::Streaming;
                     }
-
+                }
+                ReadState::WaitingResponseAddons(addons_len) => {
+                    let me = &mut *self;
+                    // Response addons are not used.
+                    ready!(me.poll_read_exact(cx, addons_len))?;
                     me.read_state = ReadState::Streaming;
                 }
                 ReadState::Streaming => {
@@ -3,26 +3,37 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use bytes::{BufMut, BytesMut};
+use bytes::BytesMut;
 use tokio::io::AsyncWriteExt;
-use uuid::Uuid;
 
 use crate::{
     app::dns_client::DnsClient,
-    proxy::{stream::SimpleProxyStream, OutboundConnect, ProxyStream, TcpOutboundHandler},
-    session::{Session, SocksAddrWireType},
+    proxy::{
+        stream::SimpleProxyStream, OutboundConnect, ProxyStream, TcpConnector, TcpOptions,
+        TcpOutboundHandler,
+    },
+    session::Session,
 };
 
+use super::protocol::{parse_uuid, Flow, RequestHeader, REQUEST_COMMAND_TCP};
 use super::*;
 
 pub struct Handler {
     pub address: String,
     pub port: u16,
     pub uuid: String,
+    pub flow: Flow,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
+
 #[async_trait]
 impl TcpOutboundHandler for Handler {
     fn name(&self) -> &str {
@@ -42,16 +53,21 @@ impl TcpOutboundHandler for Handler {
         sess: &'a Session,
         stream: Option<Box<dyn ProxyStream>>,
     ) -> io::Result<Box<dyn ProxyStream>> {
-        let uuid = Uuid::parse_str(&self.uuid).map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("parse uuid failed: {}", e))
-        })?;
+        // TODO vision padding
+        if self.flow == Flow::XtlsRprxVision {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "vless flow xtls-rprx-vision is not implemented",
+            ));
+        }
+        let header = RequestHeader {
+            uuid: parse_uuid(&self.uuid)?,
+            flow: self.flow,
+            command: REQUEST_COMMAND_TCP,
+            address: &sess.destination,
+        };
         let mut buf = BytesMut::new();
-        buf.put_u8(0x0); // version
-        buf.put_slice(uuid.as_bytes()); // uuid
-        buf.put_u8(0x0); // addons
-        buf.put_u8(0x01); // tcp command
-        sess.destination
-            .write_buf(&mut buf, SocksAddrWireType::PortFirst)?;
+        header.encode(&mut buf)?;
 
         let mut stream = if let Some(stream) = stream {
             stream
@@ -9,25 +9,33 @@ use bytes::{BufMut, BytesMut};
 use futures::future::TryFutureExt;
 use log::*;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
-use uuid::Uuid;
 
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
         OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf,
-        OutboundTransport, UdpOutboundHandler, UdpTransportType,
+        OutboundTransport, TcpConnector, TcpOptions, UdpOutboundHandler, UdpTransportType,
     },
-    session::{Session, SocksAddr, SocksAddrWireType},
+    session::{Session, SocksAddr},
 };
 
+use super::protocol::{parse_uuid, Flow, RequestHeader, REQUEST_COMMAND_UDP, VERSION};
+
 pub struct Handler {
     pub address: String,
     pub port: u16,
     pub uuid: String,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
+
 #[async_trait]
 impl UdpOutboundHandler for Handler {
     fn name(&self) -> &str {
@@ -51,16 +59,15 @@ impl UdpOutboundHandler for Handler {
         sess: &'a Session,
         transport: Option<OutboundTransport>,
     ) -> io::Result<Box<dyn OutboundDatagram>> {
-        let uuid = Uuid::parse_str(&self.uuid).map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("parse uuid failed: {}", e))
-        })?;
+        // Flows apply to TCP only, UDP sessions are requested without.
+        let header = RequestHeader {
+            uuid: parse_uuid(&self.uuid)?,
+            flow: Flow::None,
+            command: REQUEST_COMMAND_UDP,
+            address: &sess.destination,
+        };
         let mut buf = BytesMut::new();
-        buf.put_u8(0x0); // version
-        buf.put_slice(uuid.as_bytes()); // uuid
-        buf.put_u8(0x0); // addons
-        buf.put_u8(0x02); // ucp command
-        sess.destination
-            .write_buf(&mut buf, SocksAddrWireType::PortFirst)?;
+        header.encode(&mut buf)?;
 
         let mut stream = if let Some(OutboundTransport::Stream(stream)) = transport {
             stream
@@ -82,6 +89,9 @@ impl UdpOutboundHandler for Handler {
     }
 }
 
+/// A UDP session over a VLESS stream, datagrams are prefixed with 2-byte
+/// lengths. The destination is fixed by the request, all datagrams are sent
+/// to and received from it.
 pub struct Datagram<S> {
     stream: S,
     target: SocksAddr,
@@ -112,28 +122,25 @@ impl<T> OutboundDatagramRecvHalf for DatagramRecvHalf<T>
 where
     T: AsyncRead + AsyncWrite + Send + Sync,
 {
-    async fn recv_from(&mut self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+    async fn recv_from(&mut self, buf: &mut [u8]) -> io::Result<(usize, SocksAddr)> {
         let mut buf2 = BytesMut::new();
 
         if !self.2 {
-            // read version
-            buf2.resize(1, 0);
+            // read version and addons length
+            buf2.resize(2, 0);
             let _ = self.0.read_exact(&mut buf2).await?;
-            if buf2[0] != 0x0 {
+            if buf2[0] != VERSION {
                 return Err(io::Error::new(
                     io::ErrorKind::Other,
                     format!("invalid vless version: {}", buf2[0]),
                 ));
             }
 
-            // read addons
-            buf2.resize(1, 0);
-            let _ = self.0.read_exact(&mut buf2).await?;
-            if buf2[0] != 0x0 {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    format!("invalid vless version: {}", buf2[0]),
-                ));
+            // response addons are not used
+            let addons_len = buf2[1] as usize;
+            if addons_len > 0 {
+                buf2.resize(addons_len, 0);
+                let _ = self.0.read_exact(&mut buf2).await?;
             }
             self.2 = true;
         }
@@ -152,17 +159,7 @@ where
             );
         }
         buf[..to_write].copy_from_slice(&buf2[..to_write]);
-        let addr = match self.1 {
-            SocksAddr::Ip(addr) => addr,
-            _ => {
-                error!("unexpected domain address");
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    "unexpected domain address in vmess udp",
-                ));
-            }
-        };
-        Ok((to_write, addr))
+        Ok((to_write, self.1.clone()))
     }
 }
 
@@ -173,7 +170,13 @@ impl<T> OutboundDatagramSendHalf for DatagramSendHalf<T>
 where
     T: AsyncRead + AsyncWrite + Send + Sync,
 {
-    async fn send_to(&mut self, buf: &[u8], _target: &SocketAddr) -> io::Result<usize> {
+    async fn send_to(&mut self, buf: &[u8], _target: &SocksAddr) -> io::Result<usize> {
+        if buf.len() > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "udp payload too large",
+            ));
+        }
         let mut data = BytesMut::new();
         data.put_u16(buf.len() as u16);
         data.put_slice(buf);
@@ -1,17 +1,165 @@
 use std::io::Read;
 
+use aes::block_cipher::generic_array::GenericArray;
+use aes::block_cipher::{BlockCipher, NewBlockCipher};
+use aes::Aes128;
 use anyhow::{anyhow, Result};
 use byteorder::{BigEndian, ByteOrder};
 use digest::ExtendableOutputDirty;
 use log::*;
 use md5::{Digest, Md5};
+use sha2::Sha256;
 use sha3::Shake128;
+use uuid::Uuid;
 
 use crate::common::crypto::{
-    aead::{AeadCipher, AeadDecryptor, AeadEncryptor},
+    aead::{self, AeadCipher, AeadDecryptor, AeadEncryptor},
     Cipher, NonceSequence, SizedCipher,
 };
 
+const KDF_SALT_VMESS_AEAD_KDF: &[u8] = b"VMess AEAD KDF";
+const KDF_SALT_AUTH_ID_ENCRYPTION_KEY: &[u8] = b"AES Auth ID Encryption";
+const KDF_SALT_AEAD_RESP_HEADER_LEN_KEY: &[u8] = b"AEAD Resp Header Len Key";
+const KDF_SALT_AEAD_RESP_HEADER_LEN_IV: &[u8] = b"AEAD Resp Header Len IV";
+const KDF_SALT_AEAD_RESP_HEADER_PAYLOAD_KEY: &[u8] = b"AEAD Resp Header Key";
+const KDF_SALT_AEAD_RESP_HEADER_PAYLOAD_IV: &[u8] = b"AEAD Resp Header IV";
+const KDF_SALT_VMESS_HEADER_PAYLOAD_AEAD_KEY: &[u8] = b"VMess Header AEAD Key";
+const KDF_SALT_VMESS_HEADER_PAYLOAD_AEAD_IV: &[u8] = b"VMess Header AEAD Nonce";
+const KDF_SALT_VMESS_HEADER_PAYLOAD_LENGTH_AEAD_KEY: &[u8] = b"VMess Header AEAD Key_Length";
+const KDF_SALT_VMESS_HEADER_PAYLOAD_LENGTH_AEAD_IV: &[u8] = b"VMess Header AEAD Nonce_Length";
+
+// Length of the sealed length of the AEAD request and response headers.
+pub const AEAD_HEADER_LENGTH_LEN: usize = 2 + 16;
+// Length of the tag of the sealed AEAD header.
+pub const AEAD_HEADER_TAG_LEN: usize = 16;
+
+/// Returns the key of the request header, also the key to derive the keys of
+/// the AEAD header from.
+pub fn cmd_key(uuid: &Uuid) -> Vec<u8> {
+    let mut hasher = Md5::new();
+    hasher.update(uuid.as_bytes());
+    hasher.update(b"c48619fe-8f02-49e0-b9e9-edf763e17e21");
+    hasher.finalize().to_vec()
+}
+
+// The hash of the KDF, SHA-256 with no keys, otherwise HMAC with the last
+// key whose hash is the one of the rest keys.
+fn kdf_hash(keys: &[&[u8]], msg: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+    let (key, parents) = match keys.split_last() {
+        Some(v) => v,
+        None => return Sha256::digest(msg).to_vec(),
+    };
+    let mut padded_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let key = kdf_hash(parents, key);
+        padded_key[..key.len()].copy_from_slice(&key);
+    } else {
+        padded_key[..key.len()].copy_from_slice(key);
+    }
+    let mut inner: Vec<u8> = padded_key.iter().map(|b| b ^ 0x36).collect();
+    inner.extend_from_slice(msg);
+    let mut outer: Vec<u8> = padded_key.iter().map(|b| b ^ 0x5c).collect();
+    outer.extend_from_slice(&kdf_hash(parents, &inner));
+    kdf_hash(parents, &outer)
+}
+
+/// The KDF of VMess AEAD, nested HMAC-SHA256 keyed by the salt and then each
+/// element of the path.
+pub fn kdf(key: &[u8], path: &[&[u8]]) -> Vec<u8> {
+    let mut keys = vec![KDF_SALT_VMESS_AEAD_KDF];
+    keys.extend_from_slice(path);
+    kdf_hash(&keys, key)
+}
+
+// CRC-32 (IEEE) of the data.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in data {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Returns the auth ID of an AEAD request header, the encrypted timestamp in
+/// seconds, random bytes and their checksum.
+pub fn create_auth_id(cmd_key: &[u8], timestamp: u64, rand: [u8; 4]) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    BigEndian::write_u64(&mut block[..8], timestamp);
+    block[8..12].copy_from_slice(&rand);
+    let checksum = crc32(&block[..12]);
+    BigEndian::write_u32(&mut block[12..], checksum);
+    let key = kdf(cmd_key, &[KDF_SALT_AUTH_ID_ENCRYPTION_KEY]);
+    let cipher = Aes128::new(GenericArray::from_slice(&key[..16]));
+    let mut block = GenericArray::clone_from_slice(&block);
+    cipher.encrypt_block(&mut block);
+    let mut auth_id = [0u8; 16];
+    auth_id.copy_from_slice(&block);
+    auth_id
+}
+
+/// Seals the command part of a request header in the AEAD format: the auth
+/// ID, the sealed length, the connection nonce and the sealed command.
+pub fn seal_aead_header(
+    cmd_key: &[u8],
+    cmd: &[u8],
+    timestamp: u64,
+    rand: [u8; 4],
+    nonce: [u8; 8],
+) -> Result<Vec<u8>> {
+    let auth_id = create_auth_id(cmd_key, timestamp, rand);
+    let path = |salt: &'static [u8]| [salt, &auth_id[..], &nonce[..]];
+
+    let mut len = (cmd.len() as u16).to_be_bytes().to_vec();
+    let key = kdf(
+        cmd_key,
+        &path(KDF_SALT_VMESS_HEADER_PAYLOAD_LENGTH_AEAD_KEY),
+    );
+    let iv = kdf(cmd_key, &path(KDF_SALT_VMESS_HEADER_PAYLOAD_LENGTH_AEAD_IV));
+    aead::seal("aes-128-gcm", &key[..16], &iv[..12], &auth_id, &mut len)?;
+
+    let mut payload = cmd.to_vec();
+    let key = kdf(cmd_key, &path(KDF_SALT_VMESS_HEADER_PAYLOAD_AEAD_KEY));
+    let iv = kdf(cmd_key, &path(KDF_SALT_VMESS_HEADER_PAYLOAD_AEAD_IV));
+    aead::seal("aes-128-gcm", &key[..16], &iv[..12], &auth_id, &mut payload)?;
+
+    let mut out = Vec::with_capacity(16 + len.len() + 8 + payload.len());
+    out.extend_from_slice(&auth_id);
+    out.extend_from_slice(&len);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&payload);
+    Ok(out)
+}
+
+/// Opens the sealed length of an AEAD response header, with the response
+/// body key and IV.
+pub fn open_aead_response_length(key: &[u8], iv: &[u8], data: &[u8]) -> Result<usize> {
+    let mut data = data.to_vec();
+    let key = kdf(key, &[KDF_SALT_AEAD_RESP_HEADER_LEN_KEY]);
+    let iv = kdf(iv, &[KDF_SALT_AEAD_RESP_HEADER_LEN_IV]);
+    aead::open("aes-128-gcm", &key[..16], &iv[..12], &[], &mut data)?;
+    if data.len() != 2 {
+        return Err(anyhow!("invalid response header length"));
+    }
+    Ok(BigEndian::read_u16(&data) as usize)
+}
+
+/// Opens a sealed AEAD response header.
+pub fn open_aead_response_header(key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut data = data.to_vec();
+    let key = kdf(key, &[KDF_SALT_AEAD_RESP_HEADER_PAYLOAD_KEY]);
+    let iv = kdf(iv, &[KDF_SALT_AEAD_RESP_HEADER_PAYLOAD_IV]);
+    aead::open("aes-128-gcm", &key[..16], &iv[..12], &[], &mut data)?;
+    Ok(data)
+}
+
 pub fn generate_chacha20poly1305_key(key: &[u8]) -> Vec<u8> {
     let key_1 = Md5::digest(&key).to_vec();
     let key_2 = Md5::digest(&key_1).to_vec();
@@ -136,3 +284,66 @@ impl PaddingLengthGenerator for ShakeSizeParser {
         64
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors from v2ray, or produced by the v2ray algorithms.
+
+    #[test]
+    fn test_kdf() {
+        let key = kdf(
+            b"Demo Key for KDF Value Test",
+            &[
+                b"Demo Path for KDF Value Test",
+                b"Demo Path for KDF Value Test2",
+                b"Demo Path for KDF Value Test3",
+            ],
+        );
+        assert_eq!(
+            hex::encode(key),
+            "53e9d7e1bd7bd25022b71ead07d8a596efc8a845c7888652fd684b4903dc8892"
+        );
+    }
+
+    #[test]
+    fn test_seal_aead_header() {
+        let uuid = Uuid::parse_str("b831381d-6324-4d53-ad4f-8cda48b30811").unwrap();
+        let cmd_key = cmd_key(&uuid);
+        assert_eq!(hex::encode(&cmd_key), "b50d916ac0cec067981af8e5f38a758f");
+        assert_eq!(
+            hex::encode(create_auth_id(&cmd_key, 1600000000, [1, 2, 3, 4])),
+            "17f33db036b2a7d0c03b4200662291ac"
+        );
+        let header = seal_aead_header(
+            &cmd_key,
+            b"vmess aead header",
+            1600000000,
+            [1, 2, 3, 4],
+            [0, 1, 2, 3, 4, 5, 6, 7],
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(header),
+            "17f33db036b2a7d0c03b4200662291ac4a6fa3af2bd8b388a9d45374085cf989f023000102030405060704591d651ac003a929e902c953e42d595db44abe56ba52334b69a14dbe4cad3f4d"
+        );
+    }
+
+    #[test]
+    fn test_open_aead_response_header() {
+        let request_body_key: Vec<u8> = (0..16).collect();
+        let request_body_iv: Vec<u8> = (16..32).collect();
+        let key = &Sha256::digest(&request_body_key)[..16];
+        let iv = &Sha256::digest(&request_body_iv)[..16];
+        let data = hex::decode(
+            "936c422a7a3f0c4835561f50648898c4d6e3da10e4a6a796c6b2adda265894aa176da8de0a92",
+        )
+        .unwrap();
+        let len = open_aead_response_length(key, iv, &data[..AEAD_HEADER_LENGTH_LEN]).unwrap();
+        assert_eq!(len, 4);
+        let header = open_aead_response_header(key, iv, &data[AEAD_HEADER_LENGTH_LEN..]).unwrap();
+        assert_eq!(header, vec![0x2a, 0, 0, 0]);
+        assert!(open_aead_response_header(iv, key, &data[AEAD_HEADER_LENGTH_LEN..]).is_err());
+    }
+}
@@ -10,10 +10,13 @@ use hmac::{Hmac, Mac, NewMac};
 use lz_fnv::{Fnv1a, FnvHasher};
 use md5::{Digest, Md5};
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use sha2::Sha256;
 use uuid::Uuid;
 
 use crate::session::{SocksAddr, SocksAddrWireType};
 
+use super::crypto::{cmd_key, seal_aead_header};
+
 type RequestCommand = u8;
 
 pub const REQUEST_COMMAND_TCP: RequestCommand = 0x01;
@@ -23,6 +26,7 @@ type Security = u8;
 
 pub const SECURITY_TYPE_AES128_GCM: Security = 0x03;
 pub const SECURITY_TYPE_CHACHA20_POLY1305: Security = 0x04;
+pub const SECURITY_TYPE_NONE: Security = 0x05;
 
 type RequestOption = u8;
 
@@ -44,12 +48,36 @@ impl RequestHeader {
         self.option |= opt;
     }
 
+    /// Encodes the header, in the AEAD format unless the session uses the
+    /// legacy one.
     pub fn encode(&self, buf: &mut BytesMut, sess: &ClientSession) -> Result<()> {
-        // generate auth info
-        let mut timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
             Ok(n) => n.as_secs(),
             Err(_) => return Err(anyhow!("invalid system time")),
         };
+        if sess.aead {
+            self.encode_aead(buf, sess, timestamp)
+        } else {
+            self.encode_legacy(buf, sess, timestamp)
+        }
+    }
+
+    fn encode_aead(&self, buf: &mut BytesMut, sess: &ClientSession, timestamp: u64) -> Result<()> {
+        let mut cmd = BytesMut::new();
+        self.encode_command(&mut cmd, sess)?;
+        let mut rng = StdRng::from_entropy();
+        let header = seal_aead_header(&cmd_key(&self.uuid), &cmd, timestamp, rng.gen(), rng.gen())?;
+        buf.put_slice(&header);
+        Ok(())
+    }
+
+    fn encode_legacy(
+        &self,
+        buf: &mut BytesMut,
+        sess: &ClientSession,
+        mut timestamp: u64,
+    ) -> Result<()> {
+        // generate auth info
         let mut rng = StdRng::from_entropy();
         let delta: i32 = rng.gen_range(0, 30 * 2) - 30;
         timestamp = timestamp.wrapping_add(delta as u64);
@@ -62,6 +90,32 @@ impl RequestHeader {
 
         buf.put_slice(&auth_info[..]);
 
+        let mut cmd = BytesMut::new();
+        self.encode_command(&mut cmd, sess)?;
+
+        // iv for header encryption
+        let mut tmp = [0u8; 8];
+        BigEndian::write_u64(&mut tmp, timestamp as u64);
+        let mut hasher = Md5::new();
+        hasher.update(&tmp);
+        hasher.update(&tmp);
+        hasher.update(&tmp);
+        hasher.update(&tmp);
+        let iv = hasher.finalize();
+
+        // key for header ecnryption
+        let key = cmd_key(&self.uuid);
+
+        // encrypt cmd part
+        let mut enc =
+            Cfb::<Aes128>::new_var(&key, &iv).map_err(|_| anyhow!("new aes128 enc failed"))?;
+        enc.encrypt(&mut cmd);
+        buf.put_slice(&cmd);
+        Ok(())
+    }
+
+    // Encodes the command part of the header, with its checksum.
+    fn encode_command(&self, buf: &mut BytesMut, sess: &ClientSession) -> Result<()> {
         buf.put_u8(self.version);
         buf.put_slice(&sess.request_body_iv);
         buf.put_slice(&sess.request_body_key);
@@ -90,36 +144,11 @@ impl RequestHeader {
 
         // checksum
         let mut hasher = Fnv1a::<u32>::default();
-        hasher.write(&buf[auth_info.len()..]);
+        hasher.write(&buf[..]);
         let h = hasher.finish();
         let buf_size = buf.len();
         buf.resize(buf_size + 4, 0);
         BigEndian::write_u32(&mut buf[buf_size..], h);
-
-        // iv for header encryption
-        let mut tmp = [0u8; 8];
-        BigEndian::write_u64(&mut tmp, timestamp as u64);
-        let mut hasher = Md5::new();
-        hasher.update(&tmp);
-        hasher.update(&tmp);
-        hasher.update(&tmp);
-        hasher.update(&tmp);
-        let iv = hasher.finalize();
-
-        // key for header ecnryption
-        let mut hasher = Md5::new();
-        hasher.update(self.uuid.as_bytes());
-        hasher.update(
-            "c48619fe-8f02-49e0-b9e9-edf763e17e21"
-                .to_string()
-                .as_bytes(),
-        );
-        let key = hasher.finalize();
-
-        // encrypt cmd part
-        let mut enc =
-            Cfb::<Aes128>::new_var(&key, &iv).map_err(|_| anyhow!("new aes128 enc failed"))?;
-        enc.encrypt(&mut buf[auth_info.len()..]);
         Ok(())
     }
 }
@@ -130,10 +159,12 @@ pub struct ClientSession {
     pub response_body_key: Vec<u8>,
     pub response_body_iv: Vec<u8>,
     pub response_header: u8,
+    // Whether the request and response headers are in the AEAD format.
+    pub aead: bool,
 }
 
 impl ClientSession {
-    pub fn new() -> Self {
+    pub fn new(aead: bool) -> Self {
         let mut request_body_key = vec![0u8; 16];
         let mut request_body_iv = vec![0u8; 16];
         let response_header: u8;
@@ -149,8 +180,17 @@ impl ClientSession {
         (&mut request_body_iv[..]).copy_from_slice(&rand_bytes[16..32]);
         response_header = rand_bytes[32];
 
-        let response_body_key = Md5::digest(&request_body_key).to_vec();
-        let response_body_iv = Md5::digest(&request_body_iv).to_vec();
+        let (response_body_key, response_body_iv) = if aead {
+            (
+                Sha256::digest(&request_body_key)[..16].to_vec(),
+                Sha256::digest(&request_body_iv)[..16].to_vec(),
+            )
+        } else {
+            (
+                Md5::digest(&request_body_key).to_vec(),
+                Md5::digest(&request_body_iv).to_vec(),
+            )
+        };
 
         ClientSession {
             request_body_key,
@@ -158,6 +198,7 @@ impl ClientSession {
             response_body_key,
             response_body_iv,
             response_header,
+            aead,
         }
     }
 }
@@ -16,11 +16,16 @@ use crate::common::crypto::{
     Decryptor, Encryptor,
 };
 
-use super::crypto::{PaddingLengthGenerator, ShakeSizeParser, VMessAEADSequence};
+use super::crypto::{
+    open_aead_response_header, open_aead_response_length, PaddingLengthGenerator, ShakeSizeParser,
+    VMessAEADSequence, AEAD_HEADER_LENGTH_LEN, AEAD_HEADER_TAG_LEN,
+};
 use super::protocol::ClientSession;
 
 enum ReadState {
     WaitingResponseHeader,
+    WaitingAeadResponseLength,
+    WaitingAeadResponseHeader(usize),
     WaitingLength,
     WaitingData(usize, usize),
     PendingData(usize),
@@ -34,11 +39,14 @@ enum WriteState {
 pub struct VMessAuthStream<T> {
     inner: T,
     sess: ClientSession,
-    enc: AeadEncryptor<VMessAEADSequence>,
+    // None if the payload is not encrypted, the "none" security.
+    enc: Option<AeadEncryptor<VMessAEADSequence>>,
     enc_size_parser: ShakeSizeParser,
-    dec: AeadDecryptor<VMessAEADSequence>,
+    dec: Option<AeadDecryptor<VMessAEADSequence>>,
     dec_size_parser: ShakeSizeParser,
     tag_len: usize,
+    // Whether chunks are padded, the global padding option.
+    padding: bool,
     read_buf: BytesMut,
     write_buf: BytesMut,
     read_state: ReadState,
@@ -47,15 +55,22 @@ pub struct VMessAuthStream<T> {
 }
 
 impl<T> VMessAuthStream<T> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         s: T,
         sess: ClientSession,
-        enc: AeadEncryptor<VMessAEADSequence>,
+        enc: Option<AeadEncryptor<VMessAEADSequence>>,
         enc_size_parser: ShakeSizeParser,
-        dec: AeadDecryptor<VMessAEADSequence>,
+        dec: Option<AeadDecryptor<VMessAEADSequence>>,
         dec_size_parser: ShakeSizeParser,
         tag_len: usize,
+        padding: bool,
     ) -> Self {
+        let read_state = if sess.aead {
+            ReadState::WaitingAeadResponseLength
+        } else {
+            ReadState::WaitingResponseHeader
+        };
         VMessAuthStream {
             inner: s,
             sess,
@@ -64,12 +79,13 @@ impl<T> VMessAuthStream<T> {
             dec,
             dec_size_parser,
             tag_len,
+            padding,
 
             // never depend on these sizes, reserve when need
             read_buf: BytesMut::with_capacity(0x2 + 0x4000),
             write_buf: BytesMut::with_capacity(0x2 + 0x4000),
 
-            read_state: ReadState::WaitingResponseHeader,
+            read_state,
             write_state: WriteState::WaitingChunk,
             read_pos: 0,
         }
@@ -135,12 +151,45 @@ impl<T: AsyncRead + Unpin> AsyncRead for VMessAuthStream<T> {
                     // ready to read data chunks
                     me.read_state = ReadState::WaitingLength;
                 }
+                ReadState::WaitingAeadResponseLength => {
+                    let me = &mut *self;
+                    ready!(me.poll_read_exact(cx, AEAD_HEADER_LENGTH_LEN))?;
+                    let len = open_aead_response_length(
+                        &me.sess.response_body_key,
+                        &me.sess.response_body_iv,
+                        &me.read_buf[..AEAD_HEADER_LENGTH_LEN],
+                    )
+                    .map_err(|_| crypto_err())?;
+                    me.read_state = ReadState::WaitingAeadResponseHeader(len);
+                }
+                ReadState::WaitingAeadResponseHeader(len) => {
+                    let me = &mut *self;
+                    let size = len + AEAD_HEADER_TAG_LEN;
+                    ready!(me.poll_read_exact(cx, size))?;
+                    let header = open_aead_response_header(
+                        &me.sess.response_body_key,
+                        &me.sess.response_body_iv,
+                        &me.read_buf[..size],
+                    )
+                    .map_err(|_| crypto_err())?;
+
+                    if header.is_empty() || header[0] != me.sess.response_header {
+                        return Poll::Ready(Err(crypto_err()));
+                    }
+
+                    // ready to read data chunks
+                    me.read_state = ReadState::WaitingLength;
+                }
                 ReadState::WaitingLength => {
                     // read and decode payload length
                     let me = &mut *self;
                     let size_bytes = me.dec_size_parser.size_bytes();
                     ready!(me.poll_read_exact(cx, size_bytes))?;
-                    let padding_size = me.dec_size_parser.next_padding_len() as usize;
+                    let padding_size = if me.padding {
+                        me.dec_size_parser.next_padding_len() as usize
+                    } else {
+                        0
+                    };
                     let size = me.dec_size_parser.decode(&me.read_buf[..size_bytes]) as usize;
 
                     // ready to read payload
@@ -152,7 +201,9 @@ impl<T: AsyncRead + Unpin> AsyncRead for VMessAuthStream<T> {
                     ready!(me.poll_read_exact(cx, size))?;
                     let encrypted_size = size - padding_size;
                     let _ = me.read_buf.split_off(encrypted_size); // trim padding
-                    me.dec.decrypt(&mut me.read_buf).map_err(|_| crypto_err())?;
+                    if let Some(dec) = me.dec.as_mut() {
+                        dec.decrypt(&mut me.read_buf).map_err(|_| crypto_err())?;
+                    }
 
                     // ready to read plaintext payload into buf
                     me.read_state = ReadState::PendingData(encrypted_size - me.tag_len);
@@ -186,7 +237,11 @@ impl<T: AsyncWrite + Unpin> AsyncWrite for VMessAuthStream<T> {
             match self.write_state {
                 WriteState::WaitingChunk => {
                     let me = &mut *self;
-                    let padding_size = me.enc_size_parser.next_padding_len() as usize;
+                    let padding_size = if me.padding {
+                        me.enc_size_parser.next_padding_len() as usize
+                    } else {
+                        0
+                    };
                     let max_payload_size = 0x4000 - me.tag_len - padding_size;
                     let consume_len = min(buf.len(), max_payload_size);
                     let payload_len = consume_len + me.tag_len + padding_size;
@@ -202,7 +257,9 @@ impl<T: AsyncWrite + Unpin> AsyncWrite for VMessAuthStream<T> {
                     // seal payload
                     piece2.reserve(consume_len + me.tag_len);
                     piece2.put_slice(&buf[..consume_len]);
-                    me.enc.encrypt(&mut piece2).map_err(|_| crypto_err())?;
+                    if let Some(enc) = me.enc.as_mut() {
+                        enc.encrypt(&mut piece2).map_err(|_| crypto_err())?;
+                    }
 
                     let mut piece3 = piece2.split_off(consume_len + me.tag_len);
 
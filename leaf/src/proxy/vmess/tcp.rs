@@ -8,7 +8,8 @@ use uuid::Uuid;
 use crate::{
     app::dns_client::DnsClient,
     proxy::{
-        stream::SimpleProxyStream, OutboundConnect, ProxyStream, TcpConnector, TcpOutboundHandler,
+        stream::SimpleProxyStream, BufHeadProxyStream, OutboundConnect, ProxyStream, TcpConnector,
+        TcpOptions, TcpOutboundHandler,
     },
     session::Session,
 };
@@ -22,11 +23,20 @@ pub struct Handler {
     pub port: u16,
     pub uuid: String,
     pub security: String,
+    // Sends the header in the legacy format instead of the AEAD one.
+    pub legacy_header: bool,
+    // Defers the request header to be sent along with the first payload.
+    pub reduce_rtt: bool,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl TcpOutboundHandler for Handler {
@@ -59,15 +69,18 @@ impl TcpOutboundHandler for Handler {
             uuid,
         };
         request_header.set_option(REQUEST_OPTION_CHUNK_MASKING);
-        request_header.set_option(REQUEST_OPTION_GLOBAL_PADDING);
 
-        match self.security.to_lowercase().as_str() {
+        let security = self.security.to_lowercase();
+        match security.as_str() {
             "chacha20-poly1305" | "chacha20-ietf-poly1305" => {
                 request_header.security = SECURITY_TYPE_CHACHA20_POLY1305;
             }
             "aes-128-gcm" => {
                 request_header.security = SECURITY_TYPE_AES128_GCM;
             }
+            "none" => {
+                request_header.security = SECURITY_TYPE_NONE;
+            }
             _ => {
                 return Err(io::Error::new(
                     io::ErrorKind::Other,
@@ -77,7 +90,12 @@ impl TcpOutboundHandler for Handler {
         }
 
         let mut header_buf = BytesMut::new();
-        let client_sess = ClientSession::new();
+        // Payloads are neither encrypted nor padded with the none security.
+        let encrypted = request_header.security != SECURITY_TYPE_NONE;
+        if encrypted {
+            request_header.set_option(REQUEST_OPTION_GLOBAL_PADDING);
+        }
+        let client_sess = ClientSession::new(!self.legacy_header);
         request_header
             .encode(&mut header_buf, &client_sess)
             .map_err(|e| {
@@ -89,24 +107,36 @@ impl TcpOutboundHandler for Handler {
 
         let enc_size_parser = ShakeSizeParser::new(&client_sess.request_body_iv);
 
-        let enc = new_encryptor(
-            self.security.as_str(),
-            &client_sess.request_body_key,
-            &client_sess.request_body_iv,
-        )
-        .map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("new encryptor failed: {}", e))
-        })?;
+        let enc = if encrypted {
+            Some(
+                new_encryptor(
+                    &security,
+                    &client_sess.request_body_key,
+                    &client_sess.request_body_iv,
+                )
+                .map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("new encryptor failed: {}", e))
+                })?,
+            )
+        } else {
+            None
+        };
 
         let dec_size_parser = ShakeSizeParser::new(&client_sess.response_body_iv);
-        let dec = new_decryptor(
-            self.security.as_str(),
-            &client_sess.response_body_key,
-            &client_sess.response_body_iv,
-        )
-        .map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("new decryptor failed: {}", e))
-        })?;
+        let dec = if encrypted {
+            Some(
+                new_decryptor(
+                    &security,
+                    &client_sess.response_body_key,
+                    &client_sess.response_body_iv,
+                )
+                .map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("new decryptor failed: {}", e))
+                })?,
+            )
+        } else {
+            None
+        };
 
         let mut stream = if let Some(stream) = stream {
             stream
@@ -120,7 +150,15 @@ impl TcpOutboundHandler for Handler {
             .await?
         };
 
-        stream.write_all(&header_buf).await?; // write request
+        let stream: Box<dyn ProxyStream> = if self.reduce_rtt {
+            Box::new(BufHeadProxyStream {
+                inner: stream,
+                head: Some(header_buf),
+            })
+        } else {
+            stream.write_all(&header_buf).await?; // write request
+            stream
+        };
         let stream = VMessAuthStream::new(
             stream,
             client_sess,
@@ -128,8 +166,94 @@ impl TcpOutboundHandler for Handler {
             enc_size_parser,
             dec,
             dec_size_parser,
-            16, // FIXME
+            if encrypted { 16 } else { 0 },
+            encrypted,
         );
         Ok(Box::new(SimpleProxyStream(stream)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::session::SocksAddr;
+
+    // Reads what arrives until the peer pauses.
+    async fn read_all(stream: &mut TcpStream) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 1024];
+        while let Ok(Ok(n)) =
+            tokio::time::timeout(Duration::from_millis(200), stream.read(&mut buf)).await
+        {
+            if n == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..n]);
+        }
+        data
+    }
+
+    // Returns the bytes the server received before the client writes and
+    // after it writes the payload.
+    async fn handshake(reduce_rtt: bool) -> (Vec<u8>, Vec<u8>) {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Handler {
+            address: addr.ip().to_string(),
+            port: addr.port(),
+            uuid: "a3482e88-686a-4a58-8126-99c9df64b7bf".to_string(),
+            security: "none".to_string(),
+            legacy_header: false,
+            reduce_rtt,
+            bind_addr: "0.0.0.0:0".parse().unwrap(),
+            tcp_options: TcpOptions::default(),
+            dns_client: Arc::new(DnsClient::new(
+                Vec::new(),
+                HashMap::new(),
+                "0.0.0.0:0".parse().unwrap(),
+            )),
+        };
+        let mut sess = Session::default();
+        sess.destination = SocksAddr::Domain("example.com".to_string(), 80);
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+        let mut stream = handler
+            .handle_tcp(&sess, Some(Box::new(stream)))
+            .await
+            .unwrap();
+
+        let before = read_all(&mut server).await;
+        stream.write_all(b"hello").await.unwrap();
+        stream.flush().await.unwrap();
+        let after = read_all(&mut server).await;
+        (before, after)
+    }
+
+    #[test]
+    fn test_reduce_rtt() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let (before, after) = handshake(false).await;
+            assert!(!before.is_empty());
+            assert!(after.ends_with(b"hello"));
+            let header_len = before.len();
+
+            // The header is held back and sent with the first payload.
+            let (before, after) = handshake(true).await;
+            assert!(before.is_empty());
+            assert!(after.len() > header_len);
+            assert!(after.ends_with(b"hello"));
+        });
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
     app::dns_client::DnsClient,
     proxy::{
         OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf,
-        OutboundTransport, TcpConnector, UdpOutboundHandler, UdpTransportType,
+        OutboundTransport, TcpConnector, TcpOptions, UdpOutboundHandler, UdpTransportType,
     },
     session::{Session, SocksAddr},
 };
@@ -24,11 +24,18 @@ pub struct Handler {
     pub port: u16,
     pub uuid: String,
     pub security: String,
+    // Sends the header in the legacy format instead of the AEAD one.
+    pub legacy_header: bool,
     pub bind_addr: SocketAddr,
+    pub tcp_options: TcpOptions,
     pub dns_client: Arc<DnsClient>,
 }
 
-impl TcpConnector for Handler {}
+impl TcpConnector for Handler {
+    fn tcp_options(&self) -> TcpOptions {
+        self.tcp_options.clone()
+    }
+}
 
 #[async_trait]
 impl UdpOutboundHandler for Handler {
@@ -65,15 +72,18 @@ impl UdpOutboundHandler for Handler {
             uuid,
         };
         request_header.set_option(REQUEST_OPTION_CHUNK_MASKING);
-        request_header.set_option(REQUEST_OPTION_GLOBAL_PADDING);
 
-        match self.security.to_lowercase().as_str() {
+        let security = self.security.to_lowercase();
+        match security.as_str() {
             "chacha20-poly1305" | "chacha20-ietf-poly1305" => {
                 request_header.security = SECURITY_TYPE_CHACHA20_POLY1305;
             }
             "aes-128-gcm" => {
                 request_header.security = SECURITY_TYPE_AES128_GCM;
             }
+            "none" => {
+                request_header.security = SECURITY_TYPE_NONE;
+            }
             _ => {
                 return Err(io::Error::new(
                     io::ErrorKind::Other,
@@ -83,7 +93,12 @@ impl UdpOutboundHandler for Handler {
         }
 
         let mut header_buf = BytesMut::new();
-        let client_sess = ClientSession::new();
+        // Payloads are neither encrypted nor padded with the none security.
+        let encrypted = request_header.security != SECURITY_TYPE_NONE;
+        if encrypted {
+            request_header.set_option(REQUEST_OPTION_GLOBAL_PADDING);
+        }
+        let client_sess = ClientSession::new(!self.legacy_header);
         request_header
             .encode(&mut header_buf, &client_sess)
             .map_err(|e| {
@@ -94,24 +109,36 @@ impl UdpOutboundHandler for Handler {
             })?;
 
         let enc_size_parser = ShakeSizeParser::new(&client_sess.request_body_iv);
-        let enc = new_encryptor(
-            self.security.as_str(),
-            &client_sess.request_body_key,
-            &client_sess.request_body_iv,
-        )
-        .map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("new encryptor failed: {}", e))
-        })?;
+        let enc = if encrypted {
+            Some(
+                new_encryptor(
+                    &security,
+                    &client_sess.request_body_key,
+                    &client_sess.request_body_iv,
+                )
+                .map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("new encryptor failed: {}", e))
+                })?,
+            )
+        } else {
+            None
+        };
 
         let dec_size_parser = ShakeSizeParser::new(&client_sess.response_body_iv);
-        let dec = new_decryptor(
-            self.security.as_str(),
-            &client_sess.response_body_key,
-            &client_sess.response_body_iv,
-        )
-        .map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("new decryptor failed: {}", e))
-        })?;
+        let dec = if encrypted {
+            Some(
+                new_decryptor(
+                    &security,
+                    &client_sess.response_body_key,
+                    &client_sess.response_body_iv,
+                )
+                .map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("new decryptor failed: {}", e))
+                })?,
+            )
+        } else {
+            None
+        };
 
         let mut stream = if let Some(OutboundTransport::Stream(stream)) = transport {
             stream
@@ -133,7 +160,8 @@ impl UdpOutboundHandler for Handler {
             enc_size_parser,
             dec,
             dec_size_parser,
-            16, // FIXME
+            if encrypted { 16 } else { 0 },
+            encrypted,
         );
         Ok(Box::new(Datagram {
             stream,
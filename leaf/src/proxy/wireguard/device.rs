@@ -0,0 +1,529 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::{
+        atomic::{AtomicBool, AtomicU16, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+
+use boringtun::crypto::x25519::{X25519PublicKey, X25519SecretKey};
+use boringtun::noise::{errors::WireGuardError, Tunn, TunnResult};
+use cidr::{Cidr, IpCidr};
+use log::*;
+use smoltcp::{
+    iface::{Interface, InterfaceBuilder},
+    phy::{self, DeviceCapabilities, Medium},
+    socket::{SocketHandle, SocketSet, TcpSocket, TcpSocketBuffer, TcpState},
+    time::Instant as SmolInstant,
+    wire::{IpAddress, IpCidr as SmolIpCidr, IpEndpoint, Ipv4Address},
+};
+use tokio::net::udp::{RecvHalf, SendHalf};
+use tokio::net::UdpSocket;
+use tokio::sync::{broadcast, mpsc, Mutex as TokioMutex, Notify};
+use tokio::time::timeout;
+
+use crate::{app::dns_client::DnsClient, common::sys, session::SocksAddr};
+
+use super::packet;
+use super::stream::TcpStream;
+
+// Interval to drive the handshake, keepalive and key rotation timers.
+const TIMER_INTERVAL: Duration = Duration::from_millis(250);
+
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Closing TCP sockets are dropped if the peer doesn't respond in this period.
+const TCP_IDLE_TIMEOUT: u64 = 300;
+
+const TCP_BUFFER_SIZE: usize = 64 * 1024;
+
+const UDP_CHANNEL_SIZE: usize = 64;
+
+// Local ports for connections in the tunnel are allocated from this port.
+const LOCAL_PORT_BASE: u16 = 10000;
+
+fn to_io(e: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("wireguard: {}", e))
+}
+
+/// Settings of a WireGuard tunnel.
+pub struct Config {
+    pub address: String,
+    pub port: u16,
+    pub private_key: Arc<X25519SecretKey>,
+    pub peer_public_key: Arc<X25519PublicKey>,
+    pub preshared_key: Option<[u8; 32]>,
+    /// The address of this peer in the tunnel.
+    pub local_ip: Ipv4Addr,
+    /// Destinations routed to the peer, all destinations if empty.
+    pub allowed_ips: Vec<IpCidr>,
+    pub mtu: usize,
+    pub keepalive: Option<u16>,
+    pub bind_addr: SocketAddr,
+    /// The network interface the tunnel socket is bound to.
+    pub interface: Option<String>,
+}
+
+/// A WireGuard tunnel shared by the TCP and UDP handlers of an outbound. The
+/// device is brought up on the first connection, which also initiates the
+/// handshake, and brought up again if the previous one has failed.
+pub struct Tunnel {
+    config: Config,
+    dns_client: Arc<DnsClient>,
+    device: TokioMutex<Option<Arc<Device>>>,
+}
+
+impl Tunnel {
+    pub fn new(config: Config, dns_client: Arc<DnsClient>) -> Self {
+        Tunnel {
+            config,
+            dns_client,
+            device: TokioMutex::new(None),
+        }
+    }
+
+    pub async fn device(&self) -> io::Result<Arc<Device>> {
+        let mut device = self.device.lock().await;
+        if let Some(device) = device.as_ref() {
+            if !device.closed.load(Ordering::Relaxed) {
+                return Ok(device.clone());
+            }
+        }
+        let new_device = Device::start(&self.config, &self.dns_client).await?;
+        device.replace(new_device.clone());
+        Ok(new_device)
+    }
+
+    /// Resolves the destination of a session to an address in the tunnel.
+    /// Domains are resolved by the DNS client, only IPv4 is supported in the
+    /// tunnel.
+    pub async fn resolve(&self, addr: &SocksAddr) -> io::Result<SocketAddrV4> {
+        let (ip, port) = match addr {
+            SocksAddr::Ip(addr) => (addr.ip(), addr.port()),
+            SocksAddr::Domain(domain, port) => {
+                let ips = self
+                    .dns_client
+                    .lookup(domain.to_owned())
+                    .await
+                    .map_err(|e| to_io(format!("lookup {} failed: {}", domain, e)))?;
+                let ip = ips
+                    .into_iter()
+                    .find(|ip| ip.is_ipv4())
+                    .ok_or_else(|| to_io(format!("no ipv4 address for {}", domain)))?;
+                (ip, *port)
+            }
+        };
+        let ip = match ip {
+            IpAddr::V4(ip) => ip,
+            IpAddr::V6(_) => return Err(to_io("ipv6 destination not supported")),
+        };
+        if !self.config.allowed_ips.is_empty()
+            && !self
+                .config
+                .allowed_ips
+                .iter()
+                .any(|cidr| cidr.contains(&IpAddr::V4(ip)))
+        {
+            return Err(to_io(format!("{} not in allowed ips", ip)));
+        }
+        Ok(SocketAddrV4::new(ip, port))
+    }
+}
+
+impl Drop for Tunnel {
+    fn drop(&mut self) {
+        // Stops the device tasks and releases the socket when the outbound
+        // is reloaded or removed.
+        if let Ok(mut device) = self.device.try_lock() {
+            if let Some(device) = device.take() {
+                device.close();
+            }
+        }
+    }
+}
+
+/// A smoltcp device exchanging IP packets with the tunnel.
+pub struct VirtualDevice {
+    pub(super) rx: VecDeque<Vec<u8>>,
+    pub(super) tx: VecDeque<Vec<u8>>,
+    mtu: usize,
+}
+
+impl VirtualDevice {
+    pub fn new(mtu: usize) -> Self {
+        VirtualDevice {
+            rx: VecDeque::new(),
+            tx: VecDeque::new(),
+            mtu,
+        }
+    }
+}
+
+pub struct RxToken(Vec<u8>);
+
+impl phy::RxToken for RxToken {
+    fn consume<R, F>(mut self, _timestamp: SmolInstant, f: F) -> smoltcp::Result<R>
+    where
+        F: FnOnce(&mut [u8]) -> smoltcp::Result<R>,
+    {
+        f(&mut self.0)
+    }
+}
+
+pub struct TxToken<'a>(&'a mut VecDeque<Vec<u8>>);
+
+impl<'a> phy::TxToken for TxToken<'a> {
+    fn consume<R, F>(self, _timestamp: SmolInstant, len: usize, f: F) -> smoltcp::Result<R>
+    where
+        F: FnOnce(&mut [u8]) -> smoltcp::Result<R>,
+    {
+        let mut buf = vec![0u8; len];
+        let r = f(&mut buf)?;
+        self.0.push_back(buf);
+        Ok(r)
+    }
+}
+
+impl<'a> phy::Device<'a> for VirtualDevice {
+    type RxToken = RxToken;
+    type TxToken = TxToken<'a>;
+
+    fn receive(&'a mut self) -> Option<(Self::RxToken, Self::TxToken)> {
+        let pkt = self.rx.pop_front()?;
+        Some((RxToken(pkt), TxToken(&mut self.tx)))
+    }
+
+    fn transmit(&'a mut self) -> Option<Self::TxToken> {
+        Some(TxToken(&mut self.tx))
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        let mut caps = DeviceCapabilities::default();
+        caps.medium = Medium::Ip;
+        caps.max_transmission_unit = self.mtu;
+        caps
+    }
+}
+
+pub struct Stack {
+    pub iface: Interface<'static, VirtualDevice>,
+    pub sockets: SocketSet<'static>,
+}
+
+type UdpSessions = HashMap<u16, mpsc::Sender<(Vec<u8>, SocketAddr)>>;
+
+/// A running WireGuard device. TCP connections in the tunnel go through a
+/// userspace stack, UDP datagrams are encapsulated directly.
+pub struct Device {
+    tunn: Box<Tunn>,
+    send_half: TokioMutex<SendHalf>,
+    pub stack: Mutex<Stack>,
+    pub notify: Notify,
+    udp_sessions: Mutex<UdpSessions>,
+    next_port: AtomicU16,
+    local_ip: Ipv4Addr,
+    closed: AtomicBool,
+    // Wakes the device tasks on close, they also stop once the device is
+    // dropped.
+    shutdown: broadcast::Sender<()>,
+}
+
+impl Device {
+    async fn start(config: &Config, dns_client: &DnsClient) -> io::Result<Arc<Self>> {
+        let ips = dns_client
+            .lookup_with_bind(config.address.clone(), &config.bind_addr)
+            .await
+            .map_err(|e| to_io(format!("lookup {} failed: {}", &config.address, e)))?;
+        let ip = ips
+            .into_iter()
+            .find(|ip| ip.is_ipv4() == config.bind_addr.is_ipv4())
+            .ok_or_else(|| to_io(format!("no address for {}", &config.address)))?;
+        let endpoint = SocketAddr::new(ip, config.port);
+
+        let socket = UdpSocket::bind(config.bind_addr).await?;
+        sys::protect_outbound_socket(&socket)?;
+        if let Some(interface) = &config.interface {
+            sys::bind_to_interface(&socket, interface, config.bind_addr.is_ipv6())?;
+        }
+        socket.connect(endpoint).await?;
+        let (recv_half, send_half) = socket.split();
+
+        let tunn = Tunn::new(
+            config.private_key.clone(),
+            config.peer_public_key.clone(),
+            config.preshared_key,
+            config.keepalive,
+            rand::random::<u32>() >> 8,
+            None,
+        )
+        .map_err(to_io)?;
+
+        let iface = InterfaceBuilder::new(VirtualDevice::new(config.mtu))
+            .ip_addrs(vec![SmolIpCidr::new(
+                IpAddress::Ipv4(Ipv4Address::from(config.local_ip)),
+                0,
+            )])
+            .finalize();
+
+        let device = Arc::new(Device {
+            tunn,
+            send_half: TokioMutex::new(send_half),
+            stack: Mutex::new(Stack {
+                iface,
+                sockets: SocketSet::new(vec![]),
+            }),
+            notify: Notify::new(),
+            udp_sessions: Mutex::new(HashMap::new()),
+            next_port: AtomicU16::new(rand::random()),
+            local_ip: config.local_ip,
+            closed: AtomicBool::new(false),
+            shutdown: broadcast::channel(1).0,
+        });
+        debug!("wireguard device up, endpoint {}", endpoint);
+
+        tokio::spawn(Self::recv_loop(
+            Arc::downgrade(&device),
+            recv_half,
+            device.shutdown.subscribe(),
+        ));
+        tokio::spawn(Self::drive_loop(
+            Arc::downgrade(&device),
+            device.shutdown.subscribe(),
+        ));
+        Ok(device)
+    }
+
+    fn alloc_port(&self) -> u16 {
+        LOCAL_PORT_BASE
+            + self.next_port.fetch_add(1, Ordering::Relaxed) % (u16::MAX - LOCAL_PORT_BASE)
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        // Pending UDP sessions get EOF.
+        self.udp_sessions.lock().unwrap().clear();
+        self.notify.notify();
+        let _ = self.shutdown.send(());
+    }
+
+    async fn send_to_peer(&self, data: &[u8]) {
+        if let Err(e) = self.send_half.lock().await.send(data).await {
+            debug!("wireguard send failed: {}", e);
+        }
+    }
+
+    /// Sends an IP packet through the tunnel. The packet is queued if the
+    /// handshake is not completed, a handshake is initiated if there's no
+    /// valid session.
+    pub async fn send_ip(&self, pkt: &[u8]) {
+        let mut out = vec![0u8; pkt.len() + 148];
+        match self.tunn.encapsulate(pkt, &mut out) {
+            TunnResult::WriteToNetwork(data) => self.send_to_peer(data).await,
+            TunnResult::Err(e) => debug!("wireguard encapsulate failed: {:?}", e),
+            _ => (),
+        }
+    }
+
+    fn receive_ip(&self, pkt: &[u8]) {
+        if let Some((src, dst, payload)) = packet::parse_udp(pkt) {
+            let mut sessions = self.udp_sessions.lock().unwrap();
+            if let Some(tx) = sessions.get_mut(&dst.port()) {
+                if tx.try_send((payload.to_vec(), src)).is_err() {
+                    trace!("wireguard udp session {} full, dropped", dst.port());
+                }
+            }
+            return;
+        }
+        self.stack
+            .lock()
+            .unwrap()
+            .iface
+            .device_mut()
+            .rx
+            .push_back(pkt.to_vec());
+        self.notify.notify();
+    }
+
+    // The tasks only hold the device while handling a datagram or a poll, so
+    // they don't keep a dropped device alive.
+    async fn recv_loop(
+        device: Weak<Self>,
+        mut recv_half: RecvHalf,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        let mut buf = vec![0u8; 65536];
+        let mut out = vec![0u8; 65536];
+        loop {
+            let n = tokio::select! {
+                res = recv_half.recv(&mut buf) => match res {
+                    Ok(n) => n,
+                    Err(e) => {
+                        warn!("wireguard receive failed: {}", e);
+                        break;
+                    }
+                },
+                _ = shutdown.recv() => break,
+            };
+            let device = match device.upgrade() {
+                Some(device) => device,
+                None => break,
+            };
+            let mut datagram = &buf[..n];
+            loop {
+                match device.tunn.decapsulate(None, datagram, &mut out) {
+                    TunnResult::WriteToNetwork(data) => {
+                        device.send_to_peer(data).await;
+                        // Flushes packets queued during the handshake.
+                        datagram = &[];
+                    }
+                    TunnResult::WriteToTunnelV4(pkt, _) => {
+                        device.receive_ip(pkt);
+                        break;
+                    }
+                    TunnResult::WriteToTunnelV6(..) => {
+                        trace!("wireguard ipv6 packet dropped");
+                        break;
+                    }
+                    TunnResult::Err(e) => {
+                        debug!("wireguard decapsulate failed: {:?}", e);
+                        break;
+                    }
+                    TunnResult::Done => break,
+                }
+            }
+            if device.closed.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+        if let Some(device) = device.upgrade() {
+            device.close();
+        }
+    }
+
+    async fn update_timers(&self) {
+        let mut out = vec![0u8; 148];
+        match self.tunn.update_timers(&mut out) {
+            TunnResult::WriteToNetwork(data) => self.send_to_peer(data).await,
+            // A new handshake is initiated by the next packet sent.
+            TunnResult::Err(WireGuardError::ConnectionExpired) => {
+                trace!("wireguard session expired")
+            }
+            TunnResult::Err(e) => debug!("wireguard timers failed: {:?}", e),
+            _ => (),
+        }
+    }
+
+    async fn drive_loop(device: Weak<Self>, mut shutdown: broadcast::Receiver<()>) {
+        let mut last_timers = tokio::time::Instant::now();
+        loop {
+            let device = match device.upgrade() {
+                Some(device) if !device.closed.load(Ordering::Relaxed) => device,
+                _ => break,
+            };
+            let (pkts, delay) = {
+                let mut stack = device.stack.lock().unwrap();
+                let Stack { iface, sockets } = &mut *stack;
+                let now = SmolInstant::now();
+                loop {
+                    match iface.poll(sockets, now) {
+                        Ok(_) => break,
+                        Err(e) => {
+                            trace!("wireguard stack poll failed: {}", e);
+                            if iface.device().rx.is_empty() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                sockets.prune();
+                let pkts: Vec<Vec<u8>> = iface.device_mut().tx.drain(..).collect();
+                let delay = iface
+                    .poll_delay(sockets, now)
+                    .map(|d| Duration::from_millis(d.total_millis()))
+                    .unwrap_or(TIMER_INTERVAL)
+                    .min(TIMER_INTERVAL);
+                (pkts, delay)
+            };
+            for pkt in pkts {
+                device.send_ip(&pkt).await;
+            }
+            if last_timers.elapsed() >= TIMER_INTERVAL {
+                device.update_timers().await;
+                last_timers = tokio::time::Instant::now();
+            }
+            if delay > Duration::from_millis(0) {
+                tokio::select! {
+                    _ = timeout(delay, device.notify.notified()) => (),
+                    _ = shutdown.recv() => break,
+                }
+            }
+        }
+        debug!("wireguard device down");
+    }
+
+    /// Connects to `dst` in the tunnel with a TCP connection.
+    pub async fn connect_tcp(self: &Arc<Self>, dst: &SocketAddrV4) -> io::Result<TcpStream> {
+        let handle = {
+            let mut socket = TcpSocket::new(
+                TcpSocketBuffer::new(vec![0; TCP_BUFFER_SIZE]),
+                TcpSocketBuffer::new(vec![0; TCP_BUFFER_SIZE]),
+            );
+            socket.set_timeout(Some(smoltcp::time::Duration::from_secs(TCP_IDLE_TIMEOUT)));
+            let remote = IpEndpoint::new(Ipv4Address::from(*dst.ip()).into(), dst.port());
+            let local = IpEndpoint::new(Ipv4Address::from(self.local_ip).into(), self.alloc_port());
+            socket.connect(remote, local).map_err(to_io)?;
+            self.stack.lock().unwrap().sockets.add(socket)
+        };
+        self.notify.notify();
+        let stream = TcpStream::new(self.clone(), handle);
+        match timeout(TCP_CONNECT_TIMEOUT, self.wait_established(handle)).await {
+            Ok(Ok(())) => Ok(stream),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("wireguard: connect {} timed out", dst),
+            )),
+        }
+    }
+
+    async fn wait_established(&self, handle: SocketHandle) -> io::Result<()> {
+        futures::future::poll_fn(|cx| {
+            let mut stack = self.stack.lock().unwrap();
+            let mut socket = stack.sockets.get::<TcpSocket>(handle);
+            match socket.state() {
+                TcpState::Established => std::task::Poll::Ready(Ok(())),
+                TcpState::Closed | TcpState::TimeWait => {
+                    std::task::Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::ConnectionRefused,
+                        "wireguard: connection refused",
+                    )))
+                }
+                _ => {
+                    socket.register_send_waker(cx.waker());
+                    std::task::Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// Binds a local port in the tunnel for a UDP session, returns the port
+    /// and the receiver of datagrams to it.
+    pub fn bind_udp(&self) -> (SocketAddrV4, mpsc::Receiver<(Vec<u8>, SocketAddr)>) {
+        let (tx, rx) = mpsc::channel(UDP_CHANNEL_SIZE);
+        let mut sessions = self.udp_sessions.lock().unwrap();
+        let mut port = self.alloc_port();
+        while sessions.contains_key(&port) {
+            port = self.alloc_port();
+        }
+        sessions.insert(port, tx);
+        (SocketAddrV4::new(self.local_ip, port), rx)
+    }
+
+    pub fn unbind_udp(&self, port: u16) {
+        self.udp_sessions.lock().unwrap().remove(&port);
+    }
+}
@@ -0,0 +1,273 @@
+mod device;
+mod packet;
+mod stream;
+pub mod tcp;
+pub mod udp;
+
+pub use device::{Config, Device, Tunnel};
+pub use tcp::Handler as TcpHandler;
+pub use udp::Handler as UdpHandler;
+
+pub static NAME: &str = "wireguard";
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        net::{SocketAddr, SocketAddrV4},
+        sync::Arc,
+        time::Duration,
+    };
+
+    use boringtun::crypto::x25519::X25519SecretKey;
+    use boringtun::noise::{Tunn, TunnResult};
+    use smoltcp::{
+        iface::InterfaceBuilder,
+        socket::{SocketSet, TcpSocket, TcpSocketBuffer},
+        time::Instant as SmolInstant,
+        wire::{IpAddress, IpCidr as SmolIpCidr},
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UdpSocket;
+
+    use super::device::VirtualDevice;
+    use super::*;
+    use crate::{
+        app::dns_client::DnsClient,
+        proxy::{TcpOutboundHandler, UdpOutboundHandler},
+        session::{Session, SocksAddr},
+    };
+
+    // Echoes UDP datagrams received in the tunnel back to the sender.
+    async fn run_responder(mut socket: UdpSocket, tunn: Box<Tunn>, local_ip: SocketAddrV4) {
+        let mut buf = vec![0u8; 2048];
+        let mut out = vec![0u8; 2048];
+        loop {
+            let (n, peer) = socket.recv_from(&mut buf).await.unwrap();
+            let mut datagram = &buf[..n];
+            loop {
+                match tunn.decapsulate(Some(peer.ip()), datagram, &mut out) {
+                    TunnResult::WriteToNetwork(data) => {
+                        socket.send_to(data, &peer).await.unwrap();
+                        datagram = &[];
+                    }
+                    TunnResult::WriteToTunnelV4(pkt, _) => {
+                        let (src, dst, payload) = packet::parse_udp(pkt).unwrap();
+                        assert_eq!(dst.ip(), *local_ip.ip());
+                        let src = match src {
+                            SocketAddr::V4(v) => v,
+                            _ => unreachable!(),
+                        };
+                        let dst = SocketAddrV4::new(*local_ip.ip(), dst.port());
+                        let reply = packet::build_udp(&dst, &src, payload);
+                        let mut reply_out = vec![0u8; 2048];
+                        if let TunnResult::WriteToNetwork(data) =
+                            tunn.encapsulate(&reply, &mut reply_out)
+                        {
+                            socket.send_to(data, &peer).await.unwrap();
+                        }
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    // Echoes TCP data received in the tunnel on port 7 through a smoltcp stack.
+    async fn run_tcp_responder(mut socket: UdpSocket, tunn: Box<Tunn>) {
+        let mut iface = InterfaceBuilder::new(VirtualDevice::new(1420))
+            .ip_addrs(vec![SmolIpCidr::new(IpAddress::v4(10, 0, 0, 1), 24)])
+            .finalize();
+        let mut sockets = SocketSet::new(vec![]);
+        let mut listener = TcpSocket::new(
+            TcpSocketBuffer::new(vec![0; 4096]),
+            TcpSocketBuffer::new(vec![0; 4096]),
+        );
+        listener.listen(7).unwrap();
+        let handle = sockets.add(listener);
+        let mut peer = None;
+        let mut buf = vec![0u8; 2048];
+        let mut out = vec![0u8; 2048];
+        loop {
+            let _ = iface.poll(&mut sockets, SmolInstant::now());
+            {
+                let mut socket = sockets.get::<TcpSocket>(handle);
+                if socket.can_recv() {
+                    let data = socket.recv(|b| (b.len(), b.to_vec())).unwrap();
+                    socket.send_slice(&data).unwrap();
+                }
+            }
+            let _ = iface.poll(&mut sockets, SmolInstant::now());
+            let pkts: Vec<Vec<u8>> = iface.device_mut().tx.drain(..).collect();
+            if let Some(peer) = peer {
+                for pkt in pkts {
+                    if let TunnResult::WriteToNetwork(data) = tunn.encapsulate(&pkt, &mut out) {
+                        socket.send_to(data, &peer).await.unwrap();
+                    }
+                }
+            }
+            let (n, from) =
+                match tokio::time::timeout(Duration::from_millis(50), socket.recv_from(&mut buf))
+                    .await
+                {
+                    Ok(res) => res.unwrap(),
+                    Err(_) => continue,
+                };
+            peer = Some(from);
+            let mut datagram = &buf[..n];
+            loop {
+                match tunn.decapsulate(Some(from.ip()), datagram, &mut out) {
+                    TunnResult::WriteToNetwork(data) => {
+                        socket.send_to(data, &from).await.unwrap();
+                        datagram = &[];
+                    }
+                    TunnResult::WriteToTunnelV4(pkt, _) => {
+                        iface.device_mut().rx.push_back(pkt.to_vec());
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    fn new_tunnel(
+        server_addr: SocketAddr,
+        server_key: &X25519SecretKey,
+        client_key: Arc<X25519SecretKey>,
+    ) -> Arc<Tunnel> {
+        let config = Config {
+            address: "127.0.0.1".to_string(),
+            port: server_addr.port(),
+            private_key: client_key,
+            peer_public_key: Arc::new(server_key.public_key()),
+            preshared_key: None,
+            local_ip: "10.0.0.2".parse().unwrap(),
+            allowed_ips: vec!["10.0.0.0/24".parse().unwrap()],
+            mtu: 1420,
+            keepalive: None,
+            bind_addr: "127.0.0.1:0".parse().unwrap(),
+            interface: None,
+        };
+        let dns_client = Arc::new(DnsClient::new(
+            vec!["127.0.0.1:53".parse().unwrap()],
+            HashMap::new(),
+            "0.0.0.0:0".parse().unwrap(),
+        ));
+        Arc::new(Tunnel::new(config, dns_client))
+    }
+
+    #[test]
+    fn test_tcp_round_trip() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let server_key = Arc::new(X25519SecretKey::new());
+            let client_key = Arc::new(X25519SecretKey::new());
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = socket.local_addr().unwrap();
+            let responder = Tunn::new(
+                server_key.clone(),
+                Arc::new(client_key.public_key()),
+                None,
+                None,
+                1,
+                None,
+            )
+            .unwrap();
+            tokio::spawn(run_tcp_responder(socket, responder));
+
+            let handler = TcpHandler {
+                tunnel: new_tunnel(server_addr, &server_key, client_key),
+            };
+            let sess = Session {
+                destination: SocksAddr::Ip("10.0.0.1:7".parse().unwrap()),
+                ..Default::default()
+            };
+            let mut stream =
+                tokio::time::timeout(Duration::from_secs(5), handler.handle_tcp(&sess, None))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            let mut buf = [0u8; 5];
+            for msg in [&b"hello"[..], &b"world"[..]].iter() {
+                stream.write_all(msg).await.unwrap();
+                tokio::time::timeout(Duration::from_secs(5), stream.read_exact(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(&buf[..], *msg);
+            }
+
+            // The device tasks stop once the outbound is dropped.
+            let device = Arc::downgrade(&handler.tunnel.device().await.unwrap());
+            drop(stream);
+            drop(handler);
+            tokio::time::delay_for(Duration::from_millis(500)).await;
+            assert!(device.upgrade().is_none());
+        });
+    }
+
+    #[test]
+    fn test_udp_round_trip() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let server_key = Arc::new(X25519SecretKey::new());
+            let client_key = Arc::new(X25519SecretKey::new());
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = socket.local_addr().unwrap();
+            let responder = Tunn::new(
+                server_key.clone(),
+                Arc::new(client_key.public_key()),
+                None,
+                None,
+                1,
+                None,
+            )
+            .unwrap();
+            tokio::spawn(run_responder(
+                socket,
+                responder,
+                "10.0.0.1:0".parse().unwrap(),
+            ));
+
+            let handler = UdpHandler {
+                tunnel: new_tunnel(server_addr, &server_key, client_key),
+            };
+
+            let destination = SocksAddr::Ip("10.0.0.1:7".parse().unwrap());
+            let sess = Session {
+                destination: destination.clone(),
+                ..Default::default()
+            };
+            let (mut recv, mut send) = handler.handle_udp(&sess, None).await.unwrap().split();
+
+            // The first datagram is queued until the handshake completes.
+            let mut buf = [0u8; 64];
+            for msg in [&b"hello"[..], &b"world"[..]].iter() {
+                send.send_to(msg, &destination).await.unwrap();
+                let (n, from) =
+                    tokio::time::timeout(Duration::from_secs(5), recv.recv_from(&mut buf))
+                        .await
+                        .unwrap()
+                        .unwrap();
+                assert_eq!(&buf[..n], *msg);
+                assert_eq!(from.to_string(), destination.to_string());
+            }
+
+            // Not in the allowed IPs.
+            let outside = SocksAddr::Ip("10.0.1.1:7".parse().unwrap());
+            assert!(send.send_to(b"hello", &outside).await.is_err());
+        });
+    }
+}
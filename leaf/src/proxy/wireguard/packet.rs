@@ -0,0 +1,113 @@
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+const PROTO_UDP: u8 = 17;
+
+fn checksum_add(mut sum: u32, data: &[u8]) -> u32 {
+    for chunk in data.chunks(2) {
+        let word = if chunk.len() == 2 {
+            u16::from_be_bytes([chunk[0], chunk[1]])
+        } else {
+            u16::from_be_bytes([chunk[0], 0])
+        };
+        sum += word as u32;
+    }
+    sum
+}
+
+fn checksum_fold(mut sum: u32) -> u16 {
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// Builds an IPv4 packet carrying a UDP datagram from `src` to `dst`.
+pub fn build_udp(src: &SocketAddrV4, dst: &SocketAddrV4, payload: &[u8]) -> Vec<u8> {
+    let udp_len = 8 + payload.len();
+    let total_len = 20 + udp_len;
+    let mut pkt = Vec::with_capacity(total_len);
+    pkt.extend_from_slice(&[0x45, 0]);
+    pkt.extend_from_slice(&(total_len as u16).to_be_bytes());
+    pkt.extend_from_slice(&rand::random::<u16>().to_be_bytes());
+    pkt.extend_from_slice(&[0, 0, 64, PROTO_UDP, 0, 0]);
+    pkt.extend_from_slice(&src.ip().octets());
+    pkt.extend_from_slice(&dst.ip().octets());
+    let sum = checksum_fold(checksum_add(0, &pkt));
+    pkt[10..12].copy_from_slice(&sum.to_be_bytes());
+
+    pkt.extend_from_slice(&src.port().to_be_bytes());
+    pkt.extend_from_slice(&dst.port().to_be_bytes());
+    pkt.extend_from_slice(&(udp_len as u16).to_be_bytes());
+    pkt.extend_from_slice(&[0, 0]);
+    pkt.extend_from_slice(payload);
+
+    // pseudo header
+    let mut sum = checksum_add(0, &pkt[12..20]);
+    sum += PROTO_UDP as u32 + udp_len as u32;
+    let mut sum = checksum_fold(checksum_add(sum, &pkt[20..]));
+    if sum == 0 {
+        sum = 0xffff;
+    }
+    pkt[26..28].copy_from_slice(&sum.to_be_bytes());
+    pkt
+}
+
+/// Parses an IPv4 packet carrying a UDP datagram, returns the source, the
+/// destination and the payload. Returns `None` for other packets, including
+/// fragments.
+pub fn parse_udp(pkt: &[u8]) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    if pkt.len() < 20 || pkt[0] >> 4 != 4 || pkt[9] != PROTO_UDP {
+        return None;
+    }
+    let header_len = ((pkt[0] & 0x0f) as usize) * 4;
+    let total_len = u16::from_be_bytes([pkt[2], pkt[3]]) as usize;
+    if header_len < 20 || total_len < header_len + 8 || total_len > pkt.len() {
+        return None;
+    }
+    // MF set or non-zero offset
+    if u16::from_be_bytes([pkt[6], pkt[7]]) & 0x3fff != 0 {
+        return None;
+    }
+    let udp = &pkt[header_len..total_len];
+    let udp_len = u16::from_be_bytes([udp[4], udp[5]]) as usize;
+    if udp_len < 8 || udp_len > udp.len() {
+        return None;
+    }
+    let src_ip = Ipv4Addr::new(pkt[12], pkt[13], pkt[14], pkt[15]);
+    let dst_ip = Ipv4Addr::new(pkt[16], pkt[17], pkt[18], pkt[19]);
+    let src_port = u16::from_be_bytes([udp[0], udp[1]]);
+    let dst_port = u16::from_be_bytes([udp[2], udp[3]]);
+    Some((
+        SocketAddr::new(src_ip.into(), src_port),
+        SocketAddr::new(dst_ip.into(), dst_port),
+        &udp[8..udp_len],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_and_parse_udp() {
+        let src: SocketAddrV4 = "10.0.0.2:40000".parse().unwrap();
+        let dst: SocketAddrV4 = "1.1.1.1:53".parse().unwrap();
+        let pkt = build_udp(&src, &dst, b"hello");
+        assert_eq!(pkt.len(), 33);
+        assert_eq!(checksum_fold(checksum_add(0, &pkt[..20])), 0);
+
+        let mut sum = checksum_add(0, &pkt[12..20]);
+        sum += PROTO_UDP as u32 + 13;
+        assert_eq!(checksum_fold(checksum_add(sum, &pkt[20..])), 0);
+
+        let (parsed_src, parsed_dst, payload) = parse_udp(&pkt).unwrap();
+        assert_eq!(parsed_src, SocketAddr::V4(src));
+        assert_eq!(parsed_dst, SocketAddr::V4(dst));
+        assert_eq!(payload, b"hello");
+
+        // TCP
+        let mut tcp = pkt.clone();
+        tcp[9] = 6;
+        assert!(parse_udp(&tcp).is_none());
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use smoltcp::socket::{SocketHandle, TcpSocket};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use super::device::Device;
+
+/// A TCP connection in the tunnel.
+pub struct TcpStream {
+    device: Arc<Device>,
+    handle: SocketHandle,
+}
+
+impl TcpStream {
+    pub fn new(device: Arc<Device>, handle: SocketHandle) -> Self {
+        TcpStream { device, handle }
+    }
+}
+
+impl AsyncRead for TcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut stack = self.device.stack.lock().unwrap();
+        let mut socket = stack.sockets.get::<TcpSocket>(self.handle);
+        if socket.can_recv() {
+            let n = socket
+                .recv_slice(buf)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            drop(socket);
+            drop(stack);
+            // The window may have been reopened.
+            self.device.notify.notify();
+            return Poll::Ready(Ok(n));
+        }
+        if !socket.may_recv() {
+            return Poll::Ready(Ok(0));
+        }
+        socket.register_recv_waker(cx.waker());
+        Poll::Pending
+    }
+}
+
+impl AsyncWrite for TcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut stack = self.device.stack.lock().unwrap();
+        let mut socket = stack.sockets.get::<TcpSocket>(self.handle);
+        if socket.can_send() {
+            let n = socket
+                .send_slice(buf)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            drop(socket);
+            drop(stack);
+            self.device.notify.notify();
+            return Poll::Ready(Ok(n));
+        }
+        if !socket.may_send() {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        socket.register_send_waker(cx.waker());
+        Poll::Pending
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.device
+            .stack
+            .lock()
+            .unwrap()
+            .sockets
+            .get::<TcpSocket>(self.handle)
+            .close();
+        self.device.notify.notify();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for TcpStream {
+    fn drop(&mut self) {
+        if let Ok(mut stack) = self.device.stack.lock() {
+            stack.sockets.get::<TcpSocket>(self.handle).close();
+            // The socket is removed once it's closed.
+            stack.sockets.release(self.handle);
+        }
+        self.device.notify.notify();
+    }
+}
@@ -0,0 +1,36 @@
+use std::{io, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    proxy::{OutboundConnect, ProxyStream, SimpleProxyStream, TcpOutboundHandler},
+    session::Session,
+};
+
+use super::Tunnel;
+
+pub struct Handler {
+    pub tunnel: Arc<Tunnel>,
+}
+
+#[async_trait]
+impl TcpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn tcp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    async fn handle_tcp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _stream: Option<Box<dyn ProxyStream>>,
+    ) -> io::Result<Box<dyn ProxyStream>> {
+        let dst = self.tunnel.resolve(&sess.destination).await?;
+        let device = self.tunnel.device().await?;
+        let stream = device.connect_tcp(&dst).await?;
+        Ok(Box::new(SimpleProxyStream(stream)))
+    }
+}
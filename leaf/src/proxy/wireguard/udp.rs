@@ -0,0 +1,151 @@
+use std::{
+    cmp::min,
+    io,
+    net::{SocketAddr, SocketAddrV4},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use log::*;
+use tokio::sync::mpsc;
+
+use crate::{
+    proxy::{
+        OutboundConnect, OutboundDatagram, OutboundDatagramRecvHalf, OutboundDatagramSendHalf,
+        OutboundTransport, UdpOutboundHandler, UdpTransportType,
+    },
+    session::{Session, SocksAddr},
+};
+
+use super::{packet, Device, Tunnel};
+
+pub struct Handler {
+    pub tunnel: Arc<Tunnel>,
+}
+
+#[async_trait]
+impl UdpOutboundHandler for Handler {
+    fn name(&self) -> &str {
+        super::NAME
+    }
+
+    fn udp_connect_addr(&self) -> Option<OutboundConnect> {
+        None
+    }
+
+    fn udp_transport_type(&self) -> UdpTransportType {
+        UdpTransportType::Packet
+    }
+
+    async fn handle_udp<'a>(
+        &'a self,
+        sess: &'a Session,
+        _transport: Option<OutboundTransport>,
+    ) -> io::Result<Box<dyn OutboundDatagram>> {
+        let device = self.tunnel.device().await?;
+        let (local_addr, rx) = device.bind_udp();
+        // If the initial destination is of domain type, datagrams from the
+        // resolved address are returned as from the domain.
+        let destination = match &sess.destination {
+            SocksAddr::Domain(..) => Some(sess.destination.clone()),
+            _ => None,
+        };
+        Ok(Box::new(Datagram {
+            tunnel: self.tunnel.clone(),
+            device,
+            local_addr,
+            rx,
+            destination,
+        }))
+    }
+}
+
+pub struct Datagram {
+    tunnel: Arc<Tunnel>,
+    device: Arc<Device>,
+    local_addr: SocketAddrV4,
+    rx: mpsc::Receiver<(Vec<u8>, SocketAddr)>,
+    destination: Option<SocksAddr>,
+}
+
+impl OutboundDatagram for Datagram {
+    fn split(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn OutboundDatagramRecvHalf>,
+        Box<dyn OutboundDatagramSendHalf>,
+    ) {
+        (
+            Box::new(DatagramRecvHalf {
+                device: self.device.clone(),
+                port: self.local_addr.port(),
+                rx: self.rx,
+                destination: self.destination,
+            }),
+            Box::new(DatagramSendHalf {
+                tunnel: self.tunnel,
+                device: self.device,
+                local_addr: self.local_addr,
+            }),
+        )
+    }
+}
+
+pub struct DatagramRecvHalf {
+    device: Arc<Device>,
+    port: u16,
+    rx: mpsc::Receiver<(Vec<u8>, SocketAddr)>,
+    destination: Option<SocksAddr>,
+}
+
+#[async_trait]
+impl OutboundDatagramRecvHalf for DatagramRecvHalf {
+    async fn recv_from(&mut self, buf: &mut [u8]) -> io::Result<(usize, SocksAddr)> {
+        let (payload, src) =
+            self.rx.recv().await.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::BrokenPipe, "wireguard: device closed")
+            })?;
+        let to_write = min(payload.len(), buf.len());
+        if to_write < payload.len() {
+            warn!(
+                "trucated udp payload, buf size too small: {} < {}",
+                buf.len(),
+                payload.len()
+            );
+        }
+        buf[..to_write].copy_from_slice(&payload[..to_write]);
+        if let Some(destination) = self.destination.as_ref() {
+            Ok((to_write, destination.clone()))
+        } else {
+            Ok((to_write, SocksAddr::Ip(src)))
+        }
+    }
+}
+
+impl Drop for DatagramRecvHalf {
+    fn drop(&mut self) {
+        self.device.unbind_udp(self.port);
+    }
+}
+
+pub struct DatagramSendHalf {
+    tunnel: Arc<Tunnel>,
+    device: Arc<Device>,
+    local_addr: SocketAddrV4,
+}
+
+#[async_trait]
+impl OutboundDatagramSendHalf for DatagramSendHalf {
+    async fn send_to(&mut self, buf: &[u8], dst_addr: &SocksAddr) -> io::Result<usize> {
+        if buf.len() > 65507 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "wireguard: datagram too large",
+            ));
+        }
+        let dst = self.tunnel.resolve(dst_addr).await?;
+        let pkt = packet::build_udp(&self.local_addr, &dst, buf);
+        self.device.send_ip(&pkt).await;
+        Ok(buf.len())
+    }
+}
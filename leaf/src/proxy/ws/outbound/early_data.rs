@@ -0,0 +1,294 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use futures::task::{Context, Poll, Waker};
+use futures::{ready, Future};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::proxy::ProxyStream;
+
+use super::tcp::WsStream;
+
+// The handshake, given the early data to send with it.
+pub type Connect =
+    Box<dyn FnOnce(Option<Vec<u8>>) -> BoxFuture<'static, io::Result<WsStream>> + Send + Sync>;
+
+enum State {
+    // Waiting for the first write to send along with the handshake.
+    Pending(Option<Connect>),
+    // The future is only polled through `Mutex::get_mut`, the mutex is there
+    // to keep the stream `Sync`.
+    Connecting(Mutex<BoxFuture<'static, io::Result<WsStream>>>),
+    Connected(WsStream),
+}
+
+/// A WebSocket stream delaying the handshake until the first write, which is
+/// sent in the upgrade request if it fits in the early data limit.
+pub struct EarlyDataStream {
+    state: State,
+    max_early_data: usize,
+    // Tasks waiting for the handshake, as reads and writes may be polled by
+    // different tasks.
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+impl ProxyStream for EarlyDataStream {}
+
+impl EarlyDataStream {
+    pub fn new(connect: Connect, max_early_data: usize) -> Self {
+        EarlyDataStream {
+            state: State::Pending(Some(connect)),
+            max_early_data,
+            read_waker: None,
+            write_waker: None,
+        }
+    }
+
+    fn start(&mut self, early_data: Option<Vec<u8>>) {
+        if let State::Pending(connect) = &mut self.state {
+            let connect = connect.take().expect("handshake started twice");
+            self.state = State::Connecting(Mutex::new(connect(early_data)));
+            if let Some(waker) = self.read_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn poll_connected(&mut self, cx: &mut Context, read: bool) -> Poll<io::Result<&mut WsStream>> {
+        if let State::Pending(_) = self.state {
+            if read {
+                self.read_waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            // Writes not carrying early data go after the handshake.
+            self.start(None);
+        }
+        if let State::Connecting(fut) = &mut self.state {
+            let res = match fut.get_mut().unwrap().as_mut().poll(cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => {
+                    let waker = Some(cx.waker().clone());
+                    if read {
+                        self.read_waker = waker;
+                    } else {
+                        self.write_waker = waker;
+                    }
+                    return Poll::Pending;
+                }
+            };
+            self.state = State::Connected(res?);
+            let waker = if read {
+                self.write_waker.take()
+            } else {
+                self.read_waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+        match &mut self.state {
+            State::Connected(stream) => Poll::Ready(Ok(stream)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl AsyncRead for EarlyDataStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let stream = ready!(self.poll_connected(cx, true))?;
+        Pin::new(stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for EarlyDataStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if let State::Pending(_) = self.state {
+            if !buf.is_empty() && buf.len() <= self.max_early_data {
+                self.start(Some(buf.to_vec()));
+                return Poll::Ready(Ok(buf.len()));
+            }
+        }
+        let stream = ready!(self.poll_connected(cx, false))?;
+        Pin::new(stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        if let State::Pending(_) = self.state {
+            return Poll::Ready(Ok(()));
+        }
+        let stream = ready!(self.poll_connected(cx, false))?;
+        Pin::new(stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        if let State::Pending(_) = self.state {
+            return Poll::Ready(Ok(()));
+        }
+        let stream = ready!(self.poll_connected(cx, false))?;
+        Pin::new(stream).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use futures::{Future, SinkExt, StreamExt};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::WebSocketStream;
+    use tungstenite::handshake::server::{Request, Response};
+    use tungstenite::Message;
+
+    use super::super::tcp::{Handler, DEFAULT_EARLY_DATA_HEADER_NAME};
+    use crate::app::dns_client::DnsClient;
+    use crate::proxy::{ProxyStream, TcpOutboundHandler};
+    use crate::session::{Session, SocksAddr};
+
+    // Accepts a WebSocket connection, returns the early data header of the
+    // upgrade request, which is echoed back as the servers do.
+    async fn accept(listener: &mut TcpListener) -> (Option<String>, WebSocketStream<TcpStream>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut early_data = None;
+        let callback = |req: &Request, mut res: Response| {
+            if let Some(v) = req.headers().get(DEFAULT_EARLY_DATA_HEADER_NAME) {
+                early_data = Some(v.to_str().unwrap().to_string());
+                res.headers_mut()
+                    .insert(DEFAULT_EARLY_DATA_HEADER_NAME, v.clone());
+            }
+            Ok(res)
+        };
+        let ws = tokio_tungstenite::accept_hdr_async(stream, callback)
+            .await
+            .unwrap();
+        (early_data, ws)
+    }
+
+    // Returns a stream to the listener through the ws outbound.
+    async fn connect(listener: &TcpListener, max_early_data: usize) -> Box<dyn ProxyStream> {
+        let addr = listener.local_addr().unwrap();
+        let handler = Handler {
+            path: "/ws".to_string(),
+            headers: HashMap::new(),
+            max_early_data,
+            early_data_header_name: DEFAULT_EARLY_DATA_HEADER_NAME.to_string(),
+            dns_client: Arc::new(DnsClient::new(
+                Vec::new(),
+                HashMap::new(),
+                "0.0.0.0:0".parse().unwrap(),
+            )),
+        };
+        let mut sess = Session::default();
+        sess.destination = SocksAddr::Ip(addr);
+        let stream = TcpStream::connect(addr).await.unwrap();
+        handler
+            .handle_tcp(&sess, Some(Box::new(stream)))
+            .await
+            .unwrap()
+    }
+
+    fn decode(early_data: &str) -> Vec<u8> {
+        base64::decode_config(early_data, base64::URL_SAFE_NO_PAD).unwrap()
+    }
+
+    fn run<F: Future>(f: F) -> F::Output {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(f)
+    }
+
+    #[test]
+    fn test_early_data() {
+        run(async {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let mut stream = connect(&listener, 64).await;
+            // Completes without the handshake.
+            stream.write_all(b"hello").await.unwrap();
+            let server = async {
+                let (early_data, mut ws) = accept(&mut listener).await;
+                assert_eq!(decode(&early_data.unwrap()), b"hello");
+                ws.send(Message::Binary(b"world".to_vec())).await.unwrap();
+                ws
+            };
+            let client = async {
+                let mut buf = [0u8; 16];
+                let n = stream.read(&mut buf).await.unwrap();
+                assert_eq!(&buf[..n], b"world");
+            };
+            futures::future::join(server, client).await;
+        });
+    }
+
+    #[test]
+    fn test_early_data_too_large() {
+        run(async {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let mut stream = connect(&listener, 4).await;
+            let server = async {
+                let (early_data, mut ws) = accept(&mut listener).await;
+                assert!(early_data.is_none());
+                match ws.next().await.unwrap().unwrap() {
+                    Message::Binary(data) => assert_eq!(data, b"hello"),
+                    msg => panic!("unexpected message {:?}", msg),
+                }
+            };
+            let client = async {
+                // Sent in a data frame after the handshake.
+                stream.write_all(b"hello").await.unwrap();
+                stream.flush().await.unwrap();
+            };
+            futures::future::join(server, client).await;
+        });
+    }
+
+    #[test]
+    fn test_read_before_write() {
+        run(async {
+            let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let stream = connect(&listener, 64).await;
+            let (mut r, mut w) = tokio::io::split(stream);
+            let server = async {
+                let (early_data, mut ws) = accept(&mut listener).await;
+                // The handshake is started by the write, not the read.
+                assert_eq!(decode(&early_data.unwrap()), b"hello");
+                ws.send(Message::Binary(b"world".to_vec())).await.unwrap();
+                ws
+            };
+            let client = async {
+                let read = async {
+                    let mut buf = [0u8; 16];
+                    let n = r.read(&mut buf).await.unwrap();
+                    buf[..n].to_vec()
+                };
+                let write = async {
+                    tokio::time::delay_for(Duration::from_millis(100)).await;
+                    w.write_all(b"hello").await.unwrap();
+                };
+                let (data, _) = futures::future::join(read, write).await;
+                assert_eq!(data, b"world");
+            };
+            tokio::time::timeout(
+                Duration::from_secs(5),
+                futures::future::join(server, client),
+            )
+            .await
+            .unwrap();
+        });
+    }
+}
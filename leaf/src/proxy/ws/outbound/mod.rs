@@ -1,3 +1,4 @@
+mod early_data;
 pub mod tcp;
 
 pub use tcp::Handler as TcpHandler;
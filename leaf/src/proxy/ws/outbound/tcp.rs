@@ -3,8 +3,8 @@ use std::io;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use futures::TryFutureExt;
-use tokio_tungstenite::client_async_with_config;
+use futures::{future::BoxFuture, TryFutureExt};
+use tokio_tungstenite::{client_async_with_config, WebSocketStream};
 use tungstenite::protocol::WebSocketConfig;
 use url::Url;
 
@@ -14,14 +14,39 @@ use crate::{
     session::Session,
 };
 
+use super::early_data::EarlyDataStream;
 use super::stream;
 
+pub type WsStream = stream::WebSocketToStream<WebSocketStream<Box<dyn ProxyStream>>>;
+
+/// Default header to carry early data in, as other implementations do.
+pub const DEFAULT_EARLY_DATA_HEADER_NAME: &str = "Sec-WebSocket-Protocol";
+
 pub struct Handler {
     pub path: String,
     pub headers: HashMap<String, String>,
+    // Max length of the first write sent in the upgrade request, base64
+    // encoded in the early data header, 0 disables early data.
+    pub max_early_data: usize,
+    pub early_data_header_name: String,
     pub dns_client: Arc<DnsClient>,
 }
 
+impl Handler {
+    /// Checks the path and the headers make up a valid upgrade request.
+    pub fn check(&self) -> anyhow::Result<()> {
+        Url::parse("ws://localhost")?.join(&self.path)?;
+        for (k, v) in self.headers.iter() {
+            http::header::HeaderName::from_bytes(k.as_bytes())?;
+            http::header::HeaderValue::from_str(v)?;
+        }
+        if self.max_early_data > 0 {
+            http::header::HeaderName::from_bytes(self.early_data_header_name.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
 struct Request<'a> {
     pub uri: &'a str,
     pub headers: &'a HashMap<String, String>,
@@ -33,7 +58,8 @@ impl<'a> tungstenite::client::IntoClientRequest for Request<'a> {
     ) -> tungstenite::error::Result<tungstenite::handshake::client::Request> {
         let mut builder = http::Request::builder().method("GET").uri(self.uri);
         for (k, v) in self.headers.iter() {
-            if k != "Host" {
+            // Host is taken from the URI.
+            if !k.eq_ignore_ascii_case("Host") {
                 builder = builder.header(k, v);
             }
         }
@@ -41,6 +67,17 @@ impl<'a> tungstenite::client::IntoClientRequest for Request<'a> {
     }
 }
 
+/// Returns the HTTP Host of the upgrade request, the `Host` header if set,
+/// otherwise the destination host. It's independent of the TLS server name
+/// of the underlying stream, which allows domain fronting.
+pub fn request_host(headers: &HashMap<String, String>, sess: &Session) -> String {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("Host"))
+        .map(|(_, v)| v.to_owned())
+        .unwrap_or_else(|| sess.destination.host())
+}
+
 #[async_trait]
 impl TcpOutboundHandler for Handler {
     fn name(&self) -> &str {
@@ -57,34 +94,82 @@ impl TcpOutboundHandler for Handler {
         stream: Option<Box<dyn ProxyStream>>,
     ) -> io::Result<Box<dyn ProxyStream>> {
         if let Some(stream) = stream {
-            let host = if let Some(host) = self.headers.get("Host") {
-                host.to_owned()
-            } else {
-                sess.destination.host()
-            };
+            let host = request_host(&self.headers, sess);
             let mut url = Url::parse(&format!("ws://{}", host)).unwrap();
             url = url.join(self.path.as_str()).unwrap();
-            let req = Request {
-                uri: &url.to_string(),
-                headers: &self.headers,
-            };
-            let ws_config = WebSocketConfig {
-                max_send_queue: Some(1),
-                max_message_size: Some(64 << 20),
-                max_frame_size: Some(16 << 20),
-            };
-            let (socket, _) = client_async_with_config(req, stream, Some(ws_config))
-                .map_err(|e| {
-                    io::Error::new(
-                        io::ErrorKind::Other,
-                        format!("connect ws {} failed: {}", &url, e),
-                    )
-                })
-                .await?;
-            let ws_stream = stream::WebSocketToStream::new(socket);
-            Ok(Box::new(SimpleProxyStream(ws_stream)))
+            if self.max_early_data == 0 {
+                let ws_stream = connect(url, self.headers.clone(), stream).await?;
+                return Ok(Box::new(SimpleProxyStream(ws_stream)));
+            }
+            let headers = self.headers.clone();
+            let header_name = self.early_data_header_name.clone();
+            let handshake = Box::new(move |early_data: Option<Vec<u8>>| -> BoxFuture<_> {
+                let mut headers = headers;
+                if let Some(early_data) = early_data {
+                    headers.insert(
+                        header_name,
+                        base64::encode_config(&early_data, base64::URL_SAFE_NO_PAD),
+                    );
+                }
+                Box::pin(connect(url, headers, stream))
+            });
+            Ok(Box::new(EarlyDataStream::new(
+                handshake,
+                self.max_early_data,
+            )))
         } else {
             Err(io::Error::new(io::ErrorKind::Other, "invalid input"))
         }
     }
 }
+
+async fn connect(
+    url: Url,
+    headers: HashMap<String, String>,
+    stream: Box<dyn ProxyStream>,
+) -> io::Result<WsStream> {
+    let req = Request {
+        uri: &url.to_string(),
+        headers: &headers,
+    };
+    let ws_config = WebSocketConfig {
+        max_send_queue: Some(1),
+        max_message_size: Some(64 << 20),
+        max_frame_size: Some(16 << 20),
+    };
+    let (socket, _) = client_async_with_config(req, stream, Some(ws_config))
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("connect ws {} failed: {}", &url, e),
+            )
+        })
+        .await?;
+    Ok(stream::WebSocketToStream::new(socket))
+}
+
+#[cfg(all(test, feature = "outbound-tls"))]
+mod tests {
+    use super::*;
+    use crate::proxy::tls;
+
+    #[test]
+    fn test_host_independent_of_sni() {
+        let mut sess = Session::default();
+        sess.destination = ("origin.example.com", 443).into();
+
+        let mut headers = HashMap::new();
+        assert_eq!(request_host(&headers, &sess), "origin.example.com");
+        headers.insert("host".to_string(), "backend.example.com".to_string());
+        assert_eq!(request_host(&headers, &sess), "backend.example.com");
+
+        let tls = tls::TcpHandler::new(
+            "front.example.com".to_string(),
+            tls::ClientOptions::default(),
+            false,
+        );
+        assert_eq!(tls.server_name(&sess), "front.example.com");
+        let tls = tls::TcpHandler::new(String::new(), tls::ClientOptions::default(), false);
+        assert_eq!(tls.server_name(&sess), "origin.example.com");
+    }
+}
@@ -10,8 +10,28 @@ use bytes::BufMut;
 use log::*;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
+use crate::app::dns_client::DnsStrategy;
+
+/// The transport protocol of a session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Network::Tcp => write!(f, "tcp"),
+            Network::Udp => write!(f, "udp"),
+        }
+    }
+}
+
 // TODO use references
 pub struct Session {
+    /// The transport protocol of the inbound connection.
+    pub network: Network,
     /// The socket address of the remote peer of an inbound connection.
     pub source: SocketAddr,
     /// The socket address of the local socket of an inbound connection.
@@ -20,15 +40,19 @@ pub struct Session {
     pub destination: SocksAddr,
     /// The tag of the inbound handler this session initiated.
     pub inbound_tag: String,
+    /// The DNS strategy of the matched rule, `None` for the global one.
+    pub dns_strategy: Option<DnsStrategy>,
 }
 
 impl Clone for Session {
     fn clone(&self) -> Self {
         Session {
+            network: self.network,
             source: self.source,
             local_addr: self.local_addr,
             destination: self.destination.clone(),
             inbound_tag: self.inbound_tag.clone(),
+            dns_strategy: self.dns_strategy,
         }
     }
 }
@@ -36,10 +60,12 @@ impl Clone for Session {
 impl Default for Session {
     fn default() -> Self {
         Session {
+            network: Network::Tcp,
             source: "0.0.0.0:0".parse().unwrap(),
             local_addr: "0.0.0.0:0".parse().unwrap(),
             destination: SocksAddr::empty_ipv4(),
             inbound_tag: "".to_string(),
+            dns_strategy: None,
         }
     }
 }
@@ -1,31 +1,78 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use futures::future::{self, Either};
+use log::*;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::runtime;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
 
 use crate::{
     app::{
-        dispatcher::Dispatcher, inbound::manager::InboundManager, nat_manager::NatManager,
-        outbound::manager::OutboundManager, router::Router,
+        dispatcher::Dispatcher,
+        inbound::manager::InboundManager,
+        nat_manager::NatManager,
+        outbound::manager::OutboundManager,
+        rate_limit::RateLimiter,
+        rewriter::Rewriter,
+        router::Router,
+        runtime::{self, RuntimeId, RuntimeManager},
     },
     config::Config,
     session::{Session, SocksAddr},
     Runner,
 };
 
-pub fn create_runners(config: Config) -> Result<Vec<Runner>> {
-    let outbound_manager = OutboundManager::new(&config.outbounds, config.dns.as_ref().unwrap());
-    let router = Router::new(&config.routing_rules);
-    let dispatcher = Arc::new(Dispatcher::new(outbound_manager, router));
-    let nat_manager = Arc::new(NatManager::new(dispatcher.clone()));
-    let inbound_manager = InboundManager::new(&config.inbounds, dispatcher, nat_manager);
+// How often the remaining connections are checked while draining.
+const DRAIN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+type Components = (
+    Vec<Runner>,
+    Arc<RwLock<OutboundManager>>,
+    Arc<Dispatcher>,
+    Arc<NatManager>,
+);
+
+fn create_runners_with_components(config: Config) -> Result<Components> {
+    let outbound_manager = Arc::new(RwLock::new(OutboundManager::try_new(
+        &config.outbounds,
+        config.dns.as_ref().unwrap(),
+    )?));
+    let mut router = Router::new(&config.routing_rules);
+    if let Some(router_settings) = config.router.as_ref() {
+        router.set_dry_run(router_settings.dry_run);
+        let default = &router_settings.default_outbound;
+        if !default.is_empty()
+            && !outbound_manager
+                .write()
+                .unwrap()
+                .set_default_handler(default)
+        {
+            return Err(anyhow!("default outbound [{}] not found", default));
+        }
+    }
+    let rewriter = Rewriter::new(&config.inbounds);
+    let rate_limiter = RateLimiter::new(&config.inbounds, &config.outbounds);
+    let mut dispatcher = Dispatcher::new(outbound_manager.clone(), router, rewriter, rate_limiter);
+    if config.link_buffer_size > 0 {
+        dispatcher.set_link_buffer_size(config.link_buffer_size as usize * 1024);
+    }
+    let dispatcher = Arc::new(dispatcher);
+    let nat_manager = Arc::new(NatManager::new(dispatcher.clone(), &config));
+    let inbound_manager =
+        InboundManager::new(&config.inbounds, dispatcher.clone(), nat_manager.clone());
     let runners = inbound_manager.get_runners();
+    Ok((runners, outbound_manager, dispatcher, nat_manager))
+}
+
+pub fn create_runners(config: Config) -> Result<Vec<Runner>> {
+    let (runners, _, _, _) = create_runners_with_components(config)?;
     Ok(runners)
 }
 
 pub fn run_with_config(config: Config) -> Result<()> {
-    let mut rt = runtime::Builder::new()
+    let mut rt = tokio::runtime::Builder::new()
         .basic_scheduler()
         .enable_all()
         .build()
@@ -35,6 +82,75 @@ pub fn run_with_config(config: Config) -> Result<()> {
     Ok(())
 }
 
+/// Runs the config with a runtime registered under the id, so it can be
+/// operated by the id while running, blocks until the runners complete or
+/// the runtime is shut down.
+pub fn run_with_id(rt_id: RuntimeId, config: Config) -> Result<()> {
+    let mut rt = tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .unwrap();
+    let control_outbound = config.control_outbound.clone();
+    let (runners, outbound_manager, dispatcher, nat_manager) =
+        create_runners_with_components(config)?;
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    runtime::add(
+        rt_id,
+        RuntimeManager::new(
+            rt.handle().clone(),
+            outbound_manager,
+            dispatcher.clone(),
+            nat_manager.clone(),
+            control_outbound,
+            shutdown_tx,
+        ),
+    )?;
+    rt.block_on(async move {
+        let runners = Box::pin(futures::future::join_all(runners));
+        match future::select(runners, shutdown_rx).await {
+            Either::Left(_) => (),
+            Either::Right((Ok(grace), runners)) => {
+                // Dropping the runners closes the inbound listeners.
+                drop(runners);
+                if let Some(grace) = grace {
+                    info!("shutting down, draining connections for {:?}", grace);
+                    drain(&dispatcher, &nat_manager, grace).await;
+                }
+            }
+            Either::Right((Err(_), runners)) => {
+                runners.await;
+            }
+        }
+    });
+    runtime::remove(rt_id);
+    // Tasks still running, e.g. connections not drained in time, are
+    // cancelled as the runtime drops.
+    Ok(())
+}
+
+/// Waits for the relayed TCP connections and UDP sessions to end, up to the
+/// timeout, returns whether all of them ended.
+async fn drain(dispatcher: &Dispatcher, nat_manager: &NatManager, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let tcp_connections = dispatcher.num_tcp_connections();
+        let udp_sessions = nat_manager.size().await;
+        if tcp_connections == 0 && udp_sessions == 0 {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            info!(
+                "drain timed out with {} tcp connections and {} udp sessions",
+                tcp_connections, udp_sessions
+            );
+            return false;
+        }
+        tokio::time::delay_for(std::cmp::min(deadline - now, DRAIN_CHECK_INTERVAL)).await;
+    }
+}
+
 pub async fn test_outbound(tag: &str, config: &Config) {
     let outbound_manager = OutboundManager::new(&config.outbounds, config.dns.as_ref().unwrap());
     let handler = if let Some(v) = outbound_manager.get(tag) {
@@ -74,3 +190,69 @@ pub async fn test_outbound(tag: &str, config: &Config) {
         }
     }
 }
+
+#[cfg(all(test, feature = "config-json", feature = "outbound-direct"))]
+mod tests {
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::config::json;
+
+    #[test]
+    fn test_drain_tcp_relay() {
+        let config = json::to_internal(
+            json::from_string(
+                r#"{"dns":{"servers":["127.0.0.1"]},"outbounds":[{"protocol":"direct","tag":"direct"}]}"#
+                    .to_string(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            let (_, _, dispatcher, nat_manager) = create_runners_with_components(config).unwrap();
+
+            // Echoes the request back after a while, the relay is still
+            // active when draining starts.
+            let mut server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let server_addr = server.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (mut stream, _) = server.accept().await.unwrap();
+                let mut buf = [0u8; 5];
+                stream.read_exact(&mut buf).await.unwrap();
+                tokio::time::delay_for(Duration::from_millis(300)).await;
+                stream.write_all(&buf).await.unwrap();
+            });
+
+            let mut inbound = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let mut client = TcpStream::connect(inbound.local_addr().unwrap())
+                .await
+                .unwrap();
+            let (lhs, _) = inbound.accept().await.unwrap();
+            let relay_dispatcher = dispatcher.clone();
+            tokio::spawn(async move {
+                let mut sess = Session::default();
+                sess.destination = SocksAddr::Ip(server_addr);
+                relay_dispatcher.dispatch_tcp(&mut sess, lhs).await;
+            });
+
+            client.write_all(b"hello").await.unwrap();
+            while dispatcher.num_tcp_connections() == 0 {
+                tokio::time::delay_for(Duration::from_millis(10)).await;
+            }
+            let client_task = tokio::spawn(async move {
+                let mut buf = [0u8; 5];
+                client.read_exact(&mut buf).await.unwrap();
+                buf
+            });
+
+            assert!(drain(&dispatcher, &nat_manager, Duration::from_secs(5)).await);
+            assert_eq!(dispatcher.num_tcp_connections(), 0);
+            assert_eq!(&client_task.await.unwrap(), b"hello");
+        });
+    }
+}